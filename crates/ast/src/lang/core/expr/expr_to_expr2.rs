@@ -295,6 +295,11 @@ pub fn expr_to_expr2<'a>(
             )
         }
 
+        Tuple(_) | TupleAccess(_, _) => {
+            // TODO the editor doesn't support tuples yet, so treat them like a runtime error
+            (Expr2::RuntimeError(), Output::default())
+        }
+
         AccessorFunction(field) => (
             Expr2::Accessor {
                 function_var: env.var_store.fresh(),
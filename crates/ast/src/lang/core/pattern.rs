@@ -440,6 +440,11 @@ pub fn to_pattern2<'a>(
             })
         }
 
+        Tuple(_patterns) => {
+            // TODO the editor doesn't support tuples yet
+            unsupported_pattern(env, pattern_type, region)
+        }
+
         RequiredField(_name, _loc_pattern) => {
             unreachable!("should have been handled in RecordDestructure");
         }
@@ -1,5 +1,5 @@
 use bumpalo::Bump;
-use roc_load::{ExecutionMode, LoadConfig, LoadedModule, Threading};
+use roc_load::{ExecutionMode, LoadConfig, LoadedModule, OptLevel, PropertyTesting, Threading};
 use roc_target::TargetInfo;
 use std::path::Path;
 
@@ -11,6 +11,8 @@ pub fn load_module(src_file: &Path, threading: Threading) -> LoadedModule {
         render: roc_reporting::report::RenderTarget::ColorTerminal,
         threading,
         exec_mode: ExecutionMode::Check,
+        property_testing: PropertyTesting::default(),
+        opt_level: OptLevel::Normal,
     };
 
     let arena = Bump::new();
@@ -19,7 +21,7 @@ pub fn load_module(src_file: &Path, threading: Threading) -> LoadedModule {
 
     match loaded {
         Ok(x) => x,
-        Err(roc_load::LoadingProblem::FormattedReport(report)) => {
+        Err(roc_load::LoadingProblem::FormattedReport(report, _)) => {
             panic!(
                 "Failed to load module from src_file: {:?}. Report: {}",
                 src_file, report
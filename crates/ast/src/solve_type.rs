@@ -1371,6 +1371,19 @@ fn adjust_rank_content(
 
                 EmptyTagUnion => Rank::toplevel(),
 
+                EmptyTuple => Rank::toplevel(),
+
+                Tuple(elems, ext_var) => {
+                    let mut rank = adjust_rank(subs, young_mark, visit_mark, group_rank, *ext_var);
+
+                    for index in elems.into_iter() {
+                        let var = subs[index];
+                        rank = rank.max(adjust_rank(subs, young_mark, visit_mark, group_rank, var));
+                    }
+
+                    rank
+                }
+
                 Record(fields, ext_var) => {
                     let mut rank = adjust_rank(subs, young_mark, visit_mark, group_rank, *ext_var);
 
@@ -1562,7 +1575,7 @@ fn instantiate_rigids_help(
                     }
                 }
 
-                EmptyRecord | EmptyTagUnion | Erroneous(_) => {}
+                EmptyRecord | EmptyTuple | EmptyTagUnion | Erroneous(_) => {}
 
                 Record(fields, ext_var) => {
                     for index in fields.iter_variables() {
@@ -1573,6 +1586,15 @@ fn instantiate_rigids_help(
                     instantiate_rigids_help(subs, max_rank, pools, ext_var);
                 }
 
+                Tuple(elems, ext_var) => {
+                    for index in elems.into_iter() {
+                        let var = subs[index];
+                        instantiate_rigids_help(subs, max_rank, pools, var);
+                    }
+
+                    instantiate_rigids_help(subs, max_rank, pools, ext_var);
+                }
+
                 TagUnion(tags, ext_var) => {
                     for (_, index) in tags.iter_all() {
                         let slice = subs[index];
@@ -1742,7 +1764,27 @@ fn deep_copy_var_help(
                     Func(arg_vars, new_closure_var, new_ret_var)
                 }
 
-                same @ EmptyRecord | same @ EmptyTagUnion | same @ Erroneous(_) => same,
+                same @ EmptyRecord
+                | same @ EmptyTuple
+                | same @ EmptyTagUnion
+                | same @ Erroneous(_) => same,
+
+                Tuple(elems, ext_var) => {
+                    let mut new_elem_vars = Vec::with_capacity(elems.len());
+
+                    for index in elems.into_iter() {
+                        let var = subs[index];
+                        let copy_var = deep_copy_var_help(subs, max_rank, pools, var);
+                        new_elem_vars.push(copy_var);
+                    }
+
+                    let elem_vars = VariableSubsSlice::insert_into_subs(subs, new_elem_vars);
+
+                    Tuple(
+                        elem_vars,
+                        deep_copy_var_help(subs, max_rank, pools, ext_var),
+                    )
+                }
 
                 Record(fields, ext_var) => {
                     let record_fields = {
@@ -6,12 +6,13 @@ use roc_build::{
 use roc_builtins::bitcode;
 use roc_collections::VecMap;
 use roc_load::{
-    EntryPoint, ExecutionMode, Expectations, LoadConfig, LoadMonomorphizedError, LoadedModule,
-    LoadingProblem, Threading,
+    EntryPoint, ExecutionMode, Expectations, LoadConfig, LoadResult, LoadedModule, LoadingProblem,
+    ParseCache, PropertyTesting, SolvedCache, Threading,
 };
 use roc_module::symbol::{Interns, ModuleId};
 use roc_mono::ir::OptLevel;
 use roc_reporting::report::RenderTarget;
+use roc_reporting::structured::ErrorFormat;
 use roc_target::TargetInfo;
 use std::time::{Duration, Instant};
 use std::{path::PathBuf, thread::JoinHandle};
@@ -55,6 +56,15 @@ pub enum BuildFileError<'a> {
     },
 }
 
+/// Reports which stop us from loading the app get parsed by tools if they asked for
+/// machine-readable problems, so leave out the colors then.
+fn render_target(error_format: Option<ErrorFormat>) -> RenderTarget {
+    match error_format {
+        Some(_) => RenderTarget::Generic,
+        None => RenderTarget::ColorTerminal,
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn build_file<'a>(
     arena: &'a Bump,
@@ -63,12 +73,16 @@ pub fn build_file<'a>(
     opt_level: OptLevel,
     emit_debug_info: bool,
     emit_timings: bool,
+    run_by_roc: bool,
     link_type: LinkType,
     linking_strategy: LinkingStrategy,
     precompiled: bool,
     threading: Threading,
     wasm_dev_stack_bytes: Option<u32>,
     order: BuildOrdering,
+    error_format: Option<ErrorFormat>,
+    parse_cache: Option<ParseCache<'a>>,
+    solved_cache: Option<SolvedCache>,
 ) -> Result<BuiltFile, BuildFileError<'a>> {
     let compilation_start = Instant::now();
     let target_info = TargetInfo::from(target);
@@ -83,28 +97,29 @@ pub fn build_file<'a>(
 
     let load_config = LoadConfig {
         target_info,
-        // TODO: expose this from CLI?
-        render: RenderTarget::ColorTerminal,
+        render: render_target(error_format),
         threading,
         exec_mode,
+        property_testing: PropertyTesting::default(),
+        opt_level,
     };
-    let load_result = roc_load::load_and_monomorphize(
+    let load_result = roc_load::load_from_path(
         arena,
         app_module_path.clone(),
         subs_by_module,
         load_config,
+        parse_cache,
+        solved_cache,
     );
     let loaded = match load_result {
-        Ok(loaded) => loaded,
-        Err(LoadMonomorphizedError::LoadingProblem(problem)) => {
-            return Err(BuildFileError::LoadingProblem(problem))
-        }
-        Err(LoadMonomorphizedError::ErrorModule(module)) => {
+        Ok(LoadResult::Monomorphized(loaded)) => loaded,
+        Ok(LoadResult::TypeChecked(module)) => {
             return Err(BuildFileError::ErrorModule {
                 module,
                 total_time: compilation_start.elapsed(),
             })
         }
+        Err(problem) => return Err(BuildFileError::LoadingProblem(problem)),
     };
 
     use target_lexicon::Architecture;
@@ -148,6 +163,20 @@ pub fn build_file<'a>(
         unreachable!();
     };
 
+    // Some hosts can't be rebuilt in the form the surgical linker needs; link those the old way.
+    let linking_strategy = if linking_strategy == LinkingStrategy::Surgical
+        && !precompiled
+        && !roc_linker::supported_host(link_type, &host_input_path)
+    {
+        LinkingStrategy::Legacy
+    } else {
+        linking_strategy
+    };
+
+    if linking_strategy == LinkingStrategy::Surgical && link_type == LinkType::Dylib {
+        binary_path.set_extension("so");
+    }
+
     // TODO this should probably be moved before load_and_monomorphize.
     // To do this we will need to preprocess files just for their exported symbols.
     // Also, we should no longer need to do this once we have platforms on
@@ -182,6 +211,7 @@ pub fn build_file<'a>(
     let rebuild_thread = spawn_rebuild_thread(
         opt_level,
         linking_strategy,
+        link_type,
         precompiled,
         host_input_path.clone(),
         preprocessed_host_path.clone(),
@@ -248,7 +278,7 @@ pub fn build_file<'a>(
     // This only needs to be mutable for report_problems. This can't be done
     // inside a nested scope without causing a borrow error!
     let mut loaded = loaded;
-    let problems = program::report_problems_monomorphized(&mut loaded);
+    let problems = program::report_problems_monomorphized(&mut loaded, error_format);
     let expectations = std::mem::take(&mut loaded.expectations);
     let loaded = loaded;
 
@@ -280,6 +310,7 @@ pub fn build_file<'a>(
         app_o_file,
         opt_level,
         emit_debug_info,
+        run_by_roc,
         &preprocessed_host_path,
         wasm_dev_stack_bytes,
     );
@@ -404,6 +435,7 @@ pub fn build_file<'a>(
 fn spawn_rebuild_thread(
     opt_level: OptLevel,
     linking_strategy: LinkingStrategy,
+    link_type: LinkType,
     precompiled: bool,
     host_input_path: PathBuf,
     preprocessed_host_path: PathBuf,
@@ -428,6 +460,7 @@ fn spawn_rebuild_thread(
                         &thread_local_target,
                         host_input_path.as_path(),
                         None,
+                        LinkType::None,
                     );
 
                     preprocess_host_wasm32(host_dest.as_path(), &preprocessed_host_path);
@@ -436,6 +469,7 @@ fn spawn_rebuild_thread(
                     roc_linker::build_and_preprocess_host(
                         opt_level,
                         &thread_local_target,
+                        link_type,
                         host_input_path.as_path(),
                         preprocessed_host_path.as_path(),
                         exported_symbols,
@@ -448,6 +482,7 @@ fn spawn_rebuild_thread(
                         &thread_local_target,
                         host_input_path.as_path(),
                         None,
+                        LinkType::None,
                     );
                 }
             }
@@ -463,12 +498,15 @@ fn spawn_rebuild_thread(
 }
 
 #[allow(clippy::too_many_arguments)]
-pub fn check_file(
-    arena: &Bump,
+pub fn check_file<'a>(
+    arena: &'a Bump,
     roc_file_path: PathBuf,
     emit_timings: bool,
     threading: Threading,
-) -> Result<(program::Problems, Duration), LoadingProblem> {
+    error_format: Option<ErrorFormat>,
+    parse_cache: Option<ParseCache<'a>>,
+    solved_cache: Option<SolvedCache>,
+) -> Result<(program::Problems, Duration), LoadingProblem<'a>> {
     let compilation_start = Instant::now();
 
     // only used for generating errors. We don't do code generation, so hardcoding should be fine
@@ -480,13 +518,23 @@ pub fn check_file(
 
     let load_config = LoadConfig {
         target_info,
-        // TODO: expose this from CLI?
-        render: RenderTarget::ColorTerminal,
+        render: render_target(error_format),
         threading,
         exec_mode: ExecutionMode::Check,
+        property_testing: PropertyTesting::default(),
+        opt_level: OptLevel::Normal,
+    };
+    let mut loaded = match roc_load::load_from_path(
+        arena,
+        roc_file_path,
+        subs_by_module,
+        load_config,
+        parse_cache,
+        solved_cache,
+    )? {
+        LoadResult::TypeChecked(module) => module,
+        LoadResult::Monomorphized(_) => unreachable!(""),
     };
-    let mut loaded =
-        roc_load::load_and_typecheck(arena, roc_file_path, subs_by_module, load_config)?;
 
     let buf = &mut String::with_capacity(1024);
 
@@ -532,7 +580,7 @@ pub fn check_file(
     }
 
     Ok((
-        program::report_problems_typechecked(&mut loaded),
+        program::report_problems_typechecked(&mut loaded, error_format),
         compilation_end,
     ))
 }
@@ -1,18 +1,23 @@
 use std::ffi::OsStr;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 
 use crate::FormatMode;
 use bumpalo::Bump;
-use roc_error_macros::{internal_error, user_error};
+use roc_error_macros::internal_error;
 use roc_fmt::def::fmt_defs;
 use roc_fmt::module::fmt_module;
 use roc_fmt::spaces::RemoveSpaces;
 use roc_fmt::{Ast, Buf};
+use roc_module::symbol::{Interns, ModuleId};
 use roc_parse::{
     module::{self, module_defs},
-    parser::{Parser, SyntaxError},
+    parser::{Parser, SourceError, SyntaxError},
     state::State,
 };
+use roc_region::all::LineInfo;
+use roc_reporting::report::{parse_problem, RenderTarget, RocDocAllocator, DEFAULT_PALETTE};
+use similar::TextDiff;
 
 fn flatten_directories(files: std::vec::Vec<PathBuf>) -> std::vec::Vec<PathBuf> {
     let mut to_flatten = files;
@@ -62,103 +67,230 @@ fn is_roc_file(path: &Path) -> bool {
 pub fn format(files: std::vec::Vec<PathBuf>, mode: FormatMode) -> Result<(), String> {
     let files = flatten_directories(files);
 
+    let mut unformatted_files = Vec::new();
+    let mut unparseable_files = Vec::new();
+
     for file in files {
         let arena = Bump::new();
 
         let src = std::fs::read_to_string(&file).unwrap();
 
-        let ast = arena.alloc(parse_all(&arena, &src).unwrap_or_else(|e| {
-            user_error!("Unexpected parse failure when parsing this formatting:\n\n{:?}\n\nParse error was:\n\n{:?}\n\n", src, e)
-        }));
-        let mut buf = Buf::new_in(&arena);
-        fmt_all(&mut buf, ast);
-
-        let reparsed_ast = arena.alloc(parse_all(&arena, buf.as_str()).unwrap_or_else(|e| {
-            let mut fail_file = file.clone();
-            fail_file.set_extension("roc-format-failed");
-            std::fs::write(&fail_file, buf.as_str()).unwrap();
-            internal_error!(
-                "Formatting bug; formatted code isn't valid\n\n\
-                I wrote the incorrect result to this file for debugging purposes:\n{}\n\n\
-                Parse error was: {:?}\n\n",
-                fail_file.display(),
-                e
-            );
-        }));
-
-        let ast_normalized = ast.remove_spaces(&arena);
-        let reparsed_ast_normalized = reparsed_ast.remove_spaces(&arena);
-
-        // HACK!
-        // We compare the debug format strings of the ASTs, because I'm finding in practice that _somewhere_ deep inside the ast,
-        // the PartialEq implementation is returning `false` even when the Debug-formatted impl is exactly the same.
-        // I don't have the patience to debug this right now, so let's leave it for another day...
-        // TODO: fix PartialEq impl on ast types
-        if format!("{:?}", ast_normalized) != format!("{:?}", reparsed_ast_normalized) {
-            let mut fail_file = file.clone();
-            fail_file.set_extension("roc-format-failed");
-            std::fs::write(&fail_file, buf.as_str()).unwrap();
-
-            let mut before_file = file.clone();
-            before_file.set_extension("roc-format-failed-ast-before");
-            std::fs::write(&before_file, &format!("{:#?}\n", ast)).unwrap();
-
-            let mut after_file = file.clone();
-            after_file.set_extension("roc-format-failed-ast-after");
-            std::fs::write(&after_file, &format!("{:#?}\n", reparsed_ast)).unwrap();
-
-            internal_error!(
-                "Formatting bug; formatting didn't reparse as the same tree\n\n\
-                I wrote the incorrect result to this file for debugging purposes:\n{}\n\n\
-                I wrote the tree before and after formatting to these files for debugging purposes:\n{}\n{}\n\n",
-                fail_file.display(),
-                before_file.display(),
-                after_file.display());
-        }
-
-        // Now verify that the resultant formatting is _stable_ - i.e. that it doesn't change again if re-formatted
-        let mut reformatted_buf = Buf::new_in(&arena);
-        fmt_all(&mut reformatted_buf, reparsed_ast);
-        if buf.as_str() != reformatted_buf.as_str() {
-            let mut unstable_1_file = file.clone();
-            unstable_1_file.set_extension("roc-format-unstable-1");
-            std::fs::write(&unstable_1_file, buf.as_str()).unwrap();
-
-            let mut unstable_2_file = file.clone();
-            unstable_2_file.set_extension("roc-format-unstable-2");
-            std::fs::write(&unstable_2_file, reformatted_buf.as_str()).unwrap();
-
-            internal_error!(
-                "Formatting bug; formatting is not stable. Reformatting the formatted file changed it again.\n\n\
-                I wrote the result of formatting to this file for debugging purposes:\n{}\n\n\
-                I wrote the result of double-formatting here:\n{}\n\n",
-                unstable_1_file.display(),
-                unstable_2_file.display());
-        }
+        let formatted = match format_src(&arena, &file, &src) {
+            Ok(formatted) => formatted,
+            Err(report) => {
+                eprintln!("{}", report);
+                unparseable_files.push(file);
+                continue;
+            }
+        };
 
         match mode {
             FormatMode::CheckOnly => {
-                // If we notice that this file needs to be formatted, return early
-                if buf.as_str() != src {
-                    return Err("One or more files need to be reformatted.".to_string());
+                if formatted != src {
+                    print!("{}", unified_diff(&file, &src, &formatted));
+                    unformatted_files.push(file);
                 }
             }
 
             FormatMode::Format => {
-                // If all the checks above passed, actually write out the new file.
-                std::fs::write(&file, buf.as_str()).unwrap();
+                // If all the checks in format_src passed, actually write out the new file.
+                if formatted != src {
+                    std::fs::write(&file, formatted).unwrap();
+                }
             }
         }
     }
 
+    let mut messages = Vec::new();
+
+    if !unparseable_files.is_empty() {
+        messages.push(file_list(
+            "These files could not be formatted, because they have syntax errors:",
+            &unparseable_files,
+        ));
+    }
+
+    if !unformatted_files.is_empty() {
+        messages.push(file_list(
+            "These files need to be reformatted:",
+            &unformatted_files,
+        ));
+    }
+
+    if messages.is_empty() {
+        Ok(())
+    } else {
+        Err(messages.join("\n\n"))
+    }
+}
+
+/// Formats the code read from stdin. In [FormatMode::Format], prints the formatted code to stdout;
+/// in [FormatMode::CheckOnly], prints a diff instead, if the code isn't formatted already.
+pub fn format_stdin(mode: FormatMode) -> Result<(), String> {
+    let mut src = String::new();
+    std::io::stdin()
+        .read_to_string(&mut src)
+        .map_err(|error| format!("I could not read from stdin: {}", error))?;
+
+    let arena = Bump::new();
+
+    // Formatting bugs get written next to this (nonexistent) file, in the current directory.
+    let file = Path::new("stdin.roc");
+
+    let formatted = format_src(&arena, file, &src)?;
+
+    match mode {
+        FormatMode::CheckOnly => {
+            if formatted != src {
+                print!("{}", unified_diff(file, &src, &formatted));
+
+                return Err("The code from stdin needs to be reformatted.".to_string());
+            }
+        }
+
+        FormatMode::Format => {
+            print!("{}", formatted);
+        }
+    }
+
     Ok(())
 }
 
-fn parse_all<'a>(arena: &'a Bump, src: &'a str) -> Result<Ast<'a>, SyntaxError<'a>> {
+/// Formats `src`, and makes sure the formatted code means the same thing and is stable. If `src`
+/// doesn't parse, returns a report of the syntax error.
+fn format_src(arena: &Bump, file: &Path, src: &str) -> Result<String, String> {
+    let ast =
+        arena.alloc(parse_all(arena, src).map_err(|error| syntax_error_report(file, src, error))?);
+    let mut buf = Buf::new_in(arena);
+    fmt_all(&mut buf, ast);
+
+    let reparsed_ast = arena.alloc(parse_all(arena, buf.as_str()).unwrap_or_else(|e| {
+        let mut fail_file = file.to_path_buf();
+        fail_file.set_extension("roc-format-failed");
+        std::fs::write(&fail_file, buf.as_str()).unwrap();
+        internal_error!(
+            "Formatting bug; formatted code isn't valid\n\n\
+            I wrote the incorrect result to this file for debugging purposes:\n{}\n\n\
+            Parse error was: {:?}\n\n",
+            fail_file.display(),
+            e.problem
+        );
+    }));
+
+    let ast_normalized = ast.remove_spaces(arena);
+    let reparsed_ast_normalized = reparsed_ast.remove_spaces(arena);
+
+    // HACK!
+    // We compare the debug format strings of the ASTs, because I'm finding in practice that _somewhere_ deep inside the ast,
+    // the PartialEq implementation is returning `false` even when the Debug-formatted impl is exactly the same.
+    // I don't have the patience to debug this right now, so let's leave it for another day...
+    // TODO: fix PartialEq impl on ast types
+    if format!("{:?}", ast_normalized) != format!("{:?}", reparsed_ast_normalized) {
+        let mut fail_file = file.to_path_buf();
+        fail_file.set_extension("roc-format-failed");
+        std::fs::write(&fail_file, buf.as_str()).unwrap();
+
+        let mut before_file = file.to_path_buf();
+        before_file.set_extension("roc-format-failed-ast-before");
+        std::fs::write(&before_file, &format!("{:#?}\n", ast)).unwrap();
+
+        let mut after_file = file.to_path_buf();
+        after_file.set_extension("roc-format-failed-ast-after");
+        std::fs::write(&after_file, &format!("{:#?}\n", reparsed_ast)).unwrap();
+
+        internal_error!(
+            "Formatting bug; formatting didn't reparse as the same tree\n\n\
+            I wrote the incorrect result to this file for debugging purposes:\n{}\n\n\
+            I wrote the tree before and after formatting to these files for debugging purposes:\n{}\n{}\n\n",
+            fail_file.display(),
+            before_file.display(),
+            after_file.display());
+    }
+
+    // Now verify that the resultant formatting is _stable_ - i.e. that it doesn't change again if re-formatted
+    let mut reformatted_buf = Buf::new_in(arena);
+    fmt_all(&mut reformatted_buf, reparsed_ast);
+    if buf.as_str() != reformatted_buf.as_str() {
+        let mut unstable_1_file = file.to_path_buf();
+        unstable_1_file.set_extension("roc-format-unstable-1");
+        std::fs::write(&unstable_1_file, buf.as_str()).unwrap();
+
+        let mut unstable_2_file = file.to_path_buf();
+        unstable_2_file.set_extension("roc-format-unstable-2");
+        std::fs::write(&unstable_2_file, reformatted_buf.as_str()).unwrap();
+
+        internal_error!(
+            "Formatting bug; formatting is not stable. Reformatting the formatted file changed it again.\n\n\
+            I wrote the result of formatting to this file for debugging purposes:\n{}\n\n\
+            I wrote the result of double-formatting here:\n{}\n\n",
+            unstable_1_file.display(),
+            unstable_2_file.display());
+    }
+
+    Ok(buf.as_str().to_string())
+}
+
+fn syntax_error_report(file: &Path, src: &str, error: SourceError<'_, SyntaxError<'_>>) -> String {
+    let src_lines: Vec<&str> = src.lines().collect();
+    let lines = LineInfo::new(src);
+    let interns = Interns::default();
+
+    // A syntax error never mentions any symbols, so the home module doesn't matter.
+    let alloc = RocDocAllocator::new(&src_lines, ModuleId::ATTR, &interns);
+
+    let filename = file.to_path_buf();
+    let report = parse_problem(
+        &alloc,
+        &lines,
+        filename.clone(),
+        0,
+        error.into_file_error(filename),
+    );
+
+    let mut buf = String::new();
+    report.render(
+        RenderTarget::ColorTerminal,
+        &mut buf,
+        &alloc,
+        &DEFAULT_PALETTE,
+    );
+
+    buf
+}
+
+/// A diff of the formatting changes, in the format of `diff -u`
+fn unified_diff(file: &Path, src: &str, formatted: &str) -> String {
+    let path = file.display().to_string();
+    let text_diff = TextDiff::from_lines(src, formatted);
+
+    text_diff
+        .unified_diff()
+        .context_radius(3)
+        .header(&path, &path)
+        .to_string()
+}
+
+fn file_list(heading: &str, files: &[PathBuf]) -> String {
+    let mut message = heading.to_string();
+
+    for file in files {
+        message.push_str("\n\n    ");
+        message.push_str(&file.display().to_string());
+    }
+
+    message
+}
+
+fn parse_all<'a>(
+    arena: &'a Bump,
+    src: &'a str,
+) -> Result<Ast<'a>, SourceError<'a, SyntaxError<'a>>> {
     let (module, state) = module::parse_header(arena, State::new(src.as_bytes()))
-        .map_err(|e| SyntaxError::Header(e.problem))?;
+        .map_err(|e| e.map_problem(SyntaxError::Header))?;
 
-    let (_, defs, _) = module_defs().parse(arena, state).map_err(|(_, e, _)| e)?;
+    let (_, defs, _) = module_defs()
+        .parse(arena, state)
+        .map_err(|(_, e, state)| SourceError::new(e, &state))?;
 
     Ok(Ast { module, defs })
 }
@@ -8,12 +8,18 @@ use roc_build::link::{LinkType, LinkingStrategy};
 use roc_collections::VecMap;
 use roc_error_macros::{internal_error, user_error};
 use roc_gen_llvm::llvm::build::LlvmBackendMode;
-use roc_gen_llvm::run_roc::RocCallResult;
-use roc_gen_llvm::run_roc_dylib;
-use roc_load::{ExecutionMode, Expectations, LoadConfig, LoadingProblem, Threading};
+use roc_load::{
+    ExecutionMode, Expectations, LoadConfig, LoadResult, LoadingProblem, ParseCache,
+    PropertyTesting, SolvedCache, Threading,
+};
 use roc_module::symbol::{Interns, ModuleId};
 use roc_mono::ir::OptLevel;
-use roc_repl_expect::run::{expect_mono_module_to_dylib, roc_dev_expect};
+use roc_repl_expect::run::{
+    expect_mono_module_to_dylib, expect_mono_module_to_dylib_dev, roc_dev_dbg, roc_dev_expect,
+    ExpectStatus,
+};
+use roc_reporting::report::RenderTarget;
+use roc_reporting::structured::{self, Diagnostic, ErrorFormat};
 use roc_target::TargetInfo;
 use std::env;
 use std::ffi::{CString, OsStr};
@@ -21,7 +27,7 @@ use std::io;
 use std::os::raw::{c_char, c_int};
 use std::path::{Path, PathBuf};
 use std::process;
-use std::time::Instant;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use target_lexicon::BinaryFormat;
 use target_lexicon::{
     Architecture, Environment, OperatingSystem, Triple, Vendor, X86_32Architecture,
@@ -31,9 +37,12 @@ use tempfile::TempDir;
 
 pub mod build;
 mod format;
-pub use format::format;
+mod test_report;
+pub mod watch;
+pub use format::{format, format_stdin};
 
 use crate::build::{BuildFileError, BuildOrdering};
+use crate::test_report::ReportFormat;
 
 const DEFAULT_ROC_FILENAME: &str = "main.roc";
 
@@ -48,6 +57,8 @@ pub const CMD_VERSION: &str = "version";
 pub const CMD_FORMAT: &str = "format";
 pub const CMD_TEST: &str = "test";
 pub const CMD_GLUE: &str = "glue";
+pub const CMD_LSP: &str = "lsp";
+pub const CMD_EXPLAIN: &str = "explain";
 
 pub const FLAG_DEBUG: &str = "debug";
 pub const FLAG_DEV: &str = "dev";
@@ -61,8 +72,20 @@ pub const FLAG_TIME: &str = "time";
 pub const FLAG_LINKER: &str = "linker";
 pub const FLAG_PRECOMPILED: &str = "precompiled-host";
 pub const FLAG_CHECK: &str = "check";
+pub const FLAG_STDIN: &str = "stdin";
 pub const FLAG_WASM_STACK_SIZE_KB: &str = "wasm-stack-size-kb";
+pub const FLAG_FILTER: &str = "filter";
+pub const FLAG_FORMAT: &str = "format";
+pub const FLAG_NO_COLOR: &str = "no-color";
+pub const FLAG_ERROR_FORMAT: &str = "error-format";
+pub const FLAG_TIMEOUT: &str = "timeout";
+pub const FLAG_SEED: &str = "seed";
+pub const FLAG_ITERATIONS: &str = "iterations";
+pub const FLAG_WATCH: &str = "watch";
+pub const FLAG_NO_CACHE: &str = "no-cache";
+pub const FLAG_BACKEND: &str = "backend";
 pub const ROC_FILE: &str = "ROC_FILE";
+pub const ERROR_CODE: &str = "ERROR_CODE";
 pub const ROC_DIR: &str = "ROC_DIR";
 pub const GLUE_FILE: &str = "GLUE_FILE";
 pub const DIRECTORY_OR_FILES: &str = "DIRECTORY_OR_FILES";
@@ -78,7 +101,7 @@ pub fn build_app<'a>() -> Command<'a> {
 
     let flag_max_threads = Arg::new(FLAG_MAX_THREADS)
         .long(FLAG_MAX_THREADS)
-        .help("Limit the number of threads (and hence cores) used during compilation.")
+        .help("Limit the number of threads (and hence cores) used during compilation. `roc test` also runs at most this many `expect`s at once.")
         .takes_value(true)
         .validator(|s| s.parse::<usize>())
         .required(false);
@@ -103,9 +126,15 @@ pub fn build_app<'a>() -> Command<'a> {
         .help("Prints detailed compilation time information.")
         .required(false);
 
+    let flag_error_format = Arg::new(FLAG_ERROR_FORMAT)
+        .long(FLAG_ERROR_FORMAT)
+        .help("Print compilation problems in a machine-readable format instead, with a severity, code, title, file, highlighted regions and message for each.")
+        .possible_values(["json", "sarif"])
+        .required(false);
+
     let flag_linker = Arg::new(FLAG_LINKER)
         .long(FLAG_LINKER)
-        .help("Sets which linker to use. The surgical linker is enabled by default only when building for wasm32 or x86_64 Linux, because those are the only targets it currently supports. Otherwise the legacy linker is used by default.")
+        .help("Sets which linker to use. The surgical linker is enabled by default only when building for wasm32, or for x86_64 or aarch64 on Linux or macOS, because those are the only targets it currently supports. With --lib, it is only enabled on Linux. Otherwise the legacy linker is used by default.")
         .possible_values(["surgical", "legacy"])
         .required(false);
 
@@ -122,6 +151,16 @@ pub fn build_app<'a>() -> Command<'a> {
         .validator(|s| s.parse::<u32>())
         .required(false);
 
+    let flag_watch = Arg::new(FLAG_WATCH)
+        .long(FLAG_WATCH)
+        .help("Run again whenever a module of the app, or of its platform, changes.")
+        .required(false);
+
+    let flag_no_cache = Arg::new(FLAG_NO_CACHE)
+        .long(FLAG_NO_CACHE)
+        .help("Type check every module from scratch, rather than reusing the types of unchanged modules from earlier runs.")
+        .required(false);
+
     let roc_file_to_run = Arg::new(ROC_FILE)
         .help("The .roc file of an app to run")
         .allow_invalid_utf8(true)
@@ -147,9 +186,11 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_dev.clone())
             .arg(flag_debug.clone())
             .arg(flag_time.clone())
+            .arg(flag_error_format.clone())
             .arg(flag_linker.clone())
             .arg(flag_precompiled.clone())
             .arg(flag_wasm_stack_size_kb.clone())
+            .arg(flag_no_cache.clone())
             .arg(
                 Arg::new(FLAG_TARGET)
                     .long(FLAG_TARGET)
@@ -186,8 +227,65 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_dev.clone())
             .arg(flag_debug.clone())
             .arg(flag_time.clone())
+            .arg(flag_error_format.clone())
             .arg(flag_linker.clone())
             .arg(flag_precompiled.clone())
+            .arg(flag_watch.clone())
+            .arg(flag_no_cache.clone())
+            .arg(
+                Arg::new(FLAG_FILTER)
+                    .long(FLAG_FILTER)
+                    .help("Only run the `expect`s whose module name, or the name of the top-level def they are written under, contains this text.")
+                    .takes_value(true)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_FORMAT)
+                    .long(FLAG_FORMAT)
+                    .help("Report the results in a machine-readable format instead, with a name, region, duration and failure report (if any) for each `expect`.")
+                    .possible_values(["json", "junit"])
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_BACKEND)
+                    .long(FLAG_BACKEND)
+                    .help("Compile the `expect`s with this backend. The dev backend compiles much faster than LLVM, but does not support every language feature yet.")
+                    .possible_values(["llvm", "dev"])
+                    .default_value("llvm")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_NO_COLOR)
+                    .long(FLAG_NO_COLOR)
+                    .help("Print failure reports without ANSI color codes.")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_TIMEOUT)
+                    .long(FLAG_TIMEOUT)
+                    .help("Stop any `expect` which runs for longer than this many seconds, and report it as timed out.")
+                    .takes_value(true)
+                    .validator(|s| s.parse::<u64>())
+                    .default_value("60")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_SEED)
+                    .long(FLAG_SEED)
+                    .help("Generate the inputs of property-based `expect`s (those which take arguments) from this seed, e.g. to reproduce a failure. Defaults to a different seed on every run.")
+                    .takes_value(true)
+                    .validator(|s| s.parse::<u64>())
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_ITERATIONS)
+                    .long(FLAG_ITERATIONS)
+                    .help("Check each property-based `expect` against this many generated inputs. Must be at least 1.")
+                    .takes_value(true)
+                    .validator(|s| s.parse::<std::num::NonZeroU64>())
+                    .default_value("100")
+                    .required(false),
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file for the main module")
@@ -210,6 +308,7 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_time.clone())
             .arg(flag_linker.clone())
             .arg(flag_precompiled.clone())
+            .arg(flag_no_cache.clone())
             .arg(roc_file_to_run.clone())
             .arg(args_for_app.clone())
         )
@@ -223,6 +322,8 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_time.clone())
             .arg(flag_linker.clone())
             .arg(flag_precompiled.clone())
+            .arg(flag_watch.clone())
+            .arg(flag_no_cache.clone())
             .arg(roc_file_to_run.clone())
             .arg(args_for_app.clone())
         )
@@ -237,16 +338,36 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(
                 Arg::new(FLAG_CHECK)
                     .long(FLAG_CHECK)
-                    .help("Checks that specified files are formatted. If formatting is needed, it will return a non-zero exit code.")
+                    .help("Checks that specified files are formatted. If formatting is needed, it prints a diff of the changes and returns a non-zero exit code.")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_STDIN)
+                    .long(FLAG_STDIN)
+                    .help("Format the code read from stdin, and print the result to stdout.")
+                    .conflicts_with(DIRECTORY_OR_FILES)
                     .required(false),
             )
         )
+        .subcommand(Command::new(CMD_LSP)
+            .about("Start a language server that speaks the Language Server Protocol over stdio"))
+        .subcommand(Command::new(CMD_EXPLAIN)
+            .about("Explain an error code, like the E0301 in the header of a TYPE MISMATCH report")
+            .arg(
+                Arg::new(ERROR_CODE)
+                    .help("The error code to explain")
+                    .required(true),
+            )
+        )
         .subcommand(Command::new(CMD_VERSION)
             .about(concatcp!("Print the Roc compiler’s version, which is currently ", VERSION)))
         .subcommand(Command::new(CMD_CHECK)
             .about("Check the code for problems, but doesn’t build or run it")
             .arg(flag_time.clone())
+            .arg(flag_error_format.clone())
             .arg(flag_max_threads.clone())
+            .arg(flag_watch.clone())
+            .arg(flag_no_cache.clone())
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file of an app to check")
@@ -275,7 +396,7 @@ pub fn build_app<'a>() -> Command<'a> {
             )
             .arg(
                 Arg::new(GLUE_FILE)
-                    .help("The filename for the generated glue code. Currently, this must be a .rs file because only Rust glue generation is supported so far.")
+                    .help("The filename for the generated glue code. Its extension determines the language: .rs for Rust, .h for C, or .zig for Zig.")
                     .allow_invalid_utf8(true)
                     .required(true)
             )
@@ -289,6 +410,8 @@ pub fn build_app<'a>() -> Command<'a> {
         .arg(flag_time)
         .arg(flag_linker)
         .arg(flag_precompiled)
+        .arg(flag_watch)
+        .arg(flag_no_cache)
         .arg(roc_file_to_run.required(false))
         .arg(args_for_app);
 
@@ -308,7 +431,16 @@ pub fn build_app<'a>() -> Command<'a> {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// The cache of solved modules, unless the user opted out of it with `--no-cache`.
+pub fn solved_cache(matches: &ArgMatches) -> Option<SolvedCache> {
+    if matches.is_present(FLAG_NO_CACHE) {
+        None
+    } else {
+        SolvedCache::default_dir().map(SolvedCache::new)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BuildConfig {
     BuildOnly,
     BuildAndRun,
@@ -320,11 +452,34 @@ pub enum FormatMode {
     CheckOnly,
 }
 
-const SHM_SIZE: i64 = 1024;
+/// How many bytes of expect failures and dbg values a program can hand us at a time.
+/// Must match SHARED_FILE_LENGTH in the builtins' expect.zig.
+const SHM_SIZE: i64 = 64 * 1024;
+
+/// Tells a program started by `roc run` which shared memory object to write its expect
+/// failures and dbg values to. Must match SHARED_FILE_ENV_VAR in the builtins' expect.zig.
+#[cfg(target_family = "unix")]
+const EXPECT_BUFFER_ENV_VAR: &str = "ROC_EXPECT_BUFFER";
 
 pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
+    if matches.is_present(FLAG_WATCH) {
+        let path = Path::new(matches.value_of_os(ROC_FILE).unwrap());
+
+        watch::watch(path, |arena, parse_cache| {
+            test_once(matches, &triple, arena, Some(parse_cache))
+        })
+    } else {
+        test_once(matches, &triple, &Bump::new(), None)
+    }
+}
+
+fn test_once<'a>(
+    matches: &ArgMatches,
+    triple: &Triple,
+    arena: &'a Bump,
+    parse_cache: Option<ParseCache<'a>>,
+) -> io::Result<i32> {
     let start_time = Instant::now();
-    let arena = Bump::new();
     let filename = matches.value_of_os(ROC_FILE).unwrap();
     let opt_level = match (
         matches.is_present(FLAG_OPTIMIZE),
@@ -368,8 +523,35 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
         process::exit(1);
     }
 
-    let arena = &arena;
-    let target = &triple;
+    let opt_report_format: Option<ReportFormat> = matches.value_of_t(FLAG_FORMAT).ok();
+    let timeout = Duration::from_secs(matches.value_of_t(FLAG_TIMEOUT).unwrap());
+    let opt_filter = matches.value_of(FLAG_FILTER);
+
+    // Unless asked to reproduce an earlier run, generate different inputs every time.
+    let seed = matches.value_of_t(FLAG_SEED).unwrap_or_else(|_| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos() as u64)
+            .unwrap_or_default()
+    });
+    let property_testing = PropertyTesting {
+        seed,
+        iterations: matches.value_of_t(FLAG_ITERATIONS).unwrap(),
+    };
+
+    let opt_error_format: Option<ErrorFormat> = matches.value_of_t(FLAG_ERROR_FORMAT).ok();
+
+    // Machine-readable reports never include color codes.
+    let render_target = if matches.is_present(FLAG_NO_COLOR)
+        || opt_report_format.is_some()
+        || opt_error_format.is_some()
+    {
+        RenderTarget::Generic
+    } else {
+        RenderTarget::ColorTerminal
+    };
+
+    let target = triple;
     let opt_level = opt_level;
     let target_info = TargetInfo::from(target);
 
@@ -378,67 +560,177 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
 
     let load_config = LoadConfig {
         target_info,
-        // TODO: expose this from CLI?
-        render: roc_reporting::report::RenderTarget::ColorTerminal,
+        render: render_target,
         threading,
         exec_mode: ExecutionMode::Test,
+        property_testing,
+        opt_level,
+    };
+    let load_result = roc_load::load_from_path(
+        arena,
+        path.to_path_buf(),
+        subs_by_module,
+        load_config,
+        parse_cache,
+        solved_cache(matches),
+    );
+
+    let mut loaded = match load_result {
+        Ok(LoadResult::Monomorphized(loaded)) => loaded,
+        Ok(LoadResult::TypeChecked(_)) => unreachable!(""),
+        Err(LoadingProblem::FormattedReport(report, diagnostic)) => {
+            print_formatted_report(&report, diagnostic, opt_error_format);
+
+            return Ok(1);
+        }
+        Err(problem) => panic!("{:?}", problem),
     };
-    let loaded =
-        roc_load::load_and_monomorphize(arena, path.to_path_buf(), subs_by_module, load_config)
-            .unwrap();
 
-    let mut loaded = loaded;
+    // Only tools which asked for problems get them, since people read them from `roc check`.
+    if let Some(error_format) = opt_error_format {
+        let problems =
+            roc_build::program::report_problems_monomorphized(&mut loaded, Some(error_format));
+
+        if problems.errors > 0 {
+            return Ok(1);
+        }
+    }
+
     let mut expectations = std::mem::take(&mut loaded.expectations);
+    let property_expects = std::mem::take(&mut loaded.property_expects);
     let loaded = loaded;
 
     let interns = loaded.interns.clone();
 
-    let (lib, expects) = expect_mono_module_to_dylib(
-        arena,
-        target.clone(),
-        loaded,
-        opt_level,
-        LlvmBackendMode::CliTest,
-    )
-    .unwrap();
+    let (lib, mut expects) = match matches.value_of(FLAG_BACKEND) {
+        Some("dev") => match expect_mono_module_to_dylib_dev(arena, target.clone(), loaded) {
+            Ok(lib_and_expects) => lib_and_expects,
+            Err(error) => {
+                eprintln!("The dev backend could not build your tests: {}", error);
+
+                return Ok(1);
+            }
+        },
+        _ => expect_mono_module_to_dylib(
+            arena,
+            target.clone(),
+            loaded,
+            opt_level,
+            LlvmBackendMode::CliTest,
+        )
+        .unwrap(),
+    };
+
+    let locations = test_report::locate_expects(&interns, &expectations, &expects);
+
+    if let Some(filter) = opt_filter {
+        expects.retain(|expect| locations[&expect.symbol].matches(filter));
+    }
 
     let arena = &bumpalo::Bump::new();
     let interns = arena.alloc(interns);
 
-    let mut writer = std::io::stdout();
+    // Machine-readable reports go to stdout at the end, so don't mix failure reports in with them.
+    let mut writer: Box<dyn io::Write> = match opt_report_format {
+        Some(_) => Box::new(io::sink()),
+        None => Box::new(io::stdout()),
+    };
 
-    let mut shared_buffer = vec![0u8; SHM_SIZE as usize];
+    // Each expect runs in its own process, so one which crashes or loops forever
+    // can't take the others down with it.
+    #[cfg(unix)]
+    let outcomes = {
+        let max_workers = match threading {
+            Threading::Single => 1,
+            Threading::AtMost(n) => n,
+            Threading::AllAvailable => std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+        };
 
-    let set_shared_buffer = run_roc_dylib!(lib, "set_shared_buffer", (*mut u8, usize), ());
-    let mut result = RocCallResult::default();
-    let slice = (shared_buffer.as_mut_ptr(), shared_buffer.len());
-    unsafe { set_shared_buffer(slice, &mut result) };
+        let config = roc_repl_expect::run::WorkerConfig {
+            max_workers,
+            timeout,
+            shared_buffer_size: SHM_SIZE as usize,
+        };
 
-    let (failed, passed) = roc_repl_expect::run::run_expects(
-        &mut writer,
-        roc_reporting::report::RenderTarget::ColorTerminal,
-        arena,
-        interns,
-        &lib,
-        &mut expectations,
-        shared_buffer.as_mut_ptr(),
-        expects,
-    )
-    .unwrap();
+        roc_repl_expect::run::run_expects_forked(
+            &mut writer,
+            render_target,
+            arena,
+            interns,
+            &lib,
+            &mut expectations,
+            expects,
+            config,
+        )
+        .unwrap()
+    };
+
+    #[cfg(not(unix))]
+    let outcomes = {
+        use roc_gen_llvm::run_roc::RocCallResult;
+        use roc_gen_llvm::run_roc_dylib;
+
+        // There's no fork() here, so expects run in this process, and can't be timed out.
+        let _ = timeout;
+
+        let mut shared_buffer = vec![0u8; SHM_SIZE as usize];
+
+        let set_shared_buffer = run_roc_dylib!(lib, "set_shared_buffer", (*mut u8, usize), ());
+        let mut result = RocCallResult::default();
+        let slice = (shared_buffer.as_mut_ptr(), shared_buffer.len());
+        unsafe { set_shared_buffer(slice, &mut result) };
+
+        roc_repl_expect::run::run_expects(
+            &mut writer,
+            render_target,
+            arena,
+            interns,
+            &lib,
+            &mut expectations,
+            shared_buffer.as_mut_ptr(),
+            shared_buffer.len(),
+            expects,
+        )
+        .unwrap()
+    };
 
     let total_time = start_time.elapsed();
+    let failed = outcomes.iter().filter(|outcome| !outcome.passed()).count();
+    let passed = outcomes.len() - failed;
+    let count_status = |status| {
+        outcomes
+            .iter()
+            .filter(|outcome| outcome.status == status)
+            .count()
+    };
+    let crashed = count_status(ExpectStatus::Crashed);
+    let timed_out = count_status(ExpectStatus::TimedOut);
 
-    if failed == 0 && passed == 0 {
-        // TODO print this in a more nicely formatted way!
-        println!("No expectations were found.");
+    // Crashes and timeouts are counted as failures, but get called out separately.
+    let mut abnormal = String::new();
 
-        // If no tests ran, treat that as an error. This is perhaps
-        // briefly annoying at the very beginning of a project when
-        // you actually have zero tests, but it can save you from
-        // having a change to your CI script accidentally stop
-        // running tests altogether!
-        Ok(2)
-    } else {
+    if crashed > 0 {
+        abnormal.push_str(&format!(", {crashed} crashed"));
+    }
+
+    if timed_out > 0 {
+        abnormal.push_str(&format!(", {timed_out} timed out"));
+    }
+
+    if let Some(report_format) = opt_report_format {
+        print!(
+            "{}",
+            test_report::render(report_format, &outcomes, &locations, total_time)
+        );
+    } else if failed == 0 && passed == 0 {
+        // TODO print this in a more nicely formatted way!
+        match opt_filter {
+            Some(filter) => println!("No expectations matched the filter `{filter}`."),
+            None => println!("No expectations were found."),
+        }
+    } else if matches!(render_target, RenderTarget::ColorTerminal) {
         let failed_color = if failed == 0 {
             32 // green
         } else {
@@ -446,10 +738,32 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
         };
 
         println!(
-            "\n\x1B[{failed_color}m{failed}\x1B[39m failed and \x1B[32m{passed}\x1B[39m passed in {} ms.\n",
+            "\n\x1B[{failed_color}m{failed}\x1B[39m failed{abnormal} and \x1B[32m{passed}\x1B[39m passed in {} ms.\n",
+            total_time.as_millis(),
+        );
+    } else {
+        println!(
+            "\n{failed} failed{abnormal} and {passed} passed in {} ms.\n",
             total_time.as_millis(),
         );
+    }
+
+    let property_expect_failed = outcomes
+        .iter()
+        .any(|outcome| !outcome.passed() && property_expects.contains(&outcome.symbol));
+
+    if property_expect_failed && opt_report_format.is_none() {
+        println!("Inputs to property-based expects were generated from seed {seed}; run with `--{FLAG_SEED} {seed}` to reproduce them.\n");
+    }
 
+    if failed == 0 && passed == 0 {
+        // If no tests ran, treat that as an error. This is perhaps
+        // briefly annoying at the very beginning of a project when
+        // you actually have zero tests, but it can save you from
+        // having a change to your CI script accidentally stop
+        // running tests altogether!
+        Ok(2)
+    } else {
         Ok((failed > 0) as i32)
     }
 }
@@ -459,11 +773,56 @@ pub fn build(
     config: BuildConfig,
     triple: Triple,
     link_type: LinkType,
+) -> io::Result<i32> {
+    // Only `roc dev` has this flag; the programs `roc run` and `roc build` make are one-offs.
+    let watching = match config {
+        BuildConfig::BuildAndRunIfNoErrors => matches.is_present(FLAG_WATCH),
+        BuildConfig::BuildOnly | BuildConfig::BuildAndRun => false,
+    };
+
+    if watching {
+        if cfg!(not(unix)) {
+            user_error!("`roc dev --watch` is only supported on Unix-like systems for now.");
+        }
+
+        let path = Path::new(matches.value_of_os(ROC_FILE).unwrap());
+
+        watch::watch(path, |arena, parse_cache| {
+            build_once(
+                matches,
+                config,
+                &triple,
+                link_type,
+                arena,
+                Some(parse_cache),
+            )
+        })
+    } else {
+        let arena = Bump::new();
+        let exit_code = build_once(matches, config, &triple, link_type, &arena, None);
+
+        // No need to waste time freeing this memory,
+        // since the process is about to exit anyway.
+        std::mem::forget(arena);
+
+        exit_code
+    }
+}
+
+/// With a `parse_cache`, we're watching for changes, so rather than turning into the program
+/// we start it in the background.
+fn build_once<'a>(
+    matches: &ArgMatches,
+    config: BuildConfig,
+    triple: &Triple,
+    link_type: LinkType,
+    arena: &'a Bump,
+    parse_cache: Option<ParseCache<'a>>,
 ) -> io::Result<i32> {
     use build::build_file;
     use BuildConfig::*;
 
-    let arena = Bump::new();
+    let watching = parse_cache.is_some();
     let filename = matches.value_of_os(ROC_FILE).unwrap();
     let opt_level = match (
         matches.is_present(FLAG_OPTIMIZE),
@@ -479,6 +838,12 @@ pub fn build(
     let emit_debug_info = matches.is_present(FLAG_DEBUG);
     let emit_timings = matches.is_present(FLAG_TIME);
 
+    // Only `roc build` has this flag; the other commands which build also run the program.
+    let opt_error_format: Option<ErrorFormat> = match config {
+        BuildOnly => matches.value_of_t(FLAG_ERROR_FORMAT).ok(),
+        BuildAndRun | BuildAndRunIfNoErrors => None,
+    };
+
     let threading = match matches
         .value_of(FLAG_MAX_THREADS)
         .and_then(|s| s.parse::<usize>().ok())
@@ -494,7 +859,7 @@ pub fn build(
 
     let linking_strategy = if wasm_dev_backend {
         LinkingStrategy::Additive
-    } else if !roc_linker::supported(link_type, &triple)
+    } else if !roc_linker::supported(link_type, triple)
         || matches.value_of(FLAG_LINKER) == Some("legacy")
     {
         LinkingStrategy::Legacy
@@ -508,7 +873,7 @@ pub fn build(
         // When compiling for a different target, default to assuming a precompiled host.
         // Otherwise compilation would most likely fail because many toolchains assume you're compiling for the host
         // We make an exception for Wasm, because cross-compiling is the norm in that case.
-        triple != Triple::host() && !matches!(triple.architecture, Architecture::Wasm32)
+        *triple != Triple::host() && !matches!(triple.architecture, Architecture::Wasm32)
     };
     let path = Path::new(filename);
 
@@ -541,19 +906,30 @@ pub fn build(
         BuildAndRunIfNoErrors => BuildOrdering::BuildIfChecks,
         _ => BuildOrdering::AlwaysBuild,
     };
+
+    // Only a program that we run ourselves can have its expects and dbgs rendered by us.
+    // What `roc build` outputs gets run some other way.
+    let run_by_roc = match config {
+        BuildOnly => false,
+        BuildAndRun | BuildAndRunIfNoErrors => true,
+    };
     let res_binary_path = build_file(
-        &arena,
-        &triple,
+        arena,
+        triple,
         path.to_path_buf(),
         opt_level,
         emit_debug_info,
         emit_timings,
+        run_by_roc,
         link_type,
         linking_strategy,
         precompiled,
         threading,
         wasm_dev_stack_bytes,
         build_ordering,
+        opt_error_format,
+        parse_cache,
+        solved_cache(matches),
     );
 
     match res_binary_path {
@@ -571,9 +947,10 @@ pub fn build(
                         .strip_prefix(env::current_dir().unwrap())
                         .unwrap_or(&binary_path);
 
-                    // No need to waste time freeing this memory,
-                    // since the process is about to exit anyway.
-                    std::mem::forget(arena);
+                    // Machine-readable output is only the problems, so tools can parse all of it.
+                    if opt_error_format.is_some() {
+                        return Ok(problems.exit_code());
+                    }
 
                     println!(
                         "\x1B[{}m{}\x1B[39m {} and \x1B[{}m{}\x1B[39m {} found in {} ms while successfully building:\n\n    {}",
@@ -642,9 +1019,9 @@ pub fn build(
                     let mut bytes = std::fs::read(&binary_path).unwrap();
 
                     let x = roc_run(
-                        arena,
+                        Bump::new(),
                         opt_level,
-                        triple,
+                        triple.clone(),
                         args,
                         &mut bytes,
                         expectations,
@@ -676,17 +1053,25 @@ pub fn build(
 
                     let mut bytes = std::fs::read(&binary_path).unwrap();
 
-                    let x = roc_run(
-                        arena,
-                        opt_level,
-                        triple,
-                        args,
-                        &mut bytes,
-                        expectations,
-                        interns,
-                    );
-                    std::mem::forget(bytes);
-                    x
+                    let run = || {
+                        let x = roc_run(
+                            Bump::new(),
+                            opt_level,
+                            triple.clone(),
+                            args,
+                            &mut bytes,
+                            expectations,
+                            interns,
+                        );
+                        std::mem::forget(bytes);
+                        x
+                    };
+
+                    if watching {
+                        watch::run_in_background(run)
+                    } else {
+                        run()
+                    }
                 }
             }
         }
@@ -696,7 +1081,12 @@ pub fn build(
         }) => {
             debug_assert!(module.total_problems() > 0);
 
-            let problems = roc_build::program::report_problems_typechecked(&mut module);
+            let problems =
+                roc_build::program::report_problems_typechecked(&mut module, opt_error_format);
+
+            if opt_error_format.is_some() {
+                return Ok(problems.exit_code());
+            }
 
             let mut output = format!(
                 "\x1B[{}m{}\x1B[39m {} and \x1B[{}m{}\x1B[39m {} found in {} ms.\n\nYou can run the program anyway with \x1B[32mroc run",
@@ -735,8 +1125,11 @@ pub fn build(
 
             Ok(problems.exit_code())
         }
-        Err(BuildFileError::LoadingProblem(LoadingProblem::FormattedReport(report))) => {
-            print!("{}", report);
+        Err(BuildFileError::LoadingProblem(LoadingProblem::FormattedReport(
+            report,
+            diagnostic,
+        ))) => {
+            print_formatted_report(&report, diagnostic, opt_error_format);
 
             Ok(1)
         }
@@ -746,6 +1139,19 @@ pub fn build(
     }
 }
 
+/// Prints the report of a problem which stopped us from loading the app at all, like a
+/// syntax error.
+pub fn print_formatted_report(
+    report: &str,
+    diagnostic: Diagnostic,
+    opt_error_format: Option<ErrorFormat>,
+) {
+    match opt_error_format {
+        Some(error_format) => print!("{}", structured::render(error_format, &[diagnostic])),
+        None => print!("{}", report),
+    }
+}
+
 fn roc_run<'a, I: IntoIterator<Item = &'a OsStr>>(
     arena: Bump, // This should be passed an owned value, not a reference, so we can usefully mem::forget it!
     opt_level: OptLevel,
@@ -826,12 +1232,17 @@ fn make_argv_envp<'a, I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
     // envp is an array of pointers to strings, conventionally of the
     // form key=value, which are passed as the environment of the new
     // program.  The envp array must be terminated by a NULL pointer.
+    //
+    // A buffer name we inherited belongs to whoever started us, not to this program.
     let envp_cstrings: bumpalo::collections::Vec<CString> = std::env::vars_os()
-        .flat_map(|(k, v)| {
-            [
-                CString::new(k.as_bytes()).unwrap(),
-                CString::new(v.as_bytes()).unwrap(),
-            ]
+        .filter(|(k, _)| k != EXPECT_BUFFER_ENV_VAR)
+        .map(|(k, v)| {
+            let mut bytes = Vec::with_capacity(k.len() + 1 + v.len());
+            bytes.extend_from_slice(k.as_bytes());
+            bytes.push(b'=');
+            bytes.extend_from_slice(v.as_bytes());
+
+            CString::new(bytes).unwrap()
         })
         .collect_in(arena);
 
@@ -852,7 +1263,25 @@ fn roc_run_native<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
 
     unsafe {
         let executable = roc_run_executable_file_path(binary_bytes)?;
-        let (argv_cstrings, envp_cstrings) = make_argv_envp(&arena, &executable, args);
+        let (argv_cstrings, mut envp_cstrings) = make_argv_envp(&arena, &executable, args);
+
+        // an optimized build only waits on us when the program can actually send us something
+        let has_expects_or_dbgs = expectations.keys().any(|module_id| !module_id.is_builtin());
+
+        let expect_buffer_name = match opt_level {
+            OptLevel::Size | OptLevel::Optimize => None,
+            OptLevel::Normal if !has_expects_or_dbgs => None,
+            OptLevel::Development | OptLevel::Normal => {
+                // IMPORTANT: shared memory object names must begin with / and contain no other slashes!
+                let name = format!("/roc_expect_buffer_{}", std::process::id());
+
+                // the child only renders its expects and dbgs through us if it finds this variable
+                let var = format!("{}={}", EXPECT_BUFFER_ENV_VAR, name);
+                envp_cstrings.push(CString::new(var).unwrap());
+
+                Some(CString::new(name).unwrap())
+            }
+        };
 
         let argv: bumpalo::collections::Vec<*const c_char> = argv_cstrings
             .iter()
@@ -866,11 +1295,11 @@ fn roc_run_native<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
             .chain([std::ptr::null()])
             .collect_in(&arena);
 
-        match opt_level {
-            OptLevel::Development => {
-                roc_run_native_debug(executable, &argv, &envp, expectations, interns)
+        match expect_buffer_name {
+            Some(name) => {
+                roc_run_native_debug(executable, &argv, &envp, &name, expectations, interns)
             }
-            OptLevel::Normal | OptLevel::Size | OptLevel::Optimize => {
+            None => {
                 roc_run_native_fast(executable, &argv, &envp);
             }
         }
@@ -956,12 +1385,38 @@ unsafe fn roc_run_native_debug(
     executable: ExecutableFile,
     argv: &[*const c_char],
     envp: &[*const c_char],
+    expect_buffer_name: &std::ffi::CStr,
     mut expectations: VecMap<ModuleId, Expectations>,
     interns: Interns,
 ) {
-    use signal_hook::{consts::signal::SIGCHLD, consts::signal::SIGUSR1, iterator::Signals};
+    use signal_hook::{
+        consts::signal::SIGCHLD, consts::signal::SIGINT, consts::signal::SIGTERM,
+        consts::signal::SIGUSR1, consts::signal::SIGUSR2, iterator::Signals,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
-    let mut signals = Signals::new(&[SIGCHLD, SIGUSR1]).unwrap();
+    let mut signals = Signals::new(&[SIGCHLD, SIGINT, SIGTERM, SIGUSR1, SIGUSR2]).unwrap();
+
+    // the child opens this shared memory object by the name we put in its environment,
+    // so it must exist before the child starts
+    let shared_fd = libc::shm_open(
+        expect_buffer_name.as_ptr(),
+        libc::O_RDWR | libc::O_CREAT,
+        0o600,
+    );
+
+    libc::ftruncate(shared_fd, SHM_SIZE);
+
+    let shared_ptr = libc::mmap(
+        std::ptr::null_mut(),
+        SHM_SIZE as usize,
+        libc::PROT_READ | libc::PROT_WRITE,
+        libc::MAP_SHARED,
+        shared_fd,
+        0,
+    );
+
+    let shared_memory_ptr: *mut u8 = shared_ptr.cast();
 
     match libc::fork() {
         0 => {
@@ -992,10 +1447,7 @@ unsafe fn roc_run_native_debug(
 
             process::exit(1)
         }
-        1.. => {
-            let name = "/roc_expect_buffer"; // IMPORTANT: shared memory object names must begin with / and contain no other slashes!
-            let cstring = CString::new(name).unwrap();
-
+        child_pid @ 1.. => {
             let arena = &bumpalo::Bump::new();
             let interns = arena.alloc(interns);
 
@@ -1003,37 +1455,48 @@ unsafe fn roc_run_native_debug(
                 match sig {
                     SIGCHLD => {
                         // clean up
-                        libc::shm_unlink(cstring.as_ptr().cast());
+                        libc::shm_unlink(expect_buffer_name.as_ptr());
 
                         // done!
                         process::exit(0);
                     }
+                    SIGINT | SIGTERM => {
+                        // e.g. Ctrl-C, or `roc dev --watch` is about to run a newer version of
+                        // the program. Either way, don't leave the shared memory object behind.
+                        libc::kill(child_pid, libc::SIGKILL);
+                        libc::shm_unlink(expect_buffer_name.as_ptr());
+
+                        process::exit(1);
+                    }
                     SIGUSR1 => {
                         // this is the signal we use for an expect failure. Let's see what the child told us
-                        let shared_fd =
-                            libc::shm_open(cstring.as_ptr().cast(), libc::O_RDONLY, 0o666);
-
-                        libc::ftruncate(shared_fd, SHM_SIZE);
-
-                        let shared_ptr = libc::mmap(
-                            std::ptr::null_mut(),
-                            SHM_SIZE as usize,
-                            libc::PROT_READ,
-                            libc::MAP_SHARED,
-                            shared_fd,
-                            0,
-                        );
-
-                        let shared_memory_ptr: *mut u8 = shared_ptr.cast();
-
                         roc_dev_expect(
                             &mut std::io::stdout(),
                             arena,
                             &mut expectations,
                             interns,
                             shared_memory_ptr,
+                            SHM_SIZE as usize,
+                        )
+                        .unwrap();
+
+                        // let the child continue
+                        (*shared_memory_ptr.cast::<AtomicUsize>()).store(0, Ordering::SeqCst);
+                    }
+                    SIGUSR2 => {
+                        // this is the signal we use for a dbg
+                        roc_dev_dbg(
+                            &mut std::io::stderr(),
+                            arena,
+                            &mut expectations,
+                            interns,
+                            shared_memory_ptr,
+                            SHM_SIZE as usize,
                         )
                         .unwrap();
+
+                        // let the child continue
+                        (*shared_memory_ptr.cast::<AtomicUsize>()).store(0, Ordering::SeqCst);
                     }
                     _ => println!("received signal {}", sig),
                 }
@@ -1,14 +1,20 @@
+use bumpalo::Bump;
+use clap::ArgMatches;
 use roc_build::link::LinkType;
 use roc_cli::build::check_file;
+use roc_cli::watch::watch;
 use roc_cli::{
-    build_app, format, test, BuildConfig, FormatMode, Target, CMD_BUILD, CMD_CHECK, CMD_DEV,
-    CMD_DOCS, CMD_EDIT, CMD_FORMAT, CMD_GLUE, CMD_REPL, CMD_RUN, CMD_TEST, CMD_VERSION,
-    DIRECTORY_OR_FILES, FLAG_CHECK, FLAG_LIB, FLAG_NO_LINK, FLAG_TARGET, FLAG_TIME, GLUE_FILE,
-    ROC_FILE,
+    build_app, format, format_stdin, print_formatted_report, test, BuildConfig, FormatMode, Target,
+    CMD_BUILD, CMD_CHECK, CMD_DEV, CMD_DOCS, CMD_EDIT, CMD_EXPLAIN, CMD_FORMAT, CMD_GLUE, CMD_LSP,
+    CMD_REPL, CMD_RUN, CMD_TEST, CMD_VERSION, DIRECTORY_OR_FILES, ERROR_CODE, FLAG_CHECK,
+    FLAG_ERROR_FORMAT, FLAG_LIB, FLAG_NO_LINK, FLAG_STDIN, FLAG_TARGET, FLAG_TIME, FLAG_WATCH,
+    GLUE_FILE, ROC_FILE,
 };
 use roc_docs::generate_docs_html;
 use roc_error_macros::user_error;
-use roc_load::{LoadingProblem, Threading};
+use roc_load::{LoadingProblem, ParseCache, Threading};
+use roc_reporting::explain;
+use roc_reporting::structured::ErrorFormat;
 use std::fs::{self, FileType};
 use std::io;
 use std::path::{Path, PathBuf};
@@ -85,10 +91,10 @@ fn main() -> io::Result<()> {
             let input_path = Path::new(matches.value_of_os(ROC_FILE).unwrap());
             let output_path = Path::new(matches.value_of_os(GLUE_FILE).unwrap());
 
-            if Some("rs") == output_path.extension().and_then(OsStr::to_str) {
+            if roc_glue::Language::from_path(output_path).is_some() {
                 roc_glue::generate(input_path, output_path)
             } else {
-                eprintln!("Currently, `roc glue` only supports generating Rust glue files (with the .rs extension). In the future, the plan is to decouple `roc glue` from any particular output format, by having it accept a second .roc file which gets executed as a plugin to generate glue code for any desired language. However, this has not yet been implemented, and for now only .rs is supported.");
+                eprintln!("`roc glue` can generate Rust, C, or Zig glue files, and it picks which one based on the output file's extension. Please use a .rs, .h, or .zig extension for the output file.");
 
                 Ok(1)
             }
@@ -114,61 +120,16 @@ fn main() -> io::Result<()> {
             )?)
         }
         Some((CMD_CHECK, matches)) => {
-            let arena = bumpalo::Bump::new();
-
-            let emit_timings = matches.is_present(FLAG_TIME);
-            let filename = matches.value_of_os(ROC_FILE).unwrap();
-            let roc_file_path = PathBuf::from(filename);
-            let threading = match matches
-                .value_of(roc_cli::FLAG_MAX_THREADS)
-                .and_then(|s| s.parse::<usize>().ok())
-            {
-                None => Threading::AllAvailable,
-                Some(0) => user_error!("cannot build with at most 0 threads"),
-                Some(1) => Threading::Single,
-                Some(n) => Threading::AtMost(n),
-            };
-
-            match check_file(&arena, roc_file_path, emit_timings, threading) {
-                Ok((problems, total_time)) => {
-                    println!(
-                        "\x1B[{}m{}\x1B[39m {} and \x1B[{}m{}\x1B[39m {} found in {} ms.",
-                        if problems.errors == 0 {
-                            32 // green
-                        } else {
-                            33 // yellow
-                        },
-                        problems.errors,
-                        if problems.errors == 1 {
-                            "error"
-                        } else {
-                            "errors"
-                        },
-                        if problems.warnings == 0 {
-                            32 // green
-                        } else {
-                            33 // yellow
-                        },
-                        problems.warnings,
-                        if problems.warnings == 1 {
-                            "warning"
-                        } else {
-                            "warnings"
-                        },
-                        total_time.as_millis(),
-                    );
+            let roc_file_path = PathBuf::from(matches.value_of_os(ROC_FILE).unwrap());
 
-                    Ok(problems.exit_code())
-                }
-
-                Err(LoadingProblem::FormattedReport(report)) => {
-                    print!("{}", report);
+            if matches.is_present(FLAG_WATCH) {
+                watch(&roc_file_path, |arena, parse_cache| {
+                    check(matches, roc_file_path.clone(), arena, Some(parse_cache))
+                })
+            } else {
+                let arena = Bump::new();
 
-                    Ok(1)
-                }
-                Err(other) => {
-                    panic!("build_file failed with error:\n{:?}", other);
-                }
+                check(matches, roc_file_path, &arena, None)
             }
         }
         Some((CMD_REPL, _)) => {
@@ -231,42 +192,48 @@ fn main() -> io::Result<()> {
             Ok(0)
         }
         Some((CMD_FORMAT, matches)) => {
-            let maybe_values = matches.values_of_os(DIRECTORY_OR_FILES);
-
-            let mut values: Vec<OsString> = Vec::new();
+            let format_mode = match matches.is_present(FLAG_CHECK) {
+                true => FormatMode::CheckOnly,
+                false => FormatMode::Format,
+            };
 
-            match maybe_values {
-                None => {
-                    let mut os_string_values: Vec<OsString> = Vec::new();
-                    read_all_roc_files(
-                        &std::env::current_dir()?.as_os_str().to_os_string(),
-                        &mut os_string_values,
-                    )?;
-                    for os_string in os_string_values {
-                        values.push(os_string);
+            let result = if matches.is_present(FLAG_STDIN) {
+                format_stdin(format_mode)
+            } else {
+                let maybe_values = matches.values_of_os(DIRECTORY_OR_FILES);
+
+                let mut values: Vec<OsString> = Vec::new();
+
+                match maybe_values {
+                    None => {
+                        let mut os_string_values: Vec<OsString> = Vec::new();
+                        read_all_roc_files(
+                            &std::env::current_dir()?.as_os_str().to_os_string(),
+                            &mut os_string_values,
+                        )?;
+                        for os_string in os_string_values {
+                            values.push(os_string);
+                        }
                     }
-                }
-                Some(os_values) => {
-                    for os_str in os_values {
-                        values.push(os_str.to_os_string());
+                    Some(os_values) => {
+                        for os_str in os_values {
+                            values.push(os_str.to_os_string());
+                        }
                     }
                 }
-            }
 
-            let mut roc_files = Vec::new();
+                let mut roc_files = Vec::new();
 
-            // Populate roc_files
-            for os_str in values {
-                let metadata = fs::metadata(os_str.clone())?;
-                roc_files_recursive(os_str.as_os_str(), metadata.file_type(), &mut roc_files)?;
-            }
+                // Populate roc_files
+                for os_str in values {
+                    let metadata = fs::metadata(os_str.clone())?;
+                    roc_files_recursive(os_str.as_os_str(), metadata.file_type(), &mut roc_files)?;
+                }
 
-            let format_mode = match matches.is_present(FLAG_CHECK) {
-                true => FormatMode::CheckOnly,
-                false => FormatMode::Format,
+                format(roc_files, format_mode)
             };
 
-            let format_exit_code = match format(roc_files, format_mode) {
+            let format_exit_code = match result {
                 Ok(_) => 0,
                 Err(message) => {
                     eprintln!("{}", message);
@@ -276,6 +243,37 @@ fn main() -> io::Result<()> {
 
             Ok(format_exit_code)
         }
+        Some((CMD_LSP, _)) => {
+            roc_lang_srv::start()
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))?;
+
+            // Exit 0 once the editor has shut the language server down
+            Ok(0)
+        }
+        Some((CMD_EXPLAIN, matches)) => {
+            let code = matches.value_of(ERROR_CODE).unwrap();
+
+            match explain::lookup(code) {
+                Some(error_code) => {
+                    println!(
+                        "{}: {}\n\n{}",
+                        error_code.code,
+                        error_code.summary,
+                        error_code.explanation.trim_end()
+                    );
+
+                    Ok(0)
+                }
+                None => {
+                    eprintln!(
+                        "{} is not a Roc error code. Error codes look like E0301, and are shown in the header of each report.",
+                        code
+                    );
+
+                    Ok(1)
+                }
+            }
+        }
         Some((CMD_VERSION, _)) => {
             print!(
                 "{}",
@@ -290,6 +288,77 @@ fn main() -> io::Result<()> {
     std::process::exit(exit_code);
 }
 
+fn check<'a>(
+    matches: &ArgMatches,
+    roc_file_path: PathBuf,
+    arena: &'a Bump,
+    parse_cache: Option<ParseCache<'a>>,
+) -> io::Result<i32> {
+    let emit_timings = matches.is_present(FLAG_TIME);
+    let threading = match matches
+        .value_of(roc_cli::FLAG_MAX_THREADS)
+        .and_then(|s| s.parse::<usize>().ok())
+    {
+        None => Threading::AllAvailable,
+        Some(0) => user_error!("cannot build with at most 0 threads"),
+        Some(1) => Threading::Single,
+        Some(n) => Threading::AtMost(n),
+    };
+
+    let opt_error_format: Option<ErrorFormat> = matches.value_of_t(FLAG_ERROR_FORMAT).ok();
+
+    match check_file(
+        arena,
+        roc_file_path,
+        emit_timings,
+        threading,
+        opt_error_format,
+        parse_cache,
+        roc_cli::solved_cache(matches),
+    ) {
+        Ok((problems, _)) if opt_error_format.is_some() => Ok(problems.exit_code()),
+        Ok((problems, total_time)) => {
+            println!(
+                "\x1B[{}m{}\x1B[39m {} and \x1B[{}m{}\x1B[39m {} found in {} ms.",
+                if problems.errors == 0 {
+                    32 // green
+                } else {
+                    33 // yellow
+                },
+                problems.errors,
+                if problems.errors == 1 {
+                    "error"
+                } else {
+                    "errors"
+                },
+                if problems.warnings == 0 {
+                    32 // green
+                } else {
+                    33 // yellow
+                },
+                problems.warnings,
+                if problems.warnings == 1 {
+                    "warning"
+                } else {
+                    "warnings"
+                },
+                total_time.as_millis(),
+            );
+
+            Ok(problems.exit_code())
+        }
+
+        Err(LoadingProblem::FormattedReport(report, diagnostic)) => {
+            print_formatted_report(&report, diagnostic, opt_error_format);
+
+            Ok(1)
+        }
+        Err(other) => {
+            panic!("build_file failed with error:\n{:?}", other);
+        }
+    }
+}
+
 fn read_all_roc_files(
     dir: &OsString,
     roc_file_paths: &mut Vec<OsString>,
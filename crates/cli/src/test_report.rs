@@ -0,0 +1,371 @@
+//! Machine-readable reports of `roc test` results, for CI systems and dashboards.
+
+use bumpalo::Bump;
+use roc_collections::{MutMap, VecMap};
+use roc_load::Expectations;
+use roc_module::symbol::{Interns, ModuleId, Symbol};
+use roc_parse::ast::{Pattern, ValueDef};
+use roc_parse::module::{self, module_defs};
+use roc_parse::parser::Parser;
+use roc_parse::state::State;
+use roc_region::all::{LineColumnRegion, LineInfo, Region};
+use roc_repl_expect::run::{ExpectOutcome, ExpectStatus, ToplevelExpect};
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    Junit,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "json" => Ok(ReportFormat::Json),
+            "junit" => Ok(ReportFormat::Junit),
+            _ => Err(format!("Unsupported report format: {string}")),
+        }
+    }
+}
+
+/// Where a top-level expect is in the source code. This is what it gets named,
+/// filtered and reported by.
+pub struct ExpectLocation {
+    pub module_name: String,
+    pub path: PathBuf,
+    pub region: LineColumnRegion,
+    /// The top-level def the expect is written under, i.e. the closest one before it
+    pub def_name: Option<String>,
+}
+
+impl ExpectLocation {
+    /// e.g. "Main:12" for the expect which starts on line 12 of the Main module
+    pub fn name(&self) -> String {
+        format!("{}:{}", self.module_name, self.region.start.line + 1)
+    }
+
+    /// An expect matches a `--filter` if the name of its enclosing def or its module contains the filter.
+    /// Expects usually come right after the def they test, e.g. `expect isEven 4` after `isEven`.
+    pub fn matches(&self, filter: &str) -> bool {
+        let def_matches = match &self.def_name {
+            Some(def_name) => def_name.contains(filter),
+            None => false,
+        };
+
+        def_matches || self.module_name.contains(filter)
+    }
+}
+
+pub fn locate_expects(
+    interns: &Interns,
+    expectations: &VecMap<ModuleId, Expectations>,
+    expects: &[ToplevelExpect],
+) -> MutMap<Symbol, ExpectLocation> {
+    // Several expects are usually in the same module, so only read each file once.
+    let mut sources: MutMap<ModuleId, (LineInfo, Vec<(Region, String)>)> = MutMap::default();
+    let mut locations = MutMap::default();
+
+    for expect in expects {
+        let module_id = expect.symbol.module_id();
+        let path = match expectations.get(&module_id) {
+            Some(data) => data.path.clone(),
+            None => PathBuf::new(),
+        };
+        let (line_info, defs) = sources.entry(module_id).or_insert_with(|| {
+            let source = std::fs::read_to_string(&path).unwrap_or_default();
+
+            (LineInfo::new(&source), top_level_defs(&source))
+        });
+
+        let def_name = defs
+            .iter()
+            .take_while(|(region, _)| region.start() <= expect.region.start())
+            .last()
+            .map(|(_, name)| name.clone());
+
+        locations.insert(
+            expect.symbol,
+            ExpectLocation {
+                module_name: interns.module_name(module_id).as_str().to_string(),
+                path,
+                region: line_info.convert_region(expect.region),
+                def_name,
+            },
+        );
+    }
+
+    locations
+}
+
+/// The regions and names of a module's top-level defs, in the order they appear.
+/// Defs which destructure, rather than define a name, are skipped.
+fn top_level_defs(source: &str) -> Vec<(Region, String)> {
+    let arena = Bump::new();
+
+    let defs = match module::parse_header(&arena, State::new(source.as_bytes())) {
+        Ok((_, state)) => match module_defs().parse(&arena, state) {
+            Ok((_, defs, _)) => defs,
+            Err(_) => return Vec::new(),
+        },
+        Err(_) => return Vec::new(),
+    };
+
+    defs.defs()
+        .zip(defs.regions.iter())
+        .filter_map(|(def, region)| {
+            let pattern = match def {
+                Err(ValueDef::Annotation(pattern, _)) => &pattern.value,
+                Err(ValueDef::Body(pattern, _)) => &pattern.value,
+                Err(ValueDef::AnnotatedBody { body_pattern, .. }) => &body_pattern.value,
+                _ => return None,
+            };
+
+            match pattern {
+                Pattern::Identifier(name) => Some((*region, name.to_string())),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+pub fn render(
+    format: ReportFormat,
+    outcomes: &[ExpectOutcome],
+    locations: &MutMap<Symbol, ExpectLocation>,
+    total_time: Duration,
+) -> String {
+    match format {
+        ReportFormat::Json => render_json(outcomes, locations, total_time),
+        ReportFormat::Junit => render_junit(outcomes, locations, total_time),
+    }
+}
+
+fn render_json(
+    outcomes: &[ExpectOutcome],
+    locations: &MutMap<Symbol, ExpectLocation>,
+    total_time: Duration,
+) -> String {
+    let failed = outcomes.iter().filter(|outcome| !outcome.passed()).count();
+    let mut buf = String::new();
+
+    writeln!(buf, "{{").unwrap();
+    writeln!(buf, "  \"passed\": {},", outcomes.len() - failed).unwrap();
+    writeln!(buf, "  \"failed\": {failed},").unwrap();
+    writeln!(buf, "  \"duration_ms\": {:.3},", millis(total_time)).unwrap();
+    write!(buf, "  \"expects\": [").unwrap();
+
+    for (index, outcome) in outcomes.iter().enumerate() {
+        let location = &locations[&outcome.symbol];
+        let region = location.region;
+        let failure = match &outcome.failure {
+            Some(failure) => json_string(failure),
+            None => "null".to_string(),
+        };
+
+        if index > 0 {
+            buf.push(',');
+        }
+
+        // Lines and columns are 1-based, like in editors.
+        write!(
+            buf,
+            r#"
+    {{
+      "name": {},
+      "module": {},
+      "path": {},
+      "region": {{ "start_line": {}, "start_column": {}, "end_line": {}, "end_column": {} }},
+      "duration_ms": {:.3},
+      "passed": {},
+      "status": {},
+      "failure": {}
+    }}"#,
+            json_string(&location.name()),
+            json_string(&location.module_name),
+            json_string(&location.path.to_string_lossy()),
+            region.start.line + 1,
+            region.start.column + 1,
+            region.end.line + 1,
+            region.end.column + 1,
+            millis(outcome.duration),
+            outcome.passed(),
+            json_string(status_name(outcome.status)),
+            failure,
+        )
+        .unwrap();
+    }
+
+    if !outcomes.is_empty() {
+        buf.push_str("\n  ");
+    }
+
+    buf.push_str("]\n}\n");
+
+    buf
+}
+
+fn render_junit(
+    outcomes: &[ExpectOutcome],
+    locations: &MutMap<Symbol, ExpectLocation>,
+    total_time: Duration,
+) -> String {
+    // JUnit groups test cases into suites; each module is a suite.
+    let mut suites: VecMap<&str, Vec<&ExpectOutcome>> = VecMap::default();
+
+    for outcome in outcomes {
+        let module_name = locations[&outcome.symbol].module_name.as_str();
+
+        suites.get_or_insert(module_name, Vec::new).push(outcome);
+    }
+
+    let failed = count_status(outcomes, ExpectStatus::Failed);
+    let errors = outcomes.len() - failed - count_status(outcomes, ExpectStatus::Passed);
+    let mut buf = String::new();
+
+    writeln!(buf, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+    writeln!(
+        buf,
+        r#"<testsuites name="roc test" tests="{}" failures="{failed}" errors="{errors}" time="{:.3}">"#,
+        outcomes.len(),
+        total_time.as_secs_f64()
+    )
+    .unwrap();
+
+    for (module_name, suite) in suites.iter() {
+        let suite_failed = suite
+            .iter()
+            .filter(|outcome| outcome.status == ExpectStatus::Failed)
+            .count();
+        let suite_errors = suite
+            .iter()
+            .filter(|outcome| {
+                matches!(
+                    outcome.status,
+                    ExpectStatus::Crashed | ExpectStatus::TimedOut
+                )
+            })
+            .count();
+        let suite_time: Duration = suite.iter().map(|outcome| outcome.duration).sum();
+
+        writeln!(
+            buf,
+            r#"  <testsuite name="{}" tests="{}" failures="{suite_failed}" errors="{suite_errors}" time="{:.3}">"#,
+            xml_escape(module_name),
+            suite.len(),
+            suite_time.as_secs_f64()
+        )
+        .unwrap();
+
+        for outcome in suite {
+            let location = &locations[&outcome.symbol];
+
+            write!(
+                buf,
+                r#"    <testcase name="{}" classname="{}" file="{}" line="{}" time="{:.3}""#,
+                xml_escape(&location.name()),
+                xml_escape(module_name),
+                xml_escape(&location.path.to_string_lossy()),
+                location.region.start.line + 1,
+                outcome.duration.as_secs_f64()
+            )
+            .unwrap();
+
+            // JUnit reports an error (rather than a failure) when a test couldn't finish.
+            let element = match outcome.status {
+                ExpectStatus::Passed | ExpectStatus::Failed => "failure",
+                ExpectStatus::Crashed | ExpectStatus::TimedOut => "error",
+            };
+            let message = match outcome.status {
+                ExpectStatus::Passed | ExpectStatus::Failed => "expect failed",
+                ExpectStatus::Crashed => "crashed",
+                ExpectStatus::TimedOut => "timed out",
+            };
+
+            match &outcome.failure {
+                Some(failure) => {
+                    writeln!(
+                        buf,
+                        ">\n      <{element} message=\"{message}\">{}</{element}>\n    </testcase>",
+                        xml_escape(failure)
+                    )
+                    .unwrap();
+                }
+                None => {
+                    buf.push_str("/>\n");
+                }
+            }
+        }
+
+        writeln!(buf, "  </testsuite>").unwrap();
+    }
+
+    writeln!(buf, "</testsuites>").unwrap();
+
+    buf
+}
+
+fn count_status(outcomes: &[ExpectOutcome], status: ExpectStatus) -> usize {
+    outcomes
+        .iter()
+        .filter(|outcome| outcome.status == status)
+        .count()
+}
+
+fn status_name(status: ExpectStatus) -> &'static str {
+    match status {
+        ExpectStatus::Passed => "passed",
+        ExpectStatus::Failed => "failed",
+        ExpectStatus::Crashed => "crashed",
+        ExpectStatus::TimedOut => "timed_out",
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn json_string(string: &str) -> String {
+    let mut buf = String::with_capacity(string.len() + 2);
+
+    buf.push('"');
+
+    for ch in string.chars() {
+        match ch {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => write!(buf, "\\u{:04x}", ch as u32).unwrap(),
+            ch => buf.push(ch),
+        }
+    }
+
+    buf.push('"');
+
+    buf
+}
+
+fn xml_escape(string: &str) -> String {
+    let mut buf = String::with_capacity(string.len());
+
+    for ch in string.chars() {
+        match ch {
+            '&' => buf.push_str("&amp;"),
+            '<' => buf.push_str("&lt;"),
+            '>' => buf.push_str("&gt;"),
+            '"' => buf.push_str("&quot;"),
+            '\'' => buf.push_str("&apos;"),
+            // These control characters aren't allowed in XML 1.0 at all.
+            ch if (ch as u32) < 0x20 && !matches!(ch, '\n' | '\r' | '\t') => {}
+            ch => buf.push(ch),
+        }
+    }
+
+    buf
+}
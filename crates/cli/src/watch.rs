@@ -0,0 +1,153 @@
+//! `--watch`, which runs `roc check`, `roc test` or `roc dev` again whenever a module of the app
+//! (or of its platform) changes.
+
+use bumpalo::Bump;
+use roc_load::ParseCache;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::time::{Duration, SystemTime};
+
+/// How often we look at the modules' modification times
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// The parsed modules in the cache live in the arena, which only grows, so every so often we
+/// start over with a fresh arena and an empty cache.
+const RUNS_PER_ARENA: usize = 64;
+
+/// The process running the program `roc dev --watch` built last, or 0 if there is none.
+static PROGRAM: AtomicI32 = AtomicI32::new(0);
+
+/// Calls `run` now, and again every time a file in the app's module graph changes, until the
+/// user stops us. The modules parsed by one run are reused by the next, unless they changed.
+pub fn watch<F>(roc_file_path: &Path, mut run: F) -> io::Result<i32>
+where
+    F: for<'a> FnMut(&'a Bump, ParseCache<'a>) -> io::Result<i32>,
+{
+    let mut is_first_run = true;
+
+    loop {
+        let arena = Bump::new();
+        let parse_cache = ParseCache::default();
+
+        for _ in 0..RUNS_PER_ARENA {
+            if !is_first_run {
+                stop_program();
+                clear_screen()?;
+            }
+
+            is_first_run = false;
+
+            let run_start = SystemTime::now();
+
+            run(&arena, parse_cache.clone())?;
+
+            // The graph is empty if we couldn't even parse the app's header.
+            let mut files = parse_cache.files();
+
+            if !files.iter().any(|file| file == roc_file_path) {
+                files.push(roc_file_path.to_path_buf());
+            }
+
+            eprintln!(
+                "\nWatching {} {} for changes. Press Ctrl-C to stop.",
+                files.len(),
+                if files.len() == 1 { "file" } else { "files" }
+            );
+
+            wait_for_change(&files, run_start);
+        }
+    }
+}
+
+fn clear_screen() -> io::Result<()> {
+    let mut stdout = io::stdout();
+
+    // Clear the whole screen, then move the cursor to the top left corner.
+    write!(stdout, "\x1B[2J\x1B[H")?;
+
+    stdout.flush()
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Returns once one of the files was modified, created or deleted. If one was modified while
+/// the last run was going on, that run may have missed the change, so we return right away.
+fn wait_for_change(files: &[PathBuf], run_start: SystemTime) {
+    let before: Vec<Option<SystemTime>> = files.iter().map(|file| modified(file)).collect();
+
+    if before
+        .iter()
+        .any(|opt_time| matches!(opt_time, Some(time) if *time > run_start))
+    {
+        return;
+    }
+
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+
+        let changed = files
+            .iter()
+            .zip(before.iter())
+            .any(|(file, opt_time)| modified(file) != *opt_time);
+
+        if changed {
+            return;
+        }
+    }
+}
+
+/// Calls `run` in a child process, so that we can go back to watching for changes while the
+/// program runs. The next change stops it.
+#[cfg(unix)]
+pub fn run_in_background<F>(run: F) -> io::Result<i32>
+where
+    F: FnOnce() -> io::Result<i32>,
+{
+    match unsafe { libc::fork() } {
+        0 => {
+            // we are the child
+            let exit_code = run().unwrap_or(1);
+
+            std::process::exit(exit_code)
+        }
+        -1 => Err(io::Error::last_os_error()),
+        pid => {
+            PROGRAM.store(pid, Ordering::SeqCst);
+
+            Ok(0)
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub fn run_in_background<F>(_run: F) -> io::Result<i32>
+where
+    F: FnOnce() -> io::Result<i32>,
+{
+    roc_error_macros::internal_error!("`roc dev --watch` is only supported on Unix-like systems")
+}
+
+#[cfg(unix)]
+fn stop_program() {
+    let pid = PROGRAM.swap(0, Ordering::SeqCst);
+
+    if pid > 0 {
+        // If the program has already exited, this just reaps the process which ran it.
+        // Otherwise, `roc_run_native_debug` kills the program when it gets this signal.
+        unsafe {
+            libc::kill(pid, libc::SIGTERM);
+            libc::waitpid(pid, std::ptr::null_mut(), 0);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn stop_program() {
+    debug_assert_eq!(PROGRAM.load(Ordering::SeqCst), 0);
+}
@@ -16,7 +16,7 @@ mod cli_run {
     };
     use const_format::concatcp;
     use indoc::indoc;
-    use roc_cli::{CMD_BUILD, CMD_CHECK, CMD_FORMAT, CMD_RUN};
+    use roc_cli::{CMD_BUILD, CMD_CHECK, CMD_FORMAT, CMD_RUN, CMD_TEST};
     use roc_test_utils::assert_multiline_str_eq;
     use serial_test::serial;
     use std::iter;
@@ -27,7 +27,13 @@ mod cli_run {
     const OPTIMIZE_FLAG: &str = concatcp!("--", roc_cli::FLAG_OPTIMIZE);
     const LINKER_FLAG: &str = concatcp!("--", roc_cli::FLAG_LINKER);
     const CHECK_FLAG: &str = concatcp!("--", roc_cli::FLAG_CHECK);
+    const STDIN_FLAG: &str = concatcp!("--", roc_cli::FLAG_STDIN);
+    const LIB_FLAG: &str = concatcp!("--", roc_cli::FLAG_LIB);
+    const DEV_FLAG: &str = concatcp!("--", roc_cli::FLAG_DEV);
     const PRECOMPILED_HOST: &str = concatcp!("--", roc_cli::FLAG_PRECOMPILED, "=true");
+    const FILTER_FLAG: &str = concatcp!("--", roc_cli::FLAG_FILTER);
+    const FORMAT_FLAG: &str = concatcp!("--", roc_cli::FLAG_FORMAT);
+    const ITERATIONS_FLAG: &str = concatcp!("--", roc_cli::FLAG_ITERATIONS);
     #[allow(dead_code)]
     const TARGET_FLAG: &str = concatcp!("--", roc_cli::FLAG_TARGET);
 
@@ -44,12 +50,18 @@ mod cli_run {
     #[cfg(not(debug_assertions))]
     use roc_collections::all::MutMap;
 
-    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    #[cfg(all(
+        any(target_os = "linux", target_os = "macos"),
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
     const TEST_LEGACY_LINKER: bool = true;
 
-    // Surgical linker currently only supports linux x86_64,
+    // Surgical linker currently only supports linux and macOS on x86_64 and aarch64,
     // so we're always testing the legacy linker on other targets.
-    #[cfg(not(all(target_os = "linux", target_arch = "x86_64")))]
+    #[cfg(not(all(
+        any(target_os = "linux", target_os = "macos"),
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    )))]
     const TEST_LEGACY_LINKER: bool = false;
 
     #[cfg(not(target_os = "macos"))]
@@ -741,6 +753,14 @@ mod cli_run {
                 expected_ending: "1 count: 6\n2 count: 22\n",
                 use_valgrind: true,
             },
+            dict_bench => Example {
+                filename: "DictBench.roc",
+                executable_filename: "dict-bench",
+                stdin: &["100"],
+                input_file: None,
+                expected_ending: "100 50 4950\n",
+                use_valgrind: true,
+            },
             rbtree_ck => Example {
                 filename: "RBTreeCk.roc",
                 executable_filename: "rbtree-ck",
@@ -948,6 +968,211 @@ mod cli_run {
         );
     }
 
+    #[test]
+    #[serial(multi_dep_str)]
+    fn run_dbg() {
+        let file = fixture_file("dbg", "Main.roc");
+
+        // once with LLVM, once with the dev backend
+        for flags in [&[][..], &[DEV_FLAG][..]] {
+            let out = run_roc(
+                [CMD_RUN]
+                    .iter()
+                    .chain(flags)
+                    .copied()
+                    .chain(iter::once(file.to_str().unwrap())),
+                &[],
+            );
+
+            assert!(out.status.success(), "bad status {:?}", out);
+            assert!(out.stdout.ends_with("The answer is 42\n"), "{:?}", out);
+            assert!(
+                strip_colors(&out.stderr).contains("Main.roc 7:"),
+                "no dbg output in {:?}",
+                out
+            );
+            assert!(out.stderr.contains(r#""42""#), "no dbg value in {:?}", out);
+        }
+    }
+
+    #[test]
+    #[serial(multi_dep_str)]
+    fn run_dbg_optimized() {
+        // release builds strip `dbg`
+        let out = run_roc(
+            [
+                CMD_RUN,
+                OPTIMIZE_FLAG,
+                fixture_file("dbg", "Main.roc").to_str().unwrap(),
+            ],
+            &[],
+        );
+
+        assert!(out.status.success(), "bad status {:?}", out);
+        assert!(out.stdout.ends_with("The answer is 42\n"), "{:?}", out);
+        assert!(!out.stderr.contains("Main.roc"), "{:?}", out);
+    }
+
+    #[test]
+    #[serial(multi_dep_str)]
+    fn build_strips_dbg() {
+        // Nothing renders the dbg values of a program that `roc build` made, so it must not
+        // wait for anyone to do that.
+        let file = fixture_file("dbg", "Main.roc");
+
+        for flags in [&[][..], &[DEV_FLAG][..]] {
+            let out = run_roc(
+                [CMD_BUILD]
+                    .iter()
+                    .chain(flags)
+                    .copied()
+                    .chain(iter::once(file.to_str().unwrap())),
+                &[],
+            );
+            assert!(out.status.success(), "bad status {:?}", out);
+
+            let out = run_cmd(file.with_file_name("dbg").to_str().unwrap(), [], &[]);
+
+            assert!(out.status.success(), "bad status {:?}", out);
+            assert_eq!(out.stdout, "The answer is 42\n");
+            assert_eq!(out.stderr, "");
+        }
+    }
+
+    fn run_expects_fixture(extra_args: &[&str]) -> Out {
+        let file = fixture_file("expects", "Expects.roc");
+
+        run_roc(
+            iter::once(CMD_TEST)
+                .chain(extra_args.iter().copied())
+                .chain(iter::once(file.to_str().unwrap())),
+            &[],
+        )
+    }
+
+    #[test]
+    fn test_filter_by_enclosing_def() {
+        // `expect 2 + 2 == 4` doesn't mention `double`, but it's written under it.
+        let out = run_expects_fixture(&[FILTER_FLAG, "double", FORMAT_FLAG, "json"]);
+
+        assert!(out.status.success(), "bad status {:?}", out);
+        assert!(out.stdout.contains(r#""passed": 2,"#), "{:?}", out);
+        assert!(out.stdout.contains(r#""failed": 0,"#), "{:?}", out);
+        assert!(out.stdout.contains(r#""name": "Expects:15""#), "{:?}", out);
+        assert!(out.stdout.contains(r#""name": "Expects:17""#), "{:?}", out);
+        assert!(!out.stdout.contains(r#""name": "Expects:8""#), "{:?}", out);
+    }
+
+    #[test]
+    fn test_filter_by_module() {
+        let out = run_expects_fixture(&[FILTER_FLAG, "Expects", FORMAT_FLAG, "json"]);
+
+        assert!(!out.status.success(), "bad status {:?}", out);
+        assert!(out.stdout.contains(r#""passed": 3,"#), "{:?}", out);
+        assert!(out.stdout.contains(r#""failed": 1,"#), "{:?}", out);
+
+        let out = run_expects_fixture(&[FILTER_FLAG, "NotAModuleOrDef", FORMAT_FLAG, "json"]);
+
+        assert!(out.stdout.contains(r#""passed": 0,"#), "{:?}", out);
+        assert!(out.stdout.contains(r#""expects": []"#), "{:?}", out);
+    }
+
+    #[test]
+    fn test_json_report() {
+        let out = run_expects_fixture(&[FILTER_FLAG, "isEven", FORMAT_FLAG, "json"]);
+
+        assert!(!out.status.success(), "bad status {:?}", out);
+        assert!(out.stdout.starts_with("{\n"), "{:?}", out);
+        assert!(out.stdout.contains(r#""passed": 1,"#), "{:?}", out);
+        assert!(out.stdout.contains(r#""failed": 1,"#), "{:?}", out);
+        assert!(out.stdout.contains(r#""module": "Expects","#), "{:?}", out);
+        assert!(
+            out.stdout.contains(r#""region": { "start_line": 10,"#),
+            "{:?}",
+            out
+        );
+        assert!(out.stdout.contains(r#""status": "passed","#), "{:?}", out);
+        assert!(out.stdout.contains(r#""status": "failed","#), "{:?}", out);
+        assert!(out.stdout.contains(r#""failure": null"#), "{:?}", out);
+        assert!(
+            out.stdout.contains("This expectation failed"),
+            "no failure report in {:?}",
+            out
+        );
+        // Machine-readable reports never contain color codes.
+        assert!(!out.stdout.contains('\u{1b}'), "{:?}", out);
+    }
+
+    #[test]
+    fn test_junit_report() {
+        let out = run_expects_fixture(&[FORMAT_FLAG, "junit"]);
+
+        assert!(!out.status.success(), "bad status {:?}", out);
+        assert!(
+            out.stdout
+                .starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#),
+            "{:?}",
+            out
+        );
+        assert!(
+            out.stdout
+                .contains(r#"<testsuites name="roc test" tests="4" failures="1" errors="0""#),
+            "{:?}",
+            out
+        );
+        assert!(
+            out.stdout
+                .contains(r#"<testsuite name="Expects" tests="4" failures="1" errors="0""#),
+            "{:?}",
+            out
+        );
+        assert!(
+            out.stdout
+                .contains(r#"<testcase name="Expects:10" classname="Expects""#),
+            "{:?}",
+            out
+        );
+        assert!(
+            out.stdout.contains(r#"<failure message="expect failed">"#),
+            "{:?}",
+            out
+        );
+        assert_eq!(out.stdout.matches("<testcase ").count(), 4, "{:?}", out);
+        assert!(
+            out.stdout.trim_end().ends_with("</testsuites>"),
+            "{:?}",
+            out
+        );
+    }
+
+    #[test]
+    fn test_rejects_zero_iterations() {
+        let out = run_expects_fixture(&[ITERATIONS_FLAG, "0"]);
+
+        assert!(!out.status.success(), "bad status {:?}", out);
+        assert!(out.stderr.contains("iterations"), "{:?}", out);
+    }
+
+    #[test]
+    #[serial(rust_platform)]
+    fn build_lib_with_cargo_host() {
+        // The surgical linker can't use a Cargo host for a shared library, so this has to fall
+        // back to the legacy linker instead of failing.
+        let out = run_roc(
+            [
+                CMD_BUILD,
+                LIB_FLAG,
+                example_file("platform-switching/rust-platform", "rocLovesRust.roc")
+                    .to_str()
+                    .unwrap(),
+            ],
+            &[],
+        );
+
+        assert!(out.status.success(), "bad status {:?}", out);
+        assert!(!out.stderr.contains("panicked"));
+    }
+
     #[test]
     fn known_type_error() {
         check_compile_error(
@@ -955,7 +1180,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── UNRECOGNIZED NAME ─────────────────────────── tests/known_bad/TypeError.roc ─
+                ── UNRECOGNIZED NAME [E0207] ─────────────────── tests/known_bad/TypeError.roc ─
 
                 Nothing is named `d` in this scope.
 
@@ -983,7 +1208,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── MISSING DEFINITION ────────────────── tests/known_bad/ExposedNotDefined.roc ─
+                ── MISSING DEFINITION [E0103] ────────── tests/known_bad/ExposedNotDefined.roc ─
 
                 bar is listed as exposed, but it isn't defined in this module.
 
@@ -1004,7 +1229,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── UNUSED IMPORT ──────────────────────────── tests/known_bad/UnusedImport.roc ─
+                ── UNUSED IMPORT [E0102] ──────────────────── tests/known_bad/UnusedImport.roc ─
 
                 Nothing from Symbol is used in this module.
 
@@ -1027,7 +1252,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── UNKNOWN GENERATES FUNCTION ─────── tests/known_bad/UnknownGeneratesWith.roc ─
+                ── UNKNOWN GENERATES FUNCTION [E0104] ─ .../known_bad/UnknownGeneratesWith.roc ─
 
                 I don't know how to generate the foobar function.
 
@@ -1062,6 +1287,64 @@ mod cli_run {
         // This doesn't fail, since only "Formatted.roc" and non-roc files are present in this folder
         check_format_check_as_expected(&fixtures_dir("format/formatted_directory"), true);
     }
+
+    #[test]
+    fn format_check_prints_diff() {
+        let file = fixture_file("format", "NotFormatted.roc");
+        let out = run_roc([CMD_FORMAT, file.to_str().unwrap(), CHECK_FLAG], &[]);
+
+        assert!(!out.status.success());
+        assert!(out.stdout.contains("@@"));
+        assert!(out.stdout.contains("-  provides [main] to pf"));
+        assert!(out.stdout.contains("+    provides [main] to pf"));
+        assert!(out.stderr.contains("These files need to be reformatted:"));
+        assert!(out.stderr.contains("NotFormatted.roc"));
+    }
+
+    #[test]
+    fn format_stdin() {
+        let formatted = std::fs::read_to_string(fixture_file("format", "Formatted.roc")).unwrap();
+        let out = run_roc([CMD_FORMAT, STDIN_FLAG], &[&formatted]);
+
+        assert!(out.status.success());
+        assert_multiline_str_eq!(out.stdout.as_str(), formatted.as_str());
+
+        let not_formatted =
+            std::fs::read_to_string(fixture_file("format", "NotFormatted.roc")).unwrap();
+        let out = run_roc([CMD_FORMAT, STDIN_FLAG], &[&not_formatted]);
+
+        assert!(out.status.success());
+        assert!(out.stdout.contains("\n    provides [main] to pf\n"));
+    }
+
+    #[test]
+    fn format_stdin_check() {
+        let not_formatted =
+            std::fs::read_to_string(fixture_file("format", "NotFormatted.roc")).unwrap();
+        let out = run_roc([CMD_FORMAT, STDIN_FLAG, CHECK_FLAG], &[&not_formatted]);
+
+        assert!(!out.status.success());
+        assert!(out.stdout.contains("+    provides [main] to pf"));
+        assert!(out
+            .stderr
+            .contains("The code from stdin needs to be reformatted."));
+    }
+
+    #[test]
+    fn format_syntax_error() {
+        let src = indoc!(
+            r#"
+                interface Main exposes [main] imports []
+
+                main = [
+            "#
+        );
+        let out = run_roc([CMD_FORMAT, STDIN_FLAG], &[src]);
+
+        assert!(!out.status.success());
+        assert!(out.stdout.is_empty());
+        assert!(strip_colors(&out.stderr).contains("UNFINISHED LIST"));
+    }
 }
 
 #[allow(dead_code)]
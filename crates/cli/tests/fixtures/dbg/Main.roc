@@ -0,0 +1,7 @@
+app "dbg"
+    packages { pf: "../multi-dep-str/platform/main.roc" }
+    imports []
+    provides [main] to pf
+
+main : Str
+main = Str.concat "The answer is " (dbg (Num.toStr 42))
@@ -0,0 +1,17 @@
+interface Expects
+    exposes [isEven, double]
+    imports []
+
+isEven : I64 -> Bool
+isEven = \n -> n % 2 == 0
+
+expect isEven 4
+
+expect isEven 3
+
+double : I64 -> I64
+double = \n -> n * 2
+
+expect double 2 == 4
+
+expect 2 + 2 == 4
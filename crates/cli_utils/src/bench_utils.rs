@@ -137,6 +137,16 @@ pub fn bench_deriv<T: Measurement>(bench_group_opt: Option<&mut BenchmarkGroup<T
     );
 }
 
+pub fn bench_dict<T: Measurement>(bench_group_opt: Option<&mut BenchmarkGroup<T>>) {
+    exec_bench_w_input(
+        &example_file("benchmarks", "DictBench.roc"),
+        "100000",
+        "dict-bench",
+        "100000 50000 4999950000\n",
+        bench_group_opt,
+    );
+}
+
 pub fn bench_rbtree_ck<T: Measurement>(bench_group_opt: Option<&mut BenchmarkGroup<T>>) {
     exec_bench_w_input(
         &example_file("benchmarks", "RBTreeCk.roc"),
@@ -113,6 +113,7 @@ pub fn build_zig_host_native(
     target: &str,
     opt_level: OptLevel,
     shared_lib_path: Option<&Path>,
+    link_type: LinkType,
 ) -> Output {
     let mut command = Command::new(&zig_executable());
     command
@@ -121,9 +122,11 @@ pub fn build_zig_host_native(
         .env("HOME", env_home);
 
     if let Some(shared_lib_path) = shared_lib_path {
+        match link_type {
+            LinkType::Dylib => command.args(&["build-lib", "-dynamic", "-fPIC"]),
+            _ => command.args(&["build-exe", "-fPIE"]),
+        };
         command.args(&[
-            "build-exe",
-            "-fPIE",
             shared_lib_path.to_str().unwrap(),
             &bitcode::get_builtins_host_obj_path(),
         ]);
@@ -173,6 +176,7 @@ pub fn build_zig_host_native(
     target: &str,
     opt_level: OptLevel,
     shared_lib_path: Option<&Path>,
+    link_type: LinkType,
 ) -> Output {
     let mut command = Command::new(&zig_executable());
     command
@@ -181,9 +185,11 @@ pub fn build_zig_host_native(
         .env("HOME", env_home);
 
     if let Some(shared_lib_path) = shared_lib_path {
+        match link_type {
+            LinkType::Dylib => command.args(&["build-lib", "-dynamic", "-fPIC"]),
+            _ => command.args(&["build-exe", "-fPIE"]),
+        };
         command.args(&[
-            "build-exe",
-            "-fPIE",
             shared_lib_path.to_str().unwrap(),
             &bitcode::get_builtins_host_obj_path(),
         ]);
@@ -228,6 +234,7 @@ pub fn build_zig_host_native(
     _target: &str,
     opt_level: OptLevel,
     shared_lib_path: Option<&Path>,
+    link_type: LinkType,
     // For compatibility with the non-macOS def above. Keep these in sync.
 ) -> Output {
     use serde_json::Value;
@@ -279,9 +286,11 @@ pub fn build_zig_host_native(
         .env("PATH", &env_path)
         .env("HOME", &env_home);
     if let Some(shared_lib_path) = shared_lib_path {
+        match link_type {
+            LinkType::Dylib => command.args(&["build-lib", "-dynamic", "-fPIC"]),
+            _ => command.args(&["build-exe", "-fPIE"]),
+        };
         command.args(&[
-            "build-exe",
-            "-fPIE",
             shared_lib_path.to_str().unwrap(),
             &bitcode::get_builtins_host_obj_path(),
         ]);
@@ -375,14 +384,23 @@ pub fn build_zig_host_wasm32(
     command.output().unwrap()
 }
 
+fn is_macos(target: &Triple) -> bool {
+    matches!(
+        target.operating_system,
+        OperatingSystem::Darwin | OperatingSystem::MacOSX { .. }
+    )
+}
+
 pub fn build_c_host_native(
     env_path: &str,
     env_home: &str,
     env_cpath: &str,
+    target: &Triple,
     dest: &str,
     sources: &[&str],
     opt_level: OptLevel,
     shared_lib_path: Option<&Path>,
+    link_type: LinkType,
 ) -> Output {
     let mut command = Command::new("clang");
     command
@@ -396,14 +414,19 @@ pub fn build_c_host_native(
         command.args(&[
             shared_lib_path.to_str().unwrap(),
             &bitcode::get_builtins_host_obj_path(),
-            "-fPIE",
-            "-pie",
-            "-lm",
-            "-lpthread",
-            "-ldl",
-            "-lrt",
-            "-lutil",
         ]);
+        match link_type {
+            LinkType::Dylib => command.args(&["-shared", "-fPIC"]),
+            _ => command.args(&["-fPIE", "-pie"]),
+        };
+        command.args(&["-lm", "-lpthread", "-ldl", "-lutil"]);
+
+        if is_macos(target) {
+            // Leave room after the load commands for the surgical linker to add the app's segments.
+            command.arg("-Wl,-headerpad,0x1000");
+        } else {
+            command.arg("-lrt");
+        }
     } else {
         command.args(&["-fPIC", "-c"]);
     }
@@ -452,11 +475,14 @@ pub fn build_swift_host_native(
     command.output().unwrap()
 }
 
+/// Builds the host. With a `shared_lib_path`, the host is built as a `link_type` that dynamically
+/// links the dummy app at that path, ready for the surgical linker; otherwise, as an object file.
 pub fn rebuild_host(
     opt_level: OptLevel,
     target: &Triple,
     host_input_path: &Path,
     shared_lib_path: Option<&Path>,
+    link_type: LinkType,
 ) -> PathBuf {
     let c_host_src = host_input_path.with_file_name("host.c");
     let c_host_dest = host_input_path.with_file_name("c_host.o");
@@ -528,6 +554,7 @@ pub fn rebuild_host(
                     "native",
                     opt_level,
                     shared_lib_path,
+                    link_type,
                 )
             }
             Architecture::X86_32(_) => {
@@ -541,6 +568,7 @@ pub fn rebuild_host(
                     "i386-linux-musl",
                     opt_level,
                     shared_lib_path,
+                    link_type,
                 )
             }
 
@@ -555,6 +583,7 @@ pub fn rebuild_host(
                     target_zig_str(target),
                     opt_level,
                     shared_lib_path,
+                    link_type,
                 )
             }
             _ => panic!("Unsupported architecture {:?}", target.architecture),
@@ -579,7 +608,20 @@ pub fn rebuild_host(
             command.arg("--release");
         }
         let source_file = if shared_lib_path.is_some() {
-            command.env("RUSTFLAGS", "-C link-dead-code");
+            if link_type == LinkType::Dylib {
+                internal_error!(
+                    "Cargo hosts can't be built as shared libraries; roc_linker::supported_host should have picked the legacy linker"
+                );
+            }
+            if is_macos(target) {
+                // Leave room after the load commands for the surgical linker to add the app's segments.
+                command.env(
+                    "RUSTFLAGS",
+                    "-C link-dead-code -C link-arg=-Wl,-headerpad,0x1000",
+                );
+            } else {
+                command.env("RUSTFLAGS", "-C link-dead-code");
+            }
             command.args(&["--bin", "host"]);
             "src/main.rs"
         } else {
@@ -600,10 +642,12 @@ pub fn rebuild_host(
                 &env_path,
                 &env_home,
                 &env_cpath,
+                target,
                 c_host_dest.to_str().unwrap(),
                 &[c_host_src.to_str().unwrap()],
                 opt_level,
                 shared_lib_path,
+                link_type,
             );
             validate_output("host.c", "clang", output);
 
@@ -652,6 +696,7 @@ pub fn rebuild_host(
                 &env_path,
                 &env_home,
                 &env_cpath,
+                target,
                 host_dest.to_str().unwrap(),
                 &[
                     c_host_src.to_str().unwrap(),
@@ -659,6 +704,7 @@ pub fn rebuild_host(
                 ],
                 opt_level,
                 shared_lib_path,
+                link_type,
             );
             validate_output("host.c", "clang", output);
         } else {
@@ -666,10 +712,12 @@ pub fn rebuild_host(
                 &env_path,
                 &env_home,
                 &env_cpath,
+                target,
                 c_host_dest.to_str().unwrap(),
                 &[c_host_src.to_str().unwrap()],
                 opt_level,
                 shared_lib_path,
+                link_type,
             );
 
             validate_output("host.c", "clang", output);
@@ -702,10 +750,12 @@ pub fn rebuild_host(
             &env_path,
             &env_home,
             &env_cpath,
+            target,
             host_dest.to_str().unwrap(),
             &[c_host_src.to_str().unwrap()],
             opt_level,
             shared_lib_path,
+            link_type,
         );
         validate_output("host.c", "clang", output);
     } else if swift_host_src.exists() {
@@ -1258,6 +1308,69 @@ pub fn llvm_module_to_dylib(
     unsafe { Library::new(path) }
 }
 
+/// Why an object file could not be turned into a dylib that we can load
+#[derive(Debug)]
+pub enum DylibError {
+    /// The object file could not be linked
+    Link(String),
+    /// The dylib was made, but could not be loaded
+    Load(Error),
+}
+
+impl std::fmt::Display for DylibError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DylibError::Link(message) => write!(f, "linking failed: {}", message),
+            DylibError::Load(error) => write!(f, "loading the dylib failed: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for DylibError {}
+
+/// Link an object file made by the dev backend into a dylib, together with the zig builtins it calls.
+pub fn dev_object_to_dylib(target: &Triple, app_o_bytes: &[u8]) -> Result<Library, DylibError> {
+    // The builtins are only built for the host, and we can only load a dylib for the host anyway.
+    if *target != Triple::host() {
+        return Err(DylibError::Link(format!(
+            "the builtins are built for {}, not for {}",
+            Triple::host(),
+            target
+        )));
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let app_o_file = dir.path().join("app.o");
+
+    std::fs::write(&app_o_file, app_o_bytes).expect("Writing .o file failed");
+
+    let (mut child, dylib_path) = link(
+        target,
+        app_o_file.clone(),
+        &[
+            app_o_file.to_str().unwrap(),
+            &bitcode::get_builtins_host_obj_path(),
+        ],
+        LinkType::Dylib,
+    )
+    .map_err(|error| DylibError::Link(format!("the linker could not be started: {}", error)))?;
+
+    let status = child
+        .wait()
+        .map_err(|error| DylibError::Link(format!("waiting for the linker failed: {}", error)))?;
+
+    if !status.success() {
+        return Err(DylibError::Link(format!(
+            "the linker exited with {}",
+            status
+        )));
+    }
+
+    let path = dylib_path.as_path().to_str().unwrap();
+
+    unsafe { Library::new(path) }.map_err(DylibError::Load)
+}
+
 pub fn preprocess_host_wasm32(host_input_path: &Path, preprocessed_host_path: &Path) {
     let host_input = host_input_path.to_str().unwrap();
     let output_file = preprocessed_host_path.to_str().unwrap();
@@ -5,6 +5,7 @@ use roc_load::{EntryPoint, LoadedModule, MonomorphizedModule};
 use roc_module::symbol::{Interns, ModuleId};
 use roc_mono::ir::OptLevel;
 use roc_region::all::LineInfo;
+use roc_reporting::structured::ErrorFormat;
 use roc_solve_problem::TypeError;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
@@ -19,23 +20,31 @@ pub struct CodeGenTiming {
     pub emit_o_file: Duration,
 }
 
-pub fn report_problems_monomorphized(loaded: &mut MonomorphizedModule) -> Problems {
+pub fn report_problems_monomorphized(
+    loaded: &mut MonomorphizedModule,
+    error_format: Option<ErrorFormat>,
+) -> Problems {
     report_problems_help(
         loaded.total_problems(),
         &loaded.sources,
         &loaded.interns,
         &mut loaded.can_problems,
         &mut loaded.type_problems,
+        error_format,
     )
 }
 
-pub fn report_problems_typechecked(loaded: &mut LoadedModule) -> Problems {
+pub fn report_problems_typechecked(
+    loaded: &mut LoadedModule,
+    error_format: Option<ErrorFormat>,
+) -> Problems {
     report_problems_help(
         loaded.total_problems(),
         &loaded.sources,
         &loaded.interns,
         &mut loaded.can_problems,
         &mut loaded.type_problems,
+        error_format,
     )
 }
 
@@ -62,7 +71,18 @@ fn report_problems_help(
     interns: &Interns,
     can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
     type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+    error_format: Option<ErrorFormat>,
 ) -> Problems {
+    if let Some(error_format) = error_format {
+        return report_problems_structured(
+            sources,
+            interns,
+            can_problems,
+            type_problems,
+            error_format,
+        );
+    }
+
     use roc_reporting::report::{
         can_problem, type_problem, Report, RocDocAllocator, Severity::*, DEFAULT_PALETTE,
     };
@@ -156,6 +176,53 @@ fn report_problems_help(
     }
 }
 
+/// Prints every problem, including warnings, as one JSON or SARIF document.
+fn report_problems_structured(
+    sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    interns: &Interns,
+    can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
+    type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+    error_format: ErrorFormat,
+) -> Problems {
+    use roc_reporting::report::{RocDocAllocator, Severity};
+    use roc_reporting::structured::{can_diagnostic, render, type_diagnostic};
+
+    let mut diagnostics = Vec::new();
+
+    for (home, (module_path, src)) in sources.iter() {
+        let src_lines: Vec<&str> = src.split('\n').collect();
+        let lines = LineInfo::new(src);
+        let alloc = RocDocAllocator::new(&src_lines, *home, interns);
+
+        for problem in can_problems.remove(home).unwrap_or_default() {
+            diagnostics.push(can_diagnostic(&alloc, &lines, module_path.clone(), problem));
+        }
+
+        for problem in type_problems.remove(home).unwrap_or_default() {
+            diagnostics.extend(type_diagnostic(
+                &alloc,
+                &lines,
+                module_path.clone(),
+                problem,
+            ));
+        }
+    }
+
+    print!("{}", render(error_format, &diagnostics));
+
+    let count = |severity| {
+        diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    };
+
+    Problems {
+        errors: count(Severity::RuntimeError),
+        warnings: count(Severity::Warning),
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn gen_from_mono_module(
     arena: &bumpalo::Bump,
@@ -165,9 +232,14 @@ pub fn gen_from_mono_module(
     app_o_file: &Path,
     opt_level: OptLevel,
     emit_debug_info: bool,
+    run_by_roc: bool,
     preprocessed_host_path: &Path,
     wasm_dev_stack_bytes: Option<u32>,
 ) -> CodeGenTiming {
+    // Unless the `roc` process that builds the program also runs it, nobody renders the expect
+    // failures and dbg values, so they are stripped.
+    let renders_in_parent = run_by_roc && can_render_in_parent(target);
+
     match opt_level {
         OptLevel::Normal | OptLevel::Size | OptLevel::Optimize => gen_from_mono_module_llvm(
             arena,
@@ -177,18 +249,27 @@ pub fn gen_from_mono_module(
             app_o_file,
             opt_level,
             emit_debug_info,
+            renders_in_parent,
         ),
         OptLevel::Development => gen_from_mono_module_dev(
             arena,
             loaded,
             target,
             app_o_file,
+            renders_in_parent,
             preprocessed_host_path,
             wasm_dev_stack_bytes,
         ),
     }
 }
 
+/// Whether a `roc` process that runs the program can render its `expect` failures and `dbg`
+/// values. It shares memory with the program, which is only possible on 64-bit unix targets.
+fn can_render_in_parent(target: &target_lexicon::Triple) -> bool {
+    target.pointer_width() == Ok(target_lexicon::PointerWidth::U64)
+        && target.operating_system != target_lexicon::OperatingSystem::Windows
+}
+
 // TODO how should imported modules factor into this? What if those use builtins too?
 // TODO this should probably use more helper functions
 // TODO make this polymorphic in the llvm functions so it can be reused for another backend.
@@ -200,6 +281,7 @@ pub fn gen_from_mono_module_llvm(
     app_o_file: &Path,
     opt_level: OptLevel,
     emit_debug_info: bool,
+    renders_in_parent: bool,
 ) -> CodeGenTiming {
     use crate::target::{self, convert_opt_level};
     use inkwell::attributes::{Attribute, AttributeLoc};
@@ -253,6 +335,13 @@ pub fn gen_from_mono_module_llvm(
     let (dibuilder, compile_unit) = roc_gen_llvm::llvm::build::Env::new_debug_info(module);
     let (mpm, _fpm) = roc_gen_llvm::llvm::build::construct_optimization_passes(module, opt_level);
 
+    // Release builds strip `expect` and `dbg`. Otherwise, they are rendered by the `roc` process
+    // that runs the program.
+    let mode = match opt_level {
+        OptLevel::Normal if renders_in_parent => LlvmBackendMode::BinaryDev,
+        _ => LlvmBackendMode::Binary,
+    };
+
     // Compile and add all the Procs before adding main
     let env = roc_gen_llvm::llvm::build::Env {
         arena,
@@ -263,7 +352,7 @@ pub fn gen_from_mono_module_llvm(
         interns: loaded.interns,
         module,
         target_info,
-        mode: LlvmBackendMode::Binary,
+        mode,
         exposed_to_host: loaded.exposed_to_host.values.keys().copied().collect(),
     };
 
@@ -421,6 +510,7 @@ pub fn gen_from_mono_module_dev(
     loaded: MonomorphizedModule,
     target: &target_lexicon::Triple,
     app_o_file: &Path,
+    renders_in_parent: bool,
     preprocessed_host_path: &Path,
     wasm_dev_stack_bytes: Option<u32>,
 ) -> CodeGenTiming {
@@ -435,7 +525,7 @@ pub fn gen_from_mono_module_dev(
             wasm_dev_stack_bytes,
         ),
         Architecture::X86_64 | Architecture::Aarch64(_) => {
-            gen_from_mono_module_dev_assembly(arena, loaded, target, app_o_file)
+            gen_from_mono_module_dev_assembly(arena, loaded, target, app_o_file, renders_in_parent)
         }
         _ => todo!(),
     }
@@ -447,6 +537,7 @@ pub fn gen_from_mono_module_dev(
     loaded: MonomorphizedModule,
     target: &target_lexicon::Triple,
     app_o_file: &Path,
+    renders_in_parent: bool,
     _host_input_path: &Path,
     _wasm_dev_stack_bytes: Option<u32>,
 ) -> CodeGenTiming {
@@ -454,7 +545,7 @@ pub fn gen_from_mono_module_dev(
 
     match target.architecture {
         Architecture::X86_64 | Architecture::Aarch64(_) => {
-            gen_from_mono_module_dev_assembly(arena, loaded, target, app_o_file)
+            gen_from_mono_module_dev_assembly(arena, loaded, target, app_o_file, renders_in_parent)
         }
         _ => todo!(),
     }
@@ -534,6 +625,7 @@ fn gen_from_mono_module_dev_assembly(
     loaded: MonomorphizedModule,
     target: &target_lexicon::Triple,
     app_o_file: &Path,
+    renders_in_parent: bool,
 ) -> CodeGenTiming {
     let code_gen_start = Instant::now();
 
@@ -548,12 +640,19 @@ fn gen_from_mono_module_dev_assembly(
         ..
     } = loaded;
 
+    let mode = if renders_in_parent {
+        roc_gen_dev::AssemblyBackendMode::BinaryDev
+    } else {
+        roc_gen_dev::AssemblyBackendMode::Binary
+    };
+
     let env = roc_gen_dev::Env {
         arena,
         module_id,
         exposed_to_host: exposed_to_host.values.keys().copied().collect(),
         lazy_literals,
         generate_allocators,
+        mode,
     };
 
     let module_object = roc_gen_dev::build_module(&env, &mut interns, target, procedures);
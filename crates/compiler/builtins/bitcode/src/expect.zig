@@ -4,17 +4,19 @@ extern fn shm_open(name: *const i8, oflag: c_int, mode: c_uint) c_int;
 extern fn mmap(addr: ?*anyopaque, length: c_uint, prot: c_int, flags: c_int, fd: c_int, offset: c_uint) *anyopaque;
 extern fn kill(pid: c_int, sig: c_int) c_int;
 extern fn getppid() c_int;
+extern fn getenv(name: [*:0]const u8) ?[*:0]const u8;
 
 const SIGUSR1: c_int = 10;
+const SIGUSR2: c_int = 12;
 
 const O_RDWR: c_int = 2;
 const O_CREAT: c_int = 64;
 
+pub const PROT_READ: c_int = 1;
 pub const PROT_WRITE: c_int = 2;
 pub const MAP_SHARED: c_int = 0x0001;
 
-// IMPORTANT: shared memory object names must begin with / and contain no other slashes!
-var SHARED_BUFFER: []u8 = undefined;
+var SHARED_BUFFER: []u8 = &[_]u8{};
 
 pub fn setSharedBuffer(ptr: [*]u8, length: usize) callconv(.C) usize {
     SHARED_BUFFER = ptr[0..length];
@@ -27,8 +29,92 @@ pub fn expectFailedStart() callconv(.C) [*]u8 {
     return SHARED_BUFFER.ptr;
 }
 
+/// Writes past this many bytes into the buffer are skipped. A frame which doesn't fit is dropped,
+/// and the offset in the buffer's state is left past the end of the buffer to say so.
+pub fn expectBufferCapacity() callconv(.C) usize {
+    return SHARED_BUFFER.len;
+}
+
+// must match SHM_SIZE in the cli
+const SHARED_FILE_LENGTH: usize = 64 * 1024;
+
+// must match EXPECT_BUFFER_ENV_VAR in the cli
+const SHARED_FILE_ENV_VAR: [*:0]const u8 = "ROC_EXPECT_BUFFER";
+
+var SHARED_FILE_OPENED: bool = false;
+var PARENT_LISTENS: bool = false;
+var FALLBACK_BUFFER: [SHARED_FILE_LENGTH]u8 align(@alignOf(usize)) = undefined;
+
+/// Used when expects and dbgs are rendered by the `roc` process that started this program. That
+/// process creates a shared memory object before starting us, and puts its name in our
+/// environment. If the name is not there, we were started some other way, and write our frames
+/// into a buffer that nobody reads.
+pub fn expectFailedStartSharedFile() callconv(.C) [*]u8 {
+    if (!SHARED_FILE_OPENED) {
+        SHARED_FILE_OPENED = true;
+        openSharedFile();
+    }
+
+    // the parent renders every frame before we continue, so each frame is the first one
+    const state = @ptrCast([*]usize, @alignCast(@alignOf(usize), SHARED_BUFFER.ptr));
+    state[0] = 0;
+    state[1] = 2 * @sizeOf(usize);
+
+    return SHARED_BUFFER.ptr;
+}
+
+fn openSharedFile() void {
+    const name = getenv(SHARED_FILE_ENV_VAR) orelse {
+        SHARED_BUFFER = FALLBACK_BUFFER[0..];
+        return;
+    };
+
+    const shared_fd = shm_open(@ptrCast(*const i8, name), O_RDWR, 0o666);
+
+    if (shared_fd < 0) {
+        SHARED_BUFFER = FALLBACK_BUFFER[0..];
+        return;
+    }
+
+    const shared_ptr = mmap(
+        null,
+        SHARED_FILE_LENGTH,
+        PROT_READ | PROT_WRITE,
+        MAP_SHARED,
+        shared_fd,
+        0,
+    );
+
+    SHARED_BUFFER = @ptrCast([*]u8, shared_ptr)[0..SHARED_FILE_LENGTH];
+    PARENT_LISTENS = true;
+}
+
 pub fn expectFailedFinalize() callconv(.C) void {
+    notifyParent(SIGUSR1);
+}
+
+pub fn notifyParentDbg() callconv(.C) void {
+    notifyParent(SIGUSR2);
+}
+
+fn notifyParent(signal: c_int) void {
+    if (!PARENT_LISTENS) {
+        return;
+    }
+
+    const state = @ptrCast([*]usize, @alignCast(@alignOf(usize), SHARED_BUFFER.ptr));
+
+    // a frame that didn't fit was dropped without being counted, but the parent still reads
+    // the state to find that out, so we must wait for it all the same
+    if (state[1] > SHARED_BUFFER.len) {
+        @atomicStore(usize, &state[0], 1, .SeqCst);
+    }
+
     const parent_pid = getppid();
 
-    _ = kill(parent_pid, SIGUSR1);
+    _ = kill(parent_pid, signal);
+
+    // the parent resets the frame count once it has rendered the frame; wait for that so we
+    // don't overwrite the frame while it is being read
+    while (@atomicLoad(usize, &state[0], .SeqCst) != 0) {}
 }
@@ -16,7 +16,9 @@ pub fn wyhash(seed: u64, bytes: ?[*]const u8, length: usize) callconv(.C) u64 {
     }
 }
 
-pub fn wyhash_rocstr(seed: u64, input: str.RocStr) callconv(.C) u64 {
+// The string comes first, so that code gen can pass it the same way it passes
+// strings to all other `str.*` builtins.
+pub fn wyhash_rocstr(input: str.RocStr, seed: u64) callconv(.C) u64 {
     return wyhash_hash(seed, input.asSlice());
 }
 
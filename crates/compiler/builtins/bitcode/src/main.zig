@@ -55,6 +55,14 @@ comptime {
     exportListFn(list.listIsUnique, "is_unique");
 }
 
+// Dict Module
+const hash = @import("hash.zig");
+
+comptime {
+    exportDictFn(hash.wyhash, "hash");
+    exportDictFn(hash.wyhash_rocstr, "hash_str");
+}
+
 // Num Module
 const num = @import("num.zig");
 
@@ -168,8 +176,15 @@ comptime {
 
     if (builtin.target.cpu.arch != .wasm32) {
         exportUtilsFn(expect.expectFailedStart, "expect_failed_start");
+        exportUtilsFn(expect.expectBufferCapacity, "expect_buffer_capacity");
         exportUtilsFn(expect.expectFailedFinalize, "expect_failed_finalize");
 
+        if (builtin.os.tag != .windows) {
+            // used when a `roc` process renders expect failures and dbg values for us
+            exportUtilsFn(expect.expectFailedStartSharedFile, "expect_failed_start_shared_file");
+            exportUtilsFn(expect.notifyParentDbg, "notify_parent_dbg");
+        }
+
         // sets the buffer used for expect failures
         @export(expect.setSharedBuffer, .{ .name = "set_shared_buffer", .linkage = .Weak });
     }
@@ -1,5 +1,6 @@
 const utils = @import("utils.zig");
 const RocList = @import("list.zig").RocList;
+const expect_buffer = @import("expect.zig");
 const UpdateMode = utils.UpdateMode;
 const std = @import("std");
 const mem = std.mem;
@@ -2614,12 +2615,18 @@ pub fn strCloneTo(
     extra_offset: usize,
 ) callconv(.C) usize {
     const WIDTH: usize = @sizeOf(RocStr);
+
+    // this writes into the expect buffer, so skip whatever doesn't fit; see expectBufferCapacity
+    const capacity = expect_buffer.expectBufferCapacity();
+
     if (string.isSmallStr()) {
         const array: [@sizeOf(RocStr)]u8 = @bitCast([@sizeOf(RocStr)]u8, string);
 
-        var i: usize = 0;
-        while (i < WIDTH) : (i += 1) {
-            ptr[offset + i] = array[i];
+        if (offset + WIDTH <= capacity) {
+            var i: usize = 0;
+            while (i < WIDTH) : (i += 1) {
+                ptr[offset + i] = array[i];
+            }
         }
 
         return extra_offset;
@@ -2630,11 +2637,15 @@ pub fn strCloneTo(
         relative.str_bytes = @intToPtr(?[*]u8, extra_offset); // i.e. just after the string struct
 
         // write the string struct
-        const array = relative.asArray();
-        @memcpy(ptr + offset, &array, WIDTH);
+        if (offset + WIDTH <= capacity) {
+            const array = relative.asArray();
+            @memcpy(ptr + offset, &array, WIDTH);
+        }
 
         // write the string bytes just after the struct
-        @memcpy(ptr + extra_offset, slice.ptr, slice.len);
+        if (extra_offset + slice.len <= capacity) {
+            @memcpy(ptr + extra_offset, slice.ptr, slice.len);
+        }
 
         return extra_offset + slice.len;
     }
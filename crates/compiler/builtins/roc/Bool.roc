@@ -1,7 +1,30 @@
 interface Bool
-    exposes [Bool, and, or, not, isEq, isNotEq]
+    exposes [Bool, Eq, isEq, and, or, not, isNotEq, structuralEq, structuralNotEq]
     imports []
 
+## Defines a type that can be compared for equality with [isEq], or `==`.
+##
+## `a == b` is shorthand for `Bool.isEq a b`
+##
+## Records, tag unions, and builtin types other than functions implement [Eq]
+## automatically, and opaque types can derive it with `has [Eq]`. Unless a type
+## provides a custom implementation of [Eq], equality is *structural*, and works
+## as follows:
+##
+## 1. Tags are equal if they have the same tag name, and also their contents (if any) are equal.
+## 2. Records are equal if all their fields are equal.
+## 3. [Str] and [List] values are equal if they are the same length, and also all their corresponding elements are equal.
+## 4. [Num](Num#Num) values are equal if their numbers are equal, with one exception: if both arguments to `isEq` are *NaN*, then `isEq` returns `False`. See `Num.isNaN` for more about *NaN*.
+##
+## [Dict] and [Set] have custom implementations that ignore the order of their entries.
+## Any other opaque type can provide a custom implementation, like
+## `has [Eq { isEq: myIsEq }]`, where `myIsEq : MyType, MyType -> Bool`.
+##
+## Functions cannot be compared for equality, so comparing values containing
+## functions is a type error.
+Eq has
+    isEq : a, a -> Bool | a has Eq
+
 Bool : [True, False]
 
 ## Returns `True` when given `True` and `True`, and `False` when either argument is `False`.
@@ -59,25 +82,17 @@ or : Bool, Bool -> Bool
 ## Returns `False` when given `True`, and vice versa.
 not : Bool -> Bool
 
-## Returns `True` if the two values are *structurally equal*, and `False` otherwise.
-##
-## `a == b` is shorthand for `Bool.isEq a b`
-##
-## Structural equality works as follows:
-##
-## 1. Tags are equal if they have the same tag name, and also their contents (if any) are equal.
-## 2. Records are equal if all their fields are equal.
-## 3. Collections ([Str], [List], [Dict], and [Set]) are equal if they are the same length, and also all their corresponding elements are equal.
-## 4. [Num](Num#Num) values are equal if their numbers are equal, with one exception: if both arguments to `isEq` are *NaN*, then `isEq` returns `False`. See `Num.isNaN` for more about *NaN*.
-##
-## Note that `isEq` takes `'val` instead of `val`, which means `isEq` does not
-## accept arguments whose types contain functions.
-isEq : a, a -> Bool
-
 ## Calls [isEq] on the given values, then calls [not] on the result.
 ##
 ## `a != b` is shorthand for `Bool.isNotEq a b`
 ##
-## Note that `isNotEq` takes `'val` instead of `val`, which means `isNotEq` does not
-## accept arguments whose types contain functions.
-isNotEq : a, a -> Bool
+## Note that types containing functions do not implement [Eq], so they cannot
+## be passed to `isNotEq`.
+isNotEq : a, a -> Bool | a has Eq
+isNotEq = \a, b -> not (isEq a b)
+
+# INTERNAL COMPILER USE ONLY: derived implementations of [isEq] are lowered to
+# these, which compare their arguments structurally. They are exposed only so
+# derived implementations and Dict can use them; other modules can't refer to them.
+structuralEq : a, a -> Bool
+structuralNotEq : a, a -> Bool
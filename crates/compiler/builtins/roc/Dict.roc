@@ -63,118 +63,294 @@ interface Dict
 ## [Dict.remove] does: it removes an element and moves the most recent insertion into the vacated spot.
 ##
 ## This move is done as a performance optimization, and it lets [remove] have
-## [constant time complexity](https://en.wikipedia.org/wiki/Time_complexity#Constant_time). ##
+## [constant time complexity](https://en.wikipedia.org/wiki/Time_complexity#Constant_time).
+##
+## ### Performance
+##
+## Under the hood, a dictionary is a hash table using open addressing with linear probing.
+## The entries themselves are stored in a [List] in insertion order, and a second [List]
+## of buckets maps each key's hash to its position in that list. This makes [get], [insert],
+## [remove] and [contains] take constant time on average, while [walk], [keys] and [values]
+## are as fast as iterating over a [List].
+##
+## ### Keys
+##
+## Keys are hashed and compared by their structure, even if their type has a custom
+## implementation of [Eq]. For example, if an opaque type's `isEq` ignores case, the keys
+## `@CaseInsensitive "a"` and `@CaseInsensitive "A"` are still two different keys.
 ##
 ## ### Equality
 ##
-## When comparing two dictionaries for equality, they are `==` only if their both their contents and their
-## orderings match. This preserves the property that if `dict1 == dict2`, you should be able to rely on
-## `fn dict1 == fn dict2` also being `True`, even if `fn` relies on the dictionary's ordering.
-Dict k v := List [Pair k v]
+## Two dictionaries are `==` if they have the same keys, and each key is associated with
+## equal values in both. The order in which entries were inserted does not matter.
+Dict k v := {
+    buckets : List Nat,
+    data : List [Pair k v],
+} has [Eq { isEq }]
+
+isEq : Dict k v, Dict k v -> Bool | k has Eq, v has Eq
+isEq = \xs, @Dict { data } ->
+    if len xs != List.len data then
+        False
+    else
+        List.all data \Pair k v ->
+            when get xs k is
+                Ok found -> found == v
+                Err KeyNotFound -> False
 
 ## An empty dictionary.
 empty : Dict k v
-empty = @Dict []
+empty = @Dict { buckets: [], data: [] }
 
+## Returns a dictionary with room for `n` entries before it needs to grow.
 withCapacity : Nat -> Dict k v
-withCapacity = \n -> @Dict (List.withCapacity n)
+withCapacity = \n ->
+    if n == 0 then
+        empty
+    else
+        @Dict { buckets: List.repeat 0 (bucketCountFor n 8), data: List.withCapacity n }
 
-get : Dict k v, k -> Result v [KeyNotFound]*
-get = \@Dict list, needle ->
-    when List.findFirst list (\Pair key _ -> key == needle) is
-        Ok (Pair _ v) ->
-            Ok v
+get : Dict k v, k -> Result v [KeyNotFound]* | k has Eq
+get = \@Dict { buckets, data }, needle ->
+    if List.isEmpty buckets then
+        Err KeyNotFound
+    else
+        when probe buckets data needle is
+            Found _ dataIndex ->
+                when List.get data dataIndex is
+                    Ok (Pair _ v) ->
+                        Ok v
+
+                    Err OutOfBounds ->
+                        Err KeyNotFound
 
-        Err NotFound ->
-            Err KeyNotFound
+            Vacant _ ->
+                Err KeyNotFound
 
 walk : Dict k v, state, (state, k, v -> state) -> state
-walk = \@Dict list, initialState, transform ->
-    List.walk list initialState (\state, Pair k v -> transform state k v)
+walk = \@Dict { data }, initialState, transform ->
+    List.walk data initialState (\state, Pair k v -> transform state k v)
 
-insert : Dict k v, k, v -> Dict k v
-insert = \@Dict list, k, v ->
-    when List.findFirstIndex list (\Pair key _ -> key == k) is
-        Err NotFound ->
-            insertFresh (@Dict list) k v
+insert : Dict k v, k, v -> Dict k v | k has Eq
+insert = \@Dict table, key, value ->
+    { buckets, data } = growIfNeeded table
 
-        Ok index ->
-            list
-            |> List.set index (Pair k v)
-            |> @Dict
+    when probe buckets data key is
+        Found _ dataIndex ->
+            @Dict { buckets, data: List.set data dataIndex (Pair key value) }
+
+        Vacant bucketIndex ->
+            @Dict {
+                buckets: List.set buckets bucketIndex (List.len data + 1),
+                data: List.append data (Pair key value),
+            }
 
 len : Dict k v -> Nat
-len = \@Dict list ->
-    List.len list
-
-remove : Dict k v, k -> Dict k v
-remove = \@Dict list, key ->
-    when List.findFirstIndex list (\Pair k _ -> k == key) is
-        Err NotFound ->
-            @Dict list
-
-        Ok index ->
-            lastIndex = List.len list - 1
-
-            list
-            |> List.swap index lastIndex
-            |> List.dropLast
-            |> @Dict
-
-contains : Dict k v, k -> Bool
-contains = \@Dict list, needle ->
-    step = \_, Pair key _val ->
-        if key == needle then
-            Break {}
-        else
-            Continue {}
+len = \@Dict { data } ->
+    List.len data
+
+remove : Dict k v, k -> Dict k v | k has Eq
+remove = \@Dict { buckets, data }, key ->
+    if List.isEmpty buckets then
+        @Dict { buckets, data }
+    else
+        when probe buckets data key is
+            Vacant _ ->
+                @Dict { buckets, data }
+
+            Found bucketIndex dataIndex ->
+                lastIndex = List.len data - 1
 
-    when List.iterate list {} step is
-        Continue _ -> False
-        Break _ -> True
+                # The last entry moves into the vacated spot, so its bucket must follow it
+                movedBuckets =
+                    if dataIndex == lastIndex then
+                        buckets
+                    else
+                        when List.get data lastIndex is
+                            Ok (Pair lastKey _) ->
+                                when probe buckets data lastKey is
+                                    Found lastBucketIndex _ ->
+                                        List.set buckets lastBucketIndex (dataIndex + 1)
 
-single : k, v -> Dict k v
+                                    Vacant _ ->
+                                        buckets
+
+                            Err OutOfBounds ->
+                                buckets
+
+                newData =
+                    data
+                    |> List.swap dataIndex lastIndex
+                    |> List.dropLast
+
+                @Dict { buckets: removeBucket movedBuckets newData bucketIndex, data: newData }
+
+contains : Dict k v, k -> Bool | k has Eq
+contains = \@Dict { buckets, data }, needle ->
+    if List.isEmpty buckets then
+        False
+    else
+        when probe buckets data needle is
+            Found _ _ -> True
+            Vacant _ -> False
+
+single : k, v -> Dict k v | k has Eq
 single = \key, value ->
-    @Dict [Pair key value]
+    Dict.insert empty key value
 
 ## Returns a [List] of the dictionary's keys.
 keys : Dict k v -> List k
-keys = \@Dict list ->
-    List.map list (\Pair k _ -> k)
+keys = \@Dict { data } ->
+    List.map data (\Pair k _ -> k)
 
 ## Returns a [List] of the Dict's values
 values : Dict k v -> List v
-values = \@Dict list ->
-    List.map list (\Pair _ v -> v)
+values = \@Dict { data } ->
+    List.map data (\Pair _ v -> v)
 
 # union : Dict k v, Dict k v -> Dict k v
-insertAll : Dict k v, Dict k v -> Dict k v
-insertAll = \xs, @Dict ys ->
-    List.walk ys xs (\state, Pair k v -> Dict.insertIfVacant state k v)
+insertAll : Dict k v, Dict k v -> Dict k v | k has Eq
+insertAll = \xs, @Dict { data } ->
+    List.walk data xs (\state, Pair k v -> Dict.insertIfVacant state k v)
 
 # intersection : Dict k v, Dict k v -> Dict k v
-keepShared : Dict k v, Dict k v -> Dict k v
-keepShared = \@Dict xs, ys ->
-    List.keepIf xs (\Pair k _ -> Dict.contains ys k)
-    |> @Dict
+keepShared : Dict k v, Dict k v -> Dict k v | k has Eq
+keepShared = \@Dict { data }, ys ->
+    List.walk data empty \state, Pair k v ->
+        if Dict.contains ys k then
+            Dict.insert state k v
+        else
+            state
 
 # difference : Dict k v, Dict k v -> Dict k v
-removeAll : Dict k v, Dict k v -> Dict k v
-removeAll = \xs, @Dict ys ->
-    List.walk ys xs (\state, Pair k _ -> Dict.remove state k)
+removeAll : Dict k v, Dict k v -> Dict k v | k has Eq
+removeAll = \xs, @Dict { data } ->
+    List.walk data xs (\state, Pair k _ -> Dict.remove state k)
 
-## Internal helper function to insert a new association
-##
-## Precondition: `k` should not exist in the Dict yet.
-insertFresh : Dict k v, k, v -> Dict k v
-insertFresh = \@Dict list, k, v ->
-    list
-    |> List.append (Pair k v)
-    |> @Dict
-
-insertIfVacant : Dict k v, k, v -> Dict k v
+insertIfVacant : Dict k v, k, v -> Dict k v | k has Eq
 insertIfVacant = \dict, key, value ->
     if Dict.contains dict key then
         dict
     else
         Dict.insert dict key value
+
+## Internal helper to find where `key` lives in the table.
+##
+## Returns the bucket and data index of the key if it is present, or else the
+## empty bucket where it would be inserted.
+##
+## Keys are compared structurally, to agree with [hashKey], which hashes them structurally.
+##
+## Precondition: `buckets` is not empty, and at least one bucket is vacant.
+probe : List Nat, List [Pair k v], k -> [Found Nat Nat, Vacant Nat] | k has Eq
+probe = \buckets, data, key ->
+    probeHelp buckets data key (bucketFor buckets key)
+
+probeHelp : List Nat, List [Pair k v], k, Nat -> [Found Nat Nat, Vacant Nat] | k has Eq
+probeHelp = \buckets, data, key, bucketIndex ->
+    when List.get buckets bucketIndex is
+        Ok 0 ->
+            Vacant bucketIndex
+
+        Ok slot ->
+            when List.get data (slot - 1) is
+                Ok (Pair k _) if Bool.structuralEq k key ->
+                    Found bucketIndex (slot - 1)
+
+                _ ->
+                    probeHelp buckets data key (nextBucket buckets bucketIndex)
+
+        Err OutOfBounds ->
+            Vacant bucketIndex
+
+## Internal helper to empty the bucket at `hole`.
+##
+## Entries further along the same probe sequence are shifted back into the hole,
+## so that lookups never stop early at a bucket that used to be occupied.
+removeBucket : List Nat, List [Pair k v], Nat -> List Nat
+removeBucket = \buckets, data, hole ->
+    removeBucketHelp (List.set buckets hole 0) data hole (nextBucket buckets hole)
+
+removeBucketHelp : List Nat, List [Pair k v], Nat, Nat -> List Nat
+removeBucketHelp = \buckets, data, hole, index ->
+    when List.get buckets index is
+        Ok 0 ->
+            buckets
+
+        Ok slot ->
+            home =
+                when List.get data (slot - 1) is
+                    Ok (Pair key _) -> bucketFor buckets key
+                    Err OutOfBounds -> index
+
+            if probeDistance buckets home index >= probeDistance buckets hole index then
+                buckets
+                |> List.set hole slot
+                |> List.set index 0
+                |> removeBucketHelp data index (nextBucket buckets index)
+            else
+                removeBucketHelp buckets data hole (nextBucket buckets index)
+
+        Err OutOfBounds ->
+            buckets
+
+## Internal helper to make room for one more entry.
+##
+## The table grows (doubling the number of buckets) once it would become more than 7/8 full.
+growIfNeeded : { buckets : List Nat, data : List [Pair k v] } -> { buckets : List Nat, data : List [Pair k v] }
+growIfNeeded = \{ buckets, data } ->
+    bucketCount = List.len buckets
+
+    if (List.len data + 1) * 8 > bucketCount * 7 then
+        startCount = if bucketCount == 0 then 8 else bucketCount * 2
+        newCount = bucketCountFor (List.len data + 1) startCount
+
+        { buckets: rehash (List.repeat 0 newCount) data, data }
+    else
+        { buckets, data }
+
+## Internal helper to point fresh buckets at every entry of `data`.
+rehash : List Nat, List [Pair k v] -> List Nat
+rehash = \emptyBuckets, data ->
+    result =
+        List.walk data { buckets: emptyBuckets, index: 0 } \state, Pair key _ ->
+            bucketIndex = vacantBucket state.buckets (bucketFor state.buckets key)
+
+            {
+                buckets: List.set state.buckets bucketIndex (state.index + 1),
+                index: state.index + 1,
+            }
+
+    result.buckets
+
+vacantBucket : List Nat, Nat -> Nat
+vacantBucket = \buckets, index ->
+    when List.get buckets index is
+        Ok 0 -> index
+        _ -> vacantBucket buckets (nextBucket buckets index)
+
+## The smallest power of two, starting from `count`, that keeps `n` entries at most 7/8 full.
+bucketCountFor : Nat, Nat -> Nat
+bucketCountFor = \n, count ->
+    if n * 8 > count * 7 then
+        bucketCountFor n (count * 2)
+    else
+        count
+
+bucketFor : List Nat, k -> Nat
+bucketFor = \buckets, key ->
+    mask = Num.toU64 (List.len buckets - 1)
+
+    Num.toNat (Num.bitwiseAnd (hashKey key 0) mask)
+
+nextBucket : List Nat, Nat -> Nat
+nextBucket = \buckets, index ->
+    Num.bitwiseAnd (index + 1) (List.len buckets - 1)
+
+## How many steps it takes to probe from bucket `from` to bucket `to`, wrapping around.
+probeDistance : List Nat, Nat, Nat -> Nat
+probeDistance = \buckets, from, to ->
+    Num.bitwiseAnd (to + List.len buckets - from) (List.len buckets - 1)
+
+# Hash any value, using the Zig implementation of wyhash
+hashKey : k, U64 -> U64
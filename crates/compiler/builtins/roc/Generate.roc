@@ -0,0 +1,264 @@
+interface Generate
+    exposes [
+        Generator,
+        Rng,
+        generate,
+        rng,
+        split,
+        nextU64,
+        bounded,
+        smaller,
+        chooseTag,
+        u8,
+        u16,
+        u32,
+        u64,
+        u128,
+        i8,
+        i16,
+        i32,
+        i64,
+        i128,
+        nat,
+        f32,
+        f64,
+        dec,
+        str,
+        list,
+        search,
+    ]
+    imports [
+        List,
+        Str,
+    ]
+
+## A splittable source of pseudorandom numbers, used to generate test inputs.
+##
+## Generating a value never advances an [Rng]. Instead, the [Rng] is [split] into
+## independent streams, one for each part of the value being generated.
+Rng := U64
+
+## A value that can be generated at random, for property-based testing.
+##
+## Records, tuples, tag unions and opaque types can derive an implementation with
+## `has [Generator]`. The `size` bounds how big the generated value may be: integers
+## stay between `-size` and `size` (as far as their type allows), lists and strings
+## have at most `size` elements, and tag payloads are generated with a [smaller] size.
+Generator has
+    generate : Rng, U64 -> val | val has Generator
+
+## Creates an [Rng] from a seed. The same seed always generates the same values.
+rng : U64 -> Rng
+rng = \seed -> @Rng (mix seed)
+
+## Derives the `index`th independent stream of pseudorandom numbers from an [Rng].
+split : Rng, U64 -> Rng
+split = \@Rng state, index ->
+    @Rng (mix (Num.bitwiseXor state (mix (Num.addWrap index golden))))
+
+## The next pseudorandom number from an [Rng].
+nextU64 : Rng -> U64
+nextU64 = \@Rng state -> mix (Num.addWrap state golden)
+
+## A pseudorandom number between 0 and `max`, inclusive.
+bounded : Rng, U64 -> U64
+bounded = \r, max ->
+    if max == Num.maxU64 then
+        nextU64 r
+    else
+        nextU64 r % (max + 1)
+
+## The size to generate the contents of a tag with. Halving it at each level
+## keeps recursive tag unions from growing without bound.
+smaller : U64 -> U64
+smaller = \size -> size // 2
+
+## Picks which of `count` tags to generate. Once the size gets down to zero,
+## this always picks the `base` tag (the one with the fewest payloads), so that
+## recursive tag unions end up finite.
+chooseTag : Rng, U64, U64, U64 -> U64
+chooseTag = \r, size, count, base ->
+    if size == 0 then
+        base
+    else
+        bounded r (count - 1)
+
+u8 : Rng, U64 -> U8
+u8 = \r, size -> Num.toU8 (bounded r (atMost size 255))
+
+u16 : Rng, U64 -> U16
+u16 = \r, size -> Num.toU16 (bounded r (atMost size 65535))
+
+u32 : Rng, U64 -> U32
+u32 = \r, size -> Num.toU32 (bounded r (atMost size 4294967295))
+
+u64 : Rng, U64 -> U64
+u64 = \r, size -> bounded r size
+
+u128 : Rng, U64 -> U128
+u128 = \r, size -> Num.toU128 (bounded r size)
+
+nat : Rng, U64 -> Nat
+nat = \r, size -> Num.toNat (bounded r (atMost size 4294967295))
+
+i8 : Rng, U64 -> I8
+i8 = \r, size -> Num.toI8 (signed r size 127)
+
+i16 : Rng, U64 -> I16
+i16 = \r, size -> Num.toI16 (signed r size 32767)
+
+i32 : Rng, U64 -> I32
+i32 = \r, size -> Num.toI32 (signed r size 2147483647)
+
+i64 : Rng, U64 -> I64
+i64 = \r, size -> signed r size maxSignedLimit
+
+i128 : Rng, U64 -> I128
+i128 = \r, size -> Num.toI128 (signed r size maxSignedLimit)
+
+## A whole part between `-size` and `size`, plus a fraction in steps of 0.001
+f32 : Rng, U64 -> F32
+f32 = \r, size ->
+    whole = Num.toF32 (signed (split r 0) size 1000000)
+    thousandths = Num.toF32 (bounded (split r 1) 999)
+
+    whole + thousandths / 1000
+
+f64 : Rng, U64 -> F64
+f64 = \r, size ->
+    whole = Num.toF64 (signed (split r 0) size maxSignedLimit)
+    thousandths = Num.toF64 (bounded (split r 1) 999)
+
+    whole + thousandths / 1000
+
+dec : Rng, U64 -> Dec
+dec = \r, size ->
+    whole = Num.toFrac (signed (split r 0) size 1000000000)
+    thousandths = Num.toFrac (bounded (split r 1) 999)
+
+    whole + thousandths / 1000
+
+## A string of at most `size` printable ASCII characters.
+str : Rng, U64 -> Str
+str = \r, size ->
+    length = bounded (split r 0) size
+    bytes = strHelp (List.withCapacity (Num.toNat length)) r length
+
+    when Str.fromUtf8 bytes is
+        Ok s -> s
+        Err _ -> ""
+
+strHelp : List U8, Rng, U64 -> List U8
+strHelp = \accum, r, remaining ->
+    if remaining == 0 then
+        accum
+    else
+        # Printable ASCII is the 95 characters from ' ' to '~'
+        byte = 32 + Num.toU8 (bounded (split r remaining) 94)
+
+        strHelp (List.append accum byte) r (remaining - 1)
+
+## A list of at most `size` elements, each generated with the same `size`.
+list : Rng, U64 -> List elem | elem has Generator
+list = \r, size ->
+    length = bounded (split r 0) size
+
+    listHelp (List.withCapacity (Num.toNat length)) r size length
+
+listHelp : List elem, Rng, U64, U64 -> List elem | elem has Generator
+listHelp = \accum, r, size, remaining ->
+    if remaining == 0 then
+        accum
+    else
+        elem = generate (split r remaining) size
+
+        listHelp (List.append accum elem) r size (remaining - 1)
+
+## The outcome of checking a property: the last input it was checked against,
+## whether that input passed, and the `seed` and `size` which generate it.
+PropertyRun val : { input : val, passed : Bool, seed : U64, size : U64 }
+
+## Checks a property against `iterations` generated inputs, starting from `seed`.
+## `iterations` must be at least 1, since a [PropertyRun] needs an input to report.
+##
+## The size of the inputs grows with each iteration. When an input fails the
+## property, this looks for the smallest size which still fails, and returns the
+## input generated at that size with `passed: False`.
+search : U64, U64, (val -> Bool) -> PropertyRun val | val has Generator
+search = \seed, iterations, property ->
+    searchHelp (rng seed) 0 iterations property
+
+searchHelp : Rng, U64, U64, (val -> Bool) -> PropertyRun val | val has Generator
+searchHelp = \base, iteration, iterations, property ->
+    seed = nextU64 (split base iteration)
+    size = atMost iteration maxSize
+    input = generate (rng seed) size
+
+    if property input then
+        if iteration + 1 >= iterations then
+            { input, passed: True, seed, size }
+        else
+            searchHelp base (iteration + 1) iterations property
+    else
+        shrink { input, passed: False, seed, size } 0 property
+
+## Tries every size below the failing one, smallest first, with a few seeds each.
+## The first input which fails the property is as small as this can find.
+shrink : PropertyRun val, U64, (val -> Bool) -> PropertyRun val | val has Generator
+shrink = \failure, size, property ->
+    if size >= failure.size then
+        failure
+    else
+        when shrinkAttempt failure.seed size 0 property is
+            Ok smallerFailure -> smallerFailure
+            Err NotFound -> shrink failure (size + 1) property
+
+shrinkAttempt : U64, U64, U64, (val -> Bool) -> Result (PropertyRun val) [NotFound] | val has Generator
+shrinkAttempt = \seed, size, attempt, property ->
+    if attempt >= shrinkAttempts then
+        Err NotFound
+    else
+        # The first attempt reuses the failing seed, since a smaller version of
+        # the same input is the likeliest to fail in the same way.
+        attemptSeed =
+            if attempt == 0 then
+                seed
+            else
+                nextU64 (split (rng seed) attempt)
+        input = generate (rng attemptSeed) size
+
+        if property input then
+            shrinkAttempt seed size (attempt + 1) property
+        else
+            Ok { input, passed: False, seed: attemptSeed, size }
+
+maxSize : U64
+maxSize = 100
+
+shrinkAttempts : U64
+shrinkAttempts = 20
+
+## Keeps `size * 2` from overflowing when generating signed numbers.
+maxSignedLimit : U64
+maxSignedLimit = 4611686018427387903
+
+## A pseudorandom number between `-(atMost size limit)` and `atMost size limit`.
+signed : Rng, U64, U64 -> I64
+signed = \r, size, limit ->
+    max = atMost size limit
+
+    Num.toI64 (bounded r (2 * max)) - Num.toI64 max
+
+atMost : U64, U64 -> U64
+atMost = \a, b -> if a < b then a else b
+
+golden : U64
+golden = 0x9E3779B97F4A7C15
+
+## The SplitMix64 finalizer, which turns a counter into a well-mixed pseudorandom number.
+mix : U64 -> U64
+mix = \z ->
+    a = Num.mulWrap (Num.bitwiseXor z (Num.shiftRightZfBy z 30)) 0xBF58476D1CE4E5B9
+    b = Num.mulWrap (Num.bitwiseXor a (Num.shiftRightZfBy a 27)) 0x94D049BB133111EB
+
+    Num.bitwiseXor b (Num.shiftRightZfBy b 31)
@@ -0,0 +1,65 @@
+interface Hash
+    exposes [
+        Hash,
+        Hasher,
+        hash,
+        addBytes,
+        addU8,
+        addU16,
+        addU32,
+        addU64,
+        addU128,
+        addI8,
+        addI16,
+        addI32,
+        addI64,
+        addI128,
+        complete,
+        hashStrBytes,
+        hashList,
+    ]
+    imports [
+        List,
+        Str,
+    ]
+
+## A value that can be hashed.
+##
+## Records, tag unions and opaque types can derive an implementation with `has [Hash]`.
+## Note that [hash] does not produce a hash value itself; the [Hasher] must be
+## [complete]d in order to extract the hash value.
+Hash has
+    hash : hasher, a -> hasher | a has Hash, hasher has Hasher
+
+## Describes a hashing algorithm that is fed bytes and produces an integer hash.
+##
+## The [Hasher] ability describes general-purpose hashers. It only allows
+## emission of 64-bit unsigned integer hashes. It is not suitable for
+## cryptographically-secure hashing.
+Hasher has
+    addBytes : a, List U8 -> a | a has Hasher
+    addU8 : a, U8 -> a | a has Hasher
+    addU16 : a, U16 -> a | a has Hasher
+    addU32 : a, U32 -> a | a has Hasher
+    addU64 : a, U64 -> a | a has Hasher
+    addU128 : a, U128 -> a | a has Hasher
+    addI8 : a, I8 -> a | a has Hasher
+    addI16 : a, I16 -> a | a has Hasher
+    addI32 : a, I32 -> a | a has Hasher
+    addI64 : a, I64 -> a | a has Hasher
+    addI128 : a, I128 -> a | a has Hasher
+    complete : a -> U64 | a has Hasher
+
+## Adds a string into a [Hasher] by hashing its UTF-8 bytes.
+hashStrBytes : hasher, Str -> hasher | hasher has Hasher
+hashStrBytes = \hasher, s ->
+    addBytes hasher (Str.toUtf8 s)
+
+## Adds a list of [Hash]able elements to a [Hasher] by hashing each element.
+##
+## The length of the list is hashed first, so that e.g. `[[], [1]]` and `[[1], []]`
+## produce different hashes.
+hashList : hasher, List a -> hasher | a has Hash, hasher has Hasher
+hashList = \hasher, lst ->
+    List.walk lst (addU64 hasher (Num.toU64 (List.len lst))) \accumHasher, elem ->
+        hash accumHasher elem
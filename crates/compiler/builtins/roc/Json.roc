@@ -1,6 +1,9 @@
 interface Json
     exposes [
         Json,
+        FieldNameMapping,
+        json,
+        jsonWithOptions,
         toUtf8,
         fromUtf8,
     ]
@@ -20,7 +23,15 @@ interface Json
         },
     ]
 
-Json := {} has [
+## A JSON format, along with the options it writes and reads JSON with. [json] uses the default
+## options, while [jsonWithOptions] allows choosing them.
+Json := {
+    fieldNameMapping : FieldNameMapping,
+    indent : Nat,
+    emptyEncodeAsNull : Bool,
+    # how deeply nested the value being encoded is, so pretty printing knows how far to indent it
+    depth : Nat,
+} has [
          EncoderFormatting {
              u8: encodeU8,
              u16: encodeU16,
@@ -39,6 +50,7 @@ Json := {} has [
              string: encodeString,
              list: encodeList,
              record: encodeRecord,
+             tuple: encodeTuple,
              tag: encodeTag,
          },
          DecoderFormatting {
@@ -59,43 +71,102 @@ Json := {} has [
              string: decodeString,
              list: decodeList,
              record: decodeRecord,
+             tuple: decodeTuple,
+             tag: decodeTag,
          },
      ]
 
-toUtf8 = @Json {}
+## How the names of record fields are written to, and read from, JSON objects. Record fields are
+## expected to be in camelCase, as is usual in Roc.
+FieldNameMapping : [
+    Default, # `fieldName` is left as it is
+    SnakeCase, # `fieldName` <-> `field_name`
+    PascalCase, # `fieldName` <-> `FieldName`
+    KebabCase, # `fieldName` <-> `field-name`
+]
+
+## The default format: compact output, field names exactly as they are in Roc, and optional
+## values encoded as tags.
+json = @Json { fieldNameMapping: Default, indent: 0, emptyEncodeAsNull: False, depth: 0 }
+
+## A format with the given options:
+##
+## - `fieldNameMapping` renames record fields, e.g. `SnakeCase` turns `firstName` into `first_name`
+##   when encoding, and reads `first_name` back into `firstName` when decoding.
+## - `indent` is the number of spaces each level of nesting is indented by. With the default of `0`,
+##   output is written on a single line.
+## - `emptyEncodeAsNull` encodes `None` and `Err _` as `null`, and `Some a` and `Ok a` as just `a`.
+##   When decoding, a value which is not `null` is then read as `Some` or `Ok` of that value.
+##
+## >>> Json.jsonWithOptions { fieldNameMapping: SnakeCase, indent: 2 }
+jsonWithOptions : { fieldNameMapping ? FieldNameMapping, indent ? Nat, emptyEncodeAsNull ? Bool } -> Json
+jsonWithOptions = \{ fieldNameMapping ? Default, indent ? 0, emptyEncodeAsNull ? False } ->
+    @Json { fieldNameMapping, indent, emptyEncodeAsNull, depth: 0 }
+
+toUtf8 = json
+
+fromUtf8 = json
+
+# The format to encode the contents of an array or object with
+nested : Json -> Json
+nested = \@Json options ->
+    @Json { options & depth: options.depth + 1 }
+
+# When pretty printing, starts a new line indented to the current depth
+appendLineBreak : List U8, Json -> List U8
+appendLineBreak = \bytes, @Json { indent, depth } ->
+    if indent == 0 then
+        bytes
+    else
+        List.append bytes (Num.toU8 '\n')
+        |> List.concat (List.repeat (Num.toU8 ' ') (indent * depth))
+
+# Closes an array or object; when pretty printing, a non-empty one is closed on its own line
+appendClosing : List U8, U8, Nat, Json -> List U8
+appendClosing = \bytes, char, count, fmt ->
+    if count == 0 then
+        List.append bytes char
+    else
+        appendLineBreak bytes fmt |> List.append char
 
-fromUtf8 = @Json {}
+# Separates a key from its value, with a space after the colon when pretty printing
+appendKeySeparator : List U8, Json -> List U8
+appendKeySeparator = \bytes, @Json { indent } ->
+    if indent == 0 then
+        List.append bytes (Num.toU8 ':')
+    else
+        List.concat bytes [Num.toU8 ':', Num.toU8 ' ']
 
 numToBytes = \n ->
     n |> Num.toStr |> Str.toUtf8
 
-encodeU8 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeU8 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeU16 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeU16 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeU32 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeU32 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeU64 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeU64 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeU128 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeU128 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeI8 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeI8 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeI16 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeI16 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeI32 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeI32 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeI64 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeI64 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeI128 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeI128 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeF32 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeF32 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeF64 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeF64 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeDec = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeDec = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeBool = \b -> Encode.custom \bytes, @Json {} ->
+encodeBool = \b -> Encode.custom \bytes, @Json _ ->
         if
             b
         then
@@ -103,15 +174,57 @@ encodeBool = \b -> Encode.custom \bytes, @Json {} ->
         else
             List.concat bytes (Str.toUtf8 "false")
 
-encodeString = \s -> Encode.custom \bytes, @Json {} ->
-        List.append bytes (Num.toU8 '"')
-        |> List.concat (Str.toUtf8 s)
-        |> List.append (Num.toU8 '"')
+encodeString = \s -> Encode.custom \bytes, @Json _ ->
+        appendString bytes s
+
+# Appends `s` as a string literal, escaping it as described in RFC 8259, section 7
+appendString : List U8, Str -> List U8
+appendString = \bytes, s ->
+    utf8 = Str.toUtf8 s
+    withOpeningQuote = List.append bytes (Num.toU8 '"')
+
+    withContents =
+        if List.any utf8 needsEscape then
+            List.walk utf8 withOpeningQuote appendEscapedByte
+        else
+            List.concat withOpeningQuote utf8
+
+    List.append withContents (Num.toU8 '"')
+
+needsEscape : U8 -> Bool
+needsEscape = \b ->
+    # 34 = ", 92 = \, and everything below 32 is a control character
+    b == 34 || b == 92 || b < 32
+
+appendEscapedByte : List U8, U8 -> List U8
+appendEscapedByte = \bytes, b ->
+    when b is
+        34 -> List.concat bytes [92, 34] # \"
+        92 -> List.concat bytes [92, 92] # \\
+        8 -> List.concat bytes [92, 98] # \b
+        12 -> List.concat bytes [92, 102] # \f
+        10 -> List.concat bytes [92, 110] # \n
+        13 -> List.concat bytes [92, 114] # \r
+        9 -> List.concat bytes [92, 116] # \t
+        _ ->
+            if b < 32 then
+                # other control characters have no short form, so they become \u00XX
+                List.concat bytes [92, 117, 48, 48, hexDigitByte (Num.divTrunc b 16), hexDigitByte (Num.rem b 16)]
+            else
+                List.append bytes b
+
+hexDigitByte : U8 -> U8
+hexDigitByte = \n ->
+    if n < 10 then
+        n + 48 # 48 = 0
+    else
+        n + 87 # 87 + 10 = a
 
 encodeList = \lst, encodeElem ->
-    Encode.custom \bytes, @Json {} ->
+    Encode.custom \bytes, fmt ->
+        inner = nested fmt
         writeList = \{ buffer, elemsLeft }, elem ->
-            bufferWithElem = appendWith buffer (encodeElem elem) (@Json {})
+            bufferWithElem = appendWith (appendLineBreak buffer inner) (encodeElem elem) inner
             bufferWithSuffix =
                 if elemsLeft > 1 then
                     List.append bufferWithElem (Num.toU8 ',')
@@ -123,17 +236,18 @@ encodeList = \lst, encodeElem ->
         head = List.append bytes (Num.toU8 '[')
         { buffer: withList } = List.walk lst { buffer: head, elemsLeft: List.len lst } writeList
 
-        List.append withList (Num.toU8 ']')
+        appendClosing withList (Num.toU8 ']') (List.len lst) fmt
 
 encodeRecord = \fields ->
-    Encode.custom \bytes, @Json {} ->
+    Encode.custom \bytes, fmt ->
+        @Json { fieldNameMapping } = fmt
+        inner = nested fmt
         writeRecord = \{ buffer, fieldsLeft }, { key, value } ->
             bufferWithKeyValue =
-                List.append buffer (Num.toU8 '"')
-                |> List.concat (Str.toUtf8 key)
-                |> List.append (Num.toU8 '"')
-                |> List.append (Num.toU8 ':')
-                |> appendWith value (@Json {})
+                appendLineBreak buffer inner
+                |> appendString (toObjectName fieldNameMapping key)
+                |> appendKeySeparator fmt
+                |> appendWith value inner
 
             bufferWithSuffix =
                 if fieldsLeft > 1 then
@@ -146,13 +260,36 @@ encodeRecord = \fields ->
         bytesHead = List.append bytes (Num.toU8 '{')
         { buffer: bytesWithRecord } = List.walk fields { buffer: bytesHead, fieldsLeft: List.len fields } writeRecord
 
-        List.append bytesWithRecord (Num.toU8 '}')
+        appendClosing bytesWithRecord (Num.toU8 '}') (List.len fields) fmt
+
+encodeTuple = \elems ->
+    Encode.custom \bytes, fmt ->
+        inner = nested fmt
+        # Idea: encode `(a, b)` as `[a, b]`
+        writeTuple = \{ buffer, elemsLeft }, elemEncoder ->
+            bufferWithElem = appendWith (appendLineBreak buffer inner) elemEncoder inner
+            bufferWithSuffix =
+                if elemsLeft > 1 then
+                    List.append bufferWithElem (Num.toU8 ',')
+                else
+                    bufferWithElem
+
+            { buffer: bufferWithSuffix, elemsLeft: elemsLeft - 1 }
+
+        bytesHead = List.append bytes (Num.toU8 '[')
+        { buffer: bytesWithTuple } = List.walk elems { buffer: bytesHead, elemsLeft: List.len elems } writeTuple
+
+        appendClosing bytesWithTuple (Num.toU8 ']') (List.len elems) fmt
 
 encodeTag = \name, payload ->
-    Encode.custom \bytes, @Json {} ->
-        # Idea: encode `A v1 v2` as `{"A": [v1, v2]}`
+    Encode.custom \bytes, fmt ->
+        @Json { emptyEncodeAsNull } = fmt
+        # the payload is nested twice, inside of the object and then inside of its array
+        inner = nested fmt
+        payloadFmt = nested inner
+        # Idea: encode `A v1 v2` as `{"A": [v1, v2]}`, and `A` on its own as `"A"`
         writePayload = \{ buffer, itemsLeft }, encoder ->
-            bufferWithValue = appendWith buffer encoder (@Json {})
+            bufferWithValue = appendWith (appendLineBreak buffer payloadFmt) encoder payloadFmt
             bufferWithSuffix =
                 if itemsLeft > 1 then
                     List.append bufferWithValue (Num.toU8 ',')
@@ -161,236 +298,394 @@ encodeTag = \name, payload ->
 
             { buffer: bufferWithSuffix, itemsLeft: itemsLeft - 1 }
 
-        bytesHead =
-            List.append bytes (Num.toU8 '{')
-            |> List.append (Num.toU8 '"')
-            |> List.concat (Str.toUtf8 name)
-            |> List.append (Num.toU8 '"')
-            |> List.append (Num.toU8 ':')
-            |> List.append (Num.toU8 '[')
-
-        { buffer: bytesWithPayload } = List.walk payload { buffer: bytesHead, itemsLeft: List.len payload } writePayload
-
-        List.append bytesWithPayload (Num.toU8 ']')
-        |> List.append (Num.toU8 '}')
-
-takeWhile = \list, predicate ->
-    helper = \{ taken, rest } ->
-        when List.first rest is
-            Ok elem ->
-                if predicate elem then
-                    helper { taken: List.append taken elem, rest: List.split rest 1 |> .others }
+        optionalValue =
+            if emptyEncodeAsNull then
+                when (name, List.first payload) is
+                    ("None", Err ListWasEmpty) -> Null
+                    ("Err", _) -> Null
+                    ("Some", Ok encoder) | ("Ok", Ok encoder) if List.len payload == 1 -> Value encoder
+                    _ -> NotOptional
+            else
+                NotOptional
+
+        when optionalValue is
+            Null -> List.concat bytes (Str.toUtf8 "null")
+            Value encoder -> appendWith bytes encoder fmt
+            NotOptional ->
+                if List.isEmpty payload then
+                    appendString bytes name
                 else
-                    { taken, rest }
-
-            Err _ -> { taken, rest }
-
-    helper { taken: [], rest: list }
-
-asciiByte = \b -> Num.toU8 b
-
-digits = List.range (asciiByte '0') (asciiByte '9' + 1)
-
-takeDigits = \bytes ->
-    takeWhile bytes \n -> List.contains digits n
-
-takeFloat = \bytes ->
-    { taken: intPart, rest } = takeDigits bytes
-
-    when List.get rest 0 is
-        Ok 46 -> # 46 = .
-            { taken: floatPart, rest: afterAll } = takeDigits (List.split rest 1).others
-            builtFloat =
-                List.concat (List.append intPart (asciiByte '.')) floatPart
-
-            { taken: builtFloat, rest: afterAll }
-
-        _ ->
-            { taken: intPart, rest }
-
-decodeU8 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+                    bytesHead =
+                        List.append bytes (Num.toU8 '{')
+                        |> appendLineBreak inner
+                        |> appendString name
+                        |> appendKeySeparator fmt
+                        |> List.append (Num.toU8 '[')
+
+                    { buffer: bytesWithPayload } = List.walk payload { buffer: bytesHead, itemsLeft: List.len payload } writePayload
+
+                    appendClosing bytesWithPayload (Num.toU8 ']') (List.len payload) inner
+                    |> appendClosing (Num.toU8 '}') 1 fmt
+
+# Converts a Roc field name, like `fieldName`, to the name it has in a JSON object
+toObjectName : FieldNameMapping, Str -> Str
+toObjectName = \mapping, fieldName ->
+    when mapping is
+        Default -> fieldName
+        SnakeCase -> separateWords fieldName (Num.toU8 '_')
+        KebabCase -> separateWords fieldName (Num.toU8 '-')
+        PascalCase -> mapFirstByte fieldName toUppercase
+
+# Converts the name of a key in a JSON object back to the Roc field name, undoing `toObjectName`
+fromObjectName : FieldNameMapping, Str -> Str
+fromObjectName = \mapping, objectName ->
+    when mapping is
+        Default -> objectName
+        SnakeCase -> joinWords objectName (Num.toU8 '_')
+        KebabCase -> joinWords objectName (Num.toU8 '-')
+        PascalCase -> mapFirstByte objectName toLowercase
+
+isUppercase : U8 -> Bool
+isUppercase = \b ->
+    b >= Num.toU8 'A' && b <= Num.toU8 'Z'
+
+isLowercase : U8 -> Bool
+isLowercase = \b ->
+    b >= Num.toU8 'a' && b <= Num.toU8 'z'
+
+toUppercase : U8 -> U8
+toUppercase = \b ->
+    if isLowercase b then b - 32 else b
+
+toLowercase : U8 -> U8
+toLowercase = \b ->
+    if isUppercase b then b + 32 else b
+
+# Only ASCII bytes are changed when renaming, so the result is always valid UTF-8; should it somehow
+# not be, the name is left as it was.
+strFromRenamed : List U8, Str -> Str
+strFromRenamed = \bytes, original ->
+    when Str.fromUtf8 bytes is
+        Ok s -> s
+        Err _ -> original
+
+# e.g. `fieldName` -> `field_name`, when `separator` is `_`
+separateWords : Str, U8 -> Str
+separateWords = \name, separator ->
+    Str.toUtf8 name
+    |> List.walk [] \renamed, b ->
+        if isUppercase b then
+            renamed
+            |> List.append separator
+            |> List.append (toLowercase b)
+        else
+            List.append renamed b
+    |> strFromRenamed name
+
+# e.g. `field_name` -> `fieldName`, when `separator` is `_`
+joinWords : Str, U8 -> Str
+joinWords = \name, separator ->
+    joined =
+        List.walk (Str.toUtf8 name) { renamed: [], capitalizeNext: False } \state, b ->
+            if b == separator then
+                { state & capitalizeNext: True }
+            else if state.capitalizeNext then
+                { renamed: List.append state.renamed (toUppercase b), capitalizeNext: False }
+            else
+                { state & renamed: List.append state.renamed b }
 
-    when Str.fromUtf8 taken |> Result.try Str.toU8 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+    strFromRenamed joined.renamed name
 
-decodeU16 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+mapFirstByte : Str, (U8 -> U8) -> Str
+mapFirstByte = \name, mapper ->
+    utf8 = Str.toUtf8 name
 
-    when Str.fromUtf8 taken |> Result.try Str.toU16 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+    when List.first utf8 is
+        Ok b -> strFromRenamed (List.set utf8 0 (mapper b)) name
+        Err ListWasEmpty -> name
 
-decodeU32 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+asciiByte = \b -> Num.toU8 b
 
-    when Str.fromUtf8 taken |> Result.try Str.toU32 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+# Whitespace as described in RFC 8259, section 2: space, horizontal tab, line feed and carriage return
+isWhitespace : U8 -> Bool
+isWhitespace = \b ->
+    b == 32 || b == 9 || b == 10 || b == 13
+
+skipWhitespace : List U8 -> List U8
+skipWhitespace = \bytes ->
+    when List.findFirstIndex bytes (\b -> isWhitespace b == False) is
+        Ok index -> List.drop bytes index
+        Err NotFound -> []
+
+isDigit : U8 -> Bool
+isDigit = \b ->
+    b >= asciiByte '0' && b <= asciiByte '9'
+
+# The index of the first byte at or after `index` which is not a digit
+digitsEnd : List U8, Nat -> Nat
+digitsEnd = \bytes, index ->
+    when List.get bytes index is
+        Ok b if isDigit b -> digitsEnd bytes (index + 1)
+        _ -> index
+
+# Takes a number as described in RFC 8259, section 6, e.g. `-12.5e+3`.
+# Bytes that would make the number invalid, like a `.` without any digits after it, are left in `rest`.
+takeNumber : List U8 -> { taken : List U8, rest : List U8 }
+takeNumber = \bytes ->
+    intStart =
+        when List.first bytes is
+            Ok 45 -> 1 # 45 = -
+            _ -> 0
+
+    intEnd =
+        when List.get bytes intStart is
+            # a leading zero may not be followed by more digits
+            Ok 48 -> intStart + 1 # 48 = 0
+            _ -> digitsEnd bytes intStart
+
+    fracEnd =
+        when List.get bytes intEnd is
+            Ok 46 -> # 46 = .
+                end = digitsEnd bytes (intEnd + 1)
+
+                if end > intEnd + 1 then end else intEnd
+
+            _ -> intEnd
+
+    expEnd =
+        when List.get bytes fracEnd is
+            Ok 101 | Ok 69 -> # 101 = e, 69 = E
+                expDigitsStart =
+                    when List.get bytes (fracEnd + 1) is
+                        Ok 43 | Ok 45 -> fracEnd + 2 # 43 = +, 45 = -
+                        _ -> fracEnd + 1
+
+                end = digitsEnd bytes expDigitsStart
+
+                if end > expDigitsStart then end else fracEnd
+
+            _ -> fracEnd
+
+    if intEnd == intStart then
+        { taken: [], rest: bytes }
+    else
+        { before: taken, others: rest } = List.split bytes expEnd
 
-decodeU64 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+        { taken, rest }
 
-    when Str.fromUtf8 taken |> Result.try Str.toU64 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+decodeNumber = \bytes, fromStr ->
+    { taken, rest } = takeNumber (skipWhitespace bytes)
 
-decodeU128 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+    when Str.fromUtf8 taken |> Result.try fromStr is
+        Ok n -> { result: Ok n, rest: skipWhitespace rest }
+        Err _ -> { result: Err TooShort, rest: bytes }
 
-    when Str.fromUtf8 taken |> Result.try Str.toU128 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+decodeU8 = Decode.custom \bytes, @Json _ -> decodeNumber bytes Str.toU8
 
-decodeI8 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+decodeU16 = Decode.custom \bytes, @Json _ -> decodeNumber bytes Str.toU16
 
-    when Str.fromUtf8 taken |> Result.try Str.toI8 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+decodeU32 = Decode.custom \bytes, @Json _ -> decodeNumber bytes Str.toU32
 
-decodeI16 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+decodeU64 = Decode.custom \bytes, @Json _ -> decodeNumber bytes Str.toU64
 
-    when Str.fromUtf8 taken |> Result.try Str.toI16 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+decodeU128 = Decode.custom \bytes, @Json _ -> decodeNumber bytes Str.toU128
 
-decodeI32 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+decodeI8 = Decode.custom \bytes, @Json _ -> decodeNumber bytes Str.toI8
 
-    when Str.fromUtf8 taken |> Result.try Str.toI32 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+decodeI16 = Decode.custom \bytes, @Json _ -> decodeNumber bytes Str.toI16
 
-decodeI64 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+decodeI32 = Decode.custom \bytes, @Json _ -> decodeNumber bytes Str.toI32
 
-    when Str.fromUtf8 taken |> Result.try Str.toI64 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+decodeI64 = Decode.custom \bytes, @Json _ -> decodeNumber bytes Str.toI64
 
-decodeI128 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+decodeI128 = Decode.custom \bytes, @Json _ -> decodeNumber bytes Str.toI128
 
-    when Str.fromUtf8 taken |> Result.try Str.toI128 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+decodeF32 = Decode.custom \bytes, @Json _ -> decodeNumber bytes Str.toF32
 
-decodeF32 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeFloat bytes
+decodeF64 = Decode.custom \bytes, @Json _ -> decodeNumber bytes Str.toF64
 
-    when Str.fromUtf8 taken |> Result.try Str.toF32 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+decodeDec = Decode.custom \bytes, @Json _ -> decodeNumber bytes Str.toDec
 
-decodeF64 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeFloat bytes
+decodeBool = Decode.custom \bytes, @Json _ ->
+    start = skipWhitespace bytes
 
-    when Str.fromUtf8 taken |> Result.try Str.toF64 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+    if List.startsWith start (Str.toUtf8 "true") then
+        { result: Ok True, rest: skipWhitespace (List.drop start 4) }
+    else if List.startsWith start (Str.toUtf8 "false") then
+        { result: Ok False, rest: skipWhitespace (List.drop start 5) }
+    else
+        { result: Err TooShort, rest: bytes }
 
-decodeDec = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeFloat bytes
+jsonString : List U8 -> DecodeResult Str
+jsonString = \bytes ->
+    start = skipWhitespace bytes
+
+    when List.first start is
+        Ok 34 -> # 34 = "
+            when takeStringBody start 1 [] is
+                Ok { body, end } ->
+                    when Str.fromUtf8 body is
+                        Ok s -> { result: Ok s, rest: skipWhitespace (List.drop start end) }
+                        Err _ -> { result: Err TooShort, rest: bytes }
+
+                Err Invalid -> { result: Err TooShort, rest: bytes }
+
+        _ -> { result: Err TooShort, rest: bytes }
+
+# Reads the body of a string from `index` up to its closing quote, resolving escape sequences along
+# the way. On success, `end` is the index just after the closing quote.
+takeStringBody : List U8, Nat, List U8 -> Result { body : List U8, end : Nat } [Invalid]
+takeStringBody = \bytes, index, body ->
+    when List.get bytes index is
+        Ok 34 -> Ok { body, end: index + 1 } # 34 = "
+        Ok 92 -> # 92 = \
+            when List.get bytes (index + 1) is
+                Ok 34 -> takeStringBody bytes (index + 2) (List.append body 34) # \"
+                Ok 92 -> takeStringBody bytes (index + 2) (List.append body 92) # \\
+                Ok 47 -> takeStringBody bytes (index + 2) (List.append body 47) # \/
+                Ok 98 -> takeStringBody bytes (index + 2) (List.append body 8) # \b
+                Ok 102 -> takeStringBody bytes (index + 2) (List.append body 12) # \f
+                Ok 110 -> takeStringBody bytes (index + 2) (List.append body 10) # \n
+                Ok 114 -> takeStringBody bytes (index + 2) (List.append body 13) # \r
+                Ok 116 -> takeStringBody bytes (index + 2) (List.append body 9) # \t
+                Ok 117 -> # \u
+                    { scalar, next } <- takeUnicodeEscape bytes (index + 2) |> Result.try
+
+                    takeStringBody bytes next (appendUtf8 body scalar)
+
+                _ -> Err Invalid
+
+        Ok b ->
+            if b < 32 then
+                # control characters must be escaped
+                Err Invalid
+            else
+                takeStringBody bytes (index + 1) (List.append body b)
 
-    when Str.fromUtf8 taken |> Result.try Str.toDec is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+        Err OutOfBounds -> Err Invalid
 
-decodeBool = Decode.custom \bytes, @Json {} ->
-    { before: maybeFalse, others: afterFalse } = List.split bytes 5
+# Reads the hex digits of a `\uXXXX` escape starting at `index`. A UTF-16 surrogate pair, like
+# `\uD83D\uDE00`, is combined into the single scalar value it encodes.
+takeUnicodeEscape : List U8, Nat -> Result { scalar : U32, next : Nat } [Invalid]
+takeUnicodeEscape = \bytes, index ->
+    unit <- hexQuad bytes index |> Result.try
 
-    # Note: this could be more performant by traversing both branches char-by-char.
-    # Doing that would also make `rest` more correct in the erroring case.
-    if
-        maybeFalse == [asciiByte 'f', asciiByte 'a', asciiByte 'l', asciiByte 's', asciiByte 'e']
-    then
-        { result: Ok False, rest: afterFalse }
-    else
-        { before: maybeTrue, others: afterTrue } = List.split bytes 4
+    if unit >= 0xD800 && unit <= 0xDBFF then
+        # a high surrogate must be followed by an escaped low surrogate
+        if List.get bytes (index + 4) == Ok 92 && List.get bytes (index + 5) == Ok 117 then
+            low <- hexQuad bytes (index + 6) |> Result.try
 
-        if
-            maybeTrue == [asciiByte 't', asciiByte 'r', asciiByte 'u', asciiByte 'e']
-        then
-            { result: Ok True, rest: afterTrue }
+            if low >= 0xDC00 && low <= 0xDFFF then
+                Ok { scalar: 0x10000 + (unit - 0xD800) * 0x400 + (low - 0xDC00), next: index + 10 }
+            else
+                Err Invalid
         else
-            { result: Err TooShort, rest: bytes }
-
-jsonString : List U8 -> DecodeResult Str
-jsonString = \bytes ->
-    { before, others: afterStartingQuote } = List.split bytes 1
-
-    if
-        before == [asciiByte '"']
-    then
-        # TODO: handle escape sequences
-        { taken: strSequence, rest } = takeWhile afterStartingQuote \n -> n != asciiByte '"'
+            Err Invalid
+    else if unit >= 0xDC00 && unit <= 0xDFFF then
+        # a low surrogate on its own does not encode anything
+        Err Invalid
+    else
+        Ok { scalar: unit, next: index + 4 }
 
-        when Str.fromUtf8 strSequence is
-            Ok s ->
-                { others: afterEndingQuote } = List.split rest 1
+hexQuad : List U8, Nat -> Result U32 [Invalid]
+hexQuad = \bytes, index ->
+    quad = List.sublist bytes { start: index, len: 4 }
 
-                { result: Ok s, rest: afterEndingQuote }
+    if List.len quad == 4 then
+        List.walk quad (Ok 0) \state, b ->
+            value <- Result.try state
+            digit <- Result.try (hexValue b)
 
-            Err _ -> { result: Err TooShort, rest }
+            Ok (value * 16 + digit)
     else
-        { result: Err TooShort, rest: bytes }
+        Err Invalid
+
+hexValue : U8 -> Result U32 [Invalid]
+hexValue = \b ->
+    if b >= 48 && b <= 57 then
+        Ok (Num.toU32 (b - 48)) # 0-9
+    else if b >= 97 && b <= 102 then
+        Ok (Num.toU32 (b - 87)) # a-f
+    else if b >= 65 && b <= 70 then
+        Ok (Num.toU32 (b - 55)) # A-F
+    else
+        Err Invalid
+
+# Appends the UTF-8 encoding of a scalar value
+appendUtf8 : List U8, U32 -> List U8
+appendUtf8 = \bytes, scalar ->
+    continuation = \shift -> Num.toU8 (0x80 + Num.rem (Num.divTrunc scalar shift) 0x40)
+
+    if scalar < 0x80 then
+        List.append bytes (Num.toU8 scalar)
+    else if scalar < 0x800 then
+        bytes
+        |> List.append (Num.toU8 (0xC0 + Num.divTrunc scalar 0x40))
+        |> List.append (continuation 1)
+    else if scalar < 0x10000 then
+        bytes
+        |> List.append (Num.toU8 (0xE0 + Num.divTrunc scalar 0x1000))
+        |> List.append (continuation 0x40)
+        |> List.append (continuation 1)
+    else
+        bytes
+        |> List.append (Num.toU8 (0xF0 + Num.divTrunc scalar 0x40000))
+        |> List.append (continuation 0x1000)
+        |> List.append (continuation 0x40)
+        |> List.append (continuation 1)
 
-decodeString = Decode.custom \bytes, @Json {} ->
+decodeString = Decode.custom \bytes, @Json _ ->
     jsonString bytes
 
-decodeList = \decodeElem -> Decode.custom \bytes, @Json {} ->
+decodeList = \decodeElem -> Decode.custom \bytes, fmt ->
         decodeElems = \chunk, accum ->
-            when Decode.decodeWith chunk decodeElem (@Json {}) is
+            when Decode.decodeWith chunk decodeElem fmt is
                 { result, rest } ->
                     when result is
                         Ok val ->
-                            # TODO: handle spaces before ','
-                            { before: afterElem, others } = List.split rest 1
+                            { result: commaResult, rest: afterComma } = comma rest
 
-                            if
-                                afterElem == [asciiByte ',']
-                            then
-                                decodeElems others (List.append accum val)
-                            else
-                                Done (List.append accum val) rest
+                            when commaResult is
+                                Ok {} -> decodeElems afterComma (List.append accum val)
+                                Err _ -> Done (List.append accum val) rest
 
                         Err e -> Errored e rest
 
-        { before, others: afterStartingBrace } = List.split bytes 1
+        { rest: afterOpeningBracket } <- bytes |> openBracket |> tryDecode
 
-        if
-            before == [asciiByte '[']
-        then
-            # TODO: empty lists
-            when decodeElems afterStartingBrace [] is
+        if nextByteIs afterOpeningBracket (asciiByte ']') then
+            { rest: afterClosingBracket } <- afterOpeningBracket |> closingBracket |> tryDecode
+
+            { result: Ok [], rest: afterClosingBracket }
+        else
+            when decodeElems afterOpeningBracket [] is
                 Errored e rest -> { result: Err e, rest }
                 Done vals rest ->
-                    { before: maybeEndingBrace, others: afterEndingBrace } = List.split rest 1
+                    { rest: afterClosingBracket } <- rest |> closingBracket |> tryDecode
 
-                    if
-                        maybeEndingBrace == [asciiByte ']']
-                    then
-                        { result: Ok vals, rest: afterEndingBrace }
-                    else
-                        { result: Err TooShort, rest }
-        else
-            { result: Err TooShort, rest: bytes }
+                    { result: Ok vals, rest: afterClosingBracket }
 
+# Parses `char`, along with any whitespace around it
 parseExactChar : List U8, U8 -> DecodeResult {}
 parseExactChar = \bytes, char ->
-    when List.get bytes 0 is
-        Ok c ->
-            if
-                c == char
-            then
-                { result: Ok {}, rest: (List.split bytes 1).others }
-            else
-                { result: Err TooShort, rest: bytes }
+    start = skipWhitespace bytes
 
-        Err _ -> { result: Err TooShort, rest: bytes }
+    when List.first start is
+        Ok c if c == char -> { result: Ok {}, rest: skipWhitespace (List.dropFirst start) }
+        _ -> { result: Err TooShort, rest: bytes }
+
+# Whether the next byte after any whitespace is `char`
+nextByteIs : List U8, U8 -> Bool
+nextByteIs = \bytes, char ->
+    when List.first (skipWhitespace bytes) is
+        Ok c -> c == char
+        Err _ -> False
+
+openBracket : List U8 -> DecodeResult {}
+openBracket = \bytes -> parseExactChar bytes (asciiByte '[')
+
+closingBracket : List U8 -> DecodeResult {}
+closingBracket = \bytes -> parseExactChar bytes (asciiByte ']')
 
 openBrace : List U8 -> DecodeResult {}
 openBrace = \bytes -> parseExactChar bytes (asciiByte '{')
@@ -401,35 +696,93 @@ closingBrace = \bytes -> parseExactChar bytes (asciiByte '}')
 recordKey : List U8 -> DecodeResult Str
 recordKey = \bytes -> jsonString bytes
 
-anything : List U8 -> DecodeResult {}
-anything = \bytes -> { result: Err TooShort, rest: bytes }
-
 colon : List U8 -> DecodeResult {}
 colon = \bytes -> parseExactChar bytes (asciiByte ':')
 
 comma : List U8 -> DecodeResult {}
 comma = \bytes -> parseExactChar bytes (asciiByte ',')
 
+# Skips over a value of any kind, e.g. one whose record field is not being decoded
+skipValue : List U8 -> DecodeResult {}
+skipValue = \bytes ->
+    start = skipWhitespace bytes
+
+    when List.first start is
+        Ok 34 -> # 34 = "
+            { rest } <- jsonString start |> tryDecode
+
+            { result: Ok {}, rest }
+
+        Ok 91 | Ok 123 -> # 91 = [, 123 = {
+            skipNested start
+
+        _ ->
+            literalLen =
+                if List.startsWith start (Str.toUtf8 "true") || List.startsWith start (Str.toUtf8 "null") then
+                    4
+                else if List.startsWith start (Str.toUtf8 "false") then
+                    5
+                else
+                    takeNumber start |> .taken |> List.len
+
+            if literalLen == 0 then
+                { result: Err TooShort, rest: bytes }
+            else
+                { result: Ok {}, rest: skipWhitespace (List.drop start literalLen) }
+
+# Skips over an array or object, including everything nested in it. Brackets and braces inside of
+# strings are not counted.
+skipNested : List U8 -> DecodeResult {}
+skipNested = \bytes ->
+    helper = \index, depth, inString, escaped ->
+        when List.get bytes index is
+            Ok b ->
+                if inString then
+                    if escaped then
+                        helper (index + 1) depth True False
+                    else
+                        # 34 = ", 92 = \
+                        helper (index + 1) depth (b != 34) (b == 92)
+                else if b == 34 then
+                    helper (index + 1) depth True False
+                else if b == 91 || b == 123 then
+                    # 91 = [, 123 = {
+                    helper (index + 1) (depth + 1) False False
+                else if b == 93 || b == 125 then
+                    # 93 = ], 125 = }
+                    if depth == 1 then
+                        { result: Ok {}, rest: skipWhitespace (List.drop bytes (index + 1)) }
+                    else
+                        helper (index + 1) (depth - 1) False False
+                else
+                    helper (index + 1) depth False False
+
+            Err _ -> { result: Err TooShort, rest: bytes }
+
+    helper 0 0 False False
+
 tryDecode : DecodeResult a, ({ val : a, rest : List U8 } -> DecodeResult b) -> DecodeResult b
 tryDecode = \{ result, rest }, mapper ->
     when result is
         Ok val -> mapper { val, rest }
         Err e -> { result: Err e, rest }
 
-decodeRecord = \initialState, stepField, finalizer -> Decode.custom \bytes, @Json {} ->
+decodeRecord = \initialState, stepField, finalizer -> Decode.custom \bytes, fmt ->
+        @Json { fieldNameMapping } = fmt
+
         # NB: the stepper function must be passed explicitly until #2894 is resolved.
         decodeFields = \stepper, state, kvBytes ->
             { val: key, rest } <- recordKey kvBytes |> tryDecode
             { rest: afterColonBytes } <- colon rest |> tryDecode
             { val: newState, rest: beforeCommaOrBreak } <- tryDecode
                     (
-                        when stepper state key is
+                        when stepper state (fromObjectName fieldNameMapping key) is
                             Skip ->
-                                { rest: beforeCommaOrBreak } <- afterColonBytes |> anything |> tryDecode
+                                { rest: beforeCommaOrBreak } <- afterColonBytes |> skipValue |> tryDecode
                                 { result: Ok state, rest: beforeCommaOrBreak }
 
                             Keep decoder ->
-                                Decode.decodeWith afterColonBytes decoder (@Json {})
+                                Decode.decodeWith afterColonBytes decoder fmt
                     )
 
             { result: commaResult, rest: nextBytes } = comma beforeCommaOrBreak
@@ -440,10 +793,114 @@ decodeRecord = \initialState, stepField, finalizer -> Decode.custom \bytes, @Jso
 
         { rest: afterBraceBytes } <- bytes |> openBrace |> tryDecode
 
-        { val: endStateResult, rest: beforeClosingBraceBytes } <- decodeFields stepField initialState afterBraceBytes |> tryDecode
+        fieldsResult =
+            if nextByteIs afterBraceBytes (asciiByte '}') then
+                { result: Ok initialState, rest: afterBraceBytes }
+            else
+                decodeFields stepField initialState afterBraceBytes
+
+        { val: endState, rest: beforeClosingBraceBytes } <- fieldsResult |> tryDecode
 
         { rest: afterRecordBytes } <- beforeClosingBraceBytes |> closingBrace |> tryDecode
 
-        when finalizer endStateResult is
+        # Fields that were never stepped are left for the finalizer, which may decode them as missing
+        # values using this format.
+        when finalizer endState fmt is
             Ok val -> { result: Ok val, rest: afterRecordBytes }
             Err e -> { result: Err e, rest: afterRecordBytes }
+
+decodeTuple = \initialState, stepElem, finalizer -> Decode.custom \initialBytes, fmt ->
+        # NB: the stepper function must be passed explicitly until #2894 is resolved.
+        decodeElems = \stepper, state, index, bytes ->
+            { val: newState, rest: beforeCommaOrBreak } <- tryDecode
+                    (
+                        when stepper state index is
+                            TooLong ->
+                                { rest: beforeCommaOrBreak } <- bytes |> skipValue |> tryDecode
+                                { result: Ok state, rest: beforeCommaOrBreak }
+
+                            Next decoder ->
+                                Decode.decodeWith bytes decoder fmt
+                    )
+
+            { result: commaResult, rest: nextBytes } = comma beforeCommaOrBreak
+
+            when commaResult is
+                Ok {} -> decodeElems stepElem newState (index + 1) nextBytes
+                Err _ -> { result: Ok newState, rest: nextBytes }
+
+        { rest: afterBracketBytes } <- initialBytes |> openBracket |> tryDecode
+
+        elemsResult =
+            if nextByteIs afterBracketBytes (asciiByte ']') then
+                { result: Ok initialState, rest: afterBracketBytes }
+            else
+                decodeElems stepElem initialState 0 afterBracketBytes
+
+        { val: endState, rest: beforeClosingBracketBytes } <- elemsResult |> tryDecode
+
+        { rest: afterTupleBytes } <- beforeClosingBracketBytes |> closingBracket |> tryDecode
+
+        when finalizer endState is
+            Ok val -> { result: Ok val, rest: afterTupleBytes }
+            Err e -> { result: Err e, rest: afterTupleBytes }
+
+decodeTag = \stepTag -> Decode.custom \bytes, fmt ->
+        @Json { emptyEncodeAsNull } = fmt
+        start = skipWhitespace bytes
+
+        # A tag without payload is a bare string, like `"A"`; its payload is decoded from an empty array.
+        decodeWithoutPayload = \name, rest ->
+            when stepTag name is
+                Known decoder ->
+                    { result } = Decode.decodeWith (Str.toUtf8 "[]") decoder fmt
+
+                    { result, rest }
+
+                Unknown -> { result: Err TooShort, rest: bytes }
+
+        # With `emptyEncodeAsNull`, any value other than `null` is the payload of `Some` or `Ok`
+        unwrappedStep =
+            if emptyEncodeAsNull then
+                when stepTag "Some" is
+                    Known decoder -> Known decoder
+                    Unknown -> stepTag "Ok"
+            else
+                Unknown
+
+        if List.isEmpty start || List.startsWith start (Str.toUtf8 "null") then
+            # A missing value, or `null`, is read as the tag `None`
+            decodeWithoutPayload "None" (skipWhitespace (List.drop start 4))
+        else
+            when unwrappedStep is
+                Known decoder ->
+                    # The payload is decoded as an array, so the value is wrapped in one
+                    { rest: afterValueBytes } <- skipValue start |> tryDecode
+                    valueBytes = List.takeFirst start (List.len start - List.len afterValueBytes)
+                    payloadBytes =
+                        List.prepend valueBytes (asciiByte '[')
+                        |> List.append (asciiByte ']')
+
+                    { result } = Decode.decodeWith payloadBytes decoder fmt
+
+                    { result, rest: afterValueBytes }
+
+                Unknown ->
+                    if nextByteIs start (asciiByte '"') then
+                        { val: name, rest } <- jsonString start |> tryDecode
+
+                        decodeWithoutPayload name rest
+                    else
+                        # A tag with a payload, like `{"A": [v1, v2]}`
+                        { rest: afterBraceBytes } <- start |> openBrace |> tryDecode
+                        { val: name, rest: afterNameBytes } <- jsonString afterBraceBytes |> tryDecode
+                        { rest: afterColonBytes } <- colon afterNameBytes |> tryDecode
+
+                        when stepTag name is
+                            Known decoder ->
+                                { val, rest: afterPayloadBytes } <- Decode.decodeWith afterColonBytes decoder fmt |> tryDecode
+                                { rest: afterTagBytes } <- afterPayloadBytes |> closingBrace |> tryDecode
+
+                                { result: Ok val, rest: afterTagBytes }
+
+                            Unknown -> { result: Err TooShort, rest: bytes }
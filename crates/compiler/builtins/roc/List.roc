@@ -55,6 +55,7 @@ interface List
         splitLast,
         startsWith,
         endsWith,
+        isEqElems,
         all,
         dropIf,
         sortAsc,
@@ -351,7 +352,7 @@ join = \lists ->
 
     List.walk lists (List.withCapacity totalLength) (\state, list -> List.concat state list)
 
-contains : List a, a -> Bool
+contains : List a, a -> Bool | a has Eq
 contains = \list, needle ->
     List.any list (\x -> x == needle)
 
@@ -881,7 +882,7 @@ intersperse = \list, sep ->
 ## is considered to "start with" an empty list.
 ##
 ## If the first list is empty, this only returns `True` if the second list is empty.
-startsWith : List elem, List elem -> Bool
+startsWith : List elem, List elem -> Bool | elem has Eq
 startsWith = \list, prefix ->
     # TODO once we have seamless slices, verify that this wouldn't
     # have better performance with a function like List.compareSublists
@@ -893,7 +894,7 @@ startsWith = \list, prefix ->
 ## is considered to "end with" an empty list.
 ##
 ## If the first list is empty, this only returns `True` if the second list is empty.
-endsWith : List elem, List elem -> Bool
+endsWith : List elem, List elem -> Bool | elem has Eq
 endsWith = \list, suffix ->
     # TODO once we have seamless slices, verify that this wouldn't
     # have better performance with a function like List.compareSublists
@@ -902,6 +903,26 @@ endsWith = \list, suffix ->
 
     suffix == List.sublist list { start, len: length }
 
+# INTERNAL COMPILER USE ONLY: returns `True` if the two lists have the same
+# length, and all their corresponding elements are equal according to [Eq].
+# Derived implementations of [isEq] use this for lists whose elements have a
+# custom implementation, which is why it's exposed; other modules can't refer to it.
+isEqElems : List a, List a -> Bool | a has Eq
+isEqElems = \xs, ys ->
+    if List.len xs != List.len ys then
+        False
+    else
+        isEqElemsHelp xs ys 0
+
+isEqElemsHelp : List a, List a, Nat -> Bool | a has Eq
+isEqElemsHelp = \xs, ys, index ->
+    if index >= List.len xs then
+        True
+    else if List.getUnsafe xs index == List.getUnsafe ys index then
+        isEqElemsHelp xs ys (index + 1)
+    else
+        False
+
 ## Splits the list into two lists, around the given index.
 ##
 ## The returned lists are labeled `before` and `others`. The `before` list will
@@ -922,7 +943,7 @@ split = \elements, userSplitIndex ->
 ## remaining elements after that occurrence. If the delimiter is not found, returns `Err`.
 ##
 ##     List.splitFirst [Foo, Z, Bar, Z, Baz] Z == Ok { before: [Foo], after: [Bar, Baz] }
-splitFirst : List elem, elem -> Result { before : List elem, after : List elem } [NotFound]*
+splitFirst : List elem, elem -> Result { before : List elem, after : List elem } [NotFound]* | elem has Eq
 splitFirst = \list, delimiter ->
     when List.findFirstIndex list (\elem -> elem == delimiter) is
         Ok index ->
@@ -937,7 +958,7 @@ splitFirst = \list, delimiter ->
 ## remaining elements after that occurrence. If the delimiter is not found, returns `Err`.
 ##
 ##     List.splitLast [Foo, Z, Bar, Z, Baz] Z == Ok { before: [Foo, Bar], after: [Baz] }
-splitLast : List elem, elem -> Result { before : List elem, after : List elem } [NotFound]*
+splitLast : List elem, elem -> Result { before : List elem, after : List elem } [NotFound]* | elem has Eq
 splitLast = \list, delimiter ->
     when List.findLastIndex list (\elem -> elem == delimiter) is
         Ok index ->
@@ -368,6 +368,9 @@ pub const LIST_PREPEND: &str = "roc_builtins.list.prepend";
 pub const LIST_APPEND_UNSAFE: &str = "roc_builtins.list.append_unsafe";
 pub const LIST_RESERVE: &str = "roc_builtins.list.reserve";
 
+pub const DICT_HASH: &str = "roc_builtins.dict.hash";
+pub const DICT_HASH_STR: &str = "roc_builtins.dict.hash_str";
+
 pub const DEC_FROM_STR: &str = "roc_builtins.dec.from_str";
 pub const DEC_TO_STR: &str = "roc_builtins.dec.to_str";
 pub const DEC_FROM_F64: &str = "roc_builtins.dec.from_f64";
@@ -392,7 +395,11 @@ pub const UTILS_DECREF: &str = "roc_builtins.utils.decref";
 pub const UTILS_DECREF_CHECK_NULL: &str = "roc_builtins.utils.decref_check_null";
 
 pub const UTILS_EXPECT_FAILED_START: &str = "roc_builtins.utils.expect_failed_start";
+pub const UTILS_EXPECT_BUFFER_CAPACITY: &str = "roc_builtins.utils.expect_buffer_capacity";
+pub const UTILS_EXPECT_FAILED_START_SHARED_FILE: &str =
+    "roc_builtins.utils.expect_failed_start_shared_file";
 pub const UTILS_EXPECT_FAILED_FINALIZE: &str = "roc_builtins.utils.expect_failed_finalize";
+pub const UTILS_NOTIFY_PARENT_DBG: &str = "roc_builtins.utils.notify_parent_dbg";
 
 pub const UTILS_LONGJMP: &str = "longjmp";
 pub const UTILS_SETJMP: &str = "setjmp";
@@ -14,6 +14,8 @@ pub fn module_source(module_id: ModuleId) -> &'static str {
         ModuleId::ENCODE => ENCODE,
         ModuleId::DECODE => DECODE,
         ModuleId::JSON => JSON,
+        ModuleId::HASH => HASH,
+        ModuleId::GENERATE => GENERATE,
         _ => panic!(
             "ModuleId {:?} is not part of the standard library",
             module_id
@@ -32,3 +34,5 @@ const BOOL: &str = include_str!("../roc/Bool.roc");
 const ENCODE: &str = include_str!("../roc/Encode.roc");
 const DECODE: &str = include_str!("../roc/Decode.roc");
 const JSON: &str = include_str!("../roc/Json.roc");
+const HASH: &str = include_str!("../roc/Hash.roc");
+const GENERATE: &str = include_str!("../roc/Generate.roc");
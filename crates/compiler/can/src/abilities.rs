@@ -5,7 +5,7 @@ use roc_error_macros::internal_error;
 use roc_module::symbol::{ModuleId, Symbol};
 use roc_region::all::Region;
 use roc_types::{
-    subs::Variable,
+    subs::{Subs, Variable},
     types::{MemberImpl, Type},
 };
 
@@ -471,6 +471,255 @@ impl IAbilitiesStore<Resolved> {
     }
 }
 
+/// The lengths of the slices a serialized [AbilitiesStore] consists of, in order.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+struct AbilitiesStoreHeader {
+    members_of_ability: u64,
+    specialization_to_root: u64,
+    ability_members: u64,
+    declared_implementations: u64,
+    specializations: u64,
+    specialization_lambda_sets: u64,
+    resolved_specializations: u64,
+    next_specialization_id: u32,
+    _padding: u32,
+}
+
+#[derive(Clone, Copy)]
+struct SerializedAbilityMember {
+    member: Symbol,
+    parent_ability: Symbol,
+    region: Region,
+    signature_var: Variable,
+}
+
+/// A [MemberImpl]; `symbol` is only meaningful for [MemberImpl::Impl].
+#[derive(Clone, Copy)]
+struct SerializedMemberImpl {
+    impl_key: ImplKey,
+    symbol: Symbol,
+    kind: u32,
+}
+
+/// A [MemberSpecializationInfo], whose lambda sets are the given range of the lambda sets slice.
+#[derive(Clone, Copy)]
+struct SerializedSpecialization {
+    specialization: Symbol,
+    symbol: Symbol,
+    lambda_sets_start: u32,
+    lambda_sets_len: u32,
+}
+
+impl IAbilitiesStore<Resolved> {
+    /// Writes the store in the same format [Subs::serialize] uses, so that solved modules can be
+    /// cached on disk together with their [Subs].
+    pub fn serialize(
+        &self,
+        writer: &mut impl std::io::Write,
+        mut written: usize,
+    ) -> std::io::Result<usize> {
+        let members_of_ability: Vec<(Symbol, Symbol)> = self
+            .members_of_ability
+            .iter()
+            .flat_map(|(ability, members)| members.iter().map(|member| (*ability, *member)))
+            .collect();
+
+        let specialization_to_root: Vec<(Symbol, ImplKey)> = self
+            .specialization_to_root
+            .iter()
+            .map(|(symbol, impl_key)| (*symbol, *impl_key))
+            .collect();
+
+        let ability_members: Vec<SerializedAbilityMember> = self
+            .ability_members
+            .iter()
+            .map(|(member, data)| SerializedAbilityMember {
+                member: *member,
+                parent_ability: data.parent_ability,
+                region: data.region,
+                signature_var: data.signature_var(),
+            })
+            .collect();
+
+        let declared_implementations: Vec<SerializedMemberImpl> = self
+            .declared_implementations
+            .iter()
+            .map(|(impl_key, member_impl)| {
+                let (symbol, kind) = match member_impl {
+                    MemberImpl::Impl(symbol) => (*symbol, 0),
+                    MemberImpl::Derived => (impl_key.ability_member, 1),
+                    MemberImpl::Error => (impl_key.ability_member, 2),
+                };
+
+                SerializedMemberImpl {
+                    impl_key: *impl_key,
+                    symbol,
+                    kind,
+                }
+            })
+            .collect();
+
+        let mut specialization_lambda_sets: Vec<(u32, Variable)> = Vec::new();
+        let specializations: Vec<SerializedSpecialization> = self
+            .specializations
+            .iter()
+            .map(|(specialization, info)| {
+                let lambda_sets_start = specialization_lambda_sets.len() as u32;
+
+                specialization_lambda_sets.extend(
+                    info.specialization_lambda_sets
+                        .iter()
+                        .map(|(region, var)| (*region as u32, *var)),
+                );
+
+                SerializedSpecialization {
+                    specialization: *specialization,
+                    symbol: info.symbol,
+                    lambda_sets_start,
+                    lambda_sets_len: info.specialization_lambda_sets.len() as u32,
+                }
+            })
+            .collect();
+
+        let resolved_specializations: Vec<(SpecializationId, Symbol)> = self
+            .resolved_specializations
+            .iter()
+            .map(|(id, symbol)| (*id, *symbol))
+            .collect();
+
+        let header = AbilitiesStoreHeader {
+            members_of_ability: members_of_ability.len() as u64,
+            specialization_to_root: specialization_to_root.len() as u64,
+            ability_members: ability_members.len() as u64,
+            declared_implementations: declared_implementations.len() as u64,
+            specializations: specializations.len() as u64,
+            specialization_lambda_sets: specialization_lambda_sets.len() as u64,
+            resolved_specializations: resolved_specializations.len() as u64,
+            next_specialization_id: self.next_specialization_id.get(),
+            _padding: 0,
+        };
+
+        written = Subs::serialize_slice(&[header], writer, written)?;
+        written = Subs::serialize_slice(&members_of_ability, writer, written)?;
+        written = Subs::serialize_slice(&specialization_to_root, writer, written)?;
+        written = Subs::serialize_slice(&ability_members, writer, written)?;
+        written = Subs::serialize_slice(&declared_implementations, writer, written)?;
+        written = Subs::serialize_slice(&specializations, writer, written)?;
+        written = Subs::serialize_slice(&specialization_lambda_sets, writer, written)?;
+        written = Subs::serialize_slice(&resolved_specializations, writer, written)?;
+
+        Ok(written)
+    }
+
+    /// Reads back a store written by [Self::serialize], starting at `offset`.
+    pub fn deserialize(bytes: &[u8], offset: usize) -> (Self, usize) {
+        let (header, offset) = Subs::deserialize_slice::<AbilitiesStoreHeader>(bytes, 1, offset);
+        let header = header[0];
+
+        let (members_of_ability, offset) = Subs::deserialize_slice::<(Symbol, Symbol)>(
+            bytes,
+            header.members_of_ability as usize,
+            offset,
+        );
+        let (specialization_to_root, offset) = Subs::deserialize_slice::<(Symbol, ImplKey)>(
+            bytes,
+            header.specialization_to_root as usize,
+            offset,
+        );
+        let (ability_members, offset) = Subs::deserialize_slice::<SerializedAbilityMember>(
+            bytes,
+            header.ability_members as usize,
+            offset,
+        );
+        let (declared_implementations, offset) = Subs::deserialize_slice::<SerializedMemberImpl>(
+            bytes,
+            header.declared_implementations as usize,
+            offset,
+        );
+        let (specializations, offset) = Subs::deserialize_slice::<SerializedSpecialization>(
+            bytes,
+            header.specializations as usize,
+            offset,
+        );
+        let (specialization_lambda_sets, offset) = Subs::deserialize_slice::<(u32, Variable)>(
+            bytes,
+            header.specialization_lambda_sets as usize,
+            offset,
+        );
+        let (resolved_specializations, offset) =
+            Subs::deserialize_slice::<(SpecializationId, Symbol)>(
+                bytes,
+                header.resolved_specializations as usize,
+                offset,
+            );
+
+        let mut store = Self::default();
+
+        for (ability, member) in members_of_ability {
+            store
+                .members_of_ability
+                .entry(*ability)
+                .or_default()
+                .push(*member);
+        }
+
+        store
+            .specialization_to_root
+            .extend(specialization_to_root.iter().copied());
+
+        store
+            .ability_members
+            .extend(ability_members.iter().map(|serialized| {
+                let data = AbilityMemberData {
+                    parent_ability: serialized.parent_ability,
+                    region: serialized.region,
+                    typ: ResolvedMemberType(serialized.signature_var),
+                };
+
+                (serialized.member, data)
+            }));
+
+        store
+            .declared_implementations
+            .extend(declared_implementations.iter().map(|serialized| {
+                let member_impl = match serialized.kind {
+                    0 => MemberImpl::Impl(serialized.symbol),
+                    1 => MemberImpl::Derived,
+                    _ => MemberImpl::Error,
+                };
+
+                (serialized.impl_key, member_impl)
+            }));
+
+        store
+            .specializations
+            .extend(specializations.iter().map(|serialized| {
+                let start = serialized.lambda_sets_start as usize;
+                let end = start + serialized.lambda_sets_len as usize;
+
+                let specialization_lambda_sets = specialization_lambda_sets[start..end]
+                    .iter()
+                    .map(|(region, var)| (*region as u8, *var))
+                    .collect();
+
+                let info =
+                    MemberSpecializationInfo::new(serialized.symbol, specialization_lambda_sets);
+
+                (serialized.specialization, info)
+            }));
+
+        store
+            .resolved_specializations
+            .extend(resolved_specializations.iter().copied());
+
+        store.next_specialization_id = NonZeroU32::new(header.next_specialization_id)
+            .unwrap_or_else(|| internal_error!("serialized abilities store is corrupt"));
+
+        (store, offset)
+    }
+}
+
 impl IAbilitiesStore<Pending> {
     pub fn import_implementation(&mut self, impl_key: ImplKey, resolved_impl: &ResolvedImpl) {
         let member_impl = match resolved_impl {
@@ -2,6 +2,7 @@ use crate::env::Env;
 use crate::procedure::References;
 use crate::scope::{PendingAbilitiesInScope, Scope};
 use roc_collections::{ImMap, MutSet, SendMap, VecMap, VecSet};
+use roc_error_macros::internal_error;
 use roc_module::ident::{Ident, Lowercase, TagName};
 use roc_module::symbol::Symbol;
 use roc_parse::ast::{AssignedField, ExtractSpaces, Pattern, Tag, TypeAnnotation, TypeHeader};
@@ -418,6 +419,15 @@ pub fn find_type_def_symbols(
                     stack.push(&t.value);
                 }
             }
+            Tuple { elems, ext } => {
+                for t in elems.iter() {
+                    stack.push(&t.value);
+                }
+
+                for t in ext.iter() {
+                    stack.push(&t.value);
+                }
+            }
             TagUnion { ext, tags } => {
                 let mut inner_stack = Vec::with_capacity(tags.items.len());
 
@@ -812,6 +822,39 @@ fn can_annotation_help(
                 Type::Record(field_types, TypeExtension::from_type(ext_type))
             }
         }
+        Tuple { elems, ext } => {
+            let ext = match ext {
+                Some(_) => TypeExtension::from_type(can_extension_type(
+                    env,
+                    scope,
+                    var_store,
+                    introduced_variables,
+                    local_aliases,
+                    references,
+                    ext,
+                    roc_problem::can::ExtensionTypeKind::Tuple,
+                )),
+                None => TypeExtension::Closed,
+            };
+
+            let elem_types = elems
+                .iter()
+                .map(|loc_elem| {
+                    can_annotation_help(
+                        env,
+                        &loc_elem.value,
+                        loc_elem.region,
+                        scope,
+                        var_store,
+                        introduced_variables,
+                        local_aliases,
+                        references,
+                    )
+                })
+                .collect();
+
+            Type::Tuple(elem_types, ext)
+        }
         TagUnion { tags, ext, .. } => {
             let ext_type = can_extension_type(
                 env,
@@ -994,12 +1037,19 @@ fn can_extension_type<'a>(
             Type::EmptyTagUnion | Type::TagUnion(..) | Type::Variable(..) | Type::Erroneous(..)
         )
     }
+    fn valid_tuple_ext_type(typ: &Type) -> bool {
+        matches!(
+            typ,
+            Type::Tuple(..) | Type::Variable(..) | Type::Erroneous(..)
+        )
+    }
 
     use roc_problem::can::ExtensionTypeKind;
 
-    let (empty_ext_type, valid_extension_type): (_, fn(&Type) -> bool) = match ext_problem_kind {
-        ExtensionTypeKind::Record => (Type::EmptyRec, valid_record_ext_type),
-        ExtensionTypeKind::TagUnion => (Type::EmptyTagUnion, valid_tag_ext_type),
+    let valid_extension_type: fn(&Type) -> bool = match ext_problem_kind {
+        ExtensionTypeKind::Record => valid_record_ext_type,
+        ExtensionTypeKind::TagUnion => valid_tag_ext_type,
+        ExtensionTypeKind::Tuple => valid_tuple_ext_type,
     };
 
     match opt_ext {
@@ -1034,7 +1084,13 @@ fn can_extension_type<'a>(
                 Type::Variable(var)
             }
         }
-        None => empty_ext_type,
+        None => match ext_problem_kind {
+            ExtensionTypeKind::Record => Type::EmptyRec,
+            ExtensionTypeKind::TagUnion => Type::EmptyTagUnion,
+            ExtensionTypeKind::Tuple => {
+                internal_error!("closed tuples have no extension type; the caller handles them")
+            }
+        },
     }
 }
 
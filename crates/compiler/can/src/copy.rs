@@ -1,7 +1,7 @@
 use crate::{
     def::Def,
     expr::{AccessorData, ClosureData, Expr, Field, OpaqueWrapFunctionData, WhenBranchPattern},
-    pattern::{DestructType, Pattern, RecordDestruct},
+    pattern::{DestructType, Pattern, RecordDestruct, TupleDestruct},
 };
 use roc_module::{
     ident::{Lowercase, TagName},
@@ -483,6 +483,30 @@ fn deep_copy_expr_help<C: CopyEnv>(env: &mut C, copied: &mut Vec<Variable>, expr
             field: field.clone(),
         },
 
+        Tuple { tuple_var, elems } => Tuple {
+            tuple_var: sub!(*tuple_var),
+            elems: elems
+                .iter()
+                .map(|(var, loc_elem)| (sub!(*var), loc_elem.map(|e| go_help!(e))))
+                .collect(),
+        },
+
+        TupleAccess {
+            tuple_var,
+            ext_var,
+            prefix_vars,
+            elem_var,
+            loc_expr,
+            index,
+        } => TupleAccess {
+            tuple_var: sub!(*tuple_var),
+            ext_var: sub!(*ext_var),
+            prefix_vars: prefix_vars.iter().map(|v| sub!(*v)).collect(),
+            elem_var: sub!(*elem_var),
+            loc_expr: Box::new(loc_expr.map(|e| go_help!(e))),
+            index: *index,
+        },
+
         Accessor(AccessorData {
             name,
             function_var,
@@ -613,6 +637,20 @@ fn deep_copy_expr_help<C: CopyEnv>(env: &mut C, copied: &mut Vec<Variable>, expr
             lookups_in_cond: lookups_in_cond.to_vec(),
         },
 
+        Dbg {
+            loc_message,
+            loc_continuation,
+            variable,
+            symbol,
+        } => Dbg {
+            loc_message: Box::new(loc_message.map(|e| go_help!(e))),
+            loc_continuation: loc_continuation
+                .as_ref()
+                .map(|loc_continuation| Box::new(loc_continuation.map(|e| go_help!(e)))),
+            variable: sub!(*variable),
+            symbol: *symbol,
+        },
+
         TypedHole(v) => TypedHole(sub!(*v)),
 
         RuntimeError(err) => RuntimeError(err.clone()),
@@ -709,6 +747,30 @@ fn deep_copy_pattern_help<C: CopyEnv>(
                 })
                 .collect(),
         },
+        TupleDestructure {
+            whole_var,
+            ext_var,
+            destructs,
+        } => TupleDestructure {
+            whole_var: sub!(*whole_var),
+            ext_var: sub!(*ext_var),
+            destructs: destructs
+                .iter()
+                .map(|ltd| {
+                    ltd.map(
+                        |TupleDestruct {
+                             var,
+                             destruct_index,
+                             typ: (tyvar, pat),
+                         }| TupleDestruct {
+                            var: sub!(*var),
+                            destruct_index: *destruct_index,
+                            typ: (sub!(*tyvar), pat.map(|p| go_help!(p))),
+                        },
+                    )
+                })
+                .collect(),
+        },
         NumLiteral(var, s, n, bound) => NumLiteral(sub!(*var), s.clone(), *n, *bound),
         IntLiteral(v1, v2, s, n, bound) => IntLiteral(sub!(*v1), sub!(*v2), s.clone(), *n, *bound),
         FloatLiteral(v1, v2, s, n, bound) => {
@@ -831,7 +893,7 @@ fn deep_copy_type_vars<C: CopyEnv>(
 
             // Everything else is a mechanical descent.
             Structure(flat_type) => match flat_type {
-                EmptyRecord | EmptyTagUnion | Erroneous(_) => Structure(flat_type),
+                EmptyRecord | EmptyTagUnion | EmptyTuple | Erroneous(_) => Structure(flat_type),
                 Apply(symbol, arguments) => {
                     descend_slice!(arguments);
 
@@ -873,6 +935,16 @@ fn deep_copy_type_vars<C: CopyEnv>(
                         Structure(Record(new_fields, new_ext_var))
                     })
                 }
+                Tuple(elems, ext_var) => {
+                    let new_ext_var = descend_var!(ext_var);
+
+                    descend_slice!(elems);
+
+                    perform_clone!({
+                        let new_elems = clone_var_slice!(elems);
+                        Structure(Tuple(new_elems, new_ext_var))
+                    })
+                }
                 TagUnion(tags, ext_var) => {
                     let new_ext_var = descend_var!(ext_var);
 
@@ -1811,6 +1811,13 @@ fn pattern_to_vars_by_symbol(
             }
         }
 
+        TupleDestructure { destructs, .. } => {
+            for destruct in destructs {
+                let (var, nested) = &destruct.value.typ;
+                pattern_to_vars_by_symbol(vars_by_symbol, &nested.value, *var);
+            }
+        }
+
         NumLiteral(..)
         | IntLiteral(..)
         | FloatLiteral(..)
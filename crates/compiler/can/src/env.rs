@@ -142,7 +142,7 @@ impl<'a> Env<'a> {
         } else {
             match self.dep_idents.get(&module_id) {
                 Some(exposed_ids) => match exposed_ids.get_id(ident) {
-                    Some(ident_id) => {
+                    Some(ident_id) if self.can_see(Symbol::new(module_id, ident_id)) => {
                         let symbol = Symbol::new(module_id, ident_id);
 
                         if is_type_name {
@@ -153,23 +153,7 @@ impl<'a> Env<'a> {
 
                         Ok(symbol)
                     }
-                    None => {
-                        let exposed_values = exposed_ids
-                            .ident_strs()
-                            .filter(|(_, ident)| ident.starts_with(|c: char| c.is_lowercase()))
-                            .map(|(_, ident)| Lowercase::from(ident))
-                            .collect();
-                        Err(RuntimeError::ValueNotExposed {
-                            module_name: self
-                                .module_ids
-                                .get_name(module_id)
-                                .expect("Module ID known, but not in the module IDs somehow")
-                                .clone(),
-                            ident: Ident::from(ident),
-                            region,
-                            exposed_values,
-                        })
-                    }
+                    _ => Err(self.value_not_exposed(module_id, ident, region)),
                 },
                 None => Err(RuntimeError::ModuleNotImported {
                     module_name: self
@@ -193,4 +177,40 @@ impl<'a> Env<'a> {
     pub fn problem(&mut self, problem: Problem) {
         self.problems.push(problem)
     }
+
+    /// Whether this module may refer to `symbol`; only the builtins can see the hidden ones.
+    pub fn can_see(&self, symbol: Symbol) -> bool {
+        self.home.is_builtin() || !symbol.is_hidden_builtin()
+    }
+
+    /// The error for looking up `ident` in a module which doesn't expose it to us
+    pub fn value_not_exposed(
+        &self,
+        module_id: ModuleId,
+        ident: &str,
+        region: Region,
+    ) -> RuntimeError {
+        let exposed_values = match self.dep_idents.get(&module_id) {
+            Some(exposed_ids) => exposed_ids
+                .ident_strs()
+                .filter(|(ident_id, ident)| {
+                    ident.starts_with(|c: char| c.is_lowercase())
+                        && self.can_see(Symbol::new(module_id, *ident_id))
+                })
+                .map(|(_, ident)| Lowercase::from(ident))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        RuntimeError::ValueNotExposed {
+            module_name: self
+                .module_ids
+                .get_name(module_id)
+                .expect("Module ID known, but not in the module IDs somehow")
+                .clone(),
+            ident: Ident::from(ident),
+            region,
+            exposed_values,
+        }
+    }
 }
@@ -18,9 +18,9 @@ use roc_module::called_via::CalledVia;
 use roc_module::ident::{ForeignSymbol, Lowercase, TagName};
 use roc_module::low_level::LowLevel;
 use roc_module::symbol::Symbol;
-use roc_parse::ast::{self, Defs, EscapedChar, StrLiteral};
+use roc_parse::ast::{self, Base, Defs, EscapedChar, StrLiteral};
 use roc_parse::pattern::PatternType::*;
-use roc_problem::can::{PrecedenceProblem, Problem, RuntimeError};
+use roc_problem::can::{IntErrorKind, PrecedenceProblem, Problem, RuntimeError};
 use roc_region::all::{Loc, Region};
 use roc_types::subs::{ExhaustiveMark, IllegalCycleMark, RedundantMark, VarStore, Variable};
 use roc_types::types::{Alias, Category, LambdaSet, OptAbleVar, Type};
@@ -182,6 +182,23 @@ pub enum Expr {
         updates: SendMap<Lowercase, Field>,
     },
 
+    Tuple {
+        tuple_var: Variable,
+        elems: Vec<(Variable, Loc<Expr>)>,
+    },
+
+    /// Look up exactly one element of a tuple, e.g. (expr).0.
+    TupleAccess {
+        tuple_var: Variable,
+        ext_var: Variable,
+        /// The elements before the one being accessed; they are unconstrained, but tuple elements
+        /// are positional, so each one still needs a variable.
+        prefix_vars: Vec<Variable>,
+        elem_var: Variable,
+        loc_expr: Box<Loc<Expr>>,
+        index: usize,
+    },
+
     // Sum Types
     Tag {
         tag_union_var: Variable,
@@ -231,6 +248,18 @@ pub enum Expr {
         lookups_in_cond: Vec<(Symbol, Variable)>,
     },
 
+    // Debugging
+    /// Prints `loc_message`, then evaluates `loc_continuation`. Without a continuation,
+    /// evaluates to the printed value.
+    Dbg {
+        loc_message: Box<Loc<Expr>>,
+        loc_continuation: Option<Box<Loc<Expr>>>,
+        /// The type of the printed value
+        variable: Variable,
+        /// The printed value is bound to this symbol, so it can be referred to later
+        symbol: Symbol,
+    },
+
     /// Rendered as empty box in editor
     TypedHole(Variable),
 
@@ -262,6 +291,8 @@ impl Expr {
             Self::Access { field, .. } => Category::Access(field.clone()),
             Self::Accessor(data) => Category::Accessor(data.field.clone()),
             Self::Update { .. } => Category::Record,
+            Self::Tuple { .. } => Category::Tuple,
+            Self::TupleAccess { index, .. } => Category::TupleAccess(*index),
             Self::Tag {
                 name, arguments, ..
             } => Category::TagApply {
@@ -277,6 +308,11 @@ impl Expr {
                 Category::OpaqueWrap(opaque_name)
             }
             Self::Expect { .. } => Category::Expect,
+            Self::Dbg {
+                loc_continuation: Some(loc_continuation),
+                ..
+            } => loc_continuation.value.category(),
+            Self::Dbg { loc_message, .. } => loc_message.value.category(),
 
             // these nodes place no constraints on the expression's type
             Self::TypedHole(_) | Self::RuntimeError(..) => Category::Unknown,
@@ -576,6 +612,33 @@ pub fn canonicalize_expr<'a>(
                 }
             }
         }
+        ast::Expr::Tuple(elems) => {
+            let mut can_elems = Vec::with_capacity(elems.len());
+            let mut references = References::new();
+
+            for loc_elem in elems.iter() {
+                let (can_expr, elem_out) =
+                    canonicalize_expr(env, var_store, scope, loc_elem.region, &loc_elem.value);
+
+                references.union_mut(&elem_out.references);
+
+                can_elems.push((var_store.fresh(), can_expr));
+            }
+
+            let output = Output {
+                references,
+                tail_call: None,
+                ..Default::default()
+            };
+
+            (
+                Tuple {
+                    tuple_var: var_store.fresh(),
+                    elems: can_elems,
+                },
+                output,
+            )
+        }
         ast::Expr::RecordUpdate {
             fields,
             update: loc_update,
@@ -904,6 +967,36 @@ pub fn canonicalize_expr<'a>(
                 output,
             )
         }
+        ast::Expr::TupleAccess(tuple_expr, index) => {
+            let (loc_expr, output) = canonicalize_expr(env, var_store, scope, region, tuple_expr);
+
+            // Tuples can have no more elements than fit in a u16.
+            match index.parse::<u16>() {
+                Ok(index) => (
+                    TupleAccess {
+                        tuple_var: var_store.fresh(),
+                        prefix_vars: (0..index).map(|_| var_store.fresh()).collect(),
+                        elem_var: var_store.fresh(),
+                        ext_var: var_store.fresh(),
+                        loc_expr: Box::new(loc_expr),
+                        index: index as usize,
+                    },
+                    output,
+                ),
+                Err(_) => {
+                    let runtime_error = RuntimeError::InvalidInt(
+                        IntErrorKind::Overflow,
+                        Base::Decimal,
+                        region,
+                        (*index).into(),
+                    );
+
+                    env.problem(Problem::RuntimeError(runtime_error.clone()));
+
+                    (Expr::RuntimeError(runtime_error), output)
+                }
+            }
+        }
         ast::Expr::AccessorFunction(field) => (
             Accessor(AccessorData {
                 name: scope.gen_unique_symbol(),
@@ -998,6 +1091,38 @@ pub fn canonicalize_expr<'a>(
                 output,
             )
         }
+        ast::Expr::Dbg(message, opt_continuation) => {
+            let mut output = Output::default();
+
+            let (loc_message, output1) =
+                canonicalize_expr(env, var_store, scope, message.region, &message.value);
+
+            output.union(output1);
+
+            let loc_continuation = opt_continuation.map(|continuation| {
+                let (loc_continuation, output2) = canonicalize_expr(
+                    env,
+                    var_store,
+                    scope,
+                    continuation.region,
+                    &continuation.value,
+                );
+
+                output.union(output2);
+
+                Box::new(loc_continuation)
+            });
+
+            (
+                Dbg {
+                    loc_message: Box::new(loc_message),
+                    loc_continuation,
+                    variable: var_store.fresh(),
+                    symbol: scope.gen_unique_symbol(),
+                },
+                output,
+            )
+        }
         ast::Expr::If(if_thens, final_else_branch) => {
             let mut branches = Vec::with_capacity(if_thens.len());
             let mut output = Output::default();
@@ -1772,6 +1897,32 @@ pub fn inline_calls(var_store: &mut VarStore, scope: &mut Scope, expr: Expr) ->
             }
         }
 
+        Dbg {
+            loc_message,
+            loc_continuation,
+            variable,
+            symbol,
+        } => {
+            let loc_message = Loc {
+                region: loc_message.region,
+                value: inline_calls(var_store, scope, loc_message.value),
+            };
+
+            let loc_continuation = loc_continuation.map(|loc_continuation| {
+                Box::new(Loc {
+                    region: loc_continuation.region,
+                    value: inline_calls(var_store, scope, loc_continuation.value),
+                })
+            });
+
+            Dbg {
+                loc_message: Box::new(loc_message),
+                loc_continuation,
+                variable,
+                symbol,
+            }
+        }
+
         LetRec(defs, loc_expr, mark) => {
             let mut new_defs = Vec::with_capacity(defs.len());
 
@@ -1862,6 +2013,25 @@ pub fn inline_calls(var_store: &mut VarStore, scope: &mut Scope, expr: Expr) ->
             todo!("Inlining for Access with record_var {:?}, ext_var {:?}, field_var {:?}, loc_expr {:?}, field {:?}", record_var, ext_var, field_var, loc_expr, field);
         }
 
+        Tuple { tuple_var, elems } => {
+            todo!(
+                "Inlining for Tuple with tuple_var {:?} and elems {:?}",
+                tuple_var,
+                elems
+            );
+        }
+
+        TupleAccess {
+            tuple_var,
+            ext_var,
+            elem_var,
+            loc_expr,
+            index,
+            ..
+        } => {
+            todo!("Inlining for TupleAccess with tuple_var {:?}, ext_var {:?}, elem_var {:?}, loc_expr {:?}, index {:?}", tuple_var, ext_var, elem_var, loc_expr, index);
+        }
+
         Tag {
             tag_union_var: variant_var,
             ext_var,
@@ -2648,6 +2818,7 @@ fn get_lookup_symbols(expr: &Expr, var_store: &mut VarStore) -> Vec<(Symbol, Var
                 stack.push(&argument.1.value);
             }
             Expr::Access { loc_expr, .. }
+            | Expr::TupleAccess { loc_expr, .. }
             | Expr::Closure(ClosureData {
                 loc_body: loc_expr, ..
             }) => {
@@ -2656,6 +2827,9 @@ fn get_lookup_symbols(expr: &Expr, var_store: &mut VarStore) -> Vec<(Symbol, Var
             Expr::Record { fields, .. } => {
                 stack.extend(fields.iter().map(|(_, field)| &field.loc_expr.value));
             }
+            Expr::Tuple { elems, .. } => {
+                stack.extend(elems.iter().map(|(_, loc_elem)| &loc_elem.value));
+            }
             Expr::Expect {
                 loc_continuation, ..
             } => {
@@ -2664,6 +2838,17 @@ fn get_lookup_symbols(expr: &Expr, var_store: &mut VarStore) -> Vec<(Symbol, Var
                 // Intentionally ignore the lookups in the nested `expect` condition itself,
                 // because they couldn't possibly influence the outcome of this `expect`!
             }
+            Expr::Dbg {
+                loc_message,
+                loc_continuation,
+                ..
+            } => {
+                stack.push(&loc_message.value);
+
+                if let Some(loc_continuation) = loc_continuation {
+                    stack.push(&loc_continuation.value);
+                }
+            }
             Expr::Num(_, _, _, _)
             | Expr::Float(_, _, _, _, _)
             | Expr::Int(_, _, _, _, _)
@@ -2705,6 +2890,18 @@ fn get_lookup_symbols(expr: &Expr, var_store: &mut VarStore) -> Vec<(Symbol, Var
 /// This is supposed to happen just before monomorphization:
 /// all type errors and such are generated from the user source,
 /// but this transformation means that we don't need special codegen for toplevel expects
+/// Whether a top-level `expect` is property-based, i.e. took arguments, which desugaring
+/// turns into a `Generate.search` for inputs that make it fail.
+pub fn is_property_expect(loc_expr: &Loc<Expr>) -> bool {
+    match &loc_expr.value {
+        Expr::LetNonRec(def, _) => match &def.loc_expr.value {
+            Expr::Call(boxed, _, _) => matches!(boxed.1.value, Expr::Var(Symbol::GENERATE_SEARCH)),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
 pub fn toplevel_expect_to_inline_expect(mut loc_expr: Loc<Expr>) -> Loc<Expr> {
     enum StoredDef {
         NonRecursive(Region, Box<Def>),
@@ -2764,14 +2961,26 @@ struct ExpectCollector {
 
 impl crate::traverse::Visitor for ExpectCollector {
     fn visit_expr(&mut self, expr: &Expr, _region: Region, var: Variable) {
-        if let Expr::Expect {
-            lookups_in_cond,
-            loc_condition,
-            ..
-        } = expr
-        {
-            self.expects
-                .insert(loc_condition.region, lookups_in_cond.to_vec());
+        match expr {
+            Expr::Expect {
+                lookups_in_cond,
+                loc_condition,
+                ..
+            } => {
+                self.expects
+                    .insert(loc_condition.region, lookups_in_cond.to_vec());
+            }
+            Expr::Dbg {
+                loc_message,
+                variable,
+                symbol,
+                ..
+            } => {
+                // A `dbg` reports the value it prints like an `expect` reports its lookups
+                self.expects
+                    .insert(loc_message.region, vec![(*symbol, *variable)]);
+            }
+            _ => {}
         }
 
         walk_expr(self, expr, var)
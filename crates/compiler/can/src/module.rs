@@ -4,6 +4,7 @@ use crate::def::{canonicalize_defs, Def};
 use crate::effect_module::HostedGeneratedFunctions;
 use crate::env::Env;
 use crate::expr::{ClosureData, Declarations, Expr, Output, PendingDerives};
+use crate::operator::PropertyTesting;
 use crate::pattern::{BindingsFromPattern, Pattern};
 use crate::scope::Scope;
 use bumpalo::Bump;
@@ -273,6 +274,7 @@ pub fn canonicalize_module_defs<'a>(
     exposed_symbols: &VecSet<Symbol>,
     symbols_from_requires: &[(Loc<Symbol>, Loc<TypeAnnotation<'a>>)],
     var_store: &mut VarStore,
+    property_testing: PropertyTesting,
 ) -> ModuleOutput {
     let mut can_exposed_imports = MutMap::default();
     let mut scope = Scope::new(home, exposed_ident_ids, imported_abilities_state);
@@ -299,7 +301,7 @@ pub fn canonicalize_module_defs<'a>(
     // visited a BinOp node we'd recursively try to apply this to each of its nested
     // operators, and then again on *their* nested operators, ultimately applying the
     // rules multiple times unnecessarily.
-    crate::operator::desugar_defs(arena, loc_defs);
+    crate::operator::desugar_defs(arena, loc_defs, property_testing);
 
     let mut lookups = Vec::with_capacity(num_deps);
     let mut rigid_variables = RigidVariables::default();
@@ -318,7 +320,11 @@ pub fn canonicalize_module_defs<'a>(
     for (ident, (symbol, region)) in exposed_imports {
         let first_char = ident.as_inline_str().as_str().chars().next().unwrap();
 
-        if first_char.is_lowercase() {
+        if !env.can_see(symbol) {
+            let error = env.value_not_exposed(symbol.module_id(), ident.as_str(), region);
+
+            env.problem(Problem::RuntimeError(error));
+        } else if first_char.is_lowercase() {
             // this is a value definition
             let expr_var = var_store.fresh();
 
@@ -887,6 +893,15 @@ fn fix_values_captured_in_closure_pattern(
                 closure_captures,
             );
         }
+        TupleDestructure { destructs, .. } => {
+            for loc_destruct in destructs.iter_mut() {
+                fix_values_captured_in_closure_pattern(
+                    &mut loc_destruct.value.typ.1.value,
+                    no_capture_symbols,
+                    closure_captures,
+                );
+            }
+        }
         RecordDestructure { destructs, .. } => {
             for loc_destruct in destructs.iter_mut() {
                 use crate::pattern::DestructType::*;
@@ -964,6 +979,27 @@ fn fix_values_captured_in_closure_expr(
             );
         }
 
+        Dbg {
+            loc_message,
+            loc_continuation,
+            variable: _,
+            symbol: _,
+        } => {
+            fix_values_captured_in_closure_expr(
+                &mut loc_message.value,
+                no_capture_symbols,
+                closure_captures,
+            );
+
+            if let Some(loc_continuation) = loc_continuation {
+                fix_values_captured_in_closure_expr(
+                    &mut loc_continuation.value,
+                    no_capture_symbols,
+                    closure_captures,
+                );
+            }
+        }
+
         Closure(ClosureData {
             captured_symbols,
             name,
@@ -1145,7 +1181,7 @@ fn fix_values_captured_in_closure_expr(
             }
         }
 
-        Access { loc_expr, .. } => {
+        Access { loc_expr, .. } | TupleAccess { loc_expr, .. } => {
             fix_values_captured_in_closure_expr(
                 &mut loc_expr.value,
                 no_capture_symbols,
@@ -1153,6 +1189,16 @@ fn fix_values_captured_in_closure_expr(
             );
         }
 
+        Tuple { elems, .. } => {
+            for (_, loc_elem) in elems.iter_mut() {
+                fix_values_captured_in_closure_expr(
+                    &mut loc_elem.value,
+                    no_capture_symbols,
+                    closure_captures,
+                );
+            }
+        }
+
         Tag { arguments, .. } => {
             for (_, loc_arg) in arguments.iter_mut() {
                 fix_values_captured_in_closure_expr(
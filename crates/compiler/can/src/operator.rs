@@ -6,7 +6,7 @@ use roc_module::called_via::BinOp::Pizza;
 use roc_module::called_via::{BinOp, CalledVia};
 use roc_module::ident::ModuleName;
 use roc_parse::ast::Expr::{self, *};
-use roc_parse::ast::{AssignedField, ValueDef, WhenBranch};
+use roc_parse::ast::{AssignedField, Collection, Defs, Pattern, ValueDef, WhenBranch};
 use roc_region::all::{Loc, Region};
 
 // BinOp precedence logic adapted from Gluon by Markus Westerlind
@@ -63,7 +63,31 @@ fn new_op_call_expr<'a>(
     Loc { region, value }
 }
 
-fn desugar_value_def<'a>(arena: &'a Bump, def: &'a ValueDef<'a>) -> ValueDef<'a> {
+/// How a top-level `expect` which takes arguments is checked, e.g. `expect \list -> List.len list >= 0`.
+///
+/// Such an `expect` is a property test: rather than checking a fixed example, it's checked
+/// against `iterations` arguments generated from `seed`, using the `Generator` ability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PropertyTesting {
+    pub seed: u64,
+    /// How many inputs to check each property against. Must be at least 1.
+    pub iterations: u64,
+}
+
+impl Default for PropertyTesting {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            iterations: 100,
+        }
+    }
+}
+
+fn desugar_value_def<'a>(
+    arena: &'a Bump,
+    def: &'a ValueDef<'a>,
+    property_testing: PropertyTesting,
+) -> ValueDef<'a> {
     use ValueDef::*;
 
     match def {
@@ -86,7 +110,8 @@ fn desugar_value_def<'a>(arena: &'a Bump, def: &'a ValueDef<'a>) -> ValueDef<'a>
             condition,
             preceding_comment,
         } => {
-            let desugared_condition = &*arena.alloc(desugar_expr(arena, condition));
+            let desugared_condition =
+                desugar_property_expect(arena, desugar_expr(arena, condition), property_testing);
             Expect {
                 condition: desugared_condition,
                 preceding_comment: *preceding_comment,
@@ -105,12 +130,98 @@ fn desugar_value_def<'a>(arena: &'a Bump, def: &'a ValueDef<'a>) -> ValueDef<'a>
     }
 }
 
-pub fn desugar_defs<'a>(arena: &'a Bump, defs: &mut roc_parse::ast::Defs<'a>) {
+pub fn desugar_defs<'a>(
+    arena: &'a Bump,
+    defs: &mut roc_parse::ast::Defs<'a>,
+    property_testing: PropertyTesting,
+) {
     for value_def in defs.value_defs.iter_mut() {
-        *value_def = desugar_value_def(arena, arena.alloc(*value_def));
+        *value_def = desugar_value_def(arena, arena.alloc(*value_def), property_testing);
     }
 }
 
+/// Turn an `expect` whose condition is a function into a property test, e.g.
+///
+///     expect \a, b -> condition
+///
+/// becomes
+///
+///     expect
+///         generated = Generate.search seed iterations \(a, b) -> condition
+///         generated.passed
+///
+/// Like any other def in a top-level `expect`, `generated` is reported when the `expect`
+/// fails, which shows the failing input along with the seed and size that generate it.
+fn desugar_property_expect<'a>(
+    arena: &'a Bump,
+    condition: &'a Loc<Expr<'a>>,
+    property_testing: PropertyTesting,
+) -> &'a Loc<Expr<'a>> {
+    let (arguments, body) = match condition.value {
+        Closure(arguments, body) => (arguments, body),
+        _ => return condition,
+    };
+
+    debug_assert!(property_testing.iterations > 0);
+
+    let region = condition.region;
+
+    // A property of several arguments is checked against generated tuples of them.
+    let argument = match arguments {
+        [argument] => *argument,
+        _ => Loc::at(region, Pattern::Tuple(Collection::with_items(arguments))),
+    };
+    let property = &*arena.alloc(Loc::at(region, Closure(arena.alloc([argument]), body)));
+
+    let search = &*arena.alloc(Loc::at(
+        region,
+        Var {
+            module_name: ModuleName::GENERATE,
+            ident: "search",
+        },
+    ));
+    let seed = &*arena.alloc(Loc::at(
+        region,
+        Num(arena.alloc_str(&property_testing.seed.to_string())),
+    ));
+    let iterations = &*arena.alloc(Loc::at(
+        region,
+        Num(arena.alloc_str(&property_testing.iterations.to_string())),
+    ));
+    let search_call = &*arena.alloc(Loc::at(
+        region,
+        Apply(
+            search,
+            arena.alloc([seed, iterations, property]),
+            CalledVia::Space,
+        ),
+    ));
+
+    let mut defs = Defs::default();
+    defs.push_value_def(
+        ValueDef::Body(
+            arena.alloc(Loc::at(region, Pattern::Identifier("generated"))),
+            search_call,
+        ),
+        region,
+        &[],
+        &[],
+    );
+
+    let passed = &*arena.alloc(Loc::at(
+        region,
+        Access(
+            arena.alloc(Var {
+                module_name: "",
+                ident: "generated",
+            }),
+            "passed",
+        ),
+    ));
+
+    arena.alloc(Loc::at(region, Expr::Defs(arena.alloc(defs), passed)))
+}
+
 /// Reorder the expression tree based on operator precedence and associativity rules,
 /// then replace the BinOp nodes with Apply nodes. Also drop SpaceBefore and SpaceAfter nodes.
 pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc<Expr<'a>> {
@@ -139,6 +250,16 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
 
             arena.alloc(Loc { region, value })
         }
+        TupleAccess(sub_expr, index) => {
+            let region = loc_expr.region;
+            let loc_sub_expr = Loc {
+                region,
+                value: **sub_expr,
+            };
+            let value = TupleAccess(&desugar_expr(arena, arena.alloc(loc_sub_expr)).value, index);
+
+            arena.alloc(Loc { region, value })
+        }
         List(items) => {
             let mut new_items = Vec::with_capacity_in(items.len(), arena);
 
@@ -164,6 +285,20 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
             })),
         }),
 
+        Tuple(elems) => {
+            let mut new_elems = Vec::with_capacity_in(elems.len(), arena);
+
+            for elem in elems.iter() {
+                new_elems.push(desugar_expr(arena, elem));
+            }
+            let new_elems = new_elems.into_bump_slice();
+            let value: Expr<'a> = Tuple(elems.replace_items(new_elems));
+
+            arena.alloc(Loc {
+                region: loc_expr.region,
+                value,
+            })
+        }
         RecordUpdate { fields, update } => {
             // NOTE the `update` field is always a `Var { .. }`, we only desugar it to get rid of
             // any spaces before/after
@@ -344,6 +479,15 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
                 region: loc_expr.region,
             })
         }
+        Dbg(message, opt_continuation) => {
+            let desugared_message = &*arena.alloc(desugar_expr(arena, message));
+            let desugared_continuation =
+                opt_continuation.map(|continuation| desugar_expr(arena, continuation));
+            arena.alloc(Loc {
+                value: Dbg(desugared_message, desugared_continuation),
+                region: loc_expr.region,
+            })
+        }
     }
 }
 
@@ -1,7 +1,7 @@
 //! Traversals over the can ast.
 
 use roc_module::{ident::Lowercase, symbol::Symbol};
-use roc_region::all::{Loc, Region};
+use roc_region::all::{Loc, Position, Region};
 use roc_types::{subs::Variable, types::MemberImpl};
 
 use crate::{
@@ -233,6 +233,20 @@ pub fn walk_expr<V: Visitor>(visitor: &mut V, expr: &Expr, var: Variable) {
             record_var: _,
             ext_var: _,
         } => visitor.visit_expr(&loc_expr.value, loc_expr.region, *field_var),
+        Expr::Tuple {
+            tuple_var: _,
+            elems,
+        } => elems
+            .iter()
+            .for_each(|(v, le)| visitor.visit_expr(&le.value, le.region, *v)),
+        Expr::TupleAccess {
+            tuple_var,
+            loc_expr,
+            index: _,
+            prefix_vars: _,
+            elem_var: _,
+            ext_var: _,
+        } => visitor.visit_expr(&loc_expr.value, loc_expr.region, *tuple_var),
         Expr::Accessor(AccessorData { .. }) => { /* terminal */ }
         Expr::OpaqueWrapFunction(OpaqueWrapFunctionData { .. }) => { /* terminal */ }
         Expr::Update {
@@ -276,6 +290,22 @@ pub fn walk_expr<V: Visitor>(visitor: &mut V, expr: &Expr, var: Variable) {
                 Variable::NULL,
             );
         }
+        Expr::Dbg {
+            loc_message,
+            loc_continuation,
+            variable,
+            symbol: _,
+        } => {
+            visitor.visit_expr(&loc_message.value, loc_message.region, *variable);
+
+            if let Some(loc_continuation) = loc_continuation {
+                visitor.visit_expr(
+                    &loc_continuation.value,
+                    loc_continuation.region,
+                    Variable::NULL,
+                );
+            }
+        }
         Expr::TypedHole(_) => { /* terminal */ }
         Expr::RuntimeError(..) => { /* terminal */ }
     }
@@ -459,6 +489,10 @@ pub fn walk_pattern<V: Visitor>(visitor: &mut V, pattern: &Pattern) {
         RecordDestructure { destructs, .. } => destructs
             .iter()
             .for_each(|d| visitor.visit_record_destruct(&d.value, d.region)),
+        TupleDestructure { destructs, .. } => destructs.iter().for_each(|d| {
+            let (v, lp) = &d.value.typ;
+            visitor.visit_pattern(&lp.value, lp.region, Some(*v))
+        }),
         NumLiteral(..) => { /* terminal */ }
         IntLiteral(..) => { /* terminal */ }
         FloatLiteral(..) => { /* terminal */ }
@@ -520,6 +554,135 @@ pub fn find_type_at(region: Region, decls: &Declarations) -> Option<Variable> {
     visitor.typ
 }
 
+struct TypeAtPositionVisitor {
+    position: Position,
+    region_typ: Option<(Region, Variable)>,
+}
+
+impl Visitor for TypeAtPositionVisitor {
+    fn should_visit(&mut self, region: Region) -> bool {
+        region.contains(&Region::from_pos(self.position))
+    }
+
+    fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+        if self.should_visit(region) {
+            // Nested nodes are visited afterwards, so the innermost one wins.
+            self.region_typ = Some((region, var));
+
+            walk_expr(self, expr, var);
+        }
+    }
+
+    fn visit_pattern(&mut self, pat: &Pattern, region: Region, opt_var: Option<Variable>) {
+        if self.should_visit(region) {
+            if let Some(var) = opt_var {
+                self.region_typ = Some((region, var));
+            }
+
+            walk_pattern(self, pat);
+        }
+    }
+}
+
+/// Attempts to find the type of the innermost expression or pattern containing `position`,
+/// along with the region of that expression or pattern.
+pub fn find_closest_type_at(
+    position: Position,
+    decls: &Declarations,
+) -> Option<(Region, Variable)> {
+    let mut visitor = TypeAtPositionVisitor {
+        position,
+        region_typ: None,
+    };
+    visitor.visit_decls(decls);
+    visitor.region_typ
+}
+
+/// Attempts to find the symbol that is looked up or defined at `position`, if any.
+pub fn find_symbol_at(position: Position, decls: &Declarations) -> Option<Loc<Symbol>> {
+    let mut visitor = Finder {
+        position,
+        found: None,
+    };
+    visitor.visit_decls(decls);
+    return visitor.found;
+
+    struct Finder {
+        position: Position,
+        found: Option<Loc<Symbol>>,
+    }
+
+    impl Visitor for Finder {
+        fn should_visit(&mut self, region: Region) -> bool {
+            region.contains(&Region::from_pos(self.position))
+        }
+
+        fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+            if self.should_visit(region) {
+                match expr {
+                    Expr::Var(symbol) | Expr::AbilityMember(symbol, _, _) => {
+                        self.found = Some(Loc::at(region, *symbol));
+                    }
+                    _ => walk_expr(self, expr, var),
+                }
+            }
+        }
+
+        fn visit_pattern(&mut self, pattern: &Pattern, region: Region, _opt_var: Option<Variable>) {
+            if self.should_visit(region) {
+                match pattern {
+                    Pattern::Identifier(symbol)
+                    | Pattern::AbilityMemberSpecialization { ident: symbol, .. } => {
+                        self.found = Some(Loc::at(region, *symbol));
+                    }
+                    _ => walk_pattern(self, pattern),
+                }
+            }
+        }
+    }
+}
+
+/// Attempts to find where `symbol` is defined, be it at the top level or in a nested pattern.
+pub fn find_declaration(symbol: Symbol, decls: &Declarations) -> Option<Region> {
+    if let Some(loc_symbol) = decls.symbols.iter().find(|loc| loc.value == symbol) {
+        return Some(loc_symbol.region);
+    }
+
+    let mut visitor = Finder {
+        symbol,
+        found: None,
+    };
+    visitor.visit_decls(decls);
+    return visitor.found;
+
+    struct Finder {
+        symbol: Symbol,
+        found: Option<Region>,
+    }
+
+    impl Visitor for Finder {
+        fn should_visit(&mut self, _region: Region) -> bool {
+            self.found.is_none()
+        }
+
+        fn visit_pattern(&mut self, pattern: &Pattern, region: Region, _opt_var: Option<Variable>) {
+            match pattern {
+                Pattern::Identifier(symbol)
+                | Pattern::AbilityMemberSpecialization { ident: symbol, .. }
+                    if *symbol == self.symbol =>
+                {
+                    self.found = Some(region);
+                }
+                _ => {
+                    if self.should_visit(region) {
+                        walk_pattern(self, pattern);
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Given an ability Foo has foo : ..., returns (T, foo1) if the symbol at the given region is a
 /// symbol foo1 that specializes foo for T. Otherwise if the symbol is foo but the specialization
 /// is unknown, (Foo, foo) is returned. Otherwise [None] is returned.
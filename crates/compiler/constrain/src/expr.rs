@@ -291,6 +291,80 @@ pub fn constrain_expr(
             let and_constraint = constraints.and_constraint(cons);
             constraints.exists(vars, and_constraint)
         }
+        Expr::Tuple { tuple_var, elems } => {
+            // +1 for the tuple itself
+            let mut vars = Vec::with_capacity(elems.len() + 1);
+            let mut elem_types = Vec::with_capacity(elems.len());
+            let mut tuple_constraints = Vec::with_capacity(elems.len() + 1);
+
+            for (elem_var, loc_elem) in elems {
+                let elem_con = constrain_expr(
+                    constraints,
+                    env,
+                    loc_elem.region,
+                    &loc_elem.value,
+                    Expected::NoExpectation(Type::Variable(*elem_var)),
+                );
+
+                vars.push(*elem_var);
+                elem_types.push(Type::Variable(*elem_var));
+                tuple_constraints.push(elem_con);
+            }
+
+            let tuple_con = constraints.equal_types_with_storage(
+                Type::Tuple(elem_types, TypeExtension::Closed),
+                expected,
+                Category::Tuple,
+                region,
+                *tuple_var,
+            );
+
+            tuple_constraints.push(tuple_con);
+            vars.push(*tuple_var);
+
+            constraints.exists_many(vars, tuple_constraints)
+        }
+        TupleAccess {
+            tuple_var,
+            ext_var,
+            prefix_vars,
+            elem_var,
+            loc_expr,
+            index,
+        } => {
+            let ext_type = Type::Variable(*ext_var);
+
+            // The tuple has at least `index + 1` elements; whatever comes after the one we access
+            // is left to the extension.
+            let elem_types = prefix_vars
+                .iter()
+                .chain(std::iter::once(elem_var))
+                .map(|var| Type::Variable(*var))
+                .collect();
+
+            let tuple_type = Type::Tuple(elem_types, TypeExtension::from_type(ext_type));
+            let tuple_expected = Expected::NoExpectation(tuple_type);
+
+            let category = Category::TupleAccess(*index);
+
+            let tuple_con = constraints.equal_types_var(
+                *tuple_var,
+                tuple_expected.clone(),
+                category.clone(),
+                region,
+            );
+
+            let constraint =
+                constrain_expr(constraints, env, region, &loc_expr.value, tuple_expected);
+
+            let eq = constraints.equal_types_var(*elem_var, expected, category, region);
+
+            let mut vars = Vec::with_capacity(prefix_vars.len() + 3);
+            vars.extend(prefix_vars.iter().copied());
+            vars.extend([*tuple_var, *elem_var, *ext_var]);
+
+            constraints.exists_many(vars, [constraint, eq, tuple_con])
+        }
         Str(_) => constraints.equal_types(str_type(), expected, Category::Str, region),
         SingleQuote(_) => constraints.equal_types(num_u32(), expected, Category::Character, region),
         List {
@@ -520,6 +594,40 @@ pub fn constrain_expr(
             constraints.exists_many(vars, all_constraints)
         }
 
+        Dbg {
+            loc_message,
+            loc_continuation,
+            variable,
+            symbol: _,
+        } => {
+            let message_con = constrain_expr(
+                constraints,
+                env,
+                loc_message.region,
+                &loc_message.value,
+                NoExpectation(Type::Variable(*variable)),
+            );
+
+            let continuation_con = match loc_continuation {
+                Some(loc_continuation) => constrain_expr(
+                    constraints,
+                    env,
+                    loc_continuation.region,
+                    &loc_continuation.value,
+                    expected,
+                ),
+                // Without a continuation, `dbg` evaluates to the value it prints
+                None => constraints.equal_types_var(
+                    *variable,
+                    expected,
+                    Category::Storage(std::file!(), std::line!()),
+                    region,
+                ),
+            };
+
+            constraints.exists_many([*variable], [message_con, continuation_con])
+        }
+
         If {
             cond_var,
             branch_var,
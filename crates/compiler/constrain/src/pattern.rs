@@ -3,7 +3,7 @@ use crate::expr::{constrain_expr, Env};
 use roc_can::constraint::{Constraint, Constraints};
 use roc_can::expected::{Expected, PExpected};
 use roc_can::pattern::Pattern::{self, *};
-use roc_can::pattern::{DestructType, RecordDestruct};
+use roc_can::pattern::{DestructType, RecordDestruct, TupleDestruct};
 use roc_collections::all::{HumanIndex, SendMap};
 use roc_collections::VecMap;
 use roc_module::ident::Lowercase;
@@ -101,6 +101,21 @@ fn headers_from_annotation_help(
             _ => false,
         },
 
+        TupleDestructure { destructs, .. } => match annotation.value.shallow_dealias() {
+            Type::Tuple(elems, _) if elems.len() == destructs.len() => {
+                destructs.iter().all(|loc_destruct| {
+                    let destruct = &loc_destruct.value;
+
+                    headers_from_annotation_help(
+                        &destruct.typ.1.value,
+                        &Loc::at(annotation.region, &elems[destruct.destruct_index]),
+                        headers,
+                    )
+                })
+            }
+            _ => false,
+        },
+
         AppliedTag {
             tag_name,
             arguments,
@@ -446,6 +461,71 @@ pub fn constrain_pattern(
             state.constraints.push(whole_con);
             state.constraints.push(record_con);
         }
+        TupleDestructure {
+            whole_var,
+            ext_var,
+            destructs,
+        } => {
+            state.vars.push(*whole_var);
+            state.vars.push(*ext_var);
+
+            let mut elem_types = Vec::with_capacity(destructs.len());
+
+            for Loc {
+                value:
+                    TupleDestruct {
+                        var,
+                        destruct_index: _,
+                        typ: (pattern_var, loc_pattern),
+                    },
+                ..
+            } in destructs
+            {
+                let elem_type = Type::Variable(*var);
+
+                let pattern_con = constraints.equal_types(
+                    Type::Variable(*pattern_var),
+                    Expected::NoExpectation(elem_type.clone()),
+                    Category::Storage(std::file!(), std::line!()),
+                    loc_pattern.region,
+                );
+                state.constraints.push(pattern_con);
+
+                constrain_pattern(
+                    constraints,
+                    env,
+                    &loc_pattern.value,
+                    loc_pattern.region,
+                    PExpected::NoExpectation(elem_type.clone()),
+                    state,
+                );
+
+                elem_types.push(elem_type);
+
+                state.vars.push(*var);
+                state.vars.push(*pattern_var);
+            }
+
+            // Tuple patterns always match the whole tuple, so unlike records they are closed.
+            let tuple_type = Type::Tuple(elem_types, TypeExtension::Closed);
+
+            let whole_con = constraints.equal_types(
+                Type::Variable(*whole_var),
+                Expected::NoExpectation(tuple_type),
+                Category::Storage(std::file!(), std::line!()),
+                region,
+            );
+
+            let tuple_con = constraints.pattern_presence(
+                Type::Variable(*whole_var),
+                expected,
+                PatternCategory::Tuple,
+                region,
+            );
+
+            state.constraints.push(whole_con);
+            state.constraints.push(tuple_con);
+        }
         AppliedTag {
             whole_var,
             ext_var,
@@ -1,19 +1,20 @@
 //! Derivers for the `Decoding` ability.
 
 use roc_can::expr::{
-    AnnotatedMark, ClosureData, Expr, Field, Recursive, WhenBranch, WhenBranchPattern,
+    AnnotatedMark, ClosureData, Expr, Field, IntValue, Recursive, WhenBranch, WhenBranchPattern,
 };
 use roc_can::pattern::Pattern;
 use roc_collections::SendMap;
 use roc_derive_key::decoding::FlatDecodableKey;
 use roc_error_macros::internal_error;
 use roc_module::called_via::CalledVia;
-use roc_module::ident::Lowercase;
+use roc_module::ident::{Lowercase, TagName};
 use roc_module::symbol::Symbol;
 use roc_region::all::{Loc, Region};
+use roc_types::num::{IntBound, IntLitWidth};
 use roc_types::subs::{
     Content, ExhaustiveMark, FlatType, GetSubsSlice, LambdaSet, OptVariable, RecordFields,
-    RedundantMark, SubsSlice, UnionLambdas, UnionTags, Variable,
+    RedundantMark, SubsSlice, UnionLambdas, UnionTags, Variable, VariableSubsSlice,
 };
 use roc_types::types::{AliasKind, RecordField};
 
@@ -28,6 +29,8 @@ pub(crate) fn derive_decoder(
     let (body, body_type) = match key {
         FlatDecodableKey::List() => decoder_list(env, def_symbol),
         FlatDecodableKey::Record(fields) => decoder_record(env, def_symbol, fields),
+        FlatDecodableKey::Tuple(arity) => decoder_tuple(env, def_symbol, arity),
+        FlatDecodableKey::TagUnion(tags) => decoder_tag_union(env, def_symbol, tags),
     };
 
     let specialization_lambda_sets =
@@ -65,16 +68,330 @@ pub(crate) fn derive_decoder(
 //                             {result: Result.map result \val -> {state & f1: Ok val}, rest})
 //             _ -> Skip
 //
-//     finalizer = \{f0, f1} ->
-//         when f0 is
+//     # A field that never appeared is decoded from no bytes at all, so that the format can
+//     # decide whether it may be missing.
+//     orMissing = \field, fmt ->
+//         when field is
+//             Ok val -> Ok val
+//             Err NoField -> (Decode.decodeWith [] Decode.decoder fmt).result
+//
+//     finalizer = \{f0, f1}, fmt ->
+//         when orMissing f0 fmt is
 //             Ok first ->
-//                 when f1 is
+//                 when orMissing f1 fmt is
 //                     Ok second -> Ok {first, second}
-//                     Err NoField -> Err TooShort
-//             Err NoField -> Err TooShort
+//                     _ -> Err TooShort
+//             _ -> Err TooShort
 //
 //     Decode.custom \bytes, fmt -> Decode.decodeWith bytes (Decode.record initialState stepField finalizer) fmt
 fn decoder_record(env: &mut Env, _def_symbol: Symbol, fields: Vec<Lowercase>) -> (Expr, Variable) {
+    decoder_with_state(env, fields, Shape::Record)
+}
+
+// Implements decoding of a tuple. For example, for
+//
+//   (a, b)
+//
+// we'd like to generate an impl like
+//
+// decoder : Decoder (a, b) fmt | a has Decoding, b has Decoding, fmt has DecoderFormatting
+// decoder =
+//     initialState : {e0: Result a [NoField], e1: Result b [NoField]}
+//     initialState = {e0: Err NoField, e1: Err NoField}
+//
+//     stepElem = \state, index ->
+//         when index is
+//             0 ->
+//                 Next (Decode.custom \bytes, fmt ->
+//                     when Decode.decodeWith bytes Decode.decoder fmt is
+//                         {result, rest} ->
+//                             {result: Result.map result \val -> {state & e0: Ok val}, rest})
+//             1 ->
+//                 Next (Decode.custom \bytes, fmt ->
+//                     when Decode.decodeWith bytes Decode.decoder fmt is
+//                         {result, rest} ->
+//                             {result: Result.map result \val -> {state & e1: Ok val}, rest})
+//             _ -> TooLong
+//
+//     finalizer = \{e0, e1} ->
+//         when e0 is
+//             Ok e0 ->
+//                 when e1 is
+//                     Ok e1 -> Ok (e0, e1)
+//                     Err NoField -> Err TooShort
+//             Err NoField -> Err TooShort
+//
+//     Decode.custom \bytes, fmt -> Decode.decodeWith bytes (Decode.tuple initialState stepElem finalizer) fmt
+fn decoder_tuple(env: &mut Env, _def_symbol: Symbol, arity: u32) -> (Expr, Variable) {
+    let fields = (0..arity).map(|i| format!("e{}", i).into()).collect();
+
+    decoder_with_state(env, fields, Shape::Tuple)
+}
+
+// Implements decoding of a tag union. For example, for
+//
+//   [A, B a c]
+//
+// we'd like to generate an impl like
+//
+// decoder : Decoder [A, B a c] fmt | a has Decoding, c has Decoding, fmt has DecoderFormatting
+// decoder =
+//     stepTag = \tag ->
+//         when tag is
+//             "A" ->
+//                 Known (Decode.custom \bytes, fmt -> Decode.decodeWith bytes (Decode.tuple {} stepElem finalizer) fmt)
+//             "B" ->
+//                 Known (Decode.custom \bytes, fmt -> Decode.decodeWith bytes (Decode.tuple {e0: Err NoField, e1: Err NoField} stepElem finalizer) fmt)
+//             _ -> Unknown
+//
+//     Decode.custom \bytes, fmt -> Decode.decodeWith bytes (Decode.tag stepTag) fmt
+//
+// where the payload of each tag is decoded like a tuple, as in `decoder_tuple`, except that its
+// finalizer builds the tag, e.g. `Ok (B e0 e1)`.
+fn decoder_tag_union(
+    env: &mut Env,
+    _def_symbol: Symbol,
+    tags: Vec<(TagName, u16)>,
+) -> (Expr, Variable) {
+    // Generalized tag union var so we can reuse this impl between many unions:
+    // if tags = [ A arity=0, B arity=2 ], this is [ A, B t1 t2 ] for fresh t1, t2
+    let flex_tag_labels = tags
+        .into_iter()
+        .map(|(label, arity)| {
+            let variables_slice = VariableSubsSlice::reserve_into_subs(env.subs, arity.into());
+            for var_index in variables_slice {
+                env.subs[var_index] = env.subs.fresh_unnamed_flex_var();
+            }
+            (label, variables_slice)
+        })
+        .collect::<Vec<_>>();
+    let union_tags = UnionTags::insert_slices_into_subs(env.subs, flex_tag_labels);
+    let tag_union_var = synth_var(
+        env.subs,
+        Content::Structure(FlatType::TagUnion(union_tags, Variable::EMPTY_TAG_UNION)),
+    );
+
+    let tag_arg_symbol = env.new_symbol("tag");
+
+    // [Known (Decoder [A, B t1 t2] fmt), Unknown]
+    let known_payload_var = env.subs.fresh_unnamed_flex_var();
+    let known_or_unknown_var = {
+        let known_payload_subs_slice = SubsSlice::insert_into_subs(env.subs, [known_payload_var]);
+        let flat_type = FlatType::TagUnion(
+            UnionTags::insert_slices_into_subs(
+                env.subs,
+                [
+                    ("Known".into(), known_payload_subs_slice),
+                    ("Unknown".into(), Default::default()),
+                ],
+            ),
+            Variable::EMPTY_TAG_UNION,
+        );
+
+        synth_var(env.subs, Content::Structure(flat_type))
+    };
+
+    // +1 because of the default branch.
+    let mut branches = Vec::with_capacity(union_tags.len() + 1);
+
+    for (tag_name_index, payload_slice_index) in union_tags.iter_all() {
+        let tag_name = env.subs[tag_name_index].clone();
+        let payload_vars = env
+            .subs
+            .get_subs_slice(env.subs[payload_slice_index])
+            .to_vec();
+        let elems = (0..payload_vars.len())
+            .map(|i| format!("e{}", i).into())
+            .collect();
+
+        // Decode.custom \bytes, fmt -> Decode.decodeWith bytes (Decode.tuple ...) fmt
+        let (payload_decoder, payload_decoder_var) = decoder_with_state(
+            env,
+            elems,
+            Shape::Tag {
+                name: tag_name.clone(),
+                union_var: tag_union_var,
+                payload_vars,
+            },
+        );
+
+        env.unify(known_payload_var, payload_decoder_var);
+
+        // "B" -> Known (...)
+        branches.push(WhenBranch {
+            patterns: vec![WhenBranchPattern {
+                pattern: Loc::at_zero(Pattern::StrLiteral(tag_name.0.as_str().into())),
+                degenerate: false,
+            }],
+            value: Loc::at_zero(Expr::Tag {
+                tag_union_var: known_or_unknown_var,
+                ext_var: env.new_ext_var(ExtensionKind::TagUnion),
+                name: "Known".into(),
+                arguments: vec![(payload_decoder_var, Loc::at_zero(payload_decoder))],
+            }),
+            guard: None,
+            redundant: RedundantMark::known_non_redundant(),
+        });
+    }
+
+    // Example: `_ -> Unknown`
+    branches.push(WhenBranch {
+        patterns: vec![WhenBranchPattern {
+            pattern: Loc::at_zero(Pattern::Underscore),
+            degenerate: false,
+        }],
+        value: Loc::at_zero(Expr::Tag {
+            tag_union_var: known_or_unknown_var,
+            ext_var: env.new_ext_var(ExtensionKind::TagUnion),
+            name: "Unknown".into(),
+            arguments: Vec::new(),
+        }),
+        guard: None,
+        redundant: RedundantMark::known_non_redundant(),
+    });
+
+    // when tag is
+    let body = Expr::When {
+        loc_cond: Box::new(Loc::at_zero(Expr::Var(tag_arg_symbol))),
+        cond_var: Variable::STR,
+        expr_var: known_or_unknown_var,
+        region: Region::zero(),
+        branches,
+        branches_cond_var: Variable::STR,
+        exhaustive: ExhaustiveMark::known_exhaustive(),
+    };
+
+    // stepTag = \tag -> ...
+    let step_tag_closure = env.new_symbol("stepTag");
+    let step_tag_var = env.subs.fresh_unnamed_flex_var();
+    let step_tag_closure_type = {
+        let lambda_set = LambdaSet {
+            solved: UnionLambdas::tag_without_arguments(env.subs, step_tag_closure),
+            recursion_var: OptVariable::NONE,
+            unspecialized: Default::default(),
+            ambient_function: step_tag_var,
+        };
+
+        synth_var(env.subs, Content::LambdaSet(lambda_set))
+    };
+
+    {
+        let args_slice = SubsSlice::insert_into_subs(env.subs, [Variable::STR]);
+
+        env.subs.set_content(
+            step_tag_var,
+            Content::Structure(FlatType::Func(
+                args_slice,
+                step_tag_closure_type,
+                known_or_unknown_var,
+            )),
+        )
+    };
+
+    let step_tag = Expr::Closure(ClosureData {
+        function_type: step_tag_var,
+        closure_type: step_tag_closure_type,
+        return_type: known_or_unknown_var,
+        name: step_tag_closure,
+        captured_symbols: Vec::new(),
+        recursive: Recursive::NotRecursive,
+        arguments: vec![(
+            Variable::STR,
+            AnnotatedMark::known_exhaustive(),
+            Loc::at_zero(Pattern::Identifier(tag_arg_symbol)),
+        )],
+        loc_body: Box::new(Loc::at_zero(body)),
+    });
+
+    // Build up the type of `Decode.tag` we expect
+    let tag_decoder_var = env.subs.fresh_unnamed_flex_var();
+    let decode_tag_lambda_set = env.subs.fresh_unnamed_flex_var();
+    let decode_tag_var = env.import_builtin_symbol_var(Symbol::DECODE_TAG);
+    let this_decode_tag_var = {
+        let flat_type = FlatType::Func(
+            SubsSlice::insert_into_subs(env.subs, [step_tag_var]),
+            decode_tag_lambda_set,
+            tag_decoder_var,
+        );
+
+        synth_var(env.subs, Content::Structure(flat_type))
+    };
+
+    env.unify(decode_tag_var, this_decode_tag_var);
+
+    // Decode.tag stepTag
+    let call_decode_tag = Expr::Call(
+        Box::new((
+            this_decode_tag_var,
+            Loc::at_zero(Expr::AbilityMember(
+                Symbol::DECODE_TAG,
+                None,
+                this_decode_tag_var,
+            )),
+            decode_tag_lambda_set,
+            tag_decoder_var,
+        )),
+        vec![(step_tag_var, Loc::at_zero(step_tag))],
+        CalledVia::Space,
+    );
+
+    let bytes_sym = env.new_symbol("bytes");
+    let fmt_sym = env.new_symbol("fmt");
+    let fmt_var = env.subs.fresh_unnamed_flex_var();
+
+    wrap_in_decode_custom_decode_with(
+        env,
+        bytes_sym,
+        (fmt_sym, fmt_var),
+        vec![],
+        (call_decode_tag, tag_decoder_var),
+    )
+}
+
+/// What a stateful decoder produces. Records, tuples and tag payloads are all decoded into a
+/// record state, one field per record field or element; they differ in how the state is stepped
+/// through and what the finalizer builds from it.
+#[derive(Clone, PartialEq, Eq)]
+enum Shape {
+    Record,
+    Tuple,
+    /// The payload of one tag of a tag union, decoded like a tuple and then wrapped in the tag.
+    Tag {
+        name: TagName,
+        union_var: Variable,
+        /// The payload types of the tag in the union, in order.
+        payload_vars: Vec<Variable>,
+    },
+}
+
+impl Shape {
+    /// The `Decode` member that drives the decoding, e.g. `Decode.record`.
+    fn decode_symbol(&self) -> Symbol {
+        match self {
+            Shape::Record => Symbol::DECODE_RECORD,
+            Shape::Tuple | Shape::Tag { .. } => Symbol::DECODE_TUPLE,
+        }
+    }
+
+    /// The type of what the stepper is given to identify the next value, i.e. the field name of a
+    /// record or the index of a tuple element.
+    fn step_key_var(&self) -> Variable {
+        match self {
+            Shape::Record => Variable::STR,
+            Shape::Tuple | Shape::Tag { .. } => Variable::NAT,
+        }
+    }
+
+    /// The tags the stepper returns to decode the next value, or to give up on it.
+    fn step_tags(&self) -> (&'static str, &'static str) {
+        match self {
+            Shape::Record => ("Keep", "Skip"),
+            Shape::Tuple | Shape::Tag { .. } => ("Next", "TooLong"),
+        }
+    }
+}
+
+fn decoder_with_state(env: &mut Env, fields: Vec<Lowercase>, shape: Shape) -> (Expr, Variable) {
     // The decoded type of each field in the record, e.g. {first: a, second: b}.
     let mut field_vars = Vec::with_capacity(fields.len());
     // The type of each field in the decoding state, e.g. {first: Result a [NoField], second: Result b [NoField]}
@@ -91,6 +408,7 @@ fn decoder_record(env: &mut Env, _def_symbol: Symbol, fields: Vec<Lowercase>) ->
         &fields,
         &field_vars,
         &result_field_vars,
+        &shape,
     );
 
     // stepField = ...
@@ -101,12 +419,13 @@ fn decoder_record(env: &mut Env, _def_symbol: Symbol, fields: Vec<Lowercase>) ->
         &result_field_vars,
         initial_state_var,
         decode_err_var,
+        &shape,
     );
 
     // Build up the type of `Decode.record` we expect
     let record_decoder_var = env.subs.fresh_unnamed_flex_var();
     let decode_record_lambda_set = env.subs.fresh_unnamed_flex_var();
-    let decode_record_var = env.import_builtin_symbol_var(Symbol::DECODE_RECORD);
+    let decode_record_var = env.import_builtin_symbol_var(shape.decode_symbol());
     let this_decode_record_var = {
         let flat_type = FlatType::Func(
             SubsSlice::insert_into_subs(env.subs, [initial_state_var, step_var, finalizer_var]),
@@ -124,7 +443,7 @@ fn decoder_record(env: &mut Env, _def_symbol: Symbol, fields: Vec<Lowercase>) ->
         Box::new((
             this_decode_record_var,
             Loc::at_zero(Expr::AbilityMember(
-                Symbol::DECODE_RECORD,
+                shape.decode_symbol(),
                 None,
                 this_decode_record_var,
             )),
@@ -193,9 +512,12 @@ fn decoder_record_step_field(
     result_field_vars: &[Variable],
     state_record_var: Variable,
     decode_err_var: Variable,
+    shape: &Shape,
 ) -> (Expr, Variable) {
     let state_arg_symbol = env.new_symbol("stateRecord");
     let field_arg_symbol = env.new_symbol("field");
+    let (keep_label, skip_label) = shape.step_tags();
+    let step_key_var = shape.step_key_var();
 
     // +1 because of the default branch.
     let mut branches = Vec::with_capacity(fields.len() + 1);
@@ -206,8 +528,8 @@ fn decoder_record_step_field(
             UnionTags::insert_slices_into_subs(
                 env.subs,
                 [
-                    ("Keep".into(), keep_payload_subs_slice),
-                    ("Skip".into(), Default::default()),
+                    (keep_label.into(), keep_payload_subs_slice),
+                    (skip_label.into(), Default::default()),
                 ],
             ),
             Variable::EMPTY_TAG_UNION,
@@ -216,10 +538,11 @@ fn decoder_record_step_field(
         synth_var(env.subs, Content::Structure(flat_type))
     };
 
-    for ((field_name, &field_var), &result_field_var) in fields
+    for (index, ((field_name, &field_var), &result_field_var)) in fields
         .into_iter()
         .zip(field_vars.iter())
         .zip(result_field_vars.iter())
+        .enumerate()
     {
         // Example:
         // "first" ->
@@ -625,7 +948,7 @@ fn decoder_record_step_field(
             Expr::Tag {
                 tag_union_var: keep_or_skip_var,
                 ext_var: env.new_ext_var(ExtensionKind::TagUnion),
-                name: "Keep".into(),
+                name: keep_label.into(),
                 arguments: vec![(decode_custom_ret_var, Loc::at_zero(decode_custom))],
             }
         };
@@ -642,9 +965,21 @@ fn decoder_record_step_field(
             //                         Err err -> Err err
             //                 }
             //     )
+            let pattern = match shape {
+                Shape::Record => Pattern::StrLiteral(field_name.into()),
+                // For tuples and tag payloads, match on the index of the element instead, e.g. `0 ->`
+                Shape::Tuple | Shape::Tag { .. } => Pattern::IntLiteral(
+                    Variable::NAT,
+                    Variable::NATURAL,
+                    index.to_string().into_boxed_str(),
+                    IntValue::I128((index as i128).to_ne_bytes()),
+                    IntBound::Exact(IntLitWidth::Nat),
+                ),
+            };
+
             WhenBranch {
                 patterns: vec![WhenBranchPattern {
-                    pattern: Loc::at_zero(Pattern::StrLiteral(field_name.into())),
+                    pattern: Loc::at_zero(pattern),
                     degenerate: false,
                 }],
                 value: Loc::at_zero(keep),
@@ -665,7 +1000,7 @@ fn decoder_record_step_field(
         value: Loc::at_zero(Expr::Tag {
             tag_union_var: keep_or_skip_var,
             ext_var: env.new_ext_var(ExtensionKind::TagUnion),
-            name: "Skip".into(),
+            name: skip_label.into(),
             arguments: Vec::new(),
         }),
         guard: None,
@@ -677,11 +1012,11 @@ fn decoder_record_step_field(
     // when field is
     let body = Expr::When {
         loc_cond: Box::new(Loc::at_zero(Expr::Var(field_arg_symbol))),
-        cond_var: Variable::STR,
+        cond_var: step_key_var,
         expr_var: keep_or_skip_var,
         region: Region::zero(),
         branches,
-        branches_cond_var: Variable::STR,
+        branches_cond_var: step_key_var,
         exhaustive: ExhaustiveMark::known_exhaustive(),
     };
 
@@ -699,7 +1034,7 @@ fn decoder_record_step_field(
     };
 
     {
-        let args_slice = SubsSlice::insert_into_subs(env.subs, [state_record_var, Variable::STR]);
+        let args_slice = SubsSlice::insert_into_subs(env.subs, [state_record_var, step_key_var]);
 
         env.subs.set_content(
             function_type,
@@ -721,7 +1056,7 @@ fn decoder_record_step_field(
                 Loc::at_zero(Pattern::Identifier(state_arg_symbol)),
             ),
             (
-                Variable::STR,
+                step_key_var,
                 AnnotatedMark::known_exhaustive(),
                 Loc::at_zero(Pattern::Identifier(field_arg_symbol)),
             ),
@@ -733,21 +1068,41 @@ fn decoder_record_step_field(
 }
 
 // Example:
-// finalizer = \rec ->
-//     when rec.first is
+// finalizer = \rec, fmt ->
+//     when
+//         when rec.first is
+//             Ok first -> Ok first
+//             _ ->
+//                 when Decode.decodeWith [] Decode.decoder fmt is
+//                     decRec -> decRec.result
+//     is
 //         Ok first ->
-//             when rec.second is
+//             when
+//                 when rec.second is
+//                     Ok second -> Ok second
+//                     _ ->
+//                         when Decode.decodeWith [] Decode.decoder fmt is
+//                             decRec -> decRec.result
+//             is
 //                 Ok second -> Ok {first, second}
-//                 Err NoField -> Err TooShort
-//         Err NoField -> Err TooShort
+//                 _ -> Err TooShort
+//         _ -> Err TooShort
+//
+// Only records are given the format; the finalizers of tuples and tag payloads unwrap each
+// `when rec.e0 is ...` directly, and fail if an element is missing.
 fn decoder_record_finalizer(
     env: &mut Env,
     state_record_var: Variable,
     fields: &[Lowercase],
     field_vars: &[Variable],
     result_field_vars: &[Variable],
+    shape: &Shape,
 ) -> (Expr, Variable, Variable) {
     let state_arg_symbol = env.new_symbol("stateRecord");
+    let fmt_arg = match shape {
+        Shape::Record => Some((env.new_symbol("fmt"), env.subs.fresh_unnamed_flex_var())),
+        Shape::Tuple | Shape::Tag { .. } => None,
+    };
     let mut fields_map = SendMap::default();
     let mut pattern_symbols = Vec::with_capacity(fields.len());
     let decode_err_var = {
@@ -778,28 +1133,79 @@ fn decoder_record_finalizer(
     // "Ok".
     let return_type_var;
     let mut body = {
-        let subs = &mut env.subs;
-        let record_field_iter = fields
-            .iter()
-            .zip(field_vars.iter())
-            .map(|(field_name, &field_var)| (field_name.clone(), RecordField::Required(field_var)));
-        let flat_type = FlatType::Record(
-            RecordFields::insert_into_subs(subs, record_field_iter),
-            Variable::EMPTY_RECORD,
-        );
-        let done_record_var = synth_var(subs, Content::Structure(flat_type));
-        let done_record = Expr::Record {
-            record_var: done_record_var,
-            fields: fields_map,
+        let (done_record_var, done_record) = match shape {
+            Shape::Record => {
+                let record_field_iter =
+                    fields
+                        .iter()
+                        .zip(field_vars.iter())
+                        .map(|(field_name, &field_var)| {
+                            (field_name.clone(), RecordField::Required(field_var))
+                        });
+                let flat_type = FlatType::Record(
+                    RecordFields::insert_into_subs(env.subs, record_field_iter),
+                    Variable::EMPTY_RECORD,
+                );
+                let done_record_var = synth_var(env.subs, Content::Structure(flat_type));
+                let done_record = Expr::Record {
+                    record_var: done_record_var,
+                    fields: fields_map,
+                };
+
+                (done_record_var, done_record)
+            }
+            Shape::Tuple => {
+                // The fields of the state are in the order of the tuple's elements, so the
+                // decoded tuple is just (e0, e1, ...).
+                let flat_type = FlatType::Tuple(
+                    SubsSlice::insert_into_subs(env.subs, field_vars.iter().copied()),
+                    Variable::EMPTY_TUPLE,
+                );
+                let done_tuple_var = synth_var(env.subs, Content::Structure(flat_type));
+                let done_tuple = Expr::Tuple {
+                    tuple_var: done_tuple_var,
+                    elems: field_vars
+                        .iter()
+                        .zip(pattern_symbols.iter())
+                        .map(|(&elem_var, &symbol)| (elem_var, Loc::at_zero(Expr::Var(symbol))))
+                        .collect(),
+                };
+
+                (done_tuple_var, done_tuple)
+            }
+            Shape::Tag {
+                name,
+                union_var,
+                payload_vars,
+            } => {
+                // Likewise, the decoded tag is just `A e0 e1 ...`, where each element has the
+                // type of the corresponding payload in the union.
+                for (&payload_var, &field_var) in payload_vars.iter().zip(field_vars.iter()) {
+                    env.unify(payload_var, field_var);
+                }
+
+                let done_tag = Expr::Tag {
+                    tag_union_var: *union_var,
+                    ext_var: env.new_ext_var(ExtensionKind::TagUnion),
+                    name: name.clone(),
+                    arguments: field_vars
+                        .iter()
+                        .zip(pattern_symbols.iter())
+                        .map(|(&elem_var, &symbol)| (elem_var, Loc::at_zero(Expr::Var(symbol))))
+                        .collect(),
+                };
+
+                (*union_var, done_tag)
+            }
         };
 
         return_type_var = {
             let flat_type = FlatType::TagUnion(
-                UnionTags::for_result(subs, done_record_var, decode_err_var),
+                UnionTags::for_result(env.subs, done_record_var, decode_err_var),
                 Variable::EMPTY_TAG_UNION,
             );
 
-            synth_var(subs, Content::Structure(flat_type))
+            synth_var(env.subs, Content::Structure(flat_type))
         };
 
         Expr::Tag {
@@ -814,7 +1220,7 @@ fn decoder_record_finalizer(
     //
     // when rec.first is
     //     Ok first -> ...happy path...
-    //     Err NoField -> Err TooShort
+    //     _ -> Err TooShort
     for (((symbol, field_name), &field_var), &result_field_var) in pattern_symbols
         .iter()
         .rev()
@@ -822,8 +1228,8 @@ fn decoder_record_finalizer(
         .zip(field_vars.iter().rev())
         .zip(result_field_vars.iter().rev())
     {
-        // when rec.first is
-        let cond_expr = Expr::Access {
+        // rec.first
+        let field_access = Expr::Access {
             record_var: state_record_var,
             ext_var: env.new_ext_var(ExtensionKind::Record),
             field_var: result_field_var,
@@ -831,11 +1237,32 @@ fn decoder_record_finalizer(
             field: field_name.clone(),
         };
 
+        // when rec.first is
+        //
+        // or, for records,
+        //
+        // when
+        //     when rec.first is
+        //         Ok first -> Ok first
+        //         _ -> ...decode the missing field...
+        // is
+        let (cond_expr, cond_var) = match fmt_arg {
+            Some(fmt_arg) => decoder_record_field_or_missing(
+                env,
+                (field_access, result_field_var),
+                field_name,
+                field_var,
+                decode_err_var,
+                fmt_arg,
+            ),
+            None => (field_access, result_field_var),
+        };
+
         // Example: `Ok x -> expr`
         let ok_branch = WhenBranch {
             patterns: vec![WhenBranchPattern {
                 pattern: Loc::at_zero(Pattern::AppliedTag {
-                    whole_var: result_field_var,
+                    whole_var: cond_var,
                     ext_var: Variable::EMPTY_TAG_UNION,
                     tag_name: "Ok".into(),
                     arguments: vec![(field_var, Loc::at_zero(Pattern::Identifier(*symbol)))],
@@ -873,11 +1300,11 @@ fn decoder_record_finalizer(
 
         body = Expr::When {
             loc_cond: Box::new(Loc::at_zero(cond_expr)),
-            cond_var: result_field_var,
+            cond_var,
             expr_var: return_type_var,
             region: Region::zero(),
             branches: vec![ok_branch, err_branch],
-            branches_cond_var: result_field_var,
+            branches_cond_var: cond_var,
             exhaustive: ExhaustiveMark::known_exhaustive(),
         };
     }
@@ -891,8 +1318,23 @@ fn decoder_record_finalizer(
         ambient_function: function_var,
     };
     let closure_type = synth_var(env.subs, Content::LambdaSet(lambda_set));
+
+    let mut arguments = vec![(
+        state_record_var,
+        AnnotatedMark::known_exhaustive(),
+        Loc::at_zero(Pattern::Identifier(state_arg_symbol)),
+    )];
+
+    if let Some((fmt_symbol, fmt_var)) = fmt_arg {
+        arguments.push((
+            fmt_var,
+            AnnotatedMark::known_exhaustive(),
+            Loc::at_zero(Pattern::Identifier(fmt_symbol)),
+        ));
+    }
+
     let flat_type = FlatType::Func(
-        SubsSlice::insert_into_subs(env.subs, [state_record_var]),
+        SubsSlice::insert_into_subs(env.subs, arguments.iter().map(|(var, _, _)| *var)),
         closure_type,
         return_type_var,
     );
@@ -908,17 +1350,177 @@ fn decoder_record_finalizer(
         name: function_symbol,
         captured_symbols: Vec::new(),
         recursive: Recursive::NotRecursive,
-        arguments: vec![(
-            state_record_var,
-            AnnotatedMark::known_exhaustive(),
-            Loc::at_zero(Pattern::Identifier(state_arg_symbol)),
-        )],
+        arguments,
         loc_body: Box::new(Loc::at_zero(body)),
     });
 
     (finalizer, function_var, decode_err_var)
 }
 
+// Example:
+// when rec.first is
+//     Ok first -> Ok first
+//     _ ->
+//         when Decode.decodeWith [] Decode.decoder fmt is
+//             decRec -> decRec.result
+//
+// A field that was never stepped is decoded from no bytes at all, so that the format can decide
+// whether it may be missing.
+fn decoder_record_field_or_missing(
+    env: &mut Env,
+    field_access: (Expr, Variable),
+    field_name: &Lowercase,
+    field_var: Variable,
+    decode_err_var: Variable,
+    fmt_arg: (Symbol, Variable),
+) -> (Expr, Variable) {
+    let (field_access, result_field_var) = field_access;
+    let (fmt_arg_symbol, fmt_arg_var) = fmt_arg;
+    let present_symbol = env.new_symbol(field_name.as_str());
+    let rec_symbol = env.new_symbol("decRec");
+
+    // Result a DecodeError
+    let decoded_var = {
+        let flat_type = FlatType::TagUnion(
+            UnionTags::for_result(env.subs, field_var, decode_err_var),
+            Variable::EMPTY_TAG_UNION,
+        );
+
+        synth_var(env.subs, Content::Structure(flat_type))
+    };
+
+    // Ok first -> Ok first
+    let present_branch = WhenBranch {
+        patterns: vec![WhenBranchPattern {
+            pattern: Loc::at_zero(Pattern::AppliedTag {
+                whole_var: result_field_var,
+                ext_var: Variable::EMPTY_TAG_UNION,
+                tag_name: "Ok".into(),
+                arguments: vec![(field_var, Loc::at_zero(Pattern::Identifier(present_symbol)))],
+            }),
+            degenerate: false,
+        }],
+        value: Loc::at_zero(Expr::Tag {
+            tag_union_var: decoded_var,
+            ext_var: env.new_ext_var(ExtensionKind::TagUnion),
+            name: "Ok".into(),
+            arguments: vec![(field_var, Loc::at_zero(Expr::Var(present_symbol)))],
+        }),
+        guard: None,
+        redundant: RedundantMark::known_non_redundant(),
+    };
+
+    // decRec : { rest: List U8, result: Result a DecodeError }
+    let rec_var = {
+        let fields = RecordFields::insert_into_subs(
+            env.subs,
+            [
+                ("rest".into(), RecordField::Required(Variable::LIST_U8)),
+                ("result".into(), RecordField::Required(decoded_var)),
+            ],
+        );
+        let record = FlatType::Record(fields, Variable::EMPTY_RECORD);
+
+        synth_var(env.subs, Content::Structure(record))
+    };
+
+    // Decode.decodeWith [] Decode.decoder fmt
+    let decode_missing = {
+        let decoder_var = env.import_builtin_symbol_var(Symbol::DECODE_DECODER);
+        let decode_with_var = env.import_builtin_symbol_var(Symbol::DECODE_DECODE_WITH);
+        let lambda_set_var = env.subs.fresh_unnamed_flex_var();
+        let this_decode_with_var = {
+            let subs_slice = SubsSlice::insert_into_subs(
+                env.subs,
+                [Variable::LIST_U8, decoder_var, fmt_arg_var],
+            );
+            let this_decode_with_var = synth_var(
+                env.subs,
+                Content::Structure(FlatType::Func(subs_slice, lambda_set_var, rec_var)),
+            );
+
+            env.unify(decode_with_var, this_decode_with_var);
+
+            this_decode_with_var
+        };
+
+        Expr::Call(
+            Box::new((
+                this_decode_with_var,
+                Loc::at_zero(Expr::Var(Symbol::DECODE_DECODE_WITH)),
+                lambda_set_var,
+                rec_var,
+            )),
+            vec![
+                (
+                    Variable::LIST_U8,
+                    Loc::at_zero(Expr::List {
+                        elem_var: Variable::U8,
+                        loc_elems: Vec::new(),
+                    }),
+                ),
+                (
+                    decoder_var,
+                    Loc::at_zero(Expr::AbilityMember(
+                        Symbol::DECODE_DECODER,
+                        None,
+                        decoder_var,
+                    )),
+                ),
+                (fmt_arg_var, Loc::at_zero(Expr::Var(fmt_arg_symbol))),
+            ],
+            CalledVia::Space,
+        )
+    };
+
+    // _ ->
+    //     when Decode.decodeWith [] Decode.decoder fmt is
+    //         decRec -> decRec.result
+    let missing_branch = WhenBranch {
+        patterns: vec![WhenBranchPattern {
+            pattern: Loc::at_zero(Pattern::Underscore),
+            degenerate: false,
+        }],
+        value: Loc::at_zero(Expr::When {
+            loc_cond: Box::new(Loc::at_zero(decode_missing)),
+            cond_var: rec_var,
+            expr_var: decoded_var,
+            region: Region::zero(),
+            branches: vec![WhenBranch {
+                patterns: vec![WhenBranchPattern {
+                    pattern: Loc::at_zero(Pattern::Identifier(rec_symbol)),
+                    degenerate: false,
+                }],
+                value: Loc::at_zero(Expr::Access {
+                    record_var: rec_var,
+                    ext_var: env.new_ext_var(ExtensionKind::Record),
+                    field_var: decoded_var,
+                    loc_expr: Box::new(Loc::at_zero(Expr::Var(rec_symbol))),
+                    field: "result".into(),
+                }),
+                guard: None,
+                redundant: RedundantMark::known_non_redundant(),
+            }],
+            branches_cond_var: rec_var,
+            exhaustive: ExhaustiveMark::known_exhaustive(),
+        }),
+        guard: None,
+        redundant: RedundantMark::known_non_redundant(),
+    };
+
+    let when_present_or_missing = Expr::When {
+        loc_cond: Box::new(Loc::at_zero(field_access)),
+        cond_var: result_field_var,
+        expr_var: decoded_var,
+        region: Region::zero(),
+        branches: vec![present_branch, missing_branch],
+        branches_cond_var: result_field_var,
+        exhaustive: ExhaustiveMark::known_exhaustive(),
+    };
+
+    (when_present_or_missing, decoded_var)
+}
+
 // Example:
 // initialState : {first: Result a [NoField], second: Result b [NoField]}
 // initialState = {first: Err NoField, second: Err NoField}
@@ -0,0 +1,527 @@
+//! Derivers for the `Eq` ability.
+
+use std::iter::once;
+
+use roc_can::expr::{AnnotatedMark, ClosureData, Expr, Recursive, WhenBranch, WhenBranchPattern};
+use roc_can::pattern::Pattern;
+use roc_derive_key::eq::FlatEqKey;
+use roc_module::called_via::CalledVia;
+use roc_module::ident::{Lowercase, TagName};
+use roc_module::symbol::Symbol;
+use roc_region::all::{Loc, Region};
+use roc_types::subs::{
+    Content, ExhaustiveMark, FlatType, GetSubsSlice, LambdaSet, OptVariable, RecordFields,
+    RedundantMark, SubsSlice, UnionLambdas, UnionTags, Variable, VariableSubsSlice,
+};
+use roc_types::types::RecordField;
+
+use crate::util::{Env, ExtensionKind};
+use crate::{synth_var, DerivedBody};
+
+pub(crate) fn derive_is_eq(env: &mut Env<'_>, key: FlatEqKey, def_symbol: Symbol) -> DerivedBody {
+    let (body, body_type) = match key {
+        FlatEqKey::List() => is_eq_list(env, def_symbol),
+        FlatEqKey::Record(fields) => {
+            if fields.is_empty() {
+                is_eq_empty(env, Variable::EMPTY_RECORD, def_symbol)
+            } else {
+                // Generalized record var so we can reuse this impl between many records:
+                // if fields = { a, b }, this is { a: t1, b: t2 } for fresh t1, t2.
+                let flex_fields = fields
+                    .into_iter()
+                    .map(|name| {
+                        (
+                            name,
+                            RecordField::Required(env.subs.fresh_unnamed_flex_var()),
+                        )
+                    })
+                    .collect::<Vec<(Lowercase, _)>>();
+                let fields = RecordFields::insert_into_subs(env.subs, flex_fields);
+                let record_var = synth_var(
+                    env.subs,
+                    Content::Structure(FlatType::Record(
+                        fields,
+                        env.new_ext_var(ExtensionKind::Record),
+                    )),
+                );
+
+                is_eq_record(env, record_var, fields, def_symbol)
+            }
+        }
+        FlatEqKey::Tuple(arity) => {
+            if arity == 0 {
+                is_eq_empty(env, Variable::EMPTY_TUPLE, def_symbol)
+            } else {
+                // Generalized tuple var so we can reuse this impl between many tuples:
+                // if arity = 2, this is (t1, t2) for fresh t1, t2.
+                let flex_elems = (0..arity)
+                    .map(|_| env.subs.fresh_unnamed_flex_var())
+                    .collect::<Vec<_>>();
+                let elems = VariableSubsSlice::insert_into_subs(env.subs, flex_elems);
+                let tuple_var = synth_var(
+                    env.subs,
+                    Content::Structure(FlatType::Tuple(
+                        elems,
+                        env.new_ext_var(ExtensionKind::Tuple),
+                    )),
+                );
+
+                is_eq_tuple(env, tuple_var, elems, def_symbol)
+            }
+        }
+        FlatEqKey::TagUnion(tags) => {
+            if tags.is_empty() {
+                is_eq_empty(env, Variable::EMPTY_TAG_UNION, def_symbol)
+            } else {
+                // Generalized tag union var so we can reuse this impl between many unions:
+                // if tags = [ A arity=2, B arity=1 ], this is [ A t1 t2, B t3 ] for fresh t1, t2, t3
+                let flex_tag_labels = tags
+                    .into_iter()
+                    .map(|(label, arity)| {
+                        let variables_slice =
+                            VariableSubsSlice::reserve_into_subs(env.subs, arity.into());
+                        for var_index in variables_slice {
+                            env.subs[var_index] = env.subs.fresh_unnamed_flex_var();
+                        }
+                        (label, variables_slice)
+                    })
+                    .collect::<Vec<_>>();
+                let union_tags = UnionTags::insert_slices_into_subs(env.subs, flex_tag_labels);
+                let tag_union_var = synth_var(
+                    env.subs,
+                    Content::Structure(FlatType::TagUnion(
+                        union_tags,
+                        env.new_ext_var(ExtensionKind::TagUnion),
+                    )),
+                );
+
+                is_eq_tag_union(env, tag_union_var, union_tags, def_symbol)
+            }
+        }
+    };
+
+    let specialization_lambda_sets =
+        env.get_specialization_lambda_sets(body_type, Symbol::BOOL_IS_EQ);
+
+    DerivedBody {
+        body,
+        body_type,
+        specialization_lambda_sets,
+    }
+}
+
+fn is_eq_list(env: &mut Env<'_>, fn_name: Symbol) -> (Expr, Variable) {
+    // Build \lst1, lst2 -> List.isEqElems lst1 lst2
+
+    let lst1_sym = env.new_symbol("lst1");
+    let lst2_sym = env.new_symbol("lst2");
+
+    // List elem
+    let elem_var = env.subs.fresh_unnamed_flex_var();
+    let elem_var_slice = SubsSlice::insert_into_subs(env.subs, [elem_var]);
+    let list_var = synth_var(
+        env.subs,
+        Content::Structure(FlatType::Apply(Symbol::LIST_LIST, elem_var_slice)),
+    );
+
+    // List.isEqElems lst1 lst2
+    let body = call_eq_fn(
+        env,
+        Symbol::LIST_IS_EQ_ELEMS,
+        list_var,
+        Expr::Var(lst1_sym),
+        Expr::Var(lst2_sym),
+    );
+
+    build_outer_derived_closure(
+        env,
+        fn_name,
+        list_var,
+        (Pattern::Identifier(lst1_sym), Pattern::Identifier(lst2_sym)),
+        body,
+    )
+}
+
+fn is_eq_empty(env: &mut Env<'_>, unit_var: Variable, fn_name: Symbol) -> (Expr, Variable) {
+    // Build \_, _ -> True
+
+    build_outer_derived_closure(
+        env,
+        fn_name,
+        unit_var,
+        (Pattern::Underscore, Pattern::Underscore),
+        bool_tag(true),
+    )
+}
+
+fn is_eq_record(
+    env: &mut Env<'_>,
+    record_var: Variable,
+    fields: RecordFields,
+    fn_name: Symbol,
+) -> (Expr, Variable) {
+    // Suppose rcd = { a: t1, b: t2 }. Build
+    //
+    // \rcd1, rcd2 -> Bool.and (Bool.isEq rcd1.a rcd2.a) (Bool.isEq rcd1.b rcd2.b)
+
+    let rcd1_sym = env.new_symbol("rcd1");
+    let rcd2_sym = env.new_symbol("rcd2");
+
+    let field_comparisons = fields
+        .iter_all()
+        .map(|(field_name_index, field_var_index, _)| {
+            let field_name = env.subs[field_name_index].clone();
+            let field_var = env.subs[field_var_index];
+
+            // rcd1.a, rcd2.a
+            let mut field_access = |rcd_sym| Expr::Access {
+                record_var,
+                ext_var: env.subs.fresh_unnamed_flex_var(),
+                field_var,
+                loc_expr: Box::new(Loc::at_zero(Expr::Var(rcd_sym))),
+                field: field_name.clone(),
+            };
+            let lhs = field_access(rcd1_sym);
+            let rhs = field_access(rcd2_sym);
+
+            // Bool.isEq rcd1.a rcd2.a
+            call_eq_fn(env, Symbol::BOOL_IS_EQ, field_var, lhs, rhs)
+        })
+        .collect::<Vec<_>>();
+
+    let body = and_all(env, field_comparisons);
+
+    build_outer_derived_closure(
+        env,
+        fn_name,
+        record_var,
+        (Pattern::Identifier(rcd1_sym), Pattern::Identifier(rcd2_sym)),
+        body,
+    )
+}
+
+fn is_eq_tuple(
+    env: &mut Env<'_>,
+    tuple_var: Variable,
+    elems: VariableSubsSlice,
+    fn_name: Symbol,
+) -> (Expr, Variable) {
+    // Suppose tup = (t1, t2). Build
+    //
+    // \tup1, tup2 -> Bool.and (Bool.isEq tup1.0 tup2.0) (Bool.isEq tup1.1 tup2.1)
+
+    let tup1_sym = env.new_symbol("tup1");
+    let tup2_sym = env.new_symbol("tup2");
+
+    let elem_vars = env.subs.get_subs_slice(elems).to_vec();
+
+    let elem_comparisons = elem_vars
+        .iter()
+        .enumerate()
+        .map(|(index, &elem_var)| {
+            // tup1.0, tup2.0
+            let mut tuple_access = |tup_sym| Expr::TupleAccess {
+                tuple_var,
+                ext_var: env.subs.fresh_unnamed_flex_var(),
+                prefix_vars: elem_vars[..index].to_vec(),
+                elem_var,
+                loc_expr: Box::new(Loc::at_zero(Expr::Var(tup_sym))),
+                index,
+            };
+            let lhs = tuple_access(tup1_sym);
+            let rhs = tuple_access(tup2_sym);
+
+            // Bool.isEq tup1.0 tup2.0
+            call_eq_fn(env, Symbol::BOOL_IS_EQ, elem_var, lhs, rhs)
+        })
+        .collect::<Vec<_>>();
+
+    let body = and_all(env, elem_comparisons);
+
+    build_outer_derived_closure(
+        env,
+        fn_name,
+        tuple_var,
+        (Pattern::Identifier(tup1_sym), Pattern::Identifier(tup2_sym)),
+        body,
+    )
+}
+
+fn is_eq_tag_union(
+    env: &mut Env<'_>,
+    tag_union_var: Variable,
+    tags: UnionTags,
+    fn_name: Symbol,
+) -> (Expr, Variable) {
+    // Suppose tag = [ A t1 t2, B t3 ]. Build
+    //
+    // \union1, union2 -> when union1 is
+    //     A x1 x2 ->
+    //         when union2 is
+    //             A y1 y2 -> Bool.and (Bool.isEq x1 y1) (Bool.isEq x2 y2)
+    //             _ -> False
+    //     B x3 ->
+    //         when union2 is
+    //             B y3 -> Bool.isEq x3 y3
+    //             _ -> False
+    //
+    // The fallthrough branches are only needed when there is more than one tag.
+
+    let union1_sym = env.new_symbol("union1");
+    let union2_sym = env.new_symbol("union2");
+
+    let num_tags = tags.len();
+
+    let branches = tags
+        .iter_all()
+        .map(|(tag_name_index, tag_vars_slice_index)| {
+            // A
+            let tag_name = env.subs[tag_name_index].clone();
+            let vars_slice = env.subs[tag_vars_slice_index];
+            // t1 t2
+            let payload_vars = env.subs.get_subs_slice(vars_slice).to_vec();
+
+            // `A x1 x2` and `A y1 y2` patterns
+            let (lhs_pattern, lhs_syms) = tag_pattern(env, tag_union_var, &tag_name, &payload_vars);
+            let (rhs_pattern, rhs_syms) = tag_pattern(env, tag_union_var, &tag_name, &payload_vars);
+
+            // Bool.and (Bool.isEq x1 y1) (Bool.isEq x2 y2)
+            let payload_comparisons = (lhs_syms.into_iter())
+                .zip(rhs_syms.into_iter())
+                .zip(payload_vars.iter())
+                .map(|((lhs, rhs), &payload_var)| {
+                    call_eq_fn(
+                        env,
+                        Symbol::BOOL_IS_EQ,
+                        payload_var,
+                        Expr::Var(lhs),
+                        Expr::Var(rhs),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let payloads_eq = and_all(env, payload_comparisons);
+
+            let mut inner_branches = vec![when_branch(rhs_pattern, payloads_eq)];
+            if num_tags > 1 {
+                // _ -> False
+                inner_branches.push(when_branch(Pattern::Underscore, bool_tag(false)));
+            }
+
+            // when union2 is
+            //     A y1 y2 -> ..
+            //     _ -> False
+            let inner_when = Expr::When {
+                loc_cond: Box::new(Loc::at_zero(Expr::Var(union2_sym))),
+                cond_var: tag_union_var,
+                expr_var: Variable::BOOL,
+                region: Region::zero(),
+                branches: inner_branches,
+                branches_cond_var: tag_union_var,
+                exhaustive: ExhaustiveMark::known_exhaustive(),
+            };
+
+            when_branch(lhs_pattern, inner_when)
+        })
+        .collect::<Vec<_>>();
+
+    // when union1 is
+    //     A x1 x2 -> ..
+    //     B x3 -> ..
+    let when_branches = Expr::When {
+        loc_cond: Box::new(Loc::at_zero(Expr::Var(union1_sym))),
+        cond_var: tag_union_var,
+        expr_var: Variable::BOOL,
+        region: Region::zero(),
+        branches,
+        branches_cond_var: tag_union_var,
+        exhaustive: ExhaustiveMark::known_exhaustive(),
+    };
+
+    build_outer_derived_closure(
+        env,
+        fn_name,
+        tag_union_var,
+        (
+            Pattern::Identifier(union1_sym),
+            Pattern::Identifier(union2_sym),
+        ),
+        when_branches,
+    )
+}
+
+/// Build the pattern `A v1 v2` for fresh symbols `v1`, `v2`, returning the pattern and symbols.
+fn tag_pattern(
+    env: &mut Env<'_>,
+    tag_union_var: Variable,
+    tag_name: &TagName,
+    payload_vars: &[Variable],
+) -> (Pattern, Vec<Symbol>) {
+    let payload_syms: Vec<_> = std::iter::repeat_with(|| env.unique_symbol())
+        .take(payload_vars.len())
+        .collect();
+
+    let pattern = Pattern::AppliedTag {
+        whole_var: tag_union_var,
+        tag_name: tag_name.clone(),
+        ext_var: Variable::EMPTY_TAG_UNION,
+        arguments: (payload_vars.iter())
+            .zip(payload_syms.iter())
+            .map(|(var, sym)| (*var, Loc::at_zero(Pattern::Identifier(*sym))))
+            .collect(),
+    };
+
+    (pattern, payload_syms)
+}
+
+fn when_branch(pattern: Pattern, body: Expr) -> WhenBranch {
+    WhenBranch {
+        patterns: vec![WhenBranchPattern {
+            pattern: Loc::at_zero(pattern),
+            degenerate: false,
+        }],
+        value: Loc::at_zero(body),
+        guard: None,
+        redundant: RedundantMark::known_non_redundant(),
+    }
+}
+
+/// `True` or `False`.
+fn bool_tag(value: bool) -> Expr {
+    let name = if value { "True" } else { "False" };
+
+    Expr::Tag {
+        tag_union_var: Variable::BOOL,
+        ext_var: Variable::EMPTY_TAG_UNION,
+        name: TagName(name.into()),
+        arguments: vec![],
+    }
+}
+
+/// Build `Bool.and (Bool.and e1 e2) e3` from `[e1, e2, e3]`, or `True` if there is nothing to
+/// compare.
+fn and_all(env: &mut Env<'_>, comparisons: Vec<Expr>) -> Expr {
+    let mut comparisons = comparisons.into_iter();
+
+    let first = match comparisons.next() {
+        Some(first) => first,
+        None => return bool_tag(true),
+    };
+
+    comparisons.fold(first, |lhs, rhs| {
+        call_bool_fn(
+            env,
+            Symbol::BOOL_AND,
+            [(Variable::BOOL, lhs), (Variable::BOOL, rhs)],
+        )
+    })
+}
+
+/// Build `eq_fn lhs rhs`, where `eq_fn : val, val -> Bool`.
+fn call_eq_fn(env: &mut Env<'_>, eq_fn: Symbol, val_var: Variable, lhs: Expr, rhs: Expr) -> Expr {
+    call_bool_fn(env, eq_fn, [(val_var, lhs), (val_var, rhs)])
+}
+
+/// Build `bool_fn arg1 arg2`, where `bool_fn : arg1, arg2 -> Bool`.
+fn call_bool_fn(
+    env: &mut Env<'_>,
+    bool_fn: Symbol,
+    [(arg1_var, arg1), (arg2_var, arg2)]: [(Variable, Expr); 2],
+) -> Expr {
+    // build `bool_fn arg1 arg2` type
+    // expected: arg1, arg2 -[uls]-> Bool
+    let exposed_bool_fn_var = env.import_builtin_symbol_var(bool_fn);
+
+    // wanted: arg1_var, arg2_var -[clos]-> Bool
+    let this_arguments_slice = VariableSubsSlice::insert_into_subs(env.subs, [arg1_var, arg2_var]);
+    let this_bool_fn_clos_var = env.subs.fresh_unnamed_flex_var();
+    let this_bool_fn_var = synth_var(
+        env.subs,
+        Content::Structure(FlatType::Func(
+            this_arguments_slice,
+            this_bool_fn_clos_var,
+            Variable::BOOL,
+        )),
+    );
+
+    //   arg1,     arg2     -[uls]->  Bool
+    // ~ arg1_var, arg2_var -[clos]-> Bool
+    env.unify(exposed_bool_fn_var, this_bool_fn_var);
+
+    // `isEq` is an ability member, and must be resolved to a specialization; everything else is a
+    // plain function.
+    let bool_fn_head = match bool_fn {
+        Symbol::BOOL_IS_EQ => Expr::AbilityMember(bool_fn, None, this_bool_fn_var),
+        _ => Expr::Var(bool_fn),
+    };
+    let bool_fn_data = Box::new((
+        this_bool_fn_var,
+        Loc::at_zero(bool_fn_head),
+        this_bool_fn_clos_var,
+        Variable::BOOL,
+    ));
+
+    Expr::Call(
+        bool_fn_data,
+        vec![
+            (arg1_var, Loc::at_zero(arg1)),
+            (arg2_var, Loc::at_zero(arg2)),
+        ],
+        CalledVia::Space,
+    )
+}
+
+/// Build `\lhs, rhs -[fn_name]-> body`.
+fn build_outer_derived_closure(
+    env: &mut Env<'_>,
+    fn_name: Symbol,
+    val_var: Variable,
+    (lhs_pattern, rhs_pattern): (Pattern, Pattern),
+    body: Expr,
+) -> (Expr, Variable) {
+    // Create fn_var for ambient capture; we fix it up below.
+    let fn_var = synth_var(env.subs, Content::Error);
+
+    // -[fn_name]->
+    let fn_name_labels = UnionLambdas::insert_into_subs(env.subs, once((fn_name, vec![])));
+    let fn_clos_var = synth_var(
+        env.subs,
+        Content::LambdaSet(LambdaSet {
+            solved: fn_name_labels,
+            recursion_var: OptVariable::NONE,
+            unspecialized: SubsSlice::default(),
+            ambient_function: fn_var,
+        }),
+    );
+
+    // val, val -[fn_name]-> Bool
+    let args_slice = SubsSlice::insert_into_subs(env.subs, [val_var, val_var]);
+    env.subs.set_content(
+        fn_var,
+        Content::Structure(FlatType::Func(args_slice, fn_clos_var, Variable::BOOL)),
+    );
+
+    // \lhs, rhs -[fn_name]-> body
+    let clos = Expr::Closure(ClosureData {
+        function_type: fn_var,
+        closure_type: fn_clos_var,
+        return_type: Variable::BOOL,
+        name: fn_name,
+        captured_symbols: vec![],
+        recursive: Recursive::NotRecursive,
+        arguments: vec![
+            (
+                val_var,
+                AnnotatedMark::known_exhaustive(),
+                Loc::at_zero(lhs_pattern),
+            ),
+            (
+                val_var,
+                AnnotatedMark::known_exhaustive(),
+                Loc::at_zero(rhs_pattern),
+            ),
+        ],
+        loc_body: Box::new(Loc::at_zero(body)),
+    });
+
+    (clos, fn_var)
+}
@@ -0,0 +1,514 @@
+//! Derivers for the `Generator` ability.
+
+use std::iter::once;
+
+use roc_can::expr::{
+    AnnotatedMark, ClosureData, Expr, Field, IntValue, Recursive, WhenBranch, WhenBranchPattern,
+};
+use roc_can::pattern::Pattern;
+use roc_collections::SendMap;
+use roc_derive_key::generating::FlatGeneratorKey;
+use roc_module::called_via::CalledVia;
+use roc_module::ident::{Lowercase, TagName};
+use roc_module::symbol::Symbol;
+use roc_region::all::{Loc, Region};
+use roc_types::num::{IntBound, IntLitWidth};
+use roc_types::subs::{
+    Content, ExhaustiveMark, FlatType, GetSubsSlice, LambdaSet, OptVariable, RecordFields,
+    RedundantMark, SubsSlice, UnionLambdas, UnionTags, Variable, VariableSubsSlice,
+};
+use roc_types::types::RecordField;
+
+use crate::util::{Env, ExtensionKind};
+use crate::{synth_var, DerivedBody};
+
+pub(crate) fn derive_generate(
+    env: &mut Env<'_>,
+    key: FlatGeneratorKey,
+    def_symbol: Symbol,
+) -> DerivedBody {
+    let (body, body_type) = match key {
+        FlatGeneratorKey::List() => generate_list(env, def_symbol),
+        FlatGeneratorKey::Record(fields) => {
+            if fields.is_empty() {
+                generate_unit(env, Variable::EMPTY_RECORD, Expr::EmptyRecord, def_symbol)
+            } else {
+                // Generalized record var so we can reuse this impl between many records:
+                // if fields = { a, b }, this is { a: t1, b: t2 } for fresh t1, t2.
+                let flex_fields = fields
+                    .into_iter()
+                    .map(|name| {
+                        (
+                            name,
+                            RecordField::Required(env.subs.fresh_unnamed_flex_var()),
+                        )
+                    })
+                    .collect::<Vec<(Lowercase, _)>>();
+                let fields = RecordFields::insert_into_subs(env.subs, flex_fields);
+                let record_var = synth_var(
+                    env.subs,
+                    Content::Structure(FlatType::Record(
+                        fields,
+                        env.new_ext_var(ExtensionKind::Record),
+                    )),
+                );
+
+                generate_record(env, record_var, fields, def_symbol)
+            }
+        }
+        FlatGeneratorKey::Tuple(arity) => {
+            if arity == 0 {
+                let unit = Expr::Tuple {
+                    tuple_var: Variable::EMPTY_TUPLE,
+                    elems: vec![],
+                };
+
+                generate_unit(env, Variable::EMPTY_TUPLE, unit, def_symbol)
+            } else {
+                // Generalized tuple var so we can reuse this impl between many tuples:
+                // if arity = 2, this is (t1, t2) for fresh t1, t2.
+                let flex_elems = (0..arity)
+                    .map(|_| env.subs.fresh_unnamed_flex_var())
+                    .collect::<Vec<_>>();
+                let elems = VariableSubsSlice::insert_into_subs(env.subs, flex_elems);
+                let tuple_var = synth_var(
+                    env.subs,
+                    Content::Structure(FlatType::Tuple(
+                        elems,
+                        env.new_ext_var(ExtensionKind::Tuple),
+                    )),
+                );
+
+                generate_tuple(env, tuple_var, elems, def_symbol)
+            }
+        }
+        FlatGeneratorKey::TagUnion(tags) => {
+            // Generalized tag union var so we can reuse this impl between many unions:
+            // if tags = [ A arity=2, B arity=1 ], this is [ A t1 t2, B t3 ] for fresh t1, t2, t3
+            let flex_tag_labels = tags
+                .into_iter()
+                .map(|(label, arity)| {
+                    let variables_slice =
+                        VariableSubsSlice::reserve_into_subs(env.subs, arity.into());
+                    for var_index in variables_slice {
+                        env.subs[var_index] = env.subs.fresh_unnamed_flex_var();
+                    }
+                    (label, variables_slice)
+                })
+                .collect::<Vec<_>>();
+            let union_tags = UnionTags::insert_slices_into_subs(env.subs, flex_tag_labels);
+            let tag_union_var = synth_var(
+                env.subs,
+                Content::Structure(FlatType::TagUnion(
+                    union_tags,
+                    env.new_ext_var(ExtensionKind::TagUnion),
+                )),
+            );
+
+            generate_tag_union(env, tag_union_var, union_tags, def_symbol)
+        }
+    };
+
+    let specialization_lambda_sets =
+        env.get_specialization_lambda_sets(body_type, Symbol::GENERATE_GENERATE);
+
+    DerivedBody {
+        body,
+        body_type,
+        specialization_lambda_sets,
+    }
+}
+
+/// The `rng` and `size` arguments of a derived generator.
+struct GenerateArgs {
+    rng_sym: Symbol,
+    rng_var: Variable,
+    size_sym: Symbol,
+    size_var: Variable,
+}
+
+impl GenerateArgs {
+    fn new(env: &mut Env<'_>) -> Self {
+        Self {
+            rng_sym: env.new_symbol("rng"),
+            rng_var: env.subs.fresh_unnamed_flex_var(),
+            size_sym: env.new_symbol("size"),
+            size_var: env.subs.fresh_unnamed_flex_var(),
+        }
+    }
+
+    /// Build `Generate.split rng index`
+    fn split_rng(&self, env: &mut Env<'_>, index: u64) -> Expr {
+        call_generate_fn(
+            env,
+            Symbol::GENERATE_SPLIT,
+            vec![
+                (self.rng_var, Expr::Var(self.rng_sym)),
+                (Variable::U64, u64_literal(index)),
+            ],
+            self.rng_var,
+        )
+    }
+
+    /// Build `Generate.generate (Generate.split rng index) size`
+    fn generate_part(&self, env: &mut Env<'_>, index: u64, size: Expr, val_var: Variable) -> Expr {
+        let rng = self.split_rng(env, index);
+
+        call_generate_fn(
+            env,
+            Symbol::GENERATE_GENERATE,
+            vec![(self.rng_var, rng), (self.size_var, size)],
+            val_var,
+        )
+    }
+}
+
+fn generate_list(env: &mut Env<'_>, fn_name: Symbol) -> (Expr, Variable) {
+    // Build \rng, size -> Generate.list rng size
+
+    let args = GenerateArgs::new(env);
+
+    // List elem
+    let elem_var = env.subs.fresh_unnamed_flex_var();
+    let elem_var_slice = SubsSlice::insert_into_subs(env.subs, [elem_var]);
+    let list_var = synth_var(
+        env.subs,
+        Content::Structure(FlatType::Apply(Symbol::LIST_LIST, elem_var_slice)),
+    );
+
+    // Generate.list rng size
+    let body = call_generate_fn(
+        env,
+        Symbol::GENERATE_LIST,
+        vec![
+            (args.rng_var, Expr::Var(args.rng_sym)),
+            (args.size_var, Expr::Var(args.size_sym)),
+        ],
+        list_var,
+    );
+
+    build_outer_derived_closure(env, fn_name, &args, list_var, body)
+}
+
+fn generate_unit(
+    env: &mut Env<'_>,
+    unit_var: Variable,
+    unit: Expr,
+    fn_name: Symbol,
+) -> (Expr, Variable) {
+    // Build \_rng, _size -> {}
+
+    let args = GenerateArgs::new(env);
+
+    build_outer_derived_closure(env, fn_name, &args, unit_var, unit)
+}
+
+fn generate_record(
+    env: &mut Env<'_>,
+    record_var: Variable,
+    fields: RecordFields,
+    fn_name: Symbol,
+) -> (Expr, Variable) {
+    // Suppose rcd = { a: t1, b: t2 }. Build
+    //
+    // \rng, size -> {
+    //     a: Generate.generate (Generate.split rng 0) size,
+    //     b: Generate.generate (Generate.split rng 1) size,
+    // }
+
+    let args = GenerateArgs::new(env);
+
+    let mut fields_map = SendMap::default();
+
+    for (index, (field_name_index, field_var_index, _)) in fields.iter_all().enumerate() {
+        let field_name = env.subs[field_name_index].clone();
+        let field_var = env.subs[field_var_index];
+
+        let size = Expr::Var(args.size_sym);
+        let field_expr = args.generate_part(env, index as u64, size, field_var);
+
+        fields_map.insert(
+            field_name,
+            Field {
+                var: field_var,
+                region: Region::zero(),
+                loc_expr: Box::new(Loc::at_zero(field_expr)),
+            },
+        );
+    }
+
+    let body = Expr::Record {
+        record_var,
+        fields: fields_map,
+    };
+
+    build_outer_derived_closure(env, fn_name, &args, record_var, body)
+}
+
+fn generate_tuple(
+    env: &mut Env<'_>,
+    tuple_var: Variable,
+    elems: VariableSubsSlice,
+    fn_name: Symbol,
+) -> (Expr, Variable) {
+    // Suppose tup = (t1, t2). Build
+    //
+    // \rng, size -> (
+    //     Generate.generate (Generate.split rng 0) size,
+    //     Generate.generate (Generate.split rng 1) size,
+    // )
+
+    let args = GenerateArgs::new(env);
+
+    let elem_vars = env.subs.get_subs_slice(elems).to_vec();
+
+    let elems = elem_vars
+        .iter()
+        .enumerate()
+        .map(|(index, &elem_var)| {
+            let size = Expr::Var(args.size_sym);
+            let elem_expr = args.generate_part(env, index as u64, size, elem_var);
+
+            (elem_var, Loc::at_zero(elem_expr))
+        })
+        .collect();
+
+    let body = Expr::Tuple { tuple_var, elems };
+
+    build_outer_derived_closure(env, fn_name, &args, tuple_var, body)
+}
+
+fn generate_tag_union(
+    env: &mut Env<'_>,
+    tag_union_var: Variable,
+    tags: UnionTags,
+    fn_name: Symbol,
+) -> (Expr, Variable) {
+    // Suppose tag = [ A t1 t2, B t3 ]. Build
+    //
+    // \rng, size -> when Generate.chooseTag (Generate.split rng 0) size 2 1 is
+    //     0 -> A
+    //         (Generate.generate (Generate.split rng 1) (Generate.smaller size))
+    //         (Generate.generate (Generate.split rng 2) (Generate.smaller size))
+    //     _ -> B (Generate.generate (Generate.split rng 1) (Generate.smaller size))
+    //
+    // The tag is picked by its index in the (sorted) key. The last argument to `chooseTag` is
+    // the index of the tag with the fewest payloads, which is picked once the size runs out.
+    // A union with a single tag doesn't need to pick one at all.
+
+    let args = GenerateArgs::new(env);
+
+    let tags: Vec<(TagName, Vec<Variable>)> = tags
+        .iter_all()
+        .map(|(tag_name_index, tag_vars_slice_index)| {
+            let tag_name = env.subs[tag_name_index].clone();
+            let vars_slice = env.subs[tag_vars_slice_index];
+
+            (tag_name, env.subs.get_subs_slice(vars_slice).to_vec())
+        })
+        .collect();
+
+    let num_tags = tags.len();
+    let base_tag = (tags.iter().enumerate())
+        .min_by_key(|(_, (_, payload_vars))| payload_vars.len())
+        .map(|(index, _)| index)
+        .unwrap_or(0);
+
+    let mut tag_exprs = tags
+        .into_iter()
+        .map(|(tag_name, payload_vars)| {
+            // A (Generate.generate (Generate.split rng 1) (Generate.smaller size)) ...
+            let arguments = (payload_vars.iter().enumerate())
+                .map(|(index, &payload_var)| {
+                    let size = call_generate_fn(
+                        env,
+                        Symbol::GENERATE_SMALLER,
+                        vec![(args.size_var, Expr::Var(args.size_sym))],
+                        args.size_var,
+                    );
+                    let payload = args.generate_part(env, index as u64 + 1, size, payload_var);
+
+                    (payload_var, Loc::at_zero(payload))
+                })
+                .collect();
+
+            Expr::Tag {
+                tag_union_var,
+                ext_var: env.new_ext_var(ExtensionKind::TagUnion),
+                name: tag_name,
+                arguments,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let body = if num_tags == 1 {
+        tag_exprs.pop().unwrap()
+    } else {
+        let last_index = num_tags - 1;
+
+        let branches = tag_exprs
+            .into_iter()
+            .enumerate()
+            .map(|(index, tag_expr)| {
+                // `_` for the last tag, so that the `when` is exhaustive
+                let pattern = if index == last_index {
+                    Pattern::Underscore
+                } else {
+                    Pattern::IntLiteral(
+                        Variable::U64,
+                        Variable::U64,
+                        index.to_string().into_boxed_str(),
+                        IntValue::I128((index as i128).to_ne_bytes()),
+                        IntBound::Exact(IntLitWidth::U64),
+                    )
+                };
+
+                WhenBranch {
+                    patterns: vec![WhenBranchPattern {
+                        pattern: Loc::at_zero(pattern),
+                        degenerate: false,
+                    }],
+                    value: Loc::at_zero(tag_expr),
+                    guard: None,
+                    redundant: RedundantMark::known_non_redundant(),
+                }
+            })
+            .collect();
+
+        // Generate.chooseTag (Generate.split rng 0) size 2 1
+        let chosen_rng = args.split_rng(env, 0);
+        let chosen = call_generate_fn(
+            env,
+            Symbol::GENERATE_CHOOSE_TAG,
+            vec![
+                (args.rng_var, chosen_rng),
+                (args.size_var, Expr::Var(args.size_sym)),
+                (Variable::U64, u64_literal(num_tags as u64)),
+                (Variable::U64, u64_literal(base_tag as u64)),
+            ],
+            Variable::U64,
+        );
+
+        // when Generate.chooseTag ... is
+        //     0 -> A ..
+        //     _ -> B ..
+        Expr::When {
+            loc_cond: Box::new(Loc::at_zero(chosen)),
+            cond_var: Variable::U64,
+            expr_var: tag_union_var,
+            region: Region::zero(),
+            branches,
+            branches_cond_var: Variable::U64,
+            exhaustive: ExhaustiveMark::known_exhaustive(),
+        }
+    };
+
+    build_outer_derived_closure(env, fn_name, &args, tag_union_var, body)
+}
+
+fn u64_literal(value: u64) -> Expr {
+    Expr::Int(
+        Variable::U64,
+        Variable::U64,
+        value.to_string().into_boxed_str(),
+        IntValue::I128((value as i128).to_ne_bytes()),
+        IntBound::Exact(IntLitWidth::U64),
+    )
+}
+
+/// Build `generate_fn arg1 arg2 ...`, where `generate_fn` is exposed by the `Generate` module.
+///
+/// The result has type `ret_var`.
+fn call_generate_fn(
+    env: &mut Env<'_>,
+    generate_fn: Symbol,
+    arguments: Vec<(Variable, Expr)>,
+    ret_var: Variable,
+) -> Expr {
+    // expected: the type `generate_fn` is exposed with, e.g. Rng, U64 -[uls]-> val | val has Generator
+    let exposed_fn_var = env.import_builtin_symbol_var(generate_fn);
+
+    // wanted: arg1_var, arg2_var -[clos]-> ret_var
+    let argument_vars = arguments.iter().map(|(var, _)| *var).collect::<Vec<_>>();
+    let this_arguments_slice = VariableSubsSlice::insert_into_subs(env.subs, argument_vars);
+    let this_clos_var = env.subs.fresh_unnamed_flex_var();
+    let this_fn_var = synth_var(
+        env.subs,
+        Content::Structure(FlatType::Func(this_arguments_slice, this_clos_var, ret_var)),
+    );
+
+    env.unify(exposed_fn_var, this_fn_var);
+
+    // `generate` is the member of the `Generator` ability, and must be resolved to a
+    // specialization; everything else is a plain function.
+    let fn_head = match generate_fn {
+        Symbol::GENERATE_GENERATE => Expr::AbilityMember(generate_fn, None, this_fn_var),
+        _ => Expr::Var(generate_fn),
+    };
+    let fn_data = Box::new((this_fn_var, Loc::at_zero(fn_head), this_clos_var, ret_var));
+
+    Expr::Call(
+        fn_data,
+        arguments
+            .into_iter()
+            .map(|(var, expr)| (var, Loc::at_zero(expr)))
+            .collect(),
+        CalledVia::Space,
+    )
+}
+
+/// Build `\rng, size -[fn_name]-> body`.
+fn build_outer_derived_closure(
+    env: &mut Env<'_>,
+    fn_name: Symbol,
+    args: &GenerateArgs,
+    val_var: Variable,
+    body: Expr,
+) -> (Expr, Variable) {
+    // Create fn_var for ambient capture; we fix it up below.
+    let fn_var = synth_var(env.subs, Content::Error);
+
+    // -[fn_name]->
+    let fn_name_labels = UnionLambdas::insert_into_subs(env.subs, once((fn_name, vec![])));
+    let fn_clos_var = synth_var(
+        env.subs,
+        Content::LambdaSet(LambdaSet {
+            solved: fn_name_labels,
+            recursion_var: OptVariable::NONE,
+            unspecialized: SubsSlice::default(),
+            ambient_function: fn_var,
+        }),
+    );
+
+    // rng, size -[fn_name]-> val
+    let args_slice = SubsSlice::insert_into_subs(env.subs, [args.rng_var, args.size_var]);
+    env.subs.set_content(
+        fn_var,
+        Content::Structure(FlatType::Func(args_slice, fn_clos_var, val_var)),
+    );
+
+    // \rng, size -[fn_name]-> body
+    let clos = Expr::Closure(ClosureData {
+        function_type: fn_var,
+        closure_type: fn_clos_var,
+        return_type: val_var,
+        name: fn_name,
+        captured_symbols: vec![],
+        recursive: Recursive::NotRecursive,
+        arguments: vec![
+            (
+                args.rng_var,
+                AnnotatedMark::known_exhaustive(),
+                Loc::at_zero(Pattern::Identifier(args.rng_sym)),
+            ),
+            (
+                args.size_var,
+                AnnotatedMark::known_exhaustive(),
+                Loc::at_zero(Pattern::Identifier(args.size_sym)),
+            ),
+        ],
+        loc_body: Box::new(Loc::at_zero(body)),
+    });
+
+    (clos, fn_var)
+}
@@ -0,0 +1,528 @@
+//! Derivers for the `Hash` ability.
+
+use std::iter::once;
+
+use roc_can::expr::{
+    AnnotatedMark, ClosureData, Expr, IntValue, Recursive, WhenBranch, WhenBranchPattern,
+};
+use roc_can::pattern::Pattern;
+use roc_derive_key::hash::FlatHashKey;
+use roc_module::called_via::CalledVia;
+use roc_module::ident::Lowercase;
+use roc_module::symbol::Symbol;
+use roc_region::all::{Loc, Region};
+use roc_types::num::{IntBound, IntLitWidth};
+use roc_types::subs::{
+    Content, ExhaustiveMark, FlatType, GetSubsSlice, LambdaSet, OptVariable, RecordFields,
+    RedundantMark, SubsSlice, UnionLambdas, UnionTags, Variable, VariableSubsSlice,
+};
+use roc_types::types::RecordField;
+
+use crate::util::{Env, ExtensionKind};
+use crate::{synth_var, DerivedBody};
+
+pub(crate) fn derive_hash(env: &mut Env<'_>, key: FlatHashKey, def_symbol: Symbol) -> DerivedBody {
+    let (body, body_type) = match key {
+        FlatHashKey::List() => hash_list(env, def_symbol),
+        FlatHashKey::Str() => hash_str(env, def_symbol),
+        FlatHashKey::Record(fields) => {
+            if fields.is_empty() {
+                hash_empty(env, Variable::EMPTY_RECORD, def_symbol)
+            } else {
+                // Generalized record var so we can reuse this impl between many records:
+                // if fields = { a, b }, this is { a: t1, b: t2 } for fresh t1, t2.
+                let flex_fields = fields
+                    .into_iter()
+                    .map(|name| {
+                        (
+                            name,
+                            RecordField::Required(env.subs.fresh_unnamed_flex_var()),
+                        )
+                    })
+                    .collect::<Vec<(Lowercase, _)>>();
+                let fields = RecordFields::insert_into_subs(env.subs, flex_fields);
+                let record_var = synth_var(
+                    env.subs,
+                    Content::Structure(FlatType::Record(
+                        fields,
+                        env.new_ext_var(ExtensionKind::Record),
+                    )),
+                );
+
+                hash_record(env, record_var, fields, def_symbol)
+            }
+        }
+        FlatHashKey::Tuple(arity) => {
+            if arity == 0 {
+                hash_empty(env, Variable::EMPTY_TUPLE, def_symbol)
+            } else {
+                // Generalized tuple var so we can reuse this impl between many tuples:
+                // if arity = 2, this is (t1, t2) for fresh t1, t2.
+                let flex_elems = (0..arity)
+                    .map(|_| env.subs.fresh_unnamed_flex_var())
+                    .collect::<Vec<_>>();
+                let elems = VariableSubsSlice::insert_into_subs(env.subs, flex_elems);
+                let tuple_var = synth_var(
+                    env.subs,
+                    Content::Structure(FlatType::Tuple(
+                        elems,
+                        env.new_ext_var(ExtensionKind::Tuple),
+                    )),
+                );
+
+                hash_tuple(env, tuple_var, elems, def_symbol)
+            }
+        }
+        FlatHashKey::TagUnion(tags) => {
+            if tags.is_empty() {
+                hash_empty(env, Variable::EMPTY_TAG_UNION, def_symbol)
+            } else {
+                // Generalized tag union var so we can reuse this impl between many unions:
+                // if tags = [ A arity=2, B arity=1 ], this is [ A t1 t2, B t3 ] for fresh t1, t2, t3
+                let flex_tag_labels = tags
+                    .into_iter()
+                    .map(|(label, arity)| {
+                        let variables_slice =
+                            VariableSubsSlice::reserve_into_subs(env.subs, arity.into());
+                        for var_index in variables_slice {
+                            env.subs[var_index] = env.subs.fresh_unnamed_flex_var();
+                        }
+                        (label, variables_slice)
+                    })
+                    .collect::<Vec<_>>();
+                let union_tags = UnionTags::insert_slices_into_subs(env.subs, flex_tag_labels);
+                let tag_union_var = synth_var(
+                    env.subs,
+                    Content::Structure(FlatType::TagUnion(
+                        union_tags,
+                        env.new_ext_var(ExtensionKind::TagUnion),
+                    )),
+                );
+
+                hash_tag_union(env, tag_union_var, union_tags, def_symbol)
+            }
+        }
+    };
+
+    let specialization_lambda_sets =
+        env.get_specialization_lambda_sets(body_type, Symbol::HASH_HASH);
+
+    DerivedBody {
+        body,
+        body_type,
+        specialization_lambda_sets,
+    }
+}
+
+fn hash_list(env: &mut Env<'_>, fn_name: Symbol) -> (Expr, Variable) {
+    // Build \hasher, lst -> Hash.hashList hasher lst
+
+    let hasher_sym = env.new_symbol("hasher");
+    let hasher_var = env.subs.fresh_unnamed_flex_var();
+
+    let lst_sym = env.new_symbol("lst");
+
+    // List elem
+    let elem_var = env.subs.fresh_unnamed_flex_var();
+    let elem_var_slice = SubsSlice::insert_into_subs(env.subs, [elem_var]);
+    let list_var = synth_var(
+        env.subs,
+        Content::Structure(FlatType::Apply(Symbol::LIST_LIST, elem_var_slice)),
+    );
+
+    // Hash.hashList hasher lst
+    let body = call_hash_fn(
+        env,
+        Symbol::HASH_HASH_LIST,
+        hasher_var,
+        Expr::Var(hasher_sym),
+        list_var,
+        Expr::Var(lst_sym),
+    );
+
+    build_outer_derived_closure(
+        env,
+        fn_name,
+        (hasher_var, hasher_sym),
+        (list_var, Pattern::Identifier(lst_sym)),
+        body,
+    )
+}
+
+fn hash_str(env: &mut Env<'_>, fn_name: Symbol) -> (Expr, Variable) {
+    // Build \hasher, s -> Hash.hashStrBytes hasher s
+
+    let hasher_sym = env.new_symbol("hasher");
+    let hasher_var = env.subs.fresh_unnamed_flex_var();
+
+    let s_sym = env.new_symbol("s");
+
+    // Hash.hashStrBytes hasher s
+    let body = call_hash_fn(
+        env,
+        Symbol::HASH_HASH_STR_BYTES,
+        hasher_var,
+        Expr::Var(hasher_sym),
+        Variable::STR,
+        Expr::Var(s_sym),
+    );
+
+    build_outer_derived_closure(
+        env,
+        fn_name,
+        (hasher_var, hasher_sym),
+        (Variable::STR, Pattern::Identifier(s_sym)),
+        body,
+    )
+}
+
+fn hash_empty(env: &mut Env<'_>, unit_var: Variable, fn_name: Symbol) -> (Expr, Variable) {
+    // Build \hasher, _ -> hasher
+
+    let hasher_sym = env.new_symbol("hasher");
+    let hasher_var = env.subs.fresh_unnamed_flex_var();
+
+    build_outer_derived_closure(
+        env,
+        fn_name,
+        (hasher_var, hasher_sym),
+        (unit_var, Pattern::Underscore),
+        Expr::Var(hasher_sym),
+    )
+}
+
+fn hash_record(
+    env: &mut Env<'_>,
+    record_var: Variable,
+    fields: RecordFields,
+    fn_name: Symbol,
+) -> (Expr, Variable) {
+    // Suppose rcd = { a: t1, b: t2 }. Build
+    //
+    // \hasher, rcd -> Hash.hash (Hash.hash hasher rcd.a) rcd.b
+
+    let hasher_sym = env.new_symbol("hasher");
+    let hasher_var = env.subs.fresh_unnamed_flex_var();
+
+    let rcd_sym = env.new_symbol("rcd");
+
+    let body = fields.iter_all().fold(
+        Expr::Var(hasher_sym),
+        |hasher_expr, (field_name_index, field_var_index, _)| {
+            let field_name = env.subs[field_name_index].clone();
+            let field_var = env.subs[field_var_index];
+
+            // rcd.a
+            let field_access = Expr::Access {
+                record_var,
+                ext_var: env.subs.fresh_unnamed_flex_var(),
+                field_var,
+                loc_expr: Box::new(Loc::at_zero(Expr::Var(rcd_sym))),
+                field: field_name,
+            };
+
+            // Hash.hash hasher rcd.a
+            call_hash_fn(
+                env,
+                Symbol::HASH_HASH,
+                hasher_var,
+                hasher_expr,
+                field_var,
+                field_access,
+            )
+        },
+    );
+
+    build_outer_derived_closure(
+        env,
+        fn_name,
+        (hasher_var, hasher_sym),
+        (record_var, Pattern::Identifier(rcd_sym)),
+        body,
+    )
+}
+
+fn hash_tuple(
+    env: &mut Env<'_>,
+    tuple_var: Variable,
+    elems: VariableSubsSlice,
+    fn_name: Symbol,
+) -> (Expr, Variable) {
+    // Suppose tup = (t1, t2). Build
+    //
+    // \hasher, tup -> Hash.hash (Hash.hash hasher tup.0) tup.1
+
+    let hasher_sym = env.new_symbol("hasher");
+    let hasher_var = env.subs.fresh_unnamed_flex_var();
+
+    let tup_sym = env.new_symbol("tup");
+
+    let elem_vars = env.subs.get_subs_slice(elems).to_vec();
+
+    let body = elem_vars.iter().enumerate().fold(
+        Expr::Var(hasher_sym),
+        |hasher_expr, (index, &elem_var)| {
+            // tup.0
+            let tuple_access = Expr::TupleAccess {
+                tuple_var,
+                ext_var: env.subs.fresh_unnamed_flex_var(),
+                prefix_vars: elem_vars[..index].to_vec(),
+                elem_var,
+                loc_expr: Box::new(Loc::at_zero(Expr::Var(tup_sym))),
+                index,
+            };
+
+            // Hash.hash hasher tup.0
+            call_hash_fn(
+                env,
+                Symbol::HASH_HASH,
+                hasher_var,
+                hasher_expr,
+                elem_var,
+                tuple_access,
+            )
+        },
+    );
+
+    build_outer_derived_closure(
+        env,
+        fn_name,
+        (hasher_var, hasher_sym),
+        (tuple_var, Pattern::Identifier(tup_sym)),
+        body,
+    )
+}
+
+fn hash_tag_union(
+    env: &mut Env<'_>,
+    tag_union_var: Variable,
+    tags: UnionTags,
+    fn_name: Symbol,
+) -> (Expr, Variable) {
+    // Suppose tag = [ A t1 t2, B t3 ]. Build
+    //
+    // \hasher, union -> when union is
+    //     A v1 v2 -> Hash.hash (Hash.hash (Hash.addU8 hasher 0) v1) v2
+    //     B v3 -> Hash.hash (Hash.addU8 hasher 1) v3
+    //
+    // The discriminant is only hashed when there is more than one tag, and is the index of the
+    // tag in the (sorted) key.
+
+    let hasher_sym = env.new_symbol("hasher");
+    let hasher_var = env.subs.fresh_unnamed_flex_var();
+
+    let union_sym = env.new_symbol("union");
+
+    let num_tags = tags.len();
+    let (discr_width, discr_hash_fn, discr_var) = if num_tags <= u8::MAX as usize + 1 {
+        (IntLitWidth::U8, Symbol::HASH_ADD_U8, Variable::U8)
+    } else {
+        (IntLitWidth::U16, Symbol::HASH_ADD_U16, Variable::U16)
+    };
+
+    let branches = tags
+        .iter_all()
+        .enumerate()
+        .map(|(discr, (tag_name_index, tag_vars_slice_index))| {
+            // A
+            let tag_name = env.subs[tag_name_index].clone();
+            let vars_slice = env.subs[tag_vars_slice_index];
+            // t1 t2
+            let payload_vars = env.subs.get_subs_slice(vars_slice).to_vec();
+            // v1 v2
+            let payload_syms: Vec<_> = std::iter::repeat_with(|| env.unique_symbol())
+                .take(payload_vars.len())
+                .collect();
+
+            // `A v1 v2` pattern
+            let pattern = Pattern::AppliedTag {
+                whole_var: tag_union_var,
+                tag_name,
+                ext_var: Variable::EMPTY_TAG_UNION,
+                // (t1, v1) (t2, v2)
+                arguments: (payload_vars.iter())
+                    .zip(payload_syms.iter())
+                    .map(|(var, sym)| (*var, Loc::at_zero(Pattern::Identifier(*sym))))
+                    .collect(),
+            };
+            let branch_pattern = WhenBranchPattern {
+                pattern: Loc::at_zero(pattern),
+                degenerate: false,
+            };
+
+            // Hash.addU8 hasher 0
+            let hasher_with_discr = if num_tags > 1 {
+                let discr_lit = Expr::Int(
+                    discr_var,
+                    discr_var,
+                    discr.to_string().into_boxed_str(),
+                    IntValue::I128((discr as i128).to_ne_bytes()),
+                    IntBound::Exact(discr_width),
+                );
+                call_hash_fn(
+                    env,
+                    discr_hash_fn,
+                    hasher_var,
+                    Expr::Var(hasher_sym),
+                    discr_var,
+                    discr_lit,
+                )
+            } else {
+                Expr::Var(hasher_sym)
+            };
+
+            // Hash.hash (Hash.hash (Hash.addU8 hasher 0) v1) v2
+            let body = (payload_syms.iter()).zip(payload_vars.iter()).fold(
+                hasher_with_discr,
+                |hasher_expr, (&sym, &sym_var)| {
+                    call_hash_fn(
+                        env,
+                        Symbol::HASH_HASH,
+                        hasher_var,
+                        hasher_expr,
+                        sym_var,
+                        Expr::Var(sym),
+                    )
+                },
+            );
+
+            WhenBranch {
+                patterns: vec![branch_pattern],
+                value: Loc::at_zero(body),
+                guard: None,
+                redundant: RedundantMark::known_non_redundant(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // when union is
+    //     A v1 v2 -> ..
+    //     B v3 -> ..
+    let when_branches = Expr::When {
+        loc_cond: Box::new(Loc::at_zero(Expr::Var(union_sym))),
+        cond_var: tag_union_var,
+        expr_var: hasher_var,
+        region: Region::zero(),
+        branches,
+        branches_cond_var: tag_union_var,
+        exhaustive: ExhaustiveMark::known_exhaustive(),
+    };
+
+    build_outer_derived_closure(
+        env,
+        fn_name,
+        (hasher_var, hasher_sym),
+        (tag_union_var, Pattern::Identifier(union_sym)),
+        when_branches,
+    )
+}
+
+/// Build `hash_fn hasher val`, where `hash_fn : hasher, val -> hasher | hasher has Hasher`.
+///
+/// The result has type `hasher_var`, so that calls may be chained.
+fn call_hash_fn(
+    env: &mut Env<'_>,
+    hash_fn: Symbol,
+    hasher_var: Variable,
+    hasher_expr: Expr,
+    val_var: Variable,
+    val_expr: Expr,
+) -> Expr {
+    // build `hash_fn hasher val` type
+    // expected: hasher, val -[uls]-> hasher | hasher has Hasher
+    let exposed_hash_fn_var = env.import_builtin_symbol_var(hash_fn);
+
+    // wanted: hasher_var, val_var -[clos]-> hasher_var
+    let this_arguments_slice = VariableSubsSlice::insert_into_subs(env.subs, [hasher_var, val_var]);
+    let this_hash_clos_var = env.subs.fresh_unnamed_flex_var();
+    let this_hash_fn_var = synth_var(
+        env.subs,
+        Content::Structure(FlatType::Func(
+            this_arguments_slice,
+            this_hash_clos_var,
+            hasher_var,
+        )),
+    );
+
+    //   hasher,     val     -[uls]->  hasher     | hasher has Hasher
+    // ~ hasher_var, val_var -[clos]-> hasher_var
+    env.unify(exposed_hash_fn_var, this_hash_fn_var);
+
+    // `hashList` and `hashStrBytes` are plain functions; everything else is a member of the `Hash`
+    // or `Hasher` abilities, and must be resolved to a specialization.
+    let hash_fn_head = match hash_fn {
+        Symbol::HASH_HASH_LIST | Symbol::HASH_HASH_STR_BYTES => Expr::Var(hash_fn),
+        _ => Expr::AbilityMember(hash_fn, None, this_hash_fn_var),
+    };
+    let hash_fn_data = Box::new((
+        this_hash_fn_var,
+        Loc::at_zero(hash_fn_head),
+        this_hash_clos_var,
+        hasher_var,
+    ));
+
+    Expr::Call(
+        hash_fn_data,
+        vec![
+            (hasher_var, Loc::at_zero(hasher_expr)),
+            (val_var, Loc::at_zero(val_expr)),
+        ],
+        CalledVia::Space,
+    )
+}
+
+/// Build `\hasher, val -[fn_name]-> body`.
+fn build_outer_derived_closure(
+    env: &mut Env<'_>,
+    fn_name: Symbol,
+    (hasher_var, hasher_sym): (Variable, Symbol),
+    (val_var, val_pattern): (Variable, Pattern),
+    body: Expr,
+) -> (Expr, Variable) {
+    // Create fn_var for ambient capture; we fix it up below.
+    let fn_var = synth_var(env.subs, Content::Error);
+
+    // -[fn_name]->
+    let fn_name_labels = UnionLambdas::insert_into_subs(env.subs, once((fn_name, vec![])));
+    let fn_clos_var = synth_var(
+        env.subs,
+        Content::LambdaSet(LambdaSet {
+            solved: fn_name_labels,
+            recursion_var: OptVariable::NONE,
+            unspecialized: SubsSlice::default(),
+            ambient_function: fn_var,
+        }),
+    );
+
+    // hasher, val -[fn_name]-> hasher
+    let args_slice = SubsSlice::insert_into_subs(env.subs, [hasher_var, val_var]);
+    env.subs.set_content(
+        fn_var,
+        Content::Structure(FlatType::Func(args_slice, fn_clos_var, hasher_var)),
+    );
+
+    // \hasher, val -[fn_name]-> body
+    let clos = Expr::Closure(ClosureData {
+        function_type: fn_var,
+        closure_type: fn_clos_var,
+        return_type: hasher_var,
+        name: fn_name,
+        captured_symbols: vec![],
+        recursive: Recursive::NotRecursive,
+        arguments: vec![
+            (
+                hasher_var,
+                AnnotatedMark::known_exhaustive(),
+                Loc::at_zero(Pattern::Identifier(hasher_sym)),
+            ),
+            (
+                val_var,
+                AnnotatedMark::known_exhaustive(),
+                Loc::at_zero(val_pattern),
+            ),
+        ],
+        loc_body: Box::new(Loc::at_zero(body)),
+    });
+
+    (clos, fn_var)
+}
@@ -18,6 +18,9 @@ use util::Env;
 
 mod decoding;
 mod encoding;
+mod eq;
+mod generating;
+mod hash;
 
 mod util;
 
@@ -77,6 +80,11 @@ fn build_derived_body(
         DeriveKey::Decoder(decoder_key) => {
             decoding::derive_decoder(&mut env, decoder_key, derived_symbol)
         }
+        DeriveKey::Hash(hash_key) => hash::derive_hash(&mut env, hash_key, derived_symbol),
+        DeriveKey::IsEq(eq_key) => eq::derive_is_eq(&mut env, eq_key, derived_symbol),
+        DeriveKey::Generate(generate_key) => {
+            generating::derive_generate(&mut env, generate_key, derived_symbol)
+        }
     };
 
     let def = Def {
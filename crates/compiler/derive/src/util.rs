@@ -166,6 +166,7 @@ impl Env<'_> {
         } else {
             match kind {
                 ExtensionKind::Record => Variable::EMPTY_RECORD,
+                ExtensionKind::Tuple => Variable::EMPTY_TUPLE,
                 ExtensionKind::TagUnion => Variable::EMPTY_TAG_UNION,
             }
         }
@@ -174,5 +175,6 @@ impl Env<'_> {
 
 pub(crate) enum ExtensionKind {
     Record,
+    Tuple,
     TagUnion,
 }
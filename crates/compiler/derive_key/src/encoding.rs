@@ -5,7 +5,7 @@ use roc_module::{
 use roc_types::subs::{Content, FlatType, GetSubsSlice, Subs, Variable};
 
 use crate::{
-    util::{check_empty_ext_var, debug_name_record},
+    util::{check_empty_ext_var, debug_name_record, debug_name_tag, debug_name_tuple, tuple_arity},
     DeriveError,
 };
 
@@ -22,6 +22,7 @@ pub enum FlatEncodableKey {
     Dict(/* takes two variables */),
     // Unfortunate that we must allocate here, c'est la vie
     Record(Vec<Lowercase>),
+    Tuple(u32),
     TagUnion(Vec<(TagName, u16)>),
 }
 
@@ -32,19 +33,8 @@ impl FlatEncodableKey {
             FlatEncodableKey::Set() => "set".to_string(),
             FlatEncodableKey::Dict() => "dict".to_string(),
             FlatEncodableKey::Record(fields) => debug_name_record(fields),
-            FlatEncodableKey::TagUnion(tags) => {
-                let mut str = String::from('[');
-                tags.iter().enumerate().for_each(|(i, (tag, arity))| {
-                    if i > 0 {
-                        str.push(',');
-                    }
-                    str.push_str(tag.0.as_str());
-                    str.push(' ');
-                    str.push_str(&arity.to_string());
-                });
-                str.push(']');
-                str
-            }
+            FlatEncodableKey::Tuple(arity) => debug_name_tuple(*arity),
+            FlatEncodableKey::TagUnion(tags) => debug_name_tag(tags),
         }
     }
 }
@@ -73,6 +63,13 @@ impl FlatEncodable {
 
                     Ok(Key(FlatEncodableKey::Record(field_names)))
                 }
+                FlatType::Tuple(elems, ext) => {
+                    let arity = tuple_arity(subs, elems, ext, |ext| {
+                        matches!(ext, Content::Structure(FlatType::EmptyTuple))
+                    })?;
+
+                    Ok(Key(FlatEncodableKey::Tuple(arity)))
+                }
                 FlatType::TagUnion(tags, ext) | FlatType::RecursiveTagUnion(_, tags, ext) => {
                     // The recursion var doesn't matter, because the derived implementation will only
                     // look on the surface of the tag union type, and more over the payloads of the
@@ -103,6 +100,7 @@ impl FlatEncodable {
                     FlatEncodableKey::TagUnion(vec![(subs[name_index].clone(), 0)]),
                 )),
                 FlatType::EmptyRecord => Ok(Key(FlatEncodableKey::Record(vec![]))),
+                FlatType::EmptyTuple => Ok(Key(FlatEncodableKey::Tuple(0))),
                 FlatType::EmptyTagUnion => Ok(Key(FlatEncodableKey::TagUnion(vec![]))),
                 //
                 FlatType::Erroneous(_) => Err(Underivable),
@@ -0,0 +1,200 @@
+use roc_module::{
+    ident::{Lowercase, TagName},
+    symbol::{ModuleId, Symbol},
+};
+use roc_types::{
+    subs::{Content, FlatType, GetSubsSlice, Subs, Variable},
+    types::AliasKind,
+};
+
+use crate::{
+    util::{check_empty_ext_var, debug_name_record, debug_name_tag, debug_name_tuple, tuple_arity},
+    DeriveError,
+};
+
+#[derive(Hash)]
+pub enum FlatEq {
+    /// The type can be compared structurally, with a single builtin function that has exactly
+    /// one lambda in its lambda set.
+    SingleLambdaSetImmediate(Symbol),
+    Key(FlatEqKey),
+}
+
+#[derive(Hash, PartialEq, Eq, Debug, Clone)]
+pub enum FlatEqKey {
+    List(/* takes one variable */),
+    // Unfortunate that we must allocate here, c'est la vie
+    Record(Vec<Lowercase>),
+    Tuple(u32),
+    TagUnion(Vec<(TagName, u16)>),
+}
+
+impl FlatEqKey {
+    pub(crate) fn debug_name(&self) -> String {
+        match self {
+            FlatEqKey::List() => "list".to_string(),
+            FlatEqKey::Record(fields) => debug_name_record(fields),
+            FlatEqKey::Tuple(arity) => debug_name_tuple(*arity),
+            FlatEqKey::TagUnion(tags) => debug_name_tag(tags),
+        }
+    }
+}
+
+impl FlatEq {
+    pub(crate) fn from_var(subs: &Subs, var: Variable) -> Result<FlatEq, DeriveError> {
+        use DeriveError::*;
+        use FlatEq::*;
+
+        // Types that cannot contain a custom implementation of `Eq` are compared structurally by
+        // the backend, which is much cheaper than generating a comparison for each type.
+        if !may_contain_custom_eq(subs, var) {
+            return Ok(SingleLambdaSetImmediate(Symbol::BOOL_STRUCTURAL_EQ));
+        }
+
+        match *subs.get_content_without_compacting(var) {
+            Content::Structure(flat_type) => match flat_type {
+                FlatType::Apply(sym, _) => match sym {
+                    Symbol::LIST_LIST => Ok(Key(FlatEqKey::List())),
+                    // TODO: `Box` of a type with a custom `Eq` is still compared structurally
+                    _ => Ok(SingleLambdaSetImmediate(Symbol::BOOL_STRUCTURAL_EQ)),
+                },
+                FlatType::Record(fields, ext) => {
+                    // An open extension will never hold more fields at runtime, so we can treat it
+                    // as closed.
+                    check_empty_ext_var(subs, ext, |ext| {
+                        matches!(
+                            ext,
+                            Content::Structure(FlatType::EmptyRecord) | Content::FlexVar(_)
+                        )
+                    })?;
+
+                    let mut field_names: Vec<_> =
+                        subs.get_subs_slice(fields.field_names()).to_vec();
+                    field_names.sort();
+
+                    Ok(Key(FlatEqKey::Record(field_names)))
+                }
+                FlatType::Tuple(elems, ext) => {
+                    // As with records, an open extension will never hold more elements at runtime.
+                    let arity = tuple_arity(subs, elems, ext, |ext| {
+                        matches!(
+                            ext,
+                            Content::Structure(FlatType::EmptyTuple) | Content::FlexVar(_)
+                        )
+                    })?;
+
+                    Ok(Key(FlatEqKey::Tuple(arity)))
+                }
+                FlatType::TagUnion(tags, ext) | FlatType::RecursiveTagUnion(_, tags, ext) => {
+                    // As with hashing, only the surface of the tag union matters; payloads are
+                    // compared with `isEq` and left for the monomorphizer to specialize. As with
+                    // records, an open extension can never be instantiated with more tags at
+                    // runtime, so we can treat it as closed.
+                    check_empty_ext_var(subs, ext, |ext| {
+                        matches!(
+                            ext,
+                            Content::Structure(FlatType::EmptyTagUnion) | Content::FlexVar(_)
+                        )
+                    })?;
+
+                    let mut tag_names_and_payload_sizes: Vec<_> = tags
+                        .iter_all()
+                        .map(|(name_index, payload_slice_index)| {
+                            let payload_slice = subs[payload_slice_index];
+                            let payload_size = payload_slice.length;
+                            let name = &subs[name_index];
+                            (name.clone(), payload_size)
+                        })
+                        .collect();
+                    tag_names_and_payload_sizes.sort_by(|(t1, _), (t2, _)| t1.cmp(t2));
+                    Ok(Key(FlatEqKey::TagUnion(tag_names_and_payload_sizes)))
+                }
+                FlatType::FunctionOrTagUnion(name_index, _, _) => Ok(Key(FlatEqKey::TagUnion(
+                    vec![(subs[name_index].clone(), 0)],
+                ))),
+                FlatType::EmptyRecord => Ok(Key(FlatEqKey::Record(vec![]))),
+                FlatType::EmptyTuple => Ok(Key(FlatEqKey::Tuple(0))),
+                FlatType::EmptyTagUnion => Ok(Key(FlatEqKey::TagUnion(vec![]))),
+                //
+                FlatType::Erroneous(_) => Err(Underivable),
+                FlatType::Func(..) => Err(Underivable),
+            },
+            // Derived implementations for opaques look through to their underlying type; the
+            // backend treats opaques like structural aliases.
+            Content::Alias(_, _, real_var, _) => Self::from_var(subs, real_var),
+            Content::RangedNumber(_) => Ok(SingleLambdaSetImmediate(Symbol::BOOL_STRUCTURAL_EQ)),
+            //
+            Content::RecursionVar { .. } => Err(Underivable),
+            Content::Error => Err(Underivable),
+            Content::FlexVar(_)
+            | Content::RigidVar(_)
+            | Content::FlexAbleVar(_, _)
+            | Content::RigidAbleVar(_, _) => Err(UnboundVar),
+            Content::LambdaSet(_) => Err(Underivable),
+        }
+    }
+}
+
+/// Does `var` possibly contain an opaque type, which may provide its own implementation of `Eq`?
+///
+/// Unbound variables may later be instantiated to such opaques, so they are conservatively
+/// assumed to. Numbers are the exception - they are opaques, but always compared structurally.
+fn may_contain_custom_eq(subs: &Subs, var: Variable) -> bool {
+    let mut stack = vec![var];
+    let mut seen = Vec::new();
+
+    while let Some(var) = stack.pop() {
+        if seen.contains(&var) {
+            continue;
+        }
+        seen.push(var);
+
+        match *subs.get_content_without_compacting(var) {
+            Content::Structure(flat_type) => match flat_type {
+                FlatType::Apply(_, args) => {
+                    stack.extend(subs.get_subs_slice(args));
+                }
+                FlatType::Record(fields, ext) => {
+                    stack.extend(fields.iter_variables().map(|var_index| subs[var_index]));
+                    push_ext(subs, &mut stack, ext);
+                }
+                FlatType::Tuple(elems, ext) => {
+                    stack.extend(subs.get_subs_slice(elems));
+                    push_ext(subs, &mut stack, ext);
+                }
+                FlatType::TagUnion(tags, ext) | FlatType::RecursiveTagUnion(_, tags, ext) => {
+                    for slice_index in tags.variables() {
+                        stack.extend(subs.get_subs_slice(subs[slice_index]));
+                    }
+                    push_ext(subs, &mut stack, ext);
+                }
+                FlatType::FunctionOrTagUnion(_, _, ext) => push_ext(subs, &mut stack, ext),
+                FlatType::Func(..) | FlatType::Erroneous(_) => {}
+                FlatType::EmptyRecord | FlatType::EmptyTuple | FlatType::EmptyTagUnion => {}
+            },
+            Content::Alias(symbol, _, _, _) if symbol.module_id() == ModuleId::NUM => {}
+            Content::Alias(_, _, _, AliasKind::Opaque) => return true,
+            Content::Alias(_, _, real_var, AliasKind::Structural) => stack.push(real_var),
+            Content::FlexVar(_)
+            | Content::RigidVar(_)
+            | Content::FlexAbleVar(_, _)
+            | Content::RigidAbleVar(_, _) => return true,
+            // The structure of a recursion variable is the tag union we are already visiting.
+            Content::RecursionVar { .. } => {}
+            Content::RangedNumber(_) | Content::LambdaSet(_) | Content::Error => {}
+        }
+    }
+
+    false
+}
+
+/// Extension variables can only ever be instantiated to more fields, elements or tags, so there is
+/// nothing to look for unless they already are.
+fn push_ext(subs: &Subs, stack: &mut Vec<Variable>, ext: Variable) {
+    if matches!(
+        subs.get_content_without_compacting(ext),
+        Content::Structure(_) | Content::Alias(..)
+    ) {
+        stack.push(ext);
+    }
+}
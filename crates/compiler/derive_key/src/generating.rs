@@ -0,0 +1,170 @@
+use roc_module::{
+    ident::{Lowercase, TagName},
+    symbol::Symbol,
+};
+use roc_types::subs::{Content, FlatType, Subs, Variable};
+
+use crate::{
+    util::{check_empty_ext_var, debug_name_record, debug_name_tag, debug_name_tuple, tuple_arity},
+    DeriveError,
+};
+
+#[derive(Hash)]
+pub enum FlatGenerator {
+    SingleLambdaSetImmediate(Symbol),
+    Key(FlatGeneratorKey),
+}
+
+#[derive(Hash, PartialEq, Eq, Debug, Clone)]
+pub enum FlatGeneratorKey {
+    List(/* takes one variable */),
+    // Unfortunate that we must allocate here, c'est la vie
+    Record(Vec<Lowercase>),
+    Tuple(u32),
+    TagUnion(Vec<(TagName, u16)>),
+}
+
+impl FlatGeneratorKey {
+    pub(crate) fn debug_name(&self) -> String {
+        match self {
+            FlatGeneratorKey::List() => "list".to_string(),
+            FlatGeneratorKey::Record(fields) => debug_name_record(fields),
+            FlatGeneratorKey::Tuple(arity) => debug_name_tuple(*arity),
+            FlatGeneratorKey::TagUnion(tags) => debug_name_tag(tags),
+        }
+    }
+}
+
+impl FlatGenerator {
+    pub(crate) fn from_var(subs: &Subs, var: Variable) -> Result<FlatGenerator, DeriveError> {
+        use DeriveError::*;
+        use FlatGenerator::*;
+        match *subs.get_content_without_compacting(var) {
+            Content::Structure(flat_type) => match flat_type {
+                FlatType::Apply(sym, _) => match sym {
+                    Symbol::LIST_LIST => Ok(Key(FlatGeneratorKey::List())),
+                    Symbol::STR_STR => Ok(SingleLambdaSetImmediate(Symbol::GENERATE_STR)),
+                    _ => Err(Underivable),
+                },
+                FlatType::Record(fields, ext) => {
+                    let fields_iter = match fields.unsorted_iterator(subs, ext) {
+                        Ok(it) => it,
+                        Err(_) => return Err(Underivable),
+                    };
+
+                    let mut field_names = Vec::with_capacity(fields.len());
+                    for (field_name, record_field) in fields_iter {
+                        if record_field.is_optional() {
+                            // As with decoding, an optional field may or may not be there, and
+                            // which it is is decided at compile time, not by the generator.
+                            return Err(Underivable);
+                        }
+                        field_names.push(field_name.clone());
+                    }
+
+                    field_names.sort();
+
+                    Ok(Key(FlatGeneratorKey::Record(field_names)))
+                }
+                FlatType::Tuple(elems, ext) => {
+                    let arity = tuple_arity(subs, elems, ext, |ext| {
+                        matches!(ext, Content::Structure(FlatType::EmptyTuple))
+                    })?;
+
+                    Ok(Key(FlatGeneratorKey::Tuple(arity)))
+                }
+                FlatType::TagUnion(tags, ext) | FlatType::RecursiveTagUnion(_, tags, ext) => {
+                    // As with encoding, the recursion var doesn't matter: the derived generator
+                    // only builds the surface of the tag union, and leaves the payload types for
+                    // the monomorphizer to fill in.
+                    check_empty_ext_var(subs, ext, |ext| {
+                        matches!(ext, Content::Structure(FlatType::EmptyTagUnion))
+                    })?;
+
+                    // There are no values of `[]` to generate.
+                    if tags.is_empty() {
+                        return Err(Underivable);
+                    }
+
+                    let mut tag_names_and_payload_sizes: Vec<_> = tags
+                        .iter_all()
+                        .map(|(name_index, payload_slice_index)| {
+                            let payload_slice = subs[payload_slice_index];
+                            let payload_size = payload_slice.length;
+                            let name = &subs[name_index];
+                            (name.clone(), payload_size)
+                        })
+                        .collect();
+                    tag_names_and_payload_sizes.sort_by(|(t1, _), (t2, _)| t1.cmp(t2));
+                    Ok(Key(FlatGeneratorKey::TagUnion(tag_names_and_payload_sizes)))
+                }
+                FlatType::FunctionOrTagUnion(name_index, _, _) => Ok(Key(
+                    FlatGeneratorKey::TagUnion(vec![(subs[name_index].clone(), 0)]),
+                )),
+                FlatType::EmptyRecord => Ok(Key(FlatGeneratorKey::Record(vec![]))),
+                FlatType::EmptyTuple => Ok(Key(FlatGeneratorKey::Tuple(0))),
+                // There are no values of an empty tag union to generate.
+                FlatType::EmptyTagUnion => Err(Underivable),
+                //
+                FlatType::Erroneous(_) => Err(Underivable),
+                FlatType::Func(..) => Err(Underivable),
+            },
+            Content::Alias(sym, _, real_var, _) => match sym {
+                Symbol::NUM_U8 | Symbol::NUM_UNSIGNED8 => {
+                    Ok(SingleLambdaSetImmediate(Symbol::GENERATE_U8))
+                }
+                Symbol::NUM_U16 | Symbol::NUM_UNSIGNED16 => {
+                    Ok(SingleLambdaSetImmediate(Symbol::GENERATE_U16))
+                }
+                Symbol::NUM_U32 | Symbol::NUM_UNSIGNED32 => {
+                    Ok(SingleLambdaSetImmediate(Symbol::GENERATE_U32))
+                }
+                Symbol::NUM_U64 | Symbol::NUM_UNSIGNED64 => {
+                    Ok(SingleLambdaSetImmediate(Symbol::GENERATE_U64))
+                }
+                Symbol::NUM_U128 | Symbol::NUM_UNSIGNED128 => {
+                    Ok(SingleLambdaSetImmediate(Symbol::GENERATE_U128))
+                }
+                Symbol::NUM_I8 | Symbol::NUM_SIGNED8 => {
+                    Ok(SingleLambdaSetImmediate(Symbol::GENERATE_I8))
+                }
+                Symbol::NUM_I16 | Symbol::NUM_SIGNED16 => {
+                    Ok(SingleLambdaSetImmediate(Symbol::GENERATE_I16))
+                }
+                Symbol::NUM_I32 | Symbol::NUM_SIGNED32 => {
+                    Ok(SingleLambdaSetImmediate(Symbol::GENERATE_I32))
+                }
+                Symbol::NUM_I64 | Symbol::NUM_SIGNED64 => {
+                    Ok(SingleLambdaSetImmediate(Symbol::GENERATE_I64))
+                }
+                Symbol::NUM_I128 | Symbol::NUM_SIGNED128 => {
+                    Ok(SingleLambdaSetImmediate(Symbol::GENERATE_I128))
+                }
+                Symbol::NUM_NAT | Symbol::NUM_NATURAL => {
+                    Ok(SingleLambdaSetImmediate(Symbol::GENERATE_NAT))
+                }
+                Symbol::NUM_DEC | Symbol::NUM_DECIMAL => {
+                    Ok(SingleLambdaSetImmediate(Symbol::GENERATE_DEC))
+                }
+                Symbol::NUM_F32 | Symbol::NUM_BINARY32 => {
+                    Ok(SingleLambdaSetImmediate(Symbol::GENERATE_F32))
+                }
+                Symbol::NUM_F64 | Symbol::NUM_BINARY64 => {
+                    Ok(SingleLambdaSetImmediate(Symbol::GENERATE_F64))
+                }
+                // NB: I believe it is okay to unwrap opaques here because derivers are only used
+                // by the backend, and the backend treats opaques like structural aliases.
+                _ => Self::from_var(subs, real_var),
+            },
+            Content::RangedNumber(_) => Err(Underivable),
+            //
+            Content::RecursionVar { .. } => Err(Underivable),
+            Content::Error => Err(Underivable),
+            Content::FlexVar(_)
+            | Content::RigidVar(_)
+            | Content::FlexAbleVar(_, _)
+            | Content::RigidAbleVar(_, _) => Err(UnboundVar),
+            Content::LambdaSet(_) => Err(Underivable),
+        }
+    }
+}
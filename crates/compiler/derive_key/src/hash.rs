@@ -0,0 +1,134 @@
+use roc_module::{
+    ident::{Lowercase, TagName},
+    symbol::Symbol,
+};
+use roc_types::subs::{Content, FlatType, GetSubsSlice, Subs, Variable};
+
+use crate::{
+    util::{check_empty_ext_var, debug_name_record, debug_name_tag, debug_name_tuple, tuple_arity},
+    DeriveError,
+};
+
+#[derive(Hash)]
+pub enum FlatHash {
+    Immediate(Symbol),
+    Key(FlatHashKey),
+}
+
+#[derive(Hash, PartialEq, Eq, Debug, Clone)]
+pub enum FlatHashKey {
+    List(/* takes one variable */),
+    Str(),
+    // Unfortunate that we must allocate here, c'est la vie
+    Record(Vec<Lowercase>),
+    Tuple(u32),
+    TagUnion(Vec<(TagName, u16)>),
+}
+
+impl FlatHashKey {
+    pub(crate) fn debug_name(&self) -> String {
+        match self {
+            FlatHashKey::List() => "list".to_string(),
+            FlatHashKey::Str() => "str".to_string(),
+            FlatHashKey::Record(fields) => debug_name_record(fields),
+            FlatHashKey::Tuple(arity) => debug_name_tuple(*arity),
+            FlatHashKey::TagUnion(tags) => debug_name_tag(tags),
+        }
+    }
+}
+
+impl FlatHash {
+    pub(crate) fn from_var(subs: &Subs, var: Variable) -> Result<FlatHash, DeriveError> {
+        use DeriveError::*;
+        use FlatHash::*;
+        match *subs.get_content_without_compacting(var) {
+            Content::Structure(flat_type) => match flat_type {
+                FlatType::Apply(sym, _) => match sym {
+                    Symbol::LIST_LIST => Ok(Key(FlatHashKey::List())),
+                    Symbol::STR_STR => Ok(Key(FlatHashKey::Str())),
+                    // TODO: hashing a Dict or Set must not depend on the order of its entries
+                    Symbol::SET_SET | Symbol::DICT_DICT => Err(Underivable),
+                    _ => Err(Underivable),
+                },
+                FlatType::Record(fields, ext) => {
+                    check_empty_ext_var(subs, ext, |ext| {
+                        matches!(ext, Content::Structure(FlatType::EmptyRecord))
+                    })?;
+
+                    let mut field_names: Vec<_> =
+                        subs.get_subs_slice(fields.field_names()).to_vec();
+                    field_names.sort();
+
+                    Ok(Key(FlatHashKey::Record(field_names)))
+                }
+                FlatType::Tuple(elems, ext) => {
+                    let arity = tuple_arity(subs, elems, ext, |ext| {
+                        matches!(ext, Content::Structure(FlatType::EmptyTuple))
+                    })?;
+
+                    Ok(Key(FlatHashKey::Tuple(arity)))
+                }
+                FlatType::TagUnion(tags, ext) | FlatType::RecursiveTagUnion(_, tags, ext) => {
+                    // The recursion var doesn't matter, because the derived implementation will only
+                    // look on the surface of the tag union type, and more over the payloads of the
+                    // arguments will be left generic for the monomorphizer to fill in with the
+                    // appropriate type. See the comment in encoding.rs.
+                    check_empty_ext_var(subs, ext, |ext| {
+                        matches!(ext, Content::Structure(FlatType::EmptyTagUnion))
+                    })?;
+
+                    let mut tag_names_and_payload_sizes: Vec<_> = tags
+                        .iter_all()
+                        .map(|(name_index, payload_slice_index)| {
+                            let payload_slice = subs[payload_slice_index];
+                            let payload_size = payload_slice.length;
+                            let name = &subs[name_index];
+                            (name.clone(), payload_size)
+                        })
+                        .collect();
+                    tag_names_and_payload_sizes.sort_by(|(t1, _), (t2, _)| t1.cmp(t2));
+                    Ok(Key(FlatHashKey::TagUnion(tag_names_and_payload_sizes)))
+                }
+                FlatType::FunctionOrTagUnion(name_index, _, _) => Ok(Key(FlatHashKey::TagUnion(
+                    vec![(subs[name_index].clone(), 0)],
+                ))),
+                FlatType::EmptyRecord => Ok(Key(FlatHashKey::Record(vec![]))),
+                FlatType::EmptyTuple => Ok(Key(FlatHashKey::Tuple(0))),
+                FlatType::EmptyTagUnion => Ok(Key(FlatHashKey::TagUnion(vec![]))),
+                //
+                FlatType::Erroneous(_) => Err(Underivable),
+                FlatType::Func(..) => Err(Underivable),
+            },
+            Content::Alias(sym, _, real_var, _) => match sym {
+                Symbol::NUM_U8 | Symbol::NUM_UNSIGNED8 => Ok(Immediate(Symbol::HASH_ADD_U8)),
+                Symbol::NUM_U16 | Symbol::NUM_UNSIGNED16 => Ok(Immediate(Symbol::HASH_ADD_U16)),
+                Symbol::NUM_U32 | Symbol::NUM_UNSIGNED32 => Ok(Immediate(Symbol::HASH_ADD_U32)),
+                Symbol::NUM_U64 | Symbol::NUM_UNSIGNED64 => Ok(Immediate(Symbol::HASH_ADD_U64)),
+                Symbol::NUM_U128 | Symbol::NUM_UNSIGNED128 => Ok(Immediate(Symbol::HASH_ADD_U128)),
+                Symbol::NUM_I8 | Symbol::NUM_SIGNED8 => Ok(Immediate(Symbol::HASH_ADD_I8)),
+                Symbol::NUM_I16 | Symbol::NUM_SIGNED16 => Ok(Immediate(Symbol::HASH_ADD_I16)),
+                Symbol::NUM_I32 | Symbol::NUM_SIGNED32 => Ok(Immediate(Symbol::HASH_ADD_I32)),
+                Symbol::NUM_I64 | Symbol::NUM_SIGNED64 => Ok(Immediate(Symbol::HASH_ADD_I64)),
+                Symbol::NUM_I128 | Symbol::NUM_SIGNED128 => Ok(Immediate(Symbol::HASH_ADD_I128)),
+                // There is no `Hasher` method for these yet. Floats in particular cannot be hashed
+                // consistently, because NaN != NaN and 0.0 == -0.0.
+                Symbol::NUM_NAT | Symbol::NUM_NATURAL => Err(Underivable),
+                Symbol::NUM_DEC | Symbol::NUM_DECIMAL => Err(Underivable),
+                Symbol::NUM_F32 | Symbol::NUM_BINARY32 => Err(Underivable),
+                Symbol::NUM_F64 | Symbol::NUM_BINARY64 => Err(Underivable),
+                // TODO: I believe it is okay to unwrap opaques here because derivers are only used
+                // by the backend, and the backend treats opaques like structural aliases.
+                _ => Self::from_var(subs, real_var),
+            },
+            Content::RangedNumber(_) => Err(Underivable),
+            //
+            Content::RecursionVar { .. } => Err(Underivable),
+            Content::Error => Err(Underivable),
+            Content::FlexVar(_)
+            | Content::RigidVar(_)
+            | Content::FlexAbleVar(_, _)
+            | Content::RigidAbleVar(_, _) => Err(UnboundVar),
+            Content::LambdaSet(_) => Err(Underivable),
+        }
+    }
+}
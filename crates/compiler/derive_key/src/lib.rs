@@ -2,23 +2,34 @@
 //! addressed by a key of their type content. However, different derived implementations can be
 //! reused based on different properties of the type. For example:
 //!
-//! - `Eq` does not care about surface type representations; its derived implementations can be
-//!   uniquely addressed by the [`Layout`][crate::layout::Layout] of a type.
+//! - `Eq` does not care about surface type representations, unless a type contains an opaque
+//!   with a custom implementation. Types without such opaques are compared structurally, and
+//!   otherwise derived implementations are keyed by record fields and tag names.
 //! - `Encoding` must care about surface type representations; for example, `{ a: "" }` and
 //!   `{ b: "" }` have different derived implementations. However, it does not need to distinguish
 //!   between e.g. required and optional record fields.
 //! - `Decoding` is like encoding, but has some differences. For one, it *does* need to distinguish
 //!   between required and optional record fields.
+//! - `Hash` is keyed like `Encoding`, since its derived implementations must access record fields
+//!   and match on tags by name.
+//! - `Generator` is keyed like `Decoding`, since its derived implementations must build record
+//!   fields and tags by name, and cannot build optional record fields.
 //!
 //! For these reasons the content keying is based on a strategy as well, which are the variants of
 //! [`DeriveKey`].
 
 pub mod decoding;
 pub mod encoding;
+pub mod eq;
+pub mod generating;
+pub mod hash;
 mod util;
 
 use decoding::{FlatDecodable, FlatDecodableKey};
 use encoding::{FlatEncodable, FlatEncodableKey};
+use eq::{FlatEq, FlatEqKey};
+use generating::{FlatGenerator, FlatGeneratorKey};
+use hash::{FlatHash, FlatHashKey};
 
 use roc_module::symbol::Symbol;
 use roc_types::subs::{Subs, Variable};
@@ -37,6 +48,9 @@ pub enum DeriveError {
 pub enum DeriveKey {
     ToEncoder(FlatEncodableKey),
     Decoder(FlatDecodableKey),
+    Hash(FlatHashKey),
+    IsEq(FlatEqKey),
+    Generate(FlatGeneratorKey),
 }
 
 impl DeriveKey {
@@ -44,6 +58,9 @@ impl DeriveKey {
         match self {
             DeriveKey::ToEncoder(key) => format!("toEncoder_{}", key.debug_name()),
             DeriveKey::Decoder(key) => format!("decoder_{}", key.debug_name()),
+            DeriveKey::Hash(key) => format!("hash_{}", key.debug_name()),
+            DeriveKey::IsEq(key) => format!("isEq_{}", key.debug_name()),
+            DeriveKey::Generate(key) => format!("generate_{}", key.debug_name()),
         }
     }
 }
@@ -53,6 +70,9 @@ pub enum Derived {
     /// If a derived implementation name is well-known ahead-of-time, we can inline the symbol
     /// directly rather than associating a key for an implementation to be made later on.
     Immediate(Symbol),
+    /// Like an [`Immediate`][Derived::Immediate], but the symbol is a plain function rather than
+    /// an ability member, whose type has exactly one lambda in each lambda set.
+    SingleLambdaSetImmediate(Symbol),
     /// Key of the derived implementation to use. This allows association of derived implementation
     /// names to a key, when the key is known ahead-of-time but the implementation (and it's name)
     /// is yet-to-be-made.
@@ -64,6 +84,9 @@ pub enum Derived {
 pub enum DeriveBuiltin {
     ToEncoder,
     Decoder,
+    Hash,
+    IsEq,
+    Generate,
 }
 
 impl TryFrom<Symbol> for DeriveBuiltin {
@@ -73,6 +96,9 @@ impl TryFrom<Symbol> for DeriveBuiltin {
         match value {
             Symbol::ENCODE_TO_ENCODER => Ok(DeriveBuiltin::ToEncoder),
             Symbol::DECODE_DECODER => Ok(DeriveBuiltin::Decoder),
+            Symbol::HASH_HASH => Ok(DeriveBuiltin::Hash),
+            Symbol::BOOL_IS_EQ => Ok(DeriveBuiltin::IsEq),
+            Symbol::GENERATE_GENERATE => Ok(DeriveBuiltin::Generate),
             _ => Err(value),
         }
     }
@@ -93,6 +119,20 @@ impl Derived {
                 FlatDecodable::Immediate(imm) => Ok(Derived::Immediate(imm)),
                 FlatDecodable::Key(repr) => Ok(Derived::Key(DeriveKey::Decoder(repr))),
             },
+            DeriveBuiltin::Hash => match hash::FlatHash::from_var(subs, var)? {
+                FlatHash::Immediate(imm) => Ok(Derived::Immediate(imm)),
+                FlatHash::Key(repr) => Ok(Derived::Key(DeriveKey::Hash(repr))),
+            },
+            DeriveBuiltin::IsEq => match eq::FlatEq::from_var(subs, var)? {
+                FlatEq::SingleLambdaSetImmediate(imm) => Ok(Derived::SingleLambdaSetImmediate(imm)),
+                FlatEq::Key(repr) => Ok(Derived::Key(DeriveKey::IsEq(repr))),
+            },
+            DeriveBuiltin::Generate => match generating::FlatGenerator::from_var(subs, var)? {
+                FlatGenerator::SingleLambdaSetImmediate(imm) => {
+                    Ok(Derived::SingleLambdaSetImmediate(imm))
+                }
+                FlatGenerator::Key(repr) => Ok(Derived::Key(DeriveKey::Generate(repr))),
+            },
         }
     }
 }
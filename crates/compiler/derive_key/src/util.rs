@@ -1,5 +1,6 @@
-use roc_module::ident::Lowercase;
-use roc_types::subs::{Content, Subs, Variable};
+use roc_module::ident::{Lowercase, TagName};
+use roc_types::subs::{Content, Subs, Variable, VariableSubsSlice};
+use roc_types::types::{gather_tuple_elems, TupleStructure};
 
 use crate::DeriveError;
 
@@ -30,3 +31,36 @@ pub(crate) fn debug_name_record(fields: &[Lowercase]) -> String {
     str.push('}');
     str
 }
+
+/// Returns the number of elements of a tuple, after checking that the tuple is closed.
+pub(crate) fn tuple_arity(
+    subs: &Subs,
+    elems: VariableSubsSlice,
+    ext_var: Variable,
+    is_empty_ext: impl Fn(&Content) -> bool,
+) -> Result<u32, DeriveError> {
+    let TupleStructure { elems, ext } =
+        gather_tuple_elems(subs, elems, ext_var).map_err(|_| DeriveError::Underivable)?;
+
+    check_empty_ext_var(subs, ext, is_empty_ext)?;
+
+    Ok(elems.len() as u32)
+}
+
+pub(crate) fn debug_name_tuple(arity: u32) -> String {
+    format!("(arity:{})", arity)
+}
+
+pub(crate) fn debug_name_tag(tags: &[(TagName, u16)]) -> String {
+    let mut str = String::from('[');
+    tags.iter().enumerate().for_each(|(i, (tag, arity))| {
+        if i > 0 {
+            str.push(',');
+        }
+        str.push_str(tag.0.as_str());
+        str.push(' ');
+        str.push_str(&arity.to_string());
+    });
+    str.push(']');
+    str
+}
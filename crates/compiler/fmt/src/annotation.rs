@@ -193,6 +193,15 @@ impl<'a> Formattable for TypeAnnotation<'a> {
 
                 tags.iter().any(|tag| tag.value.is_multiline())
             }
+
+            Tuple { elems, ext } => {
+                match ext {
+                    Some(ann) if ann.value.is_multiline() => return true,
+                    _ => {}
+                }
+
+                elems.iter().any(|elem| elem.value.is_multiline())
+            }
         }
     }
 
@@ -312,6 +321,38 @@ impl<'a> Formattable for TypeAnnotation<'a> {
                 }
             }
 
+            Tuple { elems, ext } => {
+                if elems.is_multiline() {
+                    fmt_collection(buf, indent, Braces::Round, *elems, newlines);
+                } else {
+                    // A function type must be wrapped in parens here, otherwise
+                    // `(a, (b -> c))` would be read back as the function `a, b -> c`
+                    buf.indent(indent);
+                    buf.push('(');
+
+                    let mut it = elems.iter().peekable();
+                    while let Some(elem) = it.next() {
+                        elem.value.format_with_options(
+                            buf,
+                            Parens::InFunctionType,
+                            Newlines::No,
+                            indent,
+                        );
+
+                        if it.peek().is_some() {
+                            buf.push(',');
+                            buf.spaces(1);
+                        }
+                    }
+
+                    buf.push(')');
+                }
+
+                if let Some(loc_ext_ann) = *ext {
+                    loc_ext_ann.value.format(buf, indent);
+                }
+            }
+
             As(lhs, _spaces, TypeHeader { name, vars }) => {
                 // TODO use _spaces?
                 lhs.value
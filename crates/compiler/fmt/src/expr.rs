@@ -35,6 +35,7 @@ impl<'a> Formattable for Expr<'a> {
             | NonBase10Int { .. }
             | SingleQuote(_)
             | Access(_, _)
+            | TupleAccess(_, _)
             | AccessorFunction(_)
             | Var { .. }
             | Underscore { .. }
@@ -47,6 +48,7 @@ impl<'a> Formattable for Expr<'a> {
             Defs(_, _) | When(_, _) => true,
 
             List(items) => items.iter().any(|loc_expr| loc_expr.is_multiline()),
+            Tuple(elems) => elems.iter().any(|loc_expr| loc_expr.is_multiline()),
 
             Str(literal) => {
                 use roc_parse::ast::StrLiteral::*;
@@ -70,6 +72,8 @@ impl<'a> Formattable for Expr<'a> {
                 condition.is_multiline() || continuation.is_multiline()
             }
 
+            Dbg(value, opt_continuation) => value.is_multiline() || opt_continuation.is_some(),
+
             If(branches, final_else) => {
                 final_else.is_multiline()
                     || branches
@@ -367,11 +371,15 @@ impl<'a> Formattable for Expr<'a> {
             Expect(condition, continuation) => {
                 fmt_expect(buf, condition, continuation, self.is_multiline(), indent);
             }
+            Dbg(value, opt_continuation) => {
+                fmt_dbg(buf, value, *opt_continuation, indent);
+            }
             If(branches, final_else) => {
                 fmt_if(buf, branches, final_else, self.is_multiline(), indent);
             }
             When(loc_condition, branches) => fmt_when(buf, loc_condition, branches, indent),
             List(items) => fmt_collection(buf, indent, Braces::Square, *items, Newlines::No),
+            Tuple(elems) => fmt_collection(buf, indent, Braces::Round, *elems, Newlines::No),
             BinOps(lefts, right) => fmt_binops(buf, lefts, right, false, parens, indent),
             UnaryOp(sub_expr, unary_op) => {
                 buf.indent(indent);
@@ -391,7 +399,7 @@ impl<'a> Formattable for Expr<'a> {
                 buf.push('.');
                 buf.push_str(key);
             }
-            Access(expr, key) => {
+            Access(expr, key) | TupleAccess(expr, key) => {
                 expr.format_with_options(buf, Parens::InApply, Newlines::Yes, indent);
                 buf.push('.');
                 buf.push_str(key);
@@ -852,6 +860,36 @@ fn fmt_expect<'a, 'buf>(
     continuation.format(buf, indent);
 }
 
+fn fmt_dbg<'a, 'buf>(
+    buf: &mut Buf<'buf>,
+    value: &'a Loc<Expr<'a>>,
+    opt_continuation: Option<&'a Loc<Expr<'a>>>,
+    indent: u16,
+) {
+    if opt_continuation.is_some() {
+        buf.ensure_ends_with_newline();
+    }
+
+    buf.indent(indent);
+    buf.push_str("dbg");
+
+    let return_indent = if value.is_multiline() {
+        buf.newline();
+        indent + INDENT
+    } else {
+        buf.spaces(1);
+        indent
+    };
+
+    value.format(buf, return_indent);
+
+    if let Some(continuation) = opt_continuation {
+        buf.ensure_ends_with_newline();
+
+        continuation.format(buf, indent);
+    }
+}
+
 fn fmt_if<'a, 'buf>(
     buf: &mut Buf<'buf>,
     branches: &'a [(Loc<Expr<'a>>, Loc<Expr<'a>>)],
@@ -6,6 +6,7 @@ pub mod collection;
 pub mod def;
 pub mod expr;
 pub mod module;
+pub mod partial;
 pub mod pattern;
 pub mod spaces;
 
@@ -148,7 +149,7 @@ impl<'a> Buf<'a> {
 }
 
 /// Ensures the text ends in a newline with no whitespace preceding it.
-fn fmt_text_eof(text: &mut bumpalo::collections::String<'_>) {
+pub(crate) fn fmt_text_eof(text: &mut bumpalo::collections::String<'_>) {
     let mut chars_rev = text.chars().rev();
     let mut last_whitespace = None;
     let mut last_whitespace_index = text.len();
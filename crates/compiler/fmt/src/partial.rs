@@ -0,0 +1,124 @@
+//! Formatting modules which don't entirely parse, and formatting parts of modules.
+
+use std::ops::Range;
+
+use bumpalo::{collections::String, Bump};
+use roc_parse::module::{self, module_defs, module_defs_recovering, DefsChunk};
+use roc_parse::parser::{EHeader, Parser, SourceError};
+use roc_parse::state::State;
+
+use crate::def::fmt_defs;
+use crate::module::fmt_module;
+use crate::{fmt_text_eof, Buf};
+
+/// Formats the whole module. Top-level defs which don't parse are left the way they were written,
+/// so this only fails if the module's header doesn't parse.
+pub fn format_module<'a>(
+    arena: &'a Bump,
+    src: &'a str,
+) -> Result<&'a str, SourceError<'a, EHeader<'a>>> {
+    let (header, state) = module::parse_header(arena, State::new(src.as_bytes()))?;
+
+    let mut buf = Buf::new_in(arena);
+    fmt_module(&mut buf, arena.alloc(header));
+
+    match module_defs().parse(arena, state.clone()) {
+        Ok((_, defs, _)) => {
+            fmt_defs(&mut buf, &defs, 0);
+            buf.fmt_end_of_file();
+
+            Ok(buf.into_bump_str())
+        }
+        Err(_) => {
+            let mut text = String::from_str_in(buf.into_bump_str(), arena);
+
+            for chunk in module_defs_recovering(arena, state) {
+                push_chunk(&mut text, arena, src, &chunk);
+            }
+
+            fmt_text_eof(&mut text);
+
+            Ok(text.into_bump_str())
+        }
+    }
+}
+
+/// Formats the header and the top-level defs which overlap the byte range `range` of `src`, or
+/// the one containing `range.start` if the range is empty. Top-level defs which don't parse are
+/// left the way they were written.
+///
+/// Returns the byte range of `src` to replace, which covers whole defs, along with the formatted
+/// code to replace it with.
+pub fn format_range<'a>(
+    arena: &'a Bump,
+    src: &'a str,
+    range: Range<usize>,
+) -> Result<(Range<usize>, &'a str), SourceError<'a, EHeader<'a>>> {
+    let (header, state) = module::parse_header(arena, State::new(src.as_bytes()))?;
+    let header_end = state.pos().offset as usize;
+
+    let overlaps = |start: usize, end: usize| {
+        start < range.end.max(range.start + 1) && range.start < end.max(start + 1)
+    };
+
+    let mut text = String::new_in(arena);
+    let mut replaced: Option<Range<usize>> = None;
+
+    if overlaps(0, header_end) {
+        let mut buf = Buf::new_in(arena);
+        fmt_module(&mut buf, arena.alloc(header));
+
+        // Whatever whitespace the header parser took stays the way it was.
+        let header_src = &src[..header_end];
+
+        text.push_str(buf.into_bump_str().trim_end());
+        text.push_str(&header_src[header_src.trim_end().len()..]);
+
+        replaced = Some(0..header_end);
+    }
+
+    for chunk in module_defs_recovering(arena, state) {
+        let start = chunk.region.start().offset as usize;
+        let end = chunk.region.end().offset as usize;
+
+        if overlaps(start, end) {
+            push_chunk(&mut text, arena, src, &chunk);
+
+            replaced = Some(replaced.map_or(start, |replaced| replaced.start)..end);
+        }
+    }
+
+    let replaced = replaced.unwrap_or(range.start..range.start);
+
+    if replaced.end == src.len() && !replaced.is_empty() {
+        fmt_text_eof(&mut text);
+    }
+
+    Ok((replaced, text.into_bump_str()))
+}
+
+/// Formats a stretch of defs, keeping the blank line (if there was one) after it. If the stretch
+/// didn't parse, it stays the way it was written.
+fn push_chunk<'a>(text: &mut String<'a>, arena: &'a Bump, src: &str, chunk: &DefsChunk<'a>) {
+    let chunk_src = &src[chunk.region.start().offset as usize..chunk.region.end().offset as usize];
+
+    match &chunk.defs {
+        Ok(defs) => {
+            let mut buf = Buf::new_in(arena);
+            fmt_defs(&mut buf, defs, 0);
+
+            text.push_str(buf.into_bump_str().trim_end());
+
+            let newlines = chunk_src[chunk_src.trim_end().len()..]
+                .matches('\n')
+                .count();
+
+            for _ in 0..newlines.min(2) {
+                text.push('\n');
+            }
+        }
+        Err(_) => {
+            text.push_str(chunk_src);
+        }
+    }
+}
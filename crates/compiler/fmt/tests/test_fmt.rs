@@ -9,6 +9,7 @@ mod test_fmt {
     use roc_fmt::annotation::{Formattable, Newlines, Parens};
     use roc_fmt::def::fmt_defs;
     use roc_fmt::module::fmt_module;
+    use roc_fmt::partial::{format_module, format_range};
     use roc_fmt::Buf;
     use roc_parse::ast::Module;
     use roc_parse::module::{self, module_defs};
@@ -176,6 +177,53 @@ mod test_fmt {
         module_formats_to(input, input);
     }
 
+    // Formats a module which may have top-level defs that don't parse, and checks that
+    // formatting it again doesn't change it.
+    fn partial_module_formats_to(input: &str, expected: &str) {
+        let arena = Bump::new();
+
+        let output = format_module(&arena, input).unwrap_or_else(|error| {
+            panic!(
+                "Unexpected parse failure when parsing this module header:\n\n{}\n\nParse error was:\n\n{:?}\n\n",
+                input, error
+            )
+        });
+
+        assert_multiline_str_eq!(expected, output);
+
+        let reformatted = format_module(&arena, output).unwrap();
+
+        assert_multiline_str_eq!(output, reformatted);
+    }
+
+    // Formats the top-level defs overlapping the first occurrence of `selected` in `input`, and
+    // checks that formatting them again doesn't change them.
+    fn range_formats_to(input: &str, selected: &str, expected: &str) {
+        let arena = Bump::new();
+        let start = input.find(selected).unwrap();
+
+        let (replaced, formatted) =
+            format_range(&arena, input, start..start + selected.len()).unwrap();
+
+        let output = format!(
+            "{}{}{}",
+            &input[..replaced.start],
+            formatted,
+            &input[replaced.end..]
+        );
+
+        assert_multiline_str_eq!(expected, output.as_str());
+
+        let (replaced, reformatted) = format_range(
+            &arena,
+            &output,
+            replaced.start..replaced.start + formatted.len(),
+        )
+        .unwrap();
+
+        assert_multiline_str_eq!(&output[replaced], reformatted);
+    }
+
     // STRING LITERALS
 
     #[test]
@@ -2471,6 +2519,90 @@ mod test_fmt {
         ));
     }
 
+    // TUPLE
+    #[test]
+    fn tuple() {
+        expr_formats_same("(1, 2)");
+        expr_formats_to("(  1,2  )", "(1, 2)");
+        expr_formats_same("(\"a\", (1, 2), [3])");
+    }
+
+    #[test]
+    fn multiline_tuple() {
+        expr_formats_same(indoc!(
+            r#"
+            (
+                1,
+                2,
+            )
+            "#
+        ));
+
+        expr_formats_to(
+            indoc!(
+                r#"
+                ( 1,
+                    2 )
+                "#
+            ),
+            indoc!(
+                r#"
+                (
+                    1,
+                    2,
+                )
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn tuple_access() {
+        expr_formats_same("tup.0");
+        expr_formats_same("(1, 2).1");
+        expr_formats_same("rec.tup.0.field");
+    }
+
+    #[test]
+    fn tuple_patterns() {
+        expr_formats_same(indoc!(
+            r#"
+            swap = \(a, b) -> (b, a)
+
+            swap
+            "#
+        ));
+
+        expr_formats_same(indoc!(
+            r#"
+            when pair is
+                (Ok x, _) -> x
+                (_, y) -> y
+            "#
+        ));
+    }
+
+    #[test]
+    fn tuple_annotations() {
+        expr_formats_same(indoc!(
+            r#"
+            pair : (Str, U64)
+            pair = ("a", 1)
+
+            pair
+            "#
+        ));
+
+        expr_formats_same(indoc!(
+            r#"
+            apply : (a, (a -> b)) -> b
+            apply = \(x, f) -> f x
+
+            apply
+            "#
+        ));
+    }
+
     // LIST
     #[test]
     fn empty_list() {
@@ -5457,6 +5589,265 @@ mod test_fmt {
         ));
     }
 
+    #[test]
+    fn dbg_single_line() {
+        expr_formats_same(indoc!(
+            r#"
+            x = 5
+
+            dbg x
+
+            42
+            "#
+        ));
+
+        expr_formats_same(indoc!(
+            r#"
+            x = dbg (f y)
+
+            x
+            "#
+        ));
+    }
+
+    #[test]
+    fn dbg_multiline() {
+        expr_formats_same(indoc!(
+            r#"
+            x = 5
+
+            dbg
+                foo bar
+                |> baz
+
+            42
+            "#
+        ));
+    }
+
+    // PARTIAL FORMATTING
+
+    #[test]
+    fn partial_module_keeps_broken_def() {
+        partial_module_formats_to(
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                a =   1
+
+                b = (
+
+                c  = 3
+                "#
+            ),
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                a = 1
+
+                b = (
+
+                c = 3
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn partial_module_keeps_annotation_with_body() {
+        partial_module_formats_to(
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                f : Str
+                f =   "hi"
+
+                g = [1,
+                "#
+            ),
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                f : Str
+                f = "hi"
+
+                g = [1,
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn partial_module_keeps_comments() {
+        partial_module_formats_to(
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                # about a
+                a =  1
+
+                # about b
+                b =   2
+
+                c = when
+                "#
+            ),
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                # about a
+                a = 1
+
+                # about b
+                b = 2
+
+                c = when
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn partial_module_closing_bracket_in_first_column() {
+        partial_module_formats_to(
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                a = [
+                    1,
+                    2,
+                ]
+
+                b = ]
+                "#
+            ),
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                a = [
+                    1,
+                    2,
+                ]
+
+                b = ]
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn range_formats_only_selected_def() {
+        range_formats_to(
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                a =   1
+
+                b =   2
+
+                c =   3
+                "#
+            ),
+            "b =   2",
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                a =   1
+
+                b = 2
+
+                c =   3
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn range_formats_def_at_cursor() {
+        range_formats_to(
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                a =   1
+
+                b =   2
+                "#
+            ),
+            "",
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                a =   1
+
+                b =   2
+                "#
+            ),
+        );
+
+        range_formats_to(
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                a =   1
+
+                b =   2
+                "#
+            ),
+            "2",
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                a =   1
+
+                b = 2
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn range_formats_around_broken_def() {
+        range_formats_to(
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                a =   1
+
+                b = (
+
+                c =   3
+                "#
+            ),
+            "b = (\n\nc",
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                a =   1
+
+                b = (
+
+                c = 3
+                "#
+            ),
+        );
+    }
+
     // this is a parse error atm
     //    #[test]
     //    fn multiline_apply() {
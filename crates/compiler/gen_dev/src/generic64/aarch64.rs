@@ -1,10 +1,16 @@
 use crate::generic64::{storage::StorageManager, Assembler, CallConv, RegTrait};
-use crate::Relocation;
+use crate::{
+    sign_extended_int_builtins, single_register_int_builtins, single_register_integers, Relocation,
+};
 use bumpalo::collections::Vec;
 use packed_struct::prelude::*;
+use roc_builtins::bitcode::{FloatWidth, IntWidth};
 use roc_error_macros::internal_error;
 use roc_module::symbol::Symbol;
-use roc_mono::layout::Layout;
+use roc_mono::layout::{Builtin, Layout};
+use roc_target::TargetInfo;
+
+const TARGET_INFO: TargetInfo = TargetInfo::default_aarch64();
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 #[allow(dead_code)]
@@ -101,7 +107,40 @@ impl AArch64GeneralReg {
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 #[allow(dead_code)]
-pub enum AArch64FloatReg {}
+pub enum AArch64FloatReg {
+    V0 = 0,
+    V1 = 1,
+    V2 = 2,
+    V3 = 3,
+    V4 = 4,
+    V5 = 5,
+    V6 = 6,
+    V7 = 7,
+    V8 = 8,
+    V9 = 9,
+    V10 = 10,
+    V11 = 11,
+    V12 = 12,
+    V13 = 13,
+    V14 = 14,
+    V15 = 15,
+    V16 = 16,
+    V17 = 17,
+    V18 = 18,
+    V19 = 19,
+    V20 = 20,
+    V21 = 21,
+    V22 = 22,
+    V23 = 23,
+    V24 = 24,
+    V25 = 25,
+    V26 = 26,
+    V27 = 27,
+    V28 = 28,
+    V29 = 29,
+    V30 = 30,
+    V31 = 31,
+}
 impl RegTrait for AArch64FloatReg {
     fn value(&self) -> u8 {
         *self as u8
@@ -109,21 +148,32 @@ impl RegTrait for AArch64FloatReg {
 }
 impl std::fmt::Display for AArch64FloatReg {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "TODO",)
+        write!(f, "v{}", self.id())
+    }
+}
+
+impl AArch64FloatReg {
+    #[inline(always)]
+    fn id(&self) -> u8 {
+        *self as u8
     }
 }
 
 #[derive(Copy, Clone)]
 pub struct AArch64Assembler {}
 
-// AArch64Call may need to eventually be split by OS,
-// but I think with how we use it, they may all be the same.
+/// AAPCS64 as Linux uses it.
+/// `APPLE` switches to Apple's variant, which packs stack arguments by their natural size.
 #[derive(Copy, Clone)]
-pub struct AArch64Call {}
+pub struct AArch64Call<const APPLE: bool = false> {}
+
+pub type AArch64AppleCall = AArch64Call<true>;
 
 const STACK_ALIGNMENT: u8 = 16;
 
-impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64Call {
+impl<const APPLE: bool> CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler>
+    for AArch64Call<APPLE>
+{
     const BASE_PTR_REG: AArch64GeneralReg = AArch64GeneralReg::FP;
     const STACK_PTR_REG: AArch64GeneralReg = AArch64GeneralReg::ZRSP;
 
@@ -147,6 +197,9 @@ impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64C
         // Don't use platform register: AArch64GeneralReg::PR,
         // Don't use link register: AArch64GeneralReg::LR,
         // Don't use zero register/stack pointer: AArch64GeneralReg::ZRSP,
+        // Don't use the intra-procedure-call scratch registers: AArch64GeneralReg::IP0, AArch64GeneralReg::IP1,
+        // The assembler uses IP0 to build immediates and addresses that don't fit in an instruction.
+        // The calling convention uses IP1 to copy arguments.
 
         // Use callee saved regs last.
         AArch64GeneralReg::X19,
@@ -175,12 +228,63 @@ impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64C
         AArch64GeneralReg::X13,
         AArch64GeneralReg::X14,
         AArch64GeneralReg::X15,
-        AArch64GeneralReg::IP0,
-        AArch64GeneralReg::IP1,
     ];
-    const FLOAT_PARAM_REGS: &'static [AArch64FloatReg] = &[];
-    const FLOAT_RETURN_REGS: &'static [AArch64FloatReg] = Self::FLOAT_PARAM_REGS;
-    const FLOAT_DEFAULT_FREE_REGS: &'static [AArch64FloatReg] = &[];
+    const FLOAT_PARAM_REGS: &'static [AArch64FloatReg] = &[
+        AArch64FloatReg::V0,
+        AArch64FloatReg::V1,
+        AArch64FloatReg::V2,
+        AArch64FloatReg::V3,
+        AArch64FloatReg::V4,
+        AArch64FloatReg::V5,
+        AArch64FloatReg::V6,
+        AArch64FloatReg::V7,
+    ];
+    // Homogeneous float aggregates return up to 4 floats.
+    const FLOAT_RETURN_REGS: &'static [AArch64FloatReg] = &[
+        AArch64FloatReg::V0,
+        AArch64FloatReg::V1,
+        AArch64FloatReg::V2,
+        AArch64FloatReg::V3,
+    ];
+    const FLOAT_DEFAULT_FREE_REGS: &'static [AArch64FloatReg] = &[
+        // The regs we want to use first should be at the end of this vec.
+        // We will use pop to get which reg to use next
+
+        // Use callee saved regs last.
+        AArch64FloatReg::V8,
+        AArch64FloatReg::V9,
+        AArch64FloatReg::V10,
+        AArch64FloatReg::V11,
+        AArch64FloatReg::V12,
+        AArch64FloatReg::V13,
+        AArch64FloatReg::V14,
+        AArch64FloatReg::V15,
+        // Use caller saved regs first.
+        AArch64FloatReg::V31,
+        AArch64FloatReg::V30,
+        AArch64FloatReg::V29,
+        AArch64FloatReg::V28,
+        AArch64FloatReg::V27,
+        AArch64FloatReg::V26,
+        AArch64FloatReg::V25,
+        AArch64FloatReg::V24,
+        AArch64FloatReg::V23,
+        AArch64FloatReg::V22,
+        AArch64FloatReg::V21,
+        AArch64FloatReg::V20,
+        AArch64FloatReg::V19,
+        AArch64FloatReg::V18,
+        AArch64FloatReg::V17,
+        AArch64FloatReg::V16,
+        AArch64FloatReg::V7,
+        AArch64FloatReg::V6,
+        AArch64FloatReg::V5,
+        AArch64FloatReg::V4,
+        AArch64FloatReg::V3,
+        AArch64FloatReg::V2,
+        AArch64FloatReg::V1,
+        AArch64FloatReg::V0,
+    ];
 
     const SHADOW_SPACE_SIZE: u8 = 0;
 
@@ -201,8 +305,19 @@ impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64C
         )
     }
     #[inline(always)]
-    fn float_callee_saved(_reg: &AArch64FloatReg) -> bool {
-        todo!("AArch64 FloatRegs");
+    fn float_callee_saved(reg: &AArch64FloatReg) -> bool {
+        // Only the bottom 64 bits are callee saved, but we never use more than that.
+        matches!(
+            reg,
+            AArch64FloatReg::V8
+                | AArch64FloatReg::V9
+                | AArch64FloatReg::V10
+                | AArch64FloatReg::V11
+                | AArch64FloatReg::V12
+                | AArch64FloatReg::V13
+                | AArch64FloatReg::V14
+                | AArch64FloatReg::V15
+        )
     }
 
     #[inline(always)]
@@ -213,9 +328,24 @@ impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64C
         requested_stack_size: i32,
         fn_call_stack_size: i32,
     ) -> i32 {
-        // Full size is upcast to i64 to make sure we don't overflow here.
+        // Save the frame pointer and link register, then point the frame pointer at them.
+        // This is always needed, calls overwrite the link register.
+        stp_pre_reg64_reg64_reg64_imm7(
+            buf,
+            AArch64GeneralReg::FP,
+            AArch64GeneralReg::LR,
+            AArch64GeneralReg::ZRSP,
+            -2,
+        );
+        AArch64Assembler::add_reg64_reg64_imm32(
+            buf,
+            AArch64GeneralReg::FP,
+            AArch64GeneralReg::ZRSP,
+            0,
+        );
+
         let full_stack_size = match requested_stack_size
-            .checked_add(8 * (saved_general_regs.len() + saved_float_regs.len()) as i32 + 8) // The extra 8 is space to store the frame pointer.
+            .checked_add(8 * (saved_general_regs.len() + saved_float_regs.len()) as i32)
             .and_then(|size| size.checked_add(fn_call_stack_size))
         {
             Some(size) => size,
@@ -233,11 +363,6 @@ impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64C
         };
         if let Some(aligned_stack_size) = full_stack_size.checked_add(offset as i32) {
             if aligned_stack_size > 0 {
-                AArch64Assembler::mov_reg64_reg64(
-                    buf,
-                    AArch64GeneralReg::FP,
-                    AArch64GeneralReg::ZRSP,
-                );
                 AArch64Assembler::sub_reg64_reg64_imm32(
                     buf,
                     AArch64GeneralReg::ZRSP,
@@ -245,21 +370,16 @@ impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64C
                     aligned_stack_size,
                 );
 
+                // Put values at the top of the stack to avoid conflicts with previously saved variables.
                 // All the following stores could be optimized by using `STP` to store pairs.
-                let mut offset = aligned_stack_size;
-                offset -= 8;
-                AArch64Assembler::mov_stack32_reg64(buf, offset, AArch64GeneralReg::LR);
-                offset -= 8;
-                AArch64Assembler::mov_stack32_reg64(buf, offset, AArch64GeneralReg::FP);
-
-                offset = aligned_stack_size - fn_call_stack_size;
+                let mut offset = aligned_stack_size - fn_call_stack_size;
                 for reg in saved_general_regs {
+                    AArch64Assembler::mov_base32_reg64(buf, -offset, *reg);
                     offset -= 8;
-                    AArch64Assembler::mov_base32_reg64(buf, offset, *reg);
                 }
                 for reg in saved_float_regs {
+                    AArch64Assembler::mov_base32_freg64(buf, -offset, *reg);
                     offset -= 8;
-                    AArch64Assembler::mov_base32_freg64(buf, offset, *reg);
                 }
                 aligned_stack_size
             } else {
@@ -279,21 +399,15 @@ impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64C
         fn_call_stack_size: i32,
     ) {
         if aligned_stack_size > 0 {
-            // All the following stores could be optimized by using `STP` to store pairs.
-            let mut offset = aligned_stack_size;
-            offset -= 8;
-            AArch64Assembler::mov_reg64_stack32(buf, AArch64GeneralReg::LR, offset);
-            offset -= 8;
-            AArch64Assembler::mov_reg64_stack32(buf, AArch64GeneralReg::FP, offset);
-
-            offset = aligned_stack_size - fn_call_stack_size;
+            // All the following loads could be optimized by using `LDP` to load pairs.
+            let mut offset = aligned_stack_size - fn_call_stack_size;
             for reg in saved_general_regs {
+                AArch64Assembler::mov_reg64_base32(buf, *reg, -offset);
                 offset -= 8;
-                AArch64Assembler::mov_reg64_base32(buf, *reg, offset);
             }
             for reg in saved_float_regs {
+                AArch64Assembler::mov_freg64_base32(buf, *reg, -offset);
                 offset -= 8;
-                AArch64Assembler::mov_freg64_base32(buf, *reg, offset);
             }
             AArch64Assembler::add_reg64_reg64_imm32(
                 buf,
@@ -302,87 +416,641 @@ impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64C
                 aligned_stack_size,
             );
         }
+        ldp_post_reg64_reg64_reg64_imm7(
+            buf,
+            AArch64GeneralReg::FP,
+            AArch64GeneralReg::LR,
+            AArch64GeneralReg::ZRSP,
+            2,
+        );
     }
 
     #[inline(always)]
     fn load_args<'a>(
-        _buf: &mut Vec<'a, u8>,
-        _storage_manager: &mut StorageManager<
+        buf: &mut Vec<'a, u8>,
+        storage_manager: &mut StorageManager<
             'a,
             AArch64GeneralReg,
             AArch64FloatReg,
             AArch64Assembler,
-            AArch64Call,
+            Self,
         >,
-        _args: &'a [(Layout<'a>, Symbol)],
-        _ret_layout: &Layout<'a>,
+        args: &'a [(Layout<'a>, Symbol)],
+        ret_layout: &Layout<'a>,
     ) {
-        todo!("Loading args for AArch64");
+        // 16 is the size of the saved frame pointer and link register.
+        let mut allocator = ArgAllocator::new(16, APPLE);
+        if Self::returns_via_arg_pointer(ret_layout) {
+            storage_manager.ret_pointer_arg(AArch64GeneralReg::XR);
+        }
+        for (layout, sym) in args.iter() {
+            let class = ArgClass::of(layout);
+            match (class, allocator.next(class)) {
+                (_, ArgLocation::NoData) => {
+                    storage_manager.no_data_arg(sym);
+                }
+                (ArgClass::General { .. }, ArgLocation::GeneralRegs(i, _)) => {
+                    storage_manager.general_reg_arg(sym, Self::GENERAL_PARAM_REGS[i]);
+                }
+                (ArgClass::Float { .. }, ArgLocation::FloatRegs(i, _)) => {
+                    storage_manager.float_reg_arg(sym, Self::FLOAT_PARAM_REGS[i]);
+                }
+                (ArgClass::General { .. } | ArgClass::Float { .. }, ArgLocation::Stack(offset)) => {
+                    let size = layout.stack_size(TARGET_INFO);
+                    if size == 8 {
+                        storage_manager.primitive_stack_arg(sym, offset);
+                    } else {
+                        // Only the low bytes of the slot belong to the argument, and with packed arguments
+                        // the slot may not even be aligned. Extend it into a full slot of our own.
+                        let base_offset = storage_manager.claim_primitive_stack_arg(sym);
+                        if matches!(layout, Layout::Builtin(sign_extended_int_builtins!())) {
+                            AArch64Assembler::movsx_reg64_base32(
+                                buf,
+                                AArch64GeneralReg::IP1,
+                                offset,
+                                size as u8,
+                            );
+                        } else {
+                            AArch64Assembler::movzx_reg64_base32(
+                                buf,
+                                AArch64GeneralReg::IP1,
+                                offset,
+                                size as u8,
+                            );
+                        }
+                        AArch64Assembler::mov_base32_reg64(
+                            buf,
+                            base_offset,
+                            AArch64GeneralReg::IP1,
+                        );
+                    }
+                }
+                (ArgClass::Composite { size, .. }, ArgLocation::GeneralRegs(first, count)) => {
+                    // Spill the registers, so the value lives on the stack like any other struct.
+                    let base_offset = storage_manager.claim_stack_area(sym, size);
+                    for (i, reg) in Self::GENERAL_PARAM_REGS[first..first + count]
+                        .iter()
+                        .enumerate()
+                    {
+                        AArch64Assembler::mov_base32_reg64(buf, base_offset + 8 * i as i32, *reg);
+                    }
+                }
+                (ArgClass::Hfa { width, count }, ArgLocation::FloatRegs(first, _)) => {
+                    let base_offset =
+                        storage_manager.claim_stack_area(sym, layout.stack_size(TARGET_INFO));
+                    let width_bytes = width.stack_size() as i32;
+                    for (i, reg) in Self::FLOAT_PARAM_REGS[first..first + count]
+                        .iter()
+                        .enumerate()
+                    {
+                        AArch64Assembler::mov_base32_freg(
+                            buf,
+                            width,
+                            base_offset + width_bytes * i as i32,
+                            *reg,
+                        );
+                    }
+                }
+                (ArgClass::Composite { .. } | ArgClass::Hfa { .. }, ArgLocation::Stack(offset)) => {
+                    let size = layout.stack_size(TARGET_INFO);
+                    if offset % 8 == 0 {
+                        storage_manager.complex_stack_arg(sym, offset, size);
+                    } else {
+                        // Packed arguments can start anywhere, but stack values must be 8 byte aligned.
+                        let base_offset = storage_manager.claim_stack_area(sym, size);
+                        Self::copy_mem(
+                            buf,
+                            AArch64GeneralReg::FP,
+                            offset,
+                            AArch64GeneralReg::FP,
+                            base_offset,
+                            size,
+                        );
+                    }
+                }
+                (ArgClass::Indirect { size }, location) => {
+                    // We got a pointer to the caller's copy. Copy it again into our own stack.
+                    let ptr = match location {
+                        ArgLocation::GeneralRegs(i, _) => Self::GENERAL_PARAM_REGS[i],
+                        ArgLocation::Stack(offset) => {
+                            // X9 is caller saved and never holds an argument, so it is free here.
+                            AArch64Assembler::mov_reg64_base32(buf, AArch64GeneralReg::X9, offset);
+                            AArch64GeneralReg::X9
+                        }
+                        x => internal_error!("Indirect arguments can't be passed in {:?}", x),
+                    };
+                    let base_offset = storage_manager.claim_stack_area(sym, size);
+                    Self::copy_mem(buf, ptr, 0, AArch64GeneralReg::FP, base_offset, size);
+                }
+                (class, location) => {
+                    internal_error!("{:?} arguments can't be passed in {:?}", class, location)
+                }
+            }
+        }
     }
 
     #[inline(always)]
     fn store_args<'a>(
-        _buf: &mut Vec<'a, u8>,
-        _storage_manager: &mut StorageManager<
+        buf: &mut Vec<'a, u8>,
+        storage_manager: &mut StorageManager<
             'a,
             AArch64GeneralReg,
             AArch64FloatReg,
             AArch64Assembler,
-            AArch64Call,
+            Self,
         >,
-        _dst: &Symbol,
-        _args: &[Symbol],
-        _arg_layouts: &[Layout<'a>],
-        _ret_layout: &Layout<'a>,
+        dst: &Symbol,
+        args: &[Symbol],
+        arg_layouts: &[Layout<'a>],
+        ret_layout: &Layout<'a>,
     ) {
-        todo!("Storing args for AArch64");
+        if Self::returns_via_arg_pointer(ret_layout) {
+            // Save space on the stack for the result we will be return.
+            let base_offset =
+                storage_manager.claim_stack_area(dst, ret_layout.stack_size(TARGET_INFO));
+            // Set the indirect result location to the address base + offset.
+            AArch64Assembler::add_reg64_reg64_imm32(
+                buf,
+                AArch64GeneralReg::XR,
+                AArch64GeneralReg::FP,
+                base_offset,
+            );
+        }
+
+        // Copies of indirect arguments go right after the stack arguments.
+        let mut allocator = ArgAllocator::new(0, APPLE);
+        for layout in arg_layouts.iter() {
+            allocator.next(ArgClass::of(layout));
+        }
+        let mut copy_offset = round_up_to_8(allocator.stack_offset as u32) as i32;
+
+        // First fill in the stack.
+        // Argument registers are only loaded after this, so the first float one can be used as a buffer.
+        let mut allocator = ArgAllocator::new(0, APPLE);
+        for (sym, layout) in args.iter().zip(arg_layouts.iter()) {
+            let class = ArgClass::of(layout);
+            match (class, allocator.next(class)) {
+                (ArgClass::General { size }, ArgLocation::Stack(offset)) => {
+                    storage_manager.load_to_specified_general_reg(buf, sym, AArch64GeneralReg::IP1);
+                    AArch64Assembler::mov_stack32_reg(buf, size, offset, AArch64GeneralReg::IP1);
+                }
+                (ArgClass::Float { width }, ArgLocation::Stack(offset)) => {
+                    storage_manager.load_to_specified_float_reg(
+                        buf,
+                        sym,
+                        Self::FLOAT_PARAM_REGS[0],
+                    );
+                    AArch64Assembler::mov_stack32_freg(
+                        buf,
+                        width,
+                        offset,
+                        Self::FLOAT_PARAM_REGS[0],
+                    );
+                }
+                (ArgClass::Composite { .. } | ArgClass::Hfa { .. }, ArgLocation::Stack(offset)) => {
+                    let (base_offset, size) = storage_manager.stack_offset_and_size(sym);
+                    Self::copy_mem(
+                        buf,
+                        AArch64GeneralReg::FP,
+                        base_offset,
+                        AArch64GeneralReg::ZRSP,
+                        offset,
+                        size,
+                    );
+                }
+                (ArgClass::Indirect { .. }, location) => {
+                    // The callee gets a pointer to a copy, so it can't change our value.
+                    let (base_offset, size) = storage_manager.stack_offset_and_size(sym);
+                    Self::copy_mem(
+                        buf,
+                        AArch64GeneralReg::FP,
+                        base_offset,
+                        AArch64GeneralReg::ZRSP,
+                        copy_offset,
+                        size,
+                    );
+                    match location {
+                        ArgLocation::GeneralRegs(i, _) => {
+                            AArch64Assembler::add_reg64_reg64_imm32(
+                                buf,
+                                Self::GENERAL_PARAM_REGS[i],
+                                AArch64GeneralReg::ZRSP,
+                                copy_offset,
+                            );
+                        }
+                        ArgLocation::Stack(offset) => {
+                            AArch64Assembler::add_reg64_reg64_imm32(
+                                buf,
+                                AArch64GeneralReg::IP1,
+                                AArch64GeneralReg::ZRSP,
+                                copy_offset,
+                            );
+                            AArch64Assembler::mov_stack32_reg64(
+                                buf,
+                                offset,
+                                AArch64GeneralReg::IP1,
+                            );
+                        }
+                        x => internal_error!("Indirect arguments can't be passed in {:?}", x),
+                    }
+                    copy_offset += round_up_to_8(size) as i32;
+                }
+                _ => {}
+            }
+        }
+
+        // Then load the argument registers.
+        let mut allocator = ArgAllocator::new(0, APPLE);
+        for (sym, layout) in args.iter().zip(arg_layouts.iter()) {
+            let class = ArgClass::of(layout);
+            match (class, allocator.next(class)) {
+                (ArgClass::General { .. }, ArgLocation::GeneralRegs(i, _)) => {
+                    storage_manager.load_to_specified_general_reg(
+                        buf,
+                        sym,
+                        Self::GENERAL_PARAM_REGS[i],
+                    );
+                }
+                (ArgClass::Float { .. }, ArgLocation::FloatRegs(i, _)) => {
+                    storage_manager.load_to_specified_float_reg(
+                        buf,
+                        sym,
+                        Self::FLOAT_PARAM_REGS[i],
+                    );
+                }
+                (ArgClass::Composite { .. }, ArgLocation::GeneralRegs(first, count)) => {
+                    let (base_offset, _) = storage_manager.stack_offset_and_size(sym);
+                    for (i, reg) in Self::GENERAL_PARAM_REGS[first..first + count]
+                        .iter()
+                        .enumerate()
+                    {
+                        AArch64Assembler::mov_reg64_base32(buf, *reg, base_offset + 8 * i as i32);
+                    }
+                }
+                (ArgClass::Hfa { width, count }, ArgLocation::FloatRegs(first, _)) => {
+                    let (base_offset, _) = storage_manager.stack_offset_and_size(sym);
+                    let width_bytes = width.stack_size() as i32;
+                    for (i, reg) in Self::FLOAT_PARAM_REGS[first..first + count]
+                        .iter()
+                        .enumerate()
+                    {
+                        AArch64Assembler::mov_freg_base32(
+                            buf,
+                            width,
+                            *reg,
+                            base_offset + width_bytes * i as i32,
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+        storage_manager.update_fn_call_stack_size(copy_offset as u32);
     }
 
     fn return_complex_symbol<'a>(
-        _buf: &mut Vec<'a, u8>,
-        _storage_manager: &mut StorageManager<
+        buf: &mut Vec<'a, u8>,
+        storage_manager: &mut StorageManager<
             'a,
             AArch64GeneralReg,
             AArch64FloatReg,
             AArch64Assembler,
-            AArch64Call,
+            Self,
         >,
-        _sym: &Symbol,
-        _layout: &Layout<'a>,
+        sym: &Symbol,
+        layout: &Layout<'a>,
     ) {
-        todo!("Returning complex symbols for AArch64");
+        match ArgClass::of(layout) {
+            ArgClass::General { .. } | ArgClass::Float { .. } => {
+                internal_error!("single register layouts are not complex symbols");
+            }
+            ArgClass::NoData => {}
+            ArgClass::Composite { size, .. } => {
+                let (base_offset, _) = storage_manager.stack_offset_and_size(sym);
+                debug_assert_eq!(base_offset % 8, 0);
+                AArch64Assembler::mov_reg64_base32(buf, Self::GENERAL_RETURN_REGS[0], base_offset);
+                if size > 8 {
+                    AArch64Assembler::mov_reg64_base32(
+                        buf,
+                        Self::GENERAL_RETURN_REGS[1],
+                        base_offset + 8,
+                    );
+                }
+            }
+            ArgClass::Hfa { width, count } => {
+                let (base_offset, _) = storage_manager.stack_offset_and_size(sym);
+                let width_bytes = width.stack_size() as i32;
+                for (i, reg) in Self::FLOAT_RETURN_REGS[..count].iter().enumerate() {
+                    AArch64Assembler::mov_freg_base32(
+                        buf,
+                        width,
+                        *reg,
+                        base_offset + width_bytes * i as i32,
+                    );
+                }
+            }
+            ArgClass::Indirect { .. } => {
+                // This is a large type returned via the indirect result location.
+                storage_manager.copy_symbol_to_arg_pointer(buf, sym, layout);
+            }
+        }
     }
 
     fn load_returned_complex_symbol<'a>(
-        _buf: &mut Vec<'a, u8>,
-        _storage_manager: &mut StorageManager<
+        buf: &mut Vec<'a, u8>,
+        storage_manager: &mut StorageManager<
             'a,
             AArch64GeneralReg,
             AArch64FloatReg,
             AArch64Assembler,
-            AArch64Call,
+            Self,
         >,
-        _sym: &Symbol,
-        _layout: &Layout<'a>,
+        sym: &Symbol,
+        layout: &Layout<'a>,
+    ) {
+        match ArgClass::of(layout) {
+            ArgClass::General { .. } | ArgClass::Float { .. } => {
+                internal_error!("single register layouts are not complex symbols");
+            }
+            ArgClass::NoData => {}
+            ArgClass::Composite { size, .. } => {
+                let offset = storage_manager.claim_stack_area(sym, size);
+                AArch64Assembler::mov_base32_reg64(buf, offset, Self::GENERAL_RETURN_REGS[0]);
+                if size > 8 {
+                    AArch64Assembler::mov_base32_reg64(
+                        buf,
+                        offset + 8,
+                        Self::GENERAL_RETURN_REGS[1],
+                    );
+                }
+            }
+            ArgClass::Hfa { width, count } => {
+                let offset = storage_manager.claim_stack_area(sym, layout.stack_size(TARGET_INFO));
+                let width_bytes = width.stack_size() as i32;
+                for (i, reg) in Self::FLOAT_RETURN_REGS[..count].iter().enumerate() {
+                    AArch64Assembler::mov_base32_freg(
+                        buf,
+                        width,
+                        offset + width_bytes * i as i32,
+                        *reg,
+                    );
+                }
+            }
+            ArgClass::Indirect { .. } => {
+                // This should have been recieved via the indirect result location.
+                // That means the value is already loaded onto the stack area we allocated before the call.
+                // Nothing to do.
+            }
+        }
+    }
+}
+
+impl<const APPLE: bool> AArch64Call<APPLE> {
+    fn returns_via_arg_pointer(ret_layout: &Layout) -> bool {
+        matches!(ArgClass::of(ret_layout), ArgClass::Indirect { .. })
+    }
+
+    /// Copies `size` bytes from `src + src_offset` to `dst + dst_offset`.
+    /// Neither side has to be aligned, so this also works for packed stack arguments.
+    /// IP1 is used as a buffer.
+    fn copy_mem(
+        buf: &mut Vec<'_, u8>,
+        src: AArch64GeneralReg,
+        src_offset: i32,
+        dst: AArch64GeneralReg,
+        dst_offset: i32,
+        size: u32,
     ) {
-        todo!("Loading returned complex symbols for AArch64");
+        let mut copied = 0;
+        while copied < size {
+            let remaining = size - copied;
+            let chunk = if remaining >= 8 {
+                8
+            } else if remaining >= 4 {
+                4
+            } else if remaining >= 2 {
+                2
+            } else {
+                1
+            };
+            AArch64Assembler::load_store_offset(
+                buf,
+                LoadStoreOp::general_load(chunk),
+                AArch64GeneralReg::IP1,
+                src,
+                src_offset + copied as i32,
+            );
+            AArch64Assembler::load_store_offset(
+                buf,
+                LoadStoreOp::general_store(chunk),
+                AArch64GeneralReg::IP1,
+                dst,
+                dst_offset + copied as i32,
+            );
+            copied += chunk;
+        }
+    }
+}
+
+/// How AAPCS64 passes an argument or return value with a given layout.
+/// https://github.com/ARM-software/abi-aa/blob/main/aapcs64/aapcs64.rst#parameter-passing
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ArgClass {
+    NoData,
+    /// Fits in a single general register.
+    General {
+        size: u32,
+    },
+    /// Fits in a single float register.
+    Float {
+        width: FloatWidth,
+    },
+    /// Up to 16 bytes, passed in one or two general registers.
+    /// 16 byte aligned values, like 128 bit integers, start at an even register.
+    Composite {
+        size: u32,
+        alignment: u32,
+    },
+    /// A homogeneous floating-point aggregate, one to four floats of the same width.
+    /// Each float gets its own float register.
+    Hfa {
+        width: FloatWidth,
+        count: usize,
+    },
+    /// Anything else over 16 bytes, passed as a pointer to a copy.
+    Indirect {
+        size: u32,
+    },
+}
+
+impl ArgClass {
+    fn of(layout: &Layout) -> Self {
+        match layout {
+            single_register_integers!() => ArgClass::General {
+                size: layout.stack_size(TARGET_INFO),
+            },
+            Layout::Builtin(Builtin::Float(width @ (FloatWidth::F32 | FloatWidth::F64))) => {
+                ArgClass::Float { width: *width }
+            }
+            _ => {
+                let size = layout.stack_size(TARGET_INFO);
+                if size == 0 {
+                    ArgClass::NoData
+                } else if let Some((width, count)) = homogeneous_floats(layout) {
+                    ArgClass::Hfa { width, count }
+                } else if size <= 16 {
+                    ArgClass::Composite {
+                        size,
+                        alignment: layout.alignment_bytes(TARGET_INFO),
+                    }
+                } else {
+                    ArgClass::Indirect { size }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the width and number of floats if the layout is a struct of one to four floats of the same width.
+/// Nested structs are flattened.
+fn homogeneous_floats(layout: &Layout) -> Option<(FloatWidth, usize)> {
+    fn count_floats(layout: &Layout, width: &mut Option<FloatWidth>, count: &mut usize) -> bool {
+        match layout {
+            Layout::Builtin(Builtin::Float(field_width @ (FloatWidth::F32 | FloatWidth::F64))) => {
+                *count += 1;
+                *width.get_or_insert(*field_width) == *field_width
+            }
+            Layout::Struct { field_layouts, .. } => field_layouts
+                .iter()
+                .all(|field| count_floats(field, width, count)),
+            _ => false,
+        }
+    }
+
+    let mut width = None;
+    let mut count = 0;
+    match layout {
+        Layout::Struct { .. }
+            if count_floats(layout, &mut width, &mut count) && (1..=4).contains(&count) =>
+        {
+            width.map(|width| (width, count))
+        }
+        _ => None,
+    }
+}
+
+/// Where an argument goes, with registers given as indices into the param regs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ArgLocation {
+    NoData,
+    /// The first register and the number of registers.
+    GeneralRegs(usize, usize),
+    /// The first register and the number of registers.
+    FloatRegs(usize, usize),
+    /// The offset from the first stack argument.
+    Stack(i32),
+}
+
+/// Hands out argument locations in the order AAPCS64 assigns them.
+/// On Linux every stack argument takes at least 8 bytes.
+/// Apple platforms pack stack arguments by their natural size and alignment instead.
+struct ArgAllocator {
+    general_i: usize,
+    float_i: usize,
+    stack_offset: i32,
+    packed: bool,
+}
+
+impl ArgAllocator {
+    fn new(stack_offset: i32, packed: bool) -> Self {
+        Self {
+            general_i: 0,
+            float_i: 0,
+            stack_offset,
+            packed,
+        }
+    }
+
+    fn next(&mut self, class: ArgClass) -> ArgLocation {
+        match class {
+            ArgClass::NoData => ArgLocation::NoData,
+            ArgClass::General { size } => self.general_regs(1, size, size),
+            ArgClass::Indirect { .. } => self.general_regs(1, 8, 8),
+            ArgClass::Composite { size, alignment } => {
+                // Composites always take a multiple of 8 bytes, even when packed.
+                self.general_regs(((size + 7) / 8) as usize, round_up_to_8(size), alignment)
+            }
+            ArgClass::Float { width } => self.float_regs(1, width.stack_size(), width.stack_size()),
+            ArgClass::Hfa { width, count } => {
+                self.float_regs(count, width.stack_size() * count as u32, width.stack_size())
+            }
+        }
+    }
+
+    fn general_regs(&mut self, count: usize, size: u32, alignment: u32) -> ArgLocation {
+        let reg_count = <AArch64Call>::GENERAL_PARAM_REGS.len();
+        let first = if alignment == 16 {
+            self.general_i + self.general_i % 2
+        } else {
+            self.general_i
+        };
+        if first + count <= reg_count {
+            self.general_i = first + count;
+            ArgLocation::GeneralRegs(first, count)
+        } else {
+            // Once a value doesn't fit, no later value can use the registers either.
+            self.general_i = reg_count;
+            self.stack(size, alignment)
+        }
+    }
+
+    fn float_regs(&mut self, count: usize, size: u32, alignment: u32) -> ArgLocation {
+        let reg_count = <AArch64Call>::FLOAT_PARAM_REGS.len();
+        if self.float_i + count <= reg_count {
+            let first = self.float_i;
+            self.float_i += count;
+            ArgLocation::FloatRegs(first, count)
+        } else {
+            // Once a value doesn't fit, no later value can use the registers either.
+            self.float_i = reg_count;
+            self.stack(size, alignment)
+        }
+    }
+
+    fn stack(&mut self, size: u32, alignment: u32) -> ArgLocation {
+        let (size, alignment) = if self.packed {
+            (size, alignment)
+        } else {
+            (round_up_to_8(size), alignment.max(8))
+        };
+        let alignment = alignment as i32;
+        let offset = (self.stack_offset + alignment - 1) / alignment * alignment;
+        self.stack_offset = offset + size as i32;
+        ArgLocation::Stack(offset)
     }
 }
 
+#[inline(always)]
+fn round_up_to_8(size: u32) -> u32 {
+    (size + 7) & !7
+}
+
 impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
     #[inline(always)]
-    fn abs_reg64_reg64(_buf: &mut Vec<'_, u8>, _dst: AArch64GeneralReg, _src: AArch64GeneralReg) {
-        todo!("abs_reg64_reg64 for AArch64");
+    fn abs_reg64_reg64(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, src: AArch64GeneralReg) {
+        buf.reserve(8);
+        cmp_reg64_imm12(buf, src, 0);
+        cneg_reg64_reg64_cond(buf, dst, src, ConditionCode::LT);
     }
 
     #[inline(always)]
     fn abs_freg64_freg64(
-        _buf: &mut Vec<'_, u8>,
+        buf: &mut Vec<'_, u8>,
         _relocs: &mut Vec<'_, Relocation>,
-        _dst: AArch64FloatReg,
-        _src: AArch64FloatReg,
+        dst: AArch64FloatReg,
+        src: AArch64FloatReg,
     ) {
-        todo!("abs_reg64_reg64 for AArch64");
+        fabs_freg64_freg64(buf, dst, src);
     }
 
     #[inline(always)]
@@ -392,13 +1060,7 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         src: AArch64GeneralReg,
         imm32: i32,
     ) {
-        if imm32 < 0 {
-            todo!("immediate addition with values less than 0");
-        } else if imm32 < 0xFFF {
-            add_reg64_reg64_imm12(buf, dst, src, imm32 as u16);
-        } else {
-            todo!("immediate additions with values greater than 12bits");
-        }
+        Self::add_reg64_reg64_imm(buf, dst, src, imm32 as i64);
     }
     #[inline(always)]
     fn add_reg64_reg64_reg64(
@@ -411,108 +1073,130 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
     }
     #[inline(always)]
     fn add_freg32_freg32_freg32(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64FloatReg,
-        _src1: AArch64FloatReg,
-        _src2: AArch64FloatReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64FloatReg,
+        src1: AArch64FloatReg,
+        src2: AArch64FloatReg,
     ) {
-        todo!("adding floats for AArch64");
+        fadd_freg32_freg32_freg32(buf, dst, src1, src2);
     }
     #[inline(always)]
     fn add_freg64_freg64_freg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64FloatReg,
-        _src1: AArch64FloatReg,
-        _src2: AArch64FloatReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64FloatReg,
+        src1: AArch64FloatReg,
+        src2: AArch64FloatReg,
     ) {
-        todo!("adding floats for AArch64");
+        fadd_freg64_freg64_freg64(buf, dst, src1, src2);
     }
 
     #[inline(always)]
-    fn call(_buf: &mut Vec<'_, u8>, _relocs: &mut Vec<'_, Relocation>, _fn_name: String) {
-        todo!("calling functions literal for AArch64");
+    fn call(buf: &mut Vec<'_, u8>, relocs: &mut Vec<'_, Relocation>, fn_name: String) {
+        bl_imm26(buf, 0);
+        relocs.push(Relocation::LinkedFunction {
+            offset: buf.len() as u64 - 4,
+            name: fn_name,
+        });
     }
 
     #[inline(always)]
     fn imul_reg64_reg64_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64GeneralReg,
-        _src1: AArch64GeneralReg,
-        _src2: AArch64GeneralReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
     ) {
-        todo!("register signed multiplication for AArch64");
+        mul_reg64_reg64_reg64(buf, dst, src1, src2);
     }
 
     fn umul_reg64_reg64_reg64<'a, ASM, CC>(
-        _buf: &mut Vec<'a, u8>,
+        buf: &mut Vec<'a, u8>,
         _storage_manager: &mut StorageManager<'a, AArch64GeneralReg, AArch64FloatReg, ASM, CC>,
-        _dst: AArch64GeneralReg,
-        _src1: AArch64GeneralReg,
-        _src2: AArch64GeneralReg,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
     ) where
         ASM: Assembler<AArch64GeneralReg, AArch64FloatReg>,
         CC: CallConv<AArch64GeneralReg, AArch64FloatReg, ASM>,
     {
-        todo!("register unsigned multiplication for AArch64");
+        // The low 64 bits of the product are the same for signed and unsigned multiplication.
+        mul_reg64_reg64_reg64(buf, dst, src1, src2);
     }
 
     #[inline(always)]
     fn mul_freg32_freg32_freg32(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64FloatReg,
-        _src1: AArch64FloatReg,
-        _src2: AArch64FloatReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64FloatReg,
+        src1: AArch64FloatReg,
+        src2: AArch64FloatReg,
     ) {
-        todo!("multiplication for floats for AArch64");
+        fmul_freg32_freg32_freg32(buf, dst, src1, src2);
     }
     #[inline(always)]
     fn mul_freg64_freg64_freg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64FloatReg,
-        _src1: AArch64FloatReg,
-        _src2: AArch64FloatReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64FloatReg,
+        src1: AArch64FloatReg,
+        src2: AArch64FloatReg,
     ) {
-        todo!("multiplication for floats for AArch64");
+        fmul_freg64_freg64_freg64(buf, dst, src1, src2);
     }
 
+    /// Offsets are relative to the start of the branch, so that is what gets returned.
     #[inline(always)]
-    fn jmp_imm32(_buf: &mut Vec<'_, u8>, _offset: i32) -> usize {
-        todo!("jump instructions for AArch64");
+    fn jmp_imm32(buf: &mut Vec<'_, u8>, offset: i32) -> usize {
+        let base_offset = buf.len();
+        b_imm26(buf, offset >> 2);
+        base_offset
     }
 
     #[inline(always)]
     fn tail_call(buf: &mut Vec<'_, u8>) -> u64 {
         Self::jmp_imm32(buf, 0);
-        buf.len() as u64 - 4 // TODO is 4 the correct offset in ARM?
+        // The relocation covers the whole branch instruction.
+        buf.len() as u64 - 4
     }
 
+    /// Offsets are relative to the start of the branch, so that is what gets returned.
     #[inline(always)]
     fn jne_reg64_imm64_imm32(
-        _buf: &mut Vec<'_, u8>,
-        _reg: AArch64GeneralReg,
-        _imm: u64,
-        _offset: i32,
+        buf: &mut Vec<'_, u8>,
+        reg: AArch64GeneralReg,
+        imm: u64,
+        offset: i32,
     ) -> usize {
-        todo!("jump not equal instructions for AArch64");
+        if imm <= 0xFFF {
+            cmp_reg64_imm12(buf, reg, imm as u16);
+        } else {
+            Self::mov_reg64_imm64(buf, AArch64GeneralReg::IP0, imm as i64);
+            cmp_reg64_reg64(buf, reg, AArch64GeneralReg::IP0);
+        }
+        let base_offset = buf.len();
+        b_cond_imm19(buf, ConditionCode::NE, offset >> 2);
+        base_offset
     }
 
     #[inline(always)]
     fn mov_freg32_imm32(
-        _buf: &mut Vec<'_, u8>,
+        buf: &mut Vec<'_, u8>,
         _relocs: &mut Vec<'_, Relocation>,
-        _dst: AArch64FloatReg,
-        _imm: f32,
+        dst: AArch64FloatReg,
+        imm: f32,
     ) {
-        todo!("loading f32 literal for AArch64");
+        // Building the bits in a general register avoids loading them from a data section.
+        Self::mov_reg64_imm64(buf, AArch64GeneralReg::IP0, imm.to_bits() as i64);
+        fmov_freg32_reg32(buf, dst, AArch64GeneralReg::IP0);
     }
     #[inline(always)]
     fn mov_freg64_imm64(
-        _buf: &mut Vec<'_, u8>,
+        buf: &mut Vec<'_, u8>,
         _relocs: &mut Vec<'_, Relocation>,
-        _dst: AArch64FloatReg,
-        _imm: f64,
+        dst: AArch64FloatReg,
+        imm: f64,
     ) {
-        todo!("loading f64 literal for AArch64");
+        // Building the bits in a general register avoids loading them from a data section.
+        Self::mov_reg64_imm64(buf, AArch64GeneralReg::IP0, imm.to_bits() as i64);
+        fmov_freg64_reg64(buf, dst, AArch64GeneralReg::IP0);
     }
     #[inline(always)]
     fn mov_reg64_imm64(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, imm: i64) {
@@ -532,8 +1216,8 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         }
     }
     #[inline(always)]
-    fn mov_freg64_freg64(_buf: &mut Vec<'_, u8>, _dst: AArch64FloatReg, _src: AArch64FloatReg) {
-        todo!("moving data between float registers for AArch64");
+    fn mov_freg64_freg64(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64FloatReg) {
+        fmov_freg64_freg64(buf, dst, src);
     }
     #[inline(always)]
     fn mov_reg64_reg64(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, src: AArch64GeneralReg) {
@@ -541,34 +1225,32 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
     }
 
     #[inline(always)]
-    fn mov_freg64_base32(_buf: &mut Vec<'_, u8>, _dst: AArch64FloatReg, _offset: i32) {
-        todo!("loading floating point reg from base offset for AArch64");
+    fn mov_freg64_base32(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, offset: i32) {
+        Self::load_store_offset(
+            buf,
+            LoadStoreOp::LdrFloat64,
+            dst,
+            AArch64GeneralReg::FP,
+            offset,
+        );
     }
     #[inline(always)]
     fn mov_reg64_base32(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, offset: i32) {
-        if offset < 0 {
-            todo!("negative base offsets for AArch64");
-        } else if offset < (0xFFF << 8) {
-            debug_assert!(offset % 8 == 0);
-            ldr_reg64_reg64_imm12(buf, dst, AArch64GeneralReg::FP, (offset as u16) >> 3);
-        } else {
-            todo!("base offsets over 32k for AArch64");
-        }
+        Self::load_store_offset(buf, LoadStoreOp::Ldr64, dst, AArch64GeneralReg::FP, offset);
     }
     #[inline(always)]
-    fn mov_base32_freg64(_buf: &mut Vec<'_, u8>, _offset: i32, _src: AArch64FloatReg) {
-        todo!("saving floating point reg to base offset for AArch64");
+    fn mov_base32_freg64(buf: &mut Vec<'_, u8>, offset: i32, src: AArch64FloatReg) {
+        Self::load_store_offset(
+            buf,
+            LoadStoreOp::StrFloat64,
+            src,
+            AArch64GeneralReg::FP,
+            offset,
+        );
     }
     #[inline(always)]
     fn mov_base32_reg64(buf: &mut Vec<'_, u8>, offset: i32, src: AArch64GeneralReg) {
-        if offset < 0 {
-            todo!("negative base offsets for AArch64");
-        } else if offset < (0xFFF << 8) {
-            debug_assert!(offset % 8 == 0);
-            str_reg64_reg64_imm12(buf, src, AArch64GeneralReg::FP, (offset as u16) >> 3);
-        } else {
-            todo!("base offsets over 32k for AArch64");
-        }
+        Self::load_store_offset(buf, LoadStoreOp::Str64, src, AArch64GeneralReg::FP, offset);
     }
 
     #[inline(always)]
@@ -578,14 +1260,7 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         src: AArch64GeneralReg,
         offset: i32,
     ) {
-        if offset < 0 {
-            todo!("negative mem offsets for AArch64");
-        } else if offset < (0xFFF << 8) {
-            debug_assert!(offset % 8 == 0);
-            ldr_reg64_reg64_imm12(buf, dst, src, (offset as u16) >> 3);
-        } else {
-            todo!("mem offsets over 32k for AArch64");
-        }
+        Self::load_store_offset(buf, LoadStoreOp::Ldr64, dst, src, offset);
     }
     #[inline(always)]
     fn mov_mem64_offset32_reg64(
@@ -594,80 +1269,78 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         offset: i32,
         src: AArch64GeneralReg,
     ) {
-        if offset < 0 {
-            todo!("negative mem offsets for AArch64");
-        } else if offset < (0xFFF << 8) {
-            debug_assert!(offset % 8 == 0);
-            str_reg64_reg64_imm12(buf, src, dst, (offset as u16) >> 3);
-        } else {
-            todo!("mem offsets over 32k for AArch64");
-        }
+        Self::load_store_offset(buf, LoadStoreOp::Str64, src, dst, offset);
     }
 
     #[inline(always)]
     fn movsx_reg64_base32(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, offset: i32, size: u8) {
         debug_assert!(size <= 8);
-        if size == 8 {
-            Self::mov_reg64_base32(buf, dst, offset);
-        } else if size == 4 {
-            todo!("sign extending 4 byte values");
-        } else if size == 2 {
-            todo!("sign extending 2 byte values");
-        } else if size == 1 {
-            todo!("sign extending 1 byte values");
-        } else {
-            internal_error!("Invalid size for sign extension: {}", size);
-        }
+        let op = match size {
+            8 => LoadStoreOp::Ldr64,
+            4 => LoadStoreOp::LdrSigned32,
+            2 => LoadStoreOp::LdrSigned16,
+            1 => LoadStoreOp::LdrSigned8,
+            _ => internal_error!("Invalid size for sign extension: {}", size),
+        };
+        Self::load_store_offset(buf, op, dst, AArch64GeneralReg::FP, offset);
     }
     #[inline(always)]
     fn movzx_reg64_base32(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, offset: i32, size: u8) {
         debug_assert!(size <= 8);
-        if size == 8 {
-            Self::mov_reg64_base32(buf, dst, offset);
-        } else if size == 4 {
-            todo!("zero extending 4 byte values");
-        } else if size == 2 {
-            todo!("zero extending 2 byte values");
-        } else if size == 1 {
-            todo!("zero extending 1 byte values");
-        } else {
-            internal_error!("Invalid size for zero extension: {}", size);
-        }
+        // Loading into the 32 bit view of a register zeroes the rest of it.
+        let op = match size {
+            8 => LoadStoreOp::Ldr64,
+            4 => LoadStoreOp::Ldr32,
+            2 => LoadStoreOp::Ldr16,
+            1 => LoadStoreOp::Ldr8,
+            _ => internal_error!("Invalid size for zero extension: {}", size),
+        };
+        Self::load_store_offset(buf, op, dst, AArch64GeneralReg::FP, offset);
     }
 
     #[inline(always)]
-    fn mov_freg64_stack32(_buf: &mut Vec<'_, u8>, _dst: AArch64FloatReg, _offset: i32) {
-        todo!("loading floating point reg from stack for AArch64");
+    fn mov_freg64_stack32(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, offset: i32) {
+        Self::load_store_offset(
+            buf,
+            LoadStoreOp::LdrFloat64,
+            dst,
+            AArch64GeneralReg::ZRSP,
+            offset,
+        );
     }
     #[inline(always)]
     fn mov_reg64_stack32(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, offset: i32) {
-        if offset < 0 {
-            todo!("negative stack offsets for AArch64");
-        } else if offset < (0xFFF << 8) {
-            debug_assert!(offset % 8 == 0);
-            ldr_reg64_reg64_imm12(buf, dst, AArch64GeneralReg::ZRSP, (offset as u16) >> 3);
-        } else {
-            todo!("stack offsets over 32k for AArch64");
-        }
+        Self::load_store_offset(
+            buf,
+            LoadStoreOp::Ldr64,
+            dst,
+            AArch64GeneralReg::ZRSP,
+            offset,
+        );
     }
     #[inline(always)]
-    fn mov_stack32_freg64(_buf: &mut Vec<'_, u8>, _offset: i32, _src: AArch64FloatReg) {
-        todo!("saving floating point reg to stack for AArch64");
+    fn mov_stack32_freg64(buf: &mut Vec<'_, u8>, offset: i32, src: AArch64FloatReg) {
+        Self::load_store_offset(
+            buf,
+            LoadStoreOp::StrFloat64,
+            src,
+            AArch64GeneralReg::ZRSP,
+            offset,
+        );
     }
     #[inline(always)]
     fn mov_stack32_reg64(buf: &mut Vec<'_, u8>, offset: i32, src: AArch64GeneralReg) {
-        if offset < 0 {
-            todo!("negative stack offsets for AArch64");
-        } else if offset < (0xFFF << 8) {
-            debug_assert!(offset % 8 == 0);
-            str_reg64_reg64_imm12(buf, src, AArch64GeneralReg::ZRSP, (offset as u16) >> 3);
-        } else {
-            todo!("stack offsets over 32k for AArch64");
-        }
+        Self::load_store_offset(
+            buf,
+            LoadStoreOp::Str64,
+            src,
+            AArch64GeneralReg::ZRSP,
+            offset,
+        );
     }
     #[inline(always)]
-    fn neg_reg64_reg64(_buf: &mut Vec<'_, u8>, _dst: AArch64GeneralReg, _src: AArch64GeneralReg) {
-        todo!("neg for AArch64");
+    fn neg_reg64_reg64(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, src: AArch64GeneralReg) {
+        neg_reg64_reg64(buf, dst, src);
     }
 
     #[inline(always)]
@@ -677,108 +1350,111 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         src: AArch64GeneralReg,
         imm32: i32,
     ) {
-        if imm32 < 0 {
-            todo!("immediate subtractions with values less than 0");
-        } else if imm32 < 0xFFF {
-            sub_reg64_reg64_imm12(buf, dst, src, imm32 as u16);
-        } else {
-            todo!("immediate subtractions with values greater than 12bits");
-        }
+        Self::add_reg64_reg64_imm(buf, dst, src, -(imm32 as i64));
     }
     #[inline(always)]
     fn sub_reg64_reg64_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64GeneralReg,
-        _src1: AArch64GeneralReg,
-        _src2: AArch64GeneralReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
+    ) {
+        sub_reg64_reg64_reg64(buf, dst, src1, src2);
+    }
+
+    #[inline(always)]
+    fn and_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
+    ) {
+        and_reg64_reg64_reg64(buf, dst, src1, src2);
+    }
+
+    #[inline(always)]
+    fn or_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
     ) {
-        todo!("registers subtractions for AArch64");
+        orr_reg64_reg64_reg64(buf, dst, src1, src2);
     }
 
     #[inline(always)]
     fn eq_reg64_reg64_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64GeneralReg,
-        _src1: AArch64GeneralReg,
-        _src2: AArch64GeneralReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
     ) {
-        todo!("registers equality for AArch64");
+        cmp_reg64_reg64(buf, src1, src2);
+        cset_reg64_cond(buf, dst, ConditionCode::EQ);
     }
 
     #[inline(always)]
     fn neq_reg64_reg64_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64GeneralReg,
-        _src1: AArch64GeneralReg,
-        _src2: AArch64GeneralReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
     ) {
-        todo!("registers non-equality for AArch64");
+        cmp_reg64_reg64(buf, src1, src2);
+        cset_reg64_cond(buf, dst, ConditionCode::NE);
     }
 
     #[inline(always)]
     fn lt_reg64_reg64_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64GeneralReg,
-        _src1: AArch64GeneralReg,
-        _src2: AArch64GeneralReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
     ) {
-        todo!("registers less than for AArch64");
+        cmp_reg64_reg64(buf, src1, src2);
+        cset_reg64_cond(buf, dst, ConditionCode::LT);
     }
 
     #[inline(always)]
-    fn to_float_freg64_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64FloatReg,
-        _src: AArch64GeneralReg,
-    ) {
-        todo!("registers to float for AArch64");
+    fn to_float_freg64_reg64(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64GeneralReg) {
+        scvtf_freg64_reg64(buf, dst, src);
     }
 
     #[inline(always)]
-    fn to_float_freg32_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64FloatReg,
-        _src: AArch64GeneralReg,
-    ) {
-        todo!("registers to float for AArch64");
+    fn to_float_freg32_reg64(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64GeneralReg) {
+        scvtf_freg32_reg64(buf, dst, src);
     }
 
     #[inline(always)]
-    fn to_float_freg32_freg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64FloatReg,
-        _src: AArch64FloatReg,
-    ) {
-        todo!("registers to float for AArch64");
+    fn to_float_freg32_freg64(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64FloatReg) {
+        fcvt_freg32_freg64(buf, dst, src);
     }
 
     #[inline(always)]
-    fn to_float_freg64_freg32(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64FloatReg,
-        _src: AArch64FloatReg,
-    ) {
-        todo!("registers to float for AArch64");
+    fn to_float_freg64_freg32(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64FloatReg) {
+        fcvt_freg64_freg32(buf, dst, src);
     }
 
     #[inline(always)]
     fn lte_reg64_reg64_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64GeneralReg,
-        _src1: AArch64GeneralReg,
-        _src2: AArch64GeneralReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
     ) {
-        todo!("registers less than or equal for AArch64");
+        cmp_reg64_reg64(buf, src1, src2);
+        cset_reg64_cond(buf, dst, ConditionCode::LE);
     }
 
     #[inline(always)]
     fn gte_reg64_reg64_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64GeneralReg,
-        _src1: AArch64GeneralReg,
-        _src2: AArch64GeneralReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
     ) {
-        todo!("registers greater than or equal for AArch64");
+        cmp_reg64_reg64(buf, src1, src2);
+        cset_reg64_cond(buf, dst, ConditionCode::GE);
     }
 
     #[inline(always)]
@@ -787,17 +1463,136 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
     }
 }
 
-impl AArch64Assembler {}
-
-// Instructions
-// ARM manual section C3
-// https://developer.arm.com/documentation/ddi0487/ga
-// Map all instructions to a packed struct.
-
-trait Aarch64Bytes: PackedStruct {
+impl AArch64Assembler {
+    /// `dst = src + imm`, where either register may be the stack pointer.
+    /// Immediates that fit in 24 bits are split into a shifted and an unshifted 12 bit immediate.
+    /// Larger ones are built in IP0 first, which doesn't work with the stack pointer.
     #[inline(always)]
-    fn bytes(&self) -> [u8; 4] {
-        let mut bytes: [u8; 4] = [0, 0, 0, 0];
+    fn add_reg64_reg64_imm(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src: AArch64GeneralReg,
+        imm: i64,
+    ) {
+        let negative = imm < 0;
+        let magnitude = imm.unsigned_abs();
+        if magnitude <= 0xFF_FFFF {
+            let high = (magnitude >> 12) as u16;
+            let low = (magnitude & 0xFFF) as u16;
+            let mut src = src;
+            if high != 0 {
+                if negative {
+                    sub_reg64_reg64_imm12_lsl12(buf, dst, src, high);
+                } else {
+                    add_reg64_reg64_imm12_lsl12(buf, dst, src, high);
+                }
+                src = dst;
+            }
+            if low != 0 || high == 0 {
+                if negative {
+                    sub_reg64_reg64_imm12(buf, dst, src, low);
+                } else {
+                    add_reg64_reg64_imm12(buf, dst, src, low);
+                }
+            }
+        } else {
+            debug_assert!(
+                dst != AArch64GeneralReg::ZRSP && src != AArch64GeneralReg::ZRSP,
+                "immediates over 24 bits can't be added to the stack pointer"
+            );
+            Self::mov_reg64_imm64(buf, AArch64GeneralReg::IP0, imm);
+            add_reg64_reg64_reg64(buf, dst, src, AArch64GeneralReg::IP0);
+        }
+    }
+
+    /// Loads or stores `rt` at `base + offset`, picking whichever encoding fits the offset.
+    /// Offsets that fit neither are added to the base in IP0 first.
+    #[inline(always)]
+    fn load_store_offset<R: RegTrait>(
+        buf: &mut Vec<'_, u8>,
+        op: LoadStoreOp,
+        rt: R,
+        base: AArch64GeneralReg,
+        offset: i32,
+    ) {
+        let size = op.size_bytes();
+        if offset >= 0 && offset % size == 0 && offset / size <= 0xFFF {
+            load_store_imm12(buf, op, rt, base, (offset / size) as u16);
+        } else if (-256..256).contains(&offset) {
+            load_store_imm9(buf, op, rt, base, offset as i16);
+        } else {
+            Self::add_reg64_reg64_imm(buf, AArch64GeneralReg::IP0, base, offset as i64);
+            load_store_imm12(buf, op, rt, AArch64GeneralReg::IP0, 0);
+        }
+    }
+
+    /// Loads a float of the given width from a base offset.
+    /// Unlike single float symbols, f32s in structs only take up 4 bytes.
+    #[inline(always)]
+    fn mov_freg_base32(
+        buf: &mut Vec<'_, u8>,
+        width: FloatWidth,
+        dst: AArch64FloatReg,
+        offset: i32,
+    ) {
+        let op = match width {
+            FloatWidth::F32 => LoadStoreOp::LdrFloat32,
+            FloatWidth::F64 => LoadStoreOp::LdrFloat64,
+            FloatWidth::F128 => internal_error!("F128 is not supported"),
+        };
+        Self::load_store_offset(buf, op, dst, AArch64GeneralReg::FP, offset);
+    }
+
+    /// Stores a float of the given width to a base offset.
+    /// Unlike single float symbols, f32s in structs only take up 4 bytes.
+    #[inline(always)]
+    fn mov_base32_freg(
+        buf: &mut Vec<'_, u8>,
+        width: FloatWidth,
+        offset: i32,
+        src: AArch64FloatReg,
+    ) {
+        let op = match width {
+            FloatWidth::F32 => LoadStoreOp::StrFloat32,
+            FloatWidth::F64 => LoadStoreOp::StrFloat64,
+            FloatWidth::F128 => internal_error!("F128 is not supported"),
+        };
+        Self::load_store_offset(buf, op, src, AArch64GeneralReg::FP, offset);
+    }
+
+    /// Stores the low `size` bytes of a general register to the outgoing arguments.
+    #[inline(always)]
+    fn mov_stack32_reg(buf: &mut Vec<'_, u8>, size: u32, offset: i32, src: AArch64GeneralReg) {
+        let op = LoadStoreOp::general_store(size);
+        Self::load_store_offset(buf, op, src, AArch64GeneralReg::ZRSP, offset);
+    }
+
+    /// Stores a float of the given width to the outgoing arguments.
+    #[inline(always)]
+    fn mov_stack32_freg(
+        buf: &mut Vec<'_, u8>,
+        width: FloatWidth,
+        offset: i32,
+        src: AArch64FloatReg,
+    ) {
+        let op = match width {
+            FloatWidth::F32 => LoadStoreOp::StrFloat32,
+            FloatWidth::F64 => LoadStoreOp::StrFloat64,
+            FloatWidth::F128 => internal_error!("F128 is not supported"),
+        };
+        Self::load_store_offset(buf, op, src, AArch64GeneralReg::ZRSP, offset);
+    }
+}
+
+// Instructions
+// ARM manual section C3
+// https://developer.arm.com/documentation/ddi0487/ga
+// Map all instructions to a packed struct.
+
+trait Aarch64Bytes: PackedStruct {
+    #[inline(always)]
+    fn bytes(&self) -> [u8; 4] {
+        let mut bytes: [u8; 4] = [0, 0, 0, 0];
 
         self.pack_to_slice(&mut bytes).unwrap();
 
@@ -1049,15 +1844,411 @@ impl UnconditionalBranchRegister {
     }
 }
 
+#[derive(PackedStruct, Debug)]
+#[packed_struct(endian = "msb")]
+pub struct UnconditionalBranchImmediate {
+    op: bool,                                 // branch or branch with link
+    fixed: Integer<u8, packed_bits::Bits<5>>, // = 0b00101,
+    imm26: Integer<u32, packed_bits::Bits<26>>,
+}
+
+impl Aarch64Bytes for UnconditionalBranchImmediate {}
+
+impl UnconditionalBranchImmediate {
+    #[inline(always)]
+    fn new(op: bool, imm26: i32) -> Self {
+        Self {
+            // Only the low 26 bits are kept, so placeholder offsets don't need to fit.
+            imm26: ((imm26 as u32) & 0x3FF_FFFF).into(),
+            fixed: 0b00101.into(),
+            op,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[allow(dead_code)]
+enum ConditionCode {
+    EQ = 0,
+    NE = 1,
+    HS = 2,
+    LO = 3,
+    MI = 4,
+    PL = 5,
+    VS = 6,
+    VC = 7,
+    HI = 8,
+    LS = 9,
+    GE = 10,
+    LT = 11,
+    GT = 12,
+    LE = 13,
+    AL = 14,
+}
+
+impl ConditionCode {
+    #[inline(always)]
+    fn id(&self) -> u8 {
+        *self as u8
+    }
+
+    /// The condition that holds exactly when this one doesn't.
+    #[inline(always)]
+    fn invert(&self) -> Self {
+        match self {
+            ConditionCode::EQ => ConditionCode::NE,
+            ConditionCode::NE => ConditionCode::EQ,
+            ConditionCode::HS => ConditionCode::LO,
+            ConditionCode::LO => ConditionCode::HS,
+            ConditionCode::MI => ConditionCode::PL,
+            ConditionCode::PL => ConditionCode::MI,
+            ConditionCode::VS => ConditionCode::VC,
+            ConditionCode::VC => ConditionCode::VS,
+            ConditionCode::HI => ConditionCode::LS,
+            ConditionCode::LS => ConditionCode::HI,
+            ConditionCode::GE => ConditionCode::LT,
+            ConditionCode::LT => ConditionCode::GE,
+            ConditionCode::GT => ConditionCode::LE,
+            ConditionCode::LE => ConditionCode::GT,
+            ConditionCode::AL => internal_error!("AL has no inverse"),
+        }
+    }
+}
+
+#[derive(PackedStruct, Debug)]
+#[packed_struct(endian = "msb")]
+pub struct ConditionalBranchImmediate {
+    fixed: Integer<u8, packed_bits::Bits<7>>, // = 0b0101010,
+    o1: bool,
+    imm19: Integer<u32, packed_bits::Bits<19>>,
+    o0: bool,
+    cond: Integer<u8, packed_bits::Bits<4>>,
+}
+
+impl Aarch64Bytes for ConditionalBranchImmediate {}
+
+impl ConditionalBranchImmediate {
+    #[inline(always)]
+    fn new(cond: ConditionCode, imm19: i32) -> Self {
+        Self {
+            cond: cond.id().into(),
+            o0: false,
+            // Only the low 19 bits are kept, so placeholder offsets don't need to fit.
+            imm19: ((imm19 as u32) & 0x7_FFFF).into(),
+            o1: false,
+            fixed: 0b0101010.into(),
+        }
+    }
+}
+
+#[derive(PackedStruct, Debug)]
+#[packed_struct(endian = "msb")]
+pub struct ConditionalSelect {
+    sf: bool,
+    op: bool,
+    s: bool,
+    fixed: Integer<u8, packed_bits::Bits<8>>, // = 0b11010100,
+    reg_m: Integer<u8, packed_bits::Bits<5>>,
+    cond: Integer<u8, packed_bits::Bits<4>>,
+    op2: Integer<u8, packed_bits::Bits<2>>,
+    reg_n: Integer<u8, packed_bits::Bits<5>>,
+    reg_d: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for ConditionalSelect {}
+
+impl ConditionalSelect {
+    #[inline(always)]
+    fn new(
+        op: bool,
+        op2: u8,
+        cond: ConditionCode,
+        rm: AArch64GeneralReg,
+        rn: AArch64GeneralReg,
+        rd: AArch64GeneralReg,
+    ) -> Self {
+        debug_assert!(op2 <= 0b11);
+
+        Self {
+            reg_d: rd.id().into(),
+            reg_n: rn.id().into(),
+            op2: op2.into(),
+            cond: cond.id().into(),
+            reg_m: rm.id().into(),
+            fixed: 0b11010100.into(),
+            s: false,
+            op,
+            sf: true,
+        }
+    }
+}
+
+#[derive(PackedStruct, Debug)]
+#[packed_struct(endian = "msb")]
+pub struct DataProcessingThreeSource {
+    sf: bool,
+    op54: Integer<u8, packed_bits::Bits<2>>,
+    fixed: Integer<u8, packed_bits::Bits<5>>, // = 0b11011,
+    op31: Integer<u8, packed_bits::Bits<3>>,
+    reg_m: Integer<u8, packed_bits::Bits<5>>,
+    o0: bool,
+    reg_a: Integer<u8, packed_bits::Bits<5>>,
+    reg_n: Integer<u8, packed_bits::Bits<5>>,
+    reg_d: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for DataProcessingThreeSource {}
+
+impl DataProcessingThreeSource {
+    #[inline(always)]
+    fn new(
+        op31: u8,
+        o0: bool,
+        rm: AArch64GeneralReg,
+        ra: AArch64GeneralReg,
+        rn: AArch64GeneralReg,
+        rd: AArch64GeneralReg,
+    ) -> Self {
+        debug_assert!(op31 <= 0b111);
+
+        Self {
+            reg_d: rd.id().into(),
+            reg_n: rn.id().into(),
+            reg_a: ra.id().into(),
+            o0,
+            reg_m: rm.id().into(),
+            op31: op31.into(),
+            fixed: 0b11011.into(),
+            op54: 0b00.into(),
+            sf: true,
+        }
+    }
+}
+
+/// The `ftype` field of float instructions.
+#[inline(always)]
+fn float_type(width: FloatWidth) -> u8 {
+    match width {
+        FloatWidth::F32 => 0b00,
+        FloatWidth::F64 => 0b01,
+        FloatWidth::F128 => internal_error!("F128 is not supported"),
+    }
+}
+
+#[derive(PackedStruct, Debug)]
+#[packed_struct(endian = "msb")]
+pub struct FloatingPointTwoSource {
+    m: bool,
+    fixed: bool,
+    s: bool,
+    fixed2: Integer<u8, packed_bits::Bits<5>>, // = 0b11110,
+    ftype: Integer<u8, packed_bits::Bits<2>>,
+    fixed3: bool, // = 0b1,
+    reg_m: Integer<u8, packed_bits::Bits<5>>,
+    opcode: Integer<u8, packed_bits::Bits<4>>,
+    fixed4: Integer<u8, packed_bits::Bits<2>>, // = 0b10,
+    reg_n: Integer<u8, packed_bits::Bits<5>>,
+    reg_d: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for FloatingPointTwoSource {}
+
+impl FloatingPointTwoSource {
+    #[inline(always)]
+    fn new(
+        opcode: u8,
+        width: FloatWidth,
+        rm: AArch64FloatReg,
+        rn: AArch64FloatReg,
+        rd: AArch64FloatReg,
+    ) -> Self {
+        debug_assert!(opcode <= 0b1111);
+
+        Self {
+            reg_d: rd.id().into(),
+            reg_n: rn.id().into(),
+            fixed4: 0b10.into(),
+            opcode: opcode.into(),
+            reg_m: rm.id().into(),
+            fixed3: true,
+            ftype: float_type(width).into(),
+            fixed2: 0b11110.into(),
+            s: false,
+            fixed: false,
+            m: false,
+        }
+    }
+}
+
+#[derive(PackedStruct, Debug)]
+#[packed_struct(endian = "msb")]
+pub struct FloatingPointOneSource {
+    m: bool,
+    fixed: bool,
+    s: bool,
+    fixed2: Integer<u8, packed_bits::Bits<5>>, // = 0b11110,
+    ftype: Integer<u8, packed_bits::Bits<2>>,
+    fixed3: bool, // = 0b1,
+    opcode: Integer<u8, packed_bits::Bits<6>>,
+    fixed4: Integer<u8, packed_bits::Bits<5>>, // = 0b10000,
+    reg_n: Integer<u8, packed_bits::Bits<5>>,
+    reg_d: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for FloatingPointOneSource {}
+
+impl FloatingPointOneSource {
+    /// `width` is the width of the source.
+    #[inline(always)]
+    fn new(opcode: u8, width: FloatWidth, rn: AArch64FloatReg, rd: AArch64FloatReg) -> Self {
+        debug_assert!(opcode <= 0b111111);
+
+        Self {
+            reg_d: rd.id().into(),
+            reg_n: rn.id().into(),
+            fixed4: 0b10000.into(),
+            opcode: opcode.into(),
+            fixed3: true,
+            ftype: float_type(width).into(),
+            fixed2: 0b11110.into(),
+            s: false,
+            fixed: false,
+            m: false,
+        }
+    }
+}
+
+#[derive(PackedStruct, Debug)]
+#[packed_struct(endian = "msb")]
+pub struct FloatingPointIntegerConversion {
+    sf: bool,
+    fixed: bool,
+    s: bool,
+    fixed2: Integer<u8, packed_bits::Bits<5>>, // = 0b11110,
+    ftype: Integer<u8, packed_bits::Bits<2>>,
+    fixed3: bool, // = 0b1,
+    rmode: Integer<u8, packed_bits::Bits<2>>,
+    opcode: Integer<u8, packed_bits::Bits<3>>,
+    fixed4: Integer<u8, packed_bits::Bits<6>>, // = 0b000000,
+    reg_n: Integer<u8, packed_bits::Bits<5>>,
+    reg_d: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for FloatingPointIntegerConversion {}
+
+impl FloatingPointIntegerConversion {
+    /// The registers are ids, since one is general and the other is a float depending on the opcode.
+    #[inline(always)]
+    fn new(sf: bool, opcode: u8, width: FloatWidth, rn: u8, rd: u8) -> Self {
+        debug_assert!(opcode <= 0b111);
+
+        Self {
+            reg_d: rd.into(),
+            reg_n: rn.into(),
+            fixed4: 0b000000.into(),
+            opcode: opcode.into(),
+            rmode: 0b00.into(),
+            fixed3: true,
+            ftype: float_type(width).into(),
+            fixed2: 0b11110.into(),
+            s: false,
+            fixed: false,
+            sf,
+        }
+    }
+}
+
+/// The loads and stores we use.
+/// Each one is encoded by the size, V, and opc fields that all addressing modes share.
+#[derive(Clone, Copy, Debug)]
+enum LoadStoreOp {
+    Ldr64,
+    Str64,
+    LdrFloat64,
+    StrFloat64,
+    LdrFloat32,
+    StrFloat32,
+    /// Loads 4 bytes and sign extends them.
+    LdrSigned32,
+    /// Loads 2 bytes and sign extends them.
+    LdrSigned16,
+    /// Loads 1 byte and sign extends it.
+    LdrSigned8,
+    /// Loads 4 bytes and zero extends them.
+    Ldr32,
+    /// Loads 2 bytes and zero extends them.
+    Ldr16,
+    /// Loads 1 byte and zero extends it.
+    Ldr8,
+    /// Stores the low 4 bytes.
+    Str32,
+    /// Stores the low 2 bytes.
+    Str16,
+    /// Stores the low byte.
+    Str8,
+}
+
+impl LoadStoreOp {
+    /// Returns the size, V and opc fields.
+    #[inline(always)]
+    fn fields(&self) -> (u8, bool, u8) {
+        match self {
+            LoadStoreOp::Ldr64 => (0b11, false, 0b01),
+            LoadStoreOp::Str64 => (0b11, false, 0b00),
+            LoadStoreOp::LdrFloat64 => (0b11, true, 0b01),
+            LoadStoreOp::StrFloat64 => (0b11, true, 0b00),
+            LoadStoreOp::LdrFloat32 => (0b10, true, 0b01),
+            LoadStoreOp::StrFloat32 => (0b10, true, 0b00),
+            LoadStoreOp::LdrSigned32 => (0b10, false, 0b10),
+            LoadStoreOp::LdrSigned16 => (0b01, false, 0b10),
+            LoadStoreOp::LdrSigned8 => (0b00, false, 0b10),
+            LoadStoreOp::Ldr32 => (0b10, false, 0b01),
+            LoadStoreOp::Ldr16 => (0b01, false, 0b01),
+            LoadStoreOp::Ldr8 => (0b00, false, 0b01),
+            LoadStoreOp::Str32 => (0b10, false, 0b00),
+            LoadStoreOp::Str16 => (0b01, false, 0b00),
+            LoadStoreOp::Str8 => (0b00, false, 0b00),
+        }
+    }
+
+    /// Loads `size` bytes into a general register, zero extending them.
+    #[inline(always)]
+    fn general_load(size: u32) -> Self {
+        match size {
+            8 => LoadStoreOp::Ldr64,
+            4 => LoadStoreOp::Ldr32,
+            2 => LoadStoreOp::Ldr16,
+            1 => LoadStoreOp::Ldr8,
+            _ => internal_error!("Invalid size for a general load: {}", size),
+        }
+    }
+
+    /// Stores the low `size` bytes of a general register.
+    #[inline(always)]
+    fn general_store(size: u32) -> Self {
+        match size {
+            8 => LoadStoreOp::Str64,
+            4 => LoadStoreOp::Str32,
+            2 => LoadStoreOp::Str16,
+            1 => LoadStoreOp::Str8,
+            _ => internal_error!("Invalid size for a general store: {}", size),
+        }
+    }
+
+    /// The number of bytes accessed. Scaled offsets are multiples of this.
+    #[inline(always)]
+    fn size_bytes(&self) -> i32 {
+        1 << self.fields().0
+    }
+}
+
 // Uses unsigned Offset
-// opc = 0b01 means load
-// opc = 0b00 means store
 #[derive(PackedStruct, Debug)]
 #[packed_struct(endian = "msb")]
 pub struct LoadStoreRegisterImmediate {
     size: Integer<u8, packed_bits::Bits<2>>,
     fixed: Integer<u8, packed_bits::Bits<3>>, // = 0b111,
-    fixed2: bool,
+    v: bool,                                  // float register
     fixed3: Integer<u8, packed_bits::Bits<2>>,
     opc: Integer<u8, packed_bits::Bits<2>>,
     imm12: Integer<u16, packed_bits::Bits<12>>,
@@ -1068,31 +2259,106 @@ pub struct LoadStoreRegisterImmediate {
 impl Aarch64Bytes for LoadStoreRegisterImmediate {}
 
 impl LoadStoreRegisterImmediate {
+    /// `rt` is a general or float register id, depending on the op.
     #[inline(always)]
-    fn new(size: u8, opc: u8, imm12: u16, rn: AArch64GeneralReg, rt: AArch64GeneralReg) -> Self {
-        debug_assert!(size <= 0b11);
+    fn new(op: LoadStoreOp, imm12: u16, rn: AArch64GeneralReg, rt: u8) -> Self {
         debug_assert!(imm12 <= 0xFFF);
+        let (size, v, opc) = op.fields();
 
         Self {
-            rt: rt.id().into(),
+            rt: rt.into(),
             rn: rn.id().into(),
             imm12: imm12.into(),
             opc: opc.into(),
             fixed3: 0b01.into(),
-            fixed2: false,
+            v,
             fixed: 0b111.into(),
             size: size.into(),
         }
     }
+}
+
+// Uses a signed, unscaled offset
+#[derive(PackedStruct, Debug)]
+#[packed_struct(endian = "msb")]
+pub struct LoadStoreRegisterUnscaled {
+    size: Integer<u8, packed_bits::Bits<2>>,
+    fixed: Integer<u8, packed_bits::Bits<3>>,  // = 0b111,
+    v: bool,                                   // float register
+    fixed2: Integer<u8, packed_bits::Bits<2>>, // = 0b00,
+    opc: Integer<u8, packed_bits::Bits<2>>,
+    fixed3: bool, // = 0b0,
+    imm9: Integer<u16, packed_bits::Bits<9>>,
+    fixed4: Integer<u8, packed_bits::Bits<2>>, // = 0b00,
+    rn: Integer<u8, packed_bits::Bits<5>>,
+    rt: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for LoadStoreRegisterUnscaled {}
 
+impl LoadStoreRegisterUnscaled {
+    /// `rt` is a general or float register id, depending on the op.
     #[inline(always)]
-    fn new_load(size: u8, imm12: u16, rn: AArch64GeneralReg, rt: AArch64GeneralReg) -> Self {
-        Self::new(size, 0b01, imm12, rn, rt)
+    fn new(op: LoadStoreOp, imm9: i16, rn: AArch64GeneralReg, rt: u8) -> Self {
+        debug_assert!((-256..256).contains(&imm9));
+        let (size, v, opc) = op.fields();
+
+        Self {
+            rt: rt.into(),
+            rn: rn.id().into(),
+            fixed4: 0b00.into(),
+            imm9: ((imm9 as u16) & 0x1FF).into(),
+            fixed3: false,
+            opc: opc.into(),
+            fixed2: 0b00.into(),
+            v,
+            fixed: 0b111.into(),
+            size: size.into(),
+        }
     }
+}
+
+// 64 bit general register pairs
+#[derive(PackedStruct, Debug)]
+#[packed_struct(endian = "msb")]
+pub struct LoadStorePair {
+    opc: Integer<u8, packed_bits::Bits<2>>,
+    fixed: Integer<u8, packed_bits::Bits<3>>, // = 0b101,
+    v: bool,
+    index: Integer<u8, packed_bits::Bits<3>>, // post-index, signed offset or pre-index
+    l: bool,                                  // load or store
+    imm7: Integer<u8, packed_bits::Bits<7>>,
+    rt2: Integer<u8, packed_bits::Bits<5>>,
+    rn: Integer<u8, packed_bits::Bits<5>>,
+    rt: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for LoadStorePair {}
 
+impl LoadStorePair {
     #[inline(always)]
-    fn new_store(size: u8, imm12: u16, rn: AArch64GeneralReg, rt: AArch64GeneralReg) -> Self {
-        Self::new(size, 0b00, imm12, rn, rt)
+    fn new(
+        index: u8,
+        l: bool,
+        imm7: i8,
+        rt2: AArch64GeneralReg,
+        rn: AArch64GeneralReg,
+        rt: AArch64GeneralReg,
+    ) -> Self {
+        debug_assert!(index <= 0b111);
+        debug_assert!((-64..64).contains(&imm7));
+
+        Self {
+            rt: rt.id().into(),
+            rn: rn.id().into(),
+            rt2: rt2.id().into(),
+            imm7: ((imm7 as u8) & 0x7F).into(),
+            l,
+            index: index.into(),
+            v: false,
+            fixed: 0b101.into(),
+            opc: 0b10.into(),
+        }
     }
 }
 
@@ -1115,29 +2381,271 @@ fn add_reg64_reg64_imm12(
     buf.extend(inst.bytes());
 }
 
-/// `ADD Xd, Xm, Xn` -> Add Xm and Xn and place the result into Xd.
+/// `ADD Xd, Xn, imm12, LSL #12` -> Add Xn and imm12 shifted left by 12 and place the result into Xd.
 #[inline(always)]
-fn add_reg64_reg64_reg64(
+fn add_reg64_reg64_imm12_lsl12(
     buf: &mut Vec<'_, u8>,
     dst: AArch64GeneralReg,
-    src1: AArch64GeneralReg,
-    src2: AArch64GeneralReg,
+    src: AArch64GeneralReg,
+    imm12: u16,
+) {
+    let inst = ArithmeticImmediate::new(false, false, dst, src, imm12, true);
+
+    buf.extend(inst.bytes());
+}
+
+/// `ADD Xd, Xm, Xn` -> Add Xm and Xn and place the result into Xd.
+#[inline(always)]
+fn add_reg64_reg64_reg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
 ) {
     let inst = ArithmeticShifted::new(false, false, ShiftType::LSL, 0, src2, src1, dst);
 
     buf.extend(inst.bytes());
 }
 
-/// `LDR Xt, [Xn, #offset]` -> Load Xn + Offset Xt. ZRSP is SP.
-/// Note: imm12 is the offest divided by 8.
+/// `AND Xd, Xn, Xm` -> Bitwise and Xn and Xm and place the result into Xd.
+#[inline(always)]
+fn and_reg64_reg64_reg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    let inst = LogicalShiftedRegister::new(LogicalOp::AND, ShiftType::LSL, 0, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `B.cond imm19` -> Jump by the offset if the condition holds.
+/// Note: imm19 is the offset divided by 4.
+#[inline(always)]
+fn b_cond_imm19(buf: &mut Vec<'_, u8>, cond: ConditionCode, imm19: i32) {
+    let inst = ConditionalBranchImmediate::new(cond, imm19);
+
+    buf.extend(inst.bytes());
+}
+
+/// `B imm26` -> Jump by the offset.
+/// Note: imm26 is the offset divided by 4.
+#[inline(always)]
+fn b_imm26(buf: &mut Vec<'_, u8>, imm26: i32) {
+    let inst = UnconditionalBranchImmediate::new(false, imm26);
+
+    buf.extend(inst.bytes());
+}
+
+/// `BL imm26` -> Call the offset, placing the return address into LR.
+/// Note: imm26 is the offset divided by 4.
 #[inline(always)]
-fn ldr_reg64_reg64_imm12(
+fn bl_imm26(buf: &mut Vec<'_, u8>, imm26: i32) {
+    let inst = UnconditionalBranchImmediate::new(true, imm26);
+
+    buf.extend(inst.bytes());
+}
+
+/// `CMP Xn, imm12` -> Compare Xn and imm12, setting the flags. ZRSP is SP.
+#[inline(always)]
+fn cmp_reg64_imm12(buf: &mut Vec<'_, u8>, src: AArch64GeneralReg, imm12: u16) {
+    // CMP is equivalent to `SUBS XZR, Xn, imm12`.
+    let inst = ArithmeticImmediate::new(true, true, AArch64GeneralReg::ZRSP, src, imm12, false);
+
+    buf.extend(inst.bytes());
+}
+
+/// `CMP Xn, Xm` -> Compare Xn and Xm, setting the flags.
+#[inline(always)]
+fn cmp_reg64_reg64(buf: &mut Vec<'_, u8>, src1: AArch64GeneralReg, src2: AArch64GeneralReg) {
+    // CMP is equivalent to `SUBS XZR, Xn, Xm`.
+    let inst = ArithmeticShifted::new(
+        true,
+        true,
+        ShiftType::LSL,
+        0,
+        src2,
+        src1,
+        AArch64GeneralReg::ZRSP,
+    );
+
+    buf.extend(inst.bytes());
+}
+
+/// `CNEG Xd, Xn, cond` -> Place the negation of Xn into Xd if the condition holds, otherwise Xn.
+#[inline(always)]
+fn cneg_reg64_reg64_cond(
     buf: &mut Vec<'_, u8>,
     dst: AArch64GeneralReg,
+    src: AArch64GeneralReg,
+    cond: ConditionCode,
+) {
+    // CNEG is equivalent to `CSNEG Xd, Xn, Xn, invert(cond)`.
+    let inst = ConditionalSelect::new(true, 0b01, cond.invert(), src, src, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `CSET Xd, cond` -> Place 1 into Xd if the condition holds, otherwise 0.
+#[inline(always)]
+fn cset_reg64_cond(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, cond: ConditionCode) {
+    // CSET is equivalent to `CSINC Xd, XZR, XZR, invert(cond)`.
+    let inst = ConditionalSelect::new(
+        false,
+        0b01,
+        cond.invert(),
+        AArch64GeneralReg::ZRSP,
+        AArch64GeneralReg::ZRSP,
+        dst,
+    );
+
+    buf.extend(inst.bytes());
+}
+
+/// `FABS Dd, Dn` -> Place the absolute value of Dn into Dd.
+#[inline(always)]
+fn fabs_freg64_freg64(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64FloatReg) {
+    let inst = FloatingPointOneSource::new(0b000001, FloatWidth::F64, src, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `FADD Sd, Sn, Sm` -> Add Sn and Sm and place the result into Sd.
+#[inline(always)]
+fn fadd_freg32_freg32_freg32(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64FloatReg,
+    src1: AArch64FloatReg,
+    src2: AArch64FloatReg,
+) {
+    let inst = FloatingPointTwoSource::new(0b0010, FloatWidth::F32, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `FADD Dd, Dn, Dm` -> Add Dn and Dm and place the result into Dd.
+#[inline(always)]
+fn fadd_freg64_freg64_freg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64FloatReg,
+    src1: AArch64FloatReg,
+    src2: AArch64FloatReg,
+) {
+    let inst = FloatingPointTwoSource::new(0b0010, FloatWidth::F64, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `FCVT Sd, Dn` -> Convert the double in Dn to a single and place it into Sd.
+#[inline(always)]
+fn fcvt_freg32_freg64(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64FloatReg) {
+    let inst = FloatingPointOneSource::new(0b000100, FloatWidth::F64, src, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `FCVT Dd, Sn` -> Convert the single in Sn to a double and place it into Dd.
+#[inline(always)]
+fn fcvt_freg64_freg32(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64FloatReg) {
+    let inst = FloatingPointOneSource::new(0b000101, FloatWidth::F32, src, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `FMOV Sd, Wn` -> Move the bits of Wn to Sd.
+#[inline(always)]
+fn fmov_freg32_reg32(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64GeneralReg) {
+    let inst =
+        FloatingPointIntegerConversion::new(false, 0b111, FloatWidth::F32, src.id(), dst.id());
+
+    buf.extend(inst.bytes());
+}
+
+/// `FMOV Dd, Dn` -> Move Dn to Dd.
+#[inline(always)]
+fn fmov_freg64_freg64(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64FloatReg) {
+    let inst = FloatingPointOneSource::new(0b000000, FloatWidth::F64, src, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `FMOV Dd, Xn` -> Move the bits of Xn to Dd.
+#[inline(always)]
+fn fmov_freg64_reg64(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64GeneralReg) {
+    let inst =
+        FloatingPointIntegerConversion::new(true, 0b111, FloatWidth::F64, src.id(), dst.id());
+
+    buf.extend(inst.bytes());
+}
+
+/// `FMUL Sd, Sn, Sm` -> Multiply Sn and Sm and place the result into Sd.
+#[inline(always)]
+fn fmul_freg32_freg32_freg32(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64FloatReg,
+    src1: AArch64FloatReg,
+    src2: AArch64FloatReg,
+) {
+    let inst = FloatingPointTwoSource::new(0b0000, FloatWidth::F32, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `FMUL Dd, Dn, Dm` -> Multiply Dn and Dm and place the result into Dd.
+#[inline(always)]
+fn fmul_freg64_freg64_freg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64FloatReg,
+    src1: AArch64FloatReg,
+    src2: AArch64FloatReg,
+) {
+    let inst = FloatingPointTwoSource::new(0b0000, FloatWidth::F64, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `LDP Xt1, Xt2, [Xn], #offset` -> Load Xt1 and Xt2 from Xn, then add the offset to Xn. ZRSP is SP.
+/// Note: imm7 is the offset divided by 8.
+#[inline(always)]
+fn ldp_post_reg64_reg64_reg64_imm7(
+    buf: &mut Vec<'_, u8>,
+    dst1: AArch64GeneralReg,
+    dst2: AArch64GeneralReg,
+    base: AArch64GeneralReg,
+    imm7: i8,
+) {
+    let inst = LoadStorePair::new(0b001, true, imm7, dst2, base, dst1);
+
+    buf.extend(inst.bytes());
+}
+
+/// `LDR/STR Rt, [Xn, #offset]` -> Load or store Rt at Xn + offset. ZRSP is SP.
+/// Note: imm12 is the offset divided by the size of the access.
+#[inline(always)]
+fn load_store_imm12<R: RegTrait>(
+    buf: &mut Vec<'_, u8>,
+    op: LoadStoreOp,
+    rt: R,
     base: AArch64GeneralReg,
     imm12: u16,
 ) {
-    let inst = LoadStoreRegisterImmediate::new_load(0b11, imm12, base, dst);
+    let inst = LoadStoreRegisterImmediate::new(op, imm12, base, rt.value());
+
+    buf.extend(inst.bytes());
+}
+
+/// `LDUR/STUR Rt, [Xn, #offset]` -> Load or store Rt at Xn + offset. ZRSP is SP.
+/// Note: imm9 is not scaled, it must be between -256 and 255.
+#[inline(always)]
+fn load_store_imm9<R: RegTrait>(
+    buf: &mut Vec<'_, u8>,
+    op: LoadStoreOp,
+    rt: R,
+    base: AArch64GeneralReg,
+    imm9: i16,
+) {
+    let inst = LoadStoreRegisterUnscaled::new(op, imm9, base, rt.value());
 
     buf.extend(inst.bytes());
 }
@@ -1174,16 +2682,80 @@ fn movz_reg64_imm16(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, imm16: u16, h
     buf.extend(inst.bytes());
 }
 
-/// `STR Xt, [Xn, #offset]` -> Store Xt to Xn + Offset. ZRSP is SP.
-/// Note: imm12 is the offest divided by 8.
+/// `MUL Xd, Xn, Xm` -> Multiply Xn and Xm and place the low 64 bits of the result into Xd.
 #[inline(always)]
-fn str_reg64_reg64_imm12(
+fn mul_reg64_reg64_reg64(
     buf: &mut Vec<'_, u8>,
-    src: AArch64GeneralReg,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    // MUL is equivalent to `MADD Xd, Xn, Xm, XZR`.
+    let inst =
+        DataProcessingThreeSource::new(0b000, false, src2, AArch64GeneralReg::ZRSP, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `NEG Xd, Xm` -> Place the negation of Xm into Xd.
+#[inline(always)]
+fn neg_reg64_reg64(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, src: AArch64GeneralReg) {
+    // NEG is equivalent to `SUB Xd, XZR, Xm`.
+    let inst = ArithmeticShifted::new(
+        true,
+        false,
+        ShiftType::LSL,
+        0,
+        src,
+        AArch64GeneralReg::ZRSP,
+        dst,
+    );
+
+    buf.extend(inst.bytes());
+}
+
+/// `ORR Xd, Xn, Xm` -> Bitwise or Xn and Xm and place the result into Xd.
+#[inline(always)]
+fn orr_reg64_reg64_reg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    let inst = LogicalShiftedRegister::new(LogicalOp::ORR, ShiftType::LSL, 0, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `SCVTF Sd, Xn` -> Convert the signed integer in Xn to a single and place it into Sd.
+#[inline(always)]
+fn scvtf_freg32_reg64(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64GeneralReg) {
+    let inst =
+        FloatingPointIntegerConversion::new(true, 0b010, FloatWidth::F32, src.id(), dst.id());
+
+    buf.extend(inst.bytes());
+}
+
+/// `SCVTF Dd, Xn` -> Convert the signed integer in Xn to a double and place it into Dd.
+#[inline(always)]
+fn scvtf_freg64_reg64(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64GeneralReg) {
+    let inst =
+        FloatingPointIntegerConversion::new(true, 0b010, FloatWidth::F64, src.id(), dst.id());
+
+    buf.extend(inst.bytes());
+}
+
+/// `STP Xt1, Xt2, [Xn, #offset]!` -> Add the offset to Xn, then store Xt1 and Xt2 to Xn. ZRSP is SP.
+/// Note: imm7 is the offset divided by 8.
+#[inline(always)]
+fn stp_pre_reg64_reg64_reg64_imm7(
+    buf: &mut Vec<'_, u8>,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
     base: AArch64GeneralReg,
-    imm12: u16,
+    imm7: i8,
 ) {
-    let inst = LoadStoreRegisterImmediate::new_store(0b11, imm12, base, src);
+    let inst = LoadStorePair::new(0b011, false, imm7, src2, base, src1);
 
     buf.extend(inst.bytes());
 }
@@ -1201,6 +2773,32 @@ fn sub_reg64_reg64_imm12(
     buf.extend(inst.bytes());
 }
 
+/// `SUB Xd, Xn, imm12, LSL #12` -> Subtract Xn and imm12 shifted left by 12 and place the result into Xd.
+#[inline(always)]
+fn sub_reg64_reg64_imm12_lsl12(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src: AArch64GeneralReg,
+    imm12: u16,
+) {
+    let inst = ArithmeticImmediate::new(true, false, dst, src, imm12, true);
+
+    buf.extend(inst.bytes());
+}
+
+/// `SUB Xd, Xn, Xm` -> Subtract Xn and Xm and place the result into Xd.
+#[inline(always)]
+fn sub_reg64_reg64_reg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    let inst = ArithmeticShifted::new(true, false, ShiftType::LSL, 0, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
 /// `RET Xn` -> Return to the address stored in Xn.
 #[inline(always)]
 fn ret_reg64(buf: &mut Vec<'_, u8>, xn: AArch64GeneralReg) {
@@ -1239,6 +2837,93 @@ mod tests {
         }
     }
 
+    impl AArch64GeneralReg {
+        fn capstone_string_32bit(&self) -> String {
+            match self {
+                AArch64GeneralReg::ZRSP => "wzr".to_owned(),
+                _ => format!("w{}", self.id()),
+            }
+        }
+    }
+
+    impl AArch64FloatReg {
+        fn capstone_string(&self, width: FloatWidth) -> String {
+            match width {
+                FloatWidth::F32 => format!("s{}", self.id()),
+                FloatWidth::F64 => format!("d{}", self.id()),
+                FloatWidth::F128 => format!("q{}", self.id()),
+            }
+        }
+    }
+
+    impl LoadStoreOp {
+        fn capstone_mnemonic(&self, unscaled: bool) -> &'static str {
+            match (self, unscaled) {
+                (
+                    LoadStoreOp::Ldr64
+                    | LoadStoreOp::LdrFloat64
+                    | LoadStoreOp::LdrFloat32
+                    | LoadStoreOp::Ldr32,
+                    false,
+                ) => "ldr",
+                (
+                    LoadStoreOp::Ldr64
+                    | LoadStoreOp::LdrFloat64
+                    | LoadStoreOp::LdrFloat32
+                    | LoadStoreOp::Ldr32,
+                    true,
+                ) => "ldur",
+                (
+                    LoadStoreOp::Str64
+                    | LoadStoreOp::StrFloat64
+                    | LoadStoreOp::StrFloat32
+                    | LoadStoreOp::Str32,
+                    false,
+                ) => "str",
+                (
+                    LoadStoreOp::Str64
+                    | LoadStoreOp::StrFloat64
+                    | LoadStoreOp::StrFloat32
+                    | LoadStoreOp::Str32,
+                    true,
+                ) => "stur",
+                (LoadStoreOp::LdrSigned32, false) => "ldrsw",
+                (LoadStoreOp::LdrSigned32, true) => "ldursw",
+                (LoadStoreOp::LdrSigned16, false) => "ldrsh",
+                (LoadStoreOp::LdrSigned16, true) => "ldursh",
+                (LoadStoreOp::LdrSigned8, false) => "ldrsb",
+                (LoadStoreOp::LdrSigned8, true) => "ldursb",
+                (LoadStoreOp::Ldr16, false) => "ldrh",
+                (LoadStoreOp::Ldr16, true) => "ldurh",
+                (LoadStoreOp::Ldr8, false) => "ldrb",
+                (LoadStoreOp::Ldr8, true) => "ldurb",
+                (LoadStoreOp::Str16, false) => "strh",
+                (LoadStoreOp::Str16, true) => "sturh",
+                (LoadStoreOp::Str8, false) => "strb",
+                (LoadStoreOp::Str8, true) => "sturb",
+            }
+        }
+
+        /// The name capstone uses for the register the tests load into or store from.
+        fn capstone_rt(&self) -> &'static str {
+            match self {
+                LoadStoreOp::Ldr64
+                | LoadStoreOp::Str64
+                | LoadStoreOp::LdrSigned32
+                | LoadStoreOp::LdrSigned16
+                | LoadStoreOp::LdrSigned8 => "x3",
+                LoadStoreOp::Ldr32
+                | LoadStoreOp::Ldr16
+                | LoadStoreOp::Ldr8
+                | LoadStoreOp::Str32
+                | LoadStoreOp::Str16
+                | LoadStoreOp::Str8 => "w3",
+                LoadStoreOp::LdrFloat64 | LoadStoreOp::StrFloat64 => "d3",
+                LoadStoreOp::LdrFloat32 | LoadStoreOp::StrFloat32 => "s3",
+            }
+        }
+    }
+
     const TEST_U16: u16 = 0x1234;
     //const TEST_I32: i32 = 0x12345678;
     //const TEST_I64: i64 = 0x12345678_9ABCDEF0;
@@ -1278,6 +2963,104 @@ mod tests {
         AArch64GeneralReg::ZRSP,
     ];
 
+    const ALL_FLOAT_REGS: &[AArch64FloatReg] = &[
+        AArch64FloatReg::V0,
+        AArch64FloatReg::V1,
+        AArch64FloatReg::V2,
+        AArch64FloatReg::V3,
+        AArch64FloatReg::V4,
+        AArch64FloatReg::V5,
+        AArch64FloatReg::V6,
+        AArch64FloatReg::V7,
+        AArch64FloatReg::V8,
+        AArch64FloatReg::V9,
+        AArch64FloatReg::V10,
+        AArch64FloatReg::V11,
+        AArch64FloatReg::V12,
+        AArch64FloatReg::V13,
+        AArch64FloatReg::V14,
+        AArch64FloatReg::V15,
+        AArch64FloatReg::V16,
+        AArch64FloatReg::V17,
+        AArch64FloatReg::V18,
+        AArch64FloatReg::V19,
+        AArch64FloatReg::V20,
+        AArch64FloatReg::V21,
+        AArch64FloatReg::V22,
+        AArch64FloatReg::V23,
+        AArch64FloatReg::V24,
+        AArch64FloatReg::V25,
+        AArch64FloatReg::V26,
+        AArch64FloatReg::V27,
+        AArch64FloatReg::V28,
+        AArch64FloatReg::V29,
+        AArch64FloatReg::V30,
+        AArch64FloatReg::V31,
+    ];
+
+    const ALL_CONDITION_CODES: &[ConditionCode] = &[
+        ConditionCode::EQ,
+        ConditionCode::NE,
+        ConditionCode::HS,
+        ConditionCode::LO,
+        ConditionCode::MI,
+        ConditionCode::PL,
+        ConditionCode::VS,
+        ConditionCode::VC,
+        ConditionCode::HI,
+        ConditionCode::LS,
+        ConditionCode::GE,
+        ConditionCode::LT,
+        ConditionCode::GT,
+        ConditionCode::LE,
+    ];
+
+    const ALL_LOAD_STORE_OPS: &[LoadStoreOp] = &[
+        LoadStoreOp::Ldr64,
+        LoadStoreOp::Str64,
+        LoadStoreOp::LdrFloat64,
+        LoadStoreOp::StrFloat64,
+        LoadStoreOp::LdrFloat32,
+        LoadStoreOp::StrFloat32,
+        LoadStoreOp::LdrSigned32,
+        LoadStoreOp::LdrSigned16,
+        LoadStoreOp::LdrSigned8,
+        LoadStoreOp::Ldr32,
+        LoadStoreOp::Ldr16,
+        LoadStoreOp::Ldr8,
+        LoadStoreOp::Str32,
+        LoadStoreOp::Str16,
+        LoadStoreOp::Str8,
+    ];
+
+    /// Loads or stores X3, W3, D3, or S3 depending on the op.
+    fn load_store_rt3_imm12(
+        buf: &mut Vec<'_, u8>,
+        op: LoadStoreOp,
+        base: AArch64GeneralReg,
+        imm12: u16,
+    ) {
+        if op.fields().1 {
+            load_store_imm12(buf, op, AArch64FloatReg::V3, base, imm12);
+        } else {
+            load_store_imm12(buf, op, AArch64GeneralReg::X3, base, imm12);
+        }
+    }
+
+    /// Loads or stores X3, W3, D3, or S3 depending on the op.
+    fn load_store_rt3_imm9(
+        buf: &mut Vec<'_, u8>,
+        op: LoadStoreOp,
+        base: AArch64GeneralReg,
+        imm9: i16,
+    ) {
+        if op.fields().1 {
+            load_store_imm9(buf, op, AArch64FloatReg::V3, base, imm9);
+        } else {
+            load_store_imm9(buf, op, AArch64GeneralReg::X3, base, imm9);
+        }
+    }
+
     fn setup_capstone_and_arena<T>(
         arena: &bumpalo::Bump,
     ) -> (bumpalo::collections::Vec<T>, Capstone) {
@@ -1291,6 +3074,25 @@ mod tests {
         (buf, cs)
     }
 
+    #[test]
+    fn test_abs_reg64_reg64() {
+        disassembler_test!(
+            AArch64Assembler::abs_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg| format!(
+                "cmp {}, #0\ncneg {}, {}, lt",
+                reg2.capstone_string(UsesSP),
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            [
+                AArch64GeneralReg::X0,
+                AArch64GeneralReg::X1,
+                AArch64GeneralReg::X19
+            ]
+        );
+    }
+
     #[test]
     fn test_add_reg64_reg64_reg64() {
         disassembler_test!(
@@ -1324,11 +3126,313 @@ mod tests {
     }
 
     #[test]
-    fn test_ldr_reg64_reg64_imm12() {
+    fn test_add_reg64_reg64_imm12_lsl12() {
         disassembler_test!(
-            ldr_reg64_reg64_imm12,
+            add_reg64_reg64_imm12_lsl12,
             |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm| format!(
-                "ldr {}, [{}, #0x{:x}]",
+                "add {}, {}, #0x{:x}, lsl #12",
+                reg1.capstone_string(UsesSP),
+                reg2.capstone_string(UsesSP),
+                imm
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [0x123]
+        );
+    }
+
+    #[test]
+    fn test_add_reg64_reg64_imm32() {
+        disassembler_test!(
+            AArch64Assembler::add_reg64_reg64_imm32,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm: i32| {
+                let (op, magnitude) = if imm < 0 { ("sub", -imm) } else { ("add", imm) };
+                match magnitude {
+                    0x10 => format!(
+                        "{} {}, {}, #0x10",
+                        op,
+                        reg1.capstone_string(UsesSP),
+                        reg2.capstone_string(UsesSP)
+                    ),
+                    _ => format!(
+                        "{} {}, {}, #0x12, lsl #12\n{} {}, {}, #0x345",
+                        op,
+                        reg1.capstone_string(UsesSP),
+                        reg2.capstone_string(UsesSP),
+                        op,
+                        reg1.capstone_string(UsesSP),
+                        reg1.capstone_string(UsesSP)
+                    ),
+                }
+            },
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [0x10, -0x10, 0x12345, -0x12345]
+        );
+    }
+
+    #[test]
+    fn test_and_reg64_reg64_reg64() {
+        disassembler_test!(
+            and_reg64_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| format!(
+                "and {}, {}, {}",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR),
+                reg3.capstone_string(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_b_cond_imm19() {
+        disassembler_test!(
+            b_cond_imm19,
+            |cond: ConditionCode, imm: i32| {
+                let cond = format!("{:?}", cond).to_lowercase();
+                format!("b.{} #0x{:x}", cond, imm << 2)
+            },
+            ALL_CONDITION_CODES,
+            [0x12]
+        );
+    }
+
+    #[test]
+    fn test_b_imm26() {
+        disassembler_test!(b_imm26, |imm: i32| format!("b #0x{:x}", imm << 2), [0x12]);
+    }
+
+    #[test]
+    fn test_bl_imm26() {
+        disassembler_test!(bl_imm26, |imm: i32| format!("bl #0x{:x}", imm << 2), [0x12]);
+    }
+
+    #[test]
+    fn test_cmp_reg64_imm12() {
+        disassembler_test!(
+            cmp_reg64_imm12,
+            |reg1: AArch64GeneralReg, imm| format!(
+                "cmp {}, #0x{:x}",
+                reg1.capstone_string(UsesSP),
+                imm
+            ),
+            ALL_GENERAL_REGS,
+            [0x123]
+        );
+    }
+
+    #[test]
+    fn test_cmp_reg64_reg64() {
+        disassembler_test!(
+            cmp_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg| format!(
+                "cmp {}, {}",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_cset_reg64_cond() {
+        disassembler_test!(
+            cset_reg64_cond,
+            |reg1: AArch64GeneralReg, cond: ConditionCode| {
+                let cond = format!("{:?}", cond).to_lowercase();
+                format!("cset {}, {}", reg1.capstone_string(UsesZR), cond)
+            },
+            ALL_GENERAL_REGS,
+            ALL_CONDITION_CODES
+        );
+    }
+
+    #[test]
+    fn test_fabs_freg64_freg64() {
+        disassembler_test!(
+            fabs_freg64_freg64,
+            |reg1: AArch64FloatReg, reg2: AArch64FloatReg| format!(
+                "fabs {}, {}",
+                reg1.capstone_string(FloatWidth::F64),
+                reg2.capstone_string(FloatWidth::F64)
+            ),
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS
+        );
+    }
+
+    #[test]
+    fn test_fadd_freg32_freg32_freg32() {
+        disassembler_test!(
+            fadd_freg32_freg32_freg32,
+            |reg1: AArch64FloatReg, reg2: AArch64FloatReg, reg3: AArch64FloatReg| format!(
+                "fadd {}, {}, {}",
+                reg1.capstone_string(FloatWidth::F32),
+                reg2.capstone_string(FloatWidth::F32),
+                reg3.capstone_string(FloatWidth::F32)
+            ),
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS
+        );
+    }
+
+    #[test]
+    fn test_fadd_freg64_freg64_freg64() {
+        disassembler_test!(
+            fadd_freg64_freg64_freg64,
+            |reg1: AArch64FloatReg, reg2: AArch64FloatReg, reg3: AArch64FloatReg| format!(
+                "fadd {}, {}, {}",
+                reg1.capstone_string(FloatWidth::F64),
+                reg2.capstone_string(FloatWidth::F64),
+                reg3.capstone_string(FloatWidth::F64)
+            ),
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS
+        );
+    }
+
+    #[test]
+    fn test_fcvt_freg32_freg64() {
+        disassembler_test!(
+            fcvt_freg32_freg64,
+            |reg1: AArch64FloatReg, reg2: AArch64FloatReg| format!(
+                "fcvt {}, {}",
+                reg1.capstone_string(FloatWidth::F32),
+                reg2.capstone_string(FloatWidth::F64)
+            ),
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS
+        );
+    }
+
+    #[test]
+    fn test_fcvt_freg64_freg32() {
+        disassembler_test!(
+            fcvt_freg64_freg32,
+            |reg1: AArch64FloatReg, reg2: AArch64FloatReg| format!(
+                "fcvt {}, {}",
+                reg1.capstone_string(FloatWidth::F64),
+                reg2.capstone_string(FloatWidth::F32)
+            ),
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS
+        );
+    }
+
+    #[test]
+    fn test_fmov_freg32_reg32() {
+        disassembler_test!(
+            fmov_freg32_reg32,
+            |reg1: AArch64FloatReg, reg2: AArch64GeneralReg| format!(
+                "fmov {}, {}",
+                reg1.capstone_string(FloatWidth::F32),
+                reg2.capstone_string_32bit()
+            ),
+            ALL_FLOAT_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_fmov_freg64_freg64() {
+        disassembler_test!(
+            fmov_freg64_freg64,
+            |reg1: AArch64FloatReg, reg2: AArch64FloatReg| format!(
+                "fmov {}, {}",
+                reg1.capstone_string(FloatWidth::F64),
+                reg2.capstone_string(FloatWidth::F64)
+            ),
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS
+        );
+    }
+
+    #[test]
+    fn test_fmov_freg64_reg64() {
+        disassembler_test!(
+            fmov_freg64_reg64,
+            |reg1: AArch64FloatReg, reg2: AArch64GeneralReg| format!(
+                "fmov {}, {}",
+                reg1.capstone_string(FloatWidth::F64),
+                reg2.capstone_string(UsesZR)
+            ),
+            ALL_FLOAT_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_fmul_freg32_freg32_freg32() {
+        disassembler_test!(
+            fmul_freg32_freg32_freg32,
+            |reg1: AArch64FloatReg, reg2: AArch64FloatReg, reg3: AArch64FloatReg| format!(
+                "fmul {}, {}, {}",
+                reg1.capstone_string(FloatWidth::F32),
+                reg2.capstone_string(FloatWidth::F32),
+                reg3.capstone_string(FloatWidth::F32)
+            ),
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS
+        );
+    }
+
+    #[test]
+    fn test_fmul_freg64_freg64_freg64() {
+        disassembler_test!(
+            fmul_freg64_freg64_freg64,
+            |reg1: AArch64FloatReg, reg2: AArch64FloatReg, reg3: AArch64FloatReg| format!(
+                "fmul {}, {}, {}",
+                reg1.capstone_string(FloatWidth::F64),
+                reg2.capstone_string(FloatWidth::F64),
+                reg3.capstone_string(FloatWidth::F64)
+            ),
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS
+        );
+    }
+
+    #[test]
+    fn test_ldp_post_reg64_reg64_reg64_imm7() {
+        disassembler_test!(
+            |buf: &mut Vec<'_, u8>, reg1, reg2, imm| ldp_post_reg64_reg64_reg64_imm7(
+                buf,
+                reg1,
+                reg2,
+                AArch64GeneralReg::ZRSP,
+                imm
+            ),
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm: i8| format!(
+                "ldp {}, {}, [sp], #0x{:x}",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR),
+                imm << 3
+            ),
+            [AArch64GeneralReg::FP, AArch64GeneralReg::X19],
+            [AArch64GeneralReg::LR, AArch64GeneralReg::X20],
+            [2]
+        );
+    }
+
+    #[test]
+    fn test_ldr_reg64_reg64_imm12() {
+        disassembler_test!(
+            |buf: &mut Vec<'_, u8>, reg1, reg2, imm| load_store_imm12(
+                buf,
+                LoadStoreOp::Ldr64,
+                reg1,
+                reg2,
+                imm
+            ),
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm| format!(
+                "ldr {}, [{}, #0x{:x}]",
                 reg1.capstone_string(UsesZR),
                 reg2.capstone_string(UsesSP),
                 imm << 3
@@ -1339,6 +3443,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_load_store_imm12() {
+        disassembler_test!(
+            load_store_rt3_imm12,
+            |op: LoadStoreOp, reg: AArch64GeneralReg, imm: u16| format!(
+                "{} {}, [{}, #0x{:x}]",
+                op.capstone_mnemonic(false),
+                op.capstone_rt(),
+                reg.capstone_string(UsesSP),
+                imm as i32 * op.size_bytes()
+            ),
+            ALL_LOAD_STORE_OPS,
+            ALL_GENERAL_REGS,
+            [0x123]
+        );
+    }
+
+    #[test]
+    fn test_load_store_imm9() {
+        disassembler_test!(
+            load_store_rt3_imm9,
+            |op: LoadStoreOp, reg: AArch64GeneralReg, imm: i16| format!(
+                "{} {}, [{}, #{}0x{:x}]",
+                op.capstone_mnemonic(true),
+                op.capstone_rt(),
+                reg.capstone_string(UsesSP),
+                if imm < 0 { "-" } else { "" },
+                imm.unsigned_abs()
+            ),
+            ALL_LOAD_STORE_OPS,
+            ALL_GENERAL_REGS,
+            [-0x10, 0x11]
+        );
+    }
+
+    #[test]
+    fn test_mov_reg64_base32() {
+        disassembler_test!(
+            AArch64Assembler::mov_reg64_base32,
+            |reg1: AArch64GeneralReg, offset: i32| match offset {
+                0x10 => format!("ldr {}, [x29, #0x10]", reg1.capstone_string(UsesZR)),
+                -0x10 => format!("ldur {}, [x29, #-0x10]", reg1.capstone_string(UsesZR)),
+                _ => format!(
+                    "sub x16, x29, #1, lsl #12\nldr {}, [x16]",
+                    reg1.capstone_string(UsesZR)
+                ),
+            },
+            ALL_GENERAL_REGS,
+            [0x10, -0x10, -0x1000]
+        );
+    }
+
     #[test]
     fn test_mov_reg64_reg64() {
         disassembler_test!(
@@ -1389,10 +3545,112 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_mul_reg64_reg64_reg64() {
+        disassembler_test!(
+            mul_reg64_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| format!(
+                "mul {}, {}, {}",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR),
+                reg3.capstone_string(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_neg_reg64_reg64() {
+        disassembler_test!(
+            neg_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg| format!(
+                "neg {}, {}",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_orr_reg64_reg64_reg64() {
+        disassembler_test!(
+            orr_reg64_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| format!(
+                "orr {}, {}, {}",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR),
+                reg3.capstone_string(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_scvtf_freg32_reg64() {
+        disassembler_test!(
+            scvtf_freg32_reg64,
+            |reg1: AArch64FloatReg, reg2: AArch64GeneralReg| format!(
+                "scvtf {}, {}",
+                reg1.capstone_string(FloatWidth::F32),
+                reg2.capstone_string(UsesZR)
+            ),
+            ALL_FLOAT_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_scvtf_freg64_reg64() {
+        disassembler_test!(
+            scvtf_freg64_reg64,
+            |reg1: AArch64FloatReg, reg2: AArch64GeneralReg| format!(
+                "scvtf {}, {}",
+                reg1.capstone_string(FloatWidth::F64),
+                reg2.capstone_string(UsesZR)
+            ),
+            ALL_FLOAT_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_stp_pre_reg64_reg64_reg64_imm7() {
+        disassembler_test!(
+            |buf: &mut Vec<'_, u8>, reg1, reg2, imm| stp_pre_reg64_reg64_reg64_imm7(
+                buf,
+                reg1,
+                reg2,
+                AArch64GeneralReg::ZRSP,
+                imm
+            ),
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm: i8| format!(
+                "stp {}, {}, [sp, #-0x{:x}]!",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR),
+                -(imm as i32) << 3
+            ),
+            [AArch64GeneralReg::FP, AArch64GeneralReg::X19],
+            [AArch64GeneralReg::LR, AArch64GeneralReg::X20],
+            [-2]
+        );
+    }
+
     #[test]
     fn test_str_reg64_reg64_imm12() {
         disassembler_test!(
-            str_reg64_reg64_imm12,
+            |buf: &mut Vec<'_, u8>, reg1, reg2, imm| load_store_imm12(
+                buf,
+                LoadStoreOp::Str64,
+                reg1,
+                reg2,
+                imm
+            ),
             |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm| format!(
                 "str {}, [{}, #0x{:x}]",
                 reg1.capstone_string(UsesZR),
@@ -1421,6 +3679,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sub_reg64_reg64_imm12_lsl12() {
+        disassembler_test!(
+            sub_reg64_reg64_imm12_lsl12,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm| format!(
+                "sub {}, {}, #0x{:x}, lsl #12",
+                reg1.capstone_string(UsesSP),
+                reg2.capstone_string(UsesSP),
+                imm
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [0x123]
+        );
+    }
+
+    #[test]
+    fn test_sub_reg64_reg64_reg64() {
+        disassembler_test!(
+            sub_reg64_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| {
+                if reg2 == AArch64GeneralReg::ZRSP {
+                    format!(
+                        "neg {}, {}",
+                        reg1.capstone_string(UsesZR),
+                        reg3.capstone_string(UsesZR)
+                    )
+                } else {
+                    format!(
+                        "sub {}, {}, {}",
+                        reg1.capstone_string(UsesZR),
+                        reg2.capstone_string(UsesZR),
+                        reg3.capstone_string(UsesZR)
+                    )
+                }
+            },
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
     #[test]
     fn test_ret_reg64() {
         disassembler_test!(
@@ -1433,4 +3733,156 @@ mod tests {
             ALL_GENERAL_REGS
         );
     }
+
+    const U8: Layout = Layout::Builtin(Builtin::Int(IntWidth::U8));
+    const U32: Layout = Layout::Builtin(Builtin::Int(IntWidth::U32));
+    const I64: Layout = Layout::Builtin(Builtin::Int(IntWidth::I64));
+    const F32: Layout = Layout::Builtin(Builtin::Float(FloatWidth::F32));
+    const F64: Layout = Layout::Builtin(Builtin::Float(FloatWidth::F64));
+
+    /// Fills all the general and float argument registers, so later arguments go on the stack.
+    fn fill_arg_regs(allocator: &mut ArgAllocator) {
+        for _ in AArch64Call::<false>::GENERAL_PARAM_REGS {
+            allocator.next(ArgClass::of(&I64));
+        }
+        for _ in AArch64Call::<false>::FLOAT_PARAM_REGS {
+            allocator.next(ArgClass::of(&F64));
+        }
+    }
+
+    #[test]
+    fn test_arg_class_hfa() {
+        let floats = [F32, F32, F32];
+        assert_eq!(
+            ArgClass::of(&Layout::struct_no_name_order(&floats)),
+            ArgClass::Hfa {
+                width: FloatWidth::F32,
+                count: 3
+            }
+        );
+
+        let inner = [F64];
+        let nested = [F64, Layout::struct_no_name_order(&inner)];
+        assert_eq!(
+            ArgClass::of(&Layout::struct_no_name_order(&nested)),
+            ArgClass::Hfa {
+                width: FloatWidth::F64,
+                count: 2
+            }
+        );
+
+        let mixed_widths = [F32, F64];
+        assert_eq!(
+            homogeneous_floats(&Layout::struct_no_name_order(&mixed_widths)),
+            None
+        );
+
+        let mixed_kinds = [F64, I64];
+        assert_eq!(
+            ArgClass::of(&Layout::struct_no_name_order(&mixed_kinds)),
+            ArgClass::Composite {
+                size: 16,
+                alignment: 8
+            }
+        );
+
+        let too_many = [F32, F32, F32, F32, F32];
+        assert_eq!(
+            homogeneous_floats(&Layout::struct_no_name_order(&too_many)),
+            None
+        );
+        assert_eq!(
+            ArgClass::of(&Layout::struct_no_name_order(&too_many)),
+            ArgClass::Indirect { size: 20 }
+        );
+    }
+
+    #[test]
+    fn test_arg_allocator_spills_to_stack() {
+        let mut allocator = ArgAllocator::new(0, false);
+        for i in 0..AArch64Call::<false>::GENERAL_PARAM_REGS.len() {
+            assert_eq!(
+                allocator.next(ArgClass::of(&I64)),
+                ArgLocation::GeneralRegs(i, 1)
+            );
+        }
+        assert_eq!(allocator.next(ArgClass::of(&I64)), ArgLocation::Stack(0));
+
+        // Float registers are handed out separately.
+        for i in 0..AArch64Call::<false>::FLOAT_PARAM_REGS.len() {
+            assert_eq!(
+                allocator.next(ArgClass::of(&F64)),
+                ArgLocation::FloatRegs(i, 1)
+            );
+        }
+        assert_eq!(allocator.next(ArgClass::of(&F64)), ArgLocation::Stack(8));
+
+        // An HFA that doesn't fit in the remaining float registers goes on the stack as a whole.
+        let mut allocator = ArgAllocator::new(0, false);
+        for _ in 0..7 {
+            allocator.next(ArgClass::of(&F64));
+        }
+        let floats = [F64, F64];
+        assert_eq!(
+            allocator.next(ArgClass::of(&Layout::struct_no_name_order(&floats))),
+            ArgLocation::Stack(0)
+        );
+        assert_eq!(allocator.next(ArgClass::of(&F64)), ArgLocation::Stack(16));
+    }
+
+    #[test]
+    fn test_arg_allocator_aligned_16() {
+        let i128 = Layout::Builtin(Builtin::Int(IntWidth::I128));
+        let mut allocator = ArgAllocator::new(0, false);
+        assert_eq!(
+            allocator.next(ArgClass::of(&I64)),
+            ArgLocation::GeneralRegs(0, 1)
+        );
+        assert_eq!(
+            allocator.next(ArgClass::of(&i128)),
+            ArgLocation::GeneralRegs(2, 2)
+        );
+
+        let mut allocator = ArgAllocator::new(0, false);
+        fill_arg_regs(&mut allocator);
+        assert_eq!(allocator.next(ArgClass::of(&I64)), ArgLocation::Stack(0));
+        assert_eq!(allocator.next(ArgClass::of(&i128)), ArgLocation::Stack(16));
+    }
+
+    #[test]
+    fn test_arg_allocator_linux_stack_slots() {
+        let mut allocator = ArgAllocator::new(0, false);
+        fill_arg_regs(&mut allocator);
+        assert_eq!(allocator.next(ArgClass::of(&U8)), ArgLocation::Stack(0));
+        assert_eq!(allocator.next(ArgClass::of(&U32)), ArgLocation::Stack(8));
+        assert_eq!(allocator.next(ArgClass::of(&F32)), ArgLocation::Stack(16));
+        assert_eq!(allocator.next(ArgClass::of(&U8)), ArgLocation::Stack(24));
+        assert_eq!(allocator.stack_offset, 32);
+    }
+
+    #[test]
+    fn test_arg_allocator_apple_stack_packing() {
+        let mut allocator = ArgAllocator::new(0, true);
+        fill_arg_regs(&mut allocator);
+        assert_eq!(allocator.next(ArgClass::of(&U8)), ArgLocation::Stack(0));
+        assert_eq!(allocator.next(ArgClass::of(&U32)), ArgLocation::Stack(4));
+        assert_eq!(allocator.next(ArgClass::of(&F32)), ArgLocation::Stack(8));
+        assert_eq!(allocator.next(ArgClass::of(&U8)), ArgLocation::Stack(12));
+        // Values are aligned to their natural alignment.
+        assert_eq!(allocator.next(ArgClass::of(&I64)), ArgLocation::Stack(16));
+
+        // HFAs take their exact size, other composites take a multiple of 8 bytes.
+        let floats = [F32, F32, F32];
+        assert_eq!(
+            allocator.next(ArgClass::of(&Layout::struct_no_name_order(&floats))),
+            ArgLocation::Stack(24)
+        );
+        assert_eq!(allocator.next(ArgClass::of(&U8)), ArgLocation::Stack(36));
+        let fields = [U32, U32, U8];
+        assert_eq!(
+            allocator.next(ArgClass::of(&Layout::struct_no_name_order(&fields))),
+            ArgLocation::Stack(40)
+        );
+        assert_eq!(allocator.stack_offset, 56);
+    }
 }
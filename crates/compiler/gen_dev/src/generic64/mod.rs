@@ -6,12 +6,13 @@ use bumpalo::collections::Vec;
 use roc_builtins::bitcode::{self, FloatWidth, IntWidth};
 use roc_collections::all::MutMap;
 use roc_error_macros::internal_error;
-use roc_module::symbol::{Interns, Symbol};
+use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::code_gen_help::CodeGenHelp;
 use roc_mono::ir::{
     BranchInfo, JoinPointId, ListLiteralElement, Literal, Param, ProcLayout, SelfRecursive, Stmt,
 };
 use roc_mono::layout::{Builtin, Layout, TagIdIntType, UnionLayout};
+use roc_region::all::Region;
 use roc_target::TargetInfo;
 use std::marker::PhantomData;
 
@@ -246,6 +247,20 @@ pub trait Assembler<GeneralReg: RegTrait, FloatReg: RegTrait>: Sized + Copy {
         src2: GeneralReg,
     );
 
+    fn and_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        src1: GeneralReg,
+        src2: GeneralReg,
+    );
+
+    fn or_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        src1: GeneralReg,
+        src2: GeneralReg,
+    );
+
     fn eq_reg64_reg64_reg64(
         buf: &mut Vec<'_, u8>,
         dst: GeneralReg,
@@ -656,6 +671,72 @@ impl<
         }
     }
 
+    fn build_expect(
+        &mut self,
+        condition: &Symbol,
+        region: Region,
+        lookups: &'a [Symbol],
+        layouts: &'a [Layout<'a>],
+    ) {
+        // The failure path calls into zig, so free everything to the stack first.
+        // That way both paths agree on where every symbol is when they meet again.
+        self.storage_manager.free_all_to_stack(&mut self.buf);
+        let base_storage = self.storage_manager.clone();
+
+        let cond_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, condition);
+
+        // Jump over the failure path if the condition holds.
+        // Since we don't know the offset yet, set it to 0 and overwrite later.
+        let jne_location = self.buf.len();
+        let start_offset = ASM::jne_reg64_imm64_imm32(&mut self.buf, cond_reg, 0, 0);
+
+        self.build_expect_frame(condition.module_id(), region, lookups, layouts);
+
+        if self.env.mode.runs_expects_in_separate_process() {
+            // Signals the parent process that an expect failed.
+            self.build_void_fn_call("expect_finalize", bitcode::UTILS_EXPECT_FAILED_FINALIZE);
+        }
+
+        // Overwrite the original jne with the correct offset.
+        let mut tmp = bumpalo::vec![in self.env.arena];
+        let jne_offset = self.buf.len() - start_offset;
+        ASM::jne_reg64_imm64_imm32(&mut tmp, cond_reg, 0, jne_offset as i32);
+        for (i, byte) in tmp.iter().enumerate() {
+            self.buf[jne_location + i] = *byte;
+        }
+
+        // Nothing stored by the failure path is used after it, but it still needs the stack space.
+        let stack_size = self.storage_manager.stack_size();
+        let fn_call_stack_size = self.storage_manager.fn_call_stack_size();
+        self.storage_manager = base_storage;
+        self.storage_manager.update_stack_size(stack_size);
+        self.storage_manager
+            .update_fn_call_stack_size(fn_call_stack_size);
+    }
+
+    fn build_dbg(&mut self, symbol: &Symbol, region: Region, layout: &Layout<'a>) {
+        // This calls into zig, and the symbols it creates are not used afterwards.
+        // Handle storage the same way as the failure path of an expect.
+        self.storage_manager.free_all_to_stack(&mut self.buf);
+        let base_storage = self.storage_manager.clone();
+
+        let lookups = self.env.arena.alloc([*symbol]);
+        let layouts = self.env.arena.alloc([*layout]);
+        self.build_expect_frame(symbol.module_id(), region, lookups, layouts);
+
+        // Signals the parent process that there is a value to print, and waits until it is printed.
+        self.build_void_fn_call("dbg_notify", bitcode::UTILS_NOTIFY_PARENT_DBG);
+
+        let stack_size = self.storage_manager.stack_size();
+        let fn_call_stack_size = self.storage_manager.fn_call_stack_size();
+        self.storage_manager = base_storage;
+        self.storage_manager.update_stack_size(stack_size);
+        self.storage_manager
+            .update_fn_call_stack_size(fn_call_stack_size);
+    }
+
     fn build_join(
         &mut self,
         id: &JoinPointId,
@@ -837,7 +918,7 @@ impl<
 
     fn build_eq(&mut self, dst: &Symbol, src1: &Symbol, src2: &Symbol, arg_layout: &Layout<'a>) {
         match arg_layout {
-            Layout::Builtin(single_register_int_builtins!()) => {
+            Layout::Builtin(single_register_int_builtins!() | Builtin::Bool) => {
                 let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
                 let src1_reg = self
                     .storage_manager
@@ -853,7 +934,7 @@ impl<
 
     fn build_neq(&mut self, dst: &Symbol, src1: &Symbol, src2: &Symbol, arg_layout: &Layout<'a>) {
         match arg_layout {
-            Layout::Builtin(Builtin::Int(IntWidth::I64 | IntWidth::U64)) => {
+            Layout::Builtin(single_register_int_builtins!() | Builtin::Bool) => {
                 let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
                 let src1_reg = self
                     .storage_manager
@@ -867,6 +948,56 @@ impl<
         }
     }
 
+    fn build_and(&mut self, dst: &Symbol, src1: &Symbol, src2: &Symbol, arg_layout: &Layout<'a>) {
+        match arg_layout {
+            Layout::Builtin(Builtin::Bool) => {
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+                let src1_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, src1);
+                let src2_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, src2);
+                ASM::and_reg64_reg64_reg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
+            }
+            x => todo!("And: layout, {:?}", x),
+        }
+    }
+
+    fn build_or(&mut self, dst: &Symbol, src1: &Symbol, src2: &Symbol, arg_layout: &Layout<'a>) {
+        match arg_layout {
+            Layout::Builtin(Builtin::Bool) => {
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+                let src1_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, src1);
+                let src2_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, src2);
+                ASM::or_reg64_reg64_reg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
+            }
+            x => todo!("Or: layout, {:?}", x),
+        }
+    }
+
+    fn build_not(&mut self, dst: &Symbol, src: &Symbol, arg_layout: &Layout<'a>) {
+        match arg_layout {
+            Layout::Builtin(Builtin::Bool) => {
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+                let src_reg = self.storage_manager.load_to_general_reg(&mut self.buf, src);
+                // Bools are always 0 or 1, so `!src` is `src == 0`.
+                self.storage_manager.with_tmp_general_reg(
+                    &mut self.buf,
+                    |_storage_manager, buf, zero_reg| {
+                        ASM::mov_reg64_imm64(buf, zero_reg, 0);
+                        ASM::eq_reg64_reg64_reg64(buf, dst_reg, src_reg, zero_reg);
+                    },
+                );
+            }
+            x => todo!("Not: layout, {:?}", x),
+        }
+    }
+
     fn build_num_lt(
         &mut self,
         dst: &Symbol,
@@ -889,6 +1020,29 @@ impl<
         }
     }
 
+    fn build_num_int_cast(
+        &mut self,
+        dst: &Symbol,
+        src: &Symbol,
+        source: &Layout<'a>,
+        target: &Layout<'a>,
+    ) {
+        let target_info = self.storage_manager.target_info();
+        match (source, target) {
+            (
+                Layout::Builtin(single_register_int_builtins!()),
+                Layout::Builtin(single_register_int_builtins!()),
+            ) if target.stack_size(target_info) >= source.stack_size(target_info) => {
+                // Integers are kept sign or zero extended to the full register, according to their
+                // own type, so widening them doesn't change the register.
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+                let src_reg = self.storage_manager.load_to_general_reg(&mut self.buf, src);
+                ASM::mov_reg64_reg64(&mut self.buf, dst_reg, src_reg);
+            }
+            (source, target) => todo!("NumIntCast: from {:?} to {:?}", source, target),
+        }
+    }
+
     fn build_num_to_frac(
         &mut self,
         dst: &Symbol,
@@ -1341,16 +1495,19 @@ impl<
         match (lit, layout) {
             (
                 Literal::Int(x),
-                Layout::Builtin(Builtin::Int(
-                    IntWidth::U8
-                    | IntWidth::U16
-                    | IntWidth::U32
-                    | IntWidth::U64
-                    | IntWidth::I8
-                    | IntWidth::I16
-                    | IntWidth::I32
-                    | IntWidth::I64,
-                )),
+                Layout::Builtin(
+                    Builtin::Int(
+                        IntWidth::U8
+                        | IntWidth::U16
+                        | IntWidth::U32
+                        | IntWidth::U64
+                        | IntWidth::I8
+                        | IntWidth::I16
+                        | IntWidth::I32
+                        | IntWidth::I64,
+                    )
+                    | Builtin::Bool,
+                ),
             ) => {
                 let reg = self.storage_manager.claim_general_reg(&mut self.buf, sym);
                 let val = *x;
@@ -1453,6 +1610,560 @@ impl<
         CC: CallConv<GeneralReg, FloatReg, ASM>,
     > Backend64Bit<'a, GeneralReg, FloatReg, ASM, CC>
 {
+    /// Creates a symbol for a value that only lives in the failure path of an expect, or in a dbg.
+    fn create_symbol(&mut self, debug_name: &str) -> Symbol {
+        let ident_ids = self
+            .interns
+            .all_ident_ids
+            .get_mut(&self.env.module_id)
+            .unwrap();
+
+        let ident_id = ident_ids.add_str(debug_name);
+        Symbol::new(self.env.module_id, ident_id)
+    }
+
+    /// Stores `src + imm` in a new symbol.
+    fn add_imm_to_new_symbol(&mut self, debug_name: &str, src: &Symbol, imm: i32) -> Symbol {
+        let dst = self.create_symbol(debug_name);
+        let src_reg = self.storage_manager.load_to_general_reg(&mut self.buf, src);
+        let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, &dst);
+        ASM::add_reg64_reg64_imm32(&mut self.buf, dst_reg, src_reg, imm);
+        dst
+    }
+
+    /// Stores `src1 + src2` in a new symbol.
+    fn add_to_new_symbol(&mut self, debug_name: &str, src1: &Symbol, src2: &Symbol) -> Symbol {
+        let dst = self.create_symbol(debug_name);
+        let src1_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, src1);
+        let src2_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, src2);
+        let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, &dst);
+        ASM::add_reg64_reg64_reg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
+        dst
+    }
+
+    /// Copies `size` bytes from `src` to `dst` with `roc_memcpy`.
+    fn build_memcpy(&mut self, dst: Symbol, src: Symbol, size: Symbol) {
+        let u64_layout = Layout::Builtin(Builtin::Int(IntWidth::U64));
+        let ignored = self.create_symbol("memcpy_result");
+        self.build_fn_call(
+            &ignored,
+            "roc_memcpy".to_string(),
+            &[dst, src, size],
+            &[u64_layout; 3],
+            &u64_layout,
+        );
+    }
+
+    /// Calls a zig function that takes no arguments and returns nothing.
+    fn build_void_fn_call(&mut self, debug_name: &str, fn_name: &str) {
+        let u64_layout = Layout::Builtin(Builtin::Int(IntWidth::U64));
+        let ignored = self.create_symbol(debug_name);
+        self.build_fn_call(&ignored, fn_name.to_string(), &[], &[], &u64_layout);
+    }
+
+    /// Writes a frame for a failed expect or a dbg into the shared expect buffer.
+    /// The frame is the region and module of the expect, then the offset of each lookup,
+    /// then the lookups themselves, with pointers made relative to the start of the buffer.
+    /// This is the layout `roc_repl_expect` reads back.
+    fn build_expect_frame(
+        &mut self,
+        module_id: ModuleId,
+        region: Region,
+        lookups: &'a [Symbol],
+        layouts: &'a [Layout<'a>],
+    ) {
+        let u64_layout = Layout::Builtin(Builtin::Int(IntWidth::U64));
+        let target_info = self.storage_manager.target_info();
+
+        let start_function = if self.env.mode.runs_expects_in_separate_process() {
+            bitcode::UTILS_EXPECT_FAILED_START_SHARED_FILE
+        } else {
+            bitcode::UTILS_EXPECT_FAILED_START
+        };
+
+        let buffer = self.create_symbol("expect_buffer");
+        self.build_fn_call(&buffer, start_function.to_string(), &[], &[], &u64_layout);
+
+        // The buffer starts with the number of frames and the offset of its first free byte.
+        // That is where this frame goes.
+        let frame = self.create_symbol("expect_frame");
+        let buffer_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, &buffer);
+        let frame_reg = self
+            .storage_manager
+            .claim_general_reg(&mut self.buf, &frame);
+        ASM::mov_reg64_mem64_offset32(&mut self.buf, frame_reg, buffer_reg, 8);
+
+        // The header is the region followed by the module id, 12 bytes in total.
+        // Both stores are 8 bytes wide. The extra 4 bytes are overwritten by what comes next.
+        let region_bits =
+            (region.start().offset as u64 | (region.end().offset as u64) << 32) as i64;
+        let module_id: u32 = unsafe { std::mem::transmute(module_id) };
+        self.storage_manager.with_tmp_general_reg(
+            &mut self.buf,
+            |storage_manager, buf, frame_ptr| {
+                ASM::add_reg64_reg64_reg64(buf, frame_ptr, buffer_reg, frame_reg);
+                storage_manager.with_tmp_general_reg(buf, |_storage_manager, buf, tmp| {
+                    ASM::mov_reg64_imm64(buf, tmp, region_bits);
+                    ASM::mov_mem64_offset32_reg64(buf, frame_ptr, 0, tmp);
+                    ASM::mov_reg64_imm64(buf, tmp, module_id as i64);
+                    ASM::mov_mem64_offset32_reg64(buf, frame_ptr, 8, tmp);
+                });
+            },
+        );
+
+        let offsets_start = 12;
+        let mut offset = self.add_imm_to_new_symbol(
+            "expect_offset",
+            &frame,
+            offsets_start + 8 * lookups.len() as i32,
+        );
+
+        for (i, (lookup, layout)) in lookups.iter().zip(layouts.iter()).enumerate() {
+            // Record where this lookup starts.
+            let buffer_reg = self
+                .storage_manager
+                .load_to_general_reg(&mut self.buf, &buffer);
+            let frame_reg = self
+                .storage_manager
+                .load_to_general_reg(&mut self.buf, &frame);
+            let offset_reg = self
+                .storage_manager
+                .load_to_general_reg(&mut self.buf, &offset);
+            self.storage_manager.with_tmp_general_reg(
+                &mut self.buf,
+                |_storage_manager, buf, frame_ptr| {
+                    ASM::add_reg64_reg64_reg64(buf, frame_ptr, buffer_reg, frame_reg);
+                    ASM::mov_mem64_offset32_reg64(
+                        buf,
+                        frame_ptr,
+                        offsets_start + 8 * i as i32,
+                        offset_reg,
+                    );
+                },
+            );
+
+            // Anything the lookup points to goes right after it.
+            let stack_size = layout.stack_size(target_info);
+            let extra = self.add_imm_to_new_symbol("expect_extra", &offset, stack_size as i32);
+
+            offset = if stack_size == 0 {
+                extra
+            } else {
+                self.storage_manager
+                    .ensure_symbol_on_stack(&mut self.buf, lookup);
+                let (src, _) = self.storage_manager.stack_offset_and_size(lookup);
+                self.build_expect_clone(&buffer, &offset, 0, extra, src, layout)
+            };
+        }
+
+        // Count this frame and move the first free byte past it.
+        let buffer_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, &buffer);
+        let offset_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, &offset);
+        ASM::mov_mem64_offset32_reg64(&mut self.buf, buffer_reg, 8, offset_reg);
+        self.storage_manager
+            .with_tmp_general_reg(&mut self.buf, |_storage_manager, buf, tmp| {
+                ASM::mov_reg64_mem64_offset32(buf, tmp, buffer_reg, 0);
+                ASM::add_reg64_reg64_imm32(buf, tmp, tmp, 1);
+                ASM::mov_mem64_offset32_reg64(buf, buffer_reg, 0, tmp);
+            });
+    }
+
+    /// Clones the value at `src` in the stack to `offset + delta` in the expect buffer.
+    /// Anything the value points to is written at `extra`.
+    /// Returns a symbol with the offset of the first free byte after the clone.
+    fn build_expect_clone(
+        &mut self,
+        buffer: &Symbol,
+        offset: &Symbol,
+        delta: i32,
+        extra: Symbol,
+        src: i32,
+        layout: &Layout<'a>,
+    ) -> Symbol {
+        let u64_layout = Layout::Builtin(Builtin::Int(IntWidth::U64));
+        let target_info = self.storage_manager.target_info();
+        let stack_size = layout.stack_size(target_info);
+
+        match layout {
+            _ if stack_size == 0 => extra,
+            _ if layout.safe_to_memcpy() => {
+                self.build_expect_copy(buffer, offset, delta, src, stack_size);
+                extra
+            }
+            Layout::Builtin(Builtin::Str) => {
+                let string = self.create_symbol("expect_str");
+                self.storage_manager
+                    .complex_stack_arg(&string, src, stack_size);
+
+                let at = if delta == 0 {
+                    *offset
+                } else {
+                    self.add_imm_to_new_symbol("expect_at", offset, delta)
+                };
+
+                let new_extra = self.create_symbol("expect_extra");
+                self.build_fn_call(
+                    &new_extra,
+                    bitcode::STR_CLONE_TO.to_string(),
+                    &[string, *buffer, at, extra],
+                    &[*layout, u64_layout, u64_layout, u64_layout],
+                    &u64_layout,
+                );
+                new_extra
+            }
+            Layout::Builtin(Builtin::List(elem_layout)) if elem_layout.safe_to_memcpy() => {
+                let size =
+                    self.build_expect_list_header(buffer, offset, delta, &extra, src, elem_layout);
+
+                let elements = self.create_symbol("expect_elements");
+                let elements_reg = self
+                    .storage_manager
+                    .claim_general_reg(&mut self.buf, &elements);
+                ASM::mov_reg64_base32(&mut self.buf, elements_reg, src);
+
+                let dst = self.add_to_new_symbol("expect_dst", buffer, &extra);
+                self.build_memcpy(dst, elements, size);
+                self.add_to_new_symbol("expect_extra", &extra, &size)
+            }
+            Layout::Builtin(Builtin::List(elem_layout)) => {
+                self.build_expect_clone_elements(buffer, offset, delta, extra, src, elem_layout)
+            }
+            Layout::Struct { field_layouts, .. } => {
+                let mut extra = extra;
+                let mut field_offset = 0;
+                for field_layout in field_layouts.iter() {
+                    extra = self.build_expect_clone(
+                        buffer,
+                        offset,
+                        delta + field_offset,
+                        extra,
+                        src + field_offset,
+                        field_layout,
+                    );
+                    field_offset += field_layout.stack_size(target_info) as i32;
+                }
+                extra
+            }
+            Layout::Union(union_layout @ UnionLayout::NonRecursive(tags)) => {
+                // Copy the whole union, so the tag id and the plain fields are in place.
+                // Then clone the fields of the active tag, which fixes up everything they point to.
+                self.build_expect_copy(buffer, offset, delta, src, stack_size);
+
+                let (data_size, data_alignment) = union_layout.data_size_and_alignment(target_info);
+                let id_offset = src + (data_size - data_alignment) as i32;
+                let id_size = union_layout.discriminant().stack_size() as u8;
+
+                let extra_slot = self.claim_clone_slot("expect_extra_slot");
+                self.store_to_slot(extra_slot, &extra);
+
+                // Every branch starts with all symbols on the stack, so they agree where they meet.
+                self.storage_manager.free_all_to_stack(&mut self.buf);
+                let base_storage = self.storage_manager.clone();
+                let mut max_branch_stack_size = base_storage.stack_size();
+                let mut fn_call_stack_size = base_storage.fn_call_stack_size();
+                let mut end_jumps = bumpalo::vec![in self.env.arena];
+                let mut tmp = bumpalo::vec![in self.env.arena];
+
+                for (tag_id, field_layouts) in tags.iter().enumerate() {
+                    let fields = Layout::struct_no_name_order(*field_layouts);
+                    if fields.safe_to_memcpy() {
+                        continue;
+                    }
+
+                    self.storage_manager = base_storage.clone();
+
+                    // Skip this branch if it is not the active tag.
+                    // Since we don't know the offset yet, set it to 0 and overwrite later.
+                    let id = self.create_symbol("expect_tag_id");
+                    let id_reg = self.storage_manager.claim_general_reg(&mut self.buf, &id);
+                    ASM::movzx_reg64_base32(&mut self.buf, id_reg, id_offset, id_size);
+                    let jne_location = self.buf.len();
+                    let start_offset =
+                        ASM::jne_reg64_imm64_imm32(&mut self.buf, id_reg, tag_id as u64, 0);
+
+                    let extra = self.load_from_slot("expect_extra", extra_slot);
+                    let extra = self.build_expect_clone(buffer, offset, delta, extra, src, &fields);
+                    self.store_to_slot(extra_slot, &extra);
+
+                    // Since we don't know the offset yet, set it to 0 and overwrite later.
+                    let jmp_location = self.buf.len();
+                    let jmp_offset = ASM::jmp_imm32(&mut self.buf, 0x1234_5678);
+                    end_jumps.push((jmp_location, jmp_offset));
+
+                    tmp.clear();
+                    let jne_offset = self.buf.len() - start_offset;
+                    ASM::jne_reg64_imm64_imm32(&mut tmp, id_reg, tag_id as u64, jne_offset as i32);
+                    for (i, byte) in tmp.iter().enumerate() {
+                        self.buf[jne_location + i] = *byte;
+                    }
+
+                    max_branch_stack_size =
+                        std::cmp::max(max_branch_stack_size, self.storage_manager.stack_size());
+                    fn_call_stack_size = std::cmp::max(
+                        fn_call_stack_size,
+                        self.storage_manager.fn_call_stack_size(),
+                    );
+                }
+
+                self.storage_manager = base_storage;
+                self.storage_manager
+                    .update_stack_size(max_branch_stack_size);
+                self.storage_manager
+                    .update_fn_call_stack_size(fn_call_stack_size);
+
+                let end_offset = self.buf.len();
+                for (jmp_location, start_offset) in end_jumps.into_iter() {
+                    self.update_jmp_imm32_offset(
+                        &mut tmp,
+                        jmp_location as u64,
+                        start_offset as u64,
+                        end_offset as u64,
+                    );
+                }
+
+                self.load_from_slot("expect_extra", extra_slot)
+            }
+            Layout::LambdaSet(lambda_set) => self.build_expect_clone(
+                buffer,
+                offset,
+                delta,
+                extra,
+                src,
+                &lambda_set.runtime_representation(),
+            ),
+            Layout::Builtin(builtin) => {
+                internal_error!("{:?} is safe to memcpy, so it was copied above", builtin)
+            }
+            Layout::Union(_) | Layout::Boxed(_) | Layout::RecursivePointer => {
+                // These only live on the heap, and the dev backend can't build them yet.
+                internal_error!(
+                    "a value with layout {:?} can't exist in the dev backend",
+                    layout
+                )
+            }
+        }
+    }
+
+    /// Copies the `size` bytes at `src` in the stack to `offset + delta` in the expect buffer.
+    fn build_expect_copy(
+        &mut self,
+        buffer: &Symbol,
+        offset: &Symbol,
+        delta: i32,
+        src: i32,
+        size: u32,
+    ) {
+        let dst = self.add_to_new_symbol("expect_dst", buffer, offset);
+        let dst = self.add_imm_to_new_symbol("expect_dst", &dst, delta);
+
+        let src_ptr = self.create_symbol("expect_src");
+        let src_ptr_reg = self
+            .storage_manager
+            .claim_general_reg(&mut self.buf, &src_ptr);
+        ASM::add_reg64_reg64_imm32(&mut self.buf, src_ptr_reg, CC::BASE_PTR_REG, src);
+
+        let size_sym = self.create_symbol("expect_size");
+        let size_reg = self
+            .storage_manager
+            .claim_general_reg(&mut self.buf, &size_sym);
+        ASM::mov_reg64_imm64(&mut self.buf, size_reg, size as i64);
+
+        self.build_memcpy(dst, src_ptr, size_sym);
+    }
+
+    /// Writes the list at `src` in the stack to `offset + delta` in the expect buffer, pointing
+    /// at `extra` for its elements. The clone has no spare capacity.
+    /// Returns a symbol with the size of the elements in bytes.
+    fn build_expect_list_header(
+        &mut self,
+        buffer: &Symbol,
+        offset: &Symbol,
+        delta: i32,
+        extra: &Symbol,
+        src: i32,
+        elem_layout: &Layout<'a>,
+    ) -> Symbol {
+        let buffer_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, buffer);
+        let offset_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, offset);
+        let extra_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, extra);
+
+        let size = self.create_symbol("expect_size");
+        let size_reg = self.storage_manager.claim_general_reg(&mut self.buf, &size);
+        let elem_size = elem_layout.stack_size(self.storage_manager.target_info()) as i64;
+        self.storage_manager.with_tmp_general_reg(
+            &mut self.buf,
+            |storage_manager, buf, list_ptr| {
+                ASM::add_reg64_reg64_reg64(buf, list_ptr, buffer_reg, offset_reg);
+                ASM::mov_mem64_offset32_reg64(buf, list_ptr, delta, extra_reg);
+                storage_manager.with_tmp_general_reg(buf, |_storage_manager, buf, len| {
+                    ASM::mov_reg64_base32(buf, len, src + 8);
+                    ASM::mov_mem64_offset32_reg64(buf, list_ptr, delta + 8, len);
+                    ASM::mov_mem64_offset32_reg64(buf, list_ptr, delta + 16, len);
+                    ASM::mov_reg64_imm64(buf, size_reg, elem_size);
+                    ASM::imul_reg64_reg64_reg64(buf, size_reg, size_reg, len);
+                });
+            },
+        );
+
+        size
+    }
+
+    /// Clones the list at `src` in the stack, whose elements point to more data, one element at
+    /// a time. Returns a symbol with the offset of the first free byte after the clone.
+    fn build_expect_clone_elements(
+        &mut self,
+        buffer: &Symbol,
+        offset: &Symbol,
+        delta: i32,
+        extra: Symbol,
+        src: i32,
+        elem_layout: &Layout<'a>,
+    ) -> Symbol {
+        let elem_size = elem_layout.stack_size(self.storage_manager.target_info()) as i32;
+        // Anything with a pointer in it is aligned to 8 bytes, so it can be copied 8 bytes at a time.
+        debug_assert!(elem_size % 8 == 0);
+
+        let size = self.build_expect_list_header(buffer, offset, delta, &extra, src, elem_layout);
+
+        // The loop keeps its state on the stack: where the next element is read from and written
+        // to, where the data it points to goes, and how many elements are left.
+        let src_slot = self.claim_clone_slot("expect_src_slot");
+        let dst_slot = self.claim_clone_slot("expect_dst_slot");
+        let extra_slot = self.claim_clone_slot("expect_extra_slot");
+        let remaining_slot = self.claim_clone_slot("expect_remaining_slot");
+
+        let elements_end = self.add_to_new_symbol("expect_extra", &extra, &size);
+        self.store_to_slot(dst_slot, &extra);
+        self.store_to_slot(extra_slot, &elements_end);
+        self.storage_manager
+            .with_tmp_general_reg(&mut self.buf, |_storage_manager, buf, tmp| {
+                ASM::mov_reg64_base32(buf, tmp, src);
+                ASM::mov_base32_reg64(buf, src_slot, tmp);
+                ASM::mov_reg64_base32(buf, tmp, src + 8);
+                ASM::mov_base32_reg64(buf, remaining_slot, tmp);
+            });
+
+        // Every iteration starts with all symbols on the stack, so they agree where they meet.
+        self.storage_manager.free_all_to_stack(&mut self.buf);
+        let base_storage = self.storage_manager.clone();
+        let mut tmp = bumpalo::vec![in self.env.arena];
+
+        // Leave the loop once no elements are left.
+        let loop_start = self.buf.len();
+        let remaining = self.load_from_slot("expect_remaining", remaining_slot);
+        let remaining_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, &remaining);
+        let jne_location = self.buf.len();
+        let jne_start_offset = ASM::jne_reg64_imm64_imm32(&mut self.buf, remaining_reg, 0, 0);
+        // Since we don't know the offset yet, set it to 0 and overwrite later.
+        let exit_location = self.buf.len();
+        let exit_offset = ASM::jmp_imm32(&mut self.buf, 0x1234_5678);
+
+        let jne_offset = self.buf.len() - jne_start_offset;
+        ASM::jne_reg64_imm64_imm32(&mut tmp, remaining_reg, 0, jne_offset as i32);
+        for (i, byte) in tmp.iter().enumerate() {
+            self.buf[jne_location + i] = *byte;
+        }
+
+        // The element is on the heap, but cloning reads from the stack. Copy it over first.
+        let element = self.create_symbol("expect_element");
+        let element_offset = self
+            .storage_manager
+            .claim_stack_area(&element, elem_size as u32);
+        let src_ptr = self.load_from_slot("expect_src", src_slot);
+        let src_ptr_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, &src_ptr);
+        self.storage_manager
+            .with_tmp_general_reg(&mut self.buf, |_storage_manager, buf, tmp| {
+                for i in (0..elem_size).step_by(8) {
+                    ASM::mov_reg64_mem64_offset32(buf, tmp, src_ptr_reg, i);
+                    ASM::mov_base32_reg64(buf, element_offset + i, tmp);
+                }
+            });
+
+        let dst = self.load_from_slot("expect_dst", dst_slot);
+        let extra = self.load_from_slot("expect_extra", extra_slot);
+        let extra = self.build_expect_clone(buffer, &dst, 0, extra, element_offset, elem_layout);
+        self.store_to_slot(extra_slot, &extra);
+
+        // Move on to the next element.
+        self.storage_manager
+            .with_tmp_general_reg(&mut self.buf, |_storage_manager, buf, tmp| {
+                for slot in [src_slot, dst_slot] {
+                    ASM::mov_reg64_base32(buf, tmp, slot);
+                    ASM::add_reg64_reg64_imm32(buf, tmp, tmp, elem_size);
+                    ASM::mov_base32_reg64(buf, slot, tmp);
+                }
+                ASM::mov_reg64_base32(buf, tmp, remaining_slot);
+                ASM::sub_reg64_reg64_imm32(buf, tmp, tmp, 1);
+                ASM::mov_base32_reg64(buf, remaining_slot, tmp);
+            });
+
+        let jmp_location = self.buf.len();
+        let jmp_offset = ASM::jmp_imm32(&mut self.buf, 0x1234_5678);
+        self.update_jmp_imm32_offset(
+            &mut tmp,
+            jmp_location as u64,
+            jmp_offset as u64,
+            loop_start as u64,
+        );
+
+        let loop_end = self.buf.len();
+        self.update_jmp_imm32_offset(
+            &mut tmp,
+            exit_location as u64,
+            exit_offset as u64,
+            loop_end as u64,
+        );
+
+        // Nothing stored by the loop is used after it, but it still needs the stack space.
+        let stack_size = self.storage_manager.stack_size();
+        let fn_call_stack_size = self.storage_manager.fn_call_stack_size();
+        self.storage_manager = base_storage;
+        self.storage_manager.update_stack_size(stack_size);
+        self.storage_manager
+            .update_fn_call_stack_size(fn_call_stack_size);
+
+        self.load_from_slot("expect_extra", extra_slot)
+    }
+
+    /// Claims 8 bytes of stack for a value that a clone keeps across branches or loop iterations.
+    fn claim_clone_slot(&mut self, debug_name: &str) -> i32 {
+        let slot = self.create_symbol(debug_name);
+        self.storage_manager.claim_stack_area(&slot, 8)
+    }
+
+    fn store_to_slot(&mut self, slot: i32, sym: &Symbol) {
+        let reg = self.storage_manager.load_to_general_reg(&mut self.buf, sym);
+        ASM::mov_base32_reg64(&mut self.buf, slot, reg);
+    }
+
+    /// Loads the value in a slot into a new symbol.
+    fn load_from_slot(&mut self, debug_name: &str, slot: i32) -> Symbol {
+        let sym = self.create_symbol(debug_name);
+        let reg = self.storage_manager.claim_general_reg(&mut self.buf, &sym);
+        ASM::mov_reg64_base32(&mut self.buf, reg, slot);
+        sym
+    }
+
     /// Updates a jump instruction to a new offset and returns the number of bytes written.
     fn update_jmp_imm32_offset(
         &mut self,
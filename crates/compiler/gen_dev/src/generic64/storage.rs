@@ -946,6 +946,15 @@ impl<
         self.allocation_map.insert(*sym, Rc::new((base_offset, 8)));
     }
 
+    /// Claims a new 8 byte stack slot for a primitive argument.
+    /// Used when the argument has to be copied before it can be loaded like other primitives.
+    /// It returns base pointer relative offset of the slot.
+    pub fn claim_primitive_stack_arg(&mut self, sym: &Symbol) -> i32 {
+        let base_offset = self.claim_stack_size(8);
+        self.primitive_stack_arg(sym, base_offset);
+        base_offset
+    }
+
     /// Specifies a complex is loaded at the specific base offset.
     pub fn complex_stack_arg(&mut self, sym: &Symbol, base_offset: i32, size: u32) {
         self.symbol_storage_map
@@ -7,7 +7,7 @@ use bumpalo::collections::Vec;
 use roc_builtins::bitcode::{FloatWidth, IntWidth};
 use roc_error_macros::internal_error;
 use roc_module::symbol::Symbol;
-use roc_mono::layout::{Builtin, Layout};
+use roc_mono::layout::{Builtin, Layout, UnionLayout};
 use roc_target::TargetInfo;
 
 const TARGET_INFO: TargetInfo = TargetInfo::default_x86_64();
@@ -252,7 +252,7 @@ impl CallConv<X86_64GeneralReg, X86_64FloatReg, X86_64Assembler> for X86_64Syste
 
     #[inline(always)]
     fn load_args<'a>(
-        _buf: &mut Vec<'a, u8>,
+        buf: &mut Vec<'a, u8>,
         storage_manager: &mut StorageManager<
             'a,
             X86_64GeneralReg,
@@ -299,6 +299,27 @@ impl CallConv<X86_64GeneralReg, X86_64FloatReg, X86_64Assembler> for X86_64Syste
                     storage_manager.complex_stack_arg(sym, arg_offset, stack_size);
                     arg_offset += stack_size as i32;
                 }
+                _ if !contains_floats(layout) => {
+                    // Small structs of integers are passed in one or two general registers.
+                    let count = (stack_size as usize + 7) / 8;
+                    if general_i + count <= Self::GENERAL_PARAM_REGS.len() {
+                        let base_offset = storage_manager.claim_stack_area(sym, stack_size);
+                        for (i, reg) in Self::GENERAL_PARAM_REGS[general_i..general_i + count]
+                            .iter()
+                            .enumerate()
+                        {
+                            X86_64Assembler::mov_base32_reg64(
+                                buf,
+                                base_offset + 8 * i as i32,
+                                *reg,
+                            );
+                        }
+                        general_i += count;
+                    } else {
+                        storage_manager.complex_stack_arg(sym, arg_offset, stack_size);
+                        arg_offset += 8 * count as i32;
+                    }
+                }
                 x => {
                     todo!("Loading args with layout {:?}", x);
                 }
@@ -407,6 +428,40 @@ impl CallConv<X86_64GeneralReg, X86_64FloatReg, X86_64Assembler> for X86_64Syste
                     }
                     tmp_stack_offset += size as i32;
                 }
+                x if !contains_floats(x) => {
+                    // Small structs of integers are passed in one or two general registers.
+                    let (base_offset, size) = storage_manager.stack_offset_and_size(sym);
+                    debug_assert_eq!(base_offset % 8, 0);
+                    let count = (size as usize + 7) / 8;
+                    if general_i + count <= Self::GENERAL_PARAM_REGS.len() {
+                        for (i, reg) in Self::GENERAL_PARAM_REGS[general_i..general_i + count]
+                            .iter()
+                            .enumerate()
+                        {
+                            X86_64Assembler::mov_reg64_base32(
+                                buf,
+                                *reg,
+                                base_offset + 8 * i as i32,
+                            );
+                        }
+                        general_i += count;
+                    } else {
+                        // Copy to stack using return reg as buffer.
+                        for i in 0..count as i32 {
+                            X86_64Assembler::mov_reg64_base32(
+                                buf,
+                                Self::GENERAL_RETURN_REGS[0],
+                                base_offset + 8 * i,
+                            );
+                            X86_64Assembler::mov_stack32_reg64(
+                                buf,
+                                tmp_stack_offset + 8 * i,
+                                Self::GENERAL_RETURN_REGS[0],
+                            );
+                        }
+                        tmp_stack_offset += 8 * count as i32;
+                    }
+                }
                 x => {
                     todo!("calling with arg type, {:?}", x);
                 }
@@ -816,6 +871,19 @@ impl X86_64WindowsFastcall {
     }
 }
 
+/// Returns true if any part of the layout would be passed in a float register.
+/// Nested structs and non-recursive unions are checked field by field.
+fn contains_floats(layout: &Layout) -> bool {
+    match layout {
+        Layout::Builtin(Builtin::Float(_)) => true,
+        Layout::Struct { field_layouts, .. } => field_layouts.iter().any(contains_floats),
+        Layout::Union(UnionLayout::NonRecursive(tags)) => {
+            tags.iter().any(|fields| fields.iter().any(contains_floats))
+        }
+        _ => false,
+    }
+}
+
 #[inline(always)]
 fn x86_64_generic_setup_stack<'a>(
     buf: &mut Vec<'a, u8>,
@@ -1239,6 +1307,40 @@ impl Assembler<X86_64GeneralReg, X86_64FloatReg> for X86_64Assembler {
         sub_reg64_reg64(buf, dst, src2);
     }
 
+    #[inline(always)]
+    fn and_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        if dst == src1 {
+            and_reg64_reg64(buf, dst, src2);
+        } else if dst == src2 {
+            and_reg64_reg64(buf, dst, src1);
+        } else {
+            mov_reg64_reg64(buf, dst, src1);
+            and_reg64_reg64(buf, dst, src2);
+        }
+    }
+
+    #[inline(always)]
+    fn or_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        if dst == src1 {
+            or_reg64_reg64(buf, dst, src2);
+        } else if dst == src2 {
+            or_reg64_reg64(buf, dst, src1);
+        } else {
+            mov_reg64_reg64(buf, dst, src1);
+            or_reg64_reg64(buf, dst, src2);
+        }
+    }
+
     #[inline(always)]
     fn eq_reg64_reg64_reg64(
         buf: &mut Vec<'_, u8>,
@@ -1530,6 +1632,12 @@ fn and_reg64_imm8(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, imm: i8) {
 }
 
 /// `CMOVL r64,r/m64` -> Move if less (SF≠ OF).
+/// `AND r/m64,r64` -> Bitwise logical and r64 to r/m64.
+#[inline(always)]
+fn and_reg64_reg64(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, src: X86_64GeneralReg) {
+    binop_reg64_reg64(0x21, buf, dst, src);
+}
+
 #[inline(always)]
 fn cmovl_reg64_reg64(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, src: X86_64GeneralReg) {
     let rex = add_reg_extension(dst, REX_W);
@@ -1851,6 +1959,12 @@ fn neg_reg64(buf: &mut Vec<'_, u8>, reg: X86_64GeneralReg) {
     buf.extend(&[rex, 0xF7, 0xD8 | reg_mod]);
 }
 
+/// `OR r/m64,r64` -> Bitwise logical or r64 to r/m64.
+#[inline(always)]
+fn or_reg64_reg64(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, src: X86_64GeneralReg) {
+    binop_reg64_reg64(0x09, buf, dst, src);
+}
+
 // helper function for `set*` instructions
 #[inline(always)]
 fn set_reg64_help(op_code: u8, buf: &mut Vec<'_, u8>, reg: X86_64GeneralReg) {
@@ -2124,6 +2238,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_and_reg64_reg64() {
+        disassembler_test!(
+            and_reg64_reg64,
+            |reg1, reg2| format!("and {}, {}", reg1, reg2),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_or_reg64_reg64() {
+        disassembler_test!(
+            or_reg64_reg64,
+            |reg1, reg2| format!("or {}, {}", reg1, reg2),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
     #[test]
     fn test_sub_reg64_reg64() {
         disassembler_test!(
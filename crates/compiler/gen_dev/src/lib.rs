@@ -15,6 +15,7 @@ use roc_mono::ir::{
     SelfRecursive, Stmt,
 };
 use roc_mono::layout::{Builtin, Layout, LayoutId, LayoutIds, TagIdIntType, UnionLayout};
+use roc_region::all::Region;
 
 mod generic64;
 mod object_builder;
@@ -27,6 +28,34 @@ pub struct Env<'a> {
     pub exposed_to_host: MutSet<Symbol>,
     pub lazy_literals: bool,
     pub generate_allocators: bool,
+    pub mode: AssemblyBackendMode,
+}
+
+/// Decides what happens to `expect` and `dbg`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssemblyBackendMode {
+    /// `expect` and `dbg` are skipped, e.g. for `roc build`
+    Binary,
+    /// The program is started by `roc dev` or `roc run`. Failed `expect`s and `dbg` values are
+    /// written to shared memory, and that `roc` process renders them.
+    BinaryDev,
+    /// Used by `roc test`. A failed `expect` writes its region and lookups into the buffer given
+    /// to `set_shared_buffer`, and `dbg` is skipped.
+    Test,
+}
+
+impl AssemblyBackendMode {
+    fn runs_expects(self) -> bool {
+        match self {
+            AssemblyBackendMode::Binary => false,
+            AssemblyBackendMode::BinaryDev => true,
+            AssemblyBackendMode::Test => true,
+        }
+    }
+
+    fn runs_expects_in_separate_process(self) -> bool {
+        matches!(self, AssemblyBackendMode::BinaryDev)
+    }
 }
 
 // These relocations likely will need a length.
@@ -206,6 +235,40 @@ trait Backend<'a> {
                 self.build_jump(id, args, arg_layouts.into_bump_slice(), ret_layout);
                 self.free_symbols(stmt);
             }
+            Stmt::Expect {
+                condition,
+                region,
+                lookups,
+                layouts,
+                remainder,
+            } => {
+                if self.env().mode.runs_expects() {
+                    self.load_literal_symbols(&[*condition]);
+                    self.load_literal_symbols(lookups);
+                    self.build_expect(condition, *region, lookups, layouts);
+                }
+                self.free_symbols(stmt);
+                self.build_stmt(remainder, ret_layout);
+            }
+            Stmt::Dbg {
+                symbol,
+                region,
+                remainder,
+            } => {
+                // like LLVM, `dbg` is only kept when a `roc` process renders the value for us
+                if self.env().mode.runs_expects_in_separate_process() {
+                    self.load_literal_symbols(&[*symbol]);
+                    let layout = match self.layout_map().get(symbol) {
+                        Some(layout) => *layout,
+                        None => {
+                            internal_error!("the dbg symbol, {:?}, has no known layout", symbol)
+                        }
+                    };
+                    self.build_dbg(symbol, *region, &layout);
+                }
+                self.free_symbols(stmt);
+                self.build_stmt(remainder, ret_layout);
+            }
             x => todo!("the statement, {:?}", x),
         }
     }
@@ -219,6 +282,20 @@ trait Backend<'a> {
         ret_layout: &Layout<'a>,
     );
 
+    // build_expect generates instructions that check an expect's condition,
+    // and record the failure in the shared expect buffer if it does not hold.
+    fn build_expect(
+        &mut self,
+        condition: &Symbol,
+        region: Region,
+        lookups: &'a [Symbol],
+        layouts: &'a [Layout<'a>],
+    );
+
+    // build_dbg generates instructions that write a value into the shared expect buffer
+    // and wait for the parent process to print it.
+    fn build_dbg(&mut self, symbol: &Symbol, region: Region, layout: &Layout<'a>);
+
     // build_join generates a instructions for a join statement.
     fn build_join(
         &mut self,
@@ -488,7 +565,20 @@ trait Backend<'a> {
                     *ret_layout,
                     "Eq: expected to have return layout of type Bool"
                 );
-                self.build_eq(sym, &args[0], &args[1], &arg_layouts[0])
+                match arg_layouts[0] {
+                    Layout::Builtin(Builtin::Str) => self.build_fn_call(
+                        sym,
+                        bitcode::STR_EQUAL.to_string(),
+                        args,
+                        arg_layouts,
+                        ret_layout,
+                    ),
+                    Layout::Builtin(Builtin::List(_))
+                    | Layout::Struct { .. }
+                    | Layout::Union(_)
+                    | Layout::Boxed(_) => self.build_eq_specialized(sym, args, &arg_layouts[0]),
+                    _ => self.build_eq(sym, &args[0], &args[1], &arg_layouts[0]),
+                }
             }
             LowLevel::NotEq => {
                 debug_assert_eq!(
@@ -505,7 +595,51 @@ trait Backend<'a> {
                     *ret_layout,
                     "NotEq: expected to have return layout of type Bool"
                 );
-                self.build_neq(sym, &args[0], &args[1], &arg_layouts[0])
+                match arg_layouts[0] {
+                    Layout::Builtin(Builtin::Str | Builtin::List(_))
+                    | Layout::Struct { .. }
+                    | Layout::Union(_)
+                    | Layout::Boxed(_) => {
+                        // These have no `!=` of their own, so negate their `==`.
+                        self.build_run_low_level(
+                            &Symbol::DEV_TMP,
+                            &LowLevel::Eq,
+                            args,
+                            arg_layouts,
+                            ret_layout,
+                        );
+                        self.build_not(sym, &Symbol::DEV_TMP, ret_layout);
+                        self.free_symbol(&Symbol::DEV_TMP)
+                    }
+                    _ => self.build_neq(sym, &args[0], &args[1], &arg_layouts[0]),
+                }
+            }
+            LowLevel::And => {
+                debug_assert_eq!(2, args.len(), "And: expected to have exactly two argument");
+                debug_assert_eq!(
+                    Layout::Builtin(Builtin::Bool),
+                    *ret_layout,
+                    "And: expected to have return layout of type Bool"
+                );
+                self.build_and(sym, &args[0], &args[1], &arg_layouts[0])
+            }
+            LowLevel::Or => {
+                debug_assert_eq!(2, args.len(), "Or: expected to have exactly two argument");
+                debug_assert_eq!(
+                    Layout::Builtin(Builtin::Bool),
+                    *ret_layout,
+                    "Or: expected to have return layout of type Bool"
+                );
+                self.build_or(sym, &args[0], &args[1], &arg_layouts[0])
+            }
+            LowLevel::Not => {
+                debug_assert_eq!(1, args.len(), "Not: expected to have exactly one argument");
+                debug_assert_eq!(
+                    Layout::Builtin(Builtin::Bool),
+                    *ret_layout,
+                    "Not: expected to have return layout of type Bool"
+                );
+                self.build_not(sym, &args[0], &arg_layouts[0])
             }
             LowLevel::NumLt => {
                 debug_assert_eq!(
@@ -540,6 +674,14 @@ trait Backend<'a> {
                 );
                 self.build_num_to_frac(sym, &args[0], &arg_layouts[0], ret_layout)
             }
+            LowLevel::NumIntCast => {
+                debug_assert_eq!(
+                    1,
+                    args.len(),
+                    "NumIntCast: expected to have exactly one argument"
+                );
+                self.build_num_int_cast(sym, &args[0], &arg_layouts[0], ret_layout)
+            }
             LowLevel::NumLte => {
                 debug_assert_eq!(
                     2,
@@ -714,9 +856,44 @@ trait Backend<'a> {
     /// build_neq stores the result of `src1 != src2` into dst.
     fn build_neq(&mut self, dst: &Symbol, src1: &Symbol, src2: &Symbol, arg_layout: &Layout<'a>);
 
+    /// build_eq_specialized stores the result of `args[0] == args[1]` into dst, for data structures
+    /// (not numbers or Str). It calls a helper proc, which is generated the first time it's needed.
+    fn build_eq_specialized(&mut self, dst: &Symbol, args: &'a [Symbol], arg_layout: &Layout<'a>) {
+        let (eq_expr, new_specializations) = {
+            let (env, interns, helper_proc_gen) = self.env_interns_helpers_mut();
+            let ident_ids = interns.all_ident_ids.get_mut(&env.module_id).unwrap();
+
+            helper_proc_gen.call_specialized_equals(ident_ids, arg_layout, args)
+        };
+
+        for spec in new_specializations.into_iter() {
+            self.helper_proc_symbols_mut().push(spec);
+        }
+
+        self.build_expr(dst, &eq_expr, &Layout::Builtin(Builtin::Bool))
+    }
+
+    /// build_and stores the result of `src1 && src2` into dst.
+    fn build_and(&mut self, dst: &Symbol, src1: &Symbol, src2: &Symbol, arg_layout: &Layout<'a>);
+
+    /// build_or stores the result of `src1 || src2` into dst.
+    fn build_or(&mut self, dst: &Symbol, src1: &Symbol, src2: &Symbol, arg_layout: &Layout<'a>);
+
+    /// build_not stores the result of `!src` into dst.
+    fn build_not(&mut self, dst: &Symbol, src: &Symbol, arg_layout: &Layout<'a>);
+
     /// build_num_lt stores the result of `src1 < src2` into dst.
     fn build_num_lt(&mut self, dst: &Symbol, src1: &Symbol, src2: &Symbol, arg_layout: &Layout<'a>);
 
+    /// build_num_int_cast converts the integer in src to the integer type of the target layout.
+    fn build_num_int_cast(
+        &mut self,
+        dst: &Symbol,
+        src: &Symbol,
+        source: &Layout<'a>,
+        target: &Layout<'a>,
+    );
+
     /// build_num_to_frac convert Number to Frac
     fn build_num_to_frac(
         &mut self,
@@ -1004,7 +1181,25 @@ trait Backend<'a> {
                 }
             }
 
-            Stmt::Expect { .. } => todo!("expect is not implemented in the wasm backend"),
+            Stmt::Expect {
+                condition,
+                lookups,
+                remainder,
+                ..
+            } => {
+                self.set_last_seen(*condition, stmt);
+                for sym in *lookups {
+                    self.set_last_seen(*sym, stmt);
+                }
+                self.scan_ast(remainder);
+            }
+
+            Stmt::Dbg {
+                symbol, remainder, ..
+            } => {
+                self.set_last_seen(*symbol, stmt);
+                self.scan_ast(remainder);
+            }
 
             Stmt::RuntimeError(_) => {}
         }
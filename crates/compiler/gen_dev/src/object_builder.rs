@@ -93,7 +93,7 @@ pub fn build_module<'a>(
                 aarch64::AArch64GeneralReg,
                 aarch64::AArch64FloatReg,
                 aarch64::AArch64Assembler,
-                aarch64::AArch64Call,
+                aarch64::AArch64AppleCall,
             >(env, TargetInfo::default_aarch64(), interns);
             build_object(
                 procedures,
@@ -144,14 +144,7 @@ fn generate_wrapper<'a, B: Backend<'a>>(
     };
     output.add_symbol(symbol);
     if let Some(sym_id) = output.symbol_id(name) {
-        let reloc = write::Relocation {
-            offset: offset + proc_offset,
-            size: 32,
-            kind: RelocationKind::PltRelative,
-            encoding: RelocationEncoding::X86Branch,
-            symbol: sym_id,
-            addend: -4,
-        };
+        let reloc = branch_relocation(output, offset + proc_offset, sym_id);
 
         match output.add_relocation(text_section, reloc) {
             Ok(obj) => obj,
@@ -162,6 +155,30 @@ fn generate_wrapper<'a, B: Backend<'a>>(
     }
 }
 
+/// The relocation for a call or tail call to `symbol` at `offset`.
+/// On x86_64 the offset is the 32 bit displacement after the opcode.
+/// On aarch64 it is the whole `B` or `BL` instruction.
+fn branch_relocation(output: &Object, offset: u64, symbol: SymbolId) -> write::Relocation {
+    match output.architecture() {
+        Architecture::Aarch64 => write::Relocation {
+            offset,
+            size: 26,
+            kind: RelocationKind::Relative,
+            encoding: RelocationEncoding::AArch64Call,
+            symbol,
+            addend: 0,
+        },
+        _ => write::Relocation {
+            offset,
+            size: 32,
+            kind: RelocationKind::PltRelative,
+            encoding: RelocationEncoding::X86Branch,
+            symbol,
+            addend: -4,
+        },
+    }
+}
+
 fn build_object<'a, B: Backend<'a>>(
     procedures: MutMap<(symbol::Symbol, ProcLayout<'a>), Proc<'a>>,
     mut backend: B,
@@ -206,6 +223,12 @@ fn build_object<'a, B: Backend<'a>>(
             "roc_panic".into(),
             "roc_builtins.utils.test_panic".into(),
         );
+        generate_wrapper(
+            &mut backend,
+            &mut output,
+            "roc_memcpy".into(),
+            "memcpy".into(),
+        );
     }
 
     // Setup layout_ids for procedure calls.
@@ -459,14 +482,7 @@ fn build_proc<'a, B: Backend<'a>>(
                     }
                 }
                 if let Some(sym_id) = output.symbol_id(name.as_bytes()) {
-                    write::Relocation {
-                        offset: offset + proc_offset,
-                        size: 32,
-                        kind: RelocationKind::PltRelative,
-                        encoding: RelocationEncoding::X86Branch,
-                        symbol: sym_id,
-                        addend: -4,
-                    }
+                    branch_relocation(output, offset + proc_offset, sym_id)
                 } else {
                     internal_error!("failed to find fn symbol for {:?}", name);
                 }
@@ -2,6 +2,7 @@ use crate::llvm::bitcode::{
     call_bitcode_fn, call_bitcode_fn_fixing_for_convention, call_list_bitcode_fn,
     call_str_bitcode_fn, call_void_bitcode_fn, pass_list_or_string_to_zig_32bit, BitcodeReturns,
 };
+use crate::llvm::build_hash::generic_hash;
 use crate::llvm::build_list::{
     self, allocate_list, empty_polymorphic_list, list_append_unsafe, list_capacity, list_concat,
     list_drop_at, list_get_unsafe, list_len, list_map, list_map2, list_map3, list_map4,
@@ -164,6 +165,9 @@ impl<'a, 'ctx> Scope<'a, 'ctx> {
 pub enum LlvmBackendMode {
     /// Assumes primitives (roc_alloc, roc_panic, etc) are provided by the host
     Binary,
+    /// Like `Binary`, but keeps `expect` and `dbg`, which are rendered by the `roc` process that
+    /// started the program
+    BinaryDev,
     /// Creates a test wrapper around the main roc function to catch and report panics.
     /// Provides a testing implementation of primitives (roc_alloc, roc_panic, etc)
     GenTest,
@@ -175,6 +179,7 @@ impl LlvmBackendMode {
     pub(crate) fn has_host(self) -> bool {
         match self {
             LlvmBackendMode::Binary => true,
+            LlvmBackendMode::BinaryDev => true,
             LlvmBackendMode::GenTest => false,
             LlvmBackendMode::WasmGenTest => true,
             LlvmBackendMode::CliTest => false,
@@ -185,6 +190,7 @@ impl LlvmBackendMode {
     fn returns_roc_result(self) -> bool {
         match self {
             LlvmBackendMode::Binary => false,
+            LlvmBackendMode::BinaryDev => false,
             LlvmBackendMode::GenTest => true,
             LlvmBackendMode::WasmGenTest => true,
             LlvmBackendMode::CliTest => true,
@@ -194,6 +200,7 @@ impl LlvmBackendMode {
     fn runs_expects(self) -> bool {
         match self {
             LlvmBackendMode::Binary => false,
+            LlvmBackendMode::BinaryDev => true,
             LlvmBackendMode::GenTest => false,
             LlvmBackendMode::WasmGenTest => false,
             LlvmBackendMode::CliTest => true,
@@ -201,7 +208,7 @@ impl LlvmBackendMode {
     }
 
     fn runs_expects_in_separate_process(self) -> bool {
-        false
+        matches!(self, LlvmBackendMode::BinaryDev)
     }
 }
 
@@ -2836,6 +2843,34 @@ pub fn build_exp_stmt<'a, 'ctx, 'env>(
             )
         }
 
+        Dbg {
+            symbol,
+            region,
+            remainder,
+        } => {
+            // in release builds, `dbg` is stripped and only the remainder is generated
+            if env.mode.runs_expects_in_separate_process() {
+                clone_to_shared_memory(env, scope, layout_ids, *symbol, *region, &[*symbol]);
+
+                // NOTE: signals to the parent process that there is a value to print
+                let func = env
+                    .module
+                    .get_function(bitcode::UTILS_NOTIFY_PARENT_DBG)
+                    .unwrap();
+
+                env.builder.build_call(func, &[], "call_notify_parent_dbg");
+            }
+
+            build_exp_stmt(
+                env,
+                layout_ids,
+                func_spec_solutions,
+                scope,
+                parent,
+                remainder,
+            )
+        }
+
         RuntimeError(error_msg) => {
             throw_exception(env, error_msg);
 
@@ -3784,7 +3819,7 @@ fn expose_function_to_host_help_c_abi<'a, 'ctx, 'env>(
             )
         }
 
-        LlvmBackendMode::Binary => {}
+        LlvmBackendMode::Binary | LlvmBackendMode::BinaryDev => {}
     }
 
     // a generic version that writes the result into a passed *u8 pointer
@@ -3835,7 +3870,9 @@ fn expose_function_to_host_help_c_abi<'a, 'ctx, 'env>(
             roc_result_type(env, roc_function.get_type().get_return_type().unwrap()).into()
         }
 
-        LlvmBackendMode::Binary => basic_type_from_layout(env, &return_layout),
+        LlvmBackendMode::Binary | LlvmBackendMode::BinaryDev => {
+            basic_type_from_layout(env, &return_layout)
+        }
     };
 
     let size: BasicValueEnum = return_type.size_of().unwrap().into();
@@ -4801,7 +4838,7 @@ pub fn build_proc<'a, 'ctx, 'env>(
                 GenTest | WasmGenTest | CliTest => {
                     /* no host, or exposing types is not supported */
                 }
-                Binary => {
+                Binary | BinaryDev => {
                     for (alias_name, (generated_function, top_level, layout)) in aliases.iter() {
                         expose_alias_to_host(
                             env,
@@ -6372,7 +6409,12 @@ fn run_low_level<'a, 'ctx, 'env>(
             BasicValueEnum::IntValue(bool_val)
         }
         Hash => {
-            unimplemented!()
+            debug_assert_eq!(args.len(), 2);
+
+            let (value, value_layout) = load_symbol_and_layout(scope, &args[0]);
+            let seed = load_symbol(scope, &args[1]).into_int_value();
+
+            generic_hash(env, layout_ids, seed, value, value_layout).into()
         }
 
         ListMap | ListMap2 | ListMap3 | ListMap4 | ListSortWith => {
@@ -0,0 +1,725 @@
+use crate::llvm::bitcode::{call_bitcode_fn, call_str_bitcode_fn, BitcodeReturns};
+use crate::llvm::build::{
+    entry_block_alloca_zerofill, get_tag_id, load_roc_value, tag_pointer_clear_tag_id,
+    use_roc_value, Env, FAST_CALL_CONV,
+};
+use crate::llvm::build_list::{list_len, load_list_ptr};
+use crate::llvm::convert::{argument_type_from_union_layout, basic_type_from_layout};
+use bumpalo::collections::Vec;
+use inkwell::types::BasicType;
+use inkwell::values::{BasicValueEnum, FunctionValue, IntValue, PointerValue, StructValue};
+use inkwell::{AddressSpace, IntPredicate};
+use roc_builtins::bitcode;
+use roc_module::symbol::Symbol;
+use roc_mono::layout::{Builtin, Layout, LayoutIds, TagIdIntType, UnionLayout};
+
+#[derive(Clone, Debug)]
+enum WhenRecursive<'a> {
+    Unreachable,
+    Loop(UnionLayout<'a>),
+}
+
+/// Structurally hash a value of any (non-function) layout, starting from `seed`.
+///
+/// Two values that are `==` produce the same hash. The hash is only stable within
+/// a single compiled program; it is not meant to be persisted.
+pub fn generic_hash<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    layout_ids: &mut LayoutIds<'a>,
+    seed: IntValue<'ctx>,
+    val: BasicValueEnum<'ctx>,
+    layout: &Layout<'a>,
+) -> IntValue<'ctx> {
+    build_hash_layout(
+        env,
+        layout_ids,
+        seed,
+        val,
+        layout,
+        WhenRecursive::Unreachable,
+    )
+}
+
+fn build_hash_layout<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    layout_ids: &mut LayoutIds<'a>,
+    seed: IntValue<'ctx>,
+    val: BasicValueEnum<'ctx>,
+    layout: &Layout<'a>,
+    when_recursive: WhenRecursive<'a>,
+) -> IntValue<'ctx> {
+    match layout {
+        Layout::Builtin(builtin) => {
+            hash_builtin(env, layout_ids, seed, val, layout, builtin, when_recursive)
+        }
+
+        Layout::Struct { field_layouts, .. } => build_hash_struct(
+            env,
+            layout_ids,
+            field_layouts,
+            when_recursive,
+            seed,
+            val.into_struct_value(),
+        ),
+
+        Layout::LambdaSet(_) => unreachable!("functions cannot be hashed"),
+
+        Layout::Union(union_layout) => {
+            build_hash_tag(env, layout_ids, when_recursive, union_layout, seed, val)
+        }
+
+        Layout::Boxed(inner_layout) => {
+            let box_ptr = val.into_pointer_value();
+            let inner = load_roc_value(env, **inner_layout, box_ptr, "load_box");
+
+            build_hash_layout(env, layout_ids, seed, inner, inner_layout, when_recursive)
+        }
+
+        Layout::RecursivePointer => match when_recursive {
+            WhenRecursive::Unreachable => {
+                unreachable!("recursion pointers should never be hashed directly")
+            }
+            WhenRecursive::Loop(union_layout) => {
+                let layout = Layout::Union(union_layout);
+
+                let bt = basic_type_from_layout(env, &layout);
+
+                // cast the i64 pointer to a pointer to block of memory
+                let field_cast = env
+                    .builder
+                    .build_bitcast(val, bt, "i64_to_opaque")
+                    .into_pointer_value();
+
+                build_hash_tag(
+                    env,
+                    layout_ids,
+                    WhenRecursive::Loop(union_layout),
+                    &union_layout,
+                    seed,
+                    field_cast.into(),
+                )
+            }
+        },
+    }
+}
+
+fn hash_builtin<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    layout_ids: &mut LayoutIds<'a>,
+    seed: IntValue<'ctx>,
+    val: BasicValueEnum<'ctx>,
+    layout: &Layout<'a>,
+    builtin: &Builtin<'a>,
+    when_recursive: WhenRecursive<'a>,
+) -> IntValue<'ctx> {
+    match builtin {
+        Builtin::Int(_) | Builtin::Float(_) | Builtin::Bool | Builtin::Decimal => {
+            hash_bitcode_fn(env, seed, val, layout)
+        }
+
+        Builtin::Str => call_str_bitcode_fn(
+            env,
+            &[val],
+            &[seed.into()],
+            BitcodeReturns::Basic,
+            bitcode::DICT_HASH_STR,
+        )
+        .into_int_value(),
+
+        Builtin::List(element_layout) => build_hash_list(
+            env,
+            layout_ids,
+            layout,
+            element_layout,
+            when_recursive,
+            seed,
+            val.into_struct_value(),
+        ),
+    }
+}
+
+/// Hash the bytes of a value that does not contain any pointers
+fn hash_bitcode_fn<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    seed: IntValue<'ctx>,
+    val: BasicValueEnum<'ctx>,
+    layout: &Layout<'a>,
+) -> IntValue<'ctx> {
+    let num_bytes = env
+        .ptr_int()
+        .const_int(layout.stack_size(env.target_info) as u64, false);
+
+    let basic_type = basic_type_from_layout(env, layout);
+    let alloca = entry_block_alloca_zerofill(env, basic_type, "hash_value");
+    env.builder.build_store(alloca, val);
+
+    let u8_ptr = env.builder.build_bitcast(
+        alloca,
+        env.context.i8_type().ptr_type(AddressSpace::Generic),
+        "as_u8_ptr",
+    );
+
+    call_bitcode_fn(
+        env,
+        &[seed.into(), u8_ptr, num_bytes.into()],
+        bitcode::DICT_HASH,
+    )
+    .into_int_value()
+}
+
+/// Hash a tag id or list length, so that e.g. `[[], [1]]` and `[[1], []]` hash differently
+fn hash_u64<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    seed: IntValue<'ctx>,
+    value: IntValue<'ctx>,
+) -> IntValue<'ctx> {
+    let as_u64 =
+        env.builder
+            .build_int_z_extend_or_bit_cast(value, env.context.i64_type(), "to_u64");
+
+    hash_bitcode_fn(env, seed, as_u64.into(), &Layout::u64())
+}
+
+fn set_debug_location_in<'a, 'ctx, 'env>(env: &Env<'a, 'ctx, 'env>, parent: FunctionValue<'ctx>) {
+    use inkwell::debug_info::AsDIScope;
+
+    let ctx = env.context;
+
+    let func_scope = parent.get_subprogram().unwrap();
+    let lexical_block = env.dibuilder.create_lexical_block(
+        /* scope */ func_scope.as_debug_info_scope(),
+        /* file */ env.compile_unit.get_file(),
+        /* line_no */ 0,
+        /* column_no */ 0,
+    );
+
+    let loc = env.dibuilder.create_debug_location(
+        ctx,
+        /* line */ 0,
+        /* column */ 0,
+        /* current_scope */ lexical_block.as_debug_info_scope(),
+        /* inlined_at */ None,
+    );
+    env.builder.set_current_debug_location(ctx, loc);
+}
+
+fn build_hash_struct<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    layout_ids: &mut LayoutIds<'a>,
+    field_layouts: &'a [Layout<'a>],
+    when_recursive: WhenRecursive<'a>,
+    seed: IntValue<'ctx>,
+    value: StructValue<'ctx>,
+) -> IntValue<'ctx> {
+    let mut seed = seed;
+
+    for (index, field_layout) in field_layouts.iter().enumerate() {
+        let field = env
+            .builder
+            .build_extract_value(value, index as u32, "hash_field")
+            .unwrap();
+
+        seed = if let Layout::RecursivePointer = field_layout {
+            match &when_recursive {
+                WhenRecursive::Unreachable => {
+                    unreachable!("The current layout should not be recursive, but is")
+                }
+                WhenRecursive::Loop(union_layout) => {
+                    let field_layout = Layout::Union(*union_layout);
+
+                    let bt = basic_type_from_layout(env, &field_layout);
+
+                    // cast the i64 pointer to a pointer to block of memory
+                    let field_cast = env
+                        .builder
+                        .build_bitcast(field, bt, "i64_to_opaque")
+                        .into_pointer_value();
+
+                    build_hash_layout(
+                        env,
+                        layout_ids,
+                        seed,
+                        field_cast.into(),
+                        &field_layout,
+                        WhenRecursive::Loop(*union_layout),
+                    )
+                }
+            }
+        } else {
+            build_hash_layout(
+                env,
+                layout_ids,
+                seed,
+                use_roc_value(env, *field_layout, field, "field"),
+                field_layout,
+                when_recursive.clone(),
+            )
+        };
+    }
+
+    seed
+}
+
+fn build_hash_list<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    layout_ids: &mut LayoutIds<'a>,
+    list_layout: &Layout<'a>,
+    element_layout: &Layout<'a>,
+    when_recursive: WhenRecursive<'a>,
+    seed: IntValue<'ctx>,
+    list: StructValue<'ctx>,
+) -> IntValue<'ctx> {
+    let block = env.builder.get_insert_block().expect("to be in a function");
+    let di_location = env.builder.get_current_debug_location().unwrap();
+
+    let symbol = Symbol::GENERIC_HASH;
+    let fn_name = layout_ids
+        .get(symbol, list_layout)
+        .to_symbol_string(symbol, &env.interns);
+
+    let function = match env.module.get_function(fn_name.as_str()) {
+        Some(function_value) => function_value,
+        None => {
+            let seed_type = env.context.i64_type();
+            let arg_type = basic_type_from_layout(env, list_layout);
+
+            let function_value = crate::llvm::refcounting::build_header_help(
+                env,
+                &fn_name,
+                seed_type.into(),
+                &[seed_type.into(), arg_type],
+            );
+
+            build_hash_list_help(
+                env,
+                layout_ids,
+                when_recursive,
+                function_value,
+                element_layout,
+            );
+
+            function_value
+        }
+    };
+
+    env.builder.position_at_end(block);
+    env.builder
+        .set_current_debug_location(env.context, di_location);
+    let call = env
+        .builder
+        .build_call(function, &[seed.into(), list.into()], "list_hash");
+
+    call.set_call_convention(FAST_CALL_CONV);
+
+    call.try_as_basic_value().left().unwrap().into_int_value()
+}
+
+fn build_hash_list_help<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    layout_ids: &mut LayoutIds<'a>,
+    when_recursive: WhenRecursive<'a>,
+    parent: FunctionValue<'ctx>,
+    element_layout: &Layout<'a>,
+) {
+    let ctx = env.context;
+    let builder = env.builder;
+
+    set_debug_location_in(env, parent);
+
+    // Add args to scope
+    let mut it = parent.get_param_iter();
+    let seed = it.next().unwrap().into_int_value();
+    let list = it.next().unwrap().into_struct_value();
+
+    seed.set_name(Symbol::ARG_1.as_str(&env.interns));
+    list.set_name(Symbol::ARG_2.as_str(&env.interns));
+
+    let entry = ctx.append_basic_block(parent, "entry");
+    builder.position_at_end(entry);
+
+    let len = list_len(builder, list);
+
+    let element_type = basic_type_from_layout(env, element_layout);
+    let ptr_type = element_type.ptr_type(AddressSpace::Generic);
+    let ptr = load_list_ptr(builder, list, ptr_type);
+
+    // the length goes into the hash first
+    let seed_alloca = builder.build_alloca(ctx.i64_type(), "seed");
+    builder.build_store(seed_alloca, hash_u64(env, seed, len));
+
+    // allocate a stack slot for the current index
+    let index_alloca = builder.build_alloca(env.ptr_int(), "index");
+    builder.build_store(index_alloca, env.ptr_int().const_zero());
+
+    let loop_bb = ctx.append_basic_block(parent, "loop");
+    let body_bb = ctx.append_basic_block(parent, "body");
+    let done_bb = ctx.append_basic_block(parent, "done");
+
+    // the "top" of the loop
+    builder.build_unconditional_branch(loop_bb);
+    builder.position_at_end(loop_bb);
+
+    let curr_index = builder.build_load(index_alloca, "index").into_int_value();
+
+    // #index < len
+    let loop_end_cond =
+        builder.build_int_compare(IntPredicate::ULT, curr_index, len, "bounds_check");
+
+    builder.build_conditional_branch(loop_end_cond, body_bb, done_bb);
+
+    {
+        // loop body
+        builder.position_at_end(body_bb);
+
+        let elem = {
+            let elem_ptr = unsafe { builder.build_in_bounds_gep(ptr, &[curr_index], "load_index") };
+            load_roc_value(env, *element_layout, elem_ptr, "get_elem")
+        };
+
+        let current_seed = builder.build_load(seed_alloca, "seed").into_int_value();
+
+        let next_seed = build_hash_layout(
+            env,
+            layout_ids,
+            current_seed,
+            elem,
+            element_layout,
+            when_recursive,
+        );
+
+        builder.build_store(seed_alloca, next_seed);
+
+        // constant 1isize
+        let one = env.ptr_int().const_int(1, false);
+
+        let next_index = builder.build_int_add(curr_index, one, "nextindex");
+
+        builder.build_store(index_alloca, next_index);
+
+        // jump back to the top of the loop
+        builder.build_unconditional_branch(loop_bb);
+    }
+
+    {
+        builder.position_at_end(done_bb);
+
+        let result = builder.build_load(seed_alloca, "seed");
+        builder.build_return(Some(&result));
+    }
+}
+
+fn build_hash_tag<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    layout_ids: &mut LayoutIds<'a>,
+    when_recursive: WhenRecursive<'a>,
+    union_layout: &UnionLayout<'a>,
+    seed: IntValue<'ctx>,
+    tag: BasicValueEnum<'ctx>,
+) -> IntValue<'ctx> {
+    let block = env.builder.get_insert_block().expect("to be in a function");
+    let di_location = env.builder.get_current_debug_location().unwrap();
+
+    let tag_layout = Layout::Union(*union_layout);
+    let symbol = Symbol::GENERIC_HASH;
+    let fn_name = layout_ids
+        .get(symbol, &tag_layout)
+        .to_symbol_string(symbol, &env.interns);
+
+    let function = match env.module.get_function(fn_name.as_str()) {
+        Some(function_value) => function_value,
+        None => {
+            let seed_type = env.context.i64_type();
+            let arg_type = argument_type_from_union_layout(env, union_layout);
+
+            let function_value = crate::llvm::refcounting::build_header_help(
+                env,
+                &fn_name,
+                seed_type.into(),
+                &[seed_type.into(), arg_type],
+            );
+
+            build_hash_tag_help(
+                env,
+                layout_ids,
+                when_recursive,
+                function_value,
+                union_layout,
+            );
+
+            function_value
+        }
+    };
+
+    env.builder.position_at_end(block);
+    env.builder
+        .set_current_debug_location(env.context, di_location);
+    let call = env
+        .builder
+        .build_call(function, &[seed.into(), tag.into()], "tag_hash");
+
+    call.set_call_convention(FAST_CALL_CONV);
+
+    call.try_as_basic_value().left().unwrap().into_int_value()
+}
+
+fn build_hash_tag_help<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    layout_ids: &mut LayoutIds<'a>,
+    when_recursive: WhenRecursive<'a>,
+    parent: FunctionValue<'ctx>,
+    union_layout: &UnionLayout<'a>,
+) {
+    let ctx = env.context;
+
+    set_debug_location_in(env, parent);
+
+    // Add args to scope
+    let mut it = parent.get_param_iter();
+    let seed = it.next().unwrap().into_int_value();
+    let tag = it.next().unwrap();
+
+    seed.set_name(Symbol::ARG_1.as_str(&env.interns));
+    tag.set_name(Symbol::ARG_2.as_str(&env.interns));
+
+    let entry = ctx.append_basic_block(parent, "entry");
+    env.builder.position_at_end(entry);
+
+    use UnionLayout::*;
+
+    match union_layout {
+        NonRecursive(&[]) => {
+            // we're hashing an empty tag union; this code is effectively unreachable
+            env.builder.build_unreachable();
+        }
+        NonRecursive(tags) => {
+            let id = get_tag_id(env, parent, union_layout, tag);
+            let seed = hash_u64(env, seed, id);
+
+            build_hash_tag_switch(
+                env,
+                layout_ids,
+                parent,
+                union_layout,
+                Some(when_recursive),
+                tags,
+                None,
+                id,
+                seed,
+                tag.into_pointer_value(),
+            );
+        }
+        Recursive(tags) => {
+            let id = get_tag_id(env, parent, union_layout, tag);
+            let seed = hash_u64(env, seed, id);
+
+            // clear the tag_id so we get a pointer to the actual data
+            let tag_ptr = tag_pointer_clear_tag_id(env, tag.into_pointer_value());
+
+            build_hash_tag_switch(
+                env,
+                layout_ids,
+                parent,
+                union_layout,
+                None,
+                tags,
+                None,
+                id,
+                seed,
+                tag_ptr,
+            );
+        }
+        NullableWrapped {
+            nullable_id,
+            other_tags,
+        } => {
+            let hash_other = ctx.append_basic_block(parent, "hash_other");
+            let hash_null = ctx.append_basic_block(parent, "hash_null");
+
+            let is_null = env
+                .builder
+                .build_is_null(tag.into_pointer_value(), "is_null");
+
+            env.builder
+                .build_conditional_branch(is_null, hash_null, hash_other);
+
+            {
+                env.builder.position_at_end(hash_null);
+
+                let null_id = env.context.i64_type().const_int(*nullable_id as u64, false);
+                let answer = hash_u64(env, seed, null_id);
+                env.builder.build_return(Some(&answer));
+            }
+
+            env.builder.position_at_end(hash_other);
+
+            let id = get_tag_id(env, parent, union_layout, tag);
+            let seed = hash_u64(env, seed, id);
+
+            // clear the tag_id so we get a pointer to the actual data
+            let tag_ptr = tag_pointer_clear_tag_id(env, tag.into_pointer_value());
+
+            build_hash_tag_switch(
+                env,
+                layout_ids,
+                parent,
+                union_layout,
+                None,
+                other_tags,
+                Some(*nullable_id),
+                id,
+                seed,
+                tag_ptr,
+            );
+        }
+        NullableUnwrapped {
+            nullable_id,
+            other_fields,
+        } => {
+            let hash_other = ctx.append_basic_block(parent, "hash_other");
+            let hash_null = ctx.append_basic_block(parent, "hash_null");
+
+            let is_null = env
+                .builder
+                .build_is_null(tag.into_pointer_value(), "is_null");
+
+            env.builder
+                .build_conditional_branch(is_null, hash_null, hash_other);
+
+            let i64_type = env.context.i64_type();
+
+            {
+                env.builder.position_at_end(hash_null);
+
+                let null_id = i64_type.const_int(*nullable_id as u64, false);
+                let answer = hash_u64(env, seed, null_id);
+                env.builder.build_return(Some(&answer));
+            }
+
+            {
+                env.builder.position_at_end(hash_other);
+
+                let other_id = i64_type.const_int(!*nullable_id as u64, false);
+                let seed = hash_u64(env, seed, other_id);
+
+                let answer = hash_ptr_to_struct(
+                    env,
+                    layout_ids,
+                    union_layout,
+                    None,
+                    other_fields,
+                    seed,
+                    tag.into_pointer_value(),
+                );
+                env.builder.build_return(Some(&answer));
+            }
+        }
+        NonNullableUnwrapped(field_layouts) => {
+            let answer = hash_ptr_to_struct(
+                env,
+                layout_ids,
+                union_layout,
+                None,
+                field_layouts,
+                seed,
+                tag.into_pointer_value(),
+            );
+
+            env.builder.build_return(Some(&answer));
+        }
+    }
+}
+
+/// Switch on the tag id, and hash the payload of the active tag
+#[allow(clippy::too_many_arguments)]
+fn build_hash_tag_switch<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    layout_ids: &mut LayoutIds<'a>,
+    parent: FunctionValue<'ctx>,
+    union_layout: &UnionLayout<'a>,
+    opt_when_recursive: Option<WhenRecursive<'a>>,
+    tags: &'a [&'a [Layout<'a>]],
+    nullable_id: Option<TagIdIntType>,
+    id: IntValue<'ctx>,
+    seed: IntValue<'ctx>,
+    tag: PointerValue<'ctx>,
+) {
+    let switch_block = env.builder.get_insert_block().unwrap();
+
+    let mut cases = Vec::with_capacity_in(tags.len(), env.arena);
+
+    for (index, field_layouts) in tags.iter().enumerate() {
+        // the null tag has no entry in `tags`, so the ids after it are shifted by one
+        let tag_id = match nullable_id {
+            Some(null_id) if index >= null_id as usize => index + 1,
+            _ => index,
+        };
+
+        let block = env.context.append_basic_block(parent, "tag_id_hash");
+        env.builder.position_at_end(block);
+
+        let answer = hash_ptr_to_struct(
+            env,
+            layout_ids,
+            union_layout,
+            opt_when_recursive.clone(),
+            field_layouts,
+            seed,
+            tag,
+        );
+
+        env.builder.build_return(Some(&answer));
+
+        cases.push((id.get_type().const_int(tag_id as u64, false), block));
+    }
+
+    env.builder.position_at_end(switch_block);
+
+    match cases.pop() {
+        Some((_, default)) => {
+            env.builder.build_switch(id, default, &cases);
+        }
+        None => {
+            // we're hashing an empty tag union; this code is effectively unreachable
+            env.builder.build_unreachable();
+        }
+    }
+}
+
+fn hash_ptr_to_struct<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    layout_ids: &mut LayoutIds<'a>,
+    union_layout: &UnionLayout<'a>,
+    opt_when_recursive: Option<WhenRecursive<'a>>,
+    field_layouts: &'a [Layout<'a>],
+    seed: IntValue<'ctx>,
+    tag: PointerValue<'ctx>,
+) -> IntValue<'ctx> {
+    let struct_layout = Layout::struct_no_name_order(field_layouts);
+
+    let wrapper_type = basic_type_from_layout(env, &struct_layout);
+    debug_assert!(wrapper_type.is_struct_type());
+
+    // cast the opaque pointer to a pointer of the correct shape
+    let struct_ptr = env
+        .builder
+        .build_bitcast(
+            tag,
+            wrapper_type.ptr_type(AddressSpace::Generic),
+            "opaque_to_correct",
+        )
+        .into_pointer_value();
+
+    let struct_value = env
+        .builder
+        .build_load(struct_ptr, "load_struct")
+        .into_struct_value();
+
+    build_hash_struct(
+        env,
+        layout_ids,
+        field_layouts,
+        opt_when_recursive.unwrap_or(WhenRecursive::Loop(*union_layout)),
+        seed,
+        struct_value,
+    )
+}
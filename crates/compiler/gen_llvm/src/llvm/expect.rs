@@ -7,7 +7,7 @@ use inkwell::builder::Builder;
 use inkwell::module::Linkage;
 use inkwell::types::{BasicMetadataTypeEnum, BasicType};
 use inkwell::values::{BasicValueEnum, FunctionValue, IntValue, PointerValue};
-use inkwell::AddressSpace;
+use inkwell::{AddressSpace, IntPredicate};
 use roc_builtins::bitcode;
 use roc_module::symbol::Symbol;
 use roc_mono::layout::{Builtin, Layout, LayoutIds, UnionLayout};
@@ -101,10 +101,13 @@ pub(crate) fn clone_to_shared_memory<'a, 'ctx, 'env>(
     region: Region,
     lookups: &[Symbol],
 ) {
-    let func = env
-        .module
-        .get_function(bitcode::UTILS_EXPECT_FAILED_START)
-        .unwrap();
+    let start_function = if env.mode.runs_expects_in_separate_process() {
+        bitcode::UTILS_EXPECT_FAILED_START_SHARED_FILE
+    } else {
+        bitcode::UTILS_EXPECT_FAILED_START
+    };
+
+    let func = env.module.get_function(start_function).unwrap();
 
     let call_result = env
         .builder
@@ -175,9 +178,25 @@ pub(crate) fn clone_to_shared_memory<'a, 'ctx, 'env>(
         }
     }
 
+    // A frame that didn't fit is dropped: it isn't counted, and the offset is left past the end
+    // of the buffer, so that the reader knows. Any frames after it won't fit either.
+    let bd = env.builder;
+    let capacity = buffer_capacity(env);
+    let fits = bd.build_int_compare(IntPredicate::ULE, offset, capacity, "fits");
+
     let one = env.ptr_int().const_int(1, false);
-    let new_count = env.builder.build_int_add(count, one, "inc");
-    write_state(env, original_ptr, new_count, offset)
+    let new_count = bd.build_int_add(count, one, "inc");
+    let past_end = bd.build_int_add(capacity, one, "past_end");
+
+    let new_count = bd.build_select(fits, new_count, count, "new_count");
+    let new_offset = bd.build_select(fits, offset, past_end, "new_offset");
+
+    write_state(
+        env,
+        original_ptr,
+        new_count.into_int_value(),
+        new_offset.into_int_value(),
+    )
 }
 
 #[derive(Clone, Debug, Copy)]
@@ -222,17 +241,23 @@ fn build_clone<'a, 'ctx, 'env>(
 
         Layout::Union(union_layout) => {
             if layout.safe_to_memcpy() {
-                let ptr = unsafe {
-                    env.builder
-                        .build_in_bounds_gep(ptr, &[cursors.offset], "at_current_offset")
-                };
+                let width = env
+                    .ptr_int()
+                    .const_int(layout.stack_size(env.target_info) as u64, false);
 
-                let ptr_type = value.get_type().ptr_type(AddressSpace::Generic);
-                let ptr = env
-                    .builder
-                    .build_pointer_cast(ptr, ptr_type, "cast_ptr_type");
+                build_if_fits(env, cursors.offset, width, || {
+                    let ptr = unsafe {
+                        env.builder
+                            .build_in_bounds_gep(ptr, &[cursors.offset], "at_current_offset")
+                    };
 
-                store_roc_value(env, layout, ptr, value);
+                    let ptr_type = value.get_type().ptr_type(AddressSpace::Generic);
+                    let ptr = env
+                        .builder
+                        .build_pointer_cast(ptr, ptr_type, "cast_ptr_type");
+
+                    store_roc_value(env, layout, ptr, value);
+                });
 
                 cursors.extra_offset
             } else {
@@ -843,22 +868,66 @@ fn build_copy<'a, 'ctx, 'env>(
     offset: IntValue<'ctx>,
     value: BasicValueEnum<'ctx>,
 ) -> IntValue<'ctx> {
-    let ptr = unsafe {
-        env.builder
-            .build_in_bounds_gep(ptr, &[offset], "at_current_offset")
-    };
+    let width = value.get_type().size_of().unwrap();
 
-    let ptr_type = value.get_type().ptr_type(AddressSpace::Generic);
-    let ptr = env
-        .builder
-        .build_pointer_cast(ptr, ptr_type, "cast_ptr_type");
+    build_if_fits(env, offset, width, || {
+        let ptr = unsafe {
+            env.builder
+                .build_in_bounds_gep(ptr, &[offset], "at_current_offset")
+        };
 
-    env.builder.build_store(ptr, value);
+        let ptr_type = value.get_type().ptr_type(AddressSpace::Generic);
+        let ptr = env
+            .builder
+            .build_pointer_cast(ptr, ptr_type, "cast_ptr_type");
+
+        env.builder.build_store(ptr, value);
+    });
 
-    let width = value.get_type().size_of().unwrap();
     env.builder.build_int_add(offset, width, "new_offset")
 }
 
+fn buffer_capacity<'a, 'ctx, 'env>(env: &Env<'a, 'ctx, 'env>) -> IntValue<'ctx> {
+    let func = env
+        .module
+        .get_function(bitcode::UTILS_EXPECT_BUFFER_CAPACITY)
+        .unwrap();
+
+    env.builder
+        .build_call(func, &[], "buffer_capacity")
+        .try_as_basic_value()
+        .left()
+        .unwrap()
+        .into_int_value()
+}
+
+/// Only write `width` bytes at `offset` if they fit in the buffer. Values that don't fit are
+/// skipped, and `clone_to_shared_memory` drops the frame they belong to.
+fn build_if_fits<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    offset: IntValue<'ctx>,
+    width: IntValue<'ctx>,
+    write: impl FnOnce(),
+) {
+    let bd = env.builder;
+
+    let end = bd.build_int_add(offset, width, "end");
+    let fits = bd.build_int_compare(IntPredicate::ULE, end, buffer_capacity(env), "fits");
+
+    let parent = bd.get_insert_block().and_then(|b| b.get_parent()).unwrap();
+
+    let write_block = env.context.append_basic_block(parent, "write");
+    let done_block = env.context.append_basic_block(parent, "done");
+
+    bd.build_conditional_branch(fits, write_block, done_block);
+
+    bd.position_at_end(write_block);
+    write();
+    bd.build_unconditional_branch(done_block);
+
+    bd.position_at_end(done_block);
+}
+
 #[allow(clippy::too_many_arguments)]
 fn build_clone_builtin<'a, 'ctx, 'env>(
     env: &Env<'a, 'ctx, 'env>,
@@ -913,14 +982,16 @@ fn build_clone_builtin<'a, 'ctx, 'env>(
             let elements_width = bd.build_int_mul(element_width, len, "elements_width");
 
             if elem.safe_to_memcpy() {
-                // NOTE we are not actually sure the dest is properly aligned
-                let dest = pointer_at_offset(bd, ptr, offset);
-                let src = bd.build_pointer_cast(
-                    elements,
-                    env.context.i8_type().ptr_type(AddressSpace::Generic),
-                    "to_bytes_pointer",
-                );
-                bd.build_memcpy(dest, 1, src, 1, elements_width).unwrap();
+                build_if_fits(env, offset, elements_width, || {
+                    // NOTE we are not actually sure the dest is properly aligned
+                    let dest = pointer_at_offset(bd, ptr, offset);
+                    let src = bd.build_pointer_cast(
+                        elements,
+                        env.context.i8_type().ptr_type(AddressSpace::Generic),
+                        "to_bytes_pointer",
+                    );
+                    bd.build_memcpy(dest, 1, src, 1, elements_width).unwrap();
+                });
 
                 bd.build_int_add(offset, elements_width, "new_offset")
             } else {
@@ -1,5 +1,6 @@
 pub mod bitcode;
 pub mod build;
+pub mod build_hash;
 pub mod build_list;
 pub mod build_str;
 pub mod compare;
@@ -707,7 +707,19 @@ impl<'a> WasmBackend<'a> {
 
             Stmt::Refcounting(modify, following) => self.stmt_refcounting(modify, following),
 
-            Stmt::Expect { .. } => todo!("expect is not implemented in the wasm backend"),
+            Stmt::Expect {
+                condition,
+                remainder,
+                ..
+            } => {
+                self.stmt_expect(*condition);
+                self.stmt(remainder);
+            }
+
+            // `dbg` values are printed by the `roc` process that runs the program, through shared
+            // memory and signals. A wasm program can't reach it, so like LLVM does for wasm
+            // targets, we strip `dbg` and only generate the remainder.
+            Stmt::Dbg { remainder, .. } => self.stmt(remainder),
 
             Stmt::RuntimeError(msg) => self.stmt_runtime_error(msg),
         }
@@ -971,6 +983,30 @@ impl<'a> WasmBackend<'a> {
         self.stmt(rc_stmt);
     }
 
+    /// Failed expects are reported by the `roc` process that runs the program, and a wasm
+    /// program can't reach it. So we still evaluate the condition, but a failure crashes the
+    /// program instead of being reported, rather than letting it pass unnoticed.
+    fn stmt_expect(&mut self, condition: Symbol) {
+        // Ensure the condition is not stored only in the VM stack
+        // Otherwise we can't reach it from inside the block
+        let storage = self.storage.get(&condition).to_owned();
+        self.storage
+            .ensure_value_has_local(&mut self.code_builder, condition, storage);
+
+        // skip over the crash if the condition holds
+        self.start_block();
+        self.storage
+            .load_symbols(&mut self.code_builder, &[condition]);
+        self.code_builder.br_if(0);
+
+        self.stmt_runtime_error(
+            "An expect failed, and the wasm backend can't report expect failures. \
+            Use the LLVM or dev backend to see which expect failed and why.",
+        );
+
+        self.end_block();
+    }
+
     pub fn stmt_runtime_error(&mut self, msg: &'a str) {
         // Create a zero-terminated version of the message string
         let mut bytes = Vec::with_capacity_in(msg.len() + 1, self.env.arena);
@@ -1376,6 +1412,37 @@ impl<'a> WasmBackend<'a> {
         );
     }
 
+    /// Generate a call to a helper proc that hashes a data structure.
+    /// Call stack is expr_call_low_level -> LowLevelCall::generate -> call_hash_specialized
+    pub fn call_hash_specialized(
+        &mut self,
+        arguments: &'a [Symbol],
+        arg_layout: &Layout<'a>,
+        ret_symbol: Symbol,
+        ret_storage: &StoredValue,
+    ) {
+        let ident_ids = self
+            .interns
+            .all_ident_ids
+            .get_mut(&self.env.module_id)
+            .unwrap();
+
+        let (specialized_call_expr, new_specializations) = self
+            .helper_proc_gen
+            .call_specialized_hash(ident_ids, arg_layout, arguments);
+
+        for (spec_sym, spec_layout) in new_specializations.into_iter() {
+            self.register_helper_proc(spec_sym, spec_layout, ProcSource::Helper);
+        }
+
+        self.expr(
+            ret_symbol,
+            self.env.arena.alloc(specialized_call_expr),
+            &Layout::Builtin(Builtin::Int(IntWidth::U64)),
+            ret_storage,
+        );
+    }
+
     /*******************************************************************
      * Structs
      *******************************************************************/
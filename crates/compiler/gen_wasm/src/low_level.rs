@@ -1831,7 +1831,7 @@ impl<'a> LowLevelCall<'a> {
                 backend.storage.load_symbols(code_builder, self.arguments);
             }
 
-            Hash => todo!("{:?}", self.lowlevel),
+            Hash => self.hash(backend),
 
             Eq | NotEq => self.eq_or_neq(backend),
 
@@ -1852,6 +1852,94 @@ impl<'a> LowLevelCall<'a> {
         }
     }
 
+    /// Hashing
+    /// Numbers are written to stack memory and hashed byte-by-byte in Zig.
+    /// Data structures are hashed by a generated helper proc that threads the seed through each part.
+    fn hash(&self, backend: &mut WasmBackend<'a>) {
+        let arg_layout =
+            backend.storage.symbol_layouts[&self.arguments[0]].runtime_representation();
+
+        match arg_layout {
+            Layout::Builtin(
+                Builtin::Int(_) | Builtin::Float(_) | Builtin::Bool | Builtin::Decimal,
+            ) => {
+                let (size, alignment_bytes) = arg_layout.stack_size_and_alignment(TARGET_INFO);
+
+                let (value_local, value_offset) =
+                    match backend.storage.get(&self.arguments[0]).to_owned() {
+                        StoredValue::StackMemory { location, .. } => {
+                            location.local_and_offset(backend.storage.stack_frame_pointer)
+                        }
+                        _ => {
+                            let (local, offset) = backend
+                                .storage
+                                .allocate_anonymous_stack_memory(size, alignment_bytes);
+                            backend.storage.copy_value_to_memory(
+                                &mut backend.code_builder,
+                                local,
+                                offset,
+                                self.arguments[0],
+                            );
+                            (local, offset)
+                        }
+                    };
+
+                // wyhash(seed: u64, bytes: ?[*]const u8, length: usize)
+                backend
+                    .storage
+                    .load_symbols(&mut backend.code_builder, &[self.arguments[1]]);
+                backend.code_builder.get_local(value_local);
+                if value_offset > 0 {
+                    backend.code_builder.i32_const(value_offset as i32);
+                    backend.code_builder.i32_add();
+                }
+                backend.code_builder.i32_const(size as i32);
+                backend.call_host_fn_after_loading_args(bitcode::DICT_HASH, 3, true);
+            }
+
+            Layout::Builtin(Builtin::Str) => {
+                self.load_args_and_call_zig(backend, bitcode::DICT_HASH_STR);
+            }
+
+            // Zero-sized values contribute nothing to the hash
+            Layout::Struct { field_layouts, .. } if field_layouts.is_empty() => {
+                backend
+                    .storage
+                    .load_symbols(&mut backend.code_builder, &[self.arguments[1]]);
+            }
+            Layout::Union(UnionLayout::NonRecursive(tags)) if tags.is_empty() => {
+                backend
+                    .storage
+                    .load_symbols(&mut backend.code_builder, &[self.arguments[1]]);
+            }
+
+            Layout::Builtin(Builtin::List(_))
+            | Layout::Struct { .. }
+            | Layout::Union(_)
+            | Layout::Boxed(_) => {
+                // Don't want Zig calling convention here, we're calling internal Roc functions
+                backend
+                    .storage
+                    .load_symbols(&mut backend.code_builder, self.arguments);
+
+                backend.call_hash_specialized(
+                    self.arguments,
+                    &arg_layout,
+                    self.ret_symbol,
+                    &self.ret_storage,
+                );
+            }
+
+            Layout::LambdaSet(_) => {
+                internal_error!("Tried to hash a function {:?}", self.arguments)
+            }
+
+            Layout::RecursivePointer => {
+                internal_error!("Tried to hash RecursivePointer value {:?}", self.arguments,)
+            }
+        }
+    }
+
     /// Equality and inequality
     /// These can operate on any data type (except functions) so they're more complex than other operators.
     fn eq_or_neq(&self, backend: &mut WasmBackend<'a>) {
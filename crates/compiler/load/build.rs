@@ -23,6 +23,8 @@ const MODULES: &[(ModuleId, &str)] = &[
     (ModuleId::ENCODE, "Encode.roc"),
     (ModuleId::DECODE, "Decode.roc"),
     (ModuleId::JSON, "Json.roc"),
+    (ModuleId::HASH, "Hash.roc"),
+    (ModuleId::GENERATE, "Generate.roc"),
 ];
 
 fn main() {
@@ -65,7 +67,7 @@ fn write_subs_for_module(module_id: ModuleId, filename: &str) {
 
         let module = match res_module {
             Ok(v) => v,
-            Err(LoadingProblem::FormattedReport(report)) => {
+            Err(LoadingProblem::FormattedReport(report, _)) => {
                 panic!("{}", report);
             }
             Err(other) => {
@@ -17,7 +17,8 @@ const SKIP_SUBS_CACHE: bool = {
 pub use roc_load_internal::docs;
 pub use roc_load_internal::file::{
     EntryPoint, ExecutionMode, Expectations, LoadConfig, LoadResult, LoadStart, LoadedModule,
-    LoadingProblem, MonomorphizedModule, Phase, Threading,
+    LoadingProblem, MonomorphizedModule, OptLevel, ParseCache, Phase, PropertyTesting, SolvedCache,
+    Threading,
 };
 
 #[allow(clippy::too_many_arguments)]
@@ -33,6 +34,7 @@ fn load<'a>(
 }
 
 /// Load using only a single thread; used when compiling to webassembly
+#[allow(clippy::too_many_arguments)]
 pub fn load_single_threaded<'a>(
     arena: &'a Bump,
     load_start: LoadStart<'a>,
@@ -40,6 +42,8 @@ pub fn load_single_threaded<'a>(
     target_info: TargetInfo,
     render: RenderTarget,
     exec_mode: ExecutionMode,
+    property_testing: PropertyTesting,
+    opt_level: OptLevel,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
     let cached_subs = read_cached_subs();
 
@@ -51,6 +55,8 @@ pub fn load_single_threaded<'a>(
         cached_subs,
         render,
         exec_mode,
+        property_testing,
+        opt_level,
     )
 }
 
@@ -128,6 +134,30 @@ pub fn load_and_typecheck(
     }
 }
 
+/// Like [load_and_typecheck] or [load_and_monomorphize], depending on the `exec_mode`. Given a
+/// `parse_cache`, reuses the modules in it whose source did not change since an earlier load;
+/// given a `solved_cache`, does the same for the solved types of interface modules.
+pub fn load_from_path<'a>(
+    arena: &'a Bump,
+    filename: PathBuf,
+    exposed_types: ExposedByModule,
+    load_config: LoadConfig,
+    parse_cache: Option<ParseCache<'a>>,
+    solved_cache: Option<SolvedCache>,
+) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
+    let mut load_start = LoadStart::from_path(arena, filename, load_config.render)?;
+
+    if let Some(parse_cache) = parse_cache {
+        load_start = load_start.with_parse_cache(parse_cache);
+    }
+
+    if let Some(solved_cache) = solved_cache {
+        load_start = load_start.with_solved_cache(solved_cache);
+    }
+
+    load(arena, load_start, exposed_types, load_config)
+}
+
 pub fn load_and_typecheck_str<'a>(
     arena: &'a Bump,
     filename: PathBuf,
@@ -151,6 +181,8 @@ pub fn load_and_typecheck_str<'a>(
         target_info,
         render,
         ExecutionMode::Check,
+        PropertyTesting::default(),
+        OptLevel::Normal,
     )? {
         Monomorphized(_) => unreachable!(""),
         TypeChecked(module) => Ok(module),
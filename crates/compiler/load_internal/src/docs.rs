@@ -60,6 +60,10 @@ pub enum TypeAnnotation {
         fields: Vec<RecordField>,
         extension: Box<TypeAnnotation>,
     },
+    Tuple {
+        elems: Vec<TypeAnnotation>,
+        extension: Box<TypeAnnotation>,
+    },
     Ability {
         members: Vec<AbilityMember>,
     },
@@ -362,6 +366,22 @@ fn type_to_docs(in_func_type_ann: bool, type_annotation: ast::TypeAnnotation) ->
                 extension: Box::new(extension),
             }
         }
+        ast::TypeAnnotation::Tuple { elems, ext } => {
+            let doc_elems = elems
+                .iter()
+                .map(|elem| type_to_docs(in_func_type_ann, elem.value))
+                .collect();
+
+            let extension = match ext {
+                None => NoTypeAnn,
+                Some(ext_type_ann) => type_to_docs(in_func_type_ann, ext_type_ann.value),
+            };
+
+            Tuple {
+                elems: doc_elems,
+                extension: Box::new(extension),
+            }
+        }
         ast::TypeAnnotation::SpaceBefore(&sub_type_ann, _) => {
             type_to_docs(in_func_type_ann, sub_type_ann)
         }
@@ -1,4 +1,5 @@
 use crate::docs::ModuleDocumentation;
+pub use crate::solved_cache::SolvedCache;
 use bumpalo::Bump;
 use crossbeam::channel::{bounded, Sender};
 use crossbeam::deque::{Injector, Stealer, Worker};
@@ -13,13 +14,14 @@ use roc_can::module::{
     canonicalize_module_defs, ExposedByModule, ExposedForModule, ExposedModuleTypes, Module,
     ResolvedImplementations,
 };
+pub use roc_can::operator::PropertyTesting;
 use roc_collections::{default_hasher, BumpMap, MutMap, MutSet, VecMap, VecSet};
 use roc_constrain::module::constrain_module;
 use roc_debug_flags::dbg_do;
 #[cfg(debug_assertions)]
 use roc_debug_flags::{
-    ROC_PRINT_IR_AFTER_REFCOUNT, ROC_PRINT_IR_AFTER_RESET_REUSE, ROC_PRINT_IR_AFTER_SPECIALIZATION,
-    ROC_PRINT_LOAD_LOG,
+    ROC_PRINT_IR_AFTER_OPTIMIZATION, ROC_PRINT_IR_AFTER_REFCOUNT, ROC_PRINT_IR_AFTER_RESET_REUSE,
+    ROC_PRINT_IR_AFTER_SPECIALIZATION, ROC_PRINT_LOAD_LOG,
 };
 use roc_derive::SharedDerivedModule;
 use roc_error_macros::internal_error;
@@ -29,9 +31,10 @@ use roc_module::symbol::{
     IdentIds, IdentIdsByModule, Interns, ModuleId, ModuleIds, PQModuleName, PackageModuleIds,
     PackageQualified, Symbol,
 };
+pub use roc_mono::ir::OptLevel;
 use roc_mono::ir::{
-    CapturedSymbols, ExternalSpecializations, PartialProc, Proc, ProcLayout, Procs, ProcsBase,
-    UpdateModeIds,
+    CallSpecIds, CapturedSymbols, ExternalSpecializations, PartialProc, Proc, ProcLayout, Procs,
+    ProcsBase, UpdateModeIds,
 };
 use roc_mono::layout::{CapturesNiche, LambdaName, Layout, LayoutCache, LayoutProblem};
 use roc_parse::ast::{self, Defs, ExtractSpaces, Spaced, StrLiteral, TypeAnnotation};
@@ -42,6 +45,7 @@ use roc_parse::module::module_defs;
 use roc_parse::parser::{FileError, Parser, SyntaxError};
 use roc_region::all::{LineInfo, Loc, Region};
 use roc_reporting::report::RenderTarget;
+use roc_reporting::structured::Diagnostic;
 use roc_solve::module::{extract_module_owned_implementations, Solved, SolvedModule};
 use roc_solve_problem::TypeError;
 use roc_target::TargetInfo;
@@ -123,6 +127,10 @@ pub struct LoadConfig {
     pub render: RenderTarget,
     pub threading: Threading,
     pub exec_mode: ExecutionMode,
+    pub property_testing: PropertyTesting,
+    /// Decides whether the mono IR is inlined and simplified before refcounts are inserted,
+    /// which only happens for the dev backends
+    pub opt_level: OptLevel,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -211,6 +219,8 @@ impl Default for ModuleCache<'_> {
             ENCODE,
             DECODE,
             JSON,
+            HASH,
+            GENERATE,
         }
 
         Self {
@@ -289,7 +299,10 @@ fn start_phase<'a>(
                 // parse the file
                 let header = state.module_cache.headers.remove(&module_id).unwrap();
 
-                BuildTask::Parse { header }
+                BuildTask::Parse {
+                    header,
+                    parse_cache: state.parse_cache.clone(),
+                }
             }
             Phase::CanonicalizeAndConstrain => {
                 // canonicalize the file
@@ -386,6 +399,23 @@ fn start_phase<'a>(
                     // Give this its own scope to make sure that the Guard from the lock() is dropped
                     // immediately after contains_key returns
                     state.cached_subs.lock().contains_key(&module_id)
+                } || match &state.solved_cache {
+                    Some(solved_cache) => {
+                        // Every module gets a key, since the modules importing it need it
+                        // for theirs, but only interface modules are cached.
+                        let key = solved_cache.record_key(
+                            module_id,
+                            parsed.src,
+                            &parsed.exposed_ident_ids,
+                            &dep_idents,
+                            &module_ids,
+                            &exposed_symbols,
+                        );
+
+                        matches!(parsed.header_for, HeaderFor::Interface)
+                            && solved_cache.load(module_id, key)
+                    }
+                    None => false,
                 };
 
                 BuildTask::CanonicalizeAndConstrain {
@@ -396,6 +426,7 @@ fn start_phase<'a>(
                     aliases,
                     abilities_store,
                     skip_constraint_gen,
+                    property_testing: state.property_testing,
                 }
             }
 
@@ -431,6 +462,7 @@ fn start_phase<'a>(
                     dep_idents,
                     declarations,
                     state.cached_subs.clone(),
+                    state.solved_cache.clone(),
                     derived_module,
                 )
             }
@@ -629,9 +661,11 @@ impl LoadedModule {
         total
     }
 
+    /// The names of the exposed values which user code can refer to
     pub fn exposed_values_str(&self) -> Vec<&str> {
         self.exposed_values
             .iter()
+            .filter(|symbol| !symbol.is_hidden_builtin())
             .map(|symbol| symbol.as_str(&self.interns))
             .collect()
     }
@@ -717,6 +751,8 @@ pub struct MonomorphizedModule<'a> {
     pub type_problems: MutMap<ModuleId, Vec<TypeError>>,
     pub procedures: MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>,
     pub toplevel_expects: VecMap<Symbol, Region>,
+    /// The top-level expects which take arguments, and are checked against generated ones
+    pub property_expects: VecSet<Symbol>,
     pub entry_point: EntryPoint<'a>,
     pub exposed_to_host: ExposedToHost,
     pub sources: MutMap<ModuleId, (PathBuf, Box<str>)>,
@@ -822,6 +858,7 @@ enum Msg<'a> {
         module_timing: ModuleTiming,
         abilities_store: AbilitiesStore,
         toplevel_expects: VecMap<Symbol, Region>,
+        property_expects: VecSet<Symbol>,
     },
     MadeSpecializations {
         module_id: ModuleId,
@@ -909,6 +946,7 @@ struct State<'a> {
     pub dependencies: Dependencies<'a>,
     pub procedures: MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>,
     pub toplevel_expects: VecMap<Symbol, Region>,
+    pub property_expects: VecSet<Symbol>,
     pub exposed_to_host: ExposedToHost,
 
     /// This is the "final" list of IdentIds, after canonicalization and constraint gen
@@ -938,6 +976,8 @@ struct State<'a> {
 
     pub render: RenderTarget,
     pub exec_mode: ExecutionMode,
+    pub property_testing: PropertyTesting,
+    pub opt_level: OptLevel,
 
     /// All abilities across all modules.
     pub world_abilities: WorldAbilities,
@@ -946,6 +986,12 @@ struct State<'a> {
 
     // cached subs (used for builtin modules, could include packages in the future too)
     cached_subs: CachedSubs,
+
+    /// Modules parsed by earlier loads of this app, see [ParseCache]
+    parse_cache: Option<ParseCache<'a>>,
+
+    /// Modules solved by earlier loads, see [SolvedCache]
+    solved_cache: Option<SolvedCache>,
 }
 
 type CachedSubs = Arc<Mutex<MutMap<ModuleId, (Subs, Vec<(Symbol, Variable)>)>>>;
@@ -966,6 +1012,10 @@ impl<'a> State<'a> {
         render: RenderTarget,
         number_of_workers: usize,
         exec_mode: ExecutionMode,
+        property_testing: PropertyTesting,
+        opt_level: OptLevel,
+        parse_cache: Option<ParseCache<'a>>,
+        solved_cache: Option<SolvedCache>,
     ) -> Self {
         let arc_shorthands = Arc::new(Mutex::new(MutMap::default()));
 
@@ -982,6 +1032,7 @@ impl<'a> State<'a> {
             dependencies,
             procedures: MutMap::default(),
             toplevel_expects: VecMap::default(),
+            property_expects: VecSet::default(),
             exposed_to_host: ExposedToHost::default(),
             exposed_types,
             arc_modules,
@@ -996,8 +1047,12 @@ impl<'a> State<'a> {
             cached_subs: Arc::new(Mutex::new(cached_subs)),
             render,
             exec_mode,
+            property_testing,
+            opt_level,
             make_specializations_pass: MakeSpecializationsPass::Pass(1),
             world_abilities: Default::default(),
+            parse_cache,
+            solved_cache,
         }
     }
 }
@@ -1084,6 +1139,7 @@ enum BuildTask<'a> {
     },
     Parse {
         header: ModuleHeader<'a>,
+        parse_cache: Option<ParseCache<'a>>,
     },
     CanonicalizeAndConstrain {
         parsed: ParsedModule<'a>,
@@ -1093,6 +1149,7 @@ enum BuildTask<'a> {
         aliases: MutMap<Symbol, Alias>,
         abilities_store: PendingAbilitiesStore,
         skip_constraint_gen: bool,
+        property_testing: PropertyTesting,
     },
     Solve {
         module: Module,
@@ -1106,6 +1163,7 @@ enum BuildTask<'a> {
         declarations: Declarations,
         dep_idents: IdentIdsByModule,
         cached_subs: CachedSubs,
+        solved_cache: Option<SolvedCache>,
         derived_module: SharedDerivedModule,
     },
     BuildPendingSpecializations {
@@ -1154,8 +1212,9 @@ pub enum LoadingProblem<'a> {
     ErrJoiningWorkerThreads,
     TriedToImportAppModule,
 
-    /// a formatted report
-    FormattedReport(String),
+    /// a formatted report, and the same problem as a diagnostic for tools which
+    /// asked for machine-readable problems
+    FormattedReport(String, Diagnostic),
 }
 
 pub enum Phases {
@@ -1208,6 +1267,8 @@ pub fn load_and_typecheck_str<'a>(
         render,
         threading,
         exec_mode: ExecutionMode::Check,
+        property_testing: PropertyTesting::default(),
+        opt_level: OptLevel::Normal,
     };
 
     match load(arena, load_start, exposed_types, cached_subs, load_config)? {
@@ -1228,6 +1289,8 @@ pub struct LoadStart<'a> {
     root_id: ModuleId,
     root_msg: Msg<'a>,
     src_dir: PathBuf,
+    parse_cache: Option<ParseCache<'a>>,
+    solved_cache: Option<SolvedCache>,
 }
 
 impl<'a> LoadStart<'a> {
@@ -1293,17 +1356,17 @@ impl<'a> LoadStart<'a> {
 
                     // if parsing failed, this module did not add any identifiers
                     let root_exposed_ident_ids = IdentIds::exposed_builtins(0);
-                    let buf = to_parse_problem_report(
+                    let (buf, diagnostic) = to_parse_problem_report(
                         problem,
                         module_ids,
                         root_exposed_ident_ids,
                         render,
                     );
-                    return Err(LoadingProblem::FormattedReport(buf));
+                    return Err(LoadingProblem::FormattedReport(buf, diagnostic));
                 }
                 Err(LoadingProblem::FileProblem { filename, error }) => {
-                    let buf = to_file_problem_report(&filename, error);
-                    return Err(LoadingProblem::FormattedReport(buf));
+                    let (buf, diagnostic) = to_file_problem_report(&filename, error);
+                    return Err(LoadingProblem::FormattedReport(buf, diagnostic));
                 }
                 Err(e) => return Err(e),
             }
@@ -1315,6 +1378,8 @@ impl<'a> LoadStart<'a> {
             src_dir,
             root_id,
             root_msg,
+            parse_cache: None,
+            solved_cache: None,
         })
     }
 
@@ -1348,8 +1413,26 @@ impl<'a> LoadStart<'a> {
             ident_ids_by_module,
             root_id,
             root_msg,
+            parse_cache: None,
+            solved_cache: None,
         })
     }
+
+    /// Reuse the modules in `parse_cache` whose source did not change, and record this load's
+    /// module graph in it.
+    pub fn with_parse_cache(mut self, parse_cache: ParseCache<'a>) -> Self {
+        self.parse_cache = Some(parse_cache);
+
+        self
+    }
+
+    /// Skip constraint generation and solving for the interface modules whose solution is in
+    /// `solved_cache`, and add the solutions of the others to it.
+    pub fn with_solved_cache(mut self, solved_cache: SolvedCache) -> Self {
+        self.solved_cache = Some(solved_cache);
+
+        self
+    }
 }
 
 pub enum LoadResult<'a> {
@@ -1364,6 +1447,65 @@ pub enum Threading {
     AtMost(usize),
 }
 
+/// Shared between loads of the same app, e.g. the runs of `roc check --watch`, so that only
+/// the modules whose source changed get parsed again. Cloning it gives another handle to the
+/// same cache.
+///
+/// The parsed defs point into the arena the module was first loaded into, so every load which
+/// uses a cache must be given the same arena.
+#[derive(Debug, Clone, Default)]
+pub struct ParseCache<'a> {
+    inner: Arc<Mutex<ParseCacheInner<'a>>>,
+}
+
+#[derive(Debug, Default)]
+struct ParseCacheInner<'a> {
+    /// The defs of every module we parsed, with the hash of the source we parsed them from
+    parsed: MutMap<PathBuf, (u64, Defs<'a>)>,
+    /// The files the latest load read or tried to read, except for the builtins
+    files: MutSet<PathBuf>,
+}
+
+impl<'a> ParseCache<'a> {
+    /// The app's module graph as of the latest load: every file it discovered through imports
+    /// and the platform, including the ones which were missing or failed to parse.
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = self.inner.lock().files.iter().cloned().collect();
+
+        files.sort();
+
+        files
+    }
+
+    fn start_load(&self) {
+        self.inner.lock().files.clear();
+    }
+
+    fn record_file(&self, path: &Path) {
+        self.inner.lock().files.insert(path.to_path_buf());
+    }
+
+    fn get(&self, path: &Path, source_hash: u64) -> Option<Defs<'a>> {
+        match self.inner.lock().parsed.get(path) {
+            Some((hash, defs)) if *hash == source_hash => Some(defs.clone()),
+            _ => None,
+        }
+    }
+
+    fn insert(&self, path: PathBuf, source_hash: u64, defs: Defs<'a>) {
+        self.inner.lock().parsed.insert(path, (source_hash, defs));
+    }
+}
+
+fn source_hash(source: &[u8]) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    source.hash(&mut hasher);
+
+    hasher.finish()
+}
+
 /// The loading process works like this, starting from the given filename (e.g. "main.roc"):
 ///
 /// 1. Open the file.
@@ -1455,6 +1597,8 @@ pub fn load<'a>(
             cached_subs,
             load_config.render,
             load_config.exec_mode,
+            load_config.property_testing,
+            load_config.opt_level,
         ),
         Threads::Many(threads) => load_multi_threaded(
             arena,
@@ -1465,6 +1609,8 @@ pub fn load<'a>(
             load_config.render,
             threads,
             load_config.exec_mode,
+            load_config.property_testing,
+            load_config.opt_level,
         ),
     }
 }
@@ -1479,6 +1625,8 @@ pub fn load_single_threaded<'a>(
     cached_subs: MutMap<ModuleId, (Subs, Vec<(Symbol, Variable)>)>,
     render: RenderTarget,
     exec_mode: ExecutionMode,
+    property_testing: PropertyTesting,
+    opt_level: OptLevel,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
     let LoadStart {
         arc_modules,
@@ -1486,9 +1634,18 @@ pub fn load_single_threaded<'a>(
         root_id,
         root_msg,
         src_dir,
-        ..
+        parse_cache,
+        solved_cache,
     } = load_start;
 
+    if let Some(parse_cache) = &parse_cache {
+        parse_cache.start_load();
+    }
+
+    if let Some(solved_cache) = &solved_cache {
+        solved_cache.start_load();
+    }
+
     let (msg_tx, msg_rx) = bounded(1024);
 
     msg_tx
@@ -1506,6 +1663,10 @@ pub fn load_single_threaded<'a>(
         render,
         number_of_workers,
         exec_mode,
+        property_testing,
+        opt_level,
+        parse_cache,
+        solved_cache,
     );
 
     // We'll add tasks to this, and then worker threads will take tasks from it.
@@ -1606,25 +1767,34 @@ fn state_thread_step<'a>(
                     Ok(ControlFlow::Break(LoadResult::Monomorphized(monomorphized)))
                 }
                 Msg::FailedToReadFile { filename, error } => {
-                    let buf = to_file_problem_report(&filename, error);
-                    Err(LoadingProblem::FormattedReport(buf))
+                    if let Some(parse_cache) = &state.parse_cache {
+                        parse_cache.record_file(&filename);
+                    }
+
+                    let (buf, diagnostic) = to_file_problem_report(&filename, error);
+                    Err(LoadingProblem::FormattedReport(buf, diagnostic))
                 }
 
                 Msg::FailedToParse(problem) => {
+                    if let Some(parse_cache) = &state.parse_cache {
+                        parse_cache.record_file(&problem.filename);
+                    }
+
                     let module_ids = (*state.arc_modules).lock().clone().into_module_ids();
-                    let buf = to_parse_problem_report(
+                    let (buf, diagnostic) = to_parse_problem_report(
                         problem,
                         module_ids,
                         state.constrained_ident_ids,
                         state.render,
                     );
-                    Err(LoadingProblem::FormattedReport(buf))
+                    Err(LoadingProblem::FormattedReport(buf, diagnostic))
                 }
                 msg => {
                     // This is where most of the main thread's work gets done.
                     // Everything up to this point has been setting up the threading
                     // system which lets this logic work efficiently.
                     let arc_modules = state.arc_modules.clone();
+                    let parse_cache = state.parse_cache.clone();
 
                     let render = state.render;
 
@@ -1640,6 +1810,10 @@ fn state_thread_step<'a>(
                     match res_state {
                         Ok(new_state) => Ok(ControlFlow::Continue(new_state)),
                         Err(LoadingProblem::ParsingFailed(problem)) => {
+                            if let Some(parse_cache) = parse_cache {
+                                parse_cache.record_file(&problem.filename);
+                            }
+
                             let module_ids = Arc::try_unwrap(arc_modules)
                                 .unwrap_or_else(|_| {
                                     panic!(
@@ -1651,13 +1825,13 @@ fn state_thread_step<'a>(
 
                             // if parsing failed, this module did not add anything to IdentIds
                             let root_exposed_ident_ids = IdentIds::exposed_builtins(0);
-                            let buf = to_parse_problem_report(
+                            let (buf, diagnostic) = to_parse_problem_report(
                                 problem,
                                 module_ids,
                                 root_exposed_ident_ids,
                                 render,
                             );
-                            Err(LoadingProblem::FormattedReport(buf))
+                            Err(LoadingProblem::FormattedReport(buf, diagnostic))
                         }
                         Err(e) => Err(e),
                     }
@@ -1681,6 +1855,8 @@ fn load_multi_threaded<'a>(
     render: RenderTarget,
     available_threads: usize,
     exec_mode: ExecutionMode,
+    property_testing: PropertyTesting,
+    opt_level: OptLevel,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
     let LoadStart {
         arc_modules,
@@ -1688,9 +1864,18 @@ fn load_multi_threaded<'a>(
         root_id,
         root_msg,
         src_dir,
-        ..
+        parse_cache,
+        solved_cache,
     } = load_start;
 
+    if let Some(parse_cache) = &parse_cache {
+        parse_cache.start_load();
+    }
+
+    if let Some(solved_cache) = &solved_cache {
+        solved_cache.start_load();
+    }
+
     let (msg_tx, msg_rx) = bounded(1024);
     msg_tx
         .send(root_msg)
@@ -1723,6 +1908,10 @@ fn load_multi_threaded<'a>(
         render,
         num_workers,
         exec_mode,
+        property_testing,
+        opt_level,
+        parse_cache,
+        solved_cache,
     );
 
     // an arena for every worker, stored in an arena-allocated bumpalo vec to make the lifetimes work
@@ -2047,6 +2236,12 @@ fn update<'a>(
             log!("loaded header for {:?}", header.module_id);
             let home = header.module_id;
 
+            if let Some(parse_cache) = &state.parse_cache {
+                if !home.is_builtin() {
+                    parse_cache.record_file(&header.module_path);
+                }
+            }
+
             let mut work = MutSet::default();
 
             {
@@ -2163,6 +2358,10 @@ fn update<'a>(
                 header
                     .exposed_imports
                     .insert(Ident::from("Bool"), (Symbol::BOOL_BOOL, Region::zero()));
+
+                header
+                    .exposed_imports
+                    .insert(Ident::from("Eq"), (Symbol::BOOL_EQ, Region::zero()));
             }
 
             if header.module_id == ModuleId::NUM {
@@ -2227,6 +2426,22 @@ fn update<'a>(
                 header
                     .imported_modules
                     .insert(ModuleId::ENCODE, Region::zero());
+
+                header
+                    .package_qualified_imported_modules
+                    .insert(PackageQualified::Unqualified(ModuleId::HASH));
+
+                header
+                    .imported_modules
+                    .insert(ModuleId::HASH, Region::zero());
+
+                header
+                    .package_qualified_imported_modules
+                    .insert(PackageQualified::Unqualified(ModuleId::GENERATE));
+
+                header
+                    .imported_modules
+                    .insert(ModuleId::GENERATE, Region::zero());
             }
 
             state
@@ -2522,12 +2737,14 @@ fn update<'a>(
             module_timing,
             abilities_store,
             toplevel_expects,
+            property_expects,
         } => {
             log!("found specializations for {:?}", module_id);
 
             let subs = solved_subs.into_inner();
 
             state.toplevel_expects.extend(toplevel_expects);
+            state.property_expects.extend(property_expects);
 
             state
                 .module_cache
@@ -2668,8 +2885,35 @@ fn update<'a>(
 
                     debug_print_ir!(state, ROC_PRINT_IR_AFTER_SPECIALIZATION);
 
+                    // Procs that are called from outside the IR; optimization must keep these
+                    let entry_points: MutSet<Symbol> = state
+                        .exposed_to_host
+                        .values
+                        .keys()
+                        .chain(state.toplevel_expects.keys())
+                        .copied()
+                        .collect();
+
                     let ident_ids = state.constrained_ident_ids.get_mut(&module_id).unwrap();
 
+                    if state.opt_level.optimizes_mono_ir() {
+                        // Inlining copies calls, which need ids that no other call uses. Reset
+                        // and refcount insertion continue from the same `update_mode_ids`.
+                        let mut call_spec_ids = CallSpecIds::new();
+
+                        Proc::optimize_procedures(
+                            arena,
+                            module_id,
+                            ident_ids,
+                            &mut update_mode_ids,
+                            &mut call_spec_ids,
+                            &entry_points,
+                            &mut state.procedures,
+                        );
+
+                        debug_print_ir!(state, ROC_PRINT_IR_AFTER_OPTIMIZATION);
+                    }
+
                     Proc::insert_reset_reuse_operations(
                         arena,
                         module_id,
@@ -2840,6 +3084,7 @@ fn finish_specialization(
 
     let State {
         toplevel_expects,
+        property_expects,
         procedures,
         module_cache,
         output_path,
@@ -2877,8 +3122,13 @@ fn finish_specialization(
                         }
                         Valid(To::NewPackage(p_or_p)) => p_or_p,
                         other => {
-                            let buf = to_missing_platform_report(state.root_id, other);
-                            return Err(LoadingProblem::FormattedReport(buf));
+                            let filename = sources
+                                .get(&state.root_id)
+                                .map(|(path, _)| path.clone())
+                                .unwrap_or_default();
+                            let (buf, diagnostic) =
+                                to_missing_platform_report(state.root_id, filename, other);
+                            return Err(LoadingProblem::FormattedReport(buf, diagnostic));
                         }
                     };
 
@@ -2938,6 +3188,7 @@ fn finish_specialization(
         sources,
         timings: state.timings,
         toplevel_expects,
+        property_expects,
     })
 }
 
@@ -3194,6 +3445,8 @@ fn load_module<'a>(
         "Encode", ModuleId::ENCODE
         "Decode", ModuleId::DECODE
         "Json", ModuleId::JSON
+        "Hash", ModuleId::HASH
+        "Generate", ModuleId::GENERATE
     }
 
     let (filename, opt_shorthand) = module_name_to_path(src_dir, module_name, arc_shorthands);
@@ -4013,6 +4266,7 @@ impl<'a> BuildTask<'a> {
         dep_idents: IdentIdsByModule,
         declarations: Declarations,
         cached_subs: CachedSubs,
+        solved_cache: Option<SolvedCache>,
         derived_module: SharedDerivedModule,
     ) -> Self {
         let exposed_by_module = exposed_types.retain_modules(imported_modules.keys());
@@ -4033,6 +4287,7 @@ impl<'a> BuildTask<'a> {
             dep_idents,
             module_timing,
             cached_subs,
+            solved_cache,
             derived_module,
         }
     }
@@ -4320,6 +4575,7 @@ fn run_solve<'a>(
     decls: Declarations,
     dep_idents: IdentIdsByModule,
     cached_subs: CachedSubs,
+    solved_cache: Option<SolvedCache>,
     derived_module: SharedDerivedModule,
 ) -> Msg<'a> {
     let solve_start = Instant::now();
@@ -4357,8 +4613,16 @@ fn run_solve<'a>(
                     )
                 }
             }
+        } else if let Some(cached) = solved_cache.as_ref().and_then(|c| c.take(module_id)) {
+            (
+                Solved(cached.subs),
+                cached.solved_implementations,
+                cached.exposed_vars_by_symbol,
+                vec![],
+                cached.abilities_store,
+            )
         } else {
-            run_solve_solve(
+            let solved = run_solve_solve(
                 exposed_for_module,
                 constraints,
                 constraint,
@@ -4366,7 +4630,30 @@ fn run_solve<'a>(
                 var_store,
                 module,
                 derived_module,
-            )
+            );
+
+            // Modules with type errors aren't cached, so that we report their errors every time.
+            let (
+                solved_subs,
+                solved_implementations,
+                exposed_vars_by_symbol,
+                problems,
+                abilities_store,
+            ) = &solved;
+
+            if let Some(solved_cache) = &solved_cache {
+                if problems.is_empty() && solved_subs.inner().problems.is_empty() {
+                    solved_cache.store(
+                        module_id,
+                        solved_subs.inner(),
+                        exposed_vars_by_symbol,
+                        solved_implementations,
+                        abilities_store,
+                    );
+                }
+            }
+
+            solved
         }
     };
 
@@ -4466,6 +4753,7 @@ fn canonicalize_and_constrain<'a>(
     imported_abilities_state: PendingAbilitiesStore,
     parsed: ParsedModule<'a>,
     skip_constraint_gen: bool,
+    property_testing: PropertyTesting,
 ) -> CanAndCon {
     let canonicalize_start = Instant::now();
 
@@ -4503,6 +4791,7 @@ fn canonicalize_and_constrain<'a>(
         &exposed_symbols,
         &symbols_from_requires,
         &mut var_store,
+        property_testing,
     );
 
     // _after has an underscore because it's unused in --release builds
@@ -4582,7 +4871,12 @@ fn canonicalize_and_constrain<'a>(
             Vacant(vacant) => {
                 let should_include_builtin = matches!(
                     name.module_id(),
-                    ModuleId::ENCODE | ModuleId::DECODE | ModuleId::DICT | ModuleId::SET
+                    ModuleId::ENCODE
+                        | ModuleId::DECODE
+                        | ModuleId::DICT
+                        | ModuleId::SET
+                        | ModuleId::HASH
+                        | ModuleId::GENERATE
                 );
 
                 if !name.is_builtin() || should_include_builtin {
@@ -4624,18 +4918,40 @@ fn canonicalize_and_constrain<'a>(
     }
 }
 
-fn parse<'a>(arena: &'a Bump, header: ModuleHeader<'a>) -> Result<Msg<'a>, LoadingProblem<'a>> {
+fn parse<'a>(
+    arena: &'a Bump,
+    header: ModuleHeader<'a>,
+    parse_cache: Option<ParseCache<'a>>,
+) -> Result<Msg<'a>, LoadingProblem<'a>> {
     let mut module_timing = header.module_timing;
     let parse_start = Instant::now();
     let source = header.parse_state.original_bytes();
     let parse_state = header.parse_state;
-    let parsed_defs = match module_defs().parse(arena, parse_state) {
-        Ok((_, success, _state)) => success,
-        Err((_, fail, state)) => {
-            return Err(LoadingProblem::ParsingFailed(
-                fail.into_file_error(header.module_path, &state),
-            ));
-        }
+
+    // The defs only depend on the source, so if it didn't change since we last parsed this
+    // module, neither did they.
+    let hash = parse_cache.as_ref().map(|_| source_hash(source));
+    let cached_defs = match (&parse_cache, hash) {
+        (Some(parse_cache), Some(hash)) => parse_cache.get(&header.module_path, hash),
+        _ => None,
+    };
+
+    let parsed_defs = match cached_defs {
+        Some(defs) => defs,
+        None => match module_defs().parse(arena, parse_state) {
+            Ok((_, success, _state)) => {
+                if let (Some(parse_cache), Some(hash)) = (&parse_cache, hash) {
+                    parse_cache.insert(header.module_path.clone(), hash, success.clone());
+                }
+
+                success
+            }
+            Err((_, fail, state)) => {
+                return Err(LoadingProblem::ParsingFailed(
+                    fail.into_file_error(header.module_path, &state),
+                ));
+            }
+        },
     };
 
     // Record the parse end time once, to avoid checking the time a second time
@@ -4819,6 +5135,7 @@ fn build_pending_specializations<'a>(
 
     let mut module_thunks = bumpalo::collections::Vec::new_in(arena);
     let mut toplevel_expects = VecMap::default();
+    let mut property_expects = VecSet::default();
 
     let mut procs_base = ProcsBase {
         partial_procs: BumpMap::default(),
@@ -5111,6 +5428,10 @@ fn build_pending_specializations<'a>(
                     );
                 }
 
+                if roc_can::expr::is_property_expect(&body) {
+                    property_expects.insert(symbol);
+                }
+
                 let body = roc_can::expr::toplevel_expect_to_inline_expect(body);
 
                 let proc = PartialProc {
@@ -5228,6 +5549,7 @@ fn build_pending_specializations<'a>(
         module_timing,
         abilities_store,
         toplevel_expects,
+        property_expects,
     }
 }
 
@@ -5363,7 +5685,10 @@ fn run_task<'a>(
             ident_ids_by_module,
         )
         .map(|(_, msg)| msg),
-        Parse { header } => parse(arena, header),
+        Parse {
+            header,
+            parse_cache,
+        } => parse(arena, header, parse_cache),
         CanonicalizeAndConstrain {
             parsed,
             module_ids,
@@ -5372,6 +5697,7 @@ fn run_task<'a>(
             aliases,
             abilities_store,
             skip_constraint_gen,
+            property_testing,
         } => {
             let can_and_con = canonicalize_and_constrain(
                 arena,
@@ -5382,6 +5708,7 @@ fn run_task<'a>(
                 abilities_store,
                 parsed,
                 skip_constraint_gen,
+                property_testing,
             );
 
             Ok(Msg::CanonicalizedAndConstrained(can_and_con))
@@ -5398,6 +5725,7 @@ fn run_task<'a>(
             declarations,
             dep_idents,
             cached_subs,
+            solved_cache,
             derived_module,
         } => Ok(run_solve(
             module,
@@ -5411,6 +5739,7 @@ fn run_task<'a>(
             declarations,
             dep_idents,
             cached_subs,
+            solved_cache,
             derived_module,
         )),
         BuildPendingSpecializations {
@@ -5476,8 +5805,9 @@ fn run_task<'a>(
     Ok(())
 }
 
-fn to_file_problem_report(filename: &Path, error: io::ErrorKind) -> String {
-    use roc_reporting::report::{Report, RocDocAllocator, Severity, DEFAULT_PALETTE};
+fn to_file_problem_report(filename: &Path, error: io::ErrorKind) -> (String, Diagnostic) {
+    use roc_reporting::explain;
+    use roc_reporting::report::{Report, RocDocAllocator, Severity};
     use ven_pretty::DocAllocator;
 
     let src_lines: Vec<&str> = Vec::new();
@@ -5509,6 +5839,7 @@ fn to_file_problem_report(filename: &Path, error: io::ErrorKind) -> String {
                 doc,
                 title: "FILE NOT FOUND".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::FILE_NOT_FOUND),
             }
         }
         io::ErrorKind::PermissionDenied => {
@@ -5526,6 +5857,7 @@ fn to_file_problem_report(filename: &Path, error: io::ErrorKind) -> String {
                 doc,
                 title: "FILE PERMISSION DENIED".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::FILE_PERMISSION_DENIED),
             }
         }
         _ => {
@@ -5542,15 +5874,18 @@ fn to_file_problem_report(filename: &Path, error: io::ErrorKind) -> String {
                 doc,
                 title: "FILE PROBLEM".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::FILE_PROBLEM),
             }
         }
     };
 
-    let mut buf = String::new();
-    let palette = DEFAULT_PALETTE;
-    report.render_color_terminal(&mut buf, &alloc, &palette);
+    let (buf, mut diagnostic) = render_load_report(report, &alloc, RenderTarget::ColorTerminal);
 
-    buf
+    // The file can't be read, so there is nowhere in it to point at, but tools still need to
+    // know which file the problem is with.
+    diagnostic.filename = filename.to_path_buf();
+
+    (buf, diagnostic)
 }
 
 fn to_parse_problem_report<'a>(
@@ -5558,8 +5893,8 @@ fn to_parse_problem_report<'a>(
     mut module_ids: ModuleIds,
     all_ident_ids: IdentIdsByModule,
     render: RenderTarget,
-) -> String {
-    use roc_reporting::report::{parse_problem, RocDocAllocator, DEFAULT_PALETTE};
+) -> (String, Diagnostic) {
+    use roc_reporting::report::{parse_problem, RocDocAllocator};
 
     // TODO this is not in fact safe
     let src = unsafe { from_utf8_unchecked(problem.problem.bytes) };
@@ -5589,16 +5924,16 @@ fn to_parse_problem_report<'a>(
         problem,
     );
 
-    let mut buf = String::new();
-    let palette = DEFAULT_PALETTE;
-
-    report.render(render, &mut buf, &alloc, &palette);
-
-    buf
+    render_load_report(report, &alloc, render)
 }
 
-fn to_missing_platform_report(module_id: ModuleId, other: PlatformPath) -> String {
-    use roc_reporting::report::{Report, RocDocAllocator, Severity, DEFAULT_PALETTE};
+fn to_missing_platform_report(
+    module_id: ModuleId,
+    filename: PathBuf,
+    other: PlatformPath,
+) -> (String, Diagnostic) {
+    use roc_reporting::explain;
+    use roc_reporting::report::{Report, RocDocAllocator, Severity};
     use ven_pretty::DocAllocator;
     use PlatformPath::*;
 
@@ -5624,6 +5959,7 @@ fn to_missing_platform_report(module_id: ModuleId, other: PlatformPath) -> Strin
                     doc,
                     title: "NO PLATFORM".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::NO_PLATFORM),
                 }
             }
             RootIsInterface => {
@@ -5639,6 +5975,7 @@ fn to_missing_platform_report(module_id: ModuleId, other: PlatformPath) -> Strin
                     doc,
                     title: "NO PLATFORM".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::NO_PLATFORM),
                 }
             }
             RootIsHosted => {
@@ -5654,6 +5991,7 @@ fn to_missing_platform_report(module_id: ModuleId, other: PlatformPath) -> Strin
                     doc,
                     title: "NO PLATFORM".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::NO_PLATFORM),
                 }
             }
             RootIsPlatformModule => {
@@ -5669,16 +6007,40 @@ fn to_missing_platform_report(module_id: ModuleId, other: PlatformPath) -> Strin
                     doc,
                     title: "NO PLATFORM".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::NO_PLATFORM),
                 }
             }
         }
     };
 
-    let palette = DEFAULT_PALETTE;
+    let (buf, mut diagnostic) = render_load_report(report, &alloc, RenderTarget::ColorTerminal);
+
+    // The problem is with the header of the module we started loading from.
+    diagnostic.filename = filename;
+
+    (buf, diagnostic)
+}
+
+/// Renders a report about a problem which stops loading, and keeps the regions it points at
+/// in a diagnostic. The report must be the only one built with `alloc`.
+fn render_load_report<'b>(
+    report: roc_reporting::report::Report<'b>,
+    alloc: &'b roc_reporting::report::RocDocAllocator<'b>,
+    render: RenderTarget,
+) -> (String, Diagnostic) {
+    use roc_reporting::report::DEFAULT_PALETTE;
+
+    let diagnostic = Diagnostic::from_report(report.clone(), alloc);
+
     let mut buf = String::new();
-    report.render_color_terminal(&mut buf, &alloc, &palette);
+    match render {
+        RenderTarget::Generic => report.render_ci(&mut buf, alloc),
+        RenderTarget::ColorTerminal => {
+            report.render_color_terminal(&mut buf, alloc, &DEFAULT_PALETTE)
+        }
+    }
 
-    buf
+    (buf, diagnostic)
 }
 
 /// Builtin aliases that are not covered by type checker optimizations
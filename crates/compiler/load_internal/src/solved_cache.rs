@@ -0,0 +1,484 @@
+//! An on-disk cache of solved modules, so that a module which did not change since the last
+//! time we loaded it can skip constraint generation and solving.
+//!
+//! Builtins get the same treatment from the `Subs` that `roc_load` prebuilds for them; this
+//! cache does it for user modules, at runtime.
+use parking_lot::Mutex;
+use roc_can::abilities::{AbilitiesStore, ImplKey, MemberSpecializationInfo, ResolvedImpl};
+use roc_can::module::ResolvedImplementations;
+use roc_collections::all::MutMap;
+use roc_collections::VecSet;
+use roc_error_macros::internal_error;
+use roc_module::symbol::{IdentIds, IdentIdsByModule, ModuleId, ModuleIds, Symbol};
+use roc_types::subs::{Subs, Variable};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Caches the solved `Subs`, exposed types and abilities of interface modules in a directory,
+/// one file per module.
+///
+/// A module's file is named after a hash of everything its solved types depend on: its source,
+/// the module and ident ids it was canonicalized with, and the same hash of each module it
+/// imports. A module whose hash changed is solved from scratch and its new solution is written
+/// out.
+///
+/// The files contain the compiler's in-memory representation of types, so each compiler
+/// executable gets its own subdirectory, and creating the cache removes the subdirectories of
+/// other executables. Stale files of the current one are evicted, oldest first, once the
+/// subdirectory grows beyond [DEFAULT_MAX_SIZE] bytes.
+#[derive(Debug, Clone)]
+pub struct SolvedCache {
+    /// The subdirectory of the current compiler executable
+    dir: PathBuf,
+    max_size: u64,
+    keys: Arc<Mutex<MutMap<ModuleId, u64>>>,
+    hits: Arc<Mutex<MutMap<ModuleId, CachedModule>>>,
+    /// The keys of the modules we looked for in the cache, but didn't find
+    misses: Arc<Mutex<MutMap<ModuleId, u64>>>,
+    /// How many modules the current load read from the cache
+    hit_count: Arc<AtomicUsize>,
+}
+
+pub const DEFAULT_MAX_SIZE: u64 = 256 * 1024 * 1024;
+
+#[derive(Debug)]
+pub(crate) struct CachedModule {
+    pub subs: Subs,
+    pub exposed_vars_by_symbol: Vec<(Symbol, Variable)>,
+    pub solved_implementations: ResolvedImplementations,
+    pub abilities_store: AbilitiesStore,
+}
+
+impl SolvedCache {
+    pub fn new(dir: PathBuf) -> Self {
+        let compiler_dir_name = format!("{:016x}", compiler_hash());
+
+        remove_other_compilers(&dir, &compiler_dir_name);
+
+        Self {
+            dir: dir.join(compiler_dir_name),
+            max_size: DEFAULT_MAX_SIZE,
+            keys: Default::default(),
+            hits: Default::default(),
+            misses: Default::default(),
+            hit_count: Default::default(),
+        }
+    }
+
+    /// Evict the oldest files once the cache takes up more than `max_size` bytes.
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+
+        self
+    }
+
+    /// The directory the current compiler executable keeps its files in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// How many modules the last load read from the cache, instead of solving them
+    pub fn hit_count(&self) -> usize {
+        self.hit_count.load(Ordering::SeqCst)
+    }
+
+    /// `$ROC_CACHE_DIR/solved`, or else `solved` in the platform's usual place for caches,
+    /// e.g. `~/.cache/roc/solved` on Linux.
+    pub fn default_dir() -> Option<PathBuf> {
+        let cache_dir = match std::env::var_os("ROC_CACHE_DIR") {
+            Some(dir) => PathBuf::from(dir),
+            None => platform_cache_dir()?.join("roc"),
+        };
+
+        Some(cache_dir.join("solved"))
+    }
+
+    pub(crate) fn start_load(&self) {
+        self.keys.lock().clear();
+        self.hits.lock().clear();
+        self.misses.lock().clear();
+        self.hit_count.store(0, Ordering::SeqCst);
+
+        // Evicting is only an optimization too, so failing to do it is not an error.
+        let _ = evict(&self.dir, self.max_size);
+    }
+
+    /// Computes and remembers the key of a module which is about to be canonicalized. The keys
+    /// of the modules it imports must have been recorded already.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn record_key(
+        &self,
+        module_id: ModuleId,
+        src: &str,
+        ident_ids: &IdentIds,
+        dep_idents: &IdentIdsByModule,
+        module_ids: &ModuleIds,
+        exposed_symbols: &VecSet<Symbol>,
+    ) -> u64 {
+        let mut keys = self.keys.lock();
+
+        let mut hasher = DefaultHasher::new();
+
+        src.hash(&mut hasher);
+        module_id.hash(&mut hasher);
+        module_ids.get_name(module_id).hash(&mut hasher);
+        hash_ident_ids(ident_ids, &mut hasher);
+
+        // The seed and iterations of property-based expects are left out, even though `roc test`
+        // picks a new seed on every run: they only become number literals during canonicalization,
+        // which runs on a hit too, and they don't change any type.
+
+        // We visit the imports and exposed symbols in no particular order, so combine their
+        // hashes in a way that doesn't depend on the order.
+        let mut deps = 0u64;
+        for dep_id in dep_idents.keys() {
+            let mut dep_hasher = DefaultHasher::new();
+
+            dep_id.hash(&mut dep_hasher);
+            module_ids.get_name(*dep_id).hash(&mut dep_hasher);
+            hash_ident_ids(dep_idents.get(dep_id).unwrap(), &mut dep_hasher);
+            // The builtins are compiled into the compiler, whose hash already names our
+            // directory, and they don't get a key when their solved types are prebuilt.
+            if !dep_id.is_builtin() {
+                match keys.get(dep_id) {
+                    Some(dep_key) => dep_key.hash(&mut dep_hasher),
+                    None => internal_error!(
+                        "The solved cache key of {:?} was not recorded before that of {:?}, which imports it",
+                        dep_id,
+                        module_id
+                    ),
+                }
+            }
+
+            deps = deps.wrapping_add(dep_hasher.finish());
+        }
+
+        let mut exposed = 0u64;
+        for symbol in exposed_symbols.iter() {
+            let mut symbol_hasher = DefaultHasher::new();
+            symbol.hash(&mut symbol_hasher);
+
+            exposed = exposed.wrapping_add(symbol_hasher.finish());
+        }
+
+        deps.hash(&mut hasher);
+        exposed.hash(&mut hasher);
+
+        let key = hasher.finish();
+        keys.insert(module_id, key);
+
+        key
+    }
+
+    /// Reads the module's solution from the cache, if it's there; [Self::take] then returns it.
+    /// If it's not, [Self::store] will write it once it's solved.
+    pub(crate) fn load(&self, module_id: ModuleId, key: u64) -> bool {
+        match read_module(&self.path(key)) {
+            Some(cached) => {
+                self.hits.lock().insert(module_id, cached);
+                self.hit_count.fetch_add(1, Ordering::SeqCst);
+
+                true
+            }
+            None => {
+                self.misses.lock().insert(module_id, key);
+
+                false
+            }
+        }
+    }
+
+    pub(crate) fn take(&self, module_id: ModuleId) -> Option<CachedModule> {
+        self.hits.lock().remove(&module_id)
+    }
+
+    /// Writes out the solution of a module [Self::load] didn't find. The cache is only an
+    /// optimization, so failing to write it is not an error.
+    pub(crate) fn store(
+        &self,
+        module_id: ModuleId,
+        subs: &Subs,
+        exposed_vars_by_symbol: &[(Symbol, Variable)],
+        solved_implementations: &ResolvedImplementations,
+        abilities_store: &AbilitiesStore,
+    ) {
+        let opt_key = self.misses.lock().remove(&module_id);
+
+        if let Some(key) = opt_key {
+            let path = self.path(key);
+
+            let _ = write_module(
+                &path,
+                subs,
+                exposed_vars_by_symbol,
+                solved_implementations,
+                abilities_store,
+            );
+        }
+    }
+
+    fn path(&self, key: u64) -> PathBuf {
+        self.dir.join(format!("{:016x}", key))
+    }
+}
+
+fn hash_ident_ids(ident_ids: &IdentIds, hasher: &mut impl Hasher) {
+    for (ident_id, string) in ident_ids.ident_strs() {
+        ident_id.hash(hasher);
+        string.hash(hasher);
+    }
+}
+
+/// Identifies the compiler which wrote a cache file. Anything that changes the in-memory layout
+/// of `Subs` means a new executable, so its path, size and modification time will do.
+fn compiler_hash() -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    if let Ok(exe) = std::env::current_exe() {
+        exe.hash(&mut hasher);
+
+        if let Ok(metadata) = fs::metadata(&exe) {
+            metadata.len().hash(&mut hasher);
+            metadata.modified().ok().hash(&mut hasher);
+        }
+    }
+
+    hasher.finish()
+}
+
+/// Removes the subdirectories other compiler executables wrote; we can't read their files.
+fn remove_other_compilers(dir: &Path, compiler_dir_name: &str) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+
+        // Only touch the directories that look like ours.
+        let is_compiler_dir = name.len() == compiler_dir_name.len()
+            && name.bytes().all(|b| b.is_ascii_hexdigit())
+            && entry.file_type().map_or(false, |t| t.is_dir());
+
+        if is_compiler_dir && name != compiler_dir_name {
+            let _ = fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+/// Removes the least recently written files until the ones left take up at most `max_size`
+/// bytes. An unchanged module whose file got evicted is solved again and written out anew.
+fn evict(dir: &Path, max_size: u64) -> io::Result<()> {
+    let mut files: Vec<(SystemTime, u64, PathBuf)> = Vec::new();
+    let mut total_size = 0;
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+
+        if metadata.is_file() {
+            total_size += metadata.len();
+            files.push((
+                metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                metadata.len(),
+                entry.path(),
+            ));
+        }
+    }
+
+    files.sort();
+
+    for (_, size, path) in files {
+        if total_size <= max_size {
+            break;
+        }
+
+        fs::remove_file(path)?;
+        total_size -= size;
+    }
+
+    Ok(())
+}
+
+fn platform_cache_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        std::env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| Path::new(&home).join("Library").join("Caches"))
+    } else {
+        match std::env::var_os("XDG_CACHE_HOME") {
+            Some(dir) => Some(PathBuf::from(dir)),
+            None => std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")),
+        }
+    }
+}
+
+/// A [ResolvedImpl]; `symbol` and the lambda sets are only meaningful for [ResolvedImpl::Impl].
+#[derive(Clone, Copy)]
+struct SerializedResolvedImpl {
+    impl_key: ImplKey,
+    symbol: Symbol,
+    kind: u32,
+    lambda_sets_start: u32,
+    lambda_sets_len: u32,
+}
+
+// A cache file consists of
+//
+// - a hash of the rest of the file, so we don't read files that were only partially written
+// - the length of the serialized subs, as a u64
+// - the serialized subs and exposed variables, padded to a multiple of 8 bytes
+// - the serialized abilities store
+// - the solved implementations: two lengths, then the two slices they are the lengths of
+const SUBS_START: usize = 16;
+
+fn write_module(
+    path: &Path,
+    subs: &Subs,
+    exposed_vars_by_symbol: &[(Symbol, Variable)],
+    solved_implementations: &ResolvedImplementations,
+    abilities_store: &AbilitiesStore,
+) -> io::Result<()> {
+    let mut buffer = vec![0; SUBS_START];
+
+    let subs_len = subs.serialize(exposed_vars_by_symbol, &mut buffer)?;
+    buffer[8..SUBS_START].copy_from_slice(&(subs_len as u64).to_ne_bytes());
+    buffer.resize(SUBS_START + round_to_multiple_of_8(subs_len), 0);
+
+    let written = buffer.len();
+    let written = abilities_store.serialize(&mut buffer, written)?;
+
+    let mut lambda_sets: Vec<(u32, Variable)> = Vec::new();
+    let implementations: Vec<SerializedResolvedImpl> = solved_implementations
+        .iter()
+        .map(|(impl_key, resolved_impl)| {
+            let lambda_sets_start = lambda_sets.len() as u32;
+
+            let (symbol, kind) = match resolved_impl {
+                ResolvedImpl::Impl(info) => {
+                    lambda_sets.extend(
+                        info.specialization_lambda_sets
+                            .iter()
+                            .map(|(region, var)| (*region as u32, *var)),
+                    );
+
+                    (info.symbol, 0)
+                }
+                ResolvedImpl::Derived => (impl_key.ability_member, 1),
+                ResolvedImpl::Error => (impl_key.ability_member, 2),
+            };
+
+            SerializedResolvedImpl {
+                impl_key: *impl_key,
+                symbol,
+                kind,
+                lambda_sets_start,
+                lambda_sets_len: lambda_sets.len() as u32 - lambda_sets_start,
+            }
+        })
+        .collect();
+
+    let lengths = [implementations.len() as u64, lambda_sets.len() as u64];
+    let written = Subs::serialize_slice(&lengths, &mut buffer, written)?;
+    let written = Subs::serialize_slice(&implementations, &mut buffer, written)?;
+    Subs::serialize_slice(&lambda_sets, &mut buffer, written)?;
+
+    let checksum = checksum(&buffer[8..]);
+    buffer[..8].copy_from_slice(&checksum.to_ne_bytes());
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    // Write to a temporary file first, so that a concurrent `roc` never sees a partial file.
+    let tmp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&tmp_path, &buffer)?;
+    fs::rename(&tmp_path, path)
+}
+
+fn read_module(path: &Path) -> Option<CachedModule> {
+    let file = fs::read(path).ok()?;
+
+    if file.len() < SUBS_START {
+        return None;
+    }
+
+    // The serialized slices assume the file starts at an 8-byte boundary, like a u64 would.
+    let mut words = vec![0u64; (file.len() + 7) / 8];
+    let bytes = unsafe {
+        std::ptr::copy_nonoverlapping(file.as_ptr(), words.as_mut_ptr() as *mut u8, file.len());
+
+        std::slice::from_raw_parts(words.as_ptr() as *const u8, file.len())
+    };
+
+    let stored_checksum = u64::from_ne_bytes(bytes[..8].try_into().unwrap());
+    if stored_checksum != checksum(&bytes[8..]) {
+        return None;
+    }
+
+    let subs_len = u64::from_ne_bytes(bytes[8..SUBS_START].try_into().unwrap()) as usize;
+    let (subs, exposed_vars_by_symbol) =
+        Subs::deserialize(&bytes[SUBS_START..SUBS_START + subs_len]);
+    let exposed_vars_by_symbol = exposed_vars_by_symbol.to_vec();
+
+    let offset = SUBS_START + round_to_multiple_of_8(subs_len);
+    let (abilities_store, offset) = AbilitiesStore::deserialize(bytes, offset);
+
+    let (lengths, offset) = Subs::deserialize_slice::<u64>(bytes, 2, offset);
+    let (implementations, offset) =
+        Subs::deserialize_slice::<SerializedResolvedImpl>(bytes, lengths[0] as usize, offset);
+    let (lambda_sets, _) =
+        Subs::deserialize_slice::<(u32, Variable)>(bytes, lengths[1] as usize, offset);
+
+    let solved_implementations = implementations
+        .iter()
+        .map(|serialized| {
+            let resolved_impl = match serialized.kind {
+                0 => {
+                    let start = serialized.lambda_sets_start as usize;
+                    let end = start + serialized.lambda_sets_len as usize;
+
+                    let specialization_lambda_sets = lambda_sets[start..end]
+                        .iter()
+                        .map(|(region, var)| (*region as u8, *var))
+                        .collect();
+
+                    ResolvedImpl::Impl(MemberSpecializationInfo::new(
+                        serialized.symbol,
+                        specialization_lambda_sets,
+                    ))
+                }
+                1 => ResolvedImpl::Derived,
+                _ => ResolvedImpl::Error,
+            };
+
+            (serialized.impl_key, resolved_impl)
+        })
+        .collect();
+
+    Some(CachedModule {
+        subs,
+        exposed_vars_by_symbol,
+        solved_implementations,
+        abilities_store,
+    })
+}
+
+fn checksum(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+
+    hasher.finish()
+}
+
+fn round_to_multiple_of_8(value: usize) -> usize {
+    (value + 7) / 8 * 8
+}
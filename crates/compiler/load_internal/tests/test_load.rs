@@ -17,8 +17,9 @@ mod helpers;
 use crate::helpers::fixtures_dir;
 use bumpalo::Bump;
 use roc_can::module::ExposedByModule;
-use roc_load_internal::file::{ExecutionMode, LoadConfig, Threading};
-use roc_load_internal::file::{LoadResult, LoadStart, LoadedModule, LoadingProblem};
+use roc_load_internal::file::SolvedCache;
+use roc_load_internal::file::{ExecutionMode, LoadConfig, OptLevel, PropertyTesting, Threading};
+use roc_load_internal::file::{LoadResult, LoadStart, LoadedModule, LoadingProblem, ParseCache};
 use roc_module::ident::ModuleName;
 use roc_module::symbol::{Interns, ModuleId};
 use roc_problem::can::Problem;
@@ -46,6 +47,8 @@ fn load_and_typecheck(
         render: RenderTarget::Generic,
         threading: Threading::Single,
         exec_mode: ExecutionMode::Check,
+        property_testing: PropertyTesting::default(),
+        opt_level: OptLevel::Normal,
     };
 
     match roc_load_internal::file::load(
@@ -60,6 +63,62 @@ fn load_and_typecheck(
     }
 }
 
+fn load_with_parse_cache<'a>(
+    arena: &'a Bump,
+    filename: PathBuf,
+    parse_cache: ParseCache<'a>,
+) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
+    let load_start =
+        LoadStart::from_path(arena, filename, RenderTarget::Generic)?.with_parse_cache(parse_cache);
+    let load_config = LoadConfig {
+        target_info: TARGET_INFO,
+        render: RenderTarget::Generic,
+        threading: Threading::Single,
+        exec_mode: ExecutionMode::Check,
+        property_testing: PropertyTesting::default(),
+        opt_level: OptLevel::Normal,
+    };
+
+    roc_load_internal::file::load(
+        arena,
+        load_start,
+        Default::default(),
+        Default::default(),
+        load_config,
+    )
+}
+
+fn load_with_solved_cache(
+    arena: &Bump,
+    filename: PathBuf,
+    solved_cache: SolvedCache,
+    property_testing: PropertyTesting,
+) -> Result<LoadedModule, LoadingProblem> {
+    use LoadResult::*;
+
+    let load_start = LoadStart::from_path(arena, filename, RenderTarget::Generic)?
+        .with_solved_cache(solved_cache);
+    let load_config = LoadConfig {
+        target_info: TARGET_INFO,
+        render: RenderTarget::Generic,
+        threading: Threading::Single,
+        exec_mode: ExecutionMode::Check,
+        property_testing,
+        opt_level: OptLevel::Normal,
+    };
+
+    match roc_load_internal::file::load(
+        arena,
+        load_start,
+        Default::default(),
+        Default::default(),
+        load_config,
+    )? {
+        Monomorphized(_) => unreachable!(""),
+        TypeChecked(module) => Ok(module),
+    }
+}
+
 const TARGET_INFO: roc_target::TargetInfo = roc_target::TargetInfo::default_x86_64();
 
 // HELPERS
@@ -96,7 +155,7 @@ fn multiple_modules(subdir: &str, files: Vec<(&str, &str)>) -> Result<LoadedModu
 
     match multiple_modules_help(subdir, arena, files) {
         Err(io_error) => panic!("IO trouble: {:?}", io_error),
-        Ok(Err(LoadingProblem::FormattedReport(buf))) => Err(buf),
+        Ok(Err(LoadingProblem::FormattedReport(buf, _))) => Err(buf),
         Ok(Err(loading_problem)) => Err(format!("{:?}", loading_problem)),
         Ok(Ok(mut loaded_module)) => {
             let home = loaded_module.module_id;
@@ -182,7 +241,7 @@ fn load_fixture(
     let loaded = load_and_typecheck(&arena, filename, subs_by_module, TARGET_INFO);
     let mut loaded_module = match loaded {
         Ok(x) => x,
-        Err(roc_load_internal::file::LoadingProblem::FormattedReport(report)) => {
+        Err(roc_load_internal::file::LoadingProblem::FormattedReport(report, _)) => {
             println!("{}", report);
             panic!("{}", report);
         }
@@ -580,7 +639,7 @@ fn parse_problem() {
             report,
             indoc!(
                 "
-                    ── UNFINISHED LIST ──────────────────────────────────── tmp/parse_problem/Main ─
+                    ── UNFINISHED LIST [E0439] ──────────────────────────── tmp/parse_problem/Main ─
 
                     I cannot find the end of this list:
 
@@ -626,6 +685,194 @@ fn imported_file_not_found() {
     );
 }
 
+#[test]
+fn parse_cache_records_module_graph() {
+    let src_dir = fixtures_dir().join("interface_with_deps");
+    let arena = Bump::new();
+    let parse_cache = ParseCache::default();
+
+    // The second load reuses every module the first one parsed.
+    for _ in 0..2 {
+        let loaded =
+            load_with_parse_cache(&arena, src_dir.join("Primary.roc"), parse_cache.clone());
+
+        match loaded {
+            Ok(LoadResult::TypeChecked(mut loaded_module)) => {
+                let home = loaded_module.module_id;
+
+                assert_eq!(
+                    loaded_module.can_problems.remove(&home).unwrap_or_default(),
+                    Vec::new()
+                );
+            }
+            _ => panic!("Primary.roc failed to load"),
+        }
+
+        assert_eq!(
+            parse_cache.files(),
+            vec![
+                src_dir.join("Dep1.roc"),
+                src_dir.join("Dep2.roc"),
+                src_dir.join("Dep3").join("Blah.roc"),
+                src_dir.join("Primary.roc"),
+                src_dir.join("Res.roc"),
+            ]
+        );
+    }
+}
+
+#[test]
+fn parse_cache_records_missing_file() {
+    let src_dir = fixtures_dir().join("no_deps");
+    let arena = Bump::new();
+    let parse_cache = ParseCache::default();
+
+    let loaded = load_with_parse_cache(&arena, src_dir.join("MissingDep.roc"), parse_cache.clone());
+
+    assert!(matches!(loaded, Err(LoadingProblem::FormattedReport(..))));
+    assert!(parse_cache
+        .files()
+        .contains(&src_dir.join("ThisFileIsMissing.roc")));
+}
+
+#[test]
+fn solved_cache_reuses_unchanged_modules() {
+    let src_dir = fixtures_dir().join("interface_with_deps");
+    let cache_dir = roc_test_utils::TmpDir::new("tmp/solved_cache");
+
+    // The first load solves every module and caches it; the second one reads them all back.
+    for expected_hits in [0, 5] {
+        let arena = Bump::new();
+        let solved_cache = SolvedCache::new(cache_dir.path().to_path_buf());
+        let loaded_module = load_with_solved_cache(
+            &arena,
+            src_dir.join("Primary.roc"),
+            solved_cache.clone(),
+            PropertyTesting::default(),
+        )
+        .unwrap_or_else(|problem| panic!("{:?}", problem));
+
+        assert_eq!(solved_cache.hit_count(), expected_hits);
+
+        // Primary, Dep1, Dep2, Dep3.Blah and Res
+        assert_eq!(std::fs::read_dir(solved_cache.dir()).unwrap().count(), 5);
+
+        expect_types(
+            loaded_module,
+            hashmap! {
+                "blah2" => "Float *",
+                "blah3" => "Str",
+                "str" => "Str",
+                "alwaysThree" => "* -> Float *",
+                "identity" => "a -> a",
+                "z" => "Float *",
+                "w" => "Dep1.Identity {}",
+                "succeed" => "a -> Dep1.Identity a",
+                "yay" => "Res.Res {} err",
+                "withDefault" => "Res.Res a err, a -> a",
+            },
+        );
+    }
+}
+
+#[test]
+fn solved_cache_ignores_property_testing_seed() {
+    let src_dir = fixtures_dir().join("interface_with_deps");
+    let cache_dir = roc_test_utils::TmpDir::new("tmp/solved_cache_seed");
+
+    // `roc test` picks a new seed on every run, which must not keep it from using the cache.
+    for (seed, expected_hits) in [(1, 0), (2, 5)] {
+        let arena = Bump::new();
+        let solved_cache = SolvedCache::new(cache_dir.path().to_path_buf());
+        let property_testing = PropertyTesting {
+            seed,
+            ..PropertyTesting::default()
+        };
+
+        load_with_solved_cache(
+            &arena,
+            src_dir.join("Primary.roc"),
+            solved_cache.clone(),
+            property_testing,
+        )
+        .unwrap_or_else(|problem| panic!("{:?}", problem));
+
+        assert_eq!(solved_cache.hit_count(), expected_hits);
+    }
+}
+
+#[test]
+fn solved_cache_misses_changed_modules() {
+    let cache_dir = roc_test_utils::TmpDir::new("tmp/solved_cache_changed");
+    let src_dir = roc_test_utils::TmpDir::new("tmp/solved_cache_changed_src");
+    let filename = src_dir.path().join("Main.roc");
+
+    let sources = [
+        "interface Main exposes [x] imports []\n\nx = 1\n",
+        "interface Main exposes [x] imports []\n\nx = \"one\"\n",
+    ];
+
+    for (src, expected_type) in sources.iter().zip(["Num *", "Str"]) {
+        std::fs::write(&filename, src).unwrap();
+
+        let arena = Bump::new();
+        let solved_cache = SolvedCache::new(cache_dir.path().to_path_buf());
+        let loaded_module = load_with_solved_cache(
+            &arena,
+            filename.clone(),
+            solved_cache.clone(),
+            PropertyTesting::default(),
+        )
+        .unwrap_or_else(|problem| panic!("{:?}", problem));
+
+        assert_eq!(solved_cache.hit_count(), 0);
+        expect_types(loaded_module, hashmap! { "x" => expected_type });
+    }
+
+    // one file for each version of Main
+    let solved_cache = SolvedCache::new(cache_dir.path().to_path_buf());
+    assert_eq!(std::fs::read_dir(solved_cache.dir()).unwrap().count(), 2);
+}
+
+#[test]
+fn solved_cache_evicts_beyond_max_size() {
+    let src_dir = fixtures_dir().join("interface_with_deps");
+    let cache_dir = roc_test_utils::TmpDir::new("tmp/solved_cache_evict");
+
+    // Every load starts by evicting what the previous one wrote, so nothing is ever reused.
+    for _ in 0..2 {
+        let arena = Bump::new();
+        let solved_cache = SolvedCache::new(cache_dir.path().to_path_buf()).with_max_size(0);
+        load_with_solved_cache(
+            &arena,
+            src_dir.join("Primary.roc"),
+            solved_cache.clone(),
+            PropertyTesting::default(),
+        )
+        .unwrap_or_else(|problem| panic!("{:?}", problem));
+
+        assert_eq!(solved_cache.hit_count(), 0);
+        assert_eq!(std::fs::read_dir(solved_cache.dir()).unwrap().count(), 5);
+    }
+}
+
+#[test]
+fn solved_cache_removes_other_compilers() {
+    let cache_dir = roc_test_utils::TmpDir::new("tmp/solved_cache_other_compilers");
+    let other_compiler_dir = cache_dir.path().join("0123456789abcdef");
+    let unrelated_dir = cache_dir.path().join("not-a-compiler");
+
+    std::fs::create_dir_all(&other_compiler_dir).unwrap();
+    std::fs::write(other_compiler_dir.join("0000000000000000"), "stale").unwrap();
+    std::fs::create_dir_all(&unrelated_dir).unwrap();
+
+    let solved_cache = SolvedCache::new(cache_dir.path().to_path_buf());
+
+    assert_ne!(solved_cache.dir(), other_compiler_dir.as_path());
+    assert!(!other_compiler_dir.exists());
+    assert!(unrelated_dir.exists());
+}
+
 #[test]
 fn platform_does_not_exist() {
     let modules = vec![(
@@ -769,7 +1016,7 @@ fn opaque_wrapped_unwrapped_outside_defining_module() {
         err,
         indoc!(
             r#"
-                ── OPAQUE TYPE DECLARED OUTSIDE SCOPE ─ ...rapped_outside_defining_module/Main ─
+                ── OPAQUE TYPE DECLARED OUTSIDE SCOPE [E0209] ─ ...utside_defining_module/Main ─
 
                 The unwrapped opaque type Age referenced here:
 
@@ -783,7 +1030,7 @@ fn opaque_wrapped_unwrapped_outside_defining_module() {
 
                 Note: Opaque types can only be wrapped and unwrapped in the module they are defined in!
 
-                ── OPAQUE TYPE DECLARED OUTSIDE SCOPE ─ ...rapped_outside_defining_module/Main ─
+                ── OPAQUE TYPE DECLARED OUTSIDE SCOPE [E0209] ─ ...utside_defining_module/Main ─
 
                 The unwrapped opaque type Age referenced here:
 
@@ -797,7 +1044,7 @@ fn opaque_wrapped_unwrapped_outside_defining_module() {
 
                 Note: Opaque types can only be wrapped and unwrapped in the module they are defined in!
 
-                ── UNUSED IMPORT ─── tmp/opaque_wrapped_unwrapped_outside_defining_module/Main ─
+                ── UNUSED IMPORT [E0102] ─ ...e_wrapped_unwrapped_outside_defining_module/Main ─
 
                 Nothing from Age is used in this module.
 
@@ -812,6 +1059,32 @@ fn opaque_wrapped_unwrapped_outside_defining_module() {
     );
 }
 
+#[test]
+fn hidden_builtins_are_not_exposed() {
+    let modules = vec![(
+        "Main",
+        indoc!(
+            r#"
+                interface Main exposes [eq, eqElems] imports [Bool.{ structuralNotEq }]
+
+                eq = Bool.structuralEq 1 1
+
+                eqElems = List.isEqElems [1] [1]
+                "#
+        ),
+    )];
+
+    let report = multiple_modules("hidden_builtins_are_not_exposed", modules).unwrap_err();
+
+    for ident in ["structuralEq", "structuralNotEq", "isEqElems"] {
+        assert!(
+            report.contains(&format!("does not expose `{}`", ident)),
+            "{}",
+            report
+        );
+    }
+}
+
 #[test]
 fn issue_2863_module_type_does_not_exist() {
     let modules = vec![
@@ -852,7 +1125,7 @@ fn issue_2863_module_type_does_not_exist() {
                 report,
                 indoc!(
                     "
-                        ── UNRECOGNIZED NAME ────────── tmp/issue_2863_module_type_does_not_exist/Main ─
+                        ── UNRECOGNIZED NAME [E0207] ── tmp/issue_2863_module_type_does_not_exist/Main ─
 
                         Nothing is named `DoesNotExist` in this scope.
 
@@ -207,7 +207,6 @@ macro_rules! map_symbol_to_lowlevel {
                 LowLevel::NumDivUnchecked => unreachable!(),
 
                 // these are used internally and not tied to a symbol
-                LowLevel::Hash => unimplemented!(),
                 LowLevel::PtrCast => unimplemented!(),
                 LowLevel::RefCountInc => unimplemented!(),
                 LowLevel::RefCountDec => unimplemented!(),
@@ -306,10 +305,11 @@ map_symbol_to_lowlevel! {
     NumShiftRightBy <= NUM_SHIFT_RIGHT,
     NumShiftRightZfBy <= NUM_SHIFT_RIGHT_ZERO_FILL,
     NumToStr <= NUM_TO_STR,
-    Eq <= BOOL_EQ,
-    NotEq <= BOOL_NEQ,
+    Eq <= BOOL_STRUCTURAL_EQ,
+    NotEq <= BOOL_STRUCTURAL_NOT_EQ,
     And <= BOOL_AND,
     Or <= BOOL_OR,
     Not <= BOOL_NOT,
+    Hash <= DICT_HASH_KEY,
     Unreachable <= LIST_UNREACHABLE,
 }
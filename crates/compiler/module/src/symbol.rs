@@ -50,6 +50,9 @@ const PRETTY_PRINT_DEBUG_SYMBOLS: bool = true;
 pub const DERIVABLE_ABILITIES: &[(Symbol, &[Symbol])] = &[
     (Symbol::ENCODE_ENCODING, &[Symbol::ENCODE_TO_ENCODER]),
     (Symbol::DECODE_DECODING, &[Symbol::DECODE_DECODER]),
+    (Symbol::HASH_HASH_ABILITY, &[Symbol::HASH_HASH]),
+    (Symbol::BOOL_EQ, &[Symbol::BOOL_IS_EQ]),
+    (Symbol::GENERATE_GENERATOR, &[Symbol::GENERATE_GENERATE]),
 ];
 
 /// In Debug builds only, Symbol has a name() method that lets
@@ -89,6 +92,17 @@ impl Symbol {
         self.module_id().is_builtin()
     }
 
+    /// Builtins which their modules expose only so that derived implementations can use them.
+    /// Modules other than the builtins can't refer to them.
+    pub fn is_hidden_builtin(self) -> bool {
+        [
+            Symbol::BOOL_STRUCTURAL_EQ,
+            Symbol::BOOL_STRUCTURAL_NOT_EQ,
+            Symbol::LIST_IS_EQ_ELEMS,
+        ]
+        .contains(&self)
+    }
+
     pub fn is_derivable_ability(self) -> bool {
         self.derivable_ability().is_some()
     }
@@ -1175,8 +1189,11 @@ define_builtins! {
         4 BOOL_OR: "or"
         5 BOOL_NOT: "not"
         6 BOOL_XOR: "xor"
-        7 BOOL_EQ: "isEq"
-        8 BOOL_NEQ: "isNotEq"
+        7 BOOL_STRUCTURAL_EQ: "structuralEq"
+        8 BOOL_STRUCTURAL_NOT_EQ: "structuralNotEq"
+        9 BOOL_EQ: "Eq"
+        10 BOOL_IS_EQ: "isEq"
+        11 BOOL_IS_NOT_EQ: "isNotEq"
     }
     5 STR: "Str" => {
         0 STR_STR: "Str" imported // the Str.Str type alias
@@ -1306,6 +1323,7 @@ define_builtins! {
         72 LIST_SUBLIST_LOWLEVEL: "sublistLowlevel"
         73 LIST_CAPACITY: "capacity"
         74 LIST_MAP_TRY: "mapTry"
+        75 LIST_IS_EQ_ELEMS: "isEqElems"
     }
     7 RESULT: "Result" => {
         0 RESULT_RESULT: "Result" // the Result.Result type alias
@@ -1342,6 +1360,7 @@ define_builtins! {
 
         15 DICT_WITH_CAPACITY: "withCapacity"
         16 DICT_CAPACITY: "capacity"
+        17 DICT_HASH_KEY: "hashKey"
     }
     9 SET: "Set" => {
         0 SET_SET: "Set" // the Set.Set type alias
@@ -1393,6 +1412,7 @@ define_builtins! {
         23 ENCODE_APPEND_WITH: "appendWith"
         24 ENCODE_APPEND: "append"
         25 ENCODE_TO_BYTES: "toBytes"
+        26 ENCODE_TUPLE: "tuple"
     }
     12 DECODE: "Decode" => {
         0 DECODE_DECODE_ERROR: "DecodeError"
@@ -1422,10 +1442,59 @@ define_builtins! {
         24 DECODE_DECODE_WITH: "decodeWith"
         25 DECODE_FROM_BYTES_PARTIAL: "fromBytesPartial"
         26 DECODE_FROM_BYTES: "fromBytes"
+        27 DECODE_TUPLE: "tuple"
+        28 DECODE_TAG: "tag"
     }
     13 JSON: "Json" => {
         0 JSON_JSON: "Json"
     }
-
-    num_modules: 14 // Keep this count up to date by hand! (TODO: see the mut_map! macro for how we could determine this count correctly in the macro)
+    14 HASH: "Hash" => {
+        0 HASH_HASH_ABILITY: "Hash"
+        1 HASH_HASH: "hash"
+        2 HASH_HASHER: "Hasher"
+        3 HASH_ADD_BYTES: "addBytes"
+        4 HASH_ADD_U8: "addU8"
+        5 HASH_ADD_U16: "addU16"
+        6 HASH_ADD_U32: "addU32"
+        7 HASH_ADD_U64: "addU64"
+        8 HASH_ADD_U128: "addU128"
+        9 HASH_ADD_I8: "addI8"
+        10 HASH_ADD_I16: "addI16"
+        11 HASH_ADD_I32: "addI32"
+        12 HASH_ADD_I64: "addI64"
+        13 HASH_ADD_I128: "addI128"
+        14 HASH_COMPLETE: "complete"
+        15 HASH_HASH_STR_BYTES: "hashStrBytes"
+        16 HASH_HASH_LIST: "hashList"
+    }
+    15 GENERATE: "Generate" => {
+        0 GENERATE_GENERATOR: "Generator"
+        1 GENERATE_GENERATE: "generate"
+        2 GENERATE_RNG_TYPE: "Rng"
+        3 GENERATE_RNG: "rng"
+        4 GENERATE_SPLIT: "split"
+        5 GENERATE_NEXT_U64: "nextU64"
+        6 GENERATE_BOUNDED: "bounded"
+        7 GENERATE_SMALLER: "smaller"
+        8 GENERATE_CHOOSE_TAG: "chooseTag"
+        9 GENERATE_U8: "u8"
+        10 GENERATE_U16: "u16"
+        11 GENERATE_U32: "u32"
+        12 GENERATE_U64: "u64"
+        13 GENERATE_U128: "u128"
+        14 GENERATE_I8: "i8"
+        15 GENERATE_I16: "i16"
+        16 GENERATE_I32: "i32"
+        17 GENERATE_I64: "i64"
+        18 GENERATE_I128: "i128"
+        19 GENERATE_NAT: "nat"
+        20 GENERATE_F32: "f32"
+        21 GENERATE_F64: "f64"
+        22 GENERATE_DEC: "dec"
+        23 GENERATE_STR: "str"
+        24 GENERATE_LIST: "list"
+        25 GENERATE_SEARCH: "search"
+    }
+
+    num_modules: 16 // Keep this count up to date by hand! (TODO: see the mut_map! macro for how we could determine this count correctly in the macro)
 }
@@ -308,7 +308,7 @@ impl<'a> ParamMap<'a> {
                     stack.push(cont);
                 }
 
-                Expect { remainder, .. } => stack.push(remainder),
+                Expect { remainder, .. } | Dbg { remainder, .. } => stack.push(remainder),
 
                 Switch {
                     branches,
@@ -816,7 +816,7 @@ impl<'a> BorrowInfState<'a> {
                 self.collect_stmt(param_map, default_branch.1);
             }
 
-            Expect { remainder, .. } => {
+            Expect { remainder, .. } | Dbg { remainder, .. } => {
                 self.collect_stmt(param_map, remainder);
             }
 
@@ -987,7 +987,7 @@ fn call_info_stmt<'a>(arena: &'a Bump, stmt: &Stmt<'a>, info: &mut CallInfo<'a>)
                 stack.push(default_branch.1);
             }
 
-            Expect { remainder, .. } => stack.push(remainder),
+            Expect { remainder, .. } | Dbg { remainder, .. } => stack.push(remainder),
 
             Refcounting(_, _) => unreachable!("these have not been introduced yet"),
 
@@ -119,6 +119,13 @@ pub fn occurring_variables(stmt: &Stmt<'_>) -> (MutSet<Symbol>, MutSet<Symbol>)
                 stack.push(remainder);
             }
 
+            Dbg {
+                symbol, remainder, ..
+            } => {
+                result.insert(*symbol);
+                stack.push(remainder);
+            }
+
             Jump(_, arguments) => {
                 result.extend(arguments.iter().copied());
             }
@@ -1199,6 +1206,26 @@ impl<'a> Context<'a> {
                 (expect, b_live_vars)
             }
 
+            Dbg {
+                symbol,
+                region,
+                remainder,
+            } => {
+                let (b, mut b_live_vars) = self.visit_stmt(codegen, remainder);
+
+                let dbg = self.arena.alloc(Stmt::Dbg {
+                    symbol: *symbol,
+                    region: *region,
+                    remainder: b,
+                });
+
+                let dbg = self.add_inc_before_consume_all(&[*symbol], dbg, &b_live_vars);
+
+                b_live_vars.insert(*symbol);
+
+                (dbg, b_live_vars)
+            }
+
             RuntimeError(_) | Refcounting(_, _) => (stmt, MutSet::default()),
         }
     }
@@ -1313,6 +1340,13 @@ pub fn collect_stmt(
             collect_stmt(remainder, jp_live_vars, vars)
         }
 
+        Dbg {
+            symbol, remainder, ..
+        } => {
+            vars.insert(*symbol);
+            collect_stmt(remainder, jp_live_vars, vars)
+        }
+
         Join {
             id: j,
             parameters,
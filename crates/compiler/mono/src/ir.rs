@@ -11,13 +11,13 @@ use roc_builtins::bitcode::{FloatWidth, IntWidth};
 use roc_can::abilities::SpecializationId;
 use roc_can::expr::{AnnotatedMark, ClosureData, IntValue};
 use roc_can::module::ExposedByModule;
-use roc_collections::all::{default_hasher, BumpMap, BumpMapDefault, MutMap};
+use roc_collections::all::{default_hasher, BumpMap, BumpMapDefault, MutMap, MutSet};
 use roc_collections::VecMap;
 use roc_debug_flags::dbg_do;
 #[cfg(debug_assertions)]
 use roc_debug_flags::{
-    ROC_PRINT_IR_AFTER_REFCOUNT, ROC_PRINT_IR_AFTER_RESET_REUSE, ROC_PRINT_IR_AFTER_SPECIALIZATION,
-    ROC_PRINT_RUNTIME_ERROR_GEN,
+    ROC_PRINT_IR_AFTER_OPTIMIZATION, ROC_PRINT_IR_AFTER_REFCOUNT, ROC_PRINT_IR_AFTER_RESET_REUSE,
+    ROC_PRINT_IR_AFTER_SPECIALIZATION, ROC_PRINT_RUNTIME_ERROR_GEN,
 };
 use roc_derive::SharedDerivedModule;
 use roc_error_macros::{internal_error, todo_abilities};
@@ -42,6 +42,9 @@ pub fn pretty_print_ir_symbols() -> bool {
     dbg_do!(ROC_PRINT_IR_AFTER_SPECIALIZATION, {
         return true;
     });
+    dbg_do!(ROC_PRINT_IR_AFTER_OPTIMIZATION, {
+        return true;
+    });
     dbg_do!(ROC_PRINT_IR_AFTER_RESET_REUSE, {
         return true;
     });
@@ -104,6 +107,15 @@ pub enum OptLevel {
     Optimize,
 }
 
+impl OptLevel {
+    /// Whether to inline and simplify the mono IR before refcounts are inserted. Only the dev
+    /// backends (used for `Development`) need this, because they don't optimize the code they
+    /// generate. LLVM does the same work itself, so the other levels keep the IR as specialized.
+    pub fn optimizes_mono_ir(self) -> bool {
+        matches!(self, OptLevel::Development)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct EntryPoint<'a> {
     pub symbol: Symbol,
@@ -411,6 +423,26 @@ impl<'a> Proc<'a> {
         );
     }
 
+    pub fn optimize_procedures<'i>(
+        arena: &'a Bump,
+        home: ModuleId,
+        ident_ids: &'i mut IdentIds,
+        update_mode_ids: &'i mut UpdateModeIds,
+        call_spec_ids: &'i mut CallSpecIds,
+        entry_points: &MutSet<Symbol>,
+        procs: &mut MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>,
+    ) {
+        crate::optimize::optimize_procs(
+            arena,
+            home,
+            ident_ids,
+            update_mode_ids,
+            call_spec_ids,
+            entry_points,
+            procs,
+        );
+    }
+
     pub fn insert_reset_reuse_operations<'i>(
         arena: &'a Bump,
         home: ModuleId,
@@ -1513,6 +1545,14 @@ pub enum Stmt<'a> {
         /// what happens after the expect
         remainder: &'a Stmt<'a>,
     },
+    Dbg {
+        /// the value to print
+        symbol: Symbol,
+        /// the region of the `dbg` value in the source, used to find its type when printing
+        region: Region,
+        /// what happens after the dbg
+        remainder: &'a Stmt<'a>,
+    },
     /// a join point `join f <params> = <continuation> in remainder`
     Join {
         id: JoinPointId,
@@ -1719,7 +1759,7 @@ impl<'a> Call<'a> {
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct CallSpecId {
-    id: u32,
+    pub(crate) id: u32,
 }
 
 impl CallSpecId {
@@ -1734,7 +1774,7 @@ impl CallSpecId {
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct UpdateModeId {
-    id: u32,
+    pub(crate) id: u32,
 }
 
 impl UpdateModeId {
@@ -1762,6 +1802,35 @@ impl UpdateModeIds {
         self.next += 1;
         id
     }
+
+    /// Make sure that `id` is never handed out again
+    pub(crate) fn skip_past(&mut self, id: UpdateModeId) {
+        self.next = self.next.max(id.id + 1);
+    }
+}
+
+/// Hands out `CallSpecId`s after specialization, when the `Env` that numbered the calls is gone
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CallSpecIds {
+    next: u32,
+}
+
+impl CallSpecIds {
+    pub const fn new() -> Self {
+        // call_specialization_counter=0 is reserved
+        Self { next: 1 }
+    }
+
+    pub fn next_id(&mut self) -> CallSpecId {
+        let id = CallSpecId { id: self.next };
+        self.next += 1;
+        id
+    }
+
+    /// Make sure that `id` is never handed out again
+    pub(crate) fn skip_past(&mut self, id: CallSpecId) {
+        self.next = self.next.max(id.id + 1);
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -2100,6 +2169,15 @@ impl<'a> Stmt<'a> {
                 .append(alloc.hardline())
                 .append(remainder.to_doc(alloc)),
 
+            Dbg {
+                symbol, remainder, ..
+            } => alloc
+                .text("dbg ")
+                .append(symbol_to_doc(alloc, *symbol))
+                .append(";")
+                .append(alloc.hardline())
+                .append(remainder.to_doc(alloc)),
+
             Ret(symbol) => alloc
                 .text("ret ")
                 .append(symbol_to_doc(alloc, *symbol))
@@ -2654,7 +2732,10 @@ fn pattern_to_when<'a>(
             (env.unique_symbol(), Loc::at_zero(RuntimeError(error)))
         }
 
-        AppliedTag { .. } | RecordDestructure { .. } | UnwrappedOpaque { .. } => {
+        AppliedTag { .. }
+        | RecordDestructure { .. }
+        | TupleDestructure { .. }
+        | UnwrappedOpaque { .. } => {
             let symbol = env.unique_symbol();
 
             let wrapped_body = When {
@@ -4169,8 +4250,167 @@ pub fn with_hole<'a>(
 
         EmptyRecord => let_empty_struct(assigned, hole),
 
+        Tuple {
+            tuple_var, elems, ..
+        } => {
+            let sorted_elems = match crate::layout::sort_tuple_elems(
+                env.arena,
+                tuple_var,
+                env.subs,
+                env.target_info,
+            ) {
+                Ok(elems) => elems,
+                Err(_) => return Stmt::RuntimeError("Can't create tuple with improper layout"),
+            };
+
+            let mut elems = Vec::from_iter_in(elems.into_iter().map(Some), env.arena);
+
+            let mut elem_symbols = Vec::with_capacity_in(elems.len(), env.arena);
+            let mut can_elems = Vec::with_capacity_in(elems.len(), env.arena);
+
+            enum Elem {
+                FunctionOrUnspecialized(Symbol, Variable),
+                ValueSymbol,
+                Elem(Variable, Loc<roc_can::expr::Expr>),
+            }
+
+            for (index, variable, _) in sorted_elems.into_iter() {
+                // TODO how should function pointers be handled here?
+                use ReuseSymbol::*;
+                let (elem_var, loc_elem) = elems[index]
+                    .take()
+                    .expect("tuple element used more than once");
+
+                match can_reuse_symbol(env, procs, &loc_elem.value, elem_var) {
+                    Imported(symbol) | LocalFunction(symbol) | UnspecializedExpr(symbol) => {
+                        elem_symbols.push(symbol);
+                        can_elems.push(Elem::FunctionOrUnspecialized(symbol, variable));
+                    }
+                    Value(symbol) => {
+                        let reusable = procs.symbol_specializations.get_or_insert(
+                            env,
+                            layout_cache,
+                            symbol,
+                            elem_var,
+                        );
+                        elem_symbols.push(reusable);
+                        can_elems.push(Elem::ValueSymbol);
+                    }
+                    NotASymbol => {
+                        elem_symbols.push(env.unique_symbol());
+                        can_elems.push(Elem::Elem(elem_var, loc_elem));
+                    }
+                }
+            }
+
+            // creating a tuple from the var will unpack it if it's just a single element.
+            let layout = match layout_cache.from_var(env.arena, tuple_var, env.subs) {
+                Ok(layout) => layout,
+                Err(_) => return Stmt::RuntimeError("Can't create tuple with improper layout"),
+            };
+
+            let elem_symbols = elem_symbols.into_bump_slice();
+
+            let mut stmt = if let [only_elem] = elem_symbols {
+                let mut hole = hole.clone();
+                substitute_in_exprs(env.arena, &mut hole, assigned, *only_elem);
+                hole
+            } else {
+                Stmt::Let(assigned, Expr::Struct(elem_symbols), layout, hole)
+            };
+
+            for (elem, symbol) in can_elems.into_iter().rev().zip(elem_symbols.iter().rev()) {
+                match elem {
+                    Elem::ValueSymbol => {
+                        // this symbol is already defined; nothing to do
+                    }
+                    Elem::FunctionOrUnspecialized(symbol, variable) => {
+                        stmt = specialize_symbol(
+                            env,
+                            procs,
+                            layout_cache,
+                            Some(variable),
+                            symbol,
+                            stmt,
+                            symbol,
+                        );
+                    }
+                    Elem::Elem(elem_var, loc_elem) => {
+                        stmt = with_hole(
+                            env,
+                            loc_elem.value,
+                            elem_var,
+                            procs,
+                            layout_cache,
+                            *symbol,
+                            env.arena.alloc(stmt),
+                        );
+                    }
+                }
+            }
+
+            stmt
+        }
+
         Expect { .. } => unreachable!("I think this is unreachable"),
 
+        Dbg {
+            loc_message,
+            loc_continuation,
+            variable: dbg_var,
+            symbol: dbg_symbol,
+        } => {
+            let region = loc_message.region;
+
+            match loc_continuation {
+                Some(loc_continuation) => {
+                    let rest = with_hole(
+                        env,
+                        loc_continuation.value,
+                        variable,
+                        procs,
+                        layout_cache,
+                        assigned,
+                        hole,
+                    );
+
+                    let stmt = Stmt::Dbg {
+                        symbol: dbg_symbol,
+                        region,
+                        remainder: env.arena.alloc(rest),
+                    };
+
+                    with_hole(
+                        env,
+                        loc_message.value,
+                        dbg_var,
+                        procs,
+                        layout_cache,
+                        dbg_symbol,
+                        env.arena.alloc(stmt),
+                    )
+                }
+                None => {
+                    // `dbg` evaluates to the value it prints, so print what we assign
+                    let stmt = Stmt::Dbg {
+                        symbol: assigned,
+                        region,
+                        remainder: hole,
+                    };
+
+                    with_hole(
+                        env,
+                        loc_message.value,
+                        variable,
+                        procs,
+                        layout_cache,
+                        assigned,
+                        env.arena.alloc(stmt),
+                    )
+                }
+            }
+        }
+
         If {
             cond_var,
             branch_var,
@@ -4530,6 +4770,79 @@ pub fn with_hole<'a>(
             stmt
         }
 
+        TupleAccess {
+            tuple_var,
+            elem_var,
+            index: elem_index,
+            loc_expr,
+            ..
+        } => {
+            let sorted_elems = match crate::layout::sort_tuple_elems(
+                env.arena,
+                tuple_var,
+                env.subs,
+                env.target_info,
+            ) {
+                Ok(elems) => elems,
+                Err(_) => return Stmt::RuntimeError("Can't access tuple with improper layout"),
+            };
+
+            let mut index = None;
+            let mut elem_layouts = Vec::with_capacity_in(sorted_elems.len(), env.arena);
+
+            for (current, (source_index, _, elem_layout)) in sorted_elems.into_iter().enumerate() {
+                elem_layouts.push(elem_layout);
+
+                if source_index == elem_index {
+                    index = Some(current);
+                }
+            }
+
+            let tuple_symbol = possible_reuse_symbol_or_specialize(
+                env,
+                procs,
+                layout_cache,
+                &loc_expr.value,
+                tuple_var,
+            );
+
+            let mut stmt = match elem_layouts.as_slice() {
+                [_] => {
+                    let mut hole = hole.clone();
+                    substitute_in_exprs(env.arena, &mut hole, assigned, tuple_symbol);
+
+                    hole
+                }
+                _ => {
+                    let expr = Expr::StructAtIndex {
+                        index: index.expect("element not in its own type") as u64,
+                        field_layouts: elem_layouts.into_bump_slice(),
+                        structure: tuple_symbol,
+                    };
+
+                    let layout = layout_cache
+                        .from_var(env.arena, elem_var, env.subs)
+                        .unwrap_or_else(|err| {
+                            panic!("TODO turn fn_var into a RuntimeError {:?}", err)
+                        });
+
+                    Stmt::Let(assigned, expr, layout, hole)
+                }
+            };
+
+            stmt = assign_to_symbol(
+                env,
+                procs,
+                layout_cache,
+                tuple_var,
+                *loc_expr,
+                tuple_symbol,
+                stmt,
+            );
+
+            stmt
+        }
+
         Accessor(accessor_data) => {
             let field_var = accessor_data.field_var;
             let fresh_record_symbol = env.unique_symbol();
@@ -5368,6 +5681,10 @@ fn late_resolve_ability_specialization<'a>(
                         // The immediate is an ability member itself, so it must be resolved!
                         late_resolve_ability_specialization(env, imm, None, specialization_var)
                     }
+                    roc_derive_key::Derived::SingleLambdaSetImmediate(imm) => {
+                        // This is a plain function, so there is nothing more to resolve.
+                        imm
+                    }
                     roc_derive_key::Derived::Key(derive_key) => {
                         let mut derived_module = env
                             .derived_module
@@ -6140,6 +6457,37 @@ pub fn from_can<'a>(
             stmt
         }
 
+        Dbg {
+            loc_message,
+            loc_continuation,
+            variable: dbg_var,
+            symbol: dbg_symbol,
+        } => {
+            let rest = match loc_continuation {
+                Some(loc_continuation) => {
+                    from_can(env, variable, loc_continuation.value, procs, layout_cache)
+                }
+                // `dbg` evaluates to the value it prints
+                None => Stmt::Ret(dbg_symbol),
+            };
+
+            let stmt = Stmt::Dbg {
+                symbol: dbg_symbol,
+                region: loc_message.region,
+                remainder: env.arena.alloc(rest),
+            };
+
+            with_hole(
+                env,
+                loc_message.value,
+                dbg_var,
+                procs,
+                layout_cache,
+                dbg_symbol,
+                env.arena.alloc(stmt),
+            )
+        }
+
         LetRec(defs, cont, _cycle_mark) => {
             // because Roc is strict, only functions can be recursive!
             for def in defs.into_iter() {
@@ -6504,6 +6852,23 @@ fn substitute_in_stmt_help<'a>(
             Some(arena.alloc(expect))
         }
 
+        Dbg {
+            symbol,
+            region,
+            remainder,
+        } => {
+            let new_remainder =
+                substitute_in_stmt_help(arena, remainder, subs).unwrap_or(remainder);
+
+            let dbg = Dbg {
+                symbol: substitute(subs, *symbol).unwrap_or(*symbol),
+                region: *region,
+                remainder: new_remainder,
+            };
+
+            Some(arena.alloc(dbg))
+        }
+
         Jump(id, args) => {
             let mut did_change = false;
             let new_args = Vec::from_iter_in(
@@ -6892,6 +7257,45 @@ fn store_pattern_help<'a>(
                 return StorePattern::NotProductive(stmt);
             }
         }
+
+        TupleDestructure(destructs, [_single_elem]) => {
+            // a tuple with a single element has the layout of that element
+            return store_pattern_help(
+                env,
+                procs,
+                layout_cache,
+                &destructs[0].pat,
+                outer_symbol,
+                stmt,
+            );
+        }
+        TupleDestructure(destructs, sorted_elems) => {
+            let mut is_productive = false;
+            for (index, destruct) in destructs.iter().enumerate().rev() {
+                match store_tuple_destruct(
+                    env,
+                    procs,
+                    layout_cache,
+                    destruct,
+                    index as u64,
+                    outer_symbol,
+                    sorted_elems,
+                    stmt,
+                ) {
+                    StorePattern::Productive(new) => {
+                        is_productive = true;
+                        stmt = new;
+                    }
+                    StorePattern::NotProductive(new) => {
+                        stmt = new;
+                    }
+                }
+            }
+
+            if !is_productive {
+                return StorePattern::NotProductive(stmt);
+            }
+        }
     }
 
     StorePattern::Productive(stmt)
@@ -7157,6 +7561,70 @@ fn store_record_destruct<'a>(
     StorePattern::Productive(stmt)
 }
 
+#[allow(clippy::too_many_arguments)]
+fn store_tuple_destruct<'a>(
+    env: &mut Env<'a, '_>,
+    procs: &mut Procs<'a>,
+    layout_cache: &mut LayoutCache<'a>,
+    destruct: &TupleDestruct<'a>,
+    index: u64,
+    outer_symbol: Symbol,
+    sorted_elems: &'a [Layout<'a>],
+    mut stmt: Stmt<'a>,
+) -> StorePattern<'a> {
+    use Pattern::*;
+
+    let load = Expr::StructAtIndex {
+        index,
+        field_layouts: sorted_elems,
+        structure: outer_symbol,
+    };
+
+    match &destruct.pat {
+        Identifier(symbol) => {
+            let specialization_symbol = procs
+                .symbol_specializations
+                .remove_single(*symbol)
+                // Can happen when the symbol was never used under this body, and hence has no
+                // requested specialization.
+                .unwrap_or(*symbol);
+
+            stmt = Stmt::Let(
+                specialization_symbol,
+                load,
+                destruct.layout,
+                env.arena.alloc(stmt),
+            );
+        }
+        Underscore => {
+            // as with records, elements that are not bound in the source must not be loaded
+            return StorePattern::NotProductive(stmt);
+        }
+        IntLiteral(_, _)
+        | FloatLiteral(_, _)
+        | DecimalLiteral(_)
+        | EnumLiteral { .. }
+        | BitLiteral { .. }
+        | StrLiteral(_) => {
+            return StorePattern::NotProductive(stmt);
+        }
+
+        _ => {
+            let symbol = env.unique_symbol();
+
+            match store_pattern_help(env, procs, layout_cache, &destruct.pat, symbol, stmt) {
+                StorePattern::Productive(new) => {
+                    stmt = new;
+                    stmt = Stmt::Let(symbol, load, destruct.layout, env.arena.alloc(stmt));
+                }
+                StorePattern::NotProductive(stmt) => return StorePattern::NotProductive(stmt),
+            }
+        }
+    }
+
+    StorePattern::Productive(stmt)
+}
+
 /// We want to re-use symbols that are not function symbols
 /// for any other expression, we create a new symbol, and will
 /// later make sure it gets assigned the correct value.
@@ -8381,6 +8849,7 @@ pub enum Pattern<'a> {
     StrLiteral(Box<str>),
 
     RecordDestructure(Vec<'a, RecordDestruct<'a>>, &'a [Layout<'a>]),
+    TupleDestructure(Vec<'a, TupleDestruct<'a>>, &'a [Layout<'a>]),
     NewtypeDestructure {
         tag_name: TagName,
         arguments: Vec<'a, (Pattern<'a>, Layout<'a>)>,
@@ -8412,6 +8881,15 @@ pub enum DestructType<'a> {
     Guard(Pattern<'a>),
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct TupleDestruct<'a> {
+    /// The position of this element in the source tuple.
+    pub index: usize,
+    pub variable: Variable,
+    pub layout: Layout<'a>,
+    pub pat: Pattern<'a>,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct WhenBranch<'a> {
     pub patterns: Vec<'a, Pattern<'a>>,
@@ -9073,6 +9551,54 @@ fn from_can_pattern_help<'a>(
                 field_layouts.into_bump_slice(),
             ))
         }
+
+        TupleDestructure {
+            whole_var,
+            destructs,
+            ..
+        } => {
+            // sorted elements based on the type
+            let sorted_elems =
+                crate::layout::sort_tuple_elems(env.arena, *whole_var, env.subs, env.target_info)
+                    .map_err(RuntimeError::from)?;
+
+            // tuple patterns are closed, so every element of the type is destructured exactly
+            // once, but the pattern lists them in source order.
+            let mut destructs_by_index = Vec::from_iter_in(destructs.iter().map(Some), env.arena);
+
+            let mut mono_destructs = Vec::with_capacity_in(destructs.len(), env.arena);
+            let mut elem_layouts = Vec::with_capacity_in(sorted_elems.len(), env.arena);
+
+            for (index, variable, elem_layout) in sorted_elems.into_iter() {
+                let destruct = destructs_by_index
+                    .get_mut(index)
+                    .and_then(Option::take)
+                    .expect("tuple pattern does not destructure every element");
+
+                let pat = from_can_pattern_help(
+                    env,
+                    procs,
+                    layout_cache,
+                    &destruct.value.typ.1.value,
+                    assignments,
+                )?;
+
+                mono_destructs.push(TupleDestruct {
+                    index,
+                    variable,
+                    layout: elem_layout,
+                    pat,
+                });
+
+                // the layout of this element is part of the layout of the tuple
+                elem_layouts.push(elem_layout);
+            }
+
+            Ok(Pattern::TupleDestructure(
+                mono_destructs,
+                elem_layouts.into_bump_slice(),
+            ))
+        }
     }
 }
 
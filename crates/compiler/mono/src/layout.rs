@@ -13,7 +13,9 @@ use roc_types::subs::{
     self, Content, FlatType, GetSubsSlice, Label, OptVariable, RecordFields, Subs, UnionTags,
     UnsortedUnionLabels, Variable,
 };
-use roc_types::types::{gather_fields_unsorted_iter, RecordField, RecordFieldsError};
+use roc_types::types::{
+    gather_fields_unsorted_iter, gather_tuple_elems, RecordField, RecordFieldsError, TupleStructure,
+};
 use std::cmp::Ordering;
 use std::collections::hash_map::{DefaultHasher, Entry};
 use std::collections::HashMap;
@@ -248,6 +250,17 @@ impl FieldOrderHash {
         fields.iter().for_each(|field| field.hash(&mut hasher));
         Self(hasher.finish())
     }
+
+    pub fn from_ordered_tuple_elems(elems: &[usize]) -> Self {
+        if elems.is_empty() {
+            // HACK: we must make sure this is always equivalent to a `ZERO_FIELD_HASH`.
+            return Self::ZERO_FIELD_HASH;
+        }
+
+        let mut hasher = DefaultHasher::new();
+        elems.iter().for_each(|elem| elem.hash(&mut hasher));
+        Self(hasher.finish())
+    }
 }
 
 /// Types for code gen must be monomorphic. No type variables allowed!
@@ -1405,6 +1418,12 @@ fn lambda_set_size(subs: &Subs, var: Variable) -> (usize, usize, usize) {
                     }
                     stack.push((*ext, depth_any + 1, depth_lset));
                 }
+                FlatType::Tuple(elems, ext) => {
+                    for var in subs.get_subs_slice(*elems) {
+                        stack.push((*var, depth_any + 1, depth_lset));
+                    }
+                    stack.push((*ext, depth_any + 1, depth_lset));
+                }
                 FlatType::FunctionOrTagUnion(_, _, ext) => {
                     stack.push((*ext, depth_any + 1, depth_lset));
                 }
@@ -1425,7 +1444,10 @@ fn lambda_set_size(subs: &Subs, var: Variable) -> (usize, usize, usize) {
                     }
                     stack.push((*ext, depth_any + 1, depth_lset));
                 }
-                FlatType::Erroneous(_) | FlatType::EmptyRecord | FlatType::EmptyTagUnion => {}
+                FlatType::Erroneous(_)
+                | FlatType::EmptyRecord
+                | FlatType::EmptyTuple
+                | FlatType::EmptyTagUnion => {}
             },
             Content::FlexVar(_)
             | Content::RigidVar(_)
@@ -2461,6 +2483,39 @@ fn layout_from_flat_type<'a>(
                 })
             }
         }
+        Tuple(elems, ext_var) => {
+            let TupleStructure { elems, ext: _ } = match gather_tuple_elems(subs, elems, ext_var) {
+                Ok(structure) => structure,
+                Err(_) => return Err(LayoutProblem::Erroneous),
+            };
+
+            let mut sortables = Vec::with_capacity_in(elems.len(), arena);
+
+            for (index, elem_var) in elems.into_iter().enumerate() {
+                sortables.push((index, Layout::from_var(env, elem_var)?));
+            }
+
+            sortables.sort_by(|(index1, layout1), (index2, layout2)| {
+                cmp_fields(index1, layout1, index2, layout2, target_info)
+            });
+
+            let ordered_elem_indices =
+                Vec::from_iter_in(sortables.iter().map(|(index, _)| *index), arena);
+            let field_order_hash =
+                FieldOrderHash::from_ordered_tuple_elems(ordered_elem_indices.as_slice());
+
+            if sortables.len() == 1 {
+                // If the tuple has only one element, unwrap it.
+                Ok(sortables.pop().unwrap().1)
+            } else {
+                let layouts = Vec::from_iter_in(sortables.into_iter().map(|t| t.1), arena);
+
+                Ok(Layout::Struct {
+                    field_order_hash,
+                    field_layouts: layouts.into_bump_slice(),
+                })
+            }
+        }
         TagUnion(tags, ext_var) => {
             let (tags, ext_var) = tags.unsorted_tags_and_ext(subs, ext_var);
 
@@ -2489,6 +2544,7 @@ fn layout_from_flat_type<'a>(
         EmptyTagUnion => Ok(Layout::VOID),
         Erroneous(_) => Err(LayoutProblem::Erroneous),
         EmptyRecord => Ok(Layout::UNIT),
+        EmptyTuple => Ok(Layout::UNIT),
     }
 }
 
@@ -2554,6 +2610,51 @@ fn sort_record_fields_help<'a>(
     Ok(sorted_fields)
 }
 
+pub type SortedTupleElem<'a> = (usize, Variable, Layout<'a>);
+
+/// Sort the elements of a tuple the same way its struct layout is laid out in memory, keeping
+/// track of each element's position in the source tuple.
+pub fn sort_tuple_elems<'a>(
+    arena: &'a Bump,
+    var: Variable,
+    subs: &Subs,
+    target_info: TargetInfo,
+) -> Result<Vec<'a, SortedTupleElem<'a>>, LayoutProblem> {
+    let mut env = Env {
+        arena,
+        subs,
+        seen: Vec::new_in(arena),
+        target_info,
+    };
+
+    let elems = match subs.get_content_without_compacting(var) {
+        Content::Structure(FlatType::Tuple(elems, ext)) => {
+            match gather_tuple_elems(subs, *elems, *ext) {
+                Ok(TupleStructure { elems, ext: _ }) => elems,
+                Err(_) => return Err(LayoutProblem::Erroneous),
+            }
+        }
+        Content::Structure(FlatType::EmptyTuple) => vec![],
+        Content::Alias(_, _, real_var, _) => {
+            return sort_tuple_elems(arena, *real_var, subs, target_info)
+        }
+        _ => return Err(LayoutProblem::Erroneous),
+    };
+
+    let mut sorted_elems = Vec::with_capacity_in(elems.len(), arena);
+
+    for (index, elem_var) in elems.into_iter().enumerate() {
+        let layout = Layout::from_var(&mut env, elem_var)?;
+        sorted_elems.push((index, elem_var, layout));
+    }
+
+    sorted_elems.sort_by(|(index1, _, layout1), (index2, _, layout2)| {
+        cmp_fields(index1, layout1, index2, layout2, target_info)
+    });
+
+    Ok(sorted_elems)
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum TagOrClosure {
     Tag(TagName),
@@ -3364,6 +3465,21 @@ pub fn ext_var_is_empty_record(_subs: &Subs, _ext_var: Variable) -> bool {
     unreachable!();
 }
 
+#[cfg(debug_assertions)]
+pub fn ext_var_is_empty_tuple(subs: &Subs, ext_var: Variable) -> bool {
+    // the ext_var is empty
+    match gather_tuple_elems(subs, Default::default(), ext_var) {
+        Ok(structure) => structure.elems.is_empty(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(not(debug_assertions))]
+pub fn ext_var_is_empty_tuple(_subs: &Subs, _ext_var: Variable) -> bool {
+    // This should only ever be used in debug_assert! macros
+    unreachable!();
+}
+
 #[cfg(debug_assertions)]
 pub fn ext_var_is_empty_tag_union(subs: &Subs, ext_var: Variable) -> bool {
     // the ext_var is empty
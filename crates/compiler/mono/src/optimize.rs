@@ -0,0 +1,1298 @@
+//! Simplifies the mono IR after specialization, before reset/reuse and refcount operations are
+//! inserted. These passes only run when [crate::ir::OptLevel::optimizes_mono_ir] says so.
+//!
+//! - Calls to small non-recursive procs are inlined. A proc is small when its body is a short
+//!   chain of `let`s ending in a `ret`, like the wrappers around lowlevels in the builtins.
+//! - Arithmetic and comparisons on integer and boolean literals are folded. Operations that would
+//!   overflow are left alone, so they still crash at runtime.
+//! - A `Switch` on a value that is known at compile time is replaced by the branch it takes. Tag
+//!   ids and fields of tags and structs built in the same proc are known too.
+//! - `let`s whose value is never used are dropped, as long as computing the value can't crash.
+//! - Procs that nothing refers to anymore, like the wrappers that were inlined everywhere, are
+//!   dropped. Entry points and procs that nothing referred to before inlining are kept.
+//!
+//! The LLVM backend does all of this itself when it optimizes, but the dev backends don't.
+//! In debug builds, the result is checked for dangling symbols and calls, and reused call ids.
+
+use crate::inc_dec::occurring_variables_expr;
+use crate::ir::{
+    Call, CallSpecIds, CallType, Expr, HostExposedLayouts, ListLiteralElement, Literal, ModifyRc,
+    Proc, ProcLayout, SelfRecursive, Stmt, UpdateModeIds,
+};
+use crate::layout::{Builtin, Layout, TagIdIntType, UnionLayout};
+use bumpalo::collections::Vec;
+use bumpalo::Bump;
+use roc_builtins::bitcode::IntWidth;
+use roc_collections::all::{MutMap, MutSet};
+use roc_module::low_level::LowLevel;
+use roc_module::symbol::{IdentIds, ModuleId, Symbol};
+
+/// Procs whose body is a chain of at most this many `let`s are inlined
+const INLINE_MAX_LETS: usize = 4;
+
+type ProcKey<'a> = (Symbol, ProcLayout<'a>);
+
+pub fn optimize_procs<'a>(
+    arena: &'a Bump,
+    home: ModuleId,
+    ident_ids: &mut IdentIds,
+    update_mode_ids: &mut UpdateModeIds,
+    call_spec_ids: &mut CallSpecIds,
+    entry_points: &MutSet<Symbol>,
+    procs: &mut MutMap<ProcKey<'a>, Proc<'a>>,
+) {
+    // Procs that nothing calls are called from outside the IR, e.g. by the host or the repl
+    let mut referenced = MutSet::default();
+    for proc in procs.values() {
+        collect_referenced(&proc.body, &mut referenced);
+    }
+
+    inline_calls(
+        arena,
+        home,
+        ident_ids,
+        update_mode_ids,
+        call_spec_ids,
+        procs,
+    );
+
+    for proc in procs.values_mut() {
+        let mut known = Known::default();
+        let simplified = simplify_stmt(arena, &mut known, &proc.body);
+
+        let mut used = MutSet::default();
+        proc.body = drop_unused_lets(arena, &mut used, &simplified);
+    }
+
+    drop_unreachable_procs(entry_points, &referenced, procs);
+
+    #[cfg(debug_assertions)]
+    check_procs(procs);
+}
+
+// DROPPING PROCS
+
+/// Remove the procs that can't be reached from a root anymore. Roots are the entry points, procs
+/// that were not referenced before optimizing, and everything a host-exposed proc exposes.
+/// Procs are tracked by name, so all specializations of a reachable function are kept.
+fn drop_unreachable_procs<'a>(
+    entry_points: &MutSet<Symbol>,
+    referenced_before: &MutSet<Symbol>,
+    procs: &mut MutMap<ProcKey<'a>, Proc<'a>>,
+) {
+    let mut reachable = MutSet::default();
+    let mut stack = std::vec::Vec::new();
+
+    for ((symbol, _), proc) in procs.iter() {
+        if entry_points.contains(symbol) || !referenced_before.contains(symbol) {
+            stack.push(*symbol);
+        }
+
+        if let HostExposedLayouts::HostExposed { aliases, .. } = &proc.host_exposed_layouts {
+            stack.push(*symbol);
+            stack.extend(aliases.values().map(|(alias, _, _)| *alias));
+        }
+    }
+
+    let mut by_name: MutMap<Symbol, std::vec::Vec<&Proc<'a>>> = MutMap::default();
+    for ((symbol, _), proc) in procs.iter() {
+        by_name.entry(*symbol).or_default().push(proc);
+    }
+
+    while let Some(symbol) = stack.pop() {
+        if !reachable.insert(symbol) {
+            continue;
+        }
+
+        let mut callees = MutSet::default();
+        for proc in by_name.get(&symbol).into_iter().flatten() {
+            collect_referenced(&proc.body, &mut callees);
+        }
+
+        stack.extend(callees.into_iter().filter(|s| !reachable.contains(s)));
+    }
+
+    procs.retain(|(symbol, _), _| reachable.contains(symbol));
+}
+
+/// The names of the procs that are called, or passed to higher-order lowlevels
+fn collect_referenced(stmt: &Stmt<'_>, referenced: &mut MutSet<Symbol>) {
+    for_each_expr(stmt, &mut |expr| match expr {
+        Expr::Call(Call {
+            call_type: CallType::ByName { name, .. },
+            ..
+        }) => {
+            referenced.insert(name.name());
+        }
+        Expr::Call(Call {
+            call_type: CallType::HigherOrder(higher_order),
+            ..
+        }) => {
+            referenced.insert(higher_order.passed_function.name.name());
+        }
+        _ => {}
+    });
+}
+
+// INLINING
+
+struct InlineEnv<'a, 'i> {
+    arena: &'a Bump,
+
+    /// required for creating new `Symbol`s
+    home: ModuleId,
+    ident_ids: &'i mut IdentIds,
+
+    /// The procs as they were before inlining
+    original: MutMap<ProcKey<'a>, Proc<'a>>,
+    /// Procs into which calls have been inlined already
+    done: MutMap<ProcKey<'a>, Proc<'a>>,
+    /// Procs whose callees are being processed
+    in_progress: std::vec::Vec<ProcKey<'a>>,
+    /// Procs that can call themselves through other procs
+    recursive: MutSet<ProcKey<'a>>,
+
+    /// Inlined calls get new ids, since alias analysis wants them to be unique in a proc
+    update_mode_ids: &'i mut UpdateModeIds,
+    call_spec_ids: &'i mut CallSpecIds,
+}
+
+impl<'a, 'i> InlineEnv<'a, 'i> {
+    fn unique_symbol(&mut self) -> Symbol {
+        let ident_id = self.ident_ids.gen_unique();
+
+        Symbol::new(self.home, ident_id)
+    }
+
+    /// Inline calls in the given proc, after doing the same for the procs it calls.
+    /// That way each proc only needs to be inlined into its callers once.
+    fn visit(&mut self, key: ProcKey<'a>) {
+        if self.done.contains_key(&key) {
+            return;
+        }
+
+        if let Some(position) = self.in_progress.iter().position(|k| *k == key) {
+            // every proc from here to the top of the stack is on a cycle
+            self.recursive
+                .extend(self.in_progress[position..].iter().copied());
+            return;
+        }
+
+        let proc = match self.original.get(&key) {
+            Some(proc) => proc.clone(),
+            None => return,
+        };
+
+        self.in_progress.push(key);
+
+        let mut callees = std::vec::Vec::new();
+        collect_callees(self.arena, &proc.body, &mut callees);
+
+        for callee in callees {
+            self.visit(callee);
+        }
+
+        self.in_progress.pop();
+
+        let body = self.inline_stmt(&proc.body);
+        self.done.insert(key, Proc { body, ..proc });
+    }
+
+    fn inline_stmt(&mut self, stmt: &Stmt<'a>) -> Stmt<'a> {
+        use Stmt::*;
+
+        let arena = self.arena;
+
+        match stmt {
+            Let(symbol, expr, layout, continuation) => {
+                let continuation = self.inline_stmt(continuation);
+
+                if let Expr::Call(Call {
+                    call_type:
+                        CallType::ByName {
+                            name,
+                            ret_layout,
+                            arg_layouts,
+                            ..
+                        },
+                    arguments,
+                }) = expr
+                {
+                    let proc_layout =
+                        ProcLayout::new(arena, *arg_layouts, name.captures_niche(), **ret_layout);
+
+                    if let Some(callee) = self.inlinable(&(name.name(), proc_layout), arguments) {
+                        return self.splice(&callee, *symbol, arguments, continuation);
+                    }
+                }
+
+                Let(*symbol, expr.clone(), *layout, arena.alloc(continuation))
+            }
+            Switch {
+                cond_symbol,
+                cond_layout,
+                branches,
+                default_branch,
+                ret_layout,
+            } => {
+                let mut new_branches = Vec::with_capacity_in(branches.len(), arena);
+
+                for (tag, info, branch) in branches.iter() {
+                    new_branches.push((*tag, info.clone(), self.inline_stmt(branch)));
+                }
+
+                let new_default = self.inline_stmt(default_branch.1);
+
+                Switch {
+                    cond_symbol: *cond_symbol,
+                    cond_layout: *cond_layout,
+                    branches: new_branches.into_bump_slice(),
+                    default_branch: (default_branch.0.clone(), arena.alloc(new_default)),
+                    ret_layout: *ret_layout,
+                }
+            }
+            Refcounting(modify, continuation) => {
+                Refcounting(*modify, arena.alloc(self.inline_stmt(continuation)))
+            }
+            Expect {
+                condition,
+                region,
+                lookups,
+                layouts,
+                remainder,
+            } => Expect {
+                condition: *condition,
+                region: *region,
+                lookups: *lookups,
+                layouts: *layouts,
+                remainder: arena.alloc(self.inline_stmt(remainder)),
+            },
+            Dbg {
+                symbol,
+                region,
+                remainder,
+            } => Dbg {
+                symbol: *symbol,
+                region: *region,
+                remainder: arena.alloc(self.inline_stmt(remainder)),
+            },
+            Join {
+                id,
+                parameters,
+                body,
+                remainder,
+            } => Join {
+                id: *id,
+                parameters: *parameters,
+                body: arena.alloc(self.inline_stmt(body)),
+                remainder: arena.alloc(self.inline_stmt(remainder)),
+            },
+            Ret(_) | Jump(_, _) | RuntimeError(_) => stmt.clone(),
+        }
+    }
+
+    /// The proc to inline for this call, if any
+    fn inlinable(&self, key: &ProcKey<'a>, arguments: &[Symbol]) -> Option<Proc<'a>> {
+        let callee = self.done.get(key)?;
+
+        if self.recursive.contains(key)
+            || callee.is_self_recursive != SelfRecursive::NotSelfRecursive
+            || callee.args.len() != arguments.len()
+        {
+            return None;
+        }
+
+        let mut lets = 0;
+        let mut stmt = &callee.body;
+
+        loop {
+            match stmt {
+                Stmt::Let(_, _, _, continuation) if lets < INLINE_MAX_LETS => {
+                    lets += 1;
+                    stmt = continuation;
+                }
+                Stmt::Ret(symbol) => {
+                    // returning an argument would need an alias, which the IR doesn't have
+                    let returns_argument = callee.args.iter().any(|(_, arg)| arg == symbol);
+
+                    return if returns_argument {
+                        None
+                    } else {
+                        Some(callee.clone())
+                    };
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    /// Replace `let symbol = CallByName callee arguments` with the body of the callee.
+    /// The returned value is bound to `symbol`, and everything else the callee binds gets a
+    /// fresh symbol.
+    fn splice(
+        &mut self,
+        callee: &Proc<'a>,
+        symbol: Symbol,
+        arguments: &[Symbol],
+        continuation: Stmt<'a>,
+    ) -> Stmt<'a> {
+        let mut renames: MutMap<Symbol, Symbol> = callee
+            .args
+            .iter()
+            .map(|(_, arg)| *arg)
+            .zip(arguments.iter().copied())
+            .collect();
+
+        let mut lets = std::vec::Vec::new();
+        let mut stmt = &callee.body;
+
+        let returned = loop {
+            match stmt {
+                Stmt::Let(binding, expr, layout, next) => {
+                    lets.push((*binding, expr, *layout));
+                    stmt = next;
+                }
+                Stmt::Ret(returned) => break *returned,
+                _ => unreachable!("only chains of lets are inlined"),
+            }
+        };
+
+        for (binding, _, _) in lets.iter() {
+            let new_binding = if *binding == returned {
+                symbol
+            } else {
+                self.unique_symbol()
+            };
+
+            renames.insert(*binding, new_binding);
+        }
+
+        let mut result = continuation;
+
+        for (binding, expr, layout) in lets.into_iter().rev() {
+            let expr = rename_expr(self.arena, &renames, expr);
+            let expr = self.with_fresh_ids(expr);
+
+            result = Stmt::Let(renames[&binding], expr, layout, self.arena.alloc(result));
+        }
+
+        result
+    }
+
+    fn with_fresh_ids(&mut self, expr: Expr<'a>) -> Expr<'a> {
+        match expr {
+            Expr::Call(Call {
+                call_type,
+                arguments,
+            }) => {
+                let call_type = match call_type {
+                    CallType::ByName {
+                        name,
+                        ret_layout,
+                        arg_layouts,
+                        ..
+                    } => CallType::ByName {
+                        name,
+                        ret_layout,
+                        arg_layouts,
+                        specialization_id: self.call_spec_ids.next_id(),
+                    },
+                    CallType::LowLevel { op, .. } => CallType::LowLevel {
+                        op,
+                        update_mode: self.update_mode_ids.next_id(),
+                    },
+                    CallType::HigherOrder(higher_order) => {
+                        let mut higher_order = higher_order.clone();
+                        higher_order.update_mode = self.update_mode_ids.next_id();
+                        higher_order.passed_function.specialization_id =
+                            self.call_spec_ids.next_id();
+
+                        CallType::HigherOrder(self.arena.alloc(higher_order))
+                    }
+                    CallType::Foreign { .. } => call_type,
+                };
+
+                Expr::Call(Call {
+                    call_type,
+                    arguments,
+                })
+            }
+            other => other,
+        }
+    }
+}
+
+fn inline_calls<'a>(
+    arena: &'a Bump,
+    home: ModuleId,
+    ident_ids: &mut IdentIds,
+    update_mode_ids: &mut UpdateModeIds,
+    call_spec_ids: &mut CallSpecIds,
+    procs: &mut MutMap<ProcKey<'a>, Proc<'a>>,
+) {
+    let original = std::mem::take(procs);
+
+    // Every module numbered its calls separately, so skip past the ids of all of them
+    for proc in original.values() {
+        skip_used_ids(&proc.body, update_mode_ids, call_spec_ids);
+    }
+
+    let mut env = InlineEnv {
+        arena,
+        home,
+        ident_ids,
+        original,
+        done: MutMap::default(),
+        in_progress: std::vec::Vec::new(),
+        recursive: MutSet::default(),
+        update_mode_ids,
+        call_spec_ids,
+    };
+
+    let keys: std::vec::Vec<_> = env.original.keys().copied().collect();
+
+    for key in keys {
+        env.visit(key);
+    }
+
+    *procs = env.done;
+}
+
+fn collect_callees<'a>(arena: &'a Bump, stmt: &Stmt<'a>, callees: &mut std::vec::Vec<ProcKey<'a>>) {
+    for_each_expr(stmt, &mut |expr| {
+        if let Expr::Call(Call {
+            call_type:
+                CallType::ByName {
+                    name,
+                    ret_layout,
+                    arg_layouts,
+                    ..
+                },
+            ..
+        }) = expr
+        {
+            let proc_layout =
+                ProcLayout::new(arena, *arg_layouts, name.captures_niche(), **ret_layout);
+
+            callees.push((name.name(), proc_layout));
+        }
+    });
+}
+
+fn skip_used_ids(
+    stmt: &Stmt<'_>,
+    update_mode_ids: &mut UpdateModeIds,
+    call_spec_ids: &mut CallSpecIds,
+) {
+    for_each_expr(stmt, &mut |expr| match expr {
+        Expr::Call(Call { call_type, .. }) => match call_type {
+            CallType::ByName {
+                specialization_id, ..
+            } => {
+                call_spec_ids.skip_past(*specialization_id);
+            }
+            CallType::LowLevel { update_mode, .. } => {
+                update_mode_ids.skip_past(*update_mode);
+            }
+            CallType::HigherOrder(higher_order) => {
+                call_spec_ids.skip_past(higher_order.passed_function.specialization_id);
+                update_mode_ids.skip_past(higher_order.update_mode);
+            }
+            CallType::Foreign { .. } => {}
+        },
+        Expr::Reuse { update_mode, .. } | Expr::Reset { update_mode, .. } => {
+            update_mode_ids.skip_past(*update_mode);
+        }
+        _ => {}
+    });
+}
+
+fn for_each_expr<'a, F>(stmt: &Stmt<'a>, f: &mut F)
+where
+    F: FnMut(&Expr<'a>),
+{
+    use Stmt::*;
+
+    let mut stack = std::vec![stmt];
+
+    while let Some(stmt) = stack.pop() {
+        match stmt {
+            Let(_, expr, _, continuation) => {
+                f(expr);
+                stack.push(continuation);
+            }
+            Switch {
+                branches,
+                default_branch,
+                ..
+            } => {
+                stack.extend(branches.iter().map(|b| &b.2));
+                stack.push(default_branch.1);
+            }
+            Refcounting(_, continuation)
+            | Expect {
+                remainder: continuation,
+                ..
+            }
+            | Dbg {
+                remainder: continuation,
+                ..
+            } => stack.push(continuation),
+            Join {
+                body, remainder, ..
+            } => {
+                stack.push(body);
+                stack.push(remainder);
+            }
+            Ret(_) | Jump(_, _) | RuntimeError(_) => {}
+        }
+    }
+}
+
+// SIMPLIFICATION
+
+/// What is known about symbols at some point in a proc
+#[derive(Default)]
+struct Known<'a> {
+    /// Symbols that were replaced by other symbols, e.g. fields read from a known struct
+    renames: MutMap<Symbol, Symbol>,
+    literals: MutMap<Symbol, (Literal<'a>, Layout<'a>)>,
+    /// Tags of non-recursive unions built in this proc
+    tags: MutMap<Symbol, (TagIdIntType, &'a [Symbol])>,
+    /// Structs built in this proc
+    structs: MutMap<Symbol, &'a [Symbol]>,
+}
+
+impl<'a> Known<'a> {
+    fn rename(&self, symbol: Symbol) -> Symbol {
+        *self.renames.get(&symbol).unwrap_or(&symbol)
+    }
+
+    /// Forget anything we knew about a symbol, because it is bound (again) here.
+    /// Code duplicated by the decision tree can bind the same symbol in several branches.
+    fn bind(&mut self, symbol: Symbol) {
+        self.renames.remove(&symbol);
+        self.literals.remove(&symbol);
+        self.tags.remove(&symbol);
+        self.structs.remove(&symbol);
+    }
+
+    fn literal(&self, symbol: Symbol) -> Option<(Literal<'a>, Layout<'a>)> {
+        self.literals.get(&symbol).copied()
+    }
+
+    /// The value a `Switch` on this symbol compares to its branches, if it is known
+    fn switch_value(&self, symbol: Symbol) -> Option<u64> {
+        match self.literal(symbol)? {
+            (Literal::Int(bytes), _) => Some(i128::from_ne_bytes(bytes) as u64),
+            (Literal::Bool(b), _) => Some(b as u64),
+            (Literal::Byte(b), _) => Some(b as u64),
+            _ => None,
+        }
+    }
+}
+
+fn simplify_stmt<'a>(arena: &'a Bump, known: &mut Known<'a>, stmt: &Stmt<'a>) -> Stmt<'a> {
+    use Stmt::*;
+
+    match stmt {
+        Let(symbol, expr, layout, continuation) => {
+            known.bind(*symbol);
+
+            let expr = rename_expr(arena, &known.renames, expr);
+
+            match simplify_expr(known, &expr, layout) {
+                Simplified::Alias(other) => {
+                    known.renames.insert(*symbol, other);
+
+                    simplify_stmt(arena, known, continuation)
+                }
+                Simplified::Literal(literal) => {
+                    known.literals.insert(*symbol, (literal, *layout));
+
+                    let continuation = simplify_stmt(arena, known, continuation);
+                    Let(
+                        *symbol,
+                        Expr::Literal(literal),
+                        *layout,
+                        arena.alloc(continuation),
+                    )
+                }
+                Simplified::Unchanged => {
+                    match &expr {
+                        Expr::Literal(literal) => {
+                            known.literals.insert(*symbol, (*literal, *layout));
+                        }
+                        Expr::Tag {
+                            tag_layout: UnionLayout::NonRecursive(_),
+                            tag_id,
+                            arguments,
+                        } => {
+                            known.tags.insert(*symbol, (*tag_id, *arguments));
+                        }
+                        Expr::Struct(fields) => {
+                            known.structs.insert(*symbol, *fields);
+                        }
+                        _ => {}
+                    }
+
+                    let continuation = simplify_stmt(arena, known, continuation);
+                    Let(*symbol, expr, *layout, arena.alloc(continuation))
+                }
+            }
+        }
+        Switch {
+            cond_symbol,
+            cond_layout,
+            branches,
+            default_branch,
+            ret_layout,
+        } => {
+            let cond_symbol = known.rename(*cond_symbol);
+
+            if let Some(value) = known.switch_value(cond_symbol) {
+                let taken = branches
+                    .iter()
+                    .find(|(tag, _, _)| *tag == value)
+                    .map(|(_, _, branch)| branch)
+                    .unwrap_or(default_branch.1);
+
+                return simplify_stmt(arena, known, taken);
+            }
+
+            let mut new_branches = Vec::with_capacity_in(branches.len(), arena);
+
+            for (tag, info, branch) in branches.iter() {
+                new_branches.push((*tag, info.clone(), simplify_stmt(arena, known, branch)));
+            }
+
+            let new_default = simplify_stmt(arena, known, default_branch.1);
+
+            Switch {
+                cond_symbol,
+                cond_layout: *cond_layout,
+                branches: new_branches.into_bump_slice(),
+                default_branch: (default_branch.0.clone(), arena.alloc(new_default)),
+                ret_layout: *ret_layout,
+            }
+        }
+        Ret(symbol) => Ret(known.rename(*symbol)),
+        Refcounting(modify, continuation) => {
+            let modify = match modify {
+                ModifyRc::Inc(symbol, n) => ModifyRc::Inc(known.rename(*symbol), *n),
+                ModifyRc::Dec(symbol) => ModifyRc::Dec(known.rename(*symbol)),
+                ModifyRc::DecRef(symbol) => ModifyRc::DecRef(known.rename(*symbol)),
+            };
+
+            Refcounting(
+                modify,
+                arena.alloc(simplify_stmt(arena, known, continuation)),
+            )
+        }
+        Expect {
+            condition,
+            region,
+            lookups,
+            layouts,
+            remainder,
+        } => Expect {
+            condition: known.rename(*condition),
+            region: *region,
+            lookups: rename_slice(arena, &known.renames, *lookups),
+            layouts: *layouts,
+            remainder: arena.alloc(simplify_stmt(arena, known, remainder)),
+        },
+        Dbg {
+            symbol,
+            region,
+            remainder,
+        } => Dbg {
+            symbol: known.rename(*symbol),
+            region: *region,
+            remainder: arena.alloc(simplify_stmt(arena, known, remainder)),
+        },
+        Join {
+            id,
+            parameters,
+            body,
+            remainder,
+        } => {
+            for param in parameters.iter() {
+                known.bind(param.symbol);
+            }
+
+            let body = simplify_stmt(arena, known, body);
+            let remainder = simplify_stmt(arena, known, remainder);
+
+            Join {
+                id: *id,
+                parameters: *parameters,
+                body: arena.alloc(body),
+                remainder: arena.alloc(remainder),
+            }
+        }
+        Jump(id, arguments) => Jump(*id, rename_slice(arena, &known.renames, *arguments)),
+        RuntimeError(_) => stmt.clone(),
+    }
+}
+
+enum Simplified<'a> {
+    /// The value is another symbol that is in scope
+    Alias(Symbol),
+    Literal(Literal<'a>),
+    Unchanged,
+}
+
+fn simplify_expr<'a>(known: &Known<'a>, expr: &Expr<'a>, layout: &Layout<'a>) -> Simplified<'a> {
+    match expr {
+        Expr::Call(Call {
+            call_type: CallType::LowLevel { op, .. },
+            arguments,
+        }) => match fold_lowlevel(known, *op, arguments, layout) {
+            Some(literal) => Simplified::Literal(literal),
+            None => Simplified::Unchanged,
+        },
+        Expr::GetTagId { structure, .. } => match known.tags.get(structure) {
+            Some((tag_id, _)) => match int_literal(*tag_id as i128, layout) {
+                Some(literal) => Simplified::Literal(literal),
+                None => Simplified::Unchanged,
+            },
+            None => Simplified::Unchanged,
+        },
+        Expr::UnionAtIndex {
+            structure,
+            tag_id,
+            union_layout: UnionLayout::NonRecursive(_),
+            index,
+        } => match known.tags.get(structure) {
+            Some((known_id, arguments)) if known_id == tag_id => {
+                Simplified::Alias(arguments[*index as usize])
+            }
+            _ => Simplified::Unchanged,
+        },
+        Expr::StructAtIndex {
+            index, structure, ..
+        } => match known.structs.get(structure) {
+            Some(fields) => Simplified::Alias(fields[*index as usize]),
+            None => Simplified::Unchanged,
+        },
+        _ => Simplified::Unchanged,
+    }
+}
+
+fn fold_lowlevel<'a>(
+    known: &Known<'a>,
+    op: LowLevel,
+    arguments: &[Symbol],
+    layout: &Layout<'a>,
+) -> Option<Literal<'a>> {
+    use LowLevel::*;
+
+    let mut literals = std::vec::Vec::with_capacity(arguments.len());
+    for argument in arguments {
+        literals.push(known.literal(*argument)?);
+    }
+
+    match (op, literals.as_slice()) {
+        (Not, [(Literal::Bool(a), _)]) => Some(Literal::Bool(!a)),
+        (And, [(Literal::Bool(a), _), (Literal::Bool(b), _)]) => Some(Literal::Bool(*a && *b)),
+        (Or, [(Literal::Bool(a), _), (Literal::Bool(b), _)]) => Some(Literal::Bool(*a || *b)),
+
+        (Eq | NotEq, [(a, _), (b, _)]) => {
+            let equal = match (a, b) {
+                (Literal::Int(a), Literal::Int(b)) => a == b,
+                (Literal::Bool(a), Literal::Bool(b)) => a == b,
+                (Literal::Byte(a), Literal::Byte(b)) => a == b,
+                (Literal::Str(a), Literal::Str(b)) => a == b,
+                _ => return None,
+            };
+
+            Some(Literal::Bool(equal == matches!(op, Eq)))
+        }
+
+        (NumLt | NumLte | NumGt | NumGte, [(a, a_layout), (b, _)]) => {
+            int_width(a_layout)?;
+
+            let (a, b) = match (a, b) {
+                (Literal::Int(a), Literal::Int(b)) => {
+                    (i128::from_ne_bytes(*a), i128::from_ne_bytes(*b))
+                }
+                _ => return None,
+            };
+
+            let result = match op {
+                NumLt => a < b,
+                NumLte => a <= b,
+                NumGt => a > b,
+                _ => a >= b,
+            };
+
+            Some(Literal::Bool(result))
+        }
+
+        (
+            NumAdd | NumSub | NumMul | NumAddWrap | NumSubWrap | NumMulWrap,
+            [(Literal::Int(a), _), (Literal::Int(b), _)],
+        ) => {
+            let width = int_width(layout)?;
+            let (a, b) = (i128::from_ne_bytes(*a), i128::from_ne_bytes(*b));
+
+            let result = match op {
+                NumAdd => in_range(a.checked_add(b)?, width)?,
+                NumSub => in_range(a.checked_sub(b)?, width)?,
+                NumMul => in_range(a.checked_mul(b)?, width)?,
+                NumAddWrap => wrap(a.wrapping_add(b), width),
+                NumSubWrap => wrap(a.wrapping_sub(b), width),
+                _ => wrap(a.wrapping_mul(b), width),
+            };
+
+            Some(Literal::Int(result.to_ne_bytes()))
+        }
+
+        _ => None,
+    }
+}
+
+/// The width of an integer layout. `U128` values above `i128::MAX` are stored as
+/// `Literal::U128`, so we leave them alone.
+fn int_width(layout: &Layout) -> Option<IntWidth> {
+    match layout {
+        Layout::Builtin(Builtin::Int(IntWidth::U128)) => None,
+        Layout::Builtin(Builtin::Int(width)) => Some(*width),
+        _ => None,
+    }
+}
+
+fn int_literal<'a>(value: i128, layout: &Layout<'a>) -> Option<Literal<'a>> {
+    int_width(layout)?;
+
+    Some(Literal::Int(value.to_ne_bytes()))
+}
+
+fn in_range(value: i128, width: IntWidth) -> Option<i128> {
+    let bits = width.stack_size() * 8;
+
+    if bits == 128 {
+        return Some(value);
+    }
+
+    let (min, max) = if width.is_signed() {
+        (-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1)
+    } else {
+        (0, (1i128 << bits) - 1)
+    };
+
+    if (min..=max).contains(&value) {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+fn wrap(value: i128, width: IntWidth) -> i128 {
+    let bits = width.stack_size() * 8;
+
+    if bits == 128 {
+        return value;
+    }
+
+    let truncated = value & ((1i128 << bits) - 1);
+
+    if width.is_signed() && truncated >= (1i128 << (bits - 1)) {
+        truncated - (1i128 << bits)
+    } else {
+        truncated
+    }
+}
+
+// DEAD CODE
+
+/// Drop `let`s whose symbol is never used. `used` collects the symbols used in the statement
+/// and after it.
+fn drop_unused_lets<'a>(arena: &'a Bump, used: &mut MutSet<Symbol>, stmt: &Stmt<'a>) -> Stmt<'a> {
+    use Stmt::*;
+
+    match stmt {
+        Let(symbol, expr, layout, continuation) => {
+            let continuation = drop_unused_lets(arena, used, continuation);
+
+            if !used.contains(symbol) && can_drop(expr) {
+                continuation
+            } else {
+                occurring_variables_expr(expr, used);
+
+                Let(*symbol, expr.clone(), *layout, arena.alloc(continuation))
+            }
+        }
+        Switch {
+            cond_symbol,
+            cond_layout,
+            branches,
+            default_branch,
+            ret_layout,
+        } => {
+            let mut new_branches = Vec::with_capacity_in(branches.len(), arena);
+
+            for (tag, info, branch) in branches.iter() {
+                new_branches.push((*tag, info.clone(), drop_unused_lets(arena, used, branch)));
+            }
+
+            let new_default = drop_unused_lets(arena, used, default_branch.1);
+
+            used.insert(*cond_symbol);
+
+            Switch {
+                cond_symbol: *cond_symbol,
+                cond_layout: *cond_layout,
+                branches: new_branches.into_bump_slice(),
+                default_branch: (default_branch.0.clone(), arena.alloc(new_default)),
+                ret_layout: *ret_layout,
+            }
+        }
+        Ret(symbol) => {
+            used.insert(*symbol);
+
+            stmt.clone()
+        }
+        Refcounting(modify, continuation) => {
+            let continuation = drop_unused_lets(arena, used, continuation);
+            used.insert(modify.get_symbol());
+
+            Refcounting(*modify, arena.alloc(continuation))
+        }
+        Expect {
+            condition,
+            region,
+            lookups,
+            layouts,
+            remainder,
+        } => {
+            let remainder = drop_unused_lets(arena, used, remainder);
+            used.insert(*condition);
+            used.extend(lookups.iter().copied());
+
+            Expect {
+                condition: *condition,
+                region: *region,
+                lookups: *lookups,
+                layouts: *layouts,
+                remainder: arena.alloc(remainder),
+            }
+        }
+        Dbg {
+            symbol,
+            region,
+            remainder,
+        } => {
+            let remainder = drop_unused_lets(arena, used, remainder);
+            used.insert(*symbol);
+
+            Dbg {
+                symbol: *symbol,
+                region: *region,
+                remainder: arena.alloc(remainder),
+            }
+        }
+        Join {
+            id,
+            parameters,
+            body,
+            remainder,
+        } => {
+            let body = drop_unused_lets(arena, used, body);
+            let remainder = drop_unused_lets(arena, used, remainder);
+
+            Join {
+                id: *id,
+                parameters: *parameters,
+                body: arena.alloc(body),
+                remainder: arena.alloc(remainder),
+            }
+        }
+        Jump(_, arguments) => {
+            used.extend(arguments.iter().copied());
+
+            stmt.clone()
+        }
+        RuntimeError(_) => stmt.clone(),
+    }
+}
+
+/// Whether computing this value has no effect besides producing the value, and can't crash
+fn can_drop(expr: &Expr) -> bool {
+    use LowLevel::*;
+
+    match expr {
+        Expr::Literal(_)
+        | Expr::Tag { .. }
+        | Expr::Struct(_)
+        | Expr::StructAtIndex { .. }
+        | Expr::GetTagId { .. }
+        | Expr::UnionAtIndex { .. }
+        | Expr::Array { .. }
+        | Expr::EmptyArray
+        | Expr::ExprBox { .. }
+        | Expr::ExprUnbox { .. } => true,
+        Expr::Call(Call {
+            call_type: CallType::LowLevel { op, .. },
+            ..
+        }) => matches!(
+            op,
+            Eq | NotEq
+                | And
+                | Or
+                | Not
+                | NumLt
+                | NumLte
+                | NumGt
+                | NumGte
+                | NumAddWrap
+                | NumSubWrap
+                | NumMulWrap
+                | ListLen
+        ),
+        Expr::Call(_) | Expr::Reuse { .. } | Expr::Reset { .. } | Expr::RuntimeErrorFunction(_) => {
+            false
+        }
+    }
+}
+
+// RENAMING
+
+fn rename(renames: &MutMap<Symbol, Symbol>, symbol: Symbol) -> Symbol {
+    *renames.get(&symbol).unwrap_or(&symbol)
+}
+
+fn rename_slice<'a>(
+    arena: &'a Bump,
+    renames: &MutMap<Symbol, Symbol>,
+    symbols: &'a [Symbol],
+) -> &'a [Symbol] {
+    if symbols.iter().any(|symbol| renames.contains_key(symbol)) {
+        Vec::from_iter_in(symbols.iter().map(|symbol| rename(renames, *symbol)), arena)
+            .into_bump_slice()
+    } else {
+        symbols
+    }
+}
+
+fn rename_expr<'a>(arena: &'a Bump, renames: &MutMap<Symbol, Symbol>, expr: &Expr<'a>) -> Expr<'a> {
+    use Expr::*;
+
+    match expr {
+        Call(call) => {
+            let call_type = match &call.call_type {
+                CallType::HigherOrder(higher_order) => {
+                    let mut higher_order = (*higher_order).clone();
+                    let passed_function = &mut higher_order.passed_function;
+                    passed_function.captured_environment =
+                        rename(renames, passed_function.captured_environment);
+
+                    CallType::HigherOrder(arena.alloc(higher_order))
+                }
+                other => other.clone(),
+            };
+
+            Call(crate::ir::Call {
+                call_type,
+                arguments: rename_slice(arena, renames, call.arguments),
+            })
+        }
+        Tag {
+            tag_layout,
+            tag_id,
+            arguments,
+        } => Tag {
+            tag_layout: *tag_layout,
+            tag_id: *tag_id,
+            arguments: rename_slice(arena, renames, *arguments),
+        },
+        Struct(fields) => Struct(rename_slice(arena, renames, *fields)),
+        StructAtIndex {
+            index,
+            field_layouts,
+            structure,
+        } => StructAtIndex {
+            index: *index,
+            field_layouts: *field_layouts,
+            structure: rename(renames, *structure),
+        },
+        GetTagId {
+            structure,
+            union_layout,
+        } => GetTagId {
+            structure: rename(renames, *structure),
+            union_layout: *union_layout,
+        },
+        UnionAtIndex {
+            structure,
+            tag_id,
+            union_layout,
+            index,
+        } => UnionAtIndex {
+            structure: rename(renames, *structure),
+            tag_id: *tag_id,
+            union_layout: *union_layout,
+            index: *index,
+        },
+        Array { elem_layout, elems } => {
+            let elems = Vec::from_iter_in(
+                elems.iter().map(|elem| match elem {
+                    ListLiteralElement::Symbol(symbol) => {
+                        ListLiteralElement::Symbol(rename(renames, *symbol))
+                    }
+                    ListLiteralElement::Literal(_) => *elem,
+                }),
+                arena,
+            );
+
+            Array {
+                elem_layout: *elem_layout,
+                elems: elems.into_bump_slice(),
+            }
+        }
+        ExprBox { symbol } => ExprBox {
+            symbol: rename(renames, *symbol),
+        },
+        ExprUnbox { symbol } => ExprUnbox {
+            symbol: rename(renames, *symbol),
+        },
+        Reuse {
+            symbol,
+            update_tag_id,
+            update_mode,
+            tag_layout,
+            tag_id,
+            arguments,
+        } => Reuse {
+            symbol: rename(renames, *symbol),
+            update_tag_id: *update_tag_id,
+            update_mode: *update_mode,
+            tag_layout: *tag_layout,
+            tag_id: *tag_id,
+            arguments: rename_slice(arena, renames, *arguments),
+        },
+        Reset {
+            symbol,
+            update_mode,
+        } => Reset {
+            symbol: rename(renames, *symbol),
+            update_mode: *update_mode,
+        },
+        Literal(_) | EmptyArray | RuntimeErrorFunction(_) => expr.clone(),
+    }
+}
+
+// CHECKING
+
+/// Panics when a proc uses a symbol that isn't bound, calls a proc that doesn't exist, or uses a
+/// call id twice. Symbols are unique in a proc, so scopes are not tracked precisely.
+#[cfg(debug_assertions)]
+fn check_procs<'a>(procs: &MutMap<ProcKey<'a>, Proc<'a>>) {
+    use roc_error_macros::internal_error;
+    use Stmt::*;
+
+    let names: MutSet<Symbol> = procs.keys().map(|(symbol, _)| *symbol).collect();
+
+    for ((name, _), proc) in procs.iter() {
+        let mut bound: MutSet<Symbol> = proc.args.iter().map(|(_, symbol)| *symbol).collect();
+        let mut call_spec_ids = MutSet::default();
+        let mut update_mode_ids = MutSet::default();
+        let mut used = std::vec::Vec::new();
+        let mut stack = std::vec![&proc.body];
+
+        while let Some(stmt) = stack.pop() {
+            match stmt {
+                Let(symbol, expr, _, continuation) => {
+                    let mut occurring = MutSet::default();
+                    occurring_variables_expr(expr, &mut occurring);
+                    used.extend(occurring);
+
+                    if let Expr::Call(Call { call_type, .. }) = expr {
+                        let fresh = match call_type {
+                            CallType::ByName {
+                                name: callee,
+                                specialization_id,
+                                ..
+                            } => {
+                                if !names.contains(&callee.name()) {
+                                    internal_error!(
+                                        "{:?} calls {:?}, which is not a proc anymore",
+                                        name,
+                                        callee.name()
+                                    );
+                                }
+
+                                call_spec_ids.insert(specialization_id.id)
+                            }
+                            CallType::LowLevel { update_mode, .. } => {
+                                update_mode_ids.insert(update_mode.id)
+                            }
+                            CallType::HigherOrder(higher_order) => {
+                                call_spec_ids
+                                    .insert(higher_order.passed_function.specialization_id.id)
+                                    && update_mode_ids.insert(higher_order.update_mode.id)
+                            }
+                            CallType::Foreign { .. } => true,
+                        };
+
+                        if !fresh {
+                            internal_error!("{:?} uses a call id twice: {:?}", name, expr);
+                        }
+                    }
+
+                    bound.insert(*symbol);
+                    stack.push(continuation);
+                }
+                Switch {
+                    cond_symbol,
+                    branches,
+                    default_branch,
+                    ..
+                } => {
+                    used.push(*cond_symbol);
+                    stack.extend(branches.iter().map(|(_, _, branch)| branch));
+                    stack.push(default_branch.1);
+                }
+                Ret(symbol) => used.push(*symbol),
+                Refcounting(modify, continuation) => {
+                    used.push(modify.get_symbol());
+                    stack.push(continuation);
+                }
+                Expect {
+                    condition,
+                    lookups,
+                    remainder,
+                    ..
+                } => {
+                    used.push(*condition);
+                    used.extend(lookups.iter().copied());
+                    stack.push(remainder);
+                }
+                Dbg {
+                    symbol, remainder, ..
+                } => {
+                    used.push(*symbol);
+                    stack.push(remainder);
+                }
+                Join {
+                    parameters,
+                    body,
+                    remainder,
+                    ..
+                } => {
+                    bound.extend(parameters.iter().map(|param| param.symbol));
+                    stack.push(body);
+                    stack.push(remainder);
+                }
+                Jump(_, arguments) => used.extend(arguments.iter().copied()),
+                RuntimeError(_) => {}
+            }
+        }
+
+        // a symbol can be used in a join point body that is visited before its binding
+        if let Some(symbol) = used.iter().find(|symbol| !bound.contains(symbol)) {
+            internal_error!("{:?} uses {:?}, which is never bound", name, symbol);
+        }
+    }
+}
@@ -165,6 +165,8 @@ pub enum Expr<'a> {
     Str(StrLiteral<'a>), // string without escapes in it
     /// Look up exactly one field on a record, e.g. (expr).foo.
     Access(&'a Expr<'a>, &'a str),
+    /// Look up exactly one element of a tuple, e.g. (expr).0.
+    TupleAccess(&'a Expr<'a>, &'a str),
     /// e.g. `.foo`
     AccessorFunction(&'a str),
     /// eg 'b'
@@ -180,6 +182,8 @@ pub enum Expr<'a> {
 
     Record(Collection<'a, Loc<AssignedField<'a, Expr<'a>>>>),
 
+    Tuple(Collection<'a, &'a Loc<Expr<'a>>>),
+
     // Lookups
     Var {
         module_name: &'a str, // module_name will only be filled if the original Roc code stated something like `5 + SomeModule.myVar`, module_name will be blank if it was `5 + myVar`
@@ -200,6 +204,9 @@ pub enum Expr<'a> {
     Defs(&'a Defs<'a>, &'a Loc<Expr<'a>>),
     Backpassing(&'a [Loc<Pattern<'a>>], &'a Loc<Expr<'a>>, &'a Loc<Expr<'a>>),
     Expect(&'a Loc<Expr<'a>>, &'a Loc<Expr<'a>>),
+    /// `dbg value`, optionally followed by a continuation on the next line.
+    /// Without a continuation, the whole expression evaluates to `value`.
+    Dbg(&'a Loc<Expr<'a>>, Option<&'a Loc<Expr<'a>>>),
 
     // Application
     /// To apply by name, do Apply(Var(...), ...)
@@ -519,6 +526,13 @@ pub enum TypeAnnotation<'a> {
         ext: Option<&'a Loc<TypeAnnotation<'a>>>,
     },
 
+    Tuple {
+        elems: Collection<'a, Loc<TypeAnnotation<'a>>>,
+        /// The row type variable in an open tuple, e.g. the `r` in `( Str, Str )r`.
+        /// This is None if it's a closed tuple annotation like `( Str, Str )`.
+        ext: Option<&'a Loc<TypeAnnotation<'a>>>,
+    },
+
     /// A tag union, e.g. `[
     TagUnion {
         /// The row type variable in an open tag union, e.g. the `a` in `[Foo, Bar]a`.
@@ -641,6 +655,9 @@ pub enum Pattern<'a> {
     /// Can only occur inside of a RecordDestructure
     OptionalField(&'a str, &'a Loc<Expr<'a>>),
 
+    /// A tuple pattern, e.g. (Just x, 1)
+    Tuple(Collection<'a, Loc<Pattern<'a>>>),
+
     // Literal
     NumLiteral(&'a str),
     NonBase10Literal {
@@ -739,6 +756,13 @@ impl<'a> Pattern<'a> {
                 .iter()
                 .zip(fields_y.iter())
                 .all(|(p, q)| p.value.equivalent(&q.value)),
+            (Tuple(elems_x), Tuple(elems_y)) => {
+                elems_x.len() == elems_y.len()
+                    && elems_x
+                        .iter()
+                        .zip(elems_y.iter())
+                        .all(|(p, q)| p.value.equivalent(&q.value))
+            }
             (RequiredField(x, inner_x), RequiredField(y, inner_y)) => {
                 x == y && inner_x.value.equivalent(&inner_y.value)
             }
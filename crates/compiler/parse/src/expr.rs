@@ -85,45 +85,58 @@ pub fn expr_help<'a>(min_indent: u32) -> impl Parser<'a, Expr<'a>, EExpr<'a>> {
 
 fn loc_expr_in_parens_help<'a>(min_indent: u32) -> impl Parser<'a, Loc<Expr<'a>>, EInParens<'a>> {
     move |arena, state| {
-        let (_, loc_expr, state) = loc_expr_in_parens_help_help(min_indent).parse(arena, state)?;
-
-        Ok((
-            MadeProgress,
-            Loc {
-                region: loc_expr.region,
-                value: Expr::ParensAround(arena.alloc(loc_expr.value)),
-            },
-            state,
-        ))
-    }
-}
-
-fn loc_expr_in_parens_help_help<'a>(
-    min_indent: u32,
-) -> impl Parser<'a, Loc<Expr<'a>>, EInParens<'a>> {
-    between!(
-        word1(b'(', EInParens::Open),
-        space0_around_ee(
-            specialize_ref(EInParens::Expr, move |arena, state| parse_loc_expr(
-                min_indent, arena, state
-            )),
+        let (_, loc_elements, state) = loc!(collection_trailing_sep_e!(
+            word1(b'(', EInParens::Open),
+            specialize_ref(EInParens::Expr, move |arena, state| {
+                parse_loc_expr_no_multi_backpassing(min_indent, arena, state)
+            }),
+            word1(b',', EInParens::End),
+            word1(b')', EInParens::End),
             min_indent,
-            EInParens::IndentOpen,
+            EInParens::Open,
             EInParens::IndentEnd,
-        ),
-        word1(b')', EInParens::End)
-    )
+            Expr::SpaceBefore
+        ))
+        .parse(arena, state)?;
+
+        let elements = loc_elements.value;
+        let region = loc_elements.region;
+
+        if elements.len() > 1 {
+            Ok((
+                MadeProgress,
+                Loc::at(region, Expr::Tuple(elements.ptrify_items(arena))),
+                state,
+            ))
+        } else if elements.is_empty() {
+            Err((NoProgress, EInParens::Empty(region.start()), state))
+        } else {
+            // Exactly one element and no comma, so this is an expression in parentheses rather
+            // than a tuple. Keep any trailing comments attached to the inner expression.
+            let mut loc_elem = elements.items[0];
+
+            if !elements.final_comments().is_empty() {
+                loc_elem.value =
+                    Expr::SpaceAfter(arena.alloc(loc_elem.value), elements.final_comments());
+            }
+
+            Ok((
+                MadeProgress,
+                Loc::at(
+                    loc_elem.region,
+                    Expr::ParensAround(arena.alloc(loc_elem.value)),
+                ),
+                state,
+            ))
+        }
+    }
 }
 
 fn loc_expr_in_parens_etc_help<'a>(min_indent: u32) -> impl Parser<'a, Loc<Expr<'a>>, EExpr<'a>> {
     move |arena, state: State<'a>| {
         let parser = loc!(and!(
             specialize(EExpr::InParens, loc_expr_in_parens_help(min_indent)),
-            one_of![record_field_access_chain(), |a, s| Ok((
-                NoProgress,
-                Vec::new_in(a),
-                s
-            ))]
+            one_of![access_chain(), |a, s| Ok((NoProgress, Vec::new_in(a), s))]
         ));
 
         let (
@@ -142,11 +155,11 @@ fn loc_expr_in_parens_etc_help<'a>(min_indent: u32) -> impl Parser<'a, Loc<Expr<
         if field_accesses.is_empty() {
             region = loc_expr.region;
         } else {
-            for field in field_accesses {
+            for accessor in field_accesses {
                 // Wrap the previous answer in the new one, so we end up
                 // with a nested Expr. That way, `foo.bar.baz` gets represented
                 // in the AST as if it had been written (foo.bar).baz all along.
-                value = Expr::Access(arena.alloc(value), field);
+                value = accessor.access(arena, value);
             }
         }
 
@@ -156,8 +169,34 @@ fn loc_expr_in_parens_etc_help<'a>(min_indent: u32) -> impl Parser<'a, Loc<Expr<
     }
 }
 
-fn record_field_access_chain<'a>() -> impl Parser<'a, Vec<'a, &'a str>, EExpr<'a>> {
-    |arena, state| match record_field_access().parse(arena, state) {
+/// Something that can be accessed on a value with a `.`: a record field, or a tuple element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Accessor<'a> {
+    /// e.g. `.foo`
+    RecordField(&'a str),
+    /// e.g. `.0`
+    TupleIndex(&'a str),
+}
+
+impl<'a> Accessor<'a> {
+    fn from_part(part: &'a str) -> Self {
+        if part.starts_with(|c: char| c.is_ascii_digit()) {
+            Accessor::TupleIndex(part)
+        } else {
+            Accessor::RecordField(part)
+        }
+    }
+
+    fn access(self, arena: &'a Bump, value: Expr<'a>) -> Expr<'a> {
+        match self {
+            Accessor::RecordField(field) => Expr::Access(arena.alloc(value), field),
+            Accessor::TupleIndex(index) => Expr::TupleAccess(arena.alloc(value), index),
+        }
+    }
+}
+
+fn access_chain<'a>() -> impl Parser<'a, Vec<'a, Accessor<'a>>, EExpr<'a>> {
+    |arena, state| match access().parse(arena, state) {
         Ok((_, initial, state)) => {
             let mut accesses = Vec::with_capacity_in(1, arena);
 
@@ -165,7 +204,7 @@ fn record_field_access_chain<'a>() -> impl Parser<'a, Vec<'a, &'a str>, EExpr<'a
 
             let mut loop_state = state;
             loop {
-                match record_field_access().parse(arena, loop_state) {
+                match access().parse(arena, loop_state) {
                     Ok((_, next, state)) => {
                         accesses.push(next);
                         loop_state = state;
@@ -180,10 +219,16 @@ fn record_field_access_chain<'a>() -> impl Parser<'a, Vec<'a, &'a str>, EExpr<'a
     }
 }
 
-fn record_field_access<'a>() -> impl Parser<'a, &'a str, EExpr<'a>> {
+fn access<'a>() -> impl Parser<'a, Accessor<'a>, EExpr<'a>> {
     skip_first!(
         word1(b'.', EExpr::Access),
-        specialize(|_, pos| EExpr::Access(pos), lowercase_ident())
+        specialize(
+            |_, pos| EExpr::Access(pos),
+            one_of!(
+                map!(lowercase_ident(), Accessor::RecordField),
+                map!(crate::ident::tuple_index(), Accessor::TupleIndex)
+            )
+        )
     )
 }
 
@@ -355,6 +400,7 @@ fn parse_expr_start<'a>(
             when::expr_help(min_indent, options)
         )),
         loc!(specialize(EExpr::Expect, expect_help(min_indent, options))),
+        loc!(specialize(EExpr::Dbg, dbg_help(min_indent, options))),
         loc!(specialize(EExpr::Lambda, closure_help(min_indent, options))),
         loc!(move |a, s| parse_expr_operator_chain(min_indent, options, start_column, a, s)),
         fail_expr_start_e()
@@ -1842,6 +1888,16 @@ fn expr_to_pattern_help<'a>(arena: &'a Bump, expr: &Expr<'a>) -> Result<Pattern<
             Ok(Pattern::RecordDestructure(patterns))
         }
 
+        Expr::Tuple(elems) => {
+            let patterns = elems.map_items_result(arena, |loc_elem| {
+                let region = loc_elem.region;
+                let value = expr_to_pattern_help(arena, &loc_elem.value)?;
+                Ok(Loc { region, value })
+            })?;
+
+            Ok(Pattern::Tuple(patterns))
+        }
+
         &Expr::Float(string) => Ok(Pattern::FloatLiteral(string)),
         &Expr::Num(string) => Ok(Pattern::NumLiteral(string)),
         Expr::NonBase10Int {
@@ -1856,6 +1912,7 @@ fn expr_to_pattern_help<'a>(arena: &'a Bump, expr: &Expr<'a>) -> Result<Pattern<
         // These would not have parsed as patterns
         Expr::AccessorFunction(_)
         | Expr::Access(_, _)
+        | Expr::TupleAccess(_, _)
         | Expr::List { .. }
         | Expr::Closure(_, _)
         | Expr::Backpassing(_, _, _)
@@ -1864,6 +1921,7 @@ fn expr_to_pattern_help<'a>(arena: &'a Bump, expr: &Expr<'a>) -> Result<Pattern<
         | Expr::If(_, _)
         | Expr::When(_, _)
         | Expr::Expect(_, _)
+        | Expr::Dbg(_, _)
         | Expr::MalformedClosure
         | Expr::PrecedenceConflict { .. }
         | Expr::RecordUpdate { .. }
@@ -2361,6 +2419,59 @@ fn expect_help<'a>(
     }
 }
 
+fn dbg_help<'a>(
+    min_indent: u32,
+    options: ExprParseOptions,
+) -> impl Parser<'a, Expr<'a>, EExpect<'a>> {
+    move |arena: &'a Bump, state: State<'a>| {
+        let start_column = state.column();
+
+        let (_, _, state) = parser::keyword_e(keyword::DBG, EExpect::Dbg).parse(arena, state)?;
+
+        let (_, value, state) = space0_before_e(
+            specialize_ref(EExpect::Condition, move |arena, state| {
+                parse_loc_expr_with_options(start_column + 1, options, arena, state)
+            }),
+            start_column + 1,
+            EExpect::IndentCondition,
+        )
+        .parse(arena, state)
+        .map_err(|(_, f, s)| (MadeProgress, f, s))?;
+
+        // Like `expect`, a `dbg` that starts a line can be followed by a continuation on the
+        // lines below it, at the same indentation. Otherwise it is an expression in its own
+        // right, e.g. `x = dbg (f y)`, which evaluates to its value.
+        let has_continuation =
+            match space0_e(min_indent, EExpect::IndentCondition).parse(arena, state.clone()) {
+                Ok((_, spaces, after_spaces)) => {
+                    spaces.iter().any(|space| space.is_newline())
+                        && after_spaces.column() == start_column
+                        && !after_spaces.has_reached_end()
+                }
+                Err(_) => false,
+            };
+
+        if !has_continuation {
+            return Ok((MadeProgress, Expr::Dbg(arena.alloc(value), None), state));
+        }
+
+        let parse_cont = specialize_ref(
+            EExpect::Continuation,
+            space0_before_e(
+                move |a, s| parse_loc_expr(min_indent, a, s),
+                min_indent,
+                EExpr::IndentEnd,
+            ),
+        );
+
+        let (_, loc_cont, state) = parse_cont.parse(arena, state)?;
+
+        let expr = Expr::Dbg(arena.alloc(value), Some(arena.alloc(loc_cont)));
+
+        Ok((MadeProgress, expr, state))
+    }
+}
+
 fn if_expr_help<'a>(
     min_indent: u32,
     options: ExprParseOptions,
@@ -2466,13 +2577,13 @@ fn ident_to_expr<'a>(arena: &'a Bump, src: Ident<'a>) -> Expr<'a> {
                 }
             };
 
-            // The remaining items in the iterator are record field accesses,
+            // The remaining items in the iterator are record field or tuple element accesses,
             // e.g. `bar` in `foo.bar.baz`, followed by `baz`
-            for field in iter {
+            for part in iter {
                 // Wrap the previous answer in the new one, so we end up
                 // with a nested Expr. That way, `foo.bar.baz` gets represented
                 // in the AST as if it had been written (foo.bar).baz all along.
-                answer = Expr::Access(arena.alloc(answer), field);
+                answer = Accessor::from_part(part).access(arena, answer);
             }
 
             answer
@@ -2649,14 +2760,14 @@ fn record_literal_help<'a>(min_indent: u32) -> impl Parser<'a, Expr<'a>, EExpr<'
             };
 
             // there can be field access, e.g. `{ x : 4 }.x`
-            let (_, accesses, state) = optional(record_field_access_chain()).parse(arena, state)?;
+            let (_, accesses, state) = optional(access_chain()).parse(arena, state)?;
 
-            if let Some(fields) = accesses {
-                for field in fields {
+            if let Some(accessors) = accesses {
+                for accessor in accessors {
                     // Wrap the previous answer in the new one, so we end up
                     // with a nested Expr. That way, `foo.bar.baz` gets represented
                     // in the AST as if it had been written (foo.bar).baz all along.
-                    value = Expr::Access(arena.alloc(value), field);
+                    value = accessor.access(arena, value);
                 }
             }
 
@@ -6,5 +6,6 @@ pub const AS: &str = "as";
 pub const IS: &str = "is";
 pub const EXPECT: &str = "expect";
 pub const EXPECT_FX: &str = "expect-fx";
+pub const DBG: &str = "dbg";
 
-pub const KEYWORDS: [&str; 8] = [IF, THEN, ELSE, WHEN, AS, IS, EXPECT, EXPECT_FX];
+pub const KEYWORDS: [&str; 9] = [IF, THEN, ELSE, WHEN, AS, IS, EXPECT, EXPECT_FX, DBG];
@@ -1,4 +1,4 @@
-use crate::ast::{Collection, CommentOrNewline, Defs, Module, Spaced};
+use crate::ast::{Collection, CommentOrNewline, Defs, Module, Spaced, ValueDef};
 use crate::blankspace::{space0_around_ee, space0_before_e, space0_e};
 use crate::header::{
     package_entry, package_name, AppHeader, ExposedName, HostedHeader, ImportsEntry,
@@ -14,7 +14,7 @@ use crate::parser::{
 use crate::state::State;
 use crate::string_literal;
 use crate::type_annotation;
-use roc_region::all::{Loc, Position};
+use roc_region::all::{Loc, Position, Region};
 
 fn end_of_file<'a>() -> impl Parser<'a, (), SyntaxError<'a>> {
     |_arena, state: State<'a>| {
@@ -38,6 +38,118 @@ pub fn module_defs<'a>() -> impl Parser<'a, Defs<'a>, SyntaxError<'a>> {
     )
 }
 
+/// A stretch of a module's top-level defs, which [module_defs_recovering] parsed on its own.
+#[derive(Debug)]
+pub struct DefsChunk<'a> {
+    /// The stretch of source code, including the whitespace and comments after its defs
+    pub region: Region,
+    pub defs: Result<Defs<'a>, SyntaxError<'a>>,
+}
+
+/// Parses the module's top-level defs one stretch at a time, starting a new stretch at each of
+/// the [def_recovery_points]. A def which doesn't parse only spoils its own stretch, so tools
+/// like the formatter can still work with the rest of a module while it's being edited.
+pub fn module_defs_recovering<'a>(
+    arena: &'a bumpalo::Bump,
+    state: State<'a>,
+) -> Vec<DefsChunk<'a>> {
+    let len = state.original_bytes().len();
+    let start = state.pos().offset as usize;
+    let mut starts = def_recovery_points(state.original_bytes(), start);
+
+    // The first stretch also gets the whitespace and comments between the header and the first def.
+    match starts.first_mut() {
+        Some(first) => *first = start,
+        None => starts.push(start),
+    }
+
+    let end_of = |index: usize| starts.get(index).copied().unwrap_or(len);
+    let parse_between = |chunk_start: usize, chunk_end: usize| {
+        module_defs()
+            .parse(arena, state.with_range(chunk_start, chunk_end))
+            .map(|(_, defs, _)| defs)
+            .map_err(|(_, error, _)| error)
+    };
+
+    let mut chunks = Vec::with_capacity(starts.len());
+    let mut index = 0;
+
+    while index < starts.len() {
+        let chunk_start = starts[index];
+        let mut chunk_end = end_of(index + 1);
+        let mut defs = parse_between(chunk_start, chunk_end);
+
+        index += 1;
+
+        // An annotation starts a line of its own, but it belongs with the body on the next one.
+        let ends_in_annotation = matches!(
+            &defs,
+            Ok(defs) if matches!(defs.last(), Some(Err(ValueDef::Annotation(..))))
+        );
+
+        if ends_in_annotation && index < starts.len() {
+            let merged = parse_between(chunk_start, end_of(index + 1));
+
+            if merged.is_ok() {
+                defs = merged;
+                chunk_end = end_of(index + 1);
+                index += 1;
+            }
+        }
+
+        chunks.push(DefsChunk {
+            region: Region::new(
+                Position::new(chunk_start as u32),
+                Position::new(chunk_end as u32),
+            ),
+            defs,
+        });
+    }
+
+    chunks
+}
+
+/// The offsets (from `start` on) where a top-level def may begin: the start of every line which
+/// doesn't begin with whitespace, a comment, or a closing bracket (which may end a multi-line def
+/// in the first column), and which isn't inside a block string.
+pub fn def_recovery_points(bytes: &[u8], start: usize) -> Vec<usize> {
+    let mut points = Vec::new();
+    let mut in_block_string = false;
+    let mut offset = 0;
+
+    for line in bytes.split(|byte| *byte == b'\n') {
+        let may_begin_def = match line.first() {
+            Some(b' ' | b'\t' | b'\r' | b'#' | b')' | b']' | b'}') | None => false,
+            Some(_) => !in_block_string,
+        };
+
+        if may_begin_def && offset >= start {
+            points.push(offset);
+        }
+
+        // `"""` both opens and closes a block string.
+        if count_block_string_quotes(line) % 2 == 1 {
+            in_block_string = !in_block_string;
+        }
+
+        offset += line.len() + 1;
+    }
+
+    points
+}
+
+fn count_block_string_quotes(line: &[u8]) -> usize {
+    let mut count = 0;
+    let mut rest = line;
+
+    while let Some(index) = rest.windows(3).position(|window| window == b"\"\"\"") {
+        count += 1;
+        rest = &rest[index + 3..];
+    }
+
+    count
+}
+
 pub fn parse_header<'a>(
     arena: &'a bumpalo::Bump,
     state: State<'a>,
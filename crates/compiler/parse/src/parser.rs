@@ -351,6 +351,7 @@ pub enum EExpr<'a> {
     If(EIf<'a>, Position),
 
     Expect(EExpect<'a>, Position),
+    Dbg(EExpect<'a>, Position),
 
     Lambda(ELambda<'a>, Position),
     Underscore(Position),
@@ -415,6 +416,8 @@ pub enum ERecord<'a> {
 pub enum EInParens<'a> {
     End(Position),
     Open(Position),
+    /// Empty parens, e.g. () is not allowed
+    Empty(Position),
     ///
     Expr(&'a EExpr<'a>, Position),
 
@@ -509,6 +512,7 @@ pub enum EIf<'a> {
 pub enum EExpect<'a> {
     Space(BadInputError, Position),
     Expect(Position),
+    Dbg(Position),
     Condition(&'a EExpr<'a>, Position),
     Continuation(&'a EExpr<'a>, Position),
     IndentCondition(Position),
@@ -555,6 +559,8 @@ pub enum PRecord<'a> {
 pub enum PInParens<'a> {
     End(Position),
     Open(Position),
+    /// Empty parens, e.g. () is not allowed
+    Empty(Position),
     Pattern(&'a EPattern<'a>, Position),
 
     Space(BadInputError, Position),
@@ -622,6 +628,8 @@ pub enum ETypeTagUnion<'a> {
 pub enum ETypeInParens<'a> {
     End(Position),
     Open(Position),
+    /// Empty parens, e.g. () is not allowed
+    Empty(Position),
     ///
     Type(&'a EType<'a>, Position),
 
@@ -149,17 +149,42 @@ fn loc_parse_tag_pattern_arg<'a>(
 fn loc_pattern_in_parens_help<'a>(
     min_indent: u32,
 ) -> impl Parser<'a, Loc<Pattern<'a>>, PInParens<'a>> {
-    between!(
-        word1(b'(', PInParens::Open),
-        space0_around_ee(
-            move |arena, state| specialize_ref(PInParens::Pattern, loc_pattern_help(min_indent))
-                .parse(arena, state),
+    move |arena, state| {
+        let (_, loc_elements, state) = loc!(collection_trailing_sep_e!(
+            word1(b'(', PInParens::Open),
+            specialize_ref(PInParens::Pattern, loc_pattern_help(min_indent)),
+            word1(b',', PInParens::End),
+            word1(b')', PInParens::End),
             min_indent,
-            PInParens::IndentOpen,
+            PInParens::Open,
             PInParens::IndentEnd,
-        ),
-        word1(b')', PInParens::End)
-    )
+            Pattern::SpaceBefore
+        ))
+        .parse(arena, state)?;
+
+        let elements = loc_elements.value;
+        let region = loc_elements.region;
+
+        if elements.len() > 1 {
+            Ok((
+                MadeProgress,
+                Loc::at(region, Pattern::Tuple(elements)),
+                state,
+            ))
+        } else if elements.is_empty() {
+            Err((NoProgress, PInParens::Empty(region.start()), state))
+        } else {
+            // Exactly one element and no comma, so this is just a pattern in parentheses.
+            let mut loc_elem = elements.items[0];
+
+            if !elements.final_comments().is_empty() {
+                loc_elem.value =
+                    Pattern::SpaceAfter(arena.alloc(loc_elem.value), elements.final_comments());
+            }
+
+            Ok((MadeProgress, loc_elem, state))
+        }
+    }
 }
 
 fn number_pattern_help<'a>() -> impl Parser<'a, Pattern<'a>, EPattern<'a>> {
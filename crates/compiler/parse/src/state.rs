@@ -81,6 +81,22 @@ impl<'a> State<'a> {
         self.offset == self.original_bytes.len()
     }
 
+    /// Returns the state at `offset`, with the input ending at `end` rather than at the end of the
+    /// file. `offset` must either be this state's own offset, or the start of a line.
+    pub(crate) fn with_range(&self, offset: usize, end: usize) -> State<'a> {
+        let line_start = if offset == self.offset {
+            self.line_start
+        } else {
+            Position::new(offset as u32)
+        };
+
+        State {
+            original_bytes: &self.original_bytes[..end],
+            offset,
+            line_start,
+        }
+    }
+
     /// Returns a Region corresponding to the current state, but
     /// with the the end column advanced by the given amount. This is
     /// useful when parsing something "manually" (using input.chars())
@@ -123,7 +123,10 @@ fn term<'a>(
             one_of!(
                 loc_wildcard(),
                 loc_inferred(),
-                specialize(EType::TInParens, loc_type_in_parens(min_indent)),
+                specialize(
+                    EType::TInParens,
+                    loc_type_in_parens(min_indent, stop_at_surface_has)
+                ),
                 loc!(specialize(
                     EType::TRecord,
                     record_type(min_indent, stop_at_surface_has)
@@ -196,7 +199,10 @@ fn loc_applied_arg<'a>(
             one_of!(
                 loc_wildcard(),
                 loc_inferred(),
-                specialize(EType::TInParens, loc_type_in_parens(min_indent)),
+                specialize(
+                    EType::TInParens,
+                    loc_type_in_parens(min_indent, stop_at_surface_has)
+                ),
                 loc!(specialize(
                     EType::TRecord,
                     record_type(min_indent, stop_at_surface_has)
@@ -222,21 +228,56 @@ fn loc_applied_arg<'a>(
 
 fn loc_type_in_parens<'a>(
     min_indent: u32,
+    stop_at_surface_has: bool,
 ) -> impl Parser<'a, Loc<TypeAnnotation<'a>>, ETypeInParens<'a>> {
-    between!(
-        word1(b'(', ETypeInParens::Open),
-        space0_around_ee(
-            move |arena, state| specialize_ref(
-                ETypeInParens::Type,
-                expression(min_indent, true, false)
-            )
-            .parse(arena, state),
+    move |arena, state| {
+        let (_, loc_elements, state) = loc!(collection_trailing_sep_e!(
+            word1(b'(', ETypeInParens::Open),
+            specialize_ref(ETypeInParens::Type, expression(min_indent, true, false)),
+            word1(b',', ETypeInParens::End),
+            word1(b')', ETypeInParens::IndentEnd),
             min_indent,
-            ETypeInParens::IndentOpen,
+            ETypeInParens::Open,
             ETypeInParens::IndentEnd,
-        ),
-        word1(b')', ETypeInParens::IndentEnd)
-    )
+            TypeAnnotation::SpaceBefore
+        ))
+        .parse(arena, state)?;
+
+        let elems = loc_elements.value;
+        let region = loc_elements.region;
+
+        if elems.len() > 1 {
+            // This could be an open tuple, e.g. `(Str, Str)a`
+            let (_, ext, state) = optional(allocated(specialize_ref(
+                ETypeInParens::Type,
+                term(min_indent, stop_at_surface_has),
+            )))
+            .parse(arena, state)?;
+
+            let region = match ext {
+                Some(ext) => Region::span_across(&region, &ext.region),
+                None => region,
+            };
+
+            Ok((
+                MadeProgress,
+                Loc::at(region, TypeAnnotation::Tuple { elems, ext }),
+                state,
+            ))
+        } else if elems.is_empty() {
+            Err((NoProgress, ETypeInParens::Empty(region.start()), state))
+        } else {
+            // Exactly one element and no comma, so this is just a type in parentheses.
+            let mut loc_elem = elems.items[0];
+
+            if !elems.final_comments().is_empty() {
+                loc_elem.value =
+                    TypeAnnotation::SpaceAfter(arena.alloc(loc_elem.value), elems.final_comments());
+            }
+
+            Ok((MadeProgress, loc_elem, state))
+        }
+    }
 }
 
 #[inline(always)]
@@ -0,0 +1 @@
+f (dbg x)
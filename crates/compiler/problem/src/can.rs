@@ -179,10 +179,154 @@ pub enum Problem {
     },
 }
 
+impl Problem {
+    /// The region of the source code this problem is about, if it is about any one region.
+    pub fn region(&self) -> Option<Region> {
+        match self {
+            Problem::UnusedDef(_, region)
+            | Problem::UnusedImport(_, region)
+            | Problem::UnusedArgument(_, _, _, region)
+            | Problem::UnusedBranchDef(_, region)
+            | Problem::PrecedenceProblem(PrecedenceProblem::BothNonAssociative(region, _, _))
+            | Problem::UnsupportedPattern(_, region)
+            | Problem::Shadowing {
+                shadow: Loc { region, .. },
+                ..
+            }
+            | Problem::CyclicAlias(_, region, _, _)
+            | Problem::PhantomTypeArgument {
+                variable_region: region,
+                ..
+            }
+            | Problem::UnboundTypeVariable {
+                one_occurrence: region,
+                ..
+            }
+            | Problem::DuplicateRecordFieldValue {
+                field_region: region,
+                ..
+            }
+            | Problem::DuplicateRecordFieldType {
+                field_region: region,
+                ..
+            }
+            | Problem::InvalidOptionalValue {
+                field_region: region,
+                ..
+            }
+            | Problem::DuplicateTag {
+                tag_region: region, ..
+            }
+            | Problem::SignatureDefMismatch {
+                def_pattern: region,
+                ..
+            }
+            | Problem::InvalidAliasRigid { region, .. }
+            | Problem::InvalidInterpolation(region)
+            | Problem::InvalidHexadecimal(region)
+            | Problem::InvalidUnicodeCodePt(region)
+            | Problem::NestedDatatype {
+                differing_recursion_region: region,
+                ..
+            }
+            | Problem::InvalidExtensionType { region, .. }
+            | Problem::AbilityHasTypeVariables {
+                variables_region: region,
+                ..
+            }
+            | Problem::HasClauseIsNotAbility { region }
+            | Problem::IllegalHasClause { region }
+            | Problem::AbilityMemberMissingHasClause { region, .. }
+            | Problem::AbilityMemberMultipleBoundVars {
+                span_has_clauses: region,
+                ..
+            }
+            | Problem::AbilityNotOnToplevel { region }
+            | Problem::AbilityUsedAsType(_, _, region)
+            | Problem::NestedSpecialization(_, region)
+            | Problem::IllegalDerivedAbility(region)
+            | Problem::ImplementationNotFound { region, .. }
+            | Problem::NotAnAbilityMember { region, .. }
+            | Problem::OptionalAbilityImpl { region, .. }
+            | Problem::QualifiedAbilityImpl { region }
+            | Problem::AbilityImplNotIdent { region }
+            | Problem::DuplicateImpl {
+                duplicate: region, ..
+            }
+            | Problem::NotAnAbility(region)
+            | Problem::ImplementsNonRequired { region, .. }
+            | Problem::DoesNotImplementAbility { region, .. }
+            | Problem::NotBoundInAllPatterns { region, .. }
+            | Problem::NoIdentifiersIntroduced(region)
+            | Problem::OverloadedSpecialization {
+                overload: region, ..
+            } => Some(*region),
+            Problem::UnknownGeneratesWith(loc_ident) => Some(loc_ident.region),
+            Problem::BadRecursion(cycle) => cycle.first().map(|entry| entry.symbol_region),
+            Problem::RuntimeError(runtime_error) => runtime_error.region(),
+            Problem::ExposedButNotDefined(_) => None,
+        }
+    }
+
+    /// A code which identifies the kind of problem, and which stays the same across
+    /// compiler versions so that tools can search for, filter on or link to it.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Problem::UnusedDef(..) => "E0101",
+            Problem::UnusedImport(..) => "E0102",
+            Problem::ExposedButNotDefined(..) => "E0103",
+            Problem::UnknownGeneratesWith(..) => "E0104",
+            Problem::UnusedArgument(..) => "E0105",
+            Problem::UnusedBranchDef(..) => "E0106",
+            Problem::PrecedenceProblem(..) => "E0107",
+            Problem::UnsupportedPattern(..) => "E0108",
+            Problem::Shadowing { .. } => "E0109",
+            Problem::CyclicAlias(..) => "E0110",
+            Problem::BadRecursion(..) => "E0111",
+            Problem::PhantomTypeArgument { .. } => "E0112",
+            Problem::UnboundTypeVariable { .. } => "E0113",
+            Problem::DuplicateRecordFieldValue { .. } => "E0114",
+            Problem::DuplicateRecordFieldType { .. } => "E0115",
+            Problem::InvalidOptionalValue { .. } => "E0116",
+            Problem::DuplicateTag { .. } => "E0117",
+            Problem::SignatureDefMismatch { .. } => "E0118",
+            Problem::InvalidAliasRigid { .. } => "E0119",
+            Problem::InvalidInterpolation(..) => "E0120",
+            Problem::InvalidHexadecimal(..) => "E0121",
+            Problem::InvalidUnicodeCodePt(..) => "E0122",
+            Problem::NestedDatatype { .. } => "E0123",
+            Problem::InvalidExtensionType { .. } => "E0124",
+            Problem::AbilityHasTypeVariables { .. } => "E0125",
+            Problem::HasClauseIsNotAbility { .. } => "E0126",
+            Problem::IllegalHasClause { .. } => "E0127",
+            Problem::AbilityMemberMissingHasClause { .. } => "E0128",
+            Problem::AbilityMemberMultipleBoundVars { .. } => "E0129",
+            Problem::AbilityNotOnToplevel { .. } => "E0130",
+            Problem::AbilityUsedAsType(..) => "E0131",
+            Problem::NestedSpecialization(..) => "E0132",
+            Problem::IllegalDerivedAbility(..) => "E0133",
+            Problem::ImplementationNotFound { .. } => "E0134",
+            Problem::NotAnAbilityMember { .. } => "E0135",
+            Problem::OptionalAbilityImpl { .. } => "E0136",
+            Problem::QualifiedAbilityImpl { .. } => "E0137",
+            Problem::AbilityImplNotIdent { .. } => "E0138",
+            Problem::DuplicateImpl { .. } => "E0139",
+            Problem::NotAnAbility(..) => "E0140",
+            Problem::ImplementsNonRequired { .. } => "E0141",
+            Problem::DoesNotImplementAbility { .. } => "E0142",
+            Problem::NotBoundInAllPatterns { .. } => "E0143",
+            Problem::NoIdentifiersIntroduced(..) => "E0144",
+            Problem::OverloadedSpecialization { .. } => "E0145",
+            Problem::RuntimeError(runtime_error) => runtime_error.code(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum ExtensionTypeKind {
     Record,
     TagUnion,
+    Tuple,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -342,6 +486,98 @@ pub enum RuntimeError {
 }
 
 impl RuntimeError {
+    /// The region of the source code this error is about, if it is about any one region.
+    pub fn region(&self) -> Option<Region> {
+        use RuntimeError::*;
+
+        match self {
+            Shadowing {
+                shadow: Loc { region, .. },
+                ..
+            }
+            | InvalidOptionalValue {
+                field_region: region,
+                ..
+            }
+            | UnsupportedPattern(region)
+            | MalformedPattern(_, region)
+            | LookupNotInScope(Loc { region, .. }, _)
+            | OpaqueNotDefined {
+                usage: Loc { region, .. },
+                ..
+            }
+            | OpaqueOutsideScope {
+                referenced_region: region,
+                ..
+            }
+            | OpaqueNotApplied(Loc { region, .. })
+            | OpaqueAppliedToMultipleArgs(region)
+            | ValueNotExposed { region, .. }
+            | ModuleNotImported { region, .. }
+            | InvalidPrecedence(_, region)
+            | MalformedIdentifier(_, _, region)
+            | MalformedTypeName(_, region)
+            | MalformedClosure(region)
+            | InvalidRecordUpdate { region }
+            | InvalidFloat(_, region, _)
+            | InvalidInt(_, _, region, _)
+            | InvalidInterpolation(region)
+            | InvalidHexadecimal(region)
+            | InvalidUnicodeCodePt(region)
+            | EmptySingleQuote(region)
+            | MultipleCharsInSingleQuote(region)
+            | DegenerateBranch(region) => Some(*region),
+            CircularDef(cycle) => cycle.first().map(|entry| entry.symbol_region),
+            UnresolvedTypeVar
+            | ErroneousType
+            | NonExhaustivePattern
+            | NoImplementationNamed { .. }
+            | NoImplementation
+            | VoidValue
+            | ExposedButNotDefined(_) => None,
+        }
+    }
+
+    /// A code which identifies the kind of error, like [Problem::code].
+    pub fn code(&self) -> &'static str {
+        use RuntimeError::*;
+
+        match self {
+            Shadowing { .. } => "E0201",
+            InvalidOptionalValue { .. } => "E0202",
+            UnsupportedPattern(..) => "E0203",
+            MalformedPattern(..) => "E0204",
+            UnresolvedTypeVar => "E0205",
+            ErroneousType => "E0206",
+            LookupNotInScope(..) => "E0207",
+            OpaqueNotDefined { .. } => "E0208",
+            OpaqueOutsideScope { .. } => "E0209",
+            OpaqueNotApplied(..) => "E0210",
+            OpaqueAppliedToMultipleArgs(..) => "E0211",
+            ValueNotExposed { .. } => "E0212",
+            ModuleNotImported { .. } => "E0213",
+            InvalidPrecedence(..) => "E0214",
+            MalformedIdentifier(..) => "E0215",
+            MalformedTypeName(..) => "E0216",
+            MalformedClosure(..) => "E0217",
+            InvalidRecordUpdate { .. } => "E0218",
+            InvalidFloat(..) => "E0219",
+            InvalidInt(..) => "E0220",
+            CircularDef(..) => "E0221",
+            NonExhaustivePattern => "E0222",
+            InvalidInterpolation(..) => "E0223",
+            InvalidHexadecimal(..) => "E0224",
+            InvalidUnicodeCodePt(..) => "E0225",
+            NoImplementationNamed { .. } => "E0226",
+            NoImplementation => "E0227",
+            VoidValue => "E0228",
+            ExposedButNotDefined(..) => "E0229",
+            EmptySingleQuote(..) => "E0230",
+            MultipleCharsInSingleQuote(..) => "E0231",
+            DegenerateBranch(..) => "E0232",
+        }
+    }
+
     pub fn runtime_message(self) -> String {
         use RuntimeError::*;
 
@@ -2,7 +2,7 @@ use roc_can::abilities::AbilitiesStore;
 use roc_can::expr::PendingDerives;
 use roc_collections::{VecMap, VecSet};
 use roc_error_macros::{internal_error, todo_abilities};
-use roc_module::symbol::Symbol;
+use roc_module::symbol::{ModuleId, Symbol};
 use roc_region::all::{Loc, Region};
 use roc_solve_problem::{TypeError, UnderivableReason, Unfulfilled};
 use roc_types::num::NumericRange;
@@ -268,6 +268,19 @@ impl ObligationCache {
                 var,
             )),
 
+            Symbol::HASH_HASH_ABILITY => {
+                Some(DeriveHash::is_derivable(self, abilities_store, subs, var))
+            }
+
+            Symbol::GENERATE_GENERATOR => Some(DeriveGenerator::is_derivable(
+                self,
+                abilities_store,
+                subs,
+                var,
+            )),
+
+            Symbol::BOOL_EQ => Some(DeriveEq::is_derivable(self, abilities_store, subs, var)),
+
             _ => None,
         };
 
@@ -428,6 +441,24 @@ fn is_builtin_number_alias(symbol: Symbol) -> bool {
     )
 }
 
+/// Number aliases that have a corresponding `Hasher` method.
+#[inline(always)]
+#[rustfmt::skip]
+fn is_hashable_number_alias(symbol: Symbol) -> bool {
+    matches!(symbol,
+          Symbol::NUM_U8   | Symbol::NUM_UNSIGNED8
+        | Symbol::NUM_U16  | Symbol::NUM_UNSIGNED16
+        | Symbol::NUM_U32  | Symbol::NUM_UNSIGNED32
+        | Symbol::NUM_U64  | Symbol::NUM_UNSIGNED64
+        | Symbol::NUM_U128 | Symbol::NUM_UNSIGNED128
+        | Symbol::NUM_I8   | Symbol::NUM_SIGNED8
+        | Symbol::NUM_I16  | Symbol::NUM_SIGNED16
+        | Symbol::NUM_I32  | Symbol::NUM_SIGNED32
+        | Symbol::NUM_I64  | Symbol::NUM_SIGNED64
+        | Symbol::NUM_I128 | Symbol::NUM_SIGNED128,
+    )
+}
+
 enum DerivableError {
     NotDerivable(Variable),
 }
@@ -480,6 +511,11 @@ trait DerivableVisitor {
         Err(DerivableError::NotDerivable(var))
     }
 
+    #[inline(always)]
+    fn visit_tuple(var: Variable) -> Result<Descend, DerivableError> {
+        Err(DerivableError::NotDerivable(var))
+    }
+
     #[inline(always)]
     fn visit_tag_union(var: Variable) -> Result<Descend, DerivableError> {
         Err(DerivableError::NotDerivable(var))
@@ -500,6 +536,11 @@ trait DerivableVisitor {
         Err(DerivableError::NotDerivable(var))
     }
 
+    #[inline(always)]
+    fn visit_empty_tuple(var: Variable) -> Result<(), DerivableError> {
+        Err(DerivableError::NotDerivable(var))
+    }
+
     #[inline(always)]
     fn visit_empty_tag_union(var: Variable) -> Result<(), DerivableError> {
         Err(DerivableError::NotDerivable(var))
@@ -515,6 +556,12 @@ trait DerivableVisitor {
         Err(DerivableError::NotDerivable(var))
     }
 
+    #[inline(always)]
+    fn visit_number_opaque(_var: Variable) -> Result<Descend, DerivableError> {
+        // Numbers: by default, always decay until a ground is hit.
+        Ok(Descend(true))
+    }
+
     #[inline(always)]
     fn is_derivable(
         obligation_cache: &mut ObligationCache,
@@ -588,6 +635,18 @@ trait DerivableVisitor {
                             }
                         }
                     }
+                    Tuple(elems, ext) => {
+                        let descend = Self::visit_tuple(var)?;
+                        if descend.0 {
+                            push_var_slice!(elems);
+                            if !matches!(
+                                subs.get_content_without_compacting(ext),
+                                Content::FlexVar(_) | Content::RigidVar(_)
+                            ) {
+                                stack.push(ext);
+                            }
+                        }
+                    }
                     TagUnion(tags, ext) => {
                         let descend = Self::visit_tag_union(var)?;
                         if descend.0 {
@@ -614,6 +673,7 @@ trait DerivableVisitor {
                         }
                     }
                     EmptyRecord => Self::visit_empty_record(var)?,
+                    EmptyTuple => Self::visit_empty_tuple(var)?,
                     EmptyTagUnion => Self::visit_empty_tag_union(var)?,
 
                     Erroneous(_) => return Err(NotDerivable(var)),
@@ -624,8 +684,10 @@ trait DerivableVisitor {
                     real_var,
                     AliasKind::Opaque,
                 ) => {
-                    // Numbers: always decay until a ground is hit.
-                    stack.push(real_var);
+                    let descend = Self::visit_number_opaque(var)?;
+                    if descend.0 {
+                        stack.push(real_var);
+                    }
                 }
                 Alias(opaque, _alias_variables, _real_var, AliasKind::Opaque) => {
                     if obligation_cache
@@ -686,6 +748,11 @@ impl DerivableVisitor for DeriveEncoding {
         Ok(Descend(true))
     }
 
+    #[inline(always)]
+    fn visit_tuple(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
     #[inline(always)]
     fn visit_tag_union(_var: Variable) -> Result<Descend, DerivableError> {
         Ok(Descend(true))
@@ -706,6 +773,11 @@ impl DerivableVisitor for DeriveEncoding {
         Ok(())
     }
 
+    #[inline(always)]
+    fn visit_empty_tuple(_var: Variable) -> Result<(), DerivableError> {
+        Ok(())
+    }
+
     #[inline(always)]
     fn visit_empty_tag_union(_var: Variable) -> Result<(), DerivableError> {
         Ok(())
@@ -757,6 +829,11 @@ impl DerivableVisitor for DeriveDecoding {
         Ok(Descend(true))
     }
 
+    #[inline(always)]
+    fn visit_tuple(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
     #[inline(always)]
     fn visit_tag_union(_var: Variable) -> Result<Descend, DerivableError> {
         Ok(Descend(true))
@@ -777,6 +854,11 @@ impl DerivableVisitor for DeriveDecoding {
         Ok(())
     }
 
+    #[inline(always)]
+    fn visit_empty_tuple(_var: Variable) -> Result<(), DerivableError> {
+        Ok(())
+    }
+
     #[inline(always)]
     fn visit_empty_tag_union(_var: Variable) -> Result<(), DerivableError> {
         Ok(())
@@ -797,6 +879,253 @@ impl DerivableVisitor for DeriveDecoding {
     }
 }
 
+struct DeriveGenerator;
+impl DerivableVisitor for DeriveGenerator {
+    const ABILITY: Symbol = Symbol::GENERATE_GENERATOR;
+
+    #[inline(always)]
+    fn is_derivable_builtin_opaque(symbol: Symbol) -> bool {
+        is_builtin_number_alias(symbol)
+    }
+
+    #[inline(always)]
+    fn visit_recursion(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_apply(var: Variable, symbol: Symbol) -> Result<Descend, DerivableError> {
+        if matches!(symbol, Symbol::LIST_LIST | Symbol::STR_STR) {
+            Ok(Descend(true))
+        } else {
+            Err(DerivableError::NotDerivable(var))
+        }
+    }
+
+    #[inline(always)]
+    fn visit_record(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_tuple(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_tag_union(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_recursive_tag_union(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_function_or_tag_union(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_empty_record(_var: Variable) -> Result<(), DerivableError> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_empty_tuple(_var: Variable) -> Result<(), DerivableError> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_empty_tag_union(var: Variable) -> Result<(), DerivableError> {
+        // There are no values of an empty tag union to generate.
+        Err(DerivableError::NotDerivable(var))
+    }
+
+    #[inline(always)]
+    fn visit_alias(_var: Variable, symbol: Symbol) -> Result<Descend, DerivableError> {
+        if is_builtin_number_alias(symbol) {
+            Ok(Descend(false))
+        } else {
+            Ok(Descend(true))
+        }
+    }
+
+    #[inline(always)]
+    fn visit_ranged_number(_var: Variable, _range: NumericRange) -> Result<(), DerivableError> {
+        Ok(())
+    }
+}
+
+struct DeriveHash;
+impl DerivableVisitor for DeriveHash {
+    const ABILITY: Symbol = Symbol::HASH_HASH_ABILITY;
+
+    #[inline(always)]
+    fn is_derivable_builtin_opaque(symbol: Symbol) -> bool {
+        is_hashable_number_alias(symbol)
+    }
+
+    #[inline(always)]
+    fn visit_recursion(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_apply(var: Variable, symbol: Symbol) -> Result<Descend, DerivableError> {
+        if matches!(symbol, Symbol::LIST_LIST | Symbol::STR_STR) {
+            Ok(Descend(true))
+        } else {
+            Err(DerivableError::NotDerivable(var))
+        }
+    }
+
+    #[inline(always)]
+    fn visit_record(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_tuple(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_tag_union(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_recursive_tag_union(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_function_or_tag_union(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_empty_record(_var: Variable) -> Result<(), DerivableError> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_empty_tuple(_var: Variable) -> Result<(), DerivableError> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_empty_tag_union(_var: Variable) -> Result<(), DerivableError> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_alias(var: Variable, symbol: Symbol) -> Result<Descend, DerivableError> {
+        if is_hashable_number_alias(symbol) {
+            Ok(Descend(false))
+        } else if is_builtin_number_alias(symbol) {
+            // Nat, floats, and Dec have no `Hasher` method.
+            Err(DerivableError::NotDerivable(var))
+        } else {
+            Ok(Descend(true))
+        }
+    }
+
+    #[inline(always)]
+    fn visit_ranged_number(_var: Variable, _range: NumericRange) -> Result<(), DerivableError> {
+        Ok(())
+    }
+}
+
+struct DeriveEq;
+impl DerivableVisitor for DeriveEq {
+    const ABILITY: Symbol = Symbol::BOOL_EQ;
+
+    #[inline(always)]
+    fn is_derivable_builtin_opaque(symbol: Symbol) -> bool {
+        symbol.module_id() == ModuleId::NUM
+    }
+
+    #[inline(always)]
+    fn visit_recursion(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_apply(var: Variable, symbol: Symbol) -> Result<Descend, DerivableError> {
+        if matches!(
+            symbol,
+            Symbol::LIST_LIST | Symbol::STR_STR | Symbol::BOX_BOX_TYPE
+        ) {
+            Ok(Descend(true))
+        } else {
+            Err(DerivableError::NotDerivable(var))
+        }
+    }
+
+    #[inline(always)]
+    fn visit_record(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_tuple(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_tag_union(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_recursive_tag_union(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_function_or_tag_union(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_empty_record(_var: Variable) -> Result<(), DerivableError> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_empty_tuple(_var: Variable) -> Result<(), DerivableError> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_empty_tag_union(_var: Variable) -> Result<(), DerivableError> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_alias(_var: Variable, symbol: Symbol) -> Result<Descend, DerivableError> {
+        if is_builtin_number_alias(symbol) {
+            Ok(Descend(false))
+        } else {
+            Ok(Descend(true))
+        }
+    }
+
+    #[inline(always)]
+    fn visit_ranged_number(_var: Variable, _range: NumericRange) -> Result<(), DerivableError> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_number_opaque(_var: Variable) -> Result<Descend, DerivableError> {
+        // All numbers can be compared, whether or not their precision is known.
+        Ok(Descend(false))
+    }
+}
+
 /// Determines what type implements an ability member of a specialized signature, given the
 /// [MustImplementAbility] constraints of the signature.
 pub fn type_implementing_specialization(
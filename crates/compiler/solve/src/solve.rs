@@ -1611,6 +1611,11 @@ fn open_tag_union(subs: &mut Subs, var: Variable) {
                 stack.extend(subs.get_subs_slice(fields.variables()));
             }
 
+            Structure(Tuple(elems, _)) => {
+                // Open up all nested tag unions.
+                stack.extend(subs.get_subs_slice(elems));
+            }
+
             _ => {
                 // Everything else is not a structural type that can be opened
                 // (i.e. cannot be matched in a pattern-match)
@@ -2270,6 +2275,25 @@ fn type_to_variable<'a>(
                 register_with_known_var(subs, destination, rank, pools, content)
             }
 
+            Tuple(elems, ext) => {
+                let new_elems = VariableSubsSlice::reserve_into_subs(subs, elems.len());
+                for (target_index, elem) in (new_elems.indices()).zip(elems) {
+                    let var = helper!(elem);
+                    subs.variables[target_index] = var;
+                }
+
+                // Unlike record fields, the elements in the extension are not merged in here;
+                // unification and gathering chase the extension of a tuple when needed.
+                let ext_var = match ext {
+                    TypeExtension::Open(ext) => helper!(ext),
+                    TypeExtension::Closed => Variable::EMPTY_TUPLE,
+                };
+
+                let content = Content::Structure(FlatType::Tuple(new_elems, ext_var));
+
+                register_with_known_var(subs, destination, rank, pools, content)
+            }
+
             TagUnion(tags, ext) => {
                 // An empty tags is inefficient (but would be correct)
                 // If hit, try to turn the value into an EmptyTagUnion in canonicalization
@@ -3121,6 +3145,9 @@ fn adjust_rank_content(
                 // THEORY: an empty tag never needs to get generalized
                 EmptyTagUnion => Rank::toplevel(),
 
+                // Like an empty record, an empty tuple must not force de-generalization.
+                EmptyTuple => group_rank,
+
                 Record(fields, ext_var) => {
                     let mut rank = adjust_rank(subs, young_mark, visit_mark, group_rank, *ext_var);
 
@@ -3132,6 +3159,17 @@ fn adjust_rank_content(
                     rank
                 }
 
+                Tuple(elems, ext_var) => {
+                    let mut rank = adjust_rank(subs, young_mark, visit_mark, group_rank, *ext_var);
+
+                    for index in elems.into_iter() {
+                        let var = subs[index];
+                        rank = rank.max(adjust_rank(subs, young_mark, visit_mark, group_rank, var));
+                    }
+
+                    rank
+                }
+
                 TagUnion(tags, ext_var) => {
                     let mut rank = adjust_rank(subs, young_mark, visit_mark, group_rank, *ext_var);
                     // For performance reasons, we only keep one representation of empty tag unions
@@ -3480,7 +3518,10 @@ fn deep_copy_var_help(
                         Func(new_arguments, new_closure_var, new_ret_var)
                     }
 
-                    same @ EmptyRecord | same @ EmptyTagUnion | same @ Erroneous(_) => same,
+                    same @ EmptyRecord
+                    | same @ EmptyTagUnion
+                    | same @ EmptyTuple
+                    | same @ Erroneous(_) => same,
 
                     Record(fields, ext_var) => {
                         let record_fields = {
@@ -3522,6 +3563,12 @@ fn deep_copy_var_help(
                         Record(record_fields, work!(ext_var))
                     }
 
+                    Tuple(elems, ext_var) => {
+                        let new_elems = copy_sequence!(elems.len(), elems);
+
+                        Tuple(new_elems, work!(ext_var))
+                    }
+
                     TagUnion(tags, ext_var) => {
                         let union_tags = copy_union!(tags);
 
@@ -17,8 +17,8 @@ use roc_error_macros::{internal_error, todo_abilities};
 use roc_module::symbol::{ModuleId, Symbol};
 use roc_types::{
     subs::{
-        get_member_lambda_sets_at_region, Content, Descriptor, GetSubsSlice, LambdaSet, Mark,
-        OptVariable, Rank, Subs, SubsSlice, UlsOfVar, Variable,
+        get_member_lambda_sets_at_region, Content, Descriptor, FlatType, GetSubsSlice, LambdaSet,
+        Mark, OptVariable, Rank, Subs, SubsSlice, UlsOfVar, UnionLambdas, Variable,
     },
     types::{AliasKind, MemberImpl, Uls},
 };
@@ -601,6 +601,7 @@ enum SpecializationTypeKey {
     Opaque(Symbol),
     Derived(DeriveKey),
     Immediate(Symbol),
+    SingleLambdaSetImmediate(Symbol),
 }
 
 enum SpecializeDecision {
@@ -623,65 +624,49 @@ fn make_specialization_decision<P: Phase>(
     use SpecializationTypeKey::*;
     match subs.get_content_without_compacting(var) {
         Alias(opaque, _, _, AliasKind::Opaque) if opaque.module_id() != ModuleId::NUM => {
-            if P::IS_LATE {
-                SpecializeDecision::Specialize(Opaque(*opaque))
-            } else {
-                // Solving within a module.
-                phase.with_module_abilities_store(opaque.module_id(), |abilities_store| {
-                    let impl_key = ImplKey {
-                        opaque: *opaque,
-                        ability_member,
-                    };
-                    match abilities_store.get_implementation(impl_key) {
-                        None => {
-                            // Doesn't specialize; an error will already be reported for this.
-                            SpecializeDecision::Drop
-                        }
-                        Some(MemberImpl::Error | MemberImpl::Derived) => {
-                            // TODO: probably not right, we may want to choose a derive decision!
-                            SpecializeDecision::Specialize(Opaque(*opaque))
-                        }
-                        Some(MemberImpl::Impl(specialization_symbol)) => {
-                            match abilities_store.specialization_info(*specialization_symbol) {
-                                Some(_) => SpecializeDecision::Specialize(Opaque(*opaque)),
-
-                                // If we expect a specialization impl but don't yet know it, we must hold off
-                                // compacting the lambda set until the specialization is well-known.
-                                None => SpecializeDecision::PendingSpecialization(impl_key),
-                            }
-                        }
-                    }
-                })
-            }
-        }
-        Structure(_) | Alias(_, _, _, _) => {
-            let builtin = match ability_member.try_into() {
-                Ok(builtin) => builtin,
-                Err(_) => return SpecializeDecision::Drop,
+            let impl_key = ImplKey {
+                opaque: *opaque,
+                ability_member,
             };
-
-            // This is a structural type, find the derived ability function it should use.
-            match roc_derive_key::Derived::builtin(builtin, subs, var) {
-                Ok(derived) => match derived {
-                    roc_derive_key::Derived::Immediate(imm) => {
-                        SpecializeDecision::Specialize(Immediate(imm))
-                    }
-                    roc_derive_key::Derived::Key(derive_key) => {
-                        SpecializeDecision::Specialize(Derived(derive_key))
-                    }
-                },
-                Err(DeriveError::UnboundVar) => {
-                    // not specialized yet, but that also means that it can't possibly be derivable
-                    // at this point?
-                    // TODO: is this right? Revisit if it causes us problems in the future.
-                    SpecializeDecision::Drop
+            let opt_impl = phase
+                .with_module_abilities_store(opaque.module_id(), |abilities_store| {
+                    abilities_store.get_implementation(impl_key).copied()
+                });
+
+            match opt_impl {
+                Some(MemberImpl::Derived) => {
+                    // A derived implementation for an opaque is the derived implementation of
+                    // its underlying type, which the deriver looks through to.
+                    make_derived_specialization_decision(subs, var, ability_member)
                 }
-                Err(DeriveError::Underivable) => {
-                    // we should have reported an error for this; drop the lambda set.
+                _ if P::IS_LATE => SpecializeDecision::Specialize(Opaque(*opaque)),
+                None => {
+                    // Doesn't specialize; an error will already be reported for this.
                     SpecializeDecision::Drop
                 }
+                Some(MemberImpl::Error) => SpecializeDecision::Specialize(Opaque(*opaque)),
+                Some(MemberImpl::Impl(specialization_symbol)) => {
+                    phase.with_module_abilities_store(opaque.module_id(), |abilities_store| {
+                        match abilities_store.specialization_info(specialization_symbol) {
+                            Some(_) => SpecializeDecision::Specialize(Opaque(*opaque)),
+
+                            // If we expect a specialization impl but don't yet know it, we must hold off
+                            // compacting the lambda set until the specialization is well-known.
+                            None => SpecializeDecision::PendingSpecialization(impl_key),
+                        }
+                    })
+                }
             }
         }
+        Structure(_) | Alias(_, _, _, _) => {
+            // This is a structural type, find the derived ability function it should use.
+            make_derived_specialization_decision(subs, var, ability_member)
+        }
+        RangedNumber(..) if ability_member == Symbol::BOOL_IS_EQ => {
+            // Number literals whose type is not yet known are always compared structurally, no
+            // matter what number type they end up as.
+            SpecializeDecision::Specialize(SingleLambdaSetImmediate(Symbol::BOOL_STRUCTURAL_EQ))
+        }
         Error => SpecializeDecision::Drop,
         FlexAbleVar(_, _)
         | RigidAbleVar(..)
@@ -695,6 +680,43 @@ fn make_specialization_decision<P: Phase>(
     }
 }
 
+fn make_derived_specialization_decision(
+    subs: &Subs,
+    var: Variable,
+    ability_member: Symbol,
+) -> SpecializeDecision {
+    use SpecializationTypeKey::*;
+
+    let builtin = match ability_member.try_into() {
+        Ok(builtin) => builtin,
+        Err(_) => return SpecializeDecision::Drop,
+    };
+
+    match roc_derive_key::Derived::builtin(builtin, subs, var) {
+        Ok(derived) => match derived {
+            roc_derive_key::Derived::Immediate(imm) => {
+                SpecializeDecision::Specialize(Immediate(imm))
+            }
+            roc_derive_key::Derived::SingleLambdaSetImmediate(imm) => {
+                SpecializeDecision::Specialize(SingleLambdaSetImmediate(imm))
+            }
+            roc_derive_key::Derived::Key(derive_key) => {
+                SpecializeDecision::Specialize(Derived(derive_key))
+            }
+        },
+        Err(DeriveError::UnboundVar) => {
+            // not specialized yet, but that also means that it can't possibly be derivable
+            // at this point?
+            // TODO: is this right? Revisit if it causes us problems in the future.
+            SpecializeDecision::Drop
+        }
+        Err(DeriveError::Underivable) => {
+            // we should have reported an error for this; drop the lambda set.
+            SpecializeDecision::Drop
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn get_specialization_lambda_set_ambient_function<P: Phase>(
     subs: &mut Subs,
@@ -785,5 +807,41 @@ fn get_specialization_lambda_set_ambient_function<P: Phase>(
 
             Ok(immediate_lambda_set_at_region)
         }
+
+        SpecializationTypeKey::SingleLambdaSetImmediate(imm) => {
+            // The immediate is a plain function whose lambda set consists of only itself, so we
+            // can build its ambient function `a, a -[[imm]]-> Bool` directly.
+            debug_assert_eq!(ability_member, Symbol::BOOL_IS_EQ);
+            debug_assert_eq!(lset_region, 1);
+
+            let fresh = |subs: &mut Subs, content| {
+                subs.fresh(Descriptor {
+                    content,
+                    rank: target_rank,
+                    mark: Mark::NONE,
+                    copy: OptVariable::NONE,
+                })
+            };
+
+            let arg_var = fresh(subs, Content::FlexVar(None));
+            let ambient_function = fresh(subs, Content::Error);
+            let solved = UnionLambdas::insert_into_subs(subs, [(imm, [])]);
+            let lambda_set = fresh(
+                subs,
+                Content::LambdaSet(LambdaSet {
+                    solved,
+                    recursion_var: OptVariable::NONE,
+                    unspecialized: SubsSlice::default(),
+                    ambient_function,
+                }),
+            );
+            let arguments = SubsSlice::insert_into_subs(subs, [arg_var, arg_var]);
+            subs.set_content(
+                ambient_function,
+                Content::Structure(FlatType::Func(arguments, lambda_set, Variable::BOOL)),
+            );
+
+            Ok(ambient_function)
+        }
     }
 }
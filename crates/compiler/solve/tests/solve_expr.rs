@@ -1431,6 +1431,71 @@ mod solve_expr {
         infer_eq("{ x: 5, y : 3.14 }.x", "Num *");
     }
 
+    #[test]
+    fn two_elem_tuple() {
+        infer_eq("(5, \"foo\")", "( Num *, Str )");
+    }
+
+    #[test]
+    fn tuple_literal_accessor() {
+        infer_eq("(5, \"foo\").1", "Str");
+    }
+
+    #[test]
+    fn tuple_arg() {
+        infer_eq("\\tup -> tup.1", "( *, a )* -> a");
+    }
+
+    #[test]
+    fn tuple_destructure() {
+        infer_eq_without_problem(
+            indoc!(
+                r#"
+                swap = \(a, b) -> (b, a)
+
+                swap
+                "#
+            ),
+            "( a, b ) -> ( b, a )",
+        );
+    }
+
+    #[test]
+    fn tuple_annotation() {
+        infer_eq_without_problem(
+            indoc!(
+                r#"
+                pair : (Str, U64)
+                pair = ("a", 1)
+
+                pair.1
+                "#
+            ),
+            "U64",
+        );
+    }
+
+    #[test]
+    fn dbg_evaluates_to_value() {
+        infer_eq_without_problem("\\x -> dbg x", "a -> a");
+    }
+
+    #[test]
+    fn dbg_with_continuation() {
+        infer_eq_without_problem(
+            indoc!(
+                r#"
+                x = "hello"
+
+                dbg x
+
+                42
+                "#
+            ),
+            "Num *",
+        );
+    }
+
     #[test]
     fn record_arg() {
         infer_eq("\\rec -> rec.x", "{ x : a }* -> a");
@@ -3469,7 +3534,7 @@ mod solve_expr {
                 Dict.insert
                 "#
             ),
-            "Dict k v, k, v -> Dict k v",
+            "Dict k v, k, v -> Dict k v | k has Eq",
         );
     }
 
@@ -3730,7 +3795,7 @@ mod solve_expr {
         infer_eq_without_problem(
             indoc!(
                 r#"
-                reconstructPath : Dict position position, position -> List position
+                reconstructPath : Dict position position, position -> List position | position has Eq
                 reconstructPath = \cameFrom, goal ->
                     when Dict.get cameFrom goal is
                         Err KeyNotFound ->
@@ -3742,7 +3807,7 @@ mod solve_expr {
                 reconstructPath
                 "#
             ),
-            "Dict position position, position -> List position",
+            "Dict position position, position -> List position | position has Eq",
         );
     }
 
@@ -3777,7 +3842,7 @@ mod solve_expr {
 
                 Model position : { openSet : Set position }
 
-                cheapestOpen : Model position -> Result position [KeyNotFound]*
+                cheapestOpen : Model position -> Result position [KeyNotFound]* | position has Eq
                 cheapestOpen = \model ->
 
                     folder = \resSmallestSoFar, position ->
@@ -3792,14 +3857,14 @@ mod solve_expr {
                     Set.walk model.openSet (Ok { position: boom {}, cost: 0.0 }) folder
                         |> Result.map (\x -> x.position)
 
-                astar : Model position -> Result position [KeyNotFound]*
+                astar : Model position -> Result position [KeyNotFound]* | position has Eq
                 astar = \model -> cheapestOpen model
 
                 main =
                     astar
                 "#
             ),
-            "Model position -> Result position [KeyNotFound]*",
+            "Model position -> Result position [KeyNotFound]* | position has Eq",
         );
     }
 
@@ -59,3 +59,60 @@ pub enum UnderivableReason {
     /// A nested type is not derivable
     NestedNotDerivable(ErrorType),
 }
+
+impl TypeError {
+    /// The region of the source code this error is about, if it is about any one region.
+    pub fn region(&self) -> Option<Region> {
+        use roc_types::types::Problem;
+
+        match self {
+            TypeError::BadExpr(region, ..)
+            | TypeError::BadPattern(region, ..)
+            | TypeError::CircularType(region, ..)
+            | TypeError::BadExprMissingAbility(region, ..)
+            | TypeError::BadPatternMissingAbility(region, ..)
+            | TypeError::StructuralSpecialization { region, .. }
+            | TypeError::WrongSpecialization { region, .. } => Some(*region),
+            TypeError::CircularDef(cycle) => cycle.first().map(|entry| entry.symbol_region),
+            TypeError::BadType(problem) => match problem {
+                Problem::CircularType(_, _, region)
+                | Problem::CyclicAlias(_, region, _)
+                | Problem::Shadowed(region, _)
+                | Problem::BadTypeArguments { region, .. }
+                | Problem::HasClauseIsNotAbility(region) => Some(*region),
+                Problem::CanonicalizationProblem
+                | Problem::UnrecognizedIdent(_)
+                | Problem::InvalidModule
+                | Problem::SolvedTypeError => None,
+            },
+            TypeError::UnfulfilledAbility(Unfulfilled::OpaqueUnderivable {
+                derive_region, ..
+            }) => Some(*derive_region),
+            TypeError::UnfulfilledAbility(_) | TypeError::UnexposedLookup(_) => None,
+            TypeError::Exhaustive(roc_exhaustive::Error::Incomplete(region, ..)) => Some(*region),
+            TypeError::Exhaustive(roc_exhaustive::Error::Redundant { branch_region, .. }) => {
+                Some(*branch_region)
+            }
+        }
+    }
+
+    /// A code which identifies the kind of error, and which stays the same across compiler
+    /// versions so that tools can search for, filter on or link to it.
+    pub fn code(&self) -> &'static str {
+        match self {
+            TypeError::BadExpr(..) => "E0301",
+            TypeError::BadPattern(..) => "E0302",
+            TypeError::CircularType(..) => "E0303",
+            TypeError::CircularDef(..) => "E0304",
+            TypeError::BadType(..) => "E0305",
+            TypeError::UnexposedLookup(..) => "E0306",
+            TypeError::UnfulfilledAbility(..) => "E0307",
+            TypeError::BadExprMissingAbility(..) => "E0308",
+            TypeError::BadPatternMissingAbility(..) => "E0309",
+            TypeError::Exhaustive(roc_exhaustive::Error::Incomplete(..)) => "E0310",
+            TypeError::Exhaustive(roc_exhaustive::Error::Redundant { .. }) => "E0311",
+            TypeError::StructuralSpecialization { .. } => "E0312",
+            TypeError::WrongSpecialization { .. } => "E0313",
+        }
+    }
+}
@@ -28,6 +28,27 @@ test_key_eq! {
     explicit_empty_record_and_implicit_empty_record:
         v!(EMPTY_RECORD), v!({})
 
+    same_tuple:
+        v!((v!(U8), v!(STR),)), v!((v!(U8), v!(STR),))
+    same_tuple_elems_diff_types:
+        v!((v!(U8), v!(U8),)), v!((v!(STR), v!(STR),))
+    explicit_empty_tuple_and_implicit_empty_tuple:
+        v!(EMPTY_TUPLE), v!(())
+
+    same_tag_union:
+        v!([ A v!(U8) v!(STR), B v!(STR) ]), v!([ A v!(U8) v!(STR), B v!(STR) ])
+    same_tag_union_tags_diff_types:
+        v!([ A v!(U8) v!(U8), B v!(U8) ]), v!([ A v!(STR) v!(STR), B v!(STR) ])
+    same_tag_union_tags_any_order:
+        v!([ A v!(U8) v!(U8), B v!(U8), C ]), v!([ C, B v!(STR), A v!(STR) v!(STR) ])
+    explicit_empty_tag_union_and_implicit_empty_tag_union:
+        v!(EMPTY_TAG_UNION), v!([])
+
+    same_recursive_tag_union:
+        v!([ Nil, Cons v!(^lst)] as lst), v!([ Nil, Cons v!(^lst)] as lst)
+    same_tag_union_and_recursive_tag_union_fields:
+        v!([ Nil, Cons v!(STR)]), v!([ Nil, Cons v!(^lst)] as lst)
+
     list_list_diff_types:
         v!(Symbol::LIST_LIST v!(STR)), v!(Symbol::LIST_LIST v!(U8))
     str_str:
@@ -41,6 +62,18 @@ test_key_neq! {
         v!({ a: v!(U8), }), v!({ b: v!(U8), })
     record_empty_vs_nonempty:
         v!(EMPTY_RECORD), v!({ a: v!(U8), })
+
+    different_tuple_arities:
+        v!((v!(U8), v!(U8),)), v!((v!(U8), v!(U8), v!(U8),))
+
+    different_tag_union_tags:
+        v!([ A v!(U8) ]), v!([ B v!(U8) ])
+    tag_union_empty_vs_nonempty:
+        v!(EMPTY_TAG_UNION), v!([ B v!(U8) ])
+    different_tag_union_payload_sizes:
+        v!([ A v!(U8) ]), v!([ A v!(U8) v!(U8) ])
+    different_recursive_tag_union_tags:
+        v!([ Nil, Cons v!(^lst) ] as lst), v!([ Nil, Next v!(^lst) ] as lst)
 }
 
 #[test]
@@ -90,12 +123,12 @@ fn record_2_fields() {
         assert_snapshot!(golden, @r###"
         # derived for { first : Str, second : Str }
         # Decoder { first : val, second : val1 } fmt | fmt has DecoderFormatting, val has Decoding, val1 has Decoding
-        # List U8, fmt -[[custom(22)]]-> { rest : List U8, result : [Err [TooShort], Ok { first : val, second : val1 }] } | fmt has DecoderFormatting, val has Decoding, val1 has Decoding
+        # List U8, fmt -[[custom(27)]]-> { rest : List U8, result : [Err [TooShort], Ok { first : val, second : val1 }] } | fmt has DecoderFormatting, val has Decoding, val1 has Decoding
         # Specialization lambda sets:
-        #   @<1>: [[custom(22)]]
+        #   @<1>: [[custom(27)]]
         #Derived.decoder_{first,second} =
           Decode.custom
-            \#Derived.bytes3, #Derived.fmt3 ->
+            \#Derived.bytes3, #Derived.fmt4 ->
               Decode.decodeWith
                 #Derived.bytes3
                 (Decode.record
@@ -104,11 +137,11 @@ fn record_2_fields() {
                     when #Derived.field is
                       "first" ->
                         Keep (Decode.custom
-                          \#Derived.bytes, #Derived.fmt ->
+                          \#Derived.bytes, #Derived.fmt2 ->
                             when Decode.decodeWith
                                 #Derived.bytes
                                 Decode.decoder
-                                #Derived.fmt is
+                                #Derived.fmt2 is
                               #Derived.rec ->
                                 {
                                   result: when #Derived.rec.result is
@@ -119,11 +152,11 @@ fn record_2_fields() {
                                 })
                       "second" ->
                         Keep (Decode.custom
-                          \#Derived.bytes2, #Derived.fmt2 ->
+                          \#Derived.bytes2, #Derived.fmt3 ->
                             when Decode.decodeWith
                                 #Derived.bytes2
                                 Decode.decoder
-                                #Derived.fmt2 is
+                                #Derived.fmt3 is
                               #Derived.rec2 ->
                                 {
                                   result: when #Derived.rec2.result is
@@ -133,15 +166,29 @@ fn record_2_fields() {
                                   rest: #Derived.rec2.rest
                                 })
                       _ -> Skip
-                  \#Derived.stateRecord ->
-                    when #Derived.stateRecord.first is
+                  \#Derived.stateRecord, #Derived.fmt ->
+                    when when #Derived.stateRecord.first is
+                        Ok #Derived.first2 -> Ok #Derived.first2
+                        _ ->
+                          when Decode.decodeWith
+                              []
+                              Decode.decoder
+                              #Derived.fmt is
+                            #Derived.decRec2 -> #Derived.decRec2.result is
                       Ok #Derived.first ->
-                        when #Derived.stateRecord.second is
+                        when when #Derived.stateRecord.second is
+                            Ok #Derived.second2 -> Ok #Derived.second2
+                            _ ->
+                              when Decode.decodeWith
+                                  []
+                                  Decode.decoder
+                                  #Derived.fmt is
+                                #Derived.decRec -> #Derived.decRec.result is
                           Ok #Derived.second ->
                             Ok { second: #Derived.second, first: #Derived.first }
                           _ -> Err TooShort
                       _ -> Err TooShort)
-                #Derived.fmt3
+                #Derived.fmt4
         "###
         )
     })
@@ -33,6 +33,13 @@ test_key_eq! {
         v!({ a: v!(U8), b: v!(U8), }),
         v!({ ?a: v!(U8), ?b: v!(U8), })
 
+    same_tuple:
+        v!((v!(U8), v!(STR),)), v!((v!(U8), v!(STR),))
+    same_tuple_elems_diff_types:
+        v!((v!(U8), v!(U8),)), v!((v!(STR), v!(STR),))
+    explicit_empty_tuple_and_implicit_empty_tuple:
+        v!(EMPTY_TUPLE), v!(())
+
     same_tag_union:
         v!([ A v!(U8) v!(STR), B v!(STR) ]), v!([ A v!(U8) v!(STR), B v!(STR) ])
     same_tag_union_tags_diff_types:
@@ -78,6 +85,9 @@ test_key_neq! {
     record_empty_vs_nonempty:
         v!(EMPTY_RECORD), v!({ a: v!(U8), })
 
+    different_tuple_arities:
+        v!((v!(U8), v!(U8),)), v!((v!(U8), v!(U8), v!(U8),))
+
     different_tag_union_tags:
         v!([ A v!(U8) ]), v!([ B v!(U8) ])
     tag_union_empty_vs_nonempty:
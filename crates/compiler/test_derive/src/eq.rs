@@ -0,0 +1,87 @@
+#![cfg(test)]
+// Even with #[allow(non_snake_case)] on individual idents, rust-analyzer issues diagnostics.
+// See https://github.com/rust-lang/rust-analyzer/issues/6541.
+// For the `v!` macro we use uppercase variables when constructing tag unions.
+#![allow(non_snake_case)]
+
+use crate::{
+    test_key_eq, test_key_neq,
+    util::{check_single_lset_immediate, check_underivable},
+    v,
+};
+use roc_module::symbol::Symbol;
+use roc_types::subs::Variable;
+
+use roc_derive_key::{DeriveBuiltin::IsEq, DeriveError};
+
+test_key_eq! {
+    IsEq,
+
+    same_record:
+        v!({ a: v!(*), }), v!({ a: v!(*), })
+    same_record_fields_diff_types:
+        v!({ a: v!(*), }), v!({ a: v!(@Symbol::BOOL_BOOL => v!([ True, False ])), })
+    same_record_fields_any_order:
+        v!({ a: v!(*), b: v!(U8), c: v!(U8), }),
+        v!({ c: v!(U8), a: v!(*), b: v!(U8), })
+
+    same_tuple:
+        v!((v!(*), v!(U8),)), v!((v!(*), v!(U8),))
+    same_tuple_elems_diff_types:
+        v!((v!(*), v!(U8),)), v!((v!(*), v!(STR),))
+
+    same_tag_union:
+        v!([ A v!(*) v!(STR), B v!(STR) ]), v!([ A v!(*) v!(STR), B v!(STR) ])
+    same_tag_union_tags_diff_types:
+        v!([ A v!(*) v!(U8), B v!(U8) ]), v!([ A v!(*) v!(STR), B v!(STR) ])
+    same_tag_union_tags_any_order:
+        v!([ A v!(*) v!(U8), B v!(U8), C ]), v!([ C, B v!(STR), A v!(*) v!(STR) ])
+
+    same_recursive_tag_union:
+        v!([ Nil, Cons v!(*) v!(^lst)] as lst), v!([ Nil, Cons v!(*) v!(^lst)] as lst)
+
+    list_list_diff_types:
+        v!(Symbol::LIST_LIST v!(*)), v!(Symbol::LIST_LIST v!(@Symbol::BOOL_BOOL => v!([ True, False ])))
+
+    structural_types_all_immediate:
+        v!({ a: v!(U8), b: v!(STR), }), v!([ A v!(U8), B v!(Symbol::LIST_LIST v!(STR)) ])
+}
+
+test_key_neq! {
+    IsEq,
+
+    different_record_fields:
+        v!({ a: v!(*), }), v!({ b: v!(*), })
+    record_structural_vs_custom:
+        v!({ a: v!(U8), }), v!({ a: v!(*), })
+
+    different_tuple_arities:
+        v!((v!(*), v!(*),)), v!((v!(*), v!(*), v!(*),))
+
+    different_tag_union_tags:
+        v!([ A v!(*) ]), v!([ B v!(*) ])
+    different_recursive_tag_union_tags:
+        v!([ Nil, Cons v!(*) v!(^lst) ] as lst), v!([ Nil, Next v!(*) v!(^lst) ] as lst)
+}
+
+#[test]
+fn structural_immediates() {
+    check_single_lset_immediate(IsEq, v!(U8), Symbol::BOOL_STRUCTURAL_EQ);
+    check_single_lset_immediate(IsEq, v!(F64), Symbol::BOOL_STRUCTURAL_EQ);
+    check_single_lset_immediate(IsEq, v!(STR), Symbol::BOOL_STRUCTURAL_EQ);
+    check_single_lset_immediate(
+        IsEq,
+        v!(Symbol::LIST_LIST v!(U8)),
+        Symbol::BOOL_STRUCTURAL_EQ,
+    );
+    check_single_lset_immediate(
+        IsEq,
+        v!({ a: v!(U8), b: v!([ A v!(STR), B ]), }),
+        Symbol::BOOL_STRUCTURAL_EQ,
+    );
+}
+
+#[test]
+fn unbound_var_not_immediate() {
+    check_underivable(IsEq, v!(*), DeriveError::UnboundVar);
+}
@@ -0,0 +1,104 @@
+#![cfg(test)]
+// Even with #[allow(non_snake_case)] on individual idents, rust-analyzer issues diagnostics.
+// See https://github.com/rust-lang/rust-analyzer/issues/6541.
+// For the `v!` macro we use uppercase variables when constructing tag unions.
+#![allow(non_snake_case)]
+
+use crate::{
+    test_key_eq, test_key_neq,
+    util::{check_single_lset_immediate, check_underivable},
+    v,
+};
+use roc_module::symbol::Symbol;
+use roc_types::subs::Variable;
+
+use roc_derive_key::{DeriveBuiltin::Generate, DeriveError};
+
+test_key_eq! {
+    Generate,
+
+    same_record:
+        v!({ a: v!(U8), }), v!({ a: v!(U8), })
+    same_record_fields_diff_types:
+        v!({ a: v!(U8), }), v!({ a: v!(STR), })
+    same_record_fields_any_order:
+        v!({ a: v!(U8), b: v!(U8), c: v!(U8), }),
+        v!({ c: v!(U8), a: v!(U8), b: v!(U8), })
+    explicit_empty_record_and_implicit_empty_record:
+        v!(EMPTY_RECORD), v!({})
+
+    same_tuple:
+        v!((v!(U8), v!(STR),)), v!((v!(U8), v!(STR),))
+    same_tuple_elems_diff_types:
+        v!((v!(U8), v!(U8),)), v!((v!(STR), v!(STR),))
+    explicit_empty_tuple_and_implicit_empty_tuple:
+        v!(EMPTY_TUPLE), v!(())
+
+    same_tag_union:
+        v!([ A v!(U8) v!(STR), B v!(STR) ]), v!([ A v!(U8) v!(STR), B v!(STR) ])
+    same_tag_union_tags_diff_types:
+        v!([ A v!(U8) v!(U8), B v!(U8) ]), v!([ A v!(STR) v!(STR), B v!(STR) ])
+    same_tag_union_tags_any_order:
+        v!([ A v!(U8) v!(U8), B v!(U8), C ]), v!([ C, B v!(STR), A v!(STR) v!(STR) ])
+
+    same_recursive_tag_union:
+        v!([ Nil, Cons v!(^lst)] as lst), v!([ Nil, Cons v!(^lst)] as lst)
+    same_tag_union_and_recursive_tag_union_fields:
+        v!([ Nil, Cons v!(STR)]), v!([ Nil, Cons v!(^lst)] as lst)
+
+    list_list_diff_types:
+        v!(Symbol::LIST_LIST v!(STR)), v!(Symbol::LIST_LIST v!(U8))
+
+    alias_eq_real_type:
+        v!(Symbol::BOOL_BOOL => v!([ True, False ])), v!([False, True])
+    opaque_eq_real_type:
+        v!(@Symbol::BOOL_BOOL => v!([ True, False ])), v!([False, True])
+}
+
+test_key_neq! {
+    Generate,
+
+    different_record_fields:
+        v!({ a: v!(U8), }), v!({ b: v!(U8), })
+    record_empty_vs_nonempty:
+        v!(EMPTY_RECORD), v!({ a: v!(U8), })
+
+    different_tuple_arities:
+        v!((v!(U8), v!(U8),)), v!((v!(U8), v!(U8), v!(U8),))
+
+    different_tag_union_tags:
+        v!([ A v!(U8) ]), v!([ B v!(U8) ])
+    different_tag_union_payload_sizes:
+        v!([ A v!(U8) ]), v!([ A v!(U8) v!(U8) ])
+    different_recursive_tag_union_tags:
+        v!([ Nil, Cons v!(^lst) ] as lst), v!([ Nil, Next v!(^lst) ] as lst)
+}
+
+#[test]
+fn immediates() {
+    check_single_lset_immediate(Generate, v!(U8), Symbol::GENERATE_U8);
+    check_single_lset_immediate(Generate, v!(U16), Symbol::GENERATE_U16);
+    check_single_lset_immediate(Generate, v!(U32), Symbol::GENERATE_U32);
+    check_single_lset_immediate(Generate, v!(U64), Symbol::GENERATE_U64);
+    check_single_lset_immediate(Generate, v!(U128), Symbol::GENERATE_U128);
+    check_single_lset_immediate(Generate, v!(I8), Symbol::GENERATE_I8);
+    check_single_lset_immediate(Generate, v!(I16), Symbol::GENERATE_I16);
+    check_single_lset_immediate(Generate, v!(I32), Symbol::GENERATE_I32);
+    check_single_lset_immediate(Generate, v!(I64), Symbol::GENERATE_I64);
+    check_single_lset_immediate(Generate, v!(I128), Symbol::GENERATE_I128);
+    check_single_lset_immediate(Generate, v!(DEC), Symbol::GENERATE_DEC);
+    check_single_lset_immediate(Generate, v!(F32), Symbol::GENERATE_F32);
+    check_single_lset_immediate(Generate, v!(F64), Symbol::GENERATE_F64);
+    check_single_lset_immediate(Generate, v!(STR), Symbol::GENERATE_STR);
+}
+
+#[test]
+fn optional_record_field_derive_error() {
+    check_underivable(Generate, v!({ ?a: v!(U8), }), DeriveError::Underivable);
+}
+
+#[test]
+fn empty_tag_union_underivable() {
+    check_underivable(Generate, v!(EMPTY_TAG_UNION), DeriveError::Underivable);
+    check_underivable(Generate, v!([]), DeriveError::Underivable);
+}
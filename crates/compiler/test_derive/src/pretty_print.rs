@@ -3,7 +3,7 @@
 use roc_can::def::Def;
 use roc_can::expr::Expr::{self, *};
 use roc_can::expr::{ClosureData, OpaqueWrapFunctionData, WhenBranch};
-use roc_can::pattern::{Pattern, RecordDestruct};
+use roc_can::pattern::{Pattern, RecordDestruct, TupleDestruct};
 
 use roc_module::symbol::Interns;
 use ven_pretty::{Arena, DocAllocator, DocBuilder};
@@ -197,6 +197,27 @@ fn expr<'a>(c: &Ctx, p: EPrec, f: &'a Arena<'a>, e: &'a Expr) -> DocBuilder<'a,
             .append(f.text("}"))
             .group(),
         EmptyRecord => f.text("{}"),
+        Tuple { elems, .. } => f
+            .reflow("(")
+            .append(
+                f.intersperse(
+                    elems.iter().map(|(_, elem)| {
+                        let elem = expr(c, Free, f, &elem.value).nest(2).group();
+                        f.line().append(elem)
+                    }),
+                    f.reflow(","),
+                )
+                .nest(2)
+                .group(),
+            )
+            .append(f.line())
+            .append(f.text(")"))
+            .group(),
+        TupleAccess {
+            loc_expr, index, ..
+        } => expr(c, AppArg, f, &loc_expr.value)
+            .append(f.text(format!(".{}", index)))
+            .group(),
         Access {
             loc_expr, field, ..
         } => expr(c, AppArg, f, &loc_expr.value)
@@ -257,6 +278,7 @@ fn expr<'a>(c: &Ctx, p: EPrec, f: &'a Arena<'a>, e: &'a Expr) -> DocBuilder<'a,
         ZeroArgumentTag { .. } => todo!(),
         OpaqueRef { .. } => todo!(),
         Expect { .. } => todo!(),
+        Dbg { .. } => todo!(),
         TypedHole(_) => todo!(),
         RuntimeError(_) => todo!(),
     }
@@ -361,6 +383,19 @@ fn pattern<'a>(
             )
             .append(f.text("}"))
             .group(),
+        TupleDestructure { destructs, .. } => f
+            .text("(")
+            .append(
+                f.intersperse(
+                    destructs
+                        .iter()
+                        .map(|l| &l.value)
+                        .map(|TupleDestruct { typ: (_, p), .. }| pattern(c, Free, f, &p.value)),
+                    f.text(", "),
+                ),
+            )
+            .append(f.text(")"))
+            .group(),
         NumLiteral(_, n, _, _) | IntLiteral(_, _, n, _, _) | FloatLiteral(_, _, n, _, _) => {
             f.text(&**n)
         }
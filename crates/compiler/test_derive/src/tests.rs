@@ -2,6 +2,9 @@
 
 mod decoding;
 mod encoding;
+mod eq;
+mod generating;
+mod hash;
 
 mod pretty_print;
 mod util;
@@ -50,6 +50,21 @@ fn module_source_and_path(builtin: DeriveBuiltin) -> (ModuleId, &'static str, Pa
             module_source(ModuleId::DECODE),
             builtins_path.join("Decode.roc"),
         ),
+        DeriveBuiltin::Hash => (
+            ModuleId::HASH,
+            module_source(ModuleId::HASH),
+            builtins_path.join("Hash.roc"),
+        ),
+        DeriveBuiltin::IsEq => (
+            ModuleId::BOOL,
+            module_source(ModuleId::BOOL),
+            builtins_path.join("Bool.roc"),
+        ),
+        DeriveBuiltin::Generate => (
+            ModuleId::GENERATE,
+            module_source(ModuleId::GENERATE),
+            builtins_path.join("Generate.roc"),
+        ),
     }
 }
 
@@ -71,6 +86,14 @@ macro_rules! v {
              roc_derive::synth_var(subs, Content::Structure(FlatType::Record(fields, Variable::EMPTY_RECORD)))
          }
      }};
+     (( $($make_v:expr,)* )) => {{
+         use roc_types::subs::{Subs, SubsSlice, Content, FlatType, Variable};
+         |subs: &mut Subs| {
+             let elems = vec![ $( $make_v(subs), )* ];
+             let elems = SubsSlice::insert_into_subs(subs, elems);
+             roc_derive::synth_var(subs, Content::Structure(FlatType::Tuple(elems, Variable::EMPTY_TUPLE)))
+         }
+     }};
      ([ $($tag:ident $($payload:expr)*),* ]$( $ext:tt )?) => {{
          #[allow(unused)]
          use roc_types::subs::{Subs, UnionTags, Content, FlatType, Variable};
@@ -221,6 +244,18 @@ where
     assert_eq!(key, Ok(Derived::Immediate(immediate)));
 }
 
+pub(crate) fn check_single_lset_immediate<S>(builtin: DeriveBuiltin, synth: S, immediate: Symbol)
+where
+    S: FnOnce(&mut Subs) -> Variable,
+{
+    let mut subs = Subs::new();
+    let var = synth(&mut subs);
+
+    let key = Derived::builtin(builtin, &subs, var);
+
+    assert_eq!(key, Ok(Derived::SingleLambdaSetImmediate(immediate)));
+}
+
 #[allow(clippy::too_many_arguments)]
 fn assemble_derived_golden(
     subs: &mut Subs,
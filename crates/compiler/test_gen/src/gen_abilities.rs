@@ -519,6 +519,29 @@ fn encode_derived_record_two_fields_strings() {
     )
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn encode_derived_tuple_two_fields() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test"
+                imports [Encode.{ toEncoder }, Json]
+                provides [main] to "./platform"
+
+            main =
+                tup = ("foo", 10u8)
+                result = Str.fromUtf8 (Encode.toBytes tup Json.toUtf8)
+                when result is
+                    Ok s -> s
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from(r#"["foo",10]"#),
+        RocStr
+    )
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn encode_derived_nested_record_string() {
@@ -971,6 +994,25 @@ fn decode_record_two_fields() {
     )
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_tuple_two_elements() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Encode, Decode, Json] provides [main] to "./platform"
+
+            main =
+                when Str.toUtf8 "[\"ab\",10]" |> Decode.fromBytes Json.fromUtf8 is
+                    Ok ("ab", 10u8) -> "abcd"
+                    _ -> "something went wrong"
+            "#
+        ),
+        RocStr::from("abcd"),
+        RocStr
+    )
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn decode_record_two_fields_string_and_int() {
@@ -1051,7 +1093,6 @@ fn decode_record_two_fields_string_and_string_infer_local_var_destructured() {
 
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
-#[ignore = "json parsing impl must be fixed first"]
 fn decode_empty_record() {
     assert_evals_to!(
         indoc!(
@@ -1090,3 +1131,530 @@ fn decode_record_of_record() {
         RocStr
     )
 }
+
+mod hash {
+    #[cfg(feature = "gen-llvm")]
+    use crate::helpers::llvm::assert_evals_to;
+
+    #[cfg(feature = "gen-wasm")]
+    use crate::helpers::wasm::assert_evals_to;
+
+    #[cfg(all(test, any(feature = "gen-llvm", feature = "gen-wasm")))]
+    use indoc::indoc;
+
+    #[cfg(all(test, any(feature = "gen-llvm", feature = "gen-wasm")))]
+    use roc_std::RocList;
+
+    // A hasher that records every byte fed to it, so that tests can inspect exactly what a
+    // derived implementation hashes.
+    #[cfg(all(test, any(feature = "gen-llvm", feature = "gen-wasm")))]
+    const TEST_HASHER: &str = indoc!(
+        r#"
+        THasher := List U8 has [Hasher {addBytes: tAddBytes, addU8: tAddU8, addU16: tAddU16, addU32: tAddU32, addU64: tAddU64, addU128: tAddU128, addI8: tAddI8, addI16: tAddI16, addI32: tAddI32, addI64: tAddI64, addI128: tAddI128, complete: tComplete}]
+
+        tAddBytes = \@THasher total, bytes -> @THasher (List.concat total bytes)
+        tAddU8 = \@THasher total, n -> @THasher (List.append total n)
+        tAddU16 = \@THasher total, n -> @THasher (List.append total (Num.toU8 n))
+        tAddU32 = \@THasher total, n -> @THasher (List.append total (Num.toU8 n))
+        tAddU64 = \@THasher total, n -> @THasher (List.append total (Num.toU8 n))
+        tAddU128 = \@THasher total, n -> @THasher (List.append total (Num.toU8 n))
+        tAddI8 = \@THasher total, n -> @THasher (List.append total (Num.toU8 n))
+        tAddI16 = \@THasher total, n -> @THasher (List.append total (Num.toU8 n))
+        tAddI32 = \@THasher total, n -> @THasher (List.append total (Num.toU8 n))
+        tAddI64 = \@THasher total, n -> @THasher (List.append total (Num.toU8 n))
+        tAddI128 = \@THasher total, n -> @THasher (List.append total (Num.toU8 n))
+        tComplete = \@THasher _ -> Num.maxU64
+
+        tRead = \@THasher bytes -> bytes
+        "#
+    );
+
+    #[cfg(all(test, any(feature = "gen-llvm", feature = "gen-wasm")))]
+    fn build_test(input: &str) -> String {
+        format!(
+            indoc!(
+                r#"
+                app "test" imports [Hash.{{ Hash, Hasher }}] provides [main] to "./platform"
+
+                {}
+
+                main =
+                    @THasher []
+                    |> Hash.hash ({})
+                    |> tRead
+                "#
+            ),
+            TEST_HASHER, input,
+        )
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn immediate_u8() {
+        assert_evals_to!(&build_test("7u8"), RocList::from_slice(&[7]), RocList<u8>)
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn immediate_i64() {
+        assert_evals_to!(
+            &build_test("-2i64"),
+            RocList::from_slice(&[254]),
+            RocList<u8>
+        )
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn string() {
+        assert_evals_to!(
+            &build_test(r#""ab""#),
+            RocList::from_slice(&[97, 98]),
+            RocList<u8>
+        )
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn list_u8() {
+        assert_evals_to!(
+            &build_test("[1u8, 2, 3]"),
+            RocList::from_slice(&[3, 1, 2, 3]),
+            RocList<u8>
+        )
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn list_string() {
+        assert_evals_to!(
+            &build_test(r#"["a", "bc"]"#),
+            RocList::from_slice(&[2, 97, 98, 99]),
+            RocList<u8>
+        )
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn empty_record() {
+        assert_evals_to!(&build_test("{}"), RocList::from_slice(&[]), RocList<u8>)
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn record_two_fields() {
+        assert_evals_to!(
+            &build_test("{b: 2u8, a: 1u8}"),
+            RocList::from_slice(&[1, 2]),
+            RocList<u8>
+        )
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn record_nested() {
+        assert_evals_to!(
+            &build_test(r#"{a: {b: "x"}, c: [3u8]}"#),
+            RocList::from_slice(&[120, 1, 3]),
+            RocList<u8>
+        )
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn tag_one_label() {
+        assert_evals_to!(
+            &build_test("(A 5u8 6u8 : [A U8 U8])"),
+            RocList::from_slice(&[5, 6]),
+            RocList<u8>
+        )
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn tag_two_labels() {
+        assert_evals_to!(
+            &build_test("(B 4u8 : [A U8, B U8])"),
+            RocList::from_slice(&[1, 4]),
+            RocList<u8>
+        )
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn derived_opaque() {
+        assert_evals_to!(
+            &format!(
+                indoc!(
+                    r#"
+                    app "test" imports [Hash.{{ Hash, Hasher }}] provides [main] to "./platform"
+
+                    {}
+
+                    Id := U8 has [Hash]
+
+                    main =
+                        @THasher []
+                        |> Hash.hash (@Id 9)
+                        |> tRead
+                    "#
+                ),
+                TEST_HASHER,
+            ),
+            RocList::from_slice(&[9]),
+            RocList<u8>
+        )
+    }
+}
+
+mod generate {
+    #[cfg(feature = "gen-llvm")]
+    use crate::helpers::llvm::assert_evals_to;
+
+    #[cfg(all(test, feature = "gen-llvm"))]
+    use indoc::indoc;
+
+    #[test]
+    #[cfg(feature = "gen-llvm")]
+    fn same_seed_same_value() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" imports [Generate] provides [main] to "./platform"
+
+                text : Generate.Rng -> Str
+                text = \r -> Generate.generate r 50
+
+                main =
+                    text (Generate.rng 5) == text (Generate.rng 5)
+                        && text (Generate.rng 5) != text (Generate.rng 6)
+                "#
+            ),
+            true,
+            bool
+        )
+    }
+
+    #[test]
+    #[cfg(feature = "gen-llvm")]
+    fn list_within_size() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" imports [Generate] provides [main] to "./platform"
+
+                main =
+                    bytes : List U8
+                    bytes = Generate.generate (Generate.rng 1) 5
+
+                    List.len bytes <= 5 && List.all bytes \byte -> byte <= 5
+                "#
+            ),
+            true,
+            bool
+        )
+    }
+
+    #[test]
+    #[cfg(feature = "gen-llvm")]
+    fn derived_record_within_size() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" imports [Generate] provides [main] to "./platform"
+
+                main =
+                    point : { x : I64, y : I64 }
+                    point = Generate.generate (Generate.rng 3) 10
+
+                    Num.abs point.x <= 10 && Num.abs point.y <= 10
+                "#
+            ),
+            true,
+            bool
+        )
+    }
+
+    #[test]
+    #[cfg(feature = "gen-llvm")]
+    fn search_passing_property() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" imports [Generate] provides [main] to "./platform"
+
+                property : U64 -> Bool
+                property = \n -> n <= 100
+
+                main =
+                    run = Generate.search 42 100 property
+
+                    run.passed
+                "#
+            ),
+            true,
+            bool
+        )
+    }
+
+    #[test]
+    #[cfg(feature = "gen-llvm")]
+    fn search_stops_after_iterations() {
+        // Sizes grow by one with each iteration, so 5 iterations never generate 40.
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" imports [Generate] provides [main] to "./platform"
+
+                property : U64 -> Bool
+                property = \n -> n < 40
+
+                main =
+                    few = Generate.search 42 5 property
+                    many = Generate.search 42 100 property
+
+                    few.passed && few.size == 4 && !many.passed
+                "#
+            ),
+            true,
+            bool
+        )
+    }
+
+    #[test]
+    #[cfg(feature = "gen-llvm")]
+    fn search_single_iteration() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" imports [Generate] provides [main] to "./platform"
+
+                property : U64 -> Bool
+                property = \n -> n == 0
+
+                main =
+                    run = Generate.search 42 1 property
+
+                    run.passed && run.size == 0
+                "#
+            ),
+            true,
+            bool
+        )
+    }
+
+    #[test]
+    #[cfg(feature = "gen-llvm")]
+    fn shrinks_failing_input() {
+        // The first failure can be at any size up to 100. Shrinking goes back to the smallest
+        // size where it can still find a failing input, which can't be below 10.
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" imports [Generate] provides [main] to "./platform"
+
+                property : U64 -> Bool
+                property = \n -> n < 10
+
+                main =
+                    run = Generate.search 7 100 property
+
+                    !run.passed && run.input >= 10 && run.size >= 10 && run.size < 20
+                "#
+            ),
+            true,
+            bool
+        )
+    }
+
+    #[test]
+    #[cfg(feature = "gen-llvm")]
+    fn shrunk_input_reproduces_from_seed_and_size() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" imports [Generate] provides [main] to "./platform"
+
+                property : List U8 -> Bool
+                property = \bytes -> List.len bytes < 3
+
+                main =
+                    run = Generate.search 11 100 property
+                    regenerated : List U8
+                    regenerated = Generate.generate (Generate.rng run.seed) run.size
+
+                    !run.passed && regenerated == run.input && List.len run.input >= 3
+                "#
+            ),
+            true,
+            bool
+        )
+    }
+}
+
+mod eq {
+    #[cfg(feature = "gen-llvm")]
+    use crate::helpers::llvm::assert_evals_to;
+
+    #[cfg(feature = "gen-wasm")]
+    use crate::helpers::wasm::assert_evals_to;
+
+    #[cfg(all(test, any(feature = "gen-llvm", feature = "gen-wasm")))]
+    use indoc::indoc;
+
+    #[cfg(all(test, any(feature = "gen-llvm", feature = "gen-wasm")))]
+    use roc_std::RocStr;
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn custom_eq_impl() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                LyingEq := U8 has [Eq {isEq}]
+
+                isEq = \@LyingEq m, @LyingEq n -> m != n
+
+                main =
+                    a = @LyingEq 10
+                    b = @LyingEq 5
+                    c = @LyingEq 5
+                    if Bool.isEq a b && !(Bool.isEq b c) then
+                        "okay"
+                    else
+                        "fail"
+                "#
+            ),
+            RocStr::from("okay"),
+            RocStr
+        )
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn custom_eq_impl_through_binop() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                LyingEq := U8 has [Eq {isEq}]
+
+                isEq = \@LyingEq m, @LyingEq n -> m != n
+
+                main =
+                    a = @LyingEq 10
+                    b = @LyingEq 5
+                    c = @LyingEq 5
+                    if a == b && b != c then
+                        "okay"
+                    else
+                        "fail"
+                "#
+            ),
+            RocStr::from("okay"),
+            RocStr
+        )
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn custom_eq_impl_nested_in_record() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                AlwaysEq := U8 has [Eq {isEq}]
+
+                isEq = \_, _ -> True
+
+                main =
+                    { a: @AlwaysEq 1, b: "x" } == { a: @AlwaysEq 2, b: "x" }
+                "#
+            ),
+            true,
+            bool
+        )
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn custom_eq_impl_nested_in_tag_and_list() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                AlwaysEq := U8 has [Eq {isEq}]
+
+                isEq = \_, _ -> True
+
+                main =
+                    [A (@AlwaysEq 1), B] == [A (@AlwaysEq 2), B]
+                "#
+            ),
+            true,
+            bool
+        )
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn derived_eq_opaque() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                Id := { name : Str, n : U8 } has [Eq]
+
+                main =
+                    (@Id { name: "a", n: 1 } == @Id { name: "a", n: 1 })
+                    && (@Id { name: "a", n: 1 } != @Id { name: "a", n: 2 })
+                "#
+            ),
+            true,
+            bool
+        )
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn dict_eq_ignores_insertion_order() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main =
+                    d1 = Dict.empty |> Dict.insert 1u8 "one" |> Dict.insert 2u8 "two"
+                    d2 = Dict.empty |> Dict.insert 2u8 "two" |> Dict.insert 1u8 "one"
+
+                    d1 == d2
+                "#
+            ),
+            true,
+            bool
+        )
+    }
+
+    #[test]
+    #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+    fn set_eq_ignores_insertion_order() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main =
+                    Set.fromList [1u8, 2, 3] == Set.fromList [3u8, 1, 2]
+                "#
+            ),
+            true,
+            bool
+        )
+    }
+}
@@ -549,3 +549,131 @@ fn walk_sum_keys() {
         i64
     );
 }
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn many_keys() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            myDict : Dict.Dict I64 I64
+            myDict =
+                List.range 0 10_000
+                    |> List.walk Dict.empty (\accum, value -> Dict.insert accum value (value * 2))
+
+            List.range 0 10_000
+                |> List.walk 0 \total, key ->
+                    when Dict.get myDict key is
+                        Ok value -> total + value
+                        Err KeyNotFound -> total
+            "#
+        ),
+        99_990_000,
+        i64
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn remove_keeps_other_keys_reachable() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            myDict : Dict.Dict I64 I64
+            myDict =
+                List.range 0 100
+                    |> List.walk Dict.empty (\accum, value -> Dict.insert accum value value)
+
+            removed =
+                List.range 0 100
+                    |> List.keepIf Num.isEven
+                    |> List.walk myDict (\accum, key -> Dict.remove accum key)
+
+            List.range 0 100
+                |> List.keepIf (\key -> Dict.contains removed key)
+            "#
+        ),
+        RocList::from_slice(&[
+            1, 3, 5, 7, 9, 11, 13, 15, 17, 19, 21, 23, 25, 27, 29, 31, 33, 35, 37, 39, 41, 43, 45,
+            47, 49, 51, 53, 55, 57, 59, 61, 63, 65, 67, 69, 71, 73, 75, 77, 79, 81, 83, 85, 87, 89,
+            91, 93, 95, 97, 99
+        ]),
+        RocList<i64>
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn remove_then_insert() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            Dict.empty
+                |> Dict.insert "a" 1
+                |> Dict.insert "b" 2
+                |> Dict.remove "a"
+                |> Dict.insert "a" 3
+                |> Dict.insert "b" 4
+                |> Dict.values
+            "#
+        ),
+        RocList::from_slice(&[4, 3]),
+        RocList<i64>
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn structural_keys() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            myDict =
+                Dict.empty
+                    |> Dict.insert { name: "x", tags: [A, B 1] } 1
+                    |> Dict.insert { name: "x", tags: [B 1, A] } 2
+                    |> Dict.insert { name: "y", tags: [A, B 1] } 3
+                    |> Dict.insert { name: "x", tags: [A, B 1] } 4
+
+            when Dict.get myDict { name: "x", tags: [A, B 1] } is
+                Ok value -> value * 10 + Num.toI64 (Dict.len myDict)
+                Err KeyNotFound -> -1
+            "#
+        ),
+        43,
+        i64
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn custom_eq_keys_are_hashed_and_compared_structurally() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            Key := { id : U64, label : Str } has [Eq { isEq }]
+
+            # Equal keys must hash the same, which this custom equality doesn't guarantee
+            isEq = \@Key a, @Key b -> a.id == b.id
+
+            main =
+                labels = List.map (List.range 0 100) Num.toStr
+
+                dict =
+                    List.walk labels Dict.empty \state, label ->
+                        Dict.insert state (@Key { id: 1, label }) label
+
+                found =
+                    labels
+                    |> List.keepIf \label -> Dict.get dict (@Key { id: 1, label }) == Ok label
+                    |> List.len
+
+                Dict.len dict == List.len labels && found == List.len labels
+            "#
+        ),
+        true,
+        bool
+    );
+}
@@ -0,0 +1,608 @@
+//! Conformance tests for the builtin `Json` format, following RFC 8259.
+
+#[cfg(feature = "gen-llvm")]
+use crate::helpers::llvm::assert_evals_to;
+
+#[cfg(feature = "gen-wasm")]
+use crate::helpers::wasm::assert_evals_to;
+
+#[cfg(all(test, any(feature = "gen-llvm", feature = "gen-wasm")))]
+use indoc::indoc;
+
+#[cfg(all(test, any(feature = "gen-llvm", feature = "gen-wasm")))]
+use roc_std::RocStr;
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_string_escapes() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            main =
+                when Str.toUtf8 "\"a\\\"b\\\\c\\/d\\n\\t\\r\\b\\f\"" |> Decode.fromBytes Json.fromUtf8 is
+                    Ok s -> s
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("a\"b\\c/d\n\t\r\u{8}\u{c}"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_string_unicode_escapes() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            main =
+                when Str.toUtf8 "\"\\u0041\\u00e9\\u4E2D\"" |> Decode.fromBytes Json.fromUtf8 is
+                    Ok s -> s
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("Aé中"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_string_surrogate_pair() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            main =
+                when Str.toUtf8 "\"\\uD83D\\uDE00!\"" |> Decode.fromBytes Json.fromUtf8 is
+                    Ok s -> s
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("😀!"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_string_invalid() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            isErr = \input ->
+                decoded : Result Str _
+                decoded = Str.toUtf8 input |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok _ -> "ok"
+                    Err _ -> "err"
+
+            main =
+                [
+                    # a lone surrogate
+                    isErr "\"\\uDE00\"",
+                    # a high surrogate without its low surrogate
+                    isErr "\"\\uD83Dx\"",
+                    # control characters must be escaped
+                    isErr "\"a\nb\"",
+                    # unknown escape
+                    isErr "\"\\x\"",
+                    # unterminated
+                    isErr "\"abc",
+                ]
+                |> Str.joinWith ","
+            "#
+        ),
+        RocStr::from("err,err,err,err,err"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_numbers() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            main =
+                when Str.toUtf8 "[-12, 0, 7, -0]" |> Decode.fromBytes Json.fromUtf8 is
+                    Ok list -> Num.toStr (List.sum list + 0i64)
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("-5"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_exponent_floats() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            main =
+                when Str.toUtf8 "[1.5e3, 25E-2, -2e+1, 0.5]" |> Decode.fromBytes Json.fromUtf8 is
+                    Ok list -> List.sum list
+                    _ -> 0f64
+            "#
+        ),
+        1480.75,
+        f64
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_numbers_invalid() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            isErr = \input ->
+                decoded : Result F64 _
+                decoded = Str.toUtf8 input |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok _ -> "ok"
+                    Err _ -> "err"
+
+            main =
+                ["01", "1.", "+1", ".5", "1e", "-", "NaN"]
+                |> List.map isErr
+                |> Str.joinWith ","
+            "#
+        ),
+        RocStr::from("err,err,err,err,err,err,err"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_whitespace() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            main =
+                input = " {\n\t\"first\" : [ 1 , 2 ] ,\r\n  \"second\":\"x\", \"third\" : true } \n"
+
+                when Str.toUtf8 input |> Decode.fromBytes Json.fromUtf8 is
+                    Ok { first, second: "x", third: True } if first == [1u8, 2] -> "ok"
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("ok"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_empty_containers() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            main =
+                when Str.toUtf8 "{ \"list\": [ ], \"record\": { } }" |> Decode.fromBytes Json.fromUtf8 is
+                    Ok { list, record: {} } -> Num.toStr (List.len list + List.len (List.map list \n -> n + 1u8))
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("0"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(all(
+    any(feature = "gen-llvm", feature = "gen-wasm"),
+    not(feature = "gen-llvm-wasm") // hits a wasm3 stack overflow
+))]
+fn decode_skips_unknown_fields() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            main =
+                input = "{\"a\":1,\"extra\":{\"x\":[1,\"]}\",{\"y\":null}],\"z\":-1.5e2},\"b\":\"s\",\"more\":false,\"last\":\"\\\"}\"}"
+
+                when Str.toUtf8 input |> Decode.fromBytes Json.fromUtf8 is
+                    Ok { a: 1u8, b } -> b
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("s"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_missing_field() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            main =
+                when Str.toUtf8 "{\"first\":\"a\"}" |> Decode.fromBytes Json.fromUtf8 is
+                    Ok { first: "a", second: "b" } -> "ok"
+                    Ok _ -> "wrong"
+                    Err TooShort -> "missing"
+                    Err _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("missing"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_optional_field() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            User : { name : Str, nickname : [Some Str, None] }
+
+            describe = \input ->
+                decoded : Result User _
+                decoded = Str.toUtf8 input |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok { name, nickname: Some nickname } -> "\(name) (\(nickname))"
+                    Ok { name, nickname: None } -> name
+                    Err _ -> "<bad>"
+
+            main =
+                [
+                    describe "{\"name\":\"a\",\"nickname\":{\"Some\":[\"b\"]}}",
+                    describe "{\"name\":\"c\",\"nickname\":null}",
+                    describe "{\"name\":\"d\"}",
+                ]
+                |> Str.joinWith ","
+            "#
+        ),
+        RocStr::from("a (b),c,d"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_tag_union() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            describe = \input ->
+                decoded : Result [A, B U8 Str] _
+                decoded = Str.toUtf8 input |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok A -> "A"
+                    Ok (B n s) -> "B \(Num.toStr n) \(s)"
+                    Err _ -> "err"
+
+            main =
+                [
+                    describe "\"A\"",
+                    describe "{\"A\":[]}",
+                    describe "{ \"B\" : [ 7, \"x\" ] }",
+                    # unknown tag
+                    describe "\"C\"",
+                    # wrong payload size
+                    describe "{\"B\":[7]}",
+                    describe "\"B\"",
+                ]
+                |> Str.joinWith ","
+            "#
+        ),
+        RocStr::from("A,A,B 7 x,err,err,err"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn encode_then_decode_tag_union() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Encode, Decode, Json] provides [main] to "./platform"
+
+            Shape : [Circle F64, Rect F64 F64, Empty]
+
+            roundTrip : Shape -> Result Shape _
+            roundTrip = \shape ->
+                Encode.toBytes shape Json.toUtf8 |> Decode.fromBytes Json.fromUtf8
+
+            main =
+                when (roundTrip (Rect 2 3), roundTrip Empty) is
+                    (Ok (Rect w h), Ok Empty) -> w * h
+                    _ -> 0
+            "#
+        ),
+        6.0,
+        f64
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn encode_tag_without_payload() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Encode, Json] provides [main] to "./platform"
+
+            main =
+                x : [A, B Str]
+                x = A
+
+                result = Str.fromUtf8 (Encode.toBytes x Json.toUtf8)
+                when result is
+                    Ok s -> s
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from(r#""A""#),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn encode_string_escapes() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Encode, Json] provides [main] to "./platform"
+
+            main =
+                result = Str.fromUtf8 (Encode.toBytes ["a\"b", "c\\d\n\u(1)"] Json.toUtf8)
+                when result is
+                    Ok s -> s
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from(r#"["a\"b","c\\d\n\u0001"]"#),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn encode_then_decode_string_escapes() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Encode, Decode, Json] provides [main] to "./platform"
+
+            main =
+                original = "quote \" backslash \\ tab \t unicode é 😀"
+
+                when Encode.toBytes original Json.toUtf8 |> Decode.fromBytes Json.fromUtf8 is
+                    Ok s if s == original -> "same"
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("same"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn encode_field_name_mapping() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Encode, Json] provides [main] to "./platform"
+
+            encodeWith = \mapping ->
+                result = Str.fromUtf8 (Encode.toBytes { firstName: "a", id: 1u8 } (Json.jsonWithOptions { fieldNameMapping: mapping }))
+                when result is
+                    Ok s -> s
+                    _ -> "<bad>"
+
+            main =
+                [encodeWith Default, encodeWith SnakeCase, encodeWith PascalCase, encodeWith KebabCase]
+                |> Str.joinWith " "
+            "#
+        ),
+        RocStr::from(
+            r#"{"firstName":"a","id":1} {"first_name":"a","id":1} {"FirstName":"a","Id":1} {"first-name":"a","id":1}"#
+        ),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_field_name_mapping() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            User : { firstName : Str, lastName : Str }
+
+            decodeWith = \input, mapping ->
+                decoded : Result User _
+                decoded = Str.toUtf8 input |> Decode.fromBytes (Json.jsonWithOptions { fieldNameMapping: mapping })
+
+                when decoded is
+                    Ok { firstName, lastName } -> "\(firstName) \(lastName)"
+                    Err _ -> "err"
+
+            main =
+                [
+                    decodeWith "{\"first_name\":\"a\",\"last_name\":\"b\"}" SnakeCase,
+                    decodeWith "{\"FirstName\":\"c\",\"LastName\":\"d\"}" PascalCase,
+                    decodeWith "{\"first-name\":\"e\",\"last-name\":\"f\"}" KebabCase,
+                    # keys are not renamed by default
+                    decodeWith "{\"first_name\":\"g\",\"last_name\":\"h\"}" Default,
+                ]
+                |> Str.joinWith ","
+            "#
+        ),
+        RocStr::from("a b,c d,e f,err"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn encode_pretty() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Encode, Json] provides [main] to "./platform"
+
+            main =
+                value = { a: [1u8, 2], b: List.dropFirst [3u8], c: {}, d: B "x" }
+                result = Str.fromUtf8 (Encode.toBytes value (Json.jsonWithOptions { indent: 2 }))
+                when result is
+                    Ok s -> s
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from(indoc!(
+            r#"
+            {
+              "a": [
+                1,
+                2
+              ],
+              "b": [],
+              "c": {},
+              "d": {
+                "B": [
+                  "x"
+                ]
+              }
+            }"#
+        )),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn encode_pretty_then_decode() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Encode, Decode, Json] provides [main] to "./platform"
+
+            Config : { name : Str, ports : List U16, nested : { enabled : Bool } }
+
+            main =
+                original : Config
+                original = { name: "server", ports: [80, 443], nested: { enabled: True } }
+
+                format = Json.jsonWithOptions { fieldNameMapping: SnakeCase, indent: 4 }
+
+                decoded : Result Config _
+                decoded = Encode.toBytes original format |> Decode.fromBytes format
+
+                when decoded is
+                    Ok { name: "server", ports, nested: { enabled: True } } if ports == [80, 443] -> "same"
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("same"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn encode_empty_as_null() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Encode, Json] provides [main] to "./platform"
+
+            User : { name : Str, nickname : [Some Str, None], age : Result U8 [Unknown] }
+
+            encode = \user, emptyEncodeAsNull ->
+                result = Str.fromUtf8 (Encode.toBytes user (Json.jsonWithOptions { emptyEncodeAsNull }))
+                when result is
+                    Ok s -> s
+                    _ -> "<bad>"
+
+            main =
+                a : User
+                a = { name: "a", nickname: None, age: Ok 3 }
+
+                b : User
+                b = { name: "b", nickname: Some "bee", age: Err Unknown }
+
+                [encode a True, encode b True, encode a False]
+                |> Str.joinWith " "
+            "#
+        ),
+        RocStr::from(
+            r#"{"age":3,"name":"a","nickname":null} {"age":null,"name":"b","nickname":"bee"} {"age":{"Ok":[3]},"name":"a","nickname":"None"}"#
+        ),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_null_as_empty() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            User : { name : Str, nickname : [Some Str, None] }
+
+            describe = \input ->
+                decoded : Result User _
+                decoded = Str.toUtf8 input |> Decode.fromBytes (Json.jsonWithOptions { emptyEncodeAsNull: True })
+
+                when decoded is
+                    Ok { name, nickname: Some nickname } -> "\(name) (\(nickname))"
+                    Ok { name, nickname: None } -> name
+                    Err _ -> "<bad>"
+
+            main =
+                [
+                    describe "{\"name\":\"a\",\"nickname\":\"b\"}",
+                    describe "{\"name\":\"c\",\"nickname\":null}",
+                    describe "{\"nickname\" : \"e\" , \"name\":\"d\"}",
+                    describe "{\"name\":\"f\"}",
+                ]
+                |> Str.joinWith ","
+            "#
+        ),
+        RocStr::from("a (b),c,d (e),f"),
+        RocStr
+    )
+}
@@ -2030,6 +2030,45 @@ fn pattern_shadowing() {
     );
 }
 
+#[test]
+#[cfg(feature = "gen-wasm")]
+fn inline_expect_passes_in_wasm() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            x = 4
+
+            expect x == 4
+
+            x
+            "#
+        ),
+        4,
+        i64
+    );
+}
+
+#[test]
+#[cfg(feature = "gen-wasm")]
+#[should_panic(
+    expected = "Roc failed with message: \"An expect failed, and the wasm backend can't report expect failures."
+)]
+fn inline_expect_fails_loudly_in_wasm() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            x = 4
+
+            expect x == 5
+
+            x
+            "#
+        ),
+        4,
+        i64
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 #[ignore]
@@ -0,0 +1,148 @@
+#[cfg(feature = "gen-llvm")]
+use crate::helpers::llvm::assert_evals_to;
+
+#[cfg(feature = "gen-dev")]
+use crate::helpers::dev::assert_evals_to;
+
+#[cfg(feature = "gen-wasm")]
+use crate::helpers::wasm::assert_evals_to;
+
+use indoc::indoc;
+
+#[cfg(all(
+    test,
+    any(feature = "gen-llvm", feature = "gen-dev", feature = "gen-wasm")
+))]
+use roc_std::RocStr;
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev", feature = "gen-wasm"))]
+fn basic_tuple() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+                (15, 17, 19).0
+            "#
+        ),
+        15,
+        i64
+    );
+
+    assert_evals_to!(
+        indoc!(
+            r#"
+                (15, 17, 19).1
+            "#
+        ),
+        17,
+        i64
+    );
+
+    assert_evals_to!(
+        indoc!(
+            r#"
+                (15, 17, 19).2
+            "#
+        ),
+        19,
+        i64
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev", feature = "gen-wasm"))]
+fn i64_tuple2_literal() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+                (3, 5)
+            "#
+        ),
+        (3, 5),
+        (i64, i64)
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev", feature = "gen-wasm"))]
+fn mixed_alignment_tuple_access() {
+    // Elements are laid out by alignment, not by index, so this checks that
+    // accessors find the right element regardless.
+    assert_evals_to!(
+        indoc!(
+            r#"
+                tup : (U8, I64, Bool)
+                tup = (3, 42, Bool.true)
+
+                if tup.2 then tup.1 + Num.toI64 tup.0 else 0
+            "#
+        ),
+        45,
+        i64
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev", feature = "gen-wasm"))]
+fn tuple_destructure_in_closure() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+                swap = \(a, b) -> (b, a)
+
+                (swap (1, 2)).0
+            "#
+        ),
+        2,
+        i64
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev", feature = "gen-wasm"))]
+fn tuple_when_pattern() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+                f = \pair ->
+                    when pair is
+                        (Ok x, _) -> x
+                        (_, y) -> y
+
+                f (Err "", 7) + f (Ok 3, 9)
+            "#
+        ),
+        10,
+        i64
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev", feature = "gen-wasm"))]
+fn nested_tuple_with_str() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+                tup = (1, ("hello", 2))
+
+                tup.1.0
+            "#
+        ),
+        RocStr::from("hello"),
+        RocStr
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev", feature = "gen-wasm"))]
+fn tuple_structural_eq() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+                (1, "a", Bool.true) == (1, "a", Bool.true) && (1, 2) != (2, 1)
+            "#
+        ),
+        true,
+        bool
+    );
+}
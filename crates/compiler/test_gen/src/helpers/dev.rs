@@ -1,7 +1,7 @@
 use libloading::Library;
 use roc_build::link::{link, LinkType};
 use roc_builtins::bitcode;
-use roc_load::{EntryPoint, ExecutionMode, LoadConfig, Threading};
+use roc_load::{EntryPoint, ExecutionMode, LoadConfig, OptLevel, PropertyTesting, Threading};
 use roc_region::all::LineInfo;
 use tempfile::tempdir;
 
@@ -51,10 +51,12 @@ pub fn helper(
     }
 
     let load_config = LoadConfig {
-        target_info: roc_target::TargetInfo::default_x86_64(),
+        target_info: roc_target::TargetInfo::from(&target_lexicon::Triple::host()),
         render: roc_reporting::report::RenderTarget::ColorTerminal,
         threading: Threading::Single,
         exec_mode: ExecutionMode::Executable,
+        property_testing: PropertyTesting::default(),
+        opt_level: OptLevel::Development,
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -180,6 +182,7 @@ pub fn helper(
         exposed_to_host: exposed_to_host.values.keys().copied().collect(),
         lazy_literals,
         generate_allocators: true, // Needed for testing, since we don't have a platform
+        mode: roc_gen_dev::AssemblyBackendMode::Binary,
     };
 
     let target = target_lexicon::Triple::host();
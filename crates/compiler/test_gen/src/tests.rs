@@ -7,6 +7,7 @@
 pub mod gen_abilities;
 pub mod gen_compare;
 pub mod gen_dict;
+pub mod gen_json;
 pub mod gen_list;
 pub mod gen_num;
 pub mod gen_primitives;
@@ -16,6 +17,7 @@ pub mod gen_result;
 pub mod gen_set;
 pub mod gen_str;
 pub mod gen_tags;
+pub mod gen_tuples;
 mod helpers;
 pub mod wasm_str;
 
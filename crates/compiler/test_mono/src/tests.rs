@@ -14,12 +14,14 @@ extern crate indoc;
 const EXPANDED_STACK_SIZE: usize = 8 * 1024 * 1024;
 
 use roc_load::ExecutionMode;
-use roc_load::LoadConfig;
+use roc_load::{LoadConfig, PropertyTesting};
 use test_mono_macros::*;
 
+use bumpalo::Bump;
 use roc_collections::all::MutMap;
 use roc_load::Threading;
 use roc_module::symbol::Symbol;
+use roc_mono::ir::OptLevel;
 use roc_mono::ir::Proc;
 use roc_mono::ir::ProcLayout;
 
@@ -73,11 +75,26 @@ fn promote_expr_to_module(src: &str) -> String {
     buffer
 }
 
-fn compiles_to_ir(test_name: &str, src: &str) {
-    use bumpalo::Bump;
-    use std::path::PathBuf;
+fn compiles_to_ir(test_name: &str, src: &str, opt_level: OptLevel) {
+    if !opt_level.optimizes_mono_ir() {
+        // The snapshot shows the IR as it was specialized, but the optimization pipeline should
+        // handle every test program too. It checks its own output in debug builds.
+        let arena = Bump::new();
+        load_procedures(&arena, src, OptLevel::Development);
+    }
 
     let arena = &Bump::new();
+    let (procedures, main_fn_symbol) = load_procedures(arena, src, opt_level);
+
+    verify_procedures(test_name, procedures, main_fn_symbol);
+}
+
+fn load_procedures<'a>(
+    arena: &'a Bump,
+    src: &str,
+    opt_level: OptLevel,
+) -> (MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>, Symbol) {
+    use std::path::PathBuf;
 
     let filename = PathBuf::from("Test.roc");
     let src_dir = PathBuf::from("fake/test/path");
@@ -98,6 +115,8 @@ fn compiles_to_ir(test_name: &str, src: &str) {
         threading: Threading::Single,
         render: roc_reporting::report::RenderTarget::Generic,
         exec_mode: ExecutionMode::Executable,
+        property_testing: PropertyTesting::default(),
+        opt_level,
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -110,7 +129,7 @@ fn compiles_to_ir(test_name: &str, src: &str) {
 
     let mut loaded = match loaded {
         Ok(x) => x,
-        Err(roc_load::LoadingProblem::FormattedReport(report)) => {
+        Err(roc_load::LoadingProblem::FormattedReport(report, _)) => {
             println!("{}", report);
             panic!();
         }
@@ -138,7 +157,7 @@ fn compiles_to_ir(test_name: &str, src: &str) {
 
     let main_fn_symbol = exposed_to_host.values.keys().copied().next().unwrap();
 
-    verify_procedures(test_name, procedures, main_fn_symbol);
+    (procedures, main_fn_symbol)
 }
 
 #[cfg(debug_assertions)]
@@ -434,6 +453,37 @@ fn if_multi_branch() {
     "#
 }
 
+#[mono_test(optimize)]
+fn optimize_ir_plus() {
+    r#"
+    1 + 2
+    "#
+}
+
+#[mono_test(optimize)]
+fn optimize_ir_when_just() {
+    r#"
+    x : [Nothing, Just I64]
+    x = Just 41
+
+    when x is
+        Just v -> v + 0x1
+        Nothing -> 0x1
+    "#
+}
+
+#[mono_test(optimize)]
+fn optimize_if_multi_branch() {
+    r#"
+    if True then
+        1
+    else if False then
+        2
+    else
+        3
+    "#
+}
+
 #[mono_test]
 fn when_on_result() {
     r#"
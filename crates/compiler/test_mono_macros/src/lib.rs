@@ -4,7 +4,22 @@ use proc_macro::TokenStream;
 use quote::quote;
 
 #[proc_macro_attribute]
-pub fn mono_test(_args: TokenStream, item: TokenStream) -> TokenStream {
+pub fn mono_test(args: TokenStream, item: TokenStream) -> TokenStream {
+    let attr_args = syn::parse_macro_input!(args as syn::AttributeArgs);
+
+    // `#[mono_test(optimize)]` snapshots the IR after the optimization pipeline that the dev
+    // backends use
+    let optimize = attr_args.iter().any(|arg| match arg {
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) => path.is_ident("optimize"),
+        _ => false,
+    });
+
+    let opt_level = if optimize {
+        quote!(roc_mono::ir::OptLevel::Development)
+    } else {
+        quote!(roc_mono::ir::OptLevel::Normal)
+    };
+
     let task_fn = syn::parse_macro_input!(item as syn::ItemFn);
 
     let args = task_fn.sig.inputs.clone();
@@ -20,7 +35,7 @@ pub fn mono_test(_args: TokenStream, item: TokenStream) -> TokenStream {
         #[test]
         #(#attributes)*
         #visibility fn #name(#args) {
-            compiles_to_ir(#name_str, #body);
+            compiles_to_ir(#name_str, #body, #opt_level);
 
         }
     };
@@ -11,6 +11,7 @@ use std::fmt::Write;
 pub static WILDCARD: &str = "*";
 static EMPTY_RECORD: &str = "{}";
 static EMPTY_TAG_UNION: &str = "[]";
+static EMPTY_TUPLE: &str = "()";
 
 /// Requirements for parentheses.
 ///
@@ -255,6 +256,28 @@ fn find_names_needed(
                 find_under_alias,
             );
         }
+        Structure(Tuple(elems, ext_var)) => {
+            for index in elems.into_iter() {
+                let var = subs[index];
+                find_names_needed(
+                    var,
+                    subs,
+                    roots,
+                    root_appearances,
+                    names_taken,
+                    find_under_alias,
+                );
+            }
+
+            find_names_needed(
+                *ext_var,
+                subs,
+                roots,
+                root_appearances,
+                names_taken,
+                find_under_alias,
+            );
+        }
         Structure(TagUnion(tags, ext_var)) => {
             for slice_index in tags.variables() {
                 let slice = subs[slice_index];
@@ -402,7 +425,11 @@ fn find_names_needed(
                 find_under_alias,
             );
         }
-        Error | Structure(Erroneous(_)) | Structure(EmptyRecord) | Structure(EmptyTagUnion) => {
+        Error
+        | Structure(Erroneous(_))
+        | Structure(EmptyRecord)
+        | Structure(EmptyTagUnion)
+        | Structure(EmptyTuple) => {
             // Errors and empty records don't need names.
         }
     }
@@ -1060,6 +1087,7 @@ fn write_flat_type<'a>(
         ),
         EmptyRecord => buf.push_str(EMPTY_RECORD),
         EmptyTagUnion => buf.push_str(EMPTY_TAG_UNION),
+        EmptyTuple => buf.push_str(EMPTY_TUPLE),
         Func(args, closure, ret) => write_fn(
             env,
             ctx,
@@ -1134,6 +1162,46 @@ fn write_flat_type<'a>(
                 }
             }
         }
+        Tuple(elems, ext_var) => {
+            use crate::types::{gather_tuple_elems, TupleStructure};
+
+            // If the `ext` has concrete elements (e.g. ( I64 )( Bool )), merge them
+            let TupleStructure { elems, ext } = gather_tuple_elems(subs, *elems, *ext_var)
+                .expect("Something ended up weird in this tuple type");
+
+            if elems.is_empty() {
+                buf.push_str(EMPTY_TUPLE)
+            } else {
+                buf.push_str("( ");
+
+                for (index, var) in elems.into_iter().enumerate() {
+                    if index > 0 {
+                        buf.push_str(", ");
+                    }
+
+                    write_content(
+                        env,
+                        ctx,
+                        subs.get_content_without_compacting(var),
+                        subs,
+                        buf,
+                        Parens::Unnecessary,
+                    );
+                }
+
+                buf.push_str(" )");
+            }
+
+            match subs.get_content_without_compacting(ext) {
+                Content::Structure(EmptyTuple) => {
+                    // This is a closed tuple. We're done!
+                }
+                content => {
+                    // This is an open tuple, so print the variable right after the ')'
+                    write_content(env, ctx, content, subs, buf, parens)
+                }
+            }
+        }
         TagUnion(tags, ext_var) => {
             buf.push('[');
 
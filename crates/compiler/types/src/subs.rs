@@ -190,7 +190,7 @@ impl Subs {
         Self::serialize_slice(&buf, writer, written)
     }
 
-    pub(crate) fn serialize_slice<T>(
+    pub fn serialize_slice<T>(
         slice: &[T],
         writer: &mut impl std::io::Write,
         written: usize,
@@ -291,7 +291,7 @@ impl Subs {
         (tag_names, offset)
     }
 
-    pub(crate) fn deserialize_slice<T>(
+    pub fn deserialize_slice<T>(
         bytes: &[u8],
         length: usize,
         mut offset: usize,
@@ -943,6 +943,19 @@ fn subs_fmt_flat_type(this: &FlatType, subs: &Subs, f: &mut fmt::Formatter) -> f
 
             write!(f, "}}<{:?}>", new_ext)
         }
+        FlatType::Tuple(elems, ext) => {
+            write!(f, "( ")?;
+
+            for var in subs.get_subs_slice(*elems) {
+                write!(
+                    f,
+                    "{:?}, ",
+                    SubsFmtContent(subs.get_content_without_compacting(*var), subs)
+                )?;
+            }
+
+            write!(f, ")<{:?}>", ext)
+        }
         FlatType::TagUnion(tags, ext) => {
             write!(f, "[")?;
 
@@ -984,6 +997,7 @@ fn subs_fmt_flat_type(this: &FlatType, subs: &Subs, f: &mut fmt::Formatter) -> f
         FlatType::Erroneous(e) => write!(f, "Erroneous({:?})", e),
         FlatType::EmptyRecord => write!(f, "EmptyRecord"),
         FlatType::EmptyTagUnion => write!(f, "EmptyTagUnion"),
+        FlatType::EmptyTuple => write!(f, "EmptyTuple"),
     }
 }
 
@@ -1226,6 +1240,7 @@ define_const_var! {
 
     :pub EMPTY_RECORD,
     :pub EMPTY_TAG_UNION,
+    :pub EMPTY_TUPLE,
 
     BOOL_ENUM,
     :pub BOOL,
@@ -1713,6 +1728,10 @@ impl Subs {
             Variable::EMPTY_TAG_UNION,
             Content::Structure(FlatType::EmptyTagUnion),
         );
+        subs.set_content(
+            Variable::EMPTY_TUPLE,
+            Content::Structure(FlatType::EmptyTuple),
+        );
 
         let bool_union_tags = UnionTags::insert_into_subs(
             &mut subs,
@@ -2403,12 +2422,16 @@ pub enum FlatType {
     Apply(Symbol, VariableSubsSlice),
     Func(VariableSubsSlice, Variable, Variable),
     Record(RecordFields, Variable),
+    /// The leading elements of a tuple, in order. The extension variable holds the elements that
+    /// come after them, so an open tuple `( a, b )*` may unify with a tuple of any greater length.
+    Tuple(VariableSubsSlice, Variable),
     TagUnion(UnionTags, Variable),
     FunctionOrTagUnion(SubsIndex<TagName>, Symbol, Variable),
     RecursiveTagUnion(Variable, UnionTags, Variable),
     Erroneous(SubsIndex<Problem>),
     EmptyRecord,
     EmptyTagUnion,
+    EmptyTuple,
 }
 
 impl FlatType {
@@ -3149,6 +3172,10 @@ fn occurs(
                         let it = once(ext_var).chain(subs.get_subs_slice(slice).iter());
                         short_circuit(subs, root_var, &new_seen, it)
                     }
+                    Tuple(elems, ext_var) => {
+                        let it = once(ext_var).chain(subs.get_subs_slice(*elems).iter());
+                        short_circuit(subs, root_var, &new_seen, it)
+                    }
                     TagUnion(tags, ext_var) => {
                         occurs_union(subs, root_var, &new_seen, tags)?;
 
@@ -3163,7 +3190,7 @@ fn occurs(
 
                         short_circuit_help(subs, root_var, &new_seen, *ext_var)
                     }
-                    EmptyRecord | EmptyTagUnion | Erroneous(_) => Ok(()),
+                    EmptyRecord | EmptyTagUnion | EmptyTuple | Erroneous(_) => Ok(()),
                 }
             }
             Alias(_, args, _, _) => {
@@ -3333,8 +3360,19 @@ fn explicit_substitute(
 
                         subs.set_content(in_var, Structure(Record(vars_by_field, new_ext_var)));
                     }
+                    Tuple(elems, ext_var) => {
+                        let new_ext_var = explicit_substitute(subs, from, to, ext_var, seen);
+
+                        for index in elems.into_iter() {
+                            let var = subs[index];
+                            let new_var = explicit_substitute(subs, from, to, var, seen);
+                            subs[index] = new_var;
+                        }
+
+                        subs.set_content(in_var, Structure(Tuple(elems, new_ext_var)));
+                    }
 
-                    EmptyRecord | EmptyTagUnion | Erroneous(_) => {}
+                    EmptyRecord | EmptyTagUnion | EmptyTuple | Erroneous(_) => {}
                 }
 
                 in_var
@@ -3517,9 +3555,10 @@ fn get_var_names(
                     accum
                 }
 
-                FlatType::EmptyRecord | FlatType::EmptyTagUnion | FlatType::Erroneous(_) => {
-                    taken_names
-                }
+                FlatType::EmptyRecord
+                | FlatType::EmptyTagUnion
+                | FlatType::EmptyTuple
+                | FlatType::Erroneous(_) => taken_names,
 
                 FlatType::Record(vars_by_field, ext_var) => {
                     let mut accum = get_var_names(subs, ext_var, taken_names);
@@ -3532,6 +3571,17 @@ fn get_var_names(
 
                     accum
                 }
+                FlatType::Tuple(elems, ext_var) => {
+                    let mut accum = get_var_names(subs, ext_var, taken_names);
+
+                    for var_index in elems.into_iter() {
+                        let elem_var = subs[var_index];
+
+                        accum = get_var_names(subs, elem_var, accum)
+                    }
+
+                    accum
+                }
                 FlatType::TagUnion(tags, ext_var) => {
                     let taken_names = get_var_names(subs, ext_var, taken_names);
                     get_var_names_union(subs, tags, taken_names)
@@ -3797,6 +3847,7 @@ fn flat_type_to_err_type(
 
         EmptyRecord => ErrorType::Record(SendMap::default(), TypeExt::Closed),
         EmptyTagUnion => ErrorType::TagUnion(SendMap::default(), TypeExt::Closed),
+        EmptyTuple => ErrorType::Tuple(Vec::new(), TypeExt::Closed),
 
         Record(vars_by_field, ext_var) => {
             let mut err_fields = SendMap::default();
@@ -3837,6 +3888,34 @@ fn flat_type_to_err_type(
             }
         }
 
+        Tuple(elems, ext_var) => {
+            let mut err_elems: Vec<ErrorType> = elems
+                .into_iter()
+                .map(|index| {
+                    let elem_var = subs[index];
+                    var_to_err_type(subs, state, elem_var)
+                })
+                .collect();
+
+            match var_to_err_type(subs, state, ext_var).unwrap_structural_alias() {
+                ErrorType::Tuple(sub_elems, sub_ext) => {
+                    err_elems.extend(sub_elems);
+                    ErrorType::Tuple(err_elems, sub_ext)
+                }
+
+                ErrorType::FlexVar(var) => {
+                    ErrorType::Tuple(err_elems, TypeExt::FlexOpen(var))
+                }
+
+                ErrorType::RigidVar(var) => {
+                    ErrorType::Tuple(err_elems, TypeExt::RigidOpen(var))
+                }
+
+                other =>
+                    panic!("Tried to convert a tuple extension to an error, but the tuple extension had the ErrorType of {:?}", other)
+            }
+        }
+
         TagUnion(tags, ext_var) => {
             let err_tags = union_tags_to_err_tags(subs, state, tags);
 
@@ -4154,6 +4233,10 @@ impl StorageSubs {
                 Self::offset_record_fields(offsets, *record_fields),
                 Self::offset_variable(offsets, *ext),
             ),
+            FlatType::Tuple(elems, ext) => FlatType::Tuple(
+                Self::offset_variable_slice(offsets, *elems),
+                Self::offset_variable(offsets, *ext),
+            ),
             FlatType::TagUnion(union_tags, ext) => FlatType::TagUnion(
                 Self::offset_tag_union(offsets, *union_tags),
                 Self::offset_variable(offsets, *ext),
@@ -4173,6 +4256,7 @@ impl StorageSubs {
             }
             FlatType::EmptyRecord => FlatType::EmptyRecord,
             FlatType::EmptyTagUnion => FlatType::EmptyTagUnion,
+            FlatType::EmptyTuple => FlatType::EmptyTuple,
         }
     }
 
@@ -4467,7 +4551,10 @@ fn storage_copy_var_to_help(env: &mut StorageCopyVarToEnv<'_>, var: Variable) ->
                     Func(new_arguments, new_closure_var, new_ret_var)
                 }
 
-                same @ EmptyRecord | same @ EmptyTagUnion | same @ Erroneous(_) => same,
+                same @ EmptyRecord
+                | same @ EmptyTagUnion
+                | same @ EmptyTuple
+                | same @ Erroneous(_) => same,
 
                 Record(fields, ext_var) => {
                     let record_fields = {
@@ -4504,6 +4591,20 @@ fn storage_copy_var_to_help(env: &mut StorageCopyVarToEnv<'_>, var: Variable) ->
                     Record(record_fields, storage_copy_var_to_help(env, ext_var))
                 }
 
+                Tuple(elems, ext_var) => {
+                    let new_ext = storage_copy_var_to_help(env, ext_var);
+
+                    let new_elems = SubsSlice::reserve_into_subs(env.target, elems.len());
+
+                    for (target_index, var_index) in (new_elems.indices()).zip(elems) {
+                        let var = env.source[var_index];
+                        let copy_var = storage_copy_var_to_help(env, var);
+                        env.target.variables[target_index] = copy_var;
+                    }
+
+                    Tuple(new_elems, new_ext)
+                }
+
                 TagUnion(tags, ext_var) => {
                     let new_ext = storage_copy_var_to_help(env, ext_var);
                     let union_tags = storage_copy_union(env, tags);
@@ -4775,7 +4876,9 @@ fn is_registered(content: &Content) -> bool {
         | Content::RigidVar(_)
         | Content::FlexAbleVar(..)
         | Content::RigidAbleVar(..) => false,
-        Content::Structure(FlatType::EmptyRecord | FlatType::EmptyTagUnion) => false,
+        Content::Structure(
+            FlatType::EmptyRecord | FlatType::EmptyTagUnion | FlatType::EmptyTuple,
+        ) => false,
 
         Content::Structure(_)
         | Content::RecursionVar { .. }
@@ -4905,7 +5008,7 @@ fn copy_import_to_help(env: &mut CopyImportEnv<'_>, max_rank: Rank, var: Variabl
 
                 Erroneous(_) => internal_error!("I thought this was handled above"),
 
-                same @ EmptyRecord | same @ EmptyTagUnion => same,
+                same @ EmptyRecord | same @ EmptyTagUnion | same @ EmptyTuple => same,
 
                 Record(fields, ext_var) => {
                     let record_fields = {
@@ -4942,6 +5045,20 @@ fn copy_import_to_help(env: &mut CopyImportEnv<'_>, max_rank: Rank, var: Variabl
                     Record(record_fields, copy_import_to_help(env, max_rank, ext_var))
                 }
 
+                Tuple(elems, ext_var) => {
+                    let new_ext = copy_import_to_help(env, max_rank, ext_var);
+
+                    let new_elems = SubsSlice::reserve_into_subs(env.target, elems.len());
+
+                    for (target_index, var_index) in (new_elems.indices()).zip(elems) {
+                        let var = env.source[var_index];
+                        let copy_var = copy_import_to_help(env, max_rank, var);
+                        env.target.variables[target_index] = copy_var;
+                    }
+
+                    Tuple(new_elems, new_ext)
+                }
+
                 TagUnion(tags, ext_var) => {
                     let new_ext = copy_import_to_help(env, max_rank, ext_var);
 
@@ -5220,6 +5337,7 @@ fn instantiate_rigids_help(subs: &mut Subs, max_rank: Rank, initial: Variable) {
 
                 EmptyRecord => (),
                 EmptyTagUnion => (),
+                EmptyTuple => (),
 
                 Record(fields, ext_var) => {
                     let fields = *fields;
@@ -5228,6 +5346,13 @@ fn instantiate_rigids_help(subs: &mut Subs, max_rank: Rank, initial: Variable) {
 
                     stack.push(ext_var);
                 }
+                Tuple(elems, ext_var) => {
+                    let elems = *elems;
+                    let ext_var = *ext_var;
+                    stack.extend(var_slice!(elems));
+
+                    stack.push(ext_var);
+                }
                 TagUnion(tags, ext_var) => {
                     let tags = *tags;
                     let ext_var = *ext_var;
@@ -5340,6 +5465,10 @@ pub fn get_member_lambda_sets_at_region(subs: &Subs, var: Variable, target_regio
                     stack.extend(subs.get_subs_slice(fields.variables()));
                     stack.push(*ext);
                 }
+                FlatType::Tuple(elems, ext) => {
+                    stack.extend(subs.get_subs_slice(*elems));
+                    stack.push(*ext);
+                }
                 FlatType::TagUnion(tags, ext) => {
                     stack.extend(
                         subs.get_subs_slice(tags.variables())
@@ -5360,7 +5489,10 @@ pub fn get_member_lambda_sets_at_region(subs: &Subs, var: Variable, target_regio
                     );
                     stack.push(*ext);
                 }
-                FlatType::Erroneous(_) | FlatType::EmptyRecord | FlatType::EmptyTagUnion => {}
+                FlatType::Erroneous(_)
+                | FlatType::EmptyRecord
+                | FlatType::EmptyTagUnion
+                | FlatType::EmptyTuple => {}
             },
             Content::Alias(_, _, real_var, _) => {
                 stack.push(*real_var);
@@ -247,6 +247,8 @@ pub enum Type {
     /// A function. The types of its arguments, size of its closure, then the type of its return value.
     Function(Vec<Type>, Box<Type>, Box<Type>),
     Record(SendMap<Lowercase, RecordField<Type>>, TypeExtension),
+    /// The leading elements of a tuple; the extension holds any elements that follow them.
+    Tuple(Vec<Type>, TypeExtension),
     TagUnion(Vec<(TagName, Vec<Type>)>, TypeExtension),
     FunctionOrTagUnion(TagName, Symbol, TypeExtension),
     /// A function name that is used in our defunctionalization algorithm. For example in
@@ -328,6 +330,7 @@ impl Clone for Type {
                 Self::Function(arg0.clone(), arg1.clone(), arg2.clone())
             }
             Self::Record(arg0, arg1) => Self::Record(arg0.clone(), arg1.clone()),
+            Self::Tuple(arg0, arg1) => Self::Tuple(arg0.clone(), arg1.clone()),
             Self::TagUnion(arg0, arg1) => Self::TagUnion(arg0.clone(), arg1.clone()),
             Self::FunctionOrTagUnion(arg0, arg1, arg2) => {
                 Self::FunctionOrTagUnion(arg0.clone(), *arg1, arg2.clone())
@@ -614,6 +617,32 @@ impl fmt::Debug for Type {
                     }
                 }
             }
+            Type::Tuple(elems, ext) => {
+                write!(f, "(")?;
+
+                if !elems.is_empty() {
+                    write!(f, " ")?;
+                }
+
+                for (index, elem) in elems.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    write!(f, "{:?}", elem)?;
+                }
+
+                if !elems.is_empty() {
+                    write!(f, " ")?;
+                }
+
+                write!(f, ")")?;
+
+                match ext {
+                    TypeExtension::Closed => Ok(()),
+                    TypeExtension::Open(other) => other.fmt(f),
+                }
+            }
             Type::TagUnion(tags, ext) => {
                 write_tags(f, tags.iter())?;
 
@@ -784,6 +813,13 @@ impl Type {
                         stack.push(ext);
                     }
                 }
+                Tuple(elems, ext) => {
+                    stack.extend(elems.iter_mut());
+
+                    if let TypeExtension::Open(ext) = ext {
+                        stack.push(ext);
+                    }
+                }
                 Type::DelayedAlias(AliasCommon {
                     type_arguments,
                     lambda_set_variables,
@@ -906,6 +942,12 @@ impl Type {
                         stack.push(ext);
                     }
                 }
+                Tuple(elems, ext) => {
+                    stack.extend(elems.iter_mut());
+                    if let TypeExtension::Open(ext) = ext {
+                        stack.push(ext);
+                    }
+                }
                 Type::DelayedAlias(AliasCommon {
                     type_arguments,
                     lambda_set_variables,
@@ -1012,6 +1054,16 @@ impl Type {
                     TypeExtension::Closed => Ok(()),
                 }
             }
+            Tuple(elems, ext) => {
+                for x in elems.iter_mut() {
+                    x.substitute_alias(rep_symbol, rep_args, actual)?;
+                }
+
+                match ext {
+                    TypeExtension::Open(ext) => ext.substitute_alias(rep_symbol, rep_args, actual),
+                    TypeExtension::Closed => Ok(()),
+                }
+            }
             DelayedAlias(AliasCommon {
                 type_arguments,
                 lambda_set_variables: _no_aliases_in_lambda_sets,
@@ -1093,6 +1145,10 @@ impl Type {
                 Self::contains_symbol_ext(ext, rep_symbol)
                     || fields.values().any(|arg| arg.contains_symbol(rep_symbol))
             }
+            Tuple(elems, ext) => {
+                Self::contains_symbol_ext(ext, rep_symbol)
+                    || elems.iter().any(|arg| arg.contains_symbol(rep_symbol))
+            }
             DelayedAlias(AliasCommon {
                 symbol,
                 type_arguments,
@@ -1163,6 +1219,10 @@ impl Type {
                         .values()
                         .any(|arg| arg.contains_variable(rep_variable))
             }
+            Tuple(elems, ext) => {
+                Self::contains_variable_ext(ext, rep_variable)
+                    || elems.iter().any(|arg| arg.contains_variable(rep_variable))
+            }
             DelayedAlias(AliasCommon { .. }) => {
                 todo!()
             }
@@ -1247,6 +1307,15 @@ impl Type {
                     ext.instantiate_aliases(region, aliases, var_store, new_lambda_set_variables);
                 }
             }
+            Tuple(elems, ext) => {
+                for x in elems.iter_mut() {
+                    x.instantiate_aliases(region, aliases, var_store, new_lambda_set_variables);
+                }
+
+                if let TypeExtension::Open(ext) = ext {
+                    ext.instantiate_aliases(region, aliases, var_store, new_lambda_set_variables);
+                }
+            }
             DelayedAlias(AliasCommon {
                 type_arguments,
                 lambda_set_variables,
@@ -1496,6 +1565,12 @@ impl Type {
                 }
                 TypeExtension::Closed => fields.values().all(|field| field.as_inner().is_narrow()),
             },
+            Type::Tuple(elems, ext) => match ext {
+                TypeExtension::Open(ext) => {
+                    elems.iter().all(|elem| elem.is_narrow()) && ext.is_narrow()
+                }
+                TypeExtension::Closed => elems.iter().all(|elem| elem.is_narrow()),
+            },
             Type::Function(args, clos, ret) => {
                 args.iter().all(|a| a.is_narrow()) && clos.is_narrow() && ret.is_narrow()
             }
@@ -1543,6 +1618,10 @@ fn symbols_help(initial: &Type) -> Vec<Symbol> {
                 stack.extend(ext);
                 stack.extend(fields.values().map(|field| field.as_inner()));
             }
+            Tuple(elems, ext) => {
+                stack.extend(ext);
+                stack.extend(elems);
+            }
             DelayedAlias(AliasCommon {
                 symbol,
                 type_arguments,
@@ -1623,6 +1702,15 @@ fn variables_help(tipe: &Type, accum: &mut ImSet<Variable>) {
                 variables_help(ext, accum);
             }
         }
+        Tuple(elems, ext) => {
+            for elem in elems {
+                variables_help(elem, accum);
+            }
+
+            if let TypeExtension::Open(ext) = ext {
+                variables_help(ext, accum);
+            }
+        }
         ClosureTag {
             name: _,
             captures,
@@ -1765,6 +1853,15 @@ fn variables_help_detailed(tipe: &Type, accum: &mut VariableDetail) {
                 variables_help_detailed(ext, accum);
             }
         }
+        Tuple(elems, ext) => {
+            for elem in elems {
+                variables_help_detailed(elem, accum);
+            }
+
+            if let TypeExtension::Open(ext) = ext {
+                variables_help_detailed(ext, accum);
+            }
+        }
         ClosureTag {
             name: _,
             captures,
@@ -1867,6 +1964,13 @@ pub struct RecordStructure {
     pub ext: Variable,
 }
 
+#[derive(Debug)]
+pub struct TupleStructure {
+    /// Invariant: these are in positional order!
+    pub elems: Vec<Variable>,
+    pub ext: Variable,
+}
+
 #[derive(Debug)]
 pub struct TagUnionStructure<'a> {
     /// Invariant: these should be sorted!
@@ -2015,6 +2119,10 @@ pub enum Category {
     Access(Lowercase),
     DefaultValue(Lowercase), // for setting optional fields
 
+    // tuples
+    Tuple,
+    TupleAccess(usize),
+
     AbilityMemberSpecialization(Symbol),
 
     Expect,
@@ -2025,6 +2133,7 @@ pub enum Category {
 pub enum PatternCategory {
     Record,
     EmptyRecord,
+    Tuple,
     PatternGuard,
     PatternDefault,
     Set,
@@ -2165,6 +2274,7 @@ pub enum ErrorType {
     FlexAbleVar(Lowercase, Symbol),
     RigidAbleVar(Lowercase, Symbol),
     Record(SendMap<Lowercase, RecordField<ErrorType>>, TypeExt),
+    Tuple(Vec<ErrorType>, TypeExt),
     TagUnion(SendMap<TagName, Vec<ErrorType>>, TypeExt),
     RecursiveTagUnion(Box<ErrorType>, SendMap<TagName, Vec<ErrorType>>, TypeExt),
     Function(Vec<ErrorType>, Box<ErrorType>, Box<ErrorType>),
@@ -2203,6 +2313,10 @@ impl ErrorType {
                     .for_each(|(_, t)| t.as_inner().add_names(taken));
                 ext.add_names(taken);
             }
+            Tuple(elems, ext) => {
+                elems.iter().for_each(|t| t.add_names(taken));
+                ext.add_names(taken);
+            }
             TagUnion(tags, ext) => {
                 tags.iter()
                     .for_each(|(_, ts)| ts.iter().for_each(|t| t.add_names(taken)));
@@ -2354,6 +2468,22 @@ fn write_error_type_help(
             buf.push('}');
             write_type_ext(ext, buf);
         }
+        Tuple(elems, ext) => {
+            buf.push('(');
+
+            let mut it = elems.into_iter().peekable();
+
+            while let Some(elem) = it.next() {
+                write_error_type_help(home, interns, elem, buf, Parens::Unnecessary);
+
+                if it.peek().is_some() {
+                    buf.push_str(", ");
+                }
+            }
+
+            buf.push(')');
+            write_type_ext(ext, buf);
+        }
 
         other => todo!("cannot format {:?} yet", other),
     }
@@ -2508,6 +2638,22 @@ fn write_debug_error_type_help(error_type: ErrorType, buf: &mut String, parens:
             buf.push('}');
             write_type_ext(ext, buf);
         }
+        Tuple(elems, ext) => {
+            buf.push('(');
+
+            let mut it = elems.into_iter().peekable();
+
+            while let Some(elem) = it.next() {
+                write_debug_error_type_help(elem, buf, Parens::Unnecessary);
+
+                if it.peek().is_some() {
+                    buf.push_str(", ");
+                }
+            }
+
+            buf.push(')');
+            write_type_ext(ext, buf);
+        }
         TagUnion(tags, ext) => {
             buf.push('[');
 
@@ -2734,6 +2880,46 @@ pub fn gather_fields(
     })
 }
 
+#[derive(Debug, Copy, Clone)]
+pub struct TupleElemsError;
+
+/// Gathers the elements of a tuple, following its extension for as long as it is itself a tuple.
+pub fn gather_tuple_elems(
+    subs: &Subs,
+    other_elems: VariableSubsSlice,
+    mut var: Variable,
+) -> Result<TupleStructure, TupleElemsError> {
+    use crate::subs::Content::*;
+    use crate::subs::FlatType::*;
+
+    let mut elems = subs.get_subs_slice(other_elems).to_vec();
+
+    loop {
+        match subs.get_content_without_compacting(var) {
+            Structure(Tuple(sub_elems, sub_ext)) => {
+                elems.extend(subs.get_subs_slice(*sub_elems));
+
+                var = *sub_ext;
+            }
+
+            Alias(_, _, actual_var, _) => {
+                var = *actual_var;
+            }
+
+            Structure(EmptyTuple) => break,
+            FlexVar(_) => break,
+            RigidVar(_) => break,
+
+            // Stop on errors in the tuple
+            Error => break,
+
+            _ => return Err(TupleElemsError),
+        }
+    }
+
+    Ok(TupleStructure { elems, ext: var })
+}
+
 #[derive(Debug)]
 pub enum GatherTagsError {
     NotATagUnion(Variable),
@@ -2895,6 +3081,10 @@ fn instantiate_lambda_sets_as_unspecialized(
                     stack.push(x.as_inner_mut());
                 }
             }
+            Type::Tuple(elems, ext) => {
+                stack.extend(ext.iter_mut());
+                stack.extend(elems.iter_mut().rev());
+            }
             Type::TagUnion(tags, ext) | Type::RecursiveTagUnion(_, tags, ext) => {
                 stack.extend(ext.iter_mut());
                 for (_, ts) in tags {
@@ -13,7 +13,10 @@ use roc_types::subs::{
     OptVariable, RecordFields, Subs, SubsIndex, SubsSlice, UlsOfVar, UnionLabels, UnionLambdas,
     UnionTags, Variable, VariableSubsSlice,
 };
-use roc_types::types::{AliasKind, DoesNotImplementAbility, ErrorType, Mismatch, RecordField, Uls};
+use roc_types::types::{
+    gather_tuple_elems, AliasKind, DoesNotImplementAbility, ErrorType, Mismatch, RecordField,
+    TupleStructure, Uls,
+};
 
 macro_rules! mismatch {
     () => {{
@@ -1737,6 +1740,78 @@ fn unify_record<M: MetaCollector>(
     }
 }
 
+fn unify_tuple<M: MetaCollector>(
+    env: &mut Env,
+    pool: &mut Pool,
+    ctx: &Context,
+    elems1: VariableSubsSlice,
+    ext1: Variable,
+    elems2: VariableSubsSlice,
+    ext2: Variable,
+) -> Outcome<M> {
+    let subs = &env.subs;
+
+    let (elems1, ext1) = match gather_tuple_elems(subs, elems1, ext1) {
+        Ok(TupleStructure { elems, ext }) => (elems, ext),
+        Err(_) => return mismatch!("unify_tuple: tuple has an invalid extension"),
+    };
+    let (elems2, ext2) = match gather_tuple_elems(subs, elems2, ext2) {
+        Ok(TupleStructure { elems, ext }) => (elems, ext),
+        Err(_) => return mismatch!("unify_tuple: tuple has an invalid extension"),
+    };
+
+    let num_shared = elems1.len().min(elems2.len());
+
+    // Elements are positional, so only the elements one side has past the end of the other can
+    // be absorbed by that other side's extension.
+    let (ext_outcome, merged_elems, merged_ext) = match elems1.len().cmp(&elems2.len()) {
+        std::cmp::Ordering::Equal => {
+            let ext_outcome = unify_pool(env, pool, ext1, ext2, ctx.mode);
+
+            (ext_outcome, &elems1, ext1)
+        }
+        std::cmp::Ordering::Less => {
+            let only_in_2 =
+                VariableSubsSlice::insert_into_subs(env.subs, elems2[num_shared..].iter().copied());
+            let sub_tuple = fresh(env, pool, ctx, Structure(FlatType::Tuple(only_in_2, ext2)));
+            let ext_outcome = unify_pool(env, pool, ext1, sub_tuple, ctx.mode);
+
+            (ext_outcome, &elems2, ext2)
+        }
+        std::cmp::Ordering::Greater => {
+            let only_in_1 =
+                VariableSubsSlice::insert_into_subs(env.subs, elems1[num_shared..].iter().copied());
+            let sub_tuple = fresh(env, pool, ctx, Structure(FlatType::Tuple(only_in_1, ext1)));
+            let ext_outcome = unify_pool(env, pool, sub_tuple, ext2, ctx.mode);
+
+            (ext_outcome, &elems1, ext1)
+        }
+    };
+
+    if !ext_outcome.mismatches.is_empty() {
+        return ext_outcome;
+    }
+
+    let mut whole_outcome = ext_outcome;
+
+    for (&actual, &expected) in elems1.iter().zip(elems2.iter()) {
+        let local_outcome = unify_pool(env, pool, actual, expected, ctx.mode);
+
+        if !local_outcome.mismatches.is_empty() {
+            return mismatch!("in unify_tuple");
+        }
+
+        whole_outcome.union(local_outcome);
+    }
+
+    let merged_elems = VariableSubsSlice::insert_into_subs(env.subs, merged_elems.iter().copied());
+    let flat_type = FlatType::Tuple(merged_elems, merged_ext);
+
+    let merge_outcome = merge(env, ctx, Structure(flat_type));
+    whole_outcome.union(merge_outcome);
+    whole_outcome
+}
+
 enum OtherFields {
     None,
     Other(RecordFields, RecordFields),
@@ -2465,6 +2540,20 @@ fn unify_flat_type<M: MetaCollector>(
             unify_record(env, pool, ctx, *fields1, *ext1, *fields2, *ext2)
         }
 
+        (EmptyTuple, EmptyTuple) => merge(env, ctx, Structure(*left)),
+
+        (Tuple(elems, ext), EmptyTuple) if elems.is_empty() => {
+            unify_pool(env, pool, *ext, ctx.second, ctx.mode)
+        }
+
+        (EmptyTuple, Tuple(elems, ext)) if elems.is_empty() => {
+            unify_pool(env, pool, ctx.first, *ext, ctx.mode)
+        }
+
+        (Tuple(elems1, ext1), Tuple(elems2, ext2)) => {
+            unify_tuple(env, pool, ctx, *elems1, *ext1, *elems2, *ext2)
+        }
+
         (EmptyTagUnion, EmptyTagUnion) => merge(env, ctx, Structure(*left)),
 
         (TagUnion(tags, ext), EmptyTagUnion) if tags.is_empty() => {
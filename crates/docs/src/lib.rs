@@ -10,7 +10,9 @@ use roc_highlight::highlight_parser::{highlight_defs, highlight_expr};
 use roc_load::docs::DocEntry::DocDef;
 use roc_load::docs::{DocEntry, TypeAnnotation};
 use roc_load::docs::{ModuleDocumentation, RecordField};
-use roc_load::{ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
+use roc_load::{
+    ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, OptLevel, PropertyTesting, Threading,
+};
 use roc_module::symbol::{IdentIdsByModule, Interns, ModuleId};
 use roc_parse::ident::{parse_ident, Ident};
 use roc_parse::state::State;
@@ -76,9 +78,9 @@ pub fn generate_docs_html(filenames: Vec<PathBuf>) {
                         // actually has interesting information in it?
                         if *module_id == loaded_module.module_id {
                             let exposed_values = loaded_module
-                                .exposed_values
-                                .iter()
-                                .map(|symbol| symbol.as_str(&loaded_module.interns).to_string())
+                                .exposed_values_str()
+                                .into_iter()
+                                .map(String::from)
                                 .collect::<Vec<String>>();
 
                             Some((module, exposed_values))
@@ -437,10 +439,12 @@ pub fn load_modules_for_files(filenames: Vec<PathBuf>) -> Vec<LoadedModule> {
             render: roc_reporting::report::RenderTarget::ColorTerminal,
             threading: Threading::AllAvailable,
             exec_mode: ExecutionMode::Check,
+            property_testing: PropertyTesting::default(),
+            opt_level: OptLevel::Normal,
         };
         match roc_load::load_and_typecheck(&arena, filename, Default::default(), load_config) {
             Ok(loaded) => modules.push(loaded),
-            Err(LoadingProblem::FormattedReport(report)) => {
+            Err(LoadingProblem::FormattedReport(report, _)) => {
                 eprintln!("{}", report);
                 std::process::exit(1);
             }
@@ -603,6 +607,52 @@ fn type_annotation_to_html(indent_level: usize, buf: &mut String, type_ann: &Typ
 
             type_annotation_to_html(indent_level, buf, extension);
         }
+        TypeAnnotation::Tuple { elems, extension } => {
+            let elems_len = elems.len();
+
+            let tuple_indent = indent_level + 1;
+
+            if is_multiline {
+                new_line(buf);
+                indent(buf, tuple_indent);
+            }
+
+            buf.push('(');
+
+            if is_multiline {
+                new_line(buf);
+            }
+
+            let next_indent_level = tuple_indent + 1;
+
+            for (index, elem) in elems.iter().enumerate() {
+                if is_multiline {
+                    indent(buf, next_indent_level);
+                }
+
+                type_annotation_to_html(next_indent_level, buf, elem);
+
+                if index < (elems_len - 1) {
+                    buf.push(',');
+
+                    if !is_multiline {
+                        buf.push(' ');
+                    }
+                }
+
+                if is_multiline {
+                    new_line(buf);
+                }
+            }
+
+            if is_multiline {
+                indent(buf, tuple_indent);
+            }
+
+            buf.push(')');
+
+            type_annotation_to_html(indent_level, buf, extension);
+        }
         TypeAnnotation::Function { args, output } => {
             let mut peekable_args = args.iter().peekable();
             while let Some(arg) = peekable_args.next() {
@@ -714,6 +764,9 @@ fn should_be_multiline(type_ann: &TypeAnnotation) -> bool {
 
             is_multiline
         }
+        TypeAnnotation::Tuple { elems, extension } => {
+            should_be_multiline(extension) || elems.iter().any(should_be_multiline)
+        }
         TypeAnnotation::Ability { .. } => true,
         TypeAnnotation::Wildcard => false,
         TypeAnnotation::NoTypeAnn => false,
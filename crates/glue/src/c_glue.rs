@@ -0,0 +1,1411 @@
+use crate::rust_glue::{max_pointer_tagged_variants, tagged_pointer_bitmask};
+use crate::types::{RocNum, RocTagUnion, RocType, TypeId, Types};
+use indexmap::IndexMap;
+use roc_collections::MutSet;
+use roc_mono::layout::round_up_to_alignment;
+use roc_target::{Architecture, TargetInfo};
+use std::fmt::Write;
+
+pub static HEADER: &[u8] = include_bytes!("../templates/header.h");
+const INDENT: &str = "    ";
+const DISCRIMINANT_COMMENT: &str =
+    "// Returns which variant this tag union holds. Note that this never includes a payload!";
+
+/// C needs a type to be declared before anything refers to it, and defined
+/// before anything contains it by value. So the output has three sections:
+/// forward declarations, then type definitions, and finally functions.
+#[derive(Default)]
+struct Output {
+    declarations: Decls,
+    definitions: Decls,
+    functions: Decls,
+}
+
+/// Each declaration is keyed by its name, so that the variants of a declaration
+/// which differ between targets end up next to each other in the output.
+type Decls = IndexMap<String, IndexMap<String, Vec<TargetInfo>>>;
+
+/// Add the given declaration body, along with the target it was generated for.
+fn add_decl(decls: &mut Decls, decl_name: String, target_info: TargetInfo, body: String) {
+    let targets = decls.entry(decl_name).or_default().entry(body).or_default();
+
+    // The same declaration can come from more than one TypeId, e.g. two
+    // identical Result types, so only record each target once.
+    if !targets.contains(&target_info) {
+        targets.push(target_info);
+    }
+}
+
+pub fn emit(types_and_targets: &[(Types, TargetInfo)]) -> String {
+    let mut buf = String::new();
+    let mut output = Output::default();
+
+    for (types, target_info) in types_and_targets {
+        for id in definition_order(types) {
+            add_type(*target_info, id, types, &mut output);
+        }
+    }
+
+    for decls in [output.declarations, output.definitions, output.functions] {
+        for (_, bodies) in decls {
+            // If every target agrees on this declaration, there's no need for #if
+            let is_universal = bodies.len() == 1
+                && bodies
+                    .values()
+                    .all(|targets| targets.len() == types_and_targets.len());
+
+            for (body, targets) in bodies {
+                buf.push('\n');
+
+                if is_universal {
+                    buf.push_str(&body);
+                    buf.push('\n');
+                } else {
+                    let conditions: Vec<String> = targets
+                        .iter()
+                        .map(|target_info| {
+                            format!("defined({})", arch_macro(target_info.architecture))
+                        })
+                        .collect();
+
+                    writeln!(buf, "#if {}", conditions.join(" || ")).unwrap();
+                    buf.push_str(&body);
+                    buf.push_str("\n#endif\n");
+                }
+            }
+        }
+    }
+
+    buf
+}
+
+/// Orders the types so that each one comes after every type it contains by value.
+/// (Types::sorted_ids only accounts for the dependencies that Rust needs.)
+fn definition_order(types: &Types) -> Vec<TypeId> {
+    fn visit(id: TypeId, types: &Types, visited: &mut MutSet<TypeId>, order: &mut Vec<TypeId>) {
+        if visited.insert(id) {
+            for dep in value_deps(types.get_type(id)) {
+                visit(dep, types, visited, order);
+            }
+
+            order.push(id);
+        }
+    }
+
+    let mut visited = MutSet::default();
+    let mut order = Vec::with_capacity(types.ids().len());
+
+    for id in types.ids() {
+        visit(id, types, &mut visited, &mut order);
+    }
+
+    order
+}
+
+/// The types which this type contains by value, as opposed to behind a pointer.
+fn value_deps(typ: &RocType) -> Vec<TypeId> {
+    match typ {
+        RocType::Struct { fields, .. } => fields.iter().map(|(_, id)| *id).collect(),
+        RocType::TagUnionPayload { fields, .. } => fields.iter().map(|(_, id)| *id).collect(),
+        RocType::RocResult(ok_id, err_id) => vec![*ok_id, *err_id],
+        RocType::TagUnion(RocTagUnion::NonRecursive { tags, .. })
+        | RocType::TagUnion(RocTagUnion::Recursive { tags, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { tags, .. }) => {
+            tags.iter().filter_map(|(_, opt_id)| *opt_id).collect()
+        }
+        RocType::TagUnion(RocTagUnion::SingleTagStruct { payload_fields, .. }) => {
+            payload_fields.clone()
+        }
+        // The handles for these point to their payloads, but the payloads'
+        // typedefs still need to come before the handles' declarations.
+        RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { payload, .. }) => vec![*payload],
+        RocType::TagUnion(RocTagUnion::NullableUnwrapped {
+            non_null_payload, ..
+        }) => vec![*non_null_payload],
+        RocType::RocStr
+        | RocType::Bool
+        | RocType::Num(_)
+        | RocType::RocList(_)
+        | RocType::RocDict(_, _)
+        | RocType::RocSet(_)
+        | RocType::RocBox(_)
+        | RocType::TagUnion(RocTagUnion::Enumeration { .. })
+        | RocType::EmptyTagUnion
+        | RocType::RecursivePointer(_)
+        | RocType::Function { .. }
+        | RocType::Unit => Vec::new(),
+    }
+}
+
+fn add_type(target_info: TargetInfo, id: TypeId, types: &Types, output: &mut Output) {
+    match types.get_type(id) {
+        RocType::Struct { name, fields } => {
+            let fields = fields
+                .iter()
+                .map(|(label, field_id)| (escape_kw(label.clone()), *field_id))
+                .collect::<Vec<_>>();
+
+            add_struct(name, target_info, &fields, types, output)
+        }
+        RocType::TagUnionPayload { name, fields } => {
+            let fields = fields
+                .iter()
+                .map(|(index, field_id)| (format!("f{index}"), *field_id))
+                .collect::<Vec<_>>();
+
+            add_struct(name, target_info, &fields, types, output)
+        }
+        RocType::RocResult(ok_id, err_id) => {
+            add_result(target_info, id, *ok_id, *err_id, types, output)
+        }
+        RocType::TagUnion(tag_union) => match tag_union {
+            RocTagUnion::Enumeration { name, tags, size } => {
+                add_enumeration(name, target_info, tags, *size, output)
+            }
+            RocTagUnion::NonRecursive {
+                name,
+                tags,
+                discriminant_size,
+                discriminant_offset,
+            } => {
+                // Empty tag unions can never come up at runtime,
+                // and so don't need declared types.
+                if !tags.is_empty() {
+                    add_nonrecursive_tag_union(
+                        name,
+                        target_info,
+                        tags,
+                        *discriminant_size,
+                        *discriminant_offset,
+                        id,
+                        types,
+                        output,
+                    )
+                }
+            }
+            RocTagUnion::Recursive {
+                name,
+                tags,
+                discriminant_size,
+                ..
+            } => {
+                if !tags.is_empty() {
+                    add_recursive_tag_union(
+                        name,
+                        target_info,
+                        tags,
+                        None,
+                        *discriminant_size,
+                        types,
+                        output,
+                    )
+                }
+            }
+            RocTagUnion::NullableWrapped {
+                name,
+                index_of_null_tag,
+                tags,
+                discriminant_size,
+                ..
+            } => add_recursive_tag_union(
+                name,
+                target_info,
+                tags,
+                Some(*index_of_null_tag as usize),
+                *discriminant_size,
+                types,
+                output,
+            ),
+            RocTagUnion::NonNullableUnwrapped {
+                name,
+                tag_name,
+                payload,
+            } => {
+                add_unwrapped_tag_union(name, target_info, None, tag_name, *payload, types, output)
+            }
+            RocTagUnion::NullableUnwrapped {
+                name,
+                null_tag,
+                non_null_tag,
+                non_null_payload,
+                ..
+            } => add_unwrapped_tag_union(
+                name,
+                target_info,
+                Some(null_tag),
+                non_null_tag,
+                *non_null_payload,
+                types,
+                output,
+            ),
+            RocTagUnion::SingleTagStruct {
+                name,
+                tag_name,
+                payload_fields,
+            } => add_single_tag_struct(name, target_info, tag_name, payload_fields, types, output),
+        },
+        RocType::RocStr
+        | RocType::Bool
+        | RocType::Num(_)
+        | RocType::RocList(_)
+        | RocType::RocDict(_, _)
+        | RocType::RocSet(_)
+        | RocType::RocBox(_)
+        | RocType::EmptyTagUnion
+        | RocType::RecursivePointer(_)
+        | RocType::Unit => {
+            // These types don't need to be declared in C; they're either
+            // C primitives or else they're defined in the header.
+        }
+        RocType::Function { .. } => {
+            // TODO generate glue for calling Roc closures from C
+        }
+    }
+}
+
+fn add_struct(
+    name: &str,
+    target_info: TargetInfo,
+    fields: &[(String, TypeId)],
+    types: &Types,
+    output: &mut Output,
+) {
+    let name = escape_kw(name.to_string());
+    let has_drop = fields
+        .iter()
+        .any(|(_, field_id)| has_refcount(types.get_type(*field_id), types));
+
+    add_decl(
+        &mut output.declarations,
+        name.clone(),
+        target_info,
+        forward_decl("struct", &name, has_drop),
+    );
+
+    {
+        let mut buf = format!("struct {name} {{\n");
+        let sized_fields = sized_fields(fields, types);
+
+        for (label, field_id) in sized_fields.iter() {
+            writeln!(buf, "{INDENT}{} {label};", type_name(*field_id, types)).unwrap();
+        }
+
+        if sized_fields.is_empty() {
+            // C doesn't allow empty structs.
+            writeln!(buf, "{INDENT}uint8_t unused;").unwrap();
+        }
+
+        buf.push_str("};");
+
+        add_decl(&mut output.definitions, name.clone(), target_info, buf);
+    }
+
+    if has_drop {
+        let mut buf = format!("static inline void {name}_drop({name} *self) {{\n");
+
+        for (label, field_id) in fields {
+            write_drop(&mut buf, 1, &format!("self->{label}"), *field_id, types);
+        }
+
+        buf.push('}');
+
+        add_decl(
+            &mut output.functions,
+            format!("{name}_drop"),
+            target_info,
+            buf,
+        );
+    }
+}
+
+fn add_result(
+    target_info: TargetInfo,
+    id: TypeId,
+    ok_id: TypeId,
+    err_id: TypeId,
+    types: &Types,
+    output: &mut Output,
+) {
+    let name = type_name(id, types);
+    let ok_type = type_name(ok_id, types);
+    let err_type = type_name(err_id, types);
+    let has_drop = has_refcount(types.get_type(id), types);
+
+    add_decl(
+        &mut output.declarations,
+        name.clone(),
+        target_info,
+        forward_decl("struct", &name, has_drop),
+    );
+
+    add_decl(
+        &mut output.definitions,
+        name.clone(),
+        target_info,
+        format!(
+            r#"struct {name} {{
+    union {{
+        {ok_type} ok;
+        {err_type} err;
+    }} payload;
+    uint8_t tag; // Err = 0, Ok = 1
+}};"#
+        ),
+    );
+
+    add_decl(
+        &mut output.functions,
+        format!("{name}_ok"),
+        target_info,
+        format!(
+            r#"static inline {name} {name}_ok({ok_type} ok) {{
+    {name} answer;
+
+    memset(&answer, 0, sizeof(answer));
+    answer.payload.ok = ok;
+    answer.tag = 1;
+
+    return answer;
+}}"#
+        ),
+    );
+
+    add_decl(
+        &mut output.functions,
+        format!("{name}_err"),
+        target_info,
+        format!(
+            r#"static inline {name} {name}_err({err_type} err) {{
+    {name} answer;
+
+    memset(&answer, 0, sizeof(answer));
+    answer.payload.err = err;
+    answer.tag = 0;
+
+    return answer;
+}}"#
+        ),
+    );
+
+    add_decl(
+        &mut output.functions,
+        format!("{name}_is_ok"),
+        target_info,
+        format!(
+            r#"static inline bool {name}_is_ok(const {name} *self) {{
+    return self->tag == 1;
+}}"#
+        ),
+    );
+
+    if has_drop {
+        let mut ok_buf = String::new();
+        let mut err_buf = String::new();
+
+        write_drop(&mut ok_buf, 2, "self->payload.ok", ok_id, types);
+        write_drop(&mut err_buf, 2, "self->payload.err", err_id, types);
+
+        add_decl(
+            &mut output.functions,
+            format!("{name}_drop"),
+            target_info,
+            format!(
+                r#"static inline void {name}_drop({name} *self) {{
+    if (self->tag == 1) {{
+{ok_buf}    }} else {{
+{err_buf}    }}
+}}"#
+            ),
+        );
+    }
+}
+
+fn add_enumeration(
+    name: &str,
+    target_info: TargetInfo,
+    tags: &[String],
+    tag_bytes: u32,
+    output: &mut Output,
+) {
+    let name = escape_kw(name.to_string());
+
+    // A C enum is always the size of an int, so use a fixed-size integer
+    // for the type itself and an anonymous enum for the tags' values.
+    let mut buf = format!("typedef uint{}_t {name};\n\nenum {{\n", tag_bytes * 8);
+
+    for (index, tag_name) in tags.iter().enumerate() {
+        writeln!(buf, "{INDENT}{name}_{tag_name} = {index},").unwrap();
+    }
+
+    buf.push_str("};");
+
+    add_decl(&mut output.definitions, name, target_info, buf);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn add_nonrecursive_tag_union(
+    name: &str,
+    target_info: TargetInfo,
+    tags: &[(String, Option<TypeId>)],
+    discriminant_size: u32,
+    discriminant_offset: u32,
+    id: TypeId,
+    types: &Types,
+    output: &mut Output,
+) {
+    let name = escape_kw(name.to_string());
+    let discriminant_name = format!("discriminant_{name}");
+    let has_drop = has_refcount(types.get_type(id), types);
+
+    add_decl(
+        &mut output.declarations,
+        name.clone(),
+        target_info,
+        forward_decl("union", &name, has_drop),
+    );
+
+    // The discriminant, and then the union itself
+    {
+        let mut buf = discriminant_decl(
+            &name,
+            tags.iter().map(|(tag_name, _)| tag_name),
+            discriminant_size,
+        );
+
+        writeln!(buf, "\n\nunion {name} {{").unwrap();
+
+        for (tag_name, opt_payload_id) in tags {
+            // If there's no payload, it doesn't need a field.
+            if let Some(payload_id) = opt_payload_id {
+                writeln!(buf, "{INDENT}{} {tag_name};", type_name(*payload_id, types)).unwrap();
+            }
+        }
+
+        if tags.len() > 1 {
+            // The discriminant lives in the same bytes as the payloads, at
+            // discriminant_offset. This makes sure the union is big enough for it.
+            writeln!(
+                buf,
+                "{INDENT}uint8_t _sizer[{}];",
+                types.size_rounded_to_alignment(id)
+            )
+            .unwrap();
+        }
+
+        buf.push_str("};");
+
+        add_decl(&mut output.definitions, name.clone(), target_info, buf);
+    }
+
+    add_decl(
+        &mut output.functions,
+        format!("{name}_discriminant"),
+        target_info,
+        format!(
+            r#"{DISCRIMINANT_COMMENT}
+static inline {discriminant_name} {name}_discriminant(const {name} *self) {{
+    {discriminant_name} discriminant;
+
+    memcpy(&discriminant, (const char *)self + {discriminant_offset}, sizeof(discriminant));
+
+    return discriminant;
+}}"#
+        ),
+    );
+
+    add_decl(
+        &mut output.functions,
+        format!("{name}_set_discriminant"),
+        target_info,
+        format!(
+            r#"// Internal helper
+static inline void {name}_set_discriminant({name} *self, {discriminant_name} discriminant) {{
+    memcpy((char *)self + {discriminant_offset}, &discriminant, sizeof(discriminant));
+}}"#
+        ),
+    );
+
+    for (tag_name, opt_payload_id) in tags {
+        let (params, payload_expr) = constructor_params(*opt_payload_id, types);
+        let mut buf = format!(
+            "// Construct a tag named `{tag_name}`. This takes ownership of the payload.\nstatic inline {name} {name}_{tag_name}({params}) {{\n{INDENT}{name} answer;\n\n{INDENT}memset(&answer, 0, sizeof(answer));\n"
+        );
+
+        if let Some(payload_expr) = payload_expr {
+            writeln!(buf, "{INDENT}answer.{tag_name} = {payload_expr};").unwrap();
+        }
+
+        write!(
+            buf,
+            "{INDENT}{name}_set_discriminant(&answer, {discriminant_name}_{tag_name});\n\n{INDENT}return answer;\n}}"
+        )
+        .unwrap();
+
+        add_decl(
+            &mut output.functions,
+            format!("{name}_{tag_name}"),
+            target_info,
+            buf,
+        );
+
+        if let Some(payload_id) = opt_payload_id {
+            let payload_type = type_name(*payload_id, types);
+
+            add_decl(
+                &mut output.functions,
+                format!("{name}_as_{tag_name}"),
+                target_info,
+                format!(
+                    r#"// Borrow the payload of a tag named `{tag_name}`. This asserts that the tag is in fact `{tag_name}`.
+static inline const {payload_type} *{name}_as_{tag_name}(const {name} *self) {{
+    assert({name}_discriminant(self) == {discriminant_name}_{tag_name});
+
+    return &self->{tag_name};
+}}"#
+                ),
+            );
+        }
+    }
+
+    if has_drop {
+        let cases = drop_cases(&discriminant_name, tags, "self->", 1, types);
+
+        add_decl(
+            &mut output.functions,
+            format!("{name}_drop"),
+            target_info,
+            format!(
+                r#"static inline void {name}_drop({name} *self) {{
+    switch ({name}_discriminant(self)) {{
+{cases}    default:
+        break;
+    }}
+}}"#
+            ),
+        );
+    }
+}
+
+/// Recursive tag unions, with or without a nullable tag, are a pointer to a
+/// refcounted allocation holding the payload. Their discriminant is stored
+/// in the unused bits at the end of that pointer.
+fn add_recursive_tag_union(
+    name: &str,
+    target_info: TargetInfo,
+    tags: &[(String, Option<TypeId>)],
+    opt_null_tag_index: Option<usize>,
+    discriminant_size: u32,
+    types: &Types,
+    output: &mut Output,
+) {
+    if tags.len() > max_pointer_tagged_variants(target_info.architecture) {
+        todo!(
+            "Support {} tags in a recursive tag union on target_info {:?}. (This is too many tags for pointer tagging to work, so we need to generate different glue.)",
+            tags.len(),
+            target_info
+        );
+    }
+
+    let name = escape_kw(name.to_string());
+    let union_name = format!("union_{name}");
+    let discriminant_name = format!("discriminant_{name}");
+    let bitmask = tagged_pointer_bitmask(target_info.architecture);
+
+    add_decl(
+        &mut output.declarations,
+        name.clone(),
+        target_info,
+        format!(
+            "typedef struct {name} {{\n{INDENT}union {union_name} *pointer;\n}} {name};\n\nstatic inline void {name}_drop({name} *self);"
+        ),
+    );
+
+    {
+        let mut buf = discriminant_decl(
+            &name,
+            tags.iter().map(|(tag_name, _)| tag_name),
+            discriminant_size.max(1),
+        );
+
+        writeln!(buf, "\n\nunion {union_name} {{").unwrap();
+
+        for (tag_name, opt_payload_id) in tags {
+            if let Some(payload_id) = opt_payload_id {
+                writeln!(buf, "{INDENT}{} {tag_name};", type_name(*payload_id, types)).unwrap();
+            }
+        }
+
+        buf.push_str("};");
+
+        add_decl(
+            &mut output.definitions,
+            union_name.clone(),
+            target_info,
+            buf,
+        );
+    }
+
+    add_decl(
+        &mut output.functions,
+        format!("{name}_union_pointer"),
+        target_info,
+        format!(
+            r#"// Internal helper
+static inline union {union_name} *{name}_union_pointer({name} self) {{
+    // The discriminant is stored in the unused bits at the end of the pointer
+    return (union {union_name} *)((uintptr_t)self.pointer & ~(uintptr_t){bitmask:#x});
+}}"#
+        ),
+    );
+
+    {
+        let null_check = match opt_null_tag_index {
+            Some(index) => format!(
+                "{INDENT}if (self.pointer == NULL) {{\n{INDENT}{INDENT}return {discriminant_name}_{};\n{INDENT}}}\n\n",
+                tags[index].0
+            ),
+            None => String::new(),
+        };
+
+        add_decl(
+            &mut output.functions,
+            format!("{name}_discriminant"),
+            target_info,
+            format!(
+                r#"{DISCRIMINANT_COMMENT}
+static inline {discriminant_name} {name}_discriminant({name} self) {{
+{null_check}    // The discriminant is stored in the unused bits at the end of the pointer
+    return ({discriminant_name})((uintptr_t)self.pointer & {bitmask:#x});
+}}"#
+            ),
+        );
+    }
+
+    for (index, (tag_name, opt_payload_id)) in tags.iter().enumerate() {
+        let (params, payload_expr) = constructor_params(*opt_payload_id, types);
+        let body = if opt_null_tag_index == Some(index) {
+            format!("{INDENT}return ({name}){{ NULL }};")
+        } else {
+            let assign_payload = match payload_expr {
+                Some(payload_expr) => format!("\n{INDENT}pointer->{tag_name} = {payload_expr};\n"),
+                None => String::new(),
+            };
+
+            format!(
+                r#"    union {union_name} *pointer = roc_alloc_refcounted(sizeof(union {union_name}), _Alignof(union {union_name}));
+{assign_payload}
+    // The discriminant is stored in the unused bits at the end of the pointer
+    return ({name}){{ (union {union_name} *)((uintptr_t)pointer | {discriminant_name}_{tag_name}) }};"#
+            )
+        };
+
+        add_decl(
+            &mut output.functions,
+            format!("{name}_{tag_name}"),
+            target_info,
+            format!(
+                "// Construct a tag named `{tag_name}`. This takes ownership of the payload.\nstatic inline {name} {name}_{tag_name}({params}) {{\n{body}\n}}"
+            ),
+        );
+
+        if let Some(payload_id) = opt_payload_id {
+            let payload_type = type_name(*payload_id, types);
+
+            add_decl(
+                &mut output.functions,
+                format!("{name}_as_{tag_name}"),
+                target_info,
+                format!(
+                    r#"// Borrow the payload of a tag named `{tag_name}`. This asserts that the tag is in fact `{tag_name}`.
+static inline const {payload_type} *{name}_as_{tag_name}({name} self) {{
+    assert({name}_discriminant(self) == {discriminant_name}_{tag_name});
+
+    return &{name}_union_pointer(self)->{tag_name};
+}}"#
+                ),
+            );
+        }
+    }
+
+    add_decl(
+        &mut output.functions,
+        format!("{name}_incref"),
+        target_info,
+        format!(
+            r#"static inline void {name}_incref({name} self) {{
+    roc_incref({name}_union_pointer(self));
+}}"#
+        ),
+    );
+
+    {
+        let cases = drop_cases(&discriminant_name, tags, "pointer->", 2, types);
+
+        add_decl(
+            &mut output.functions,
+            format!("{name}_drop"),
+            target_info,
+            format!(
+                r#"static inline void {name}_drop({name} *self) {{
+    union {union_name} *pointer = {name}_union_pointer(*self);
+
+    if (roc_decref_is_last(pointer)) {{
+        switch ({name}_discriminant(*self)) {{
+{cases}        default:
+            break;
+        }}
+
+        roc_free_refcounted(pointer, _Alignof(union {union_name}));
+    }}
+}}"#
+            ),
+        );
+    }
+}
+
+/// Unwrapped tag unions have a single non-null tag, so they are a pointer
+/// straight to that tag's payload. If there's a null tag, it's NULL.
+fn add_unwrapped_tag_union(
+    name: &str,
+    target_info: TargetInfo,
+    opt_null_tag: Option<&String>,
+    non_null_tag: &str,
+    payload_id: TypeId,
+    types: &Types,
+    output: &mut Output,
+) {
+    let name = escape_kw(name.to_string());
+    let payload_type = type_name(payload_id, types);
+
+    add_decl(
+        &mut output.declarations,
+        name.clone(),
+        target_info,
+        format!(
+            "typedef struct {name} {{\n{INDENT}{payload_type} *pointer;\n}} {name};\n\nstatic inline void {name}_drop({name} *self);"
+        ),
+    );
+
+    if let Some(null_tag) = opt_null_tag {
+        let discriminant_name = format!("discriminant_{name}");
+        let mut tag_names = [null_tag.as_str(), non_null_tag];
+
+        // The discriminant's tags are in alphabetical order
+        tag_names.sort_unstable();
+
+        add_decl(
+            &mut output.definitions,
+            discriminant_name.clone(),
+            target_info,
+            discriminant_decl(&name, tag_names.iter(), 1),
+        );
+
+        add_decl(
+            &mut output.functions,
+            format!("{name}_discriminant"),
+            target_info,
+            format!(
+                r#"{DISCRIMINANT_COMMENT}
+static inline {discriminant_name} {name}_discriminant({name} self) {{
+    if (self.pointer == NULL) {{
+        return {discriminant_name}_{null_tag};
+    }} else {{
+        return {discriminant_name}_{non_null_tag};
+    }}
+}}"#
+            ),
+        );
+
+        add_decl(
+            &mut output.functions,
+            format!("{name}_{null_tag}"),
+            target_info,
+            format!(
+                r#"// Construct a tag named `{null_tag}`.
+static inline {name} {name}_{null_tag}(void) {{
+    return ({name}){{ NULL }};
+}}"#
+            ),
+        );
+    }
+
+    {
+        let (params, payload_expr) = constructor_params(Some(payload_id), types);
+        let payload_expr = payload_expr.unwrap_or_default();
+
+        add_decl(
+            &mut output.functions,
+            format!("{name}_{non_null_tag}"),
+            target_info,
+            format!(
+                r#"// Construct a tag named `{non_null_tag}`. This takes ownership of the payload.
+static inline {name} {name}_{non_null_tag}({params}) {{
+    {payload_type} *pointer = roc_alloc_refcounted(sizeof({payload_type}), _Alignof({payload_type}));
+
+    *pointer = {payload_expr};
+
+    return ({name}){{ pointer }};
+}}"#
+            ),
+        );
+    }
+
+    add_decl(
+        &mut output.functions,
+        format!("{name}_as_{non_null_tag}"),
+        target_info,
+        format!(
+            r#"// Borrow the payload of a tag named `{non_null_tag}`. This asserts that the tag is in fact `{non_null_tag}`.
+static inline const {payload_type} *{name}_as_{non_null_tag}({name} self) {{
+    assert(self.pointer != NULL);
+
+    return self.pointer;
+}}"#
+        ),
+    );
+
+    add_decl(
+        &mut output.functions,
+        format!("{name}_incref"),
+        target_info,
+        format!(
+            r#"static inline void {name}_incref({name} self) {{
+    roc_incref(self.pointer);
+}}"#
+        ),
+    );
+
+    {
+        let mut drop_payload = String::new();
+
+        write_drop(&mut drop_payload, 2, "(*self->pointer)", payload_id, types);
+
+        add_decl(
+            &mut output.functions,
+            format!("{name}_drop"),
+            target_info,
+            format!(
+                r#"static inline void {name}_drop({name} *self) {{
+    if (roc_decref_is_last(self->pointer)) {{
+{drop_payload}        roc_free_refcounted(self->pointer, _Alignof({payload_type}));
+    }}
+}}"#
+            ),
+        );
+    }
+}
+
+/// Single-tag unions are stored as structs, since they have only one
+/// alternative. However, they still offer the usual tag union functions.
+fn add_single_tag_struct(
+    name: &str,
+    target_info: TargetInfo,
+    tag_name: &str,
+    payload_fields: &[TypeId],
+    types: &Types,
+    output: &mut Output,
+) {
+    let name = escape_kw(name.to_string());
+    let fields = payload_fields
+        .iter()
+        .enumerate()
+        .map(|(index, field_id)| (format!("f{index}"), *field_id))
+        .collect::<Vec<_>>();
+
+    add_struct(&name, target_info, &fields, types, output);
+
+    let fields = sized_fields(&fields, types);
+
+    let (params, body) = if fields.is_empty() {
+        // C doesn't allow empty initializers, so zero out the (empty) struct instead.
+        (
+            "void".to_string(),
+            format!("{name} answer;\n\n{INDENT}memset(&answer, 0, sizeof(answer));\n\n{INDENT}return answer;"),
+        )
+    } else {
+        let params = fields
+            .iter()
+            .map(|(label, field_id)| format!("{} {label}", type_name(*field_id, types)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let inits = fields
+            .iter()
+            .map(|(label, _)| format!(".{label} = {label}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        (params, format!("return ({name}){{ {inits} }};"))
+    };
+
+    add_decl(
+        &mut output.functions,
+        format!("{name}_{tag_name}"),
+        target_info,
+        format!(
+            r#"// Construct a tag named `{tag_name}`. This takes ownership of the payload.
+static inline {name} {name}_{tag_name}({params}) {{
+    {body}
+}}"#
+        ),
+    );
+}
+
+/// The fields which take up space. C has no zero-sized types, so zero-sized fields (such as
+/// empty records) are left out of structs, which keeps their layout the same as Roc's.
+fn sized_fields<'a>(fields: &'a [(String, TypeId)], types: &Types) -> Vec<&'a (String, TypeId)> {
+    fields
+        .iter()
+        .filter(|(_, field_id)| types.size_ignoring_alignment(*field_id) > 0)
+        .collect()
+}
+
+/// e.g. "typedef struct Foo Foo;" - along with a prototype for its drop
+/// function, in case it's needed before its definition.
+fn forward_decl(keyword: &str, name: &str, has_drop: bool) -> String {
+    let mut buf = format!("typedef {keyword} {name} {name};");
+
+    if has_drop {
+        write!(buf, "\n\nstatic inline void {name}_drop({name} *self);").unwrap();
+    }
+
+    buf
+}
+
+/// e.g. "typedef uint8_t discriminant_Foo;" followed by an enum of the tags' values
+fn discriminant_decl<I, S>(name: &str, tag_names: I, discriminant_size: u32) -> String
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    let discriminant_name = format!("discriminant_{name}");
+    let mut buf = format!(
+        "typedef uint{}_t {discriminant_name};\n\nenum {{\n",
+        discriminant_size * 8
+    );
+
+    for (index, tag_name) in tag_names.enumerate() {
+        writeln!(
+            buf,
+            "{INDENT}{discriminant_name}_{} = {index},",
+            tag_name.as_ref()
+        )
+        .unwrap();
+    }
+
+    buf.push_str("};");
+
+    buf
+}
+
+/// The parameter list for a tag's constructor, along with the expression which
+/// builds its payload out of those parameters. Payload records are flattened,
+/// so that e.g. `Foo_Bar(f0, f1)` doesn't need to be given a struct.
+fn constructor_params(opt_payload_id: Option<TypeId>, types: &Types) -> (String, Option<String>) {
+    let payload_id = match opt_payload_id {
+        Some(payload_id) => payload_id,
+        None => return ("void".to_string(), None),
+    };
+
+    match types.get_type(payload_id) {
+        RocType::TagUnionPayload { fields, .. } => {
+            let mut fields = fields
+                .iter()
+                .filter(|(_, field_id)| types.size_ignoring_alignment(*field_id) > 0)
+                .collect::<Vec<_>>();
+
+            fields.sort_by_key(|(index, _)| *index);
+
+            let params = fields
+                .iter()
+                .map(|(index, field_id)| format!("{} f{index}", type_name(*field_id, types)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let inits = fields
+                .iter()
+                .map(|(index, _)| format!(".f{index} = f{index}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let params = if params.is_empty() {
+                "void".to_string()
+            } else {
+                params
+            };
+            // C doesn't allow empty initializers; this sets the placeholder of an empty struct.
+            let inits = if inits.is_empty() {
+                "0".to_string()
+            } else {
+                inits
+            };
+
+            (
+                params,
+                Some(format!("({}){{ {inits} }}", type_name(payload_id, types))),
+            )
+        }
+        _ => (
+            format!("{} payload", type_name(payload_id, types)),
+            Some("payload".to_string()),
+        ),
+    }
+}
+
+/// The `case` arms of a drop function's switch, for each tag whose payload has refcounts.
+fn drop_cases(
+    discriminant_name: &str,
+    tags: &[(String, Option<TypeId>)],
+    prefix: &str,
+    indents: usize,
+    types: &Types,
+) -> String {
+    let mut buf = String::new();
+
+    for (tag_name, opt_payload_id) in tags {
+        if let Some(payload_id) = opt_payload_id {
+            if has_refcount(types.get_type(*payload_id), types) {
+                write_line(
+                    &mut buf,
+                    indents,
+                    &format!("case {discriminant_name}_{tag_name}:"),
+                );
+                write_drop(
+                    &mut buf,
+                    indents + 1,
+                    &format!("{prefix}{tag_name}"),
+                    *payload_id,
+                    types,
+                );
+                write_line(&mut buf, indents + 1, "break;");
+            }
+        }
+    }
+
+    buf
+}
+
+/// Write the statements which give up the references held by the value at `place`.
+fn write_drop(buf: &mut String, indents: usize, place: &str, id: TypeId, types: &Types) {
+    let typ = types.get_type(id);
+
+    if !has_refcount(typ, types) {
+        return;
+    }
+
+    match typ {
+        RocType::RocStr => {
+            write_line(buf, indents, &format!("roc_str_drop(&{place});"));
+        }
+        RocType::RocList(elem_id) => {
+            write_elements_drop(
+                buf,
+                indents,
+                &format!("{place}.elements"),
+                &format!("{place}.length"),
+                &[(0, *elem_id)],
+                types.size_rounded_to_alignment(*elem_id),
+                types.align(*elem_id),
+                types,
+            );
+        }
+        RocType::RocDict(key_id, val_id) => {
+            write_dict_drop(buf, indents, place, *key_id, Some(*val_id), types);
+        }
+        RocType::RocSet(elem_id) => {
+            write_dict_drop(buf, indents, place, *elem_id, None, types);
+        }
+        RocType::RocBox(elem_id) => {
+            let elem_type = type_name(*elem_id, types);
+
+            write_line(
+                buf,
+                indents,
+                &format!("if (roc_decref_is_last({place}.pointer)) {{"),
+            );
+            write_drop(
+                buf,
+                indents + 1,
+                &format!("(*({elem_type} *){place}.pointer)"),
+                *elem_id,
+                types,
+            );
+            write_line(
+                buf,
+                indents + 1,
+                &format!(
+                    "roc_free_refcounted({place}.pointer, {});",
+                    types.align(*elem_id)
+                ),
+            );
+            write_line(buf, indents, "}");
+        }
+        RocType::RecursivePointer(content_id) => {
+            write_line(
+                buf,
+                indents,
+                &format!("{}_drop(&{place});", type_name(*content_id, types)),
+            );
+        }
+        _ => {
+            write_line(
+                buf,
+                indents,
+                &format!("{}_drop(&{place});", type_name(id, types)),
+            );
+        }
+    }
+}
+
+/// Dicts (and Sets, which are Dicts with unit values) store a list of bucket
+/// indices, plus a list of their entries.
+fn write_dict_drop(
+    buf: &mut String,
+    indents: usize,
+    place: &str,
+    key_id: TypeId,
+    opt_val_id: Option<TypeId>,
+    types: &Types,
+) {
+    let ptr_size = types.target().ptr_size() as u32;
+
+    write_elements_drop(
+        buf,
+        indents,
+        &format!("{place}.buckets.elements"),
+        &format!("{place}.buckets.length"),
+        &[],
+        ptr_size,
+        ptr_size,
+        types,
+    );
+
+    // Entries are laid out like a record of the key and value, so the more
+    // aligned of the two comes first. (If they're tied, the key comes first.)
+    let mut entry_fields = vec![key_id];
+
+    entry_fields.extend(opt_val_id);
+    entry_fields.sort_by(|a, b| types.align(*b).cmp(&types.align(*a)));
+
+    let mut offset = 0;
+    let mut entry_align = 1;
+    let mut fields = Vec::with_capacity(entry_fields.len());
+
+    for field_id in entry_fields {
+        let align = types.align(field_id);
+
+        offset = round_up_to_alignment(offset, align);
+        fields.push((offset, field_id));
+        offset += types.size_rounded_to_alignment(field_id);
+        entry_align = entry_align.max(align);
+    }
+
+    write_elements_drop(
+        buf,
+        indents,
+        &format!("{place}.data.elements"),
+        &format!("{place}.data.length"),
+        &fields,
+        round_up_to_alignment(offset, entry_align),
+        entry_align,
+        types,
+    );
+}
+
+/// Give up a reference to a refcounted array of elements. If it was the
+/// last one, drop each element's fields (at the given byte offsets) and
+/// then free the array.
+#[allow(clippy::too_many_arguments)]
+fn write_elements_drop(
+    buf: &mut String,
+    indents: usize,
+    elements: &str,
+    length: &str,
+    fields: &[(u32, TypeId)],
+    stride: u32,
+    alignment: u32,
+    types: &Types,
+) {
+    write_line(
+        buf,
+        indents,
+        &format!("if (roc_decref_is_last({elements})) {{"),
+    );
+
+    let fields_to_drop: Vec<_> = fields
+        .iter()
+        .filter(|(_, field_id)| has_refcount(types.get_type(*field_id), types))
+        .collect();
+
+    if !fields_to_drop.is_empty() {
+        // Nested loops need distinct names for their counters
+        let counter = format!("i{indents}");
+
+        write_line(
+            buf,
+            indents + 1,
+            &format!("for (size_t {counter} = 0; {counter} < {length}; {counter}++) {{"),
+        );
+
+        for (offset, field_id) in fields_to_drop {
+            let field_type = type_name(*field_id, types);
+            let field_place = format!(
+                "(*({field_type} *)((char *){elements} + {counter} * {stride} + {offset}))"
+            );
+
+            write_drop(buf, indents + 2, &field_place, *field_id, types);
+        }
+
+        write_line(buf, indents + 1, "}");
+    }
+
+    write_line(
+        buf,
+        indents + 1,
+        &format!("roc_free_refcounted({elements}, {alignment});"),
+    );
+    write_line(buf, indents, "}");
+}
+
+fn write_line(buf: &mut String, indents: usize, line: &str) {
+    for _ in 0..indents {
+        buf.push_str(INDENT);
+    }
+
+    buf.push_str(line);
+    buf.push('\n');
+}
+
+/// Whether values of this type hold any reference counts, and so need to be dropped.
+pub(crate) fn has_refcount(typ: &RocType, types: &Types) -> bool {
+    match typ {
+        RocType::Unit
+        | RocType::EmptyTagUnion
+        | RocType::Bool
+        | RocType::Num(_)
+        | RocType::TagUnion(RocTagUnion::Enumeration { .. })
+        | RocType::Function { .. } => false,
+        RocType::RocStr
+        | RocType::RocList(_)
+        | RocType::RocDict(_, _)
+        | RocType::RocSet(_)
+        | RocType::RocBox(_)
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped { .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { .. })
+        | RocType::TagUnion(RocTagUnion::Recursive { .. })
+        | RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { .. })
+        | RocType::RecursivePointer { .. } => true,
+        RocType::TagUnion(RocTagUnion::SingleTagStruct { payload_fields, .. }) => payload_fields
+            .iter()
+            .any(|id| has_refcount(types.get_type(*id), types)),
+        RocType::TagUnion(RocTagUnion::NonRecursive { tags, .. }) => {
+            tags.iter().any(|(_, payloads)| {
+                payloads
+                    .iter()
+                    .any(|id| has_refcount(types.get_type(*id), types))
+            })
+        }
+        RocType::RocResult(ok_id, err_id) => {
+            has_refcount(types.get_type(*ok_id), types)
+                || has_refcount(types.get_type(*err_id), types)
+        }
+        RocType::Struct { fields, .. } => fields
+            .iter()
+            .any(|(_, id)| has_refcount(types.get_type(*id), types)),
+        RocType::TagUnionPayload { fields, .. } => fields
+            .iter()
+            .any(|(_, id)| has_refcount(types.get_type(*id), types)),
+    }
+}
+
+fn type_name(id: TypeId, types: &Types) -> String {
+    match types.get_type(id) {
+        RocType::Unit | RocType::EmptyTagUnion => "RocUnit".to_string(),
+        RocType::RocStr => "RocStr".to_string(),
+        RocType::Bool => "bool".to_string(),
+        RocType::Num(num) => num_type_name(*num).to_string(),
+        RocType::RocList(_) => "RocList".to_string(),
+        RocType::RocDict(_, _) => "RocDict".to_string(),
+        RocType::RocSet(_) => "RocSet".to_string(),
+        RocType::RocBox(_) => "RocBox".to_string(),
+        RocType::RocResult(ok_id, err_id) => format!(
+            "RocResult_{}_{}",
+            mangled_name(*ok_id, types),
+            mangled_name(*err_id, types)
+        ),
+        RocType::Struct { name, .. }
+        | RocType::TagUnionPayload { name, .. }
+        | RocType::TagUnion(RocTagUnion::NonRecursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Recursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Enumeration { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::SingleTagStruct { name, .. })
+        | RocType::Function { name, .. } => escape_kw(name.clone()),
+        RocType::RecursivePointer(content) => type_name(*content, types),
+    }
+}
+
+/// C has no generics, so a Result's name has to spell out its type arguments -
+/// including the element types of collections, since those affect how it's dropped.
+fn mangled_name(id: TypeId, types: &Types) -> String {
+    match types.get_type(id) {
+        RocType::Num(num) => format!("{num:?}"),
+        RocType::RocList(elem_id) => format!("RocList_{}", mangled_name(*elem_id, types)),
+        RocType::RocSet(elem_id) => format!("RocSet_{}", mangled_name(*elem_id, types)),
+        RocType::RocBox(elem_id) => format!("RocBox_{}", mangled_name(*elem_id, types)),
+        RocType::RocDict(key_id, val_id) => format!(
+            "RocDict_{}_{}",
+            mangled_name(*key_id, types),
+            mangled_name(*val_id, types)
+        ),
+        _ => type_name(id, types),
+    }
+}
+
+fn num_type_name(num: RocNum) -> &'static str {
+    match num {
+        RocNum::I8 => "int8_t",
+        RocNum::U8 => "uint8_t",
+        RocNum::I16 => "int16_t",
+        RocNum::U16 => "uint16_t",
+        RocNum::I32 => "int32_t",
+        RocNum::U32 => "uint32_t",
+        RocNum::I64 => "int64_t",
+        RocNum::U64 => "uint64_t",
+        RocNum::I128 => "RocI128",
+        RocNum::U128 => "RocU128",
+        RocNum::F32 => "float",
+        RocNum::F64 => "double",
+        RocNum::F128 => "long double",
+        RocNum::Dec => "RocDec",
+    }
+}
+
+/// The macro which C compilers predefine when targeting this architecture
+fn arch_macro(architecture: Architecture) -> &'static str {
+    match architecture {
+        Architecture::X86_64 => "__x86_64__",
+        Architecture::X86_32 => "__i386__",
+        Architecture::Aarch64 => "__aarch64__",
+        Architecture::Aarch32 => "__arm__",
+        Architecture::Wasm32 => "__wasm32__",
+    }
+}
+
+// Based on https://en.cppreference.com/w/c/keyword
+const RESERVED_KEYWORDS: &[&str] = &[
+    "auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else",
+    "enum", "extern", "float", "for", "goto", "if", "inline", "int", "long", "register",
+    "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch", "typedef",
+    "union", "unsigned", "void", "volatile", "while", "bool", "true", "false",
+];
+
+/// Escape a C reserved keyword, if necessary.
+fn escape_kw(input: String) -> String {
+    if RESERVED_KEYWORDS.contains(&input.as_str()) {
+        // C has no raw identifiers, so add an underscore after it instead.
+        format!("{input}_")
+    } else {
+        input
+    }
+}
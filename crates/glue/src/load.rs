@@ -1,9 +1,12 @@
-use crate::rust_glue;
 use crate::types::{Env, Types};
+use crate::{c_glue, rust_glue, zig_glue};
 use bumpalo::Bump;
-use roc_load::{ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
+use roc_load::{
+    ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, OptLevel, PropertyTesting, Threading,
+};
 use roc_reporting::report::RenderTarget;
 use roc_target::{Architecture, OperatingSystem, TargetInfo};
+use std::ffi::OsStr;
 use std::fs::File;
 use std::io::{self, ErrorKind, Write};
 use std::path::{Path, PathBuf};
@@ -11,7 +14,55 @@ use std::process;
 use strum::IntoEnumIterator;
 use target_lexicon::Triple;
 
+/// The language to generate glue code in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    C,
+    Zig,
+}
+
+impl Language {
+    /// Picks the language based on the output file's extension, e.g. "glue.zig" means Zig.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(OsStr::to_str) {
+            Some("rs") => Some(Language::Rust),
+            Some("h") => Some(Language::C),
+            Some("zig") => Some(Language::Zig),
+            _ => None,
+        }
+    }
+
+    pub fn header(&self) -> &'static [u8] {
+        match self {
+            Language::Rust => rust_glue::HEADER,
+            Language::C => c_glue::HEADER,
+            Language::Zig => zig_glue::HEADER,
+        }
+    }
+
+    pub fn emit(&self, types_and_targets: &[(Types, TargetInfo)]) -> String {
+        match self {
+            Language::Rust => rust_glue::emit(types_and_targets),
+            Language::C => c_glue::emit(types_and_targets),
+            Language::Zig => zig_glue::emit(types_and_targets),
+        }
+    }
+}
+
 pub fn generate(input_path: &Path, output_path: &Path) -> io::Result<i32> {
+    let language = match Language::from_path(output_path) {
+        Some(language) => language,
+        None => {
+            eprintln!(
+                "`roc glue` picks the language to generate based on the output file's extension, but it doesn't know what language to use for {}. The supported extensions are .rs (Rust), .h (C), and .zig (Zig).",
+                output_path.display()
+            );
+
+            return Ok(1);
+        }
+    };
+
     match load_types(input_path.to_path_buf(), Threading::AllAvailable) {
         Ok(types_and_targets) => {
             let mut file = File::create(output_path).unwrap_or_else(|err| {
@@ -24,8 +75,8 @@ pub fn generate(input_path: &Path, output_path: &Path) -> io::Result<i32> {
                 process::exit(1);
             });
 
-            let mut buf = std::str::from_utf8(rust_glue::HEADER).unwrap().to_string();
-            let body = rust_glue::emit(&types_and_targets);
+            let mut buf = std::str::from_utf8(language.header()).unwrap().to_string();
+            let body = language.emit(&types_and_targets);
 
             buf.push_str(&body);
 
@@ -88,10 +139,12 @@ pub fn load_types(
             render: RenderTarget::Generic,
             threading,
             exec_mode: ExecutionMode::Check,
+            property_testing: PropertyTesting::default(),
+            opt_level: OptLevel::Normal,
         },
     )
     .unwrap_or_else(|problem| match problem {
-        LoadingProblem::FormattedReport(report) => {
+        LoadingProblem::FormattedReport(report, _) => {
             eprintln!("{}", report);
 
             process::exit(1);
@@ -2133,7 +2133,7 @@ fn write_indents(indentations: usize, buf: &mut String) {
     }
 }
 
-fn max_pointer_tagged_variants(architecture: Architecture) -> usize {
+pub(crate) fn max_pointer_tagged_variants(architecture: Architecture) -> usize {
     match architecture {
         // On a 64-bit system, pointers have 3 bits that are unused, so return 2^3 = 8
         Architecture::X86_64 | Architecture::Aarch64 => 8,
@@ -2143,7 +2143,7 @@ fn max_pointer_tagged_variants(architecture: Architecture) -> usize {
 }
 
 #[inline(always)]
-fn tagged_pointer_bitmask(architecture: Architecture) -> u8 {
+pub(crate) fn tagged_pointer_bitmask(architecture: Architecture) -> u8 {
     match architecture {
         // On a 64-bit system, pointers have 3 bits that are unused
         Architecture::X86_64 | Architecture::Aarch64 => 0b0000_0111,
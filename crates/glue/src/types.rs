@@ -18,7 +18,7 @@ use roc_mono::layout::{
 use roc_target::TargetInfo;
 use roc_types::{
     subs::{Content, FlatType, GetSubsSlice, Subs, UnionLabels, UnionTags, Variable},
-    types::{AliasKind, RecordField},
+    types::{gather_tuple_elems, RecordField, TupleStructure},
 };
 use std::fmt::Display;
 
@@ -394,6 +394,10 @@ impl Types {
         }
     }
 
+    pub fn target(&self) -> TargetInfo {
+        self.target
+    }
+
     pub fn replace(&mut self, id: TypeId, typ: RocType) {
         debug_assert!(self.types.get(id.0).is_some());
 
@@ -794,7 +798,35 @@ fn add_type_help<'a>(
             todo!()
         }
         Content::Structure(FlatType::Erroneous(_)) => todo!(),
-        Content::Structure(FlatType::EmptyRecord) => types.add_anonymous(RocType::Unit, layout),
+        Content::Structure(FlatType::Tuple(elems, ext)) => {
+            let TupleStructure { elems, .. } =
+                gather_tuple_elems(subs, *elems, *ext).expect("something weird in content");
+
+            let name = match opt_name {
+                Some(sym) => sym.as_str(env.interns).to_string(),
+                None => env.struct_names.get_name(var),
+            };
+
+            // Sort the elements by their positions, like the layout does, and only then name
+            // them; "f10" would sort before "f2".
+            add_struct(
+                env,
+                name,
+                elems.into_iter().enumerate(),
+                types,
+                layout,
+                |name, fields| RocType::Struct {
+                    name,
+                    fields: fields
+                        .into_iter()
+                        .map(|(index, type_id)| (format!("f{}", index), type_id))
+                        .collect(),
+                },
+            )
+        }
+        Content::Structure(FlatType::EmptyRecord | FlatType::EmptyTuple) => {
+            types.add_anonymous(RocType::Unit, layout)
+        }
         Content::Structure(FlatType::EmptyTagUnion) => {
             types.add_anonymous(RocType::EmptyTagUnion, layout)
         }
@@ -821,6 +853,46 @@ fn add_type_help<'a>(
 
                         types.add_anonymous(RocType::Bool, layout)
                     }
+                    Layout::Struct { .. } if *name == Symbol::DICT_DICT => {
+                        let type_vars = env.subs.get_subs_slice(alias_vars.type_variables());
+
+                        debug_assert_eq!(type_vars.len(), 2);
+
+                        let key_var = type_vars[0];
+                        let key_layout =
+                            env.layout_cache.from_var(env.arena, key_var, subs).unwrap();
+                        let key_id = add_type_help(env, key_layout, key_var, None, types);
+
+                        let val_var = type_vars[1];
+                        let val_layout =
+                            env.layout_cache.from_var(env.arena, val_var, subs).unwrap();
+                        let val_id = add_type_help(env, val_layout, val_var, None, types);
+
+                        let dict_id = types.add_anonymous(RocType::RocDict(key_id, val_id), layout);
+
+                        types.depends(dict_id, key_id);
+                        types.depends(dict_id, val_id);
+
+                        dict_id
+                    }
+                    Layout::Struct { .. } if *name == Symbol::SET_SET => {
+                        let type_vars = env.subs.get_subs_slice(alias_vars.type_variables());
+
+                        debug_assert_eq!(type_vars.len(), 1);
+
+                        let elem_var = type_vars[0];
+                        let elem_layout = env
+                            .layout_cache
+                            .from_var(env.arena, elem_var, subs)
+                            .unwrap();
+                        let elem_id = add_type_help(env, elem_layout, elem_var, None, types);
+
+                        let set_id = types.add_anonymous(RocType::RocSet(elem_id), layout);
+
+                        types.depends(set_id, elem_id);
+
+                        set_id
+                    }
                     Layout::Union(union_layout) if *name == Symbol::RESULT_RESULT => {
                         match union_layout {
                             UnionLayout::NonRecursive(tags) => {
@@ -934,91 +1006,6 @@ fn add_builtin_type<'a>(
 
             list_id
         }
-        (
-            Builtin::List(elem_layout),
-            Alias(Symbol::DICT_DICT, _alias_variables, alias_var, AliasKind::Opaque),
-        ) => {
-            match (
-                elem_layout,
-                env.subs.get_content_without_compacting(*alias_var),
-            ) {
-                (
-                    Layout::Struct { field_layouts, .. },
-                    Content::Structure(FlatType::Apply(Symbol::LIST_LIST, args_subs_slice)),
-                ) => {
-                    let (key_var, val_var) = {
-                        let args_tuple = env.subs.get_subs_slice(*args_subs_slice);
-
-                        debug_assert_eq!(args_tuple.len(), 1);
-
-                        match env.subs.get_content_without_compacting(args_tuple[0]) {
-                            Content::Structure(FlatType::TagUnion(union_tags, ext_var)) => {
-                                let (mut iter, _) = union_tags.sorted_iterator_and_ext(env.subs, *ext_var);
-                                let payloads = iter.next().unwrap().1;
-
-                                debug_assert_eq!(iter.next(), None);
-
-                                (payloads[0], payloads[1])
-                            }
-                            _ => {
-                                unreachable!()
-                            }
-                        }
-                    };
-
-                    debug_assert_eq!(field_layouts.len(), 2);
-
-                    let key_id = add_type_help(env, field_layouts[0], key_var, opt_name, types);
-                    let val_id = add_type_help(env, field_layouts[1], val_var, opt_name, types);
-                    let dict_id = types.add_anonymous(RocType::RocDict(key_id, val_id), layout);
-
-                    types.depends(dict_id, key_id);
-                    types.depends(dict_id, val_id);
-
-                    dict_id
-                }
-                (elem_layout, alias_content) => unreachable!(
-                    "Unrecognized List element for Dict. Layout was: {:?} and alias_content was: {:?}",
-                    elem_layout,
-                    alias_content
-                ),
-            }
-        }
-        (
-            Builtin::List(elem_layout),
-            Alias(Symbol::SET_SET, _alias_vars, alias_var, AliasKind::Opaque),
-        ) => {
-            match (
-                elem_layout,
-                env.subs.get_content_without_compacting(*alias_var),
-            ) {
-                (
-                    Layout::Struct { field_layouts, .. },
-                    Alias(Symbol::DICT_DICT, alias_args, _alias_var, AliasKind::Opaque),
-                ) => {
-                    let dict_type_vars = env.subs.get_subs_slice(alias_args.type_variables());
-
-                    debug_assert_eq!(dict_type_vars.len(), 2);
-
-                    // Sets only use the key of the Dict they wrap, not the value
-                    let elem_var = dict_type_vars[0];
-
-                    debug_assert_eq!(field_layouts.len(), 2);
-
-                    let elem_id = add_type_help(env, field_layouts[0], elem_var, opt_name, types);
-                    let set_id = types.add_anonymous(RocType::RocSet(elem_id), layout);
-
-                    types.depends(set_id, elem_id);
-
-                    set_id
-                }
-                (elem_layout, alias_content) => unreachable!(
-                    "Unrecognized List element for Set. Layout was: {:?} and alias_content was: {:?}",
-                    elem_layout,
-                    alias_content
-                ),
-            }
-        }
         (Builtin::List(elem_layout), alias) => {
             unreachable!(
                 "The type alias {:?} was not an Apply(Symbol::LIST_LIST) as expected, given that its builtin was Builtin::List({:?})",
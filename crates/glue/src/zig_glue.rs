@@ -0,0 +1,869 @@
+use crate::c_glue::has_refcount;
+use crate::rust_glue::{max_pointer_tagged_variants, tagged_pointer_bitmask};
+use crate::types::{RocNum, RocTagUnion, RocType, TypeId, Types};
+use indexmap::IndexMap;
+use roc_target::{Architecture, TargetInfo};
+use std::fmt::Write;
+
+pub static HEADER: &[u8] = include_bytes!("../templates/header.zig");
+const INDENT: &str = "    ";
+const DISCRIMINANT_DOC_COMMENT: &str =
+    "/// Returns which variant this tag union holds. Note that this never includes a payload!";
+
+/// Zig doesn't care about declaration order, so each type is a single
+/// declaration - a container type with its functions inside it. If the
+/// container differs between targets, it's chosen with a switch on the arch.
+type Decls = IndexMap<String, IndexMap<String, Vec<TargetInfo>>>;
+
+/// Add the given type expression, along with the target it was generated for.
+fn add_decl(decls: &mut Decls, name: String, target_info: TargetInfo, body: String) {
+    let targets = decls.entry(name).or_default().entry(body).or_default();
+
+    if !targets.contains(&target_info) {
+        targets.push(target_info);
+    }
+}
+
+pub fn emit(types_and_targets: &[(Types, TargetInfo)]) -> String {
+    let mut buf = String::new();
+    let mut decls = Decls::default();
+
+    for (types, target_info) in types_and_targets {
+        for id in types.sorted_ids() {
+            add_type(*target_info, id, types, &mut decls);
+        }
+    }
+
+    for (name, bodies) in decls {
+        let is_universal = bodies.len() == 1
+            && bodies
+                .values()
+                .all(|targets| targets.len() == types_and_targets.len());
+
+        buf.push('\n');
+
+        if is_universal {
+            for body in bodies.keys() {
+                writeln!(buf, "pub const {name} = {body};").unwrap();
+            }
+        } else {
+            writeln!(buf, "pub const {name} = switch (builtin.cpu.arch) {{").unwrap();
+
+            for (body, targets) in bodies {
+                let archs = targets
+                    .iter()
+                    .map(|target_info| arch_to_str(target_info.architecture))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                // Indent the body to line up with the switch arm it's in
+                let body = body
+                    .lines()
+                    .map(|line| {
+                        if line.is_empty() {
+                            String::new()
+                        } else {
+                            format!("{INDENT}{line}")
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                writeln!(buf, "{INDENT}{archs} => {},", body.trim_start()).unwrap();
+            }
+
+            writeln!(
+                buf,
+                "{INDENT}else => @compileError(\"This glue was not generated for the target architecture.\"),\n}};"
+            )
+            .unwrap();
+        }
+    }
+
+    buf
+}
+
+fn add_type(target_info: TargetInfo, id: TypeId, types: &Types, decls: &mut Decls) {
+    match types.get_type(id) {
+        RocType::Struct { name, fields } => {
+            let fields = fields
+                .iter()
+                .map(|(label, field_id)| (escape_kw(label.clone()), *field_id))
+                .collect::<Vec<_>>();
+
+            add_decl(
+                decls,
+                escape_kw(name.clone()),
+                target_info,
+                struct_body(&fields, String::new(), types),
+            );
+        }
+        RocType::TagUnionPayload { name, fields } => {
+            let fields = fields
+                .iter()
+                .map(|(index, field_id)| (format!("f{index}"), *field_id))
+                .collect::<Vec<_>>();
+
+            add_decl(
+                decls,
+                escape_kw(name.clone()),
+                target_info,
+                struct_body(&fields, String::new(), types),
+            );
+        }
+        RocType::TagUnion(tag_union) => match tag_union {
+            RocTagUnion::Enumeration { name, tags, size } => {
+                let mut body = format!("enum(u{}) {{\n", size * 8);
+
+                for (index, tag_name) in tags.iter().enumerate() {
+                    writeln!(body, "{INDENT}{} = {index},", escape_kw(tag_name.clone())).unwrap();
+                }
+
+                body.push('}');
+
+                add_decl(decls, escape_kw(name.clone()), target_info, body);
+            }
+            RocTagUnion::NonRecursive {
+                name,
+                tags,
+                discriminant_size,
+                discriminant_offset,
+            } => {
+                // Empty tag unions can never come up at runtime,
+                // and so don't need declared types.
+                if !tags.is_empty() {
+                    add_decl(
+                        decls,
+                        escape_kw(name.clone()),
+                        target_info,
+                        nonrecursive_tag_union_body(
+                            tags,
+                            *discriminant_size,
+                            *discriminant_offset,
+                            id,
+                            types,
+                        ),
+                    );
+                }
+            }
+            RocTagUnion::Recursive {
+                name,
+                tags,
+                discriminant_size,
+                ..
+            } => {
+                if !tags.is_empty() {
+                    add_decl(
+                        decls,
+                        escape_kw(name.clone()),
+                        target_info,
+                        recursive_tag_union_body(
+                            target_info,
+                            tags,
+                            None,
+                            *discriminant_size,
+                            types,
+                        ),
+                    );
+                }
+            }
+            RocTagUnion::NullableWrapped {
+                name,
+                index_of_null_tag,
+                tags,
+                discriminant_size,
+                ..
+            } => {
+                add_decl(
+                    decls,
+                    escape_kw(name.clone()),
+                    target_info,
+                    recursive_tag_union_body(
+                        target_info,
+                        tags,
+                        Some(*index_of_null_tag as usize),
+                        *discriminant_size,
+                        types,
+                    ),
+                );
+            }
+            RocTagUnion::NonNullableUnwrapped {
+                name,
+                tag_name,
+                payload,
+            } => {
+                add_decl(
+                    decls,
+                    escape_kw(name.clone()),
+                    target_info,
+                    unwrapped_tag_union_body(None, tag_name, *payload, types),
+                );
+            }
+            RocTagUnion::NullableUnwrapped {
+                name,
+                null_tag,
+                non_null_tag,
+                non_null_payload,
+                ..
+            } => {
+                add_decl(
+                    decls,
+                    escape_kw(name.clone()),
+                    target_info,
+                    unwrapped_tag_union_body(
+                        Some(null_tag),
+                        non_null_tag,
+                        *non_null_payload,
+                        types,
+                    ),
+                );
+            }
+            RocTagUnion::SingleTagStruct {
+                name,
+                tag_name,
+                payload_fields,
+            } => {
+                // Store single-tag unions as structs rather than unions,
+                // because they have only one alternative. However, still
+                // offer the usual tag union functions.
+                let fields = payload_fields
+                    .iter()
+                    .enumerate()
+                    .map(|(index, field_id)| (format!("f{index}"), *field_id))
+                    .collect::<Vec<_>>();
+                let params = fields
+                    .iter()
+                    .map(|(label, field_id)| format!("{label}: {}", type_name(*field_id, types)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let inits = fields
+                    .iter()
+                    .map(|(label, _)| format!(".{label} = {label}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let init_fn = format!(
+                    r#"
+    /// Construct a tag named `{tag_name}`. This takes ownership of the payload.
+    pub fn init{tag_name}({params}) @This() {{
+        return .{{ {inits} }};
+    }}
+"#
+                );
+
+                add_decl(
+                    decls,
+                    escape_kw(name.clone()),
+                    target_info,
+                    struct_body(&fields, init_fn, types),
+                );
+            }
+        },
+        RocType::RocStr
+        | RocType::Bool
+        | RocType::Num(_)
+        | RocType::RocList(_)
+        | RocType::RocDict(_, _)
+        | RocType::RocSet(_)
+        | RocType::RocBox(_)
+        | RocType::RocResult(_, _)
+        | RocType::EmptyTagUnion
+        | RocType::RecursivePointer(_)
+        | RocType::Unit => {
+            // These types don't need to be declared in Zig; they're either
+            // Zig primitives or else they're defined in the header.
+        }
+        RocType::Function { .. } => {
+            // TODO generate glue for calling Roc closures from Zig
+        }
+    }
+}
+
+/// An extern struct with the given fields, followed by the given functions
+/// and a `drop` function if any of the fields need one.
+fn struct_body(fields: &[(String, TypeId)], functions: String, types: &Types) -> String {
+    let mut buf = "extern struct {\n".to_string();
+
+    for (label, field_id) in fields {
+        writeln!(buf, "{INDENT}{label}: {},", type_name(*field_id, types)).unwrap();
+    }
+
+    buf.push_str(&functions);
+
+    let fields_to_drop: Vec<_> = fields
+        .iter()
+        .filter(|(_, field_id)| has_refcount(types.get_type(*field_id), types))
+        .collect();
+
+    if !fields_to_drop.is_empty() {
+        write!(buf, "\n{INDENT}pub fn drop(self: *@This()) void {{\n").unwrap();
+
+        for (label, _) in fields_to_drop {
+            writeln!(buf, "{INDENT}{INDENT}self.{label}.drop();").unwrap();
+        }
+
+        writeln!(buf, "{INDENT}}}").unwrap();
+    }
+
+    buf.push('}');
+
+    buf
+}
+
+fn nonrecursive_tag_union_body(
+    tags: &[(String, Option<TypeId>)],
+    discriminant_size: u32,
+    discriminant_offset: u32,
+    id: TypeId,
+    types: &Types,
+) -> String {
+    let mut buf = "extern union {\n".to_string();
+
+    for (tag_name, opt_payload_id) in tags {
+        // If there's no payload, it doesn't need a field.
+        if let Some(payload_id) = opt_payload_id {
+            writeln!(
+                buf,
+                "{INDENT}{}: {},",
+                escape_kw(tag_name.clone()),
+                type_name(*payload_id, types)
+            )
+            .unwrap();
+        }
+    }
+
+    if tags.len() > 1 {
+        // The discriminant lives in the same bytes as the payloads, at
+        // discriminant_offset. This makes sure the union is big enough for it.
+        writeln!(
+            buf,
+            "{INDENT}_sizer: [{}]u8,",
+            types.size_rounded_to_alignment(id)
+        )
+        .unwrap();
+    }
+
+    buf.push_str(&discriminant_enum(
+        tags.iter().map(|(tag_name, _)| tag_name),
+        discriminant_size,
+    ));
+
+    write!(
+        buf,
+        r#"
+    {DISCRIMINANT_DOC_COMMENT}
+    pub fn discriminant(self: *const @This()) Discriminant {{
+        return @ptrCast(*align(1) const Discriminant, @ptrCast([*]const u8, self) + {discriminant_offset}).*;
+    }}
+
+    fn setDiscriminant(self: *@This(), discriminant: Discriminant) void {{
+        @ptrCast(*align(1) Discriminant, @ptrCast([*]u8, self) + {discriminant_offset}).* = discriminant;
+    }}
+"#
+    )
+    .unwrap();
+
+    for (tag_name, opt_payload_id) in tags {
+        let (params, payload_expr) = init_params(*opt_payload_id, types);
+        let field = escape_kw(tag_name.clone());
+        let assign_payload = match payload_expr {
+            Some(payload_expr) => format!("\n{INDENT}{INDENT}answer.{field} = {payload_expr};"),
+            None => String::new(),
+        };
+
+        write!(
+            buf,
+            r#"
+    /// Construct a tag named `{tag_name}`. This takes ownership of the payload.
+    pub fn init{tag_name}({params}) @This() {{
+        var answer = std.mem.zeroes(@This());
+{assign_payload}
+        answer.setDiscriminant(.{field});
+
+        return answer;
+    }}
+"#
+        )
+        .unwrap();
+
+        if let Some(payload_id) = opt_payload_id {
+            write!(
+                buf,
+                r#"
+    /// Borrow the payload of a tag named `{tag_name}`. This asserts that the tag is in fact `{tag_name}`.
+    pub fn as{tag_name}(self: *const @This()) *const {} {{
+        std.debug.assert(self.discriminant() == .{field});
+
+        return &self.{field};
+    }}
+"#,
+                type_name(*payload_id, types)
+            )
+            .unwrap();
+        }
+    }
+
+    if has_refcount(types.get_type(id), types) {
+        write!(
+            buf,
+            r#"
+    pub fn drop(self: *@This()) void {{
+        switch (self.discriminant()) {{
+{}        }}
+    }}
+"#,
+            drop_prongs(tags, "self.", types)
+        )
+        .unwrap();
+    }
+
+    buf.push('}');
+
+    buf
+}
+
+/// Recursive tag unions, with or without a nullable tag, are a pointer to a
+/// refcounted allocation holding the payload. Their discriminant is stored
+/// in the unused bits at the end of that pointer.
+fn recursive_tag_union_body(
+    target_info: TargetInfo,
+    tags: &[(String, Option<TypeId>)],
+    opt_null_tag_index: Option<usize>,
+    discriminant_size: u32,
+    types: &Types,
+) -> String {
+    if tags.len() > max_pointer_tagged_variants(target_info.architecture) {
+        todo!(
+            "Support {} tags in a recursive tag union on target_info {:?}. (This is too many tags for pointer tagging to work, so we need to generate different glue.)",
+            tags.len(),
+            target_info
+        );
+    }
+
+    let bitmask = tagged_pointer_bitmask(target_info.architecture);
+
+    // The pointer is untyped, because the discriminant in its unused bits
+    // would otherwise make it fail Zig's alignment checks.
+    let mut buf = format!("extern struct {{\n{INDENT}pointer: ?*anyopaque,\n");
+
+    buf.push_str(&discriminant_enum(
+        tags.iter().map(|(tag_name, _)| tag_name),
+        discriminant_size.max(1),
+    ));
+
+    writeln!(buf, "\n{INDENT}pub const Union = extern union {{").unwrap();
+
+    for (tag_name, opt_payload_id) in tags {
+        if let Some(payload_id) = opt_payload_id {
+            writeln!(
+                buf,
+                "{INDENT}{INDENT}{}: {},",
+                escape_kw(tag_name.clone()),
+                type_name(*payload_id, types)
+            )
+            .unwrap();
+        }
+    }
+
+    writeln!(buf, "{INDENT}}};").unwrap();
+
+    let null_check = match opt_null_tag_index {
+        Some(index) => format!(
+            "if (self.pointer == null) {{\n{INDENT}{INDENT}{INDENT}return .{};\n{INDENT}{INDENT}}}\n\n{INDENT}{INDENT}",
+            escape_kw(tags[index].0.clone())
+        ),
+        None => String::new(),
+    };
+
+    write!(
+        buf,
+        r#"
+    fn unionPointer(self: @This()) ?*Union {{
+        // The discriminant is stored in the unused bits at the end of the pointer
+        return @intToPtr(?*Union, @ptrToInt(self.pointer) & ~@as(usize, {bitmask:#x}));
+    }}
+
+    {DISCRIMINANT_DOC_COMMENT}
+    pub fn discriminant(self: @This()) Discriminant {{
+        {null_check}// The discriminant is stored in the unused bits at the end of the pointer
+        return @intToEnum(Discriminant, @truncate(u8, @ptrToInt(self.pointer) & {bitmask:#x}));
+    }}
+"#
+    )
+    .unwrap();
+
+    for (index, (tag_name, opt_payload_id)) in tags.iter().enumerate() {
+        let (params, payload_expr) = init_params(*opt_payload_id, types);
+        let field = escape_kw(tag_name.clone());
+        let body = if opt_null_tag_index == Some(index) {
+            "return .{ .pointer = null };".to_string()
+        } else {
+            let assign_payload = match payload_expr {
+                Some(payload_expr) => {
+                    format!("\n{INDENT}{INDENT}pointer.* = .{{ .{field} = {payload_expr} }};\n")
+                }
+                None => String::new(),
+            };
+
+            format!(
+                r#"const pointer = rocAllocRefcounted(Union);
+{assign_payload}
+        // The discriminant is stored in the unused bits at the end of the pointer
+        return .{{ .pointer = @intToPtr(?*anyopaque, @ptrToInt(pointer) | @enumToInt(Discriminant.{field})) }};"#
+            )
+        };
+
+        write!(
+            buf,
+            r#"
+    /// Construct a tag named `{tag_name}`. This takes ownership of the payload.
+    pub fn init{tag_name}({params}) @This() {{
+        {body}
+    }}
+"#
+        )
+        .unwrap();
+
+        if let Some(payload_id) = opt_payload_id {
+            write!(
+                buf,
+                r#"
+    /// Borrow the payload of a tag named `{tag_name}`. This asserts that the tag is in fact `{tag_name}`.
+    pub fn as{tag_name}(self: @This()) *const {} {{
+        std.debug.assert(self.discriminant() == .{field});
+
+        return &self.unionPointer().?.{field};
+    }}
+"#,
+                type_name(*payload_id, types)
+            )
+            .unwrap();
+        }
+    }
+
+    let prongs = drop_prongs(tags, "pointer.", types)
+        .lines()
+        .map(|line| format!("{INDENT}{INDENT}{line}\n"))
+        .collect::<String>();
+
+    write!(
+        buf,
+        r#"
+    pub fn incref(self: @This()) void {{
+        rocIncref(self.unionPointer());
+    }}
+
+    pub fn drop(self: *@This()) void {{
+        if (self.unionPointer()) |pointer| {{
+            if (rocDecrefIsLast(pointer)) {{
+                switch (self.discriminant()) {{
+{prongs}                }}
+
+                rocFreeRefcounted(pointer, @alignOf(Union));
+            }}
+        }}
+    }}
+}}"#
+    )
+    .unwrap();
+
+    buf
+}
+
+/// Unwrapped tag unions have a single non-null tag, so they are a pointer
+/// straight to that tag's payload. If there's a null tag, it's null.
+fn unwrapped_tag_union_body(
+    opt_null_tag: Option<&String>,
+    non_null_tag: &str,
+    payload_id: TypeId,
+    types: &Types,
+) -> String {
+    let payload_type = type_name(payload_id, types);
+    let mut buf = format!("extern struct {{\n{INDENT}pointer: ?*{payload_type},\n");
+
+    if let Some(null_tag) = opt_null_tag {
+        let mut tag_names = [null_tag.as_str(), non_null_tag];
+
+        // The discriminant's tags are in alphabetical order
+        tag_names.sort_unstable();
+
+        buf.push_str(&discriminant_enum(tag_names.iter(), 1));
+
+        write!(
+            buf,
+            r#"
+    {DISCRIMINANT_DOC_COMMENT}
+    pub fn discriminant(self: @This()) Discriminant {{
+        if (self.pointer == null) {{
+            return .{};
+        }} else {{
+            return .{};
+        }}
+    }}
+
+    /// Construct a tag named `{null_tag}`.
+    pub fn init{null_tag}() @This() {{
+        return .{{ .pointer = null }};
+    }}
+"#,
+            escape_kw(null_tag.clone()),
+            escape_kw(non_null_tag.to_string())
+        )
+        .unwrap();
+    }
+
+    let (params, payload_expr) = init_params(Some(payload_id), types);
+    let payload_expr = payload_expr.unwrap_or_default();
+
+    write!(
+        buf,
+        r#"
+    /// Construct a tag named `{non_null_tag}`. This takes ownership of the payload.
+    pub fn init{non_null_tag}({params}) @This() {{
+        const pointer = rocAllocRefcounted({payload_type});
+
+        pointer.* = {payload_expr};
+
+        return .{{ .pointer = pointer }};
+    }}
+
+    /// Borrow the payload of a tag named `{non_null_tag}`. This asserts that the tag is in fact `{non_null_tag}`.
+    pub fn as{non_null_tag}(self: @This()) *const {payload_type} {{
+        return self.pointer.?;
+    }}
+
+    pub fn incref(self: @This()) void {{
+        rocIncref(self.pointer);
+    }}
+
+    pub fn drop(self: *@This()) void {{
+        if (self.pointer) |pointer| {{
+            if (rocDecrefIsLast(pointer)) {{
+                rocDropValue({payload_type}, pointer);
+                rocFreeRefcounted(pointer, @alignOf({payload_type}));
+            }}
+        }}
+    }}
+}}"#
+    )
+    .unwrap();
+
+    buf
+}
+
+/// e.g. "pub const Discriminant = enum(u8) { Bar = 0, Foo = 1 };", nested in the tag union
+fn discriminant_enum<I, S>(tag_names: I, discriminant_size: u32) -> String
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut buf = format!(
+        "\n{INDENT}pub const Discriminant = enum(u{}) {{\n",
+        discriminant_size * 8
+    );
+
+    for (index, tag_name) in tag_names.enumerate() {
+        writeln!(
+            buf,
+            "{INDENT}{INDENT}{} = {index},",
+            escape_kw(tag_name.as_ref().to_string())
+        )
+        .unwrap();
+    }
+
+    writeln!(buf, "{INDENT}}};").unwrap();
+
+    buf
+}
+
+/// The parameter list for a tag's init function, along with the expression which
+/// builds its payload out of those parameters. Payload records are flattened,
+/// so that e.g. `Foo.initBar(f0, f1)` doesn't need to be given a struct.
+fn init_params(opt_payload_id: Option<TypeId>, types: &Types) -> (String, Option<String>) {
+    let payload_id = match opt_payload_id {
+        Some(payload_id) => payload_id,
+        None => return (String::new(), None),
+    };
+
+    match types.get_type(payload_id) {
+        RocType::TagUnionPayload { fields, .. } => {
+            let mut fields = fields.clone();
+
+            fields.sort_by_key(|(index, _)| *index);
+
+            let params = fields
+                .iter()
+                .map(|(index, field_id)| format!("f{index}: {}", type_name(*field_id, types)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let inits = fields
+                .iter()
+                .map(|(index, _)| format!(".f{index} = f{index}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            (
+                params,
+                Some(format!("{}{{ {inits} }}", type_name(payload_id, types))),
+            )
+        }
+        _ => (
+            format!("payload: {}", type_name(payload_id, types)),
+            Some("payload".to_string()),
+        ),
+    }
+}
+
+/// The switch prongs of a drop function, for each tag whose payload has refcounts.
+fn drop_prongs(tags: &[(String, Option<TypeId>)], prefix: &str, types: &Types) -> String {
+    let mut buf = String::new();
+    let mut is_exhaustive = true;
+
+    for (tag_name, opt_payload_id) in tags {
+        match opt_payload_id {
+            Some(payload_id) if has_refcount(types.get_type(*payload_id), types) => {
+                let field = escape_kw(tag_name.clone());
+
+                writeln!(
+                    buf,
+                    "{INDENT}{INDENT}{INDENT}.{field} => {prefix}{field}.drop(),"
+                )
+                .unwrap();
+            }
+            _ => {
+                is_exhaustive = false;
+            }
+        }
+    }
+
+    // Zig rejects an `else` prong which can never be reached
+    if !is_exhaustive {
+        writeln!(buf, "{INDENT}{INDENT}{INDENT}else => {{}},").unwrap();
+    }
+
+    buf
+}
+
+fn type_name(id: TypeId, types: &Types) -> String {
+    match types.get_type(id) {
+        RocType::Unit | RocType::EmptyTagUnion => "RocUnit".to_string(),
+        RocType::RocStr => "RocStr".to_string(),
+        RocType::Bool => "bool".to_string(),
+        RocType::Num(num) => num_type_name(*num).to_string(),
+        RocType::RocList(elem_id) => format!("RocList({})", type_name(*elem_id, types)),
+        RocType::RocDict(key_id, val_id) => format!(
+            "RocDict({}, {})",
+            type_name(*key_id, types),
+            type_name(*val_id, types)
+        ),
+        RocType::RocSet(elem_id) => format!("RocSet({})", type_name(*elem_id, types)),
+        RocType::RocBox(elem_id) => format!("RocBox({})", type_name(*elem_id, types)),
+        RocType::RocResult(ok_id, err_id) => format!(
+            "RocResult({}, {})",
+            type_name(*ok_id, types),
+            type_name(*err_id, types)
+        ),
+        RocType::Struct { name, .. }
+        | RocType::TagUnionPayload { name, .. }
+        | RocType::TagUnion(RocTagUnion::NonRecursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Recursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Enumeration { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::SingleTagStruct { name, .. })
+        | RocType::Function { name, .. } => escape_kw(name.clone()),
+        RocType::RecursivePointer(content) => type_name(*content, types),
+    }
+}
+
+fn num_type_name(num: RocNum) -> &'static str {
+    match num {
+        RocNum::I8 => "i8",
+        RocNum::U8 => "u8",
+        RocNum::I16 => "i16",
+        RocNum::U16 => "u16",
+        RocNum::I32 => "i32",
+        RocNum::U32 => "u32",
+        RocNum::I64 => "i64",
+        RocNum::U64 => "u64",
+        RocNum::I128 => "RocI128",
+        RocNum::U128 => "RocU128",
+        RocNum::F32 => "f32",
+        RocNum::F64 => "f64",
+        RocNum::F128 => "f128",
+        RocNum::Dec => "RocDec",
+    }
+}
+
+/// The tag of this architecture in Zig's `std.Target.Cpu.Arch`
+fn arch_to_str(architecture: Architecture) -> &'static str {
+    match architecture {
+        Architecture::X86_64 => ".x86_64",
+        Architecture::X86_32 => ".i386",
+        Architecture::Aarch64 => ".aarch64",
+        Architecture::Aarch32 => ".arm",
+        Architecture::Wasm32 => ".wasm32",
+    }
+}
+
+// Based on https://ziglang.org/documentation/0.9.1/#Keyword-Reference
+const RESERVED_KEYWORDS: &[&str] = &[
+    "addrspace",
+    "align",
+    "allowzero",
+    "and",
+    "anyframe",
+    "anytype",
+    "asm",
+    "async",
+    "await",
+    "break",
+    "callconv",
+    "catch",
+    "comptime",
+    "const",
+    "continue",
+    "defer",
+    "else",
+    "enum",
+    "errdefer",
+    "error",
+    "export",
+    "extern",
+    "fn",
+    "for",
+    "if",
+    "inline",
+    "linksection",
+    "noalias",
+    "noinline",
+    "nosuspend",
+    "opaque",
+    "or",
+    "orelse",
+    "packed",
+    "pub",
+    "resume",
+    "return",
+    "struct",
+    "suspend",
+    "switch",
+    "test",
+    "threadlocal",
+    "try",
+    "union",
+    "unreachable",
+    "usingnamespace",
+    "var",
+    "volatile",
+    "while",
+];
+
+/// Escape a Zig reserved keyword, if necessary.
+fn escape_kw(input: String) -> String {
+    if RESERVED_KEYWORDS.contains(&input.as_str()) {
+        format!("@\"{input}\"")
+    } else {
+        input
+    }
+}
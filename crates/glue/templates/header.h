@@ -0,0 +1,157 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+#pragma once
+
+#include <assert.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+// The host provides these, for both the Roc app and this glue to use.
+extern void *roc_alloc(size_t size, unsigned int alignment);
+extern void roc_dealloc(void *ptr, unsigned int alignment);
+
+// A zero-sized value, such as an empty record. C has no zero-sized types, so this takes up a
+// byte; zero-sized fields are left out of the glue's structs, so their layout still matches Roc's.
+typedef struct RocUnit {
+    uint8_t unused;
+} RocUnit;
+
+typedef struct RocStr {
+    uint8_t *bytes;
+    size_t length;
+    size_t capacity;
+} RocStr;
+
+typedef struct RocList {
+    void *elements;
+    size_t length;
+    size_t capacity;
+} RocList;
+
+typedef struct RocBox {
+    void *pointer;
+} RocBox;
+
+typedef struct RocDict {
+    RocList buckets;
+    RocList data;
+} RocDict;
+
+typedef struct RocSet {
+    RocList buckets;
+    RocList data;
+} RocSet;
+
+typedef struct RocI128 {
+    _Alignas(16) uint64_t lo;
+    int64_t hi;
+} RocI128;
+
+typedef struct RocU128 {
+    _Alignas(16) uint64_t lo;
+    uint64_t hi;
+} RocU128;
+
+// A fixed-point decimal, stored as an i128 scaled up by 10^18.
+typedef struct RocDec {
+    _Alignas(16) uint64_t lo;
+    int64_t hi;
+} RocDec;
+
+#define ROC_REFCOUNT_ONE INTPTR_MIN
+#define ROC_REFCOUNT_READONLY 0
+
+// Reference counts live right before the data they count, padded out to the data's alignment.
+static inline size_t roc_refcount_prefix(uint32_t alignment) {
+    return alignment > sizeof(intptr_t) ? alignment : sizeof(intptr_t);
+}
+
+// Allocates `size` bytes with a reference count of one, and returns a pointer to those bytes.
+static inline void *roc_alloc_refcounted(size_t size, uint32_t alignment) {
+    size_t prefix = roc_refcount_prefix(alignment);
+    char *data = (char *)roc_alloc(prefix + size, prefix) + prefix;
+
+    ((intptr_t *)data)[-1] = ROC_REFCOUNT_ONE;
+
+    return data;
+}
+
+static inline void roc_incref(void *data) {
+    if (data != NULL) {
+        intptr_t *refcount = (intptr_t *)data - 1;
+
+        if (*refcount != ROC_REFCOUNT_READONLY) {
+            *refcount += 1;
+        }
+    }
+}
+
+// Gives up a reference to `data`. Returns true if it was the last one, in which case
+// the caller must drop whatever `data` contains and then call `roc_free_refcounted`.
+static inline bool roc_decref_is_last(void *data) {
+    if (data == NULL) {
+        return false;
+    }
+
+    intptr_t *refcount = (intptr_t *)data - 1;
+
+    if (*refcount == ROC_REFCOUNT_ONE) {
+        return true;
+    }
+
+    if (*refcount != ROC_REFCOUNT_READONLY) {
+        *refcount -= 1;
+    }
+
+    return false;
+}
+
+static inline void roc_free_refcounted(void *data, uint32_t alignment) {
+    roc_dealloc((char *)data - roc_refcount_prefix(alignment), alignment);
+}
+
+// Small strings are stored inline, with their length in the last byte.
+static inline bool roc_str_is_small(const RocStr *str) {
+    return (intptr_t)str->capacity < 0;
+}
+
+static inline size_t roc_str_len(const RocStr *str) {
+    if (roc_str_is_small(str)) {
+        return ((const uint8_t *)str)[sizeof(RocStr) - 1] & 0x7f;
+    } else {
+        return str->length;
+    }
+}
+
+static inline const uint8_t *roc_str_bytes(const RocStr *str) {
+    if (roc_str_is_small(str)) {
+        return (const uint8_t *)str;
+    } else {
+        return str->bytes;
+    }
+}
+
+static inline RocStr roc_str_from_bytes(const uint8_t *bytes, size_t length) {
+    RocStr str;
+
+    if (length < sizeof(RocStr)) {
+        memset(&str, 0, sizeof(RocStr));
+        memcpy(&str, bytes, length);
+        ((uint8_t *)&str)[sizeof(RocStr) - 1] = (uint8_t)length | 0x80;
+    } else {
+        str.bytes = roc_alloc_refcounted(length, 1);
+        str.length = length;
+        str.capacity = length;
+        memcpy(str.bytes, bytes, length);
+    }
+
+    return str;
+}
+
+static inline void roc_str_drop(RocStr *str) {
+    if (!roc_str_is_small(str) && roc_decref_is_last(str->bytes)) {
+        roc_free_refcounted(str->bytes, 1);
+    }
+}
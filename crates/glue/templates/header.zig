@@ -0,0 +1,318 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+const std = @import("std");
+const builtin = @import("builtin");
+
+// The host provides these, for both the Roc app and this glue to use.
+extern fn roc_alloc(size: usize, alignment: u32) callconv(.C) ?*anyopaque;
+extern fn roc_dealloc(c_ptr: *anyopaque, alignment: u32) callconv(.C) void;
+
+/// A zero-sized value, such as an empty record.
+pub const RocUnit = extern struct {};
+
+const REFCOUNT_ONE: isize = std.math.minInt(isize);
+const REFCOUNT_READONLY: isize = 0;
+
+/// Reference counts live right before the data they count, padded out to the data's alignment.
+fn rocRefcountPrefix(alignment: u32) usize {
+    return std.math.max(alignment, @sizeOf(usize));
+}
+
+fn rocRefcount(data: *anyopaque) *isize {
+    return @intToPtr(*isize, @ptrToInt(data) - @sizeOf(usize));
+}
+
+/// Allocates `size` bytes with a reference count of one, and returns a pointer to those bytes.
+pub fn rocAllocRefcountedBytes(size: usize, alignment: u32) [*]u8 {
+    const prefix = rocRefcountPrefix(alignment);
+    const raw = roc_alloc(prefix + size, @intCast(u32, prefix)) orelse @panic("roc_alloc returned null");
+    const data = @intToPtr([*]u8, @ptrToInt(raw) + prefix);
+
+    rocRefcount(data).* = REFCOUNT_ONE;
+
+    return data;
+}
+
+pub fn rocAllocRefcounted(comptime T: type) *T {
+    return @ptrCast(*T, @alignCast(@alignOf(T), rocAllocRefcountedBytes(@sizeOf(T), @alignOf(T))));
+}
+
+pub fn rocIncref(opt_data: ?*anyopaque) void {
+    if (opt_data) |data| {
+        const refcount = rocRefcount(data);
+
+        if (refcount.* != REFCOUNT_READONLY) {
+            refcount.* += 1;
+        }
+    }
+}
+
+/// Gives up a reference to `data`. Returns true if it was the last one, in which case
+/// the caller must drop whatever `data` contains and then call `rocFreeRefcounted`.
+pub fn rocDecrefIsLast(opt_data: ?*anyopaque) bool {
+    const data = opt_data orelse return false;
+    const refcount = rocRefcount(data);
+
+    if (refcount.* == REFCOUNT_ONE) {
+        return true;
+    }
+
+    if (refcount.* != REFCOUNT_READONLY) {
+        refcount.* -= 1;
+    }
+
+    return false;
+}
+
+pub fn rocFreeRefcounted(data: *anyopaque, alignment: u32) void {
+    roc_dealloc(@intToPtr(*anyopaque, @ptrToInt(data) - rocRefcountPrefix(alignment)), alignment);
+}
+
+/// Whether values of this type hold reference counts, and so have a `drop` method.
+pub fn rocNeedsDrop(comptime T: type) bool {
+    return switch (@typeInfo(T)) {
+        .Struct, .Union, .Enum => @hasDecl(T, "drop"),
+        else => false,
+    };
+}
+
+pub fn rocDropValue(comptime T: type, value: *T) void {
+    if (comptime rocNeedsDrop(T)) {
+        value.drop();
+    }
+}
+
+pub const RocStr = extern struct {
+    bytes: ?[*]u8,
+    length: usize,
+    capacity: usize,
+
+    /// Small strings are stored inline, with their length in the last byte.
+    pub fn isSmall(self: *const RocStr) bool {
+        return @bitCast(isize, self.capacity) < 0;
+    }
+
+    pub fn len(self: *const RocStr) usize {
+        if (self.isSmall()) {
+            return @ptrCast([*]const u8, self)[@sizeOf(RocStr) - 1] & 0x7f;
+        } else {
+            return self.length;
+        }
+    }
+
+    pub fn asSlice(self: *const RocStr) []const u8 {
+        if (self.isSmall()) {
+            return @ptrCast([*]const u8, self)[0..self.len()];
+        } else {
+            return self.bytes.?[0..self.length];
+        }
+    }
+
+    pub fn fromSlice(slice: []const u8) RocStr {
+        var str = std.mem.zeroes(RocStr);
+
+        if (slice.len < @sizeOf(RocStr)) {
+            const bytes = @ptrCast([*]u8, &str);
+
+            std.mem.copy(u8, bytes[0..slice.len], slice);
+            bytes[@sizeOf(RocStr) - 1] = @intCast(u8, slice.len) | 0x80;
+        } else {
+            const bytes = rocAllocRefcountedBytes(slice.len, 1);
+
+            std.mem.copy(u8, bytes[0..slice.len], slice);
+            str.bytes = bytes;
+            str.length = slice.len;
+            str.capacity = slice.len;
+        }
+
+        return str;
+    }
+
+    pub fn drop(self: *RocStr) void {
+        if (!self.isSmall() and rocDecrefIsLast(self.bytes)) {
+            rocFreeRefcounted(self.bytes.?, 1);
+        }
+    }
+};
+
+pub fn RocList(comptime T: type) type {
+    return extern struct {
+        elements: ?[*]T,
+        length: usize,
+        capacity: usize,
+
+        const Self = @This();
+
+        pub fn empty() Self {
+            return .{ .elements = null, .length = 0, .capacity = 0 };
+        }
+
+        /// Copies the elements into a new list, which takes ownership of them.
+        pub fn fromSlice(slice: []const T) Self {
+            if (slice.len == 0) {
+                return empty();
+            }
+
+            const bytes = rocAllocRefcountedBytes(slice.len * @sizeOf(T), @alignOf(T));
+            const elements = @ptrCast([*]T, @alignCast(@alignOf(T), bytes));
+
+            std.mem.copy(T, elements[0..slice.len], slice);
+
+            return .{ .elements = elements, .length = slice.len, .capacity = slice.len };
+        }
+
+        pub fn asSlice(self: Self) []T {
+            if (self.elements) |elements| {
+                return elements[0..self.length];
+            } else {
+                return &[_]T{};
+            }
+        }
+
+        pub fn drop(self: *Self) void {
+            if (self.elements) |elements| {
+                if (rocDecrefIsLast(elements)) {
+                    if (comptime rocNeedsDrop(T)) {
+                        for (elements[0..self.length]) |*element| {
+                            element.drop();
+                        }
+                    }
+
+                    rocFreeRefcounted(elements, @alignOf(T));
+                }
+            }
+        }
+    };
+}
+
+pub fn RocDict(comptime K: type, comptime V: type) type {
+    return extern struct {
+        buckets: RocList(usize),
+        data: RocList(Entry),
+
+        const Self = @This();
+
+        /// Entries are laid out like a record of the key and value, so the more
+        /// aligned of the two comes first. (If they're tied, the key comes first.)
+        pub const Entry = if (@alignOf(V) > @alignOf(K))
+            extern struct {
+                value: V,
+                key: K,
+
+                pub fn drop(self: *@This()) void {
+                    rocDropValue(K, &self.key);
+                    rocDropValue(V, &self.value);
+                }
+            }
+        else
+            extern struct {
+                key: K,
+                value: V,
+
+                pub fn drop(self: *@This()) void {
+                    rocDropValue(K, &self.key);
+                    rocDropValue(V, &self.value);
+                }
+            };
+
+        pub fn drop(self: *Self) void {
+            self.buckets.drop();
+            self.data.drop();
+        }
+    };
+}
+
+/// A Set is a Dict whose values are all the zero-sized unit value,
+/// so its entries have the same layout as its elements.
+pub fn RocSet(comptime T: type) type {
+    return extern struct {
+        buckets: RocList(usize),
+        data: RocList(T),
+
+        const Self = @This();
+
+        pub fn drop(self: *Self) void {
+            self.buckets.drop();
+            self.data.drop();
+        }
+    };
+}
+
+pub fn RocBox(comptime T: type) type {
+    return extern struct {
+        pointer: *T,
+
+        const Self = @This();
+
+        /// Moves the value into a new box, which takes ownership of it.
+        pub fn init(value: T) Self {
+            const pointer = rocAllocRefcounted(T);
+
+            pointer.* = value;
+
+            return .{ .pointer = pointer };
+        }
+
+        pub fn drop(self: *Self) void {
+            if (rocDecrefIsLast(self.pointer)) {
+                rocDropValue(T, self.pointer);
+                rocFreeRefcounted(self.pointer, @alignOf(T));
+            }
+        }
+    };
+}
+
+pub fn RocResult(comptime T: type, comptime E: type) type {
+    return extern struct {
+        payload: extern union {
+            ok: T,
+            err: E,
+        },
+        tag: u8, // Err = 0, Ok = 1
+
+        const Self = @This();
+
+        pub fn initOk(ok: T) Self {
+            var answer = std.mem.zeroes(Self);
+
+            answer.payload.ok = ok;
+            answer.tag = 1;
+
+            return answer;
+        }
+
+        pub fn initErr(err: E) Self {
+            var answer = std.mem.zeroes(Self);
+
+            answer.payload.err = err;
+            answer.tag = 0;
+
+            return answer;
+        }
+
+        pub fn isOk(self: *const Self) bool {
+            return self.tag == 1;
+        }
+
+        pub fn drop(self: *Self) void {
+            if (self.isOk()) {
+                rocDropValue(T, &self.payload.ok);
+            } else {
+                rocDropValue(E, &self.payload.err);
+            }
+        }
+    };
+}
+
+pub const RocI128 = extern struct {
+    value: i128 align(16),
+};
+
+pub const RocU128 = extern struct {
+    value: u128 align(16),
+};
+
+/// A fixed-point decimal, stored as an i128 scaled up by 10^18.
+pub const RocDec = extern struct {
+    value: i128 align(16),
+};
@@ -0,0 +1,144 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+#[macro_use]
+extern crate indoc;
+
+mod helpers;
+
+#[cfg(test)]
+mod test_gen_c {
+    use crate::helpers::{check_fixture_glue_compiles, fixture_dir_names, generate_fixture_glue};
+    use roc_glue::Language;
+
+    fn generate_c(fixture_dir: &str) -> String {
+        generate_fixture_glue(fixture_dir, Language::C)
+            .strip_prefix('\n')
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    #[test]
+    fn basic_record() {
+        assert_eq!(
+            generate_c("basic-record"),
+            indoc!(
+                r#"
+                typedef struct MyRcd MyRcd;
+
+                struct MyRcd {
+                    RocU128 b;
+                    uint64_t a;
+                };
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn nested_record() {
+        assert_eq!(
+            generate_c("nested-record"),
+            indoc!(
+                r#"
+                typedef struct Inner Inner;
+
+                typedef struct Outer Outer;
+
+                static inline void Outer_drop(Outer *self);
+
+                struct Inner {
+                    float b;
+                    uint16_t a;
+                };
+
+                #if defined(__arm__) || defined(__wasm32__) || defined(__i386__)
+                struct Outer {
+                    Inner x;
+                    RocStr y;
+                    RocList z;
+                };
+                #endif
+
+                #if defined(__aarch64__) || defined(__x86_64__)
+                struct Outer {
+                    RocStr y;
+                    RocList z;
+                    Inner x;
+                };
+                #endif
+
+                static inline void Outer_drop(Outer *self) {
+                    roc_str_drop(&self->y);
+                    if (roc_decref_is_last(self->z.elements)) {
+                        roc_free_refcounted(self->z.elements, 1);
+                    }
+                }
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn enumeration() {
+        assert_eq!(
+            generate_c("enumeration"),
+            indoc!(
+                r#"
+                typedef uint8_t MyEnum;
+
+                enum {
+                    MyEnum_Bar = 0,
+                    MyEnum_Baz = 1,
+                    MyEnum_Foo = 2,
+                };
+            "#
+            )
+        );
+    }
+
+    /// Generates a test for each of the given fixtures, which checks that a C compiler accepts
+    /// its glue (including that its records have Roc's layout), and a test which checks that
+    /// every directory in fixtures/ has one.
+    macro_rules! fixtures {
+        ($($test_name:ident:$fixture_dir:expr,)+) => {
+            $(
+                #[test]
+                fn $test_name() {
+                    check_fixture_glue_compiles(
+                        $fixture_dir,
+                        Language::C,
+                        "glue.h",
+                        "cc",
+                        &["-std=c11", "-fsyntax-only", "-x", "c"],
+                    );
+                }
+            )*
+
+            #[test]
+            fn all_fixtures_have_tests() {
+                let mut tested = vec![$($fixture_dir.to_string()),*];
+                tested.sort();
+
+                assert_eq!(tested, fixture_dir_names());
+            }
+        }
+    }
+
+    fixtures! {
+        basic_record_compiles:"basic-record",
+        nested_record_compiles:"nested-record",
+        dict_compiles:"dict",
+        set_compiles:"set",
+        enumeration_compiles:"enumeration",
+        union_with_padding_compiles:"union-with-padding",
+        single_tag_union_compiles:"single-tag-union",
+        union_without_padding_compiles:"union-without-padding",
+        nullable_wrapped_compiles:"nullable-wrapped",
+        nullable_unwrapped_compiles:"nullable-unwrapped",
+        nonnullable_unwrapped_compiles:"nonnullable-unwrapped",
+        basic_recursive_union_compiles:"basic-recursive-union",
+        advanced_recursive_union_compiles:"advanced-recursive-union",
+        list_recursive_union_compiles:"list-recursive-union",
+    }
+}
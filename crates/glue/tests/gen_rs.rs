@@ -135,6 +135,34 @@ mod test_gen_rs {
         );
     }
 
+    #[test]
+    fn tuple_anonymous() {
+        let module = "main = (1u64, 2u128)";
+
+        assert_eq!(
+            generate_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                #[cfg(any(
+                    target_arch = "arm",
+                    target_arch = "aarch64",
+                    target_arch = "wasm32",
+                    target_arch = "x86",
+                    target_arch = "x86_64"
+                ))]
+                #[derive(Clone, Copy, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
+                #[repr(C)]
+                pub struct R1 {
+                    pub f1: roc_std::U128,
+                    pub f0: u64,
+                }
+            "#
+            )
+        );
+    }
+
     #[test]
     fn nested_record_anonymous() {
         let module = r#"main = { x: { a: 5u16, b: 24f32 }, y: "foo", z: [1u8, 2] }"#;
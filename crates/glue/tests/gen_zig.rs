@@ -0,0 +1,137 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+#[macro_use]
+extern crate indoc;
+
+mod helpers;
+
+#[cfg(test)]
+mod test_gen_zig {
+    use crate::helpers::{check_fixture_glue_compiles, fixture_dir_names, generate_fixture_glue};
+    use roc_glue::Language;
+
+    fn generate_zig(fixture_dir: &str) -> String {
+        generate_fixture_glue(fixture_dir, Language::Zig)
+            .strip_prefix('\n')
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    #[test]
+    fn basic_record() {
+        assert_eq!(
+            generate_zig("basic-record"),
+            indoc!(
+                r#"
+                pub const MyRcd = extern struct {
+                    b: RocU128,
+                    a: u64,
+                };
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn nested_record() {
+        assert_eq!(
+            generate_zig("nested-record"),
+            indoc!(
+                r#"
+                pub const Outer = switch (builtin.cpu.arch) {
+                    .arm, .wasm32, .i386 => extern struct {
+                        x: Inner,
+                        y: RocStr,
+                        z: RocList(u8),
+
+                        pub fn drop(self: *@This()) void {
+                            self.y.drop();
+                            self.z.drop();
+                        }
+                    },
+                    .aarch64, .x86_64 => extern struct {
+                        y: RocStr,
+                        z: RocList(u8),
+                        x: Inner,
+
+                        pub fn drop(self: *@This()) void {
+                            self.y.drop();
+                            self.z.drop();
+                        }
+                    },
+                    else => @compileError("This glue was not generated for the target architecture."),
+                };
+
+                pub const Inner = extern struct {
+                    b: f32,
+                    a: u16,
+                };
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn enumeration() {
+        assert_eq!(
+            generate_zig("enumeration"),
+            indoc!(
+                r#"
+                pub const MyEnum = enum(u8) {
+                    Bar = 0,
+                    Baz = 1,
+                    Foo = 2,
+                };
+            "#
+            )
+        );
+    }
+
+    /// Generates a test for each of the given fixtures, which checks that zig accepts its glue
+    /// (including that its records have Roc's layout), and a test which checks that every
+    /// directory in fixtures/ has one.
+    macro_rules! fixtures {
+        ($($test_name:ident:$fixture_dir:expr,)+) => {
+            $(
+                #[test]
+                fn $test_name() {
+                    check_fixture_glue_compiles(
+                        $fixture_dir,
+                        Language::Zig,
+                        "glue.zig",
+                        "zig",
+                        // Unlike `zig ast-check`, this runs semantic analysis, which evaluates the
+                        // layout checks' comptime blocks.
+                        &["build-obj", "-fno-emit-bin"],
+                    );
+                }
+            )*
+
+            #[test]
+            fn all_fixtures_have_tests() {
+                let mut tested = vec![$($fixture_dir.to_string()),*];
+                tested.sort();
+
+                assert_eq!(tested, fixture_dir_names());
+            }
+        }
+    }
+
+    fixtures! {
+        basic_record_compiles:"basic-record",
+        nested_record_compiles:"nested-record",
+        dict_compiles:"dict",
+        set_compiles:"set",
+        enumeration_compiles:"enumeration",
+        union_with_padding_compiles:"union-with-padding",
+        single_tag_union_compiles:"single-tag-union",
+        union_without_padding_compiles:"union-without-padding",
+        nullable_wrapped_compiles:"nullable-wrapped",
+        nullable_unwrapped_compiles:"nullable-unwrapped",
+        nonnullable_unwrapped_compiles:"nonnullable-unwrapped",
+        basic_recursive_union_compiles:"basic-recursive-union",
+        advanced_recursive_union_compiles:"advanced-recursive-union",
+        list_recursive_union_compiles:"list-recursive-union",
+    }
+}
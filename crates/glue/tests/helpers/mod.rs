@@ -1,10 +1,12 @@
 use roc_glue::load::load_types;
 use roc_glue::rust_glue;
+use roc_glue::Language;
 use roc_load::Threading;
 use std::env;
 use std::fs::File;
 use std::io::Write;
 use std::path::PathBuf;
+use std::process::Command;
 
 #[allow(dead_code)]
 pub fn generate_bindings(decl_src: &str) -> String {
@@ -43,6 +45,116 @@ pub fn generate_bindings(decl_src: &str) -> String {
     rust_glue::emit(&pairs)
 }
 
+/// Generates glue in the given language for the platform.roc in one of the fixtures/ directories.
+#[allow(dead_code)]
+pub fn generate_fixture_glue(dir_name: &str, language: Language) -> String {
+    let platform_path = fixtures_dir(dir_name).join("platform.roc");
+    let types_and_targets =
+        load_types(platform_path, Threading::Single).expect("had problems loading");
+
+    language.emit(&types_and_targets)
+}
+
+/// Writes the glue for one of the fixtures/ directories, header included, to `file_name` in a
+/// temporary directory, along with checks that each record in it has the size and alignment Roc
+/// (and so the Rust glue) gives it on this machine. Then runs `compiler` with `args` on it, and
+/// panics with what the compiler printed if it rejects the glue.
+#[allow(dead_code)]
+pub fn check_fixture_glue_compiles(
+    dir_name: &str,
+    language: Language,
+    file_name: &str,
+    compiler: &str,
+    args: &[&str],
+) {
+    use tempfile::tempdir;
+
+    let dir = tempdir().expect("Unable to create tempdir");
+    let file_path = dir.path().join(file_name);
+
+    let mut file = File::create(&file_path).unwrap();
+    file.write_all(language.header()).unwrap();
+    file.write_all(generate_fixture_glue(dir_name, language).as_bytes())
+        .unwrap();
+    file.write_all(layout_checks(dir_name, language).as_bytes())
+        .unwrap();
+    drop(file);
+
+    let output = Command::new(compiler)
+        .args(args)
+        .arg(&file_path)
+        .output()
+        .unwrap_or_else(|err| panic!("Unable to run {}: {}", compiler, err));
+
+    if !output.status.success() {
+        panic!(
+            "{} rejected the glue for {}:\n\n{}{}",
+            compiler,
+            dir_name,
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    dir.close().expect("Unable to close tempdir");
+}
+
+/// Compile-time checks that each record in the glue for one of the fixtures/ directories has the
+/// size and alignment of Roc's layout for it on this machine.
+#[allow(dead_code)]
+fn layout_checks(dir_name: &str, language: Language) -> String {
+    use roc_glue::types::RocType;
+    use roc_target::TargetInfo;
+
+    let platform_path = fixtures_dir(dir_name).join("platform.roc");
+    let host_architecture = TargetInfo::from(&target_lexicon::Triple::host()).architecture;
+    let types = load_types(platform_path, Threading::Single)
+        .expect("had problems loading")
+        .into_iter()
+        .find(|(_, target_info)| target_info.architecture == host_architecture)
+        .map(|(types, _)| types)
+        .expect("no glue was generated for this machine's architecture");
+
+    let mut buf = String::new();
+
+    for id in types.ids() {
+        if let RocType::Struct { name, .. } = types.get_type(id) {
+            let size = types.size_rounded_to_alignment(id);
+            let align = types.align(id);
+
+            let check = match language {
+                Language::C => format!(
+                    "_Static_assert(sizeof({name}) == {size} && _Alignof({name}) == {align}, \"{name} doesn't have Roc's layout\");\n"
+                ),
+                Language::Zig => format!(
+                    "comptime {{\n    if (@sizeOf({name}) != {size} or @alignOf({name}) != {align}) @compileError(\"{name} doesn't have Roc's layout\");\n}}\n"
+                ),
+                Language::Rust => String::new(),
+            };
+
+            buf.push('\n');
+            buf.push_str(&check);
+        }
+    }
+
+    buf
+}
+
+/// The names of all the directories in fixtures/, sorted
+#[allow(dead_code)]
+pub fn fixture_dir_names() -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(fixtures_dir(""))
+        .unwrap()
+        .map(|entry| entry.unwrap())
+        .filter(|entry| entry.file_type().unwrap().is_dir())
+        .map(|entry| entry.file_name().into_string().unwrap())
+        .collect();
+
+    names.sort();
+
+    names
+}
+
 #[allow(dead_code)]
 pub fn fixtures_dir(dir_name: &str) -> PathBuf {
     let mut path = root_dir();
@@ -0,0 +1,335 @@
+//! Everything the language server knows about a module, from loading and type checking it.
+
+use std::path::{Path, PathBuf};
+
+use bumpalo::Bump;
+use lsp_types::{
+    Diagnostic, DiagnosticSeverity, GotoDefinitionResponse, Hover, HoverContents, LanguageString,
+    Location, MarkedString, Position, Range, SemanticToken, SemanticTokenType, SemanticTokens,
+    SemanticTokensLegend, TextEdit, Url,
+};
+use roc_can::expr::Declarations;
+use roc_can::traverse::{find_closest_type_at, find_declaration, find_symbol_at};
+use roc_collections::MutMap;
+use roc_fmt::partial::format_module;
+use roc_highlight::tokenizer::{full_tokenize, Token};
+use roc_load::{LoadedModule, LoadingProblem};
+use roc_module::symbol::{Interns, ModuleId};
+use roc_region::all::{LineInfo, Region};
+use roc_reporting::report::{
+    can_problem, type_problem, RenderTarget, Report, RocDocAllocator, Severity,
+};
+use roc_target::TargetInfo;
+use roc_types::pretty_print::{name_and_print_var, DebugPrint};
+use roc_types::subs::Subs;
+
+use crate::convert::LineIndex;
+
+/// A type checked module, along with the modules it depends on.
+pub(crate) struct Analysis {
+    module_id: ModuleId,
+    interns: Interns,
+    subs: Subs,
+    declarations_by_id: MutMap<ModuleId, Declarations>,
+    sources: MutMap<ModuleId, (PathBuf, Box<str>)>,
+}
+
+impl Analysis {
+    /// Loads and type checks the module at `path`, whose contents are `source`. Only this
+    /// module is reloaded from `source`; the modules it imports are read from disk.
+    ///
+    /// Problems with the module are returned as diagnostics. If the module couldn't be
+    /// loaded at all, there is no analysis to answer other requests with.
+    pub fn new(path: &Path, source: &str) -> (Option<Analysis>, Vec<Diagnostic>) {
+        let arena = Bump::new();
+        let src_dir = path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf();
+
+        let loaded = roc_load::load_and_typecheck_str(
+            &arena,
+            path.to_path_buf(),
+            arena.alloc_str(source),
+            src_dir,
+            Default::default(),
+            TargetInfo::default_x86_64(),
+            RenderTarget::Generic,
+        );
+
+        match loaded {
+            Ok(mut loaded) => {
+                let diagnostics = diagnostics(&mut loaded);
+                let analysis = Analysis {
+                    module_id: loaded.module_id,
+                    interns: loaded.interns,
+                    subs: loaded.solved.into_inner(),
+                    declarations_by_id: loaded.declarations_by_id,
+                    sources: loaded.sources,
+                };
+
+                (Some(analysis), diagnostics)
+            }
+            Err(problem) => {
+                let (message, range) = match problem {
+                    LoadingProblem::FormattedReport(report, diagnostic) => {
+                        // Problems in other modules, like ones this module imports, go at the
+                        // start of this module.
+                        let range = match diagnostic.regions.first() {
+                            Some(region) if diagnostic.filename == path => {
+                                LineIndex::new(source).line_column_range(*region)
+                            }
+                            _ => Range::default(),
+                        };
+
+                        (report, range)
+                    }
+                    other => (format!("{:?}", other), Range::default()),
+                };
+
+                let diagnostic = Diagnostic {
+                    range,
+                    severity: Some(DiagnosticSeverity::ERROR),
+                    source: Some("roc".to_string()),
+                    message,
+                    ..Diagnostic::default()
+                };
+
+                (None, vec![diagnostic])
+            }
+        }
+    }
+
+    /// The type of the innermost expression or pattern at `position`.
+    ///
+    /// Positions are looked up in the source that was checked, which lags behind the editor's
+    /// while a newer version is being checked.
+    pub fn hover(&mut self, position: Position) -> Option<Hover> {
+        let (_, source) = self.sources.get(&self.module_id)?;
+        let line_index = LineIndex::new(source);
+        let declarations = self.declarations_by_id.get(&self.module_id)?;
+
+        let (region, var) = find_closest_type_at(line_index.roc_position(position), declarations)?;
+        let type_str = name_and_print_var(
+            var,
+            &mut self.subs,
+            self.module_id,
+            &self.interns,
+            DebugPrint::NOTHING,
+        );
+
+        Some(Hover {
+            contents: HoverContents::Scalar(MarkedString::LanguageString(LanguageString {
+                language: "roc".to_string(),
+                value: type_str,
+            })),
+            range: Some(line_index.range(region)),
+        })
+    }
+
+    /// Where the symbol at `position` is defined, which may be in another module.
+    pub fn definition(&self, position: Position) -> Option<GotoDefinitionResponse> {
+        let (_, source) = self.sources.get(&self.module_id)?;
+        let line_index = LineIndex::new(source);
+        let declarations = self.declarations_by_id.get(&self.module_id)?;
+
+        let symbol = find_symbol_at(line_index.roc_position(position), declarations)?.value;
+
+        let module_id = symbol.module_id();
+        let region = find_declaration(symbol, self.declarations_by_id.get(&module_id)?)?;
+        let (path, module_source) = self.sources.get(&module_id)?;
+
+        let uri = Url::from_file_path(path).ok()?;
+        let range = LineIndex::new(module_source).range(region);
+
+        Some(GotoDefinitionResponse::Scalar(Location::new(uri, range)))
+    }
+}
+
+fn diagnostics(loaded: &mut LoadedModule) -> Vec<Diagnostic> {
+    let home = loaded.module_id;
+    let (path, source) = match loaded.sources.get(&home) {
+        Some((path, source)) => (path.clone(), source.clone()),
+        None => return Vec::new(),
+    };
+
+    let src_lines: Vec<&str> = source.split('\n').collect();
+    let lines = LineInfo::new(&source);
+    let line_index = LineIndex::new(&source);
+    let alloc = RocDocAllocator::new(&src_lines, home, &loaded.interns);
+
+    let mut diagnostics = Vec::new();
+
+    for problem in loaded.can_problems.remove(&home).unwrap_or_default() {
+        let region = problem.region();
+        let report = can_problem(&alloc, &lines, path.clone(), problem);
+
+        diagnostics.push(to_diagnostic(&alloc, &line_index, region, report));
+    }
+
+    for problem in loaded.type_problems.remove(&home).unwrap_or_default() {
+        let region = problem.region();
+
+        if let Some(report) = type_problem(&alloc, &lines, path.clone(), problem) {
+            diagnostics.push(to_diagnostic(&alloc, &line_index, region, report));
+        }
+    }
+
+    diagnostics
+}
+
+fn to_diagnostic<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    line_index: &LineIndex,
+    region: Option<Region>,
+    mut report: Report<'b>,
+) -> Diagnostic {
+    let severity = match report.severity {
+        Severity::RuntimeError => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+    };
+
+    // Editors show which file the problem is in already, so leave out the header with the
+    // title and path, and use the report's error code as the diagnostic's code instead.
+    let title = std::mem::take(&mut report.title);
+    let code = report.code.map(str::to_string).unwrap_or(title);
+    let mut message = String::new();
+    report.render_ci(&mut message, alloc);
+
+    Diagnostic {
+        range: match region {
+            Some(region) => line_index.range(region),
+            None => Range::default(),
+        },
+        severity: Some(severity),
+        code: Some(lsp_types::NumberOrString::String(code)),
+        source: Some("roc".to_string()),
+        message: message.trim().to_string(),
+        ..Diagnostic::default()
+    }
+}
+
+/// Formats the whole source with `roc_fmt`, leaving top-level defs which don't parse the way they
+/// are. Returns `None` if the header doesn't parse.
+pub(crate) fn format(source: &str) -> Option<Vec<TextEdit>> {
+    let arena = Bump::new();
+    let formatted = format_module(&arena, source).ok()?;
+
+    if formatted == source {
+        Some(Vec::new())
+    } else {
+        let range = LineIndex::new(source).full_range();
+
+        Some(vec![TextEdit::new(range, formatted.to_string())])
+    }
+}
+
+/// Formats the top-level defs which overlap `range`, like [format] does.
+pub(crate) fn format_range(source: &str, range: Range) -> Option<Vec<TextEdit>> {
+    let arena = Bump::new();
+    let line_index = LineIndex::new(source);
+    let range = line_index.offset(range.start)..line_index.offset(range.end);
+
+    let (replaced, formatted) = roc_fmt::partial::format_range(&arena, source, range).ok()?;
+
+    if formatted == &source[replaced.clone()] {
+        Some(Vec::new())
+    } else {
+        let range = Range::new(
+            line_index.position(replaced.start),
+            line_index.position(replaced.end),
+        );
+
+        Some(vec![TextEdit::new(range, formatted.to_string())])
+    }
+}
+
+/// The token types we highlight, in the order their indices refer to.
+const TOKEN_TYPES: [SemanticTokenType; 6] = [
+    SemanticTokenType::KEYWORD,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::OPERATOR,
+    SemanticTokenType::STRING,
+    SemanticTokenType::NUMBER,
+];
+
+pub(crate) fn semantic_tokens_legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: Vec::new(),
+    }
+}
+
+fn token_type(token: Token) -> Option<SemanticTokenType> {
+    use Token::*;
+
+    let token_type = match token {
+        KeywordIf | KeywordThen | KeywordElse | KeywordWhen | KeywordAs | KeywordIs
+        | KeywordExpect | KeywordApp | KeywordInterface | KeywordPackages | KeywordImports
+        | KeywordProvides | KeywordTo | KeywordExposes | KeywordEffects | KeywordPlatform
+        | KeywordRequires | KeywordDbg => SemanticTokenType::KEYWORD,
+        LowercaseIdent => SemanticTokenType::VARIABLE,
+        UppercaseIdent => SemanticTokenType::TYPE,
+        OpPlus | OpMinus | OpSlash | OpPercent | OpCaret | OpGreaterThan | OpLessThan
+        | OpAssignment | OpPizza | OpEquals | OpNotEquals | OpGreaterThanOrEq | OpLessThanOrEq
+        | OpAnd | OpOr | OpDoubleSlash | OpBackpassing | Ampersand | Pipe | Bang | LambdaStart
+        | Arrow | FatArrow | Asterisk | QuestionMark => SemanticTokenType::OPERATOR,
+        Token::String => SemanticTokenType::STRING,
+        Token::NumberBase | Token::Number => SemanticTokenType::NUMBER,
+        _ => return None,
+    };
+
+    Some(token_type)
+}
+
+/// Highlights the source with the tokens from `roc_highlight`.
+pub(crate) fn semantic_tokens(source: &str) -> SemanticTokens {
+    let line_index = LineIndex::new(source);
+    let table = full_tokenize(source);
+
+    let mut data = Vec::with_capacity(table.tokens.len());
+    let mut previous = Position::new(0, 0);
+
+    let tokens = table
+        .tokens
+        .iter()
+        .zip(table.offsets.iter().zip(&table.lengths));
+
+    for (&token, (&offset, &length)) in tokens {
+        let token_type = match token_type(token) {
+            Some(token_type) => token_type,
+            None => continue,
+        };
+
+        let start = line_index.position(offset);
+        let end = line_index.position(offset + length);
+
+        // Tokens can't span lines, so leave out multiline strings.
+        if length == 0 || start.line != end.line {
+            continue;
+        }
+
+        let delta_line = start.line - previous.line;
+        let delta_start = if delta_line == 0 {
+            start.character - previous.character
+        } else {
+            start.character
+        };
+
+        data.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length: end.character - start.character,
+            token_type: TOKEN_TYPES.iter().position(|t| *t == token_type).unwrap() as u32,
+            token_modifiers_bitset: 0,
+        });
+
+        previous = start;
+    }
+
+    SemanticTokens {
+        result_id: None,
+        data,
+    }
+}
@@ -0,0 +1,116 @@
+//! Type checks documents on a thread of its own, so the main loop keeps answering requests while
+//! a module is checked, and a crash in the compiler doesn't take the whole server down.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use std::time::Duration;
+
+use crossbeam::channel::{self, Receiver, RecvTimeoutError, Sender};
+use lsp_types::{Diagnostic, DiagnosticSeverity, Range, Url};
+
+use crate::analysis::Analysis;
+
+/// How long a document has to stay unchanged before it is checked. Editors send a change for
+/// every keystroke, and checking the module after each of them would just fall behind.
+pub(crate) const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// A version of a document that needs to be checked
+pub(crate) struct Check {
+    pub url: Url,
+    pub version: i32,
+    pub source: String,
+}
+
+pub(crate) struct Checked {
+    pub url: Url,
+    pub version: i32,
+    /// Only `None` while the module can't be loaded, e.g. because it doesn't parse.
+    pub analysis: Option<Analysis>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Starts the checker thread. It stops once the returned sender is dropped.
+pub(crate) fn spawn(debounce: Duration) -> (Sender<Check>, Receiver<Checked>) {
+    let (check_tx, check_rx) = channel::unbounded();
+    let (checked_tx, checked_rx) = channel::unbounded();
+
+    thread::Builder::new()
+        .name("roc-lsp-checker".to_string())
+        .spawn(move || run(debounce, check_rx, checked_tx))
+        .expect("failed to start the checker thread");
+
+    (check_tx, checked_rx)
+}
+
+fn run(debounce: Duration, checks: Receiver<Check>, checked: Sender<Checked>) {
+    // Only the newest version of each document is worth checking
+    let mut pending: HashMap<Url, Check> = HashMap::new();
+
+    while let Ok(check) = checks.recv() {
+        pending.insert(check.url.clone(), check);
+
+        loop {
+            match checks.recv_timeout(debounce) {
+                Ok(check) => {
+                    pending.insert(check.url.clone(), check);
+                }
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        for (_, check) in pending.drain() {
+            if checked.send(check_document(check)).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Loads and type checks just this module. Its imports are read from disk.
+fn check_document(check: Check) -> Checked {
+    let Check {
+        url,
+        version,
+        source,
+    } = check;
+
+    let (analysis, diagnostics) = match url.to_file_path() {
+        Ok(path) => match panic::catch_unwind(AssertUnwindSafe(|| Analysis::new(&path, &source))) {
+            Ok(result) => result,
+            Err(payload) => (None, vec![crash_diagnostic(payload)]),
+        },
+        // We can only load modules which are files, since their imports are files too.
+        Err(()) => (None, Vec::new()),
+    };
+
+    Checked {
+        url,
+        version,
+        analysis,
+        diagnostics,
+    }
+}
+
+fn crash_diagnostic(payload: Box<dyn Any + Send>) -> Diagnostic {
+    let reason = match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "no reason given".to_string(),
+        },
+    };
+
+    Diagnostic {
+        range: Range::default(),
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some("roc".to_string()),
+        message: format!(
+            "The compiler crashed while checking this module, so there is nothing to show for it: {}",
+            reason
+        ),
+        ..Diagnostic::default()
+    }
+}
@@ -0,0 +1,91 @@
+//! Conversions between the byte offsets the compiler uses and the line/character positions
+//! of the Language Server Protocol, which counts characters in UTF-16 code units.
+
+use lsp_types::{Position as LspPosition, Range};
+use roc_region::all::{LineColumn, LineColumnRegion, Position, Region};
+
+pub(crate) struct LineIndex<'a> {
+    source: &'a str,
+    /// The byte offset at which each line starts
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    pub fn position(&self, offset: usize) -> LspPosition {
+        let offset = offset.min(self.source.len());
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+        let line_start = self.line_starts[line];
+        let character = self.source[line_start..offset]
+            .chars()
+            .map(char::len_utf16)
+            .sum::<usize>();
+
+        LspPosition::new(line as u32, character as u32)
+    }
+
+    pub fn offset(&self, position: LspPosition) -> usize {
+        let line_start = match self.line_starts.get(position.line as usize) {
+            Some(&line_start) => line_start,
+            None => return self.source.len(),
+        };
+        let line = self.source[line_start..]
+            .split_inclusive('\n')
+            .next()
+            .unwrap_or_default();
+
+        let mut character = 0;
+        for (index, ch) in line.char_indices() {
+            if character >= position.character as usize || ch == '\n' {
+                return line_start + index;
+            }
+            character += ch.len_utf16();
+        }
+
+        line_start + line.len()
+    }
+
+    pub fn roc_position(&self, position: LspPosition) -> Position {
+        Position::new(self.offset(position) as u32)
+    }
+
+    pub fn range(&self, region: Region) -> Range {
+        Range::new(
+            self.position(region.start().offset as usize),
+            self.position(region.end().offset as usize),
+        )
+    }
+
+    /// The range of a region given in lines and byte columns, like the ones reports highlight.
+    pub fn line_column_range(&self, region: LineColumnRegion) -> Range {
+        Range::new(
+            self.position(self.line_column_offset(region.start)),
+            self.position(self.line_column_offset(region.end)),
+        )
+    }
+
+    fn line_column_offset(&self, line_column: LineColumn) -> usize {
+        match self.line_starts.get(line_column.line as usize) {
+            Some(&line_start) => line_start + line_column.column as usize,
+            None => self.source.len(),
+        }
+    }
+
+    /// The range spanning the whole source
+    pub fn full_range(&self) -> Range {
+        Range::new(LspPosition::new(0, 0), self.position(self.source.len()))
+    }
+}
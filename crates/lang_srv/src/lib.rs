@@ -0,0 +1,12 @@
+//! A language server for Roc, which speaks the Language Server Protocol over stdio.
+//!
+//! Each open document is loaded and type checked on its own once it stops changing, on a
+//! background thread, and that analysis answers hover and go-to-definition requests. Formatting
+//! and highlighting only need the document's current source.
+mod analysis;
+mod checker;
+mod convert;
+mod registry;
+mod server;
+
+pub use server::{serve, start, ServerError};
@@ -0,0 +1,136 @@
+//! The documents the editor has open, and what we know about each of them.
+
+use std::collections::HashMap;
+
+use lsp_types::{
+    Diagnostic, GotoDefinitionResponse, Hover, Position, Range, SemanticTokens,
+    TextDocumentContentChangeEvent, TextEdit, Url,
+};
+
+use crate::analysis::{self, Analysis};
+use crate::checker::{Check, Checked};
+use crate::convert::LineIndex;
+
+pub(crate) struct Document {
+    pub version: i32,
+    pub source: String,
+    /// The analysis of the newest version that was checked. Only `None` while the module can't be
+    /// loaded, e.g. because it doesn't parse, or before the first check is done.
+    analysis: Option<Analysis>,
+}
+
+impl Document {
+    fn apply_change(&mut self, change: TextDocumentContentChangeEvent) {
+        match change.range {
+            Some(range) => {
+                let line_index = LineIndex::new(&self.source);
+                let start = line_index.offset(range.start);
+                let end = line_index.offset(range.end);
+
+                self.source.replace_range(start..end, &change.text);
+            }
+            None => self.source = change.text,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Registry {
+    documents: HashMap<Url, Document>,
+}
+
+impl Registry {
+    /// Starts tracking a document, and returns what to check.
+    pub fn open(&mut self, url: Url, version: i32, source: String) -> Check {
+        let document = Document {
+            version,
+            source: source.clone(),
+            analysis: None,
+        };
+        self.documents.insert(url.clone(), document);
+
+        Check {
+            url,
+            version,
+            source,
+        }
+    }
+
+    /// Applies the changes to a document in order, and returns what to check.
+    pub fn change(
+        &mut self,
+        url: &Url,
+        version: i32,
+        changes: Vec<TextDocumentContentChangeEvent>,
+    ) -> Option<Check> {
+        let document = self.documents.get_mut(url)?;
+
+        for change in changes {
+            document.apply_change(change);
+        }
+        document.version = version;
+
+        Some(Check {
+            url: url.clone(),
+            version,
+            source: document.source.clone(),
+        })
+    }
+
+    /// Keeps the analysis of a document, unless it has been changed or closed since it was
+    /// checked. Returns the diagnostics to publish, if they are still up to date.
+    pub fn checked(&mut self, checked: Checked) -> Option<(Url, Vec<Diagnostic>)> {
+        let Checked {
+            url,
+            version,
+            analysis,
+            diagnostics,
+        } = checked;
+
+        let document = self.documents.get_mut(&url)?;
+
+        if document.version != version {
+            return None;
+        }
+
+        document.analysis = analysis;
+
+        Some((url, diagnostics))
+    }
+
+    pub fn close(&mut self, url: &Url) {
+        self.documents.remove(url);
+    }
+
+    pub fn version(&self, url: &Url) -> Option<i32> {
+        self.documents.get(url).map(|document| document.version)
+    }
+
+    pub fn hover(&mut self, url: &Url, position: Position) -> Option<Hover> {
+        self.documents
+            .get_mut(url)?
+            .analysis
+            .as_mut()?
+            .hover(position)
+    }
+
+    pub fn definition(&self, url: &Url, position: Position) -> Option<GotoDefinitionResponse> {
+        self.documents
+            .get(url)?
+            .analysis
+            .as_ref()?
+            .definition(position)
+    }
+
+    pub fn format(&self, url: &Url) -> Option<Vec<TextEdit>> {
+        analysis::format(&self.documents.get(url)?.source)
+    }
+
+    pub fn format_range(&self, url: &Url, range: Range) -> Option<Vec<TextEdit>> {
+        analysis::format_range(&self.documents.get(url)?.source, range)
+    }
+
+    pub fn semantic_tokens(&self, url: &Url) -> Option<SemanticTokens> {
+        Some(analysis::semantic_tokens(&self.documents.get(url)?.source))
+    }
+}
@@ -0,0 +1,243 @@
+//! The main loop, which answers the editor's requests and notifications over stdio.
+
+use std::error::Error;
+
+use crossbeam::channel::{select, Receiver, Sender};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _,
+    PublishDiagnostics,
+};
+use lsp_types::request::{
+    Formatting, GotoDefinition, HoverRequest, RangeFormatting, Request as _,
+    SemanticTokensFullRequest,
+};
+use lsp_types::{
+    Diagnostic, DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DocumentFormattingParams, DocumentRangeFormattingParams, GotoDefinitionParams, HoverParams,
+    HoverProviderCapability, OneOf, PublishDiagnosticsParams, SemanticTokensFullOptions,
+    SemanticTokensOptions, SemanticTokensParams, SemanticTokensResult,
+    SemanticTokensServerCapabilities, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Url,
+};
+
+use crate::analysis::semantic_tokens_legend;
+use crate::checker::{self, Check, Checked, DEBOUNCE};
+use crate::registry::Registry;
+
+pub type ServerError = Box<dyn Error + Sync + Send>;
+
+fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(
+            TextDocumentSyncKind::INCREMENTAL,
+        )),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        document_formatting_provider: Some(OneOf::Left(true)),
+        document_range_formatting_provider: Some(OneOf::Left(true)),
+        semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(
+            SemanticTokensOptions {
+                legend: semantic_tokens_legend(),
+                full: Some(SemanticTokensFullOptions::Bool(true)),
+                ..SemanticTokensOptions::default()
+            },
+        )),
+        ..ServerCapabilities::default()
+    }
+}
+
+/// Runs the language server over stdin and stdout until the editor asks it to exit.
+pub fn start() -> Result<(), ServerError> {
+    let (connection, io_threads) = Connection::stdio();
+
+    serve(connection)?;
+
+    io_threads.join()?;
+
+    Ok(())
+}
+
+/// Answers the editor on `connection`, starting with the initialization handshake, until the
+/// editor asks the server to shut down.
+pub fn serve(connection: Connection) -> Result<(), ServerError> {
+    connection.initialize(serde_json::to_value(&capabilities())?)?;
+
+    let (checks, checked) = checker::spawn(DEBOUNCE);
+    let mut server = Server {
+        connection: &connection,
+        registry: Registry::default(),
+        checks,
+    };
+
+    server.run(&checked)
+}
+
+struct Server<'a> {
+    connection: &'a Connection,
+    registry: Registry,
+    /// Documents go to the checker thread, so the main loop never waits for the compiler.
+    checks: Sender<Check>,
+}
+
+impl<'a> Server<'a> {
+    fn run(&mut self, checked: &Receiver<Checked>) -> Result<(), ServerError> {
+        loop {
+            select! {
+                recv(self.connection.receiver) -> message => match message {
+                    Ok(Message::Request(request)) => {
+                        if self.connection.handle_shutdown(&request)? {
+                            return Ok(());
+                        }
+
+                        let response = self.handle_request(request);
+                        self.connection.sender.send(Message::Response(response))?;
+                    }
+                    Ok(Message::Notification(notification)) => {
+                        self.handle_notification(notification)?
+                    }
+                    Ok(Message::Response(_)) => {}
+                    // the editor went away
+                    Err(_) => return Ok(()),
+                },
+                recv(checked) -> checked => {
+                    if let Some((uri, diagnostics)) = self.registry.checked(checked?) {
+                        self.publish_diagnostics(uri, diagnostics)?;
+                    }
+                }
+            }
+        }
+    }
+
+    fn handle_request(&mut self, request: Request) -> Response {
+        let Request { id, method, params } = request;
+
+        let result = match method.as_str() {
+            HoverRequest::METHOD => serde_json::from_value(params).map(|params: HoverParams| {
+                let document = params.text_document_position_params;
+                let hover = self
+                    .registry
+                    .hover(&document.text_document.uri, document.position);
+
+                serde_json::to_value(hover)
+            }),
+            GotoDefinition::METHOD => {
+                serde_json::from_value(params).map(|params: GotoDefinitionParams| {
+                    let document = params.text_document_position_params;
+                    let definition = self
+                        .registry
+                        .definition(&document.text_document.uri, document.position);
+
+                    serde_json::to_value(definition)
+                })
+            }
+            Formatting::METHOD => {
+                serde_json::from_value(params).map(|params: DocumentFormattingParams| {
+                    let edits = self.registry.format(&params.text_document.uri);
+
+                    serde_json::to_value(edits)
+                })
+            }
+            RangeFormatting::METHOD => {
+                serde_json::from_value(params).map(|params: DocumentRangeFormattingParams| {
+                    let edits = self
+                        .registry
+                        .format_range(&params.text_document.uri, params.range);
+
+                    serde_json::to_value(edits)
+                })
+            }
+            SemanticTokensFullRequest::METHOD => {
+                serde_json::from_value(params).map(|params: SemanticTokensParams| {
+                    let tokens = self
+                        .registry
+                        .semantic_tokens(&params.text_document.uri)
+                        .map(SemanticTokensResult::Tokens);
+
+                    serde_json::to_value(tokens)
+                })
+            }
+            _ => {
+                return Response::new_err(
+                    id,
+                    ErrorCode::MethodNotFound as i32,
+                    format!("Unsupported request: {}", method),
+                );
+            }
+        };
+
+        match result.and_then(|value| value) {
+            Ok(value) => Response {
+                id,
+                result: Some(value),
+                error: None,
+            },
+            Err(error) => Response::new_err(
+                id,
+                ErrorCode::InvalidParams as i32,
+                format!("Invalid parameters for {}: {}", method, error),
+            ),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<(), ServerError> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let document = params.text_document;
+                let check = self
+                    .registry
+                    .open(document.uri, document.version, document.text);
+
+                self.checks.send(check)?;
+
+                Ok(())
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let document = params.text_document;
+                let check =
+                    self.registry
+                        .change(&document.uri, document.version, params.content_changes);
+
+                if let Some(check) = check {
+                    self.checks.send(check)?;
+                }
+
+                Ok(())
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                self.registry.close(&uri);
+
+                // Problems in a closed document are stale, so clear them in the editor.
+                self.publish_diagnostics(uri, Vec::new())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn publish_diagnostics(
+        &self,
+        uri: Url,
+        diagnostics: Vec<Diagnostic>,
+    ) -> Result<(), ServerError> {
+        let version = self.registry.version(&uri);
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version,
+        };
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+
+        self.connection
+            .sender
+            .send(Message::Notification(notification))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,290 @@
+//! Talks to the language server the way an editor would, over an in-memory connection.
+
+#[macro_use]
+extern crate indoc;
+
+use std::path::Path;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use lsp_server::{Connection, Message, Notification, Request, RequestId};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidOpenTextDocument, Exit, Initialized, Notification as _,
+    PublishDiagnostics,
+};
+use lsp_types::request::{GotoDefinition, HoverRequest, Request as _, Shutdown};
+use lsp_types::{
+    DiagnosticSeverity, DidChangeTextDocumentParams, DidOpenTextDocumentParams,
+    GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents, HoverParams,
+    InitializedParams, MarkedString, Position, PublishDiagnosticsParams,
+    TextDocumentContentChangeEvent, TextDocumentIdentifier, TextDocumentItem,
+    TextDocumentPositionParams, Url, VersionedTextDocumentIdentifier,
+};
+use serde_json::json;
+
+/// Loading the builtins takes a while in debug builds
+const TIMEOUT: Duration = Duration::from_secs(120);
+
+const MODULE: &str = indoc!(
+    r#"
+    interface Test
+        exposes [num, str]
+        imports []
+
+    num : I64
+    num = 42
+
+    str = Num.toStr num
+    "#
+);
+
+struct Editor {
+    connection: Connection,
+    server: Option<JoinHandle<()>>,
+    next_id: i32,
+}
+
+impl Editor {
+    fn start() -> Self {
+        let (server_connection, connection) = Connection::memory();
+        let server = thread::spawn(move || roc_lang_srv::serve(server_connection).unwrap());
+
+        let mut editor = Editor {
+            connection,
+            server: Some(server),
+            next_id: 0,
+        };
+
+        editor.request_raw("initialize", json!({ "capabilities": {} }));
+        editor.notify::<Initialized>(InitializedParams {});
+
+        editor
+    }
+
+    fn notify<N: lsp_types::notification::Notification>(&self, params: N::Params) {
+        let notification = Notification::new(N::METHOD.to_string(), params);
+
+        self.connection
+            .sender
+            .send(Message::Notification(notification))
+            .unwrap();
+    }
+
+    fn request_raw(&mut self, method: &str, params: serde_json::Value) -> serde_json::Value {
+        self.next_id += 1;
+        let id = RequestId::from(self.next_id);
+        let request = Request::new(id.clone(), method.to_string(), params);
+
+        self.connection
+            .sender
+            .send(Message::Request(request))
+            .unwrap();
+
+        loop {
+            match self.connection.receiver.recv_timeout(TIMEOUT).unwrap() {
+                Message::Response(response) if response.id == id => {
+                    assert!(response.error.is_none(), "{:?}", response.error);
+
+                    return response.result.unwrap_or(serde_json::Value::Null);
+                }
+                // diagnostics can arrive at any time
+                _ => {}
+            }
+        }
+    }
+
+    fn request<R: lsp_types::request::Request>(&mut self, params: R::Params) -> R::Result {
+        let result = self.request_raw(R::METHOD, serde_json::to_value(params).unwrap());
+
+        serde_json::from_value(result).unwrap()
+    }
+
+    fn diagnostics(&self) -> PublishDiagnosticsParams {
+        loop {
+            match self.connection.receiver.recv_timeout(TIMEOUT).unwrap() {
+                Message::Notification(notification)
+                    if notification.method == PublishDiagnostics::METHOD =>
+                {
+                    return serde_json::from_value(notification.params).unwrap();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn open(&self, url: &Url, source: &str) -> PublishDiagnosticsParams {
+        self.notify::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem::new(
+                url.clone(),
+                "roc".to_string(),
+                1,
+                source.to_string(),
+            ),
+        });
+
+        self.diagnostics()
+    }
+
+    fn position_params(url: &Url, line: u32, character: u32) -> TextDocumentPositionParams {
+        TextDocumentPositionParams::new(
+            TextDocumentIdentifier::new(url.clone()),
+            Position::new(line, character),
+        )
+    }
+}
+
+impl Drop for Editor {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            return;
+        }
+
+        self.request::<Shutdown>(());
+        self.notify::<Exit>(());
+
+        self.server.take().unwrap().join().unwrap();
+    }
+}
+
+fn module_url(dir: &Path) -> Url {
+    Url::from_file_path(dir.join("Test.roc")).unwrap()
+}
+
+#[test]
+fn publishes_diagnostics_for_type_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let url = module_url(dir.path());
+    let editor = Editor::start();
+
+    let source = format!("{}\nbad : Str\nbad = 1\n", MODULE);
+    let published = editor.open(&url, &source);
+
+    assert_eq!(published.uri, url);
+    assert_eq!(published.version, Some(1));
+
+    let mismatch = published
+        .diagnostics
+        .iter()
+        .find(|diagnostic| diagnostic.message.contains("bad"))
+        .unwrap_or_else(|| panic!("no diagnostic about `bad` in {:?}", published.diagnostics));
+
+    assert_eq!(mismatch.severity, Some(DiagnosticSeverity::ERROR));
+    assert_eq!(mismatch.range.start.line, 10);
+}
+
+#[test]
+fn publishes_diagnostics_for_syntax_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let url = module_url(dir.path());
+    let editor = Editor::start();
+
+    let source = format!("{}\nbad = [\n", MODULE);
+    let published = editor.open(&url, &source);
+
+    let [unfinished] = <[_; 1]>::try_from(published.diagnostics).unwrap();
+
+    assert_eq!(unfinished.severity, Some(DiagnosticSeverity::ERROR));
+    assert!(unfinished.message.contains("UNFINISHED LIST"));
+    assert_eq!(unfinished.range.start.line, 9);
+}
+
+#[test]
+fn republishes_diagnostics_for_the_newest_version() {
+    let dir = tempfile::tempdir().unwrap();
+    let url = module_url(dir.path());
+    let editor = Editor::start();
+
+    let source = format!("{}\nbad : Str\nbad = 1\n", MODULE);
+    assert!(!editor.open(&url, &source).diagnostics.is_empty());
+
+    // Fix the type error in two quick edits; only the last version needs to be checked.
+    for (version, text) in [(2, "\"one\""), (3, "\"two\"")] {
+        editor.notify::<DidChangeTextDocument>(DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier::new(url.clone(), version),
+            content_changes: vec![TextDocumentContentChangeEvent {
+                range: Some(lsp_types::Range::new(
+                    Position::new(10, 6),
+                    Position::new(10, 100),
+                )),
+                range_length: None,
+                text: text.to_string(),
+            }],
+        });
+    }
+
+    let published = editor.diagnostics();
+
+    assert_eq!(published.version, Some(3));
+    assert_eq!(published.diagnostics, Vec::new());
+}
+
+#[test]
+fn hover_shows_the_type() {
+    let dir = tempfile::tempdir().unwrap();
+    let url = module_url(dir.path());
+    let mut editor = Editor::start();
+
+    assert_eq!(editor.open(&url, MODULE).diagnostics, Vec::new());
+
+    // `num` in `str = Num.toStr num`
+    let hover: Option<Hover> = editor.request::<HoverRequest>(HoverParams {
+        text_document_position_params: Editor::position_params(&url, 7, 17),
+        work_done_progress_params: Default::default(),
+    });
+
+    match hover.expect("no hover").contents {
+        HoverContents::Scalar(MarkedString::LanguageString(string)) => {
+            assert_eq!(string.language, "roc");
+            assert_eq!(string.value, "I64");
+        }
+        other => panic!("unexpected hover contents {:?}", other),
+    }
+}
+
+#[test]
+fn definition_goes_to_the_def() {
+    let dir = tempfile::tempdir().unwrap();
+    let url = module_url(dir.path());
+    let mut editor = Editor::start();
+
+    assert_eq!(editor.open(&url, MODULE).diagnostics, Vec::new());
+
+    // `num` in `str = Num.toStr num`
+    let definition = editor.request::<GotoDefinition>(GotoDefinitionParams {
+        text_document_position_params: Editor::position_params(&url, 7, 17),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    });
+
+    match definition.expect("no definition") {
+        GotoDefinitionResponse::Scalar(location) => {
+            assert_eq!(location.uri, url);
+            assert!(
+                (4..=5).contains(&location.range.start.line),
+                "{:?}",
+                location.range
+            );
+        }
+        other => panic!("unexpected definition {:?}", other),
+    }
+}
+
+#[test]
+fn requests_before_the_first_check_get_no_answer() {
+    let dir = tempfile::tempdir().unwrap();
+    let url = module_url(dir.path());
+    let mut editor = Editor::start();
+
+    editor.notify::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+        text_document: TextDocumentItem::new(url.clone(), "roc".to_string(), 1, MODULE.to_string()),
+    });
+
+    // The checker waits for the document to settle, but the server answers right away.
+    let hover = editor.request::<HoverRequest>(HoverParams {
+        text_document_position_params: Editor::position_params(&url, 7, 17),
+        work_done_progress_params: Default::default(),
+    });
+
+    assert_eq!(hover, None);
+    assert_eq!(editor.diagnostics().version, Some(1));
+}
@@ -0,0 +1,261 @@
+//! Reading and rewriting the few aarch64 instructions the surgical linker needs to touch.
+//!
+//! Every aarch64 instruction is 4 bytes, so finding branches does not need a full disassembler the
+//! way x86_64 does. Relocations here are applied by patching the immediate bits of an instruction,
+//! rather than by writing out a whole offset.
+
+use object::{elf, macho, RelocationKind};
+use roc_error_macros::internal_error;
+
+/// `nop`, used to pad over the parts of a PLT entry or stub that are no longer needed.
+pub const NOP: u32 = 0xd503201f;
+
+const BRANCH_MASK: u32 = 0x7c00_0000;
+const BRANCH: u32 = 0x1400_0000; // both `b` and `bl`, which differ only in the top bit
+const IMM26_MASK: u32 = 0x03ff_ffff;
+
+/// If `inst` is a `b` or `bl` located at `address`, the address it branches to.
+pub fn branch_target(inst: u32, address: u64) -> Option<u64> {
+    if inst & BRANCH_MASK == BRANCH {
+        // Sign extend the 26 bit word offset, then turn it into a byte offset.
+        let offset = (((inst & IMM26_MASK) << 6) as i32 >> 6) as i64 * 4;
+
+        Some((address as i64 + offset) as u64)
+    } else {
+        None
+    }
+}
+
+/// `b` to a location `offset` bytes away from the branch itself.
+pub fn branch(offset: i64) -> u32 {
+    set_branch_offset(BRANCH, offset)
+}
+
+/// Changes where the `b` or `bl` in `inst` branches to, keeping everything else about it.
+pub fn set_branch_offset(inst: u32, offset: i64) -> u32 {
+    if offset % 4 != 0 || !(-(1 << 27)..(1 << 27)).contains(&offset) {
+        internal_error!(
+            "A branch of {:+x} bytes is out of range for aarch64; the app is too far from the host",
+            offset
+        );
+    }
+
+    (inst & !IMM26_MASK) | ((offset >> 2) as u32 & IMM26_MASK)
+}
+
+/// The relocations that show up in aarch64 app objects, other than plain offsets. ELF and Mach-O
+/// call them different things, but they are applied the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relocation {
+    /// The 26 bit word offset of a `b` or `bl`.
+    Branch26,
+    /// The 21 bit page offset of an `adrp`.
+    Page21,
+    /// The low 12 bits of an address, used by the `add` or load/store that follows an `adrp`.
+    PageOffset12,
+    /// An `adrp` of the page of a GOT entry.
+    GotPage21,
+    /// A load of a GOT entry, following a `GotPage21`.
+    GotPageOffset12,
+}
+
+impl Relocation {
+    pub fn from_kind(kind: RelocationKind) -> Option<Self> {
+        use Relocation::*;
+
+        match kind {
+            RelocationKind::Elf(elf::R_AARCH64_CALL26 | elf::R_AARCH64_JUMP26) => Some(Branch26),
+            RelocationKind::Elf(elf::R_AARCH64_ADR_PREL_PG_HI21) => Some(Page21),
+            RelocationKind::Elf(
+                elf::R_AARCH64_ADD_ABS_LO12_NC
+                | elf::R_AARCH64_LDST8_ABS_LO12_NC
+                | elf::R_AARCH64_LDST16_ABS_LO12_NC
+                | elf::R_AARCH64_LDST32_ABS_LO12_NC
+                | elf::R_AARCH64_LDST64_ABS_LO12_NC
+                | elf::R_AARCH64_LDST128_ABS_LO12_NC,
+            ) => Some(PageOffset12),
+            RelocationKind::Elf(elf::R_AARCH64_ADR_GOT_PAGE) => Some(GotPage21),
+            RelocationKind::Elf(elf::R_AARCH64_LD64_GOT_LO12_NC) => Some(GotPageOffset12),
+            RelocationKind::MachO { value, .. } => match value {
+                macho::ARM64_RELOC_BRANCH26 => Some(Branch26),
+                macho::ARM64_RELOC_PAGE21 => Some(Page21),
+                macho::ARM64_RELOC_PAGEOFF12 => Some(PageOffset12),
+                macho::ARM64_RELOC_GOT_LOAD_PAGE21 => Some(GotPage21),
+                macho::ARM64_RELOC_GOT_LOAD_PAGEOFF12 => Some(GotPageOffset12),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Patches `inst`, located at `address`, to refer to `target`.
+    ///
+    /// Every symbol is defined in either the app or the host by the time we link, so there is no
+    /// need for a GOT: GOT loads are relaxed into computing the address directly, the same way other
+    /// linkers do for symbols that turn out to be local.
+    pub fn apply(self, inst: u32, address: u64, target: u64) -> u32 {
+        match self {
+            Relocation::Branch26 => set_branch_offset(inst, target as i64 - address as i64),
+            Relocation::Page21 | Relocation::GotPage21 => {
+                let pages = ((target & !0xfff) as i64 - (address & !0xfff) as i64) >> 12;
+
+                if !(-(1 << 20)..(1 << 20)).contains(&pages) {
+                    internal_error!("An adrp of {:+x} pages is out of range for aarch64", pages);
+                }
+
+                let immlo = (pages as u32 & 0x3) << 29;
+                let immhi = ((pages >> 2) as u32 & 0x7ffff) << 5;
+
+                (inst & !(0x3 << 29 | 0x7ffff << 5)) | immlo | immhi
+            }
+            Relocation::PageOffset12 => {
+                let offset = target as u32 & 0xfff;
+
+                set_imm12(inst, offset >> load_store_scale(inst))
+            }
+            Relocation::GotPageOffset12 => {
+                // `ldr xt, [xn, #got_entry]` becomes `add xt, xn, #target`
+                let registers = inst & 0x3ff;
+
+                ADD_IMMEDIATE_64 | ((target as u32 & 0xfff) << 10) | registers
+            }
+        }
+    }
+}
+
+const ADD_IMMEDIATE_64: u32 = 0x9100_0000;
+
+fn set_imm12(inst: u32, imm12: u32) -> u32 {
+    (inst & !(0xfff << 10)) | ((imm12 & 0xfff) << 10)
+}
+
+/// The unsigned offset of a load or store is scaled by the size of what it accesses, so the low
+/// 12 bits of an address need to be shifted down by this much. It is 0 for `add`.
+fn load_store_scale(inst: u32) -> u32 {
+    let is_load_store_unsigned_offset = inst & 0x3b00_0000 == 0x3900_0000;
+
+    if is_load_store_unsigned_offset {
+        let size = inst >> 30;
+        let is_simd = inst & (1 << 26) != 0;
+        let is_128_bit = is_simd && size == 0 && inst & (1 << 23) != 0;
+
+        if is_128_bit {
+            4
+        } else {
+            size
+        }
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BL_0: u32 = 0x9400_0000;
+    const ADRP_X8: u32 = 0x9000_0008;
+    const ADD_X8_X8: u32 = 0x9100_0108;
+    const LDR_W0_X8: u32 = 0xb940_0100;
+    const LDR_X0_X8: u32 = 0xf940_0100;
+    const LDRB_W0_X8: u32 = 0x3940_0100;
+    const LDR_Q0_X8: u32 = 0x3dc0_0100;
+
+    #[test]
+    fn branch_targets() {
+        assert_eq!(branch_target(0x9400_0004, 0x1000), Some(0x1010));
+        assert_eq!(branch_target(0x17ff_ffff, 0x1000), Some(0xffc));
+        assert_eq!(branch_target(NOP, 0x1000), None);
+        assert_eq!(branch_target(ADRP_X8, 0x1000), None);
+    }
+
+    #[test]
+    fn patched_branches_round_trip() {
+        for offset in [0, 4, -4, 0x7ff_fffc, -0x800_0000] {
+            let patched = set_branch_offset(BL_0, offset);
+
+            assert_eq!(patched & !IMM26_MASK, BL_0);
+            assert_eq!(
+                branch_target(patched, 0x1000_0000),
+                Some((0x1000_0000 + offset) as u64)
+            );
+        }
+
+        assert_eq!(branch(8), 0x1400_0002);
+    }
+
+    #[test]
+    #[should_panic]
+    fn branch_out_of_range() {
+        set_branch_offset(BL_0, 1 << 27);
+    }
+
+    #[test]
+    fn page21() {
+        // adrp x8, 0x3000 from 0x1234 is 2 pages forward: immlo = 2, immhi = 0
+        assert_eq!(
+            Relocation::Page21.apply(ADRP_X8, 0x1234, 0x3456),
+            ADRP_X8 | 2 << 29
+        );
+
+        // 5 pages back: -5 = 0b...11011, so immlo = 0b11 and immhi is all ones but for the lowest bit
+        let patched = Relocation::Page21.apply(ADRP_X8, 0x6000, 0x1000);
+        assert_eq!(patched >> 29 & 0x3, 0b11);
+        assert_eq!(patched >> 5 & 0x7ffff, 0x7fffe);
+        assert_eq!(patched & 0x9f00_001f, ADRP_X8);
+    }
+
+    #[test]
+    fn page_offset12_scales_by_access_size() {
+        let target = 0x5678;
+
+        assert_eq!(
+            Relocation::PageOffset12.apply(ADD_X8_X8, 0, target),
+            ADD_X8_X8 | 0x678 << 10
+        );
+        assert_eq!(
+            Relocation::PageOffset12.apply(LDRB_W0_X8, 0, target),
+            LDRB_W0_X8 | 0x678 << 10
+        );
+        assert_eq!(
+            Relocation::PageOffset12.apply(LDR_W0_X8, 0, target),
+            LDR_W0_X8 | (0x678 >> 2) << 10
+        );
+        assert_eq!(
+            Relocation::PageOffset12.apply(LDR_X0_X8, 0, target),
+            LDR_X0_X8 | (0x678 >> 3) << 10
+        );
+        assert_eq!(
+            Relocation::PageOffset12.apply(LDR_Q0_X8, 0, target),
+            LDR_Q0_X8 | (0x678 >> 4) << 10
+        );
+    }
+
+    #[test]
+    fn got_loads_are_relaxed() {
+        assert_eq!(
+            Relocation::GotPageOffset12.apply(LDR_X0_X8, 0, 0x5678),
+            0x9100_0000 | 0x678 << 10 | 8 << 5
+        );
+    }
+
+    #[test]
+    fn relocation_kinds() {
+        assert_eq!(
+            Relocation::from_kind(RelocationKind::Elf(elf::R_AARCH64_CALL26)),
+            Some(Relocation::Branch26)
+        );
+        assert_eq!(
+            Relocation::from_kind(RelocationKind::Elf(elf::R_AARCH64_LDST64_ABS_LO12_NC)),
+            Some(Relocation::PageOffset12)
+        );
+        assert_eq!(
+            Relocation::from_kind(RelocationKind::MachO {
+                value: macho::ARM64_RELOC_PAGE21,
+                relative: true
+            }),
+            Some(Relocation::Page21)
+        );
+        assert_eq!(Relocation::from_kind(RelocationKind::Relative), None);
+    }
+}
@@ -26,14 +26,71 @@ use std::time::{Duration, Instant};
 use target_lexicon::Triple;
 use tempfile::Builder;
 
+mod aarch64;
 mod metadata;
 use metadata::VirtualOffset;
 
 const MIN_SECTION_ALIGNMENT: usize = 0x40;
 
-// TODO: Analyze if this offset is always correct.
-const PLT_ADDRESS_OFFSET: u64 = 0x10;
-const STUB_ADDRESS_OFFSET: u64 = 0x06;
+/// aarch64 code finds data with `adrp`, which works in 4k pages,
+/// so anything we shift on aarch64 has to move by whole pages.
+const AARCH64_PAGE_SIZE: u64 = 0x1000;
+
+/// Where the entries of the PLT (or the `__stubs` section on Mach-O) are,
+/// and how big each one is. Entry `i` is at `header_size + i * entry_size`.
+// TODO: Analyze if these offsets are always correct.
+struct PltLayout {
+    header_size: u64,
+    entry_size: u64,
+}
+
+fn plt_layout(target: &Triple) -> PltLayout {
+    use target_lexicon::{Architecture, BinaryFormat};
+
+    let (header_size, entry_size) = match (target.binary_format, target.architecture) {
+        (BinaryFormat::Elf, Architecture::X86_64) => (0x10, 0x10),
+        (BinaryFormat::Elf, Architecture::Aarch64(_)) => (0x20, 0x10),
+        // Mach-O stubs have no header: `jmp *ptr(%rip)` on x86_64,
+        // and `adrp`, `ldr`, `br` on arm64.
+        (BinaryFormat::Macho, Architecture::X86_64) => (0, 6),
+        (BinaryFormat::Macho, Architecture::Aarch64(_)) => (0, 12),
+        _ => {
+            // We should have verified this via supported() before calling this function
+            unreachable!()
+        }
+    };
+
+    PltLayout {
+        header_size,
+        entry_size,
+    }
+}
+
+/// The ELF dynamic relocation types we look for, which are numbered differently per architecture.
+struct ElfDynamicRelocations {
+    jump_slot: u32,
+    glob_dat: u32,
+    relative: u32,
+}
+
+fn elf_dynamic_relocations(target: &Triple) -> ElfDynamicRelocations {
+    match target.architecture {
+        target_lexicon::Architecture::X86_64 => ElfDynamicRelocations {
+            jump_slot: elf::R_X86_64_JUMP_SLOT,
+            glob_dat: elf::R_X86_64_GLOB_DAT,
+            relative: elf::R_X86_64_RELATIVE,
+        },
+        target_lexicon::Architecture::Aarch64(_) => ElfDynamicRelocations {
+            jump_slot: elf::R_AARCH64_JUMP_SLOT,
+            glob_dat: elf::R_AARCH64_GLOB_DAT,
+            relative: elf::R_AARCH64_RELATIVE,
+        },
+        _ => {
+            // We should have verified this via supported() before calling this function
+            unreachable!()
+        }
+    }
+}
 
 struct ElfDynamicDeps {
     got_app_syms: Vec<(String, usize)>,
@@ -53,21 +110,43 @@ fn report_timing(label: &str, duration: Duration) {
     println!("\t{:9.3} ms   {}", duration.as_secs_f64() * 1000.0, label,);
 }
 
+/// Whether the host next to `host_input_path` can be rebuilt for surgical linking. Cargo and Swift
+/// hosts can only be built as executables, so shared libraries with those hosts need the legacy
+/// linker.
+pub fn supported_host(link_type: LinkType, host_input_path: &Path) -> bool {
+    if link_type != LinkType::Dylib {
+        return true;
+    }
+
+    // The same order in which `rebuild_host` looks for the host's sources
+    let host_src = ["host.zig", "Cargo.toml", "host.rs", "host.c", "host.swift"]
+        .into_iter()
+        .find(|name| host_input_path.with_file_name(name).exists());
+
+    !matches!(host_src, Some("Cargo.toml" | "host.swift"))
+}
+
 pub fn supported(link_type: LinkType, target: &Triple) -> bool {
     matches!(
         (link_type, target),
         (
-            LinkType::Executable,
+            LinkType::Executable | LinkType::Dylib,
             Triple {
-                architecture: target_lexicon::Architecture::X86_64,
+                architecture: target_lexicon::Architecture::X86_64
+                    | target_lexicon::Architecture::Aarch64(_),
                 operating_system: target_lexicon::OperatingSystem::Linux,
                 binary_format: target_lexicon::BinaryFormat::Elf,
                 ..
-            } // | Triple {
-              //     operating_system: target_lexicon::OperatingSystem::Darwin,
-              //     binary_format: target_lexicon::BinaryFormat::Macho,
-              //     ..
-              // }
+            }
+        ) | (
+            LinkType::Executable,
+            Triple {
+                architecture: target_lexicon::Architecture::X86_64
+                    | target_lexicon::Architecture::Aarch64(_),
+                operating_system: target_lexicon::OperatingSystem::Darwin,
+                binary_format: target_lexicon::BinaryFormat::Macho,
+                ..
+            }
         )
     )
 }
@@ -75,6 +154,7 @@ pub fn supported(link_type: LinkType, target: &Triple) -> bool {
 pub fn build_and_preprocess_host(
     opt_level: OptLevel,
     target: &Triple,
+    link_type: LinkType,
     host_input_path: &Path,
     preprocessed_host_path: &Path,
     exposed_to_host: Vec<String>,
@@ -82,7 +162,13 @@ pub fn build_and_preprocess_host(
 ) {
     let dummy_lib = host_input_path.with_file_name("libapp.so");
     generate_dynamic_lib(target, exposed_to_host, exported_closure_types, &dummy_lib);
-    rebuild_host(opt_level, target, host_input_path, Some(&dummy_lib));
+    rebuild_host(
+        opt_level,
+        target,
+        host_input_path,
+        Some(&dummy_lib),
+        link_type,
+    );
     let dynhost = host_input_path.with_file_name("dynhost");
     let metadata = host_input_path.with_file_name("metadata");
     // let prehost = host_input_path.with_file_name("preprocessedhost");
@@ -138,6 +224,7 @@ fn generate_dynamic_lib(
     };
     let obj_arch = match target.architecture {
         target_lexicon::Architecture::X86_64 => Architecture::X86_64,
+        target_lexicon::Architecture::Aarch64(_) => Architecture::Aarch64,
         _ => {
             // We should have verified this via supported() before calling this function
             unreachable!()
@@ -357,8 +444,11 @@ pub fn preprocess(
     let mut app_func_addresses: MutMap<u64, &str> = MutMap::default();
     let mut macho_load_so_offset = None;
 
+    let plt_layout = plt_layout(target);
+
     match target.binary_format {
         target_lexicon::BinaryFormat::Elf => {
+            let jump_slot = elf_dynamic_relocations(target).jump_slot;
             let plt_relocs = (match exec_obj.dynamic_relocations() {
                 Some(relocs) => relocs,
                 None => {
@@ -366,7 +456,7 @@ pub fn preprocess(
                 }
             })
             .filter_map(|(_, reloc)| {
-                if let RelocationKind::Elf(7) = reloc.kind() {
+                if reloc.kind() == RelocationKind::Elf(jump_slot) {
                     Some(reloc)
                 } else {
                     None
@@ -375,8 +465,10 @@ pub fn preprocess(
             for (i, reloc) in plt_relocs.enumerate() {
                 for symbol in app_syms.iter() {
                     if reloc.target() == RelocationTarget::Symbol(symbol.index()) {
-                        let func_address = (i as u64 + 1) * PLT_ADDRESS_OFFSET + plt_address;
-                        let func_offset = (i as u64 + 1) * PLT_ADDRESS_OFFSET + plt_offset;
+                        let plt_entry_offset =
+                            plt_layout.header_size + i as u64 * plt_layout.entry_size;
+                        let func_address = plt_address + plt_entry_offset;
+                        let func_offset = plt_offset + plt_entry_offset;
                         app_func_addresses.insert(func_address, symbol.name().unwrap());
                         md.plt_addresses.insert(
                             symbol.name().unwrap().to_string(),
@@ -388,7 +480,7 @@ pub fn preprocess(
             }
         }
         target_lexicon::BinaryFormat::Macho => {
-            use macho::{DyldInfoCommand, DylibCommand, Section64, SegmentCommand64};
+            use macho::{DylibCommand, DysymtabCommand, Section64, SegmentCommand64};
 
             let exec_header =
                 load_struct_inplace::<macho::MachHeader64<LittleEndian>>(exec_data, 0);
@@ -398,8 +490,11 @@ pub fn preprocess(
 
             let mut stubs_symbol_index = None;
             let mut stubs_symbol_count = None;
+            let mut indirect_symbols_offset = None;
+
+            let shared_lib_filename = shared_lib.file_name();
 
-            'cmds: for _ in 0..num_load_cmds {
+            for _ in 0..num_load_cmds {
                 let info =
                     load_struct_inplace::<macho::LoadCommand<LittleEndian>>(exec_data, offset);
                 let cmd = info.cmd.get(NativeEndian);
@@ -421,68 +516,17 @@ pub fn preprocess(
                         for section_info in sections_info {
                             if &section_info.sectname[0..7] == b"__stubs" {
                                 stubs_symbol_index = Some(section_info.reserved1.get(NativeEndian));
-                                stubs_symbol_count =
-                                    Some(section_info.size.get(NativeEndian) / STUB_ADDRESS_OFFSET);
-
-                                break 'cmds;
+                                stubs_symbol_count = Some(
+                                    section_info.size.get(NativeEndian) / plt_layout.entry_size,
+                                );
                             }
                         }
                     }
-                }
-
-                offset += cmdsize as usize;
-            }
-
-            let stubs_symbol_index = stubs_symbol_index.unwrap_or_else(|| {
-                panic!("Could not find stubs symbol index.");
-            });
-            let stubs_symbol_count = stubs_symbol_count.unwrap_or_else(|| {
-                panic!("Could not find stubs symbol count.");
-            });
-
-            // Reset offset before looping through load commands again
-            offset = mem::size_of_val(exec_header);
-
-            let shared_lib_filename = shared_lib.file_name();
-
-            for _ in 0..num_load_cmds {
-                let info =
-                    load_struct_inplace::<macho::LoadCommand<LittleEndian>>(exec_data, offset);
-                let cmd = info.cmd.get(NativeEndian);
-                let cmdsize = info.cmdsize.get(NativeEndian);
-
-                if cmd == macho::LC_DYLD_INFO_ONLY {
+                } else if cmd == macho::LC_DYSYMTAB {
                     let info =
-                        load_struct_inplace::<DyldInfoCommand<LittleEndian>>(exec_data, offset);
-
-                    let lazy_bind_offset = info.lazy_bind_off.get(NativeEndian) as usize;
+                        load_struct_inplace::<DysymtabCommand<LittleEndian>>(exec_data, offset);
 
-                    let lazy_bind_symbols = mach_object::LazyBind::parse(
-                        &exec_data[lazy_bind_offset..],
-                        mem::size_of::<usize>(),
-                    );
-
-                    // Find all the lazily-bound roc symbols
-                    // (e.g. "_roc__mainForHost_1_exposed")
-                    // For Macho, we may need to deal with some GOT stuff here as well.
-                    for (i, symbol) in lazy_bind_symbols
-                        .skip(stubs_symbol_index as usize)
-                        .take(stubs_symbol_count as usize)
-                        .enumerate()
-                    {
-                        if let Some(sym) = app_syms
-                            .iter()
-                            .find(|app_sym| app_sym.name() == Ok(&symbol.name))
-                        {
-                            let func_address = (i as u64 + 1) * STUB_ADDRESS_OFFSET + plt_address;
-                            let func_offset = (i as u64 + 1) * STUB_ADDRESS_OFFSET + plt_offset;
-                            app_func_addresses.insert(func_address, sym.name().unwrap());
-                            md.plt_addresses.insert(
-                                sym.name().unwrap().to_string(),
-                                (func_offset, func_address),
-                            );
-                        }
-                    }
+                    indirect_symbols_offset = Some(info.indirectsymoff.get(NativeEndian));
                 } else if cmd == macho::LC_LOAD_DYLIB {
                     let info = load_struct_inplace::<DylibCommand<LittleEndian>>(exec_data, offset);
                     let name_offset = info.dylib.name.offset.get(NativeEndian) as usize;
@@ -520,6 +564,41 @@ pub fn preprocess(
 
                 offset += cmdsize as usize;
             }
+
+            let stubs_symbol_index = stubs_symbol_index.unwrap_or_else(|| {
+                panic!("Could not find stubs symbol index.");
+            });
+            let stubs_symbol_count = stubs_symbol_count.unwrap_or_else(|| {
+                panic!("Could not find stubs symbol count.");
+            });
+            let indirect_symbols_offset = indirect_symbols_offset.unwrap_or_else(|| {
+                panic!("Could not find the indirect symbol table.");
+            });
+
+            // Stub `i` calls the symbol at entry `reserved1 + i` of the indirect symbol table.
+            // Those entries are indices into the regular symbol table,
+            // which is what `object` indexes symbols by too.
+            let indirect_symbols = load_structs_inplace::<endian::U32<LittleEndian>>(
+                exec_data,
+                indirect_symbols_offset as usize + stubs_symbol_index as usize * 4,
+                stubs_symbol_count as usize,
+            );
+
+            for (i, symbol_index) in indirect_symbols.iter().enumerate() {
+                let symbol_index = symbol_index.get(NativeEndian) as usize;
+
+                if let Some(sym) = app_syms
+                    .iter()
+                    .find(|app_sym| app_sym.index().0 == symbol_index)
+                {
+                    let stub_offset = plt_layout.header_size + i as u64 * plt_layout.entry_size;
+                    let func_address = plt_address + stub_offset;
+                    let func_offset = plt_offset + stub_offset;
+                    app_func_addresses.insert(func_address, sym.name().unwrap());
+                    md.plt_addresses
+                        .insert(sym.name().unwrap().to_string(), (func_offset, func_address));
+                }
+            }
         }
         _ => {
             // We should have verified this via supported() before calling this function
@@ -590,6 +669,52 @@ pub fn preprocess(
                 internal_error!("Failed to load text section, {:+x?}: {}", sec, err);
             }
         };
+
+        if let target_lexicon::Architecture::Aarch64(_) = target.architecture {
+            // Every instruction is 4 bytes and aligned, so we can just look at each one in turn.
+            for (i, word) in data.chunks_exact(4).enumerate() {
+                let inst = u32::from_le_bytes(<[u8; 4]>::try_from(word).unwrap());
+                let inst_address = sec.address() + 4 * i as u64;
+
+                let branch_target = match aarch64::branch_target(inst, inst_address) {
+                    Some(branch_target) => branch_target,
+                    None => continue,
+                };
+
+                if let Some(func_name) = app_func_addresses.get(&branch_target) {
+                    if compressed {
+                        internal_error!(
+                            "Surgical linking does not work with compressed text sections: {:+x?}",
+                            sec
+                        );
+                    }
+
+                    let offset = file_offset + 4 * i as u64;
+                    if verbose {
+                        println!(
+                            "Found branch from {:+x} to {:+x}({})",
+                            inst_address, branch_target, func_name
+                        );
+                        println!(
+                            "\tNeed to surgically replace the instruction at file offset {:+x}",
+                            offset,
+                        );
+                    }
+                    // aarch64 branches are relative to the branch itself, not the next instruction.
+                    md.surgeries
+                        .get_mut(*func_name)
+                        .unwrap()
+                        .push(metadata::SurgeryEntry {
+                            file_offset: offset,
+                            virtual_offset: VirtualOffset::Relative(inst_address),
+                            size: 4,
+                        });
+                }
+            }
+
+            continue;
+        }
+
         let mut decoder = Decoder::with_ip(64, &data, sec.address(), DecoderOptions::NONE);
         let mut inst = Instruction::default();
 
@@ -700,7 +825,7 @@ pub fn preprocess(
                     dynamic_lib_count,
                     shared_lib_index,
                 } = scan_elf_dynamic_deps(
-                    &exec_obj, &mut md, &app_syms, shared_lib, exec_data, verbose,
+                    &exec_obj, &mut md, &app_syms, shared_lib, exec_data, target, verbose,
                 );
 
                 scanning_dynamic_deps_duration = scanning_dynamic_deps_start.elapsed();
@@ -716,6 +841,7 @@ pub fn preprocess(
                     &got_sections,
                     dynamic_lib_count,
                     shared_lib_index,
+                    target,
                     verbose,
                 )
             }
@@ -761,7 +887,10 @@ pub fn preprocess(
                     // TODO this is correct on modern Macs (they align to the page size)
                     // but maybe someone can override the alignment somehow? Maybe in the
                     // future this could change? Is there some way to make this more future-proof?
-                    md.load_align_constraint = 4096;
+                    md.load_align_constraint = match target.architecture {
+                        target_lexicon::Architecture::Aarch64(_) => 0x4000,
+                        _ => 0x1000,
+                    };
 
                     gen_macho_le(
                         exec_data,
@@ -859,7 +988,7 @@ fn gen_macho_le(
     out_filename: &str,
     macho_load_so_offset: usize,
     _target: &Triple,
-    _verbose: bool,
+    verbose: bool,
 ) -> (MmapMut, File) {
     // Just adding some extra context/useful info here.
     // I was talking to Jakub from the Zig team about macho linking and here are some useful comments:
@@ -877,36 +1006,52 @@ fn gen_macho_le(
     let exec_header = load_struct_inplace::<macho::MachHeader64<LittleEndian>>(exec_data, 0);
     let num_load_cmds = exec_header.ncmds.get(NativeEndian);
     let size_of_cmds = exec_header.sizeofcmds.get(NativeEndian) as usize;
+    let end_of_cmds = size_of_cmds + mem::size_of_val(exec_header);
 
-    // Add a new text segment and data segment
-    let segment_cmd_size = mem::size_of::<SegmentCommand64<LittleEndian>>();
-    let section_size = mem::size_of::<Section64<LittleEndian>>();
-
-    // We need the full command size, including the dynamic-length string at the end.
-    // To get that, we need to load the command.
-    let info =
-        load_struct_inplace::<macho::LoadCommand<LittleEndian>>(exec_data, macho_load_so_offset);
-    let total_cmd_size = info.cmdsize.get(NativeEndian) as usize;
-
-    // ======================== Important TODO ==========================
-    // TODO: we accidentally instroduced a big change here.
-    // We use a mix of added_bytes and md.added_byte_count.
-    // Theses should proabably be the same variable.
-    // Also, I just realized that I have been shifting a lot of virtual offsets.
-    // This should not be necessary. If we add a fully 4k of buffer to the file, all of the virtual offsets will still stay aligned.
-    // So a lot of the following work can probably be commented out if we fix that.
-    // Of coruse, it will cost about 4k bytes to a final executable.
-    // This is what the elf version currently does.
-    // Theoretically it is not needed (if we update all virtual addresses in the binary),
-    // but I definitely ran into problems with elf when not adding this extra buffering.
+    // Unlike with elf, nothing in the host moves: the app gets added after the host's code and data
+    // during surgery, and the load commands for it go in the padding linkers leave after the
+    // existing load commands. Make sure that padding is big enough.
+    let mut first_section_offset = exec_data.len();
+    let mut offset = mem::size_of_val(exec_header);
+    for _ in 0..num_load_cmds {
+        let info = load_struct_inplace::<macho::LoadCommand<LittleEndian>>(exec_data, offset);
+        let cmd_size = info.cmdsize.get(NativeEndian) as usize;
 
-    // Copy header and shift everything to enable more program sections.
-    let added_byte_count = ((2 * segment_cmd_size) + (2 * section_size) - total_cmd_size) as u64;
-    md.added_byte_count = added_byte_count
-        // add some alignment padding
-        + (MIN_SECTION_ALIGNMENT as u64 - added_byte_count % MIN_SECTION_ALIGNMENT as u64);
+        if info.cmd.get(NativeEndian) == macho::LC_SEGMENT_64 {
+            let cmd = load_struct_inplace::<SegmentCommand64<LittleEndian>>(exec_data, offset);
+            let sections = load_structs_inplace::<Section64<LittleEndian>>(
+                exec_data,
+                offset + mem::size_of_val(cmd),
+                cmd.nsects.get(NativeEndian) as usize,
+            );
 
-    md.exec_len = exec_data.len() as u64 + md.added_byte_count;
+            for section in sections {
+                // Zero is used for things like BSS that don't exist in the file.
+                let section_offset = section.offset.get(NativeEndian) as usize;
+                if section_offset > 0 {
+                    first_section_offset = first_section_offset.min(section_offset);
+                }
+            }
+        }
+
+        offset += cmd_size;
+    }
+
+    let header_padding = first_section_offset - end_of_cmds;
+    if verbose {
+        println!("Padding after load commands: {:+x}", header_padding);
+    }
+    if header_padding < MACHO_SEGMENT_WITH_SECTION_SIZE * 2 {
+        user_error!(
+            "The host executable only has {} bytes of room after its load commands, but the surgical linker needs {}.\nTry linking the host with `-Wl,-headerpad,0x1000`.",
+            header_padding,
+            MACHO_SEGMENT_WITH_SECTION_SIZE * 2
+        );
+    }
+
+    md.added_byte_count = 0;
+    md.exec_len = exec_data.len() as u64;
+    md.macho_cmd_loc = end_of_cmds as u64;
 
     let out_file = fs::OpenOptions::new()
         .read(true)
@@ -920,611 +1065,146 @@ fn gen_macho_le(
         .unwrap_or_else(|e| internal_error!("{}", e));
     let mut out_mmap =
         unsafe { MmapMut::map_mut(&out_file).unwrap_or_else(|e| internal_error!("{}", e)) };
-    let end_of_cmds = size_of_cmds + mem::size_of_val(exec_header);
-
-    // "Delete" the dylib load command - by copying all the bytes before it
-    // and all the bytes after it, while skipping over its bytes.
-    // It has a dynamic-length string at the end that we also need to delete,
-    // in addition to the header.
-    out_mmap[..macho_load_so_offset].copy_from_slice(&exec_data[..macho_load_so_offset]);
 
-    out_mmap[macho_load_so_offset..end_of_cmds - total_cmd_size]
-        .copy_from_slice(&exec_data[macho_load_so_offset + total_cmd_size..end_of_cmds]);
+    out_mmap.copy_from_slice(exec_data);
 
-    // Copy the rest of the file, leaving a gap for the surgical linking to add our 2 commands
-    // (which happens after preprocessing), and some zero padding at the end for alignemnt.
-    // (It seems to cause bugs if that padding isn't there!)
-    let rest_of_data = &exec_data[end_of_cmds..];
-    let start_index = end_of_cmds + md.added_byte_count as usize;
+    // The app will be linked right into the executable, so it must not go looking for the dummy
+    // library at runtime. Deleting the load command would renumber the dylibs after it, which
+    // every bound symbol refers to by number, so mark it as weak instead: dyld is fine with a
+    // weak dylib being missing. Any host references left to its symbols get resolved to null,
+    // but surgery redirects every call to them anyway.
+    let cmd = load_struct_inplace_mut::<macho::LoadCommand<LittleEndian>>(
+        &mut out_mmap,
+        macho_load_so_offset,
+    );
+    cmd.cmd.set(LittleEndian, macho::LC_LOAD_WEAK_DYLIB);
 
-    out_mmap[start_index..start_index + rest_of_data.len()].copy_from_slice(rest_of_data);
+    (out_mmap, out_file)
+}
 
-    let out_header = load_struct_inplace_mut::<macho::MachHeader64<LittleEndian>>(&mut out_mmap, 0);
+#[allow(clippy::too_many_arguments)]
+fn gen_elf_le(
+    exec_data: &[u8],
+    md: &mut metadata::Metadata,
+    out_filename: &str,
+    got_app_syms: &[(String, usize)],
+    got_sections: &[(usize, usize)],
+    dynamic_lib_count: usize,
+    shared_lib_index: usize,
+    target: &Triple,
+    verbose: bool,
+) -> (MmapMut, File) {
+    let exec_header = load_struct_inplace::<elf::FileHeader64<LittleEndian>>(exec_data, 0);
+    let ph_offset = exec_header.e_phoff.get(NativeEndian);
+    let ph_ent_size = exec_header.e_phentsize.get(NativeEndian);
+    let ph_num = exec_header.e_phnum.get(NativeEndian);
+    let sh_offset = exec_header.e_shoff.get(NativeEndian);
+    let sh_ent_size = exec_header.e_shentsize.get(NativeEndian);
+    let sh_num = exec_header.e_shnum.get(NativeEndian);
 
-    // TODO: this needs to change to adding the 2 new commands when we are ready.
-    // -1 because we're deleting 1 load command and then NOT adding 2 new ones.
-    {
-        let added_bytes = -(total_cmd_size as isize); // TODO: Change when add the new sections.
-        out_header.ncmds.set(LittleEndian, num_load_cmds - 1);
-        out_header
-            .sizeofcmds
-            .set(LittleEndian, (size_of_cmds as isize + added_bytes) as u32);
+    if verbose {
+        println!();
+        println!("PH Offset: {:+x}", ph_offset);
+        println!("PH Entry Size: {}", ph_ent_size);
+        println!("PH Entry Count: {}", ph_num);
+        println!("SH Offset: {:+x}", sh_offset);
+        println!("SH Entry Size: {}", sh_ent_size);
+        println!("SH Entry Count: {}", sh_num);
     }
 
-    // Go through every command and shift it by added_bytes if it's absolute, unless it's inside the command header
-    let mut offset = mem::size_of_val(exec_header);
+    // Copy header and shift everything to enable more program sections.
+    let added_header_count = 2;
+    md.added_byte_count = ph_ent_size as u64 * added_header_count;
+    let shift_alignment = match target.architecture {
+        // Shifting by whole pages keeps every `adrp` in the host pointing at the right page.
+        target_lexicon::Architecture::Aarch64(_) => AARCH64_PAGE_SIZE,
+        _ => MIN_SECTION_ALIGNMENT as u64,
+    };
+    md.added_byte_count =
+        md.added_byte_count + (shift_alignment - md.added_byte_count % shift_alignment);
+    let ph_end = ph_offset as usize + ph_num as usize * ph_ent_size as usize;
+    let physical_shift_start = ph_end as u64;
 
-    // TODO: Add this back in the future when needed.
-    // let cpu_type = match target.architecture {
-    //     target_lexicon::Architecture::X86_64 => macho::CPU_TYPE_X86_64,
-    //     target_lexicon::Architecture::Aarch64(_) => macho::CPU_TYPE_ARM64,
-    //     _ => {
-    //         // We should have verified this via supported() before calling this function
-    //         unreachable!()
-    //     }
-    // };
-
-    // minus one because we "deleted" a load command
-    for _ in 0..(num_load_cmds - 1) {
-        let info = load_struct_inplace::<macho::LoadCommand<LittleEndian>>(&out_mmap, offset);
-        let cmd_size = info.cmdsize.get(NativeEndian) as usize;
+    md.exec_len = exec_data.len() as u64 + md.added_byte_count;
+    let out_file = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(out_filename)
+        .unwrap_or_else(|e| internal_error!("{}", e));
+    out_file
+        .set_len(md.exec_len)
+        .unwrap_or_else(|e| internal_error!("{}", e));
+    let mut out_mmap =
+        unsafe { MmapMut::map_mut(&out_file).unwrap_or_else(|e| internal_error!("{}", e)) };
 
-        match info.cmd.get(NativeEndian) {
-            macho::LC_SEGMENT_64 => {
-                let cmd = load_struct_inplace_mut::<macho::SegmentCommand64<LittleEndian>>(
-                    &mut out_mmap,
-                    offset,
-                );
+    out_mmap[..ph_end].copy_from_slice(&exec_data[..ph_end]);
 
-                // Ignore page zero, it never moves.
-                if cmd.segname == "__PAGEZERO\0\0\0\0\0\0".as_bytes()
-                    || cmd.vmaddr.get(NativeEndian) == 0
-                {
-                    offset += cmd_size;
-                    continue;
-                }
+    let program_headers = load_structs_inplace_mut::<elf::ProgramHeader64<LittleEndian>>(
+        &mut out_mmap,
+        ph_offset as usize,
+        ph_num as usize,
+    );
+    let mut first_load_found = false;
+    let mut virtual_shift_start = 0;
+    for ph in program_headers.iter() {
+        let p_type = ph.p_type.get(NativeEndian);
+        if p_type == elf::PT_LOAD && ph.p_offset.get(NativeEndian) == 0 {
+            first_load_found = true;
+            md.load_align_constraint = ph.p_align.get(NativeEndian);
+            virtual_shift_start = physical_shift_start + ph.p_vaddr.get(NativeEndian);
+        }
+    }
+    if !first_load_found {
+        user_error!("Executable does not load any data at 0x00000000\nProbably input the wrong file as the executable");
+    }
+    if verbose {
+        println!(
+            "Shifting all data after: {:+x}({:+x})",
+            physical_shift_start, virtual_shift_start
+        );
+    }
 
-                let old_file_offest = cmd.fileoff.get(NativeEndian);
-                // The segment with file offset zero also includes the header.
-                // As such, its file offset does not change.
-                // Instead, its file size should be increased.
-                if old_file_offest > 0 {
-                    cmd.fileoff
-                        .set(LittleEndian, old_file_offest + md.added_byte_count as u64);
-                    cmd.vmaddr.set(
-                        LittleEndian,
-                        cmd.vmaddr.get(NativeEndian) + md.added_byte_count as u64,
-                    );
-                } else {
-                    cmd.filesize.set(
-                        LittleEndian,
-                        cmd.filesize.get(NativeEndian) + md.added_byte_count as u64,
-                    );
-                    cmd.vmsize.set(
-                        LittleEndian,
-                        cmd.vmsize.get(NativeEndian) + md.added_byte_count as u64,
-                    );
-                }
+    // Shift all of the program headers.
+    for ph in program_headers.iter_mut() {
+        let p_type = ph.p_type.get(NativeEndian);
+        let p_offset = ph.p_offset.get(NativeEndian);
+        if (p_type == elf::PT_LOAD && p_offset == 0) || p_type == elf::PT_PHDR {
+            // Extend length for the first segment and the program header.
+            ph.p_filesz = endian::U64::new(
+                LittleEndian,
+                ph.p_filesz.get(NativeEndian) + md.added_byte_count,
+            );
+            ph.p_memsz = endian::U64::new(
+                LittleEndian,
+                ph.p_memsz.get(NativeEndian) + md.added_byte_count,
+            );
+        } else {
+            // Shift if needed.
+            if physical_shift_start <= p_offset {
+                ph.p_offset = endian::U64::new(LittleEndian, p_offset + md.added_byte_count);
+            }
+            let p_vaddr = ph.p_vaddr.get(NativeEndian);
+            if virtual_shift_start <= p_vaddr {
+                ph.p_vaddr = endian::U64::new(LittleEndian, p_vaddr + md.added_byte_count);
+                ph.p_paddr = endian::U64::new(LittleEndian, p_vaddr + md.added_byte_count);
+            }
+        }
+    }
 
-                // let num_sections = cmd.nsects.get(NativeEndian);
-                // let sections = load_structs_inplace_mut::<macho::Section64<LittleEndian>>(
-                //     &mut out_mmap,
-                //     offset + mem::size_of::<macho::SegmentCommand64<LittleEndian>>(),
-                //     num_sections as usize,
-                // );
-                // struct Relocation {
-                //     offset: u32,
-                //     num_relocations: u32,
-                // }
-
-                // let mut relocation_offsets = Vec::with_capacity(sections.len());
-
-                // for section in sections {
-                //     section.addr.set(
-                //         LittleEndian,
-                //         section.addr.get(NativeEndian) + md.added_byte_count as u64,
-                //     );
-
-                //     // If offset is zero, don't update it.
-                //     // Zero is used for things like BSS that don't exist in the file.
-                //     let old_offset = section.offset.get(NativeEndian);
-                //     if old_offset > 0 {
-                //         section
-                //             .offset
-                //             .set(LittleEndian, old_offset + md.added_byte_count as u32);
-                //     }
-
-                //     // dbg!(&section.reloff.get(NativeEndian));
-                //     // dbg!(section.reloff.get(NativeEndian) as i32);
-                //     // dbg!(&section);
-                //     // dbg!(&md.added_byte_count);
-                //     // dbg!(String::from_utf8_lossy(&section.sectname));
-                //     if section.nreloc.get(NativeEndian) > 0 {
-                //         section.reloff.set(
-                //             LittleEndian,
-                //             section.reloff.get(NativeEndian) + md.added_byte_count as u32,
-                //         );
-                //     }
-
-                //     relocation_offsets.push(Relocation {
-                //         offset: section.reloff.get(NativeEndian),
-                //         num_relocations: section.nreloc.get(NativeEndian),
-                //     });
-                // }
-
-                // TODO FIXME this is necessary for ARM, but seems to be broken. Skipping for now since we're just targeting x86
-                // for Relocation {
-                //     offset,
-                //     num_relocations,
-                // } in relocation_offsets
-                // {
-                //     let relos = load_structs_inplace_mut::<macho::Relocation<LittleEndian>>(
-                //         &mut out_mmap,
-                //         offset as usize,
-                //         num_relocations as usize,
-                //     );
-
-                //     // TODO this has never been tested, because scattered relocations only come up on ARM!
-                //     for relo in relos.iter_mut() {
-                //         if relo.r_scattered(LittleEndian, cpu_type) {
-                //             let mut scattered_info = relo.scattered_info(NativeEndian);
-
-                //             if !scattered_info.r_pcrel {
-                //                 scattered_info.r_value += md.added_byte_count as u32;
-
-                //                 let new_info = scattered_info.relocation(LittleEndian);
-
-                //                 relo.r_word0 = new_info.r_word0;
-                //                 relo.r_word1 = new_info.r_word1;
-                //             }
-                //         }
-                //     }
-                // }
-
-                // TODO this seems to be wrong and unnecessary, and should probably be deleted.
-                // offset += num_sections as usize * mem::size_of::<macho::Section64<LittleEndian>>();
+    // Get last segment virtual address.
+    let last_segment_vaddr = program_headers
+        .iter()
+        .filter_map(|ph| {
+            if ph.p_type.get(NativeEndian) != elf::PT_GNU_STACK {
+                Some(ph.p_vaddr.get(NativeEndian) + ph.p_memsz.get(NativeEndian))
+            } else {
+                None
             }
-            macho::LC_SYMTAB => {
-                let cmd = load_struct_inplace_mut::<macho::SymtabCommand<LittleEndian>>(
-                    &mut out_mmap,
-                    offset,
-                );
-
-                let sym_offset = cmd.symoff.get(NativeEndian);
-                let num_syms = cmd.nsyms.get(NativeEndian);
-
-                if num_syms > 0 {
-                    cmd.symoff
-                        .set(LittleEndian, sym_offset + md.added_byte_count as u32);
-                }
-
-                if cmd.strsize.get(NativeEndian) > 0 {
-                    cmd.stroff.set(
-                        LittleEndian,
-                        cmd.stroff.get(NativeEndian) + md.added_byte_count as u32,
-                    );
-                }
-
-                let table = load_structs_inplace_mut::<macho::Nlist64<LittleEndian>>(
-                    &mut out_mmap,
-                    sym_offset as usize + md.added_byte_count as usize,
-                    num_syms as usize,
-                );
-
-                for entry in table {
-                    let entry_type = entry.n_type & macho::N_TYPE;
-                    if entry_type == macho::N_ABS || entry_type == macho::N_SECT {
-                        entry.n_value.set(
-                            LittleEndian,
-                            entry.n_value.get(NativeEndian) + md.added_byte_count as u64,
-                        );
-                    }
-                }
-            }
-            macho::LC_DYSYMTAB => {
-                let cmd = load_struct_inplace_mut::<macho::DysymtabCommand<LittleEndian>>(
-                    &mut out_mmap,
-                    offset,
-                );
-
-                if cmd.ntoc.get(NativeEndian) > 0 {
-                    cmd.tocoff.set(
-                        LittleEndian,
-                        cmd.tocoff.get(NativeEndian) + md.added_byte_count as u32,
-                    );
-                }
-
-                if cmd.nmodtab.get(NativeEndian) > 0 {
-                    cmd.modtaboff.set(
-                        LittleEndian,
-                        cmd.modtaboff.get(NativeEndian) + md.added_byte_count as u32,
-                    );
-                }
-
-                if cmd.nextrefsyms.get(NativeEndian) > 0 {
-                    cmd.extrefsymoff.set(
-                        LittleEndian,
-                        cmd.extrefsymoff.get(NativeEndian) + md.added_byte_count as u32,
-                    );
-                }
-
-                if cmd.nindirectsyms.get(NativeEndian) > 0 {
-                    cmd.indirectsymoff.set(
-                        LittleEndian,
-                        cmd.indirectsymoff.get(NativeEndian) + md.added_byte_count as u32,
-                    );
-                }
-
-                if cmd.nextrel.get(NativeEndian) > 0 {
-                    cmd.extreloff.set(
-                        LittleEndian,
-                        cmd.extreloff.get(NativeEndian) + md.added_byte_count as u32,
-                    );
-                }
-
-                if cmd.nlocrel.get(NativeEndian) > 0 {
-                    cmd.locreloff.set(
-                        LittleEndian,
-                        cmd.locreloff.get(NativeEndian) + md.added_byte_count as u32,
-                    );
-                }
-
-                // TODO maybe we need to update something else too - relocations maybe?
-                // I think this also has symbols that need to get moved around.
-                // Look at otool -I at least for the indirect symbols.
-            }
-            macho::LC_TWOLEVEL_HINTS => {
-                let cmd = load_struct_inplace_mut::<macho::TwolevelHintsCommand<LittleEndian>>(
-                    &mut out_mmap,
-                    offset,
-                );
-
-                if cmd.nhints.get(NativeEndian) > 0 {
-                    cmd.offset.set(
-                        LittleEndian,
-                        cmd.offset.get(NativeEndian) + md.added_byte_count as u32,
-                    );
-                }
-            }
-            macho::LC_FUNCTION_STARTS => {
-                let cmd = load_struct_inplace_mut::<macho::LinkeditDataCommand<LittleEndian>>(
-                    &mut out_mmap,
-                    offset,
-                );
-
-                if cmd.datasize.get(NativeEndian) > 0 {
-                    cmd.dataoff.set(
-                        LittleEndian,
-                        cmd.dataoff.get(NativeEndian) + md.added_byte_count as u32,
-                    );
-                    // TODO: This lists the start of every function. Which, of course, have moved.
-                    // That being said, to my understanding this section is optional and may just be debug information.
-                    // As such, updating it should not be required.
-                    // It will be more work to update due to being in "DWARF-style ULEB128" values.
-                }
-            }
-            macho::LC_DATA_IN_CODE => {
-                let (offset, size) = {
-                    let cmd = load_struct_inplace_mut::<macho::LinkeditDataCommand<LittleEndian>>(
-                        &mut out_mmap,
-                        offset,
-                    );
-
-                    if cmd.datasize.get(NativeEndian) > 0 {
-                        cmd.dataoff.set(
-                            LittleEndian,
-                            cmd.dataoff.get(NativeEndian) + md.added_byte_count as u32,
-                        );
-                    }
-                    (
-                        cmd.dataoff.get(NativeEndian),
-                        cmd.datasize.get(NativeEndian),
-                    )
-                };
-
-                // Update every data in code entry.
-                if size > 0 {
-                    let entry_size = mem::size_of::<macho::DataInCodeEntry<LittleEndian>>();
-                    let entries = load_structs_inplace_mut::<macho::DataInCodeEntry<LittleEndian>>(
-                        &mut out_mmap,
-                        offset as usize,
-                        size as usize / entry_size,
-                    );
-                    for entry in entries.iter_mut() {
-                        entry.offset.set(
-                            LittleEndian,
-                            entry.offset.get(NativeEndian) + md.added_byte_count as u32,
-                        )
-                    }
-                }
-            }
-            macho::LC_CODE_SIGNATURE
-            | macho::LC_SEGMENT_SPLIT_INFO
-            | macho::LC_DYLIB_CODE_SIGN_DRS
-            | macho::LC_LINKER_OPTIMIZATION_HINT
-            | macho::LC_DYLD_EXPORTS_TRIE
-            | macho::LC_DYLD_CHAINED_FIXUPS => {
-                let cmd = load_struct_inplace_mut::<macho::LinkeditDataCommand<LittleEndian>>(
-                    &mut out_mmap,
-                    offset,
-                );
-
-                if cmd.datasize.get(NativeEndian) > 0 {
-                    cmd.dataoff.set(
-                        LittleEndian,
-                        cmd.dataoff.get(NativeEndian) + md.added_byte_count as u32,
-                    );
-                }
-            }
-            macho::LC_ENCRYPTION_INFO_64 => {
-                let cmd = load_struct_inplace_mut::<macho::EncryptionInfoCommand64<LittleEndian>>(
-                    &mut out_mmap,
-                    offset,
-                );
-
-                if cmd.cryptsize.get(NativeEndian) > 0 {
-                    cmd.cryptoff.set(
-                        LittleEndian,
-                        cmd.cryptoff.get(NativeEndian) + md.added_byte_count as u32,
-                    );
-                }
-            }
-            macho::LC_DYLD_INFO | macho::LC_DYLD_INFO_ONLY => {
-                let cmd = load_struct_inplace_mut::<macho::DyldInfoCommand<LittleEndian>>(
-                    &mut out_mmap,
-                    offset,
-                );
-
-                if cmd.rebase_size.get(NativeEndian) > 0 {
-                    cmd.rebase_off.set(
-                        LittleEndian,
-                        cmd.rebase_off.get(NativeEndian) + md.added_byte_count as u32,
-                    );
-                }
-
-                if cmd.bind_size.get(NativeEndian) > 0 {
-                    cmd.bind_off.set(
-                        LittleEndian,
-                        cmd.bind_off.get(NativeEndian) + md.added_byte_count as u32,
-                    );
-                }
-
-                if cmd.weak_bind_size.get(NativeEndian) > 0 {
-                    cmd.weak_bind_off.set(
-                        LittleEndian,
-                        cmd.weak_bind_off.get(NativeEndian) + md.added_byte_count as u32,
-                    );
-                }
-
-                if cmd.lazy_bind_size.get(NativeEndian) > 0 {
-                    cmd.lazy_bind_off.set(
-                        LittleEndian,
-                        cmd.lazy_bind_off.get(NativeEndian) + md.added_byte_count as u32,
-                    );
-                }
-
-                // TODO: Parse and update the related tables here.
-                // It is possible we may just need to delete things that point to stuff that will be in the roc app.
-                // We also may just be able to ignore it (lazy bindings should never run).
-                // This definitely has a list of virtual address that need to be updated.
-                // Some of them definitely will point to the roc app and should probably be removed.
-                // Also `xcrun dyldinfo` is useful for debugging this.
-            }
-            macho::LC_SYMSEG => {
-                let cmd = load_struct_inplace_mut::<macho::SymsegCommand<LittleEndian>>(
-                    &mut out_mmap,
-                    offset,
-                );
-
-                if cmd.size.get(NativeEndian) > 0 {
-                    cmd.offset.set(
-                        LittleEndian,
-                        cmd.offset.get(NativeEndian) + md.added_byte_count as u32,
-                    );
-                }
-            }
-            macho::LC_MAIN => {
-                let cmd = load_struct_inplace_mut::<macho::EntryPointCommand<LittleEndian>>(
-                    &mut out_mmap,
-                    offset,
-                );
-
-                cmd.entryoff.set(
-                    LittleEndian,
-                    cmd.entryoff.get(NativeEndian) + md.added_byte_count as u64,
-                );
-            }
-            macho::LC_NOTE => {
-                let cmd = load_struct_inplace_mut::<macho::NoteCommand<LittleEndian>>(
-                    &mut out_mmap,
-                    offset,
-                );
-
-                if cmd.size.get(NativeEndian) > 0 {
-                    cmd.offset.set(
-                        LittleEndian,
-                        cmd.offset.get(NativeEndian) + md.added_byte_count as u64,
-                    );
-                }
-            }
-            macho::LC_ID_DYLIB
-            | macho::LC_LOAD_WEAK_DYLIB
-            | macho::LC_LOAD_DYLIB
-            | macho::LC_REEXPORT_DYLIB
-            | macho::LC_SOURCE_VERSION
-            | macho::LC_IDENT
-            | macho::LC_LINKER_OPTION
-            | macho::LC_BUILD_VERSION
-            | macho::LC_VERSION_MIN_MACOSX
-            | macho::LC_VERSION_MIN_IPHONEOS
-            | macho::LC_VERSION_MIN_WATCHOS
-            | macho::LC_VERSION_MIN_TVOS
-            | macho::LC_UUID
-            | macho::LC_RPATH
-            | macho::LC_ID_DYLINKER
-            | macho::LC_LOAD_DYLINKER
-            | macho::LC_DYLD_ENVIRONMENT
-            | macho::LC_ROUTINES_64
-            | macho::LC_THREAD
-            | macho::LC_UNIXTHREAD
-            | macho::LC_PREBOUND_DYLIB
-            | macho::LC_SUB_FRAMEWORK
-            | macho::LC_SUB_CLIENT
-            | macho::LC_SUB_UMBRELLA
-            | macho::LC_SUB_LIBRARY => {
-                // These don't involve file offsets, so no change is needed for these.
-                // Just continue the loop!
-            }
-            macho::LC_PREBIND_CKSUM => {
-                // We don't *think* we need to change this, but
-                // maybe what we're doing will break checksums?
-            }
-            macho::LC_SEGMENT | macho::LC_ROUTINES | macho::LC_ENCRYPTION_INFO => {
-                // These are 32-bit and unsuppoted
-                unreachable!();
-            }
-            macho::LC_FVMFILE | macho::LC_IDFVMLIB | macho::LC_LOADFVMLIB => {
-                // These are obsolete and unsupported
-                unreachable!()
-            }
-            cmd => {
-                eprintln!(
-                    "- - - Unrecognized Mach-O command during linker preprocessing: 0x{:x?}",
-                    cmd
-                );
-                // panic!(
-                //     "Unrecognized Mach-O command during linker preprocessing: 0x{:x?}",
-                //     cmd
-                // );
-            }
-        }
-
-        offset += dbg!(cmd_size);
-    }
-
-    // cmd_loc should be where the last offset ended
-    md.macho_cmd_loc = offset as u64;
-
-    (out_mmap, out_file)
-}
-
-#[allow(clippy::too_many_arguments)]
-fn gen_elf_le(
-    exec_data: &[u8],
-    md: &mut metadata::Metadata,
-    out_filename: &str,
-    got_app_syms: &[(String, usize)],
-    got_sections: &[(usize, usize)],
-    dynamic_lib_count: usize,
-    shared_lib_index: usize,
-    verbose: bool,
-) -> (MmapMut, File) {
-    let exec_header = load_struct_inplace::<elf::FileHeader64<LittleEndian>>(exec_data, 0);
-    let ph_offset = exec_header.e_phoff.get(NativeEndian);
-    let ph_ent_size = exec_header.e_phentsize.get(NativeEndian);
-    let ph_num = exec_header.e_phnum.get(NativeEndian);
-    let sh_offset = exec_header.e_shoff.get(NativeEndian);
-    let sh_ent_size = exec_header.e_shentsize.get(NativeEndian);
-    let sh_num = exec_header.e_shnum.get(NativeEndian);
-
-    if verbose {
-        println!();
-        println!("PH Offset: {:+x}", ph_offset);
-        println!("PH Entry Size: {}", ph_ent_size);
-        println!("PH Entry Count: {}", ph_num);
-        println!("SH Offset: {:+x}", sh_offset);
-        println!("SH Entry Size: {}", sh_ent_size);
-        println!("SH Entry Count: {}", sh_num);
-    }
-
-    // Copy header and shift everything to enable more program sections.
-    let added_header_count = 2;
-    md.added_byte_count = ph_ent_size as u64 * added_header_count;
-    md.added_byte_count = md.added_byte_count
-        + (MIN_SECTION_ALIGNMENT as u64 - md.added_byte_count % MIN_SECTION_ALIGNMENT as u64);
-    let ph_end = ph_offset as usize + ph_num as usize * ph_ent_size as usize;
-    let physical_shift_start = ph_end as u64;
-
-    md.exec_len = exec_data.len() as u64 + md.added_byte_count;
-    let out_file = fs::OpenOptions::new()
-        .read(true)
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(out_filename)
-        .unwrap_or_else(|e| internal_error!("{}", e));
-    out_file
-        .set_len(md.exec_len)
-        .unwrap_or_else(|e| internal_error!("{}", e));
-    let mut out_mmap =
-        unsafe { MmapMut::map_mut(&out_file).unwrap_or_else(|e| internal_error!("{}", e)) };
-
-    out_mmap[..ph_end].copy_from_slice(&exec_data[..ph_end]);
-
-    let program_headers = load_structs_inplace_mut::<elf::ProgramHeader64<LittleEndian>>(
-        &mut out_mmap,
-        ph_offset as usize,
-        ph_num as usize,
-    );
-    let mut first_load_found = false;
-    let mut virtual_shift_start = 0;
-    for ph in program_headers.iter() {
-        let p_type = ph.p_type.get(NativeEndian);
-        if p_type == elf::PT_LOAD && ph.p_offset.get(NativeEndian) == 0 {
-            first_load_found = true;
-            md.load_align_constraint = ph.p_align.get(NativeEndian);
-            virtual_shift_start = physical_shift_start + ph.p_vaddr.get(NativeEndian);
-        }
-    }
-    if !first_load_found {
-        user_error!("Executable does not load any data at 0x00000000\nProbably input the wrong file as the executable");
-    }
-    if verbose {
-        println!(
-            "Shifting all data after: {:+x}({:+x})",
-            physical_shift_start, virtual_shift_start
-        );
-    }
-
-    // Shift all of the program headers.
-    for ph in program_headers.iter_mut() {
-        let p_type = ph.p_type.get(NativeEndian);
-        let p_offset = ph.p_offset.get(NativeEndian);
-        if (p_type == elf::PT_LOAD && p_offset == 0) || p_type == elf::PT_PHDR {
-            // Extend length for the first segment and the program header.
-            ph.p_filesz = endian::U64::new(
-                LittleEndian,
-                ph.p_filesz.get(NativeEndian) + md.added_byte_count,
-            );
-            ph.p_memsz = endian::U64::new(
-                LittleEndian,
-                ph.p_memsz.get(NativeEndian) + md.added_byte_count,
-            );
-        } else {
-            // Shift if needed.
-            if physical_shift_start <= p_offset {
-                ph.p_offset = endian::U64::new(LittleEndian, p_offset + md.added_byte_count);
-            }
-            let p_vaddr = ph.p_vaddr.get(NativeEndian);
-            if virtual_shift_start <= p_vaddr {
-                ph.p_vaddr = endian::U64::new(LittleEndian, p_vaddr + md.added_byte_count);
-                ph.p_paddr = endian::U64::new(LittleEndian, p_vaddr + md.added_byte_count);
-            }
-        }
-    }
-
-    // Get last segment virtual address.
-    let last_segment_vaddr = program_headers
-        .iter()
-        .filter_map(|ph| {
-            if ph.p_type.get(NativeEndian) != elf::PT_GNU_STACK {
-                Some(ph.p_vaddr.get(NativeEndian) + ph.p_memsz.get(NativeEndian))
-            } else {
-                None
-            }
-        })
-        .max()
-        .unwrap();
+        })
+        .max()
+        .unwrap();
 
     // Copy the rest of the file shifted as needed.
     out_mmap[physical_shift_start as usize + md.added_byte_count as usize..]
@@ -1537,6 +1217,7 @@ fn gen_elf_le(
         sh_num as usize,
     );
 
+    let dynamic_relocations = elf_dynamic_relocations(target);
     let mut rel_sections: Vec<(u64, u64)> = vec![];
     let mut rela_sections: Vec<(u64, u64)> = vec![];
     for sh in section_headers.iter_mut() {
@@ -1596,7 +1277,7 @@ fn gen_elf_le(
                 rel.r_offset = endian::U64::new(LittleEndian, r_offset + md.added_byte_count);
                 // Deal with potential adjusts to absolute jumps.
                 // TODO: Verify other relocation types.
-                if rel.r_type(LittleEndian, false) == elf::R_X86_64_RELATIVE {
+                if rel.r_type(LittleEndian, false) == dynamic_relocations.relative {
                     let r_addend = rel.r_addend.get(LittleEndian);
                     rel.r_addend
                         .set(LittleEndian, r_addend + md.added_byte_count as i64);
@@ -1604,11 +1285,11 @@ fn gen_elf_le(
             }
             // If the relocation goes to a roc function, we need to surgically link it and change it to relative.
             let r_type = rel.r_type(NativeEndian, false);
-            if r_type == elf::R_X86_64_GLOB_DAT {
+            if r_type == dynamic_relocations.glob_dat {
                 let r_sym = rel.r_sym(NativeEndian, false);
                 for (name, index) in got_app_syms.iter() {
                     if *index as u32 == r_sym {
-                        rel.set_r_info(LittleEndian, false, 0, elf::R_X86_64_RELATIVE);
+                        rel.set_r_info(LittleEndian, false, 0, dynamic_relocations.relative);
                         let addend_addr = sec_offset as usize
                             + i * mem::size_of::<elf::Rela64<LittleEndian>>()
                             // This 16 skips the first 2 fields and gets to the addend field.
@@ -1693,6 +1374,22 @@ fn gen_elf_le(
         }
     }
 
+    // Same for the dynamic symbols, which a shared library host exports.
+    let dynsym_offset = md.dynamic_symbol_table_section_offset + md.added_byte_count;
+
+    let dynamic_symbols = load_structs_inplace_mut::<elf::Sym64<LittleEndian>>(
+        &mut out_mmap,
+        dynsym_offset as usize,
+        md.dynamic_symbol_table_size as usize / mem::size_of::<elf::Sym64<LittleEndian>>(),
+    );
+
+    for sym in dynamic_symbols {
+        let addr = sym.st_value.get(NativeEndian);
+        if virtual_shift_start <= addr {
+            sym.st_value = endian::U64::new(LittleEndian, addr + md.added_byte_count);
+        }
+    }
+
     // Update all data in the global offset table.
     for (offset, size) in got_sections {
         let global_offsets = load_structs_inplace_mut::<endian::U64<LittleEndian>>(
@@ -1753,6 +1450,7 @@ fn scan_elf_dynamic_deps(
     app_syms: &[Symbol],
     shared_lib: &Path,
     exec_data: &[u8],
+    target: &Triple,
     verbose: bool,
 ) -> ElfDynamicDeps {
     let dyn_sec = match exec_obj.section_by_name(".dynamic") {
@@ -1868,6 +1566,7 @@ fn scan_elf_dynamic_deps(
         }
     };
     md.dynamic_symbol_table_section_offset = dynsym_offset as u64;
+    md.dynamic_symbol_table_size = dynsym_sec.size();
 
     let mut got_sections: Vec<(usize, usize)> = vec![];
     for sec in exec_obj
@@ -1887,6 +1586,7 @@ fn scan_elf_dynamic_deps(
         }
     }
 
+    let glob_dat = elf_dynamic_relocations(target).glob_dat;
     let got_app_syms: Vec<(String, usize)> = (match exec_obj.dynamic_relocations() {
         Some(relocs) => relocs,
         None => {
@@ -1895,7 +1595,7 @@ fn scan_elf_dynamic_deps(
         }
     })
     .filter_map(|(_, reloc)| {
-        if let RelocationKind::Elf(6) = reloc.kind() {
+        if reloc.kind() == RelocationKind::Elf(glob_dat) {
             for symbol in app_syms.iter() {
                 if reloc.target() == RelocationTarget::Symbol(symbol.index()) {
                     return Some((symbol.name().unwrap().to_string(), symbol.index().0));
@@ -1955,7 +1655,13 @@ pub fn surgery(
         .open(out_filename)
         .unwrap_or_else(|e| internal_error!("{}", e));
 
-    let max_out_len = md.exec_len + app_data.len() as u64 + md.load_align_constraint;
+    // The app and whatever follows it may each need to start on a new page.
+    // The regenerated dynamic hash tables always fit in the size of the dynamic symbol table.
+    let max_out_len = md.exec_len
+        + app_data.len() as u64
+        + 2 * md.load_align_constraint
+        + md.dynamic_symbol_table_size
+        + 64;
     exec_file
         .set_len(max_out_len)
         .unwrap_or_else(|e| internal_error!("{}", e));
@@ -1969,19 +1675,11 @@ pub fn surgery(
     let mut offset = 0;
     let output = match target.binary_format {
         target_lexicon::BinaryFormat::Elf => {
-            surgery_elf(verbose, &md, &mut exec_mmap, &mut offset, app_obj)
-        }
-        target_lexicon::BinaryFormat::Macho => surgery_macho(
-            app_filename,
-            metadata_filename,
-            out_filename,
-            verbose,
-            time,
-            &md,
-            &mut exec_mmap,
-            &mut offset,
-            app_obj,
-        ),
+            surgery_elf(verbose, &md, &mut exec_mmap, &mut offset, app_obj, target)
+        }
+        target_lexicon::BinaryFormat::Macho => {
+            surgery_macho(verbose, &md, &mut exec_mmap, &mut offset, app_obj, target)
+        }
         _ => {
             // We should have verified this via supported() before calling this function
             unreachable!()
@@ -1999,11 +1697,33 @@ pub fn surgery(
     drop(exec_mmap);
 
     exec_file
-        .set_len(offset as u64 + 1)
+        .set_len(offset as u64)
         .unwrap_or_else(|e| internal_error!("{}", e));
     drop(exec_file);
     let flushing_data_duration = flushing_data_start.elapsed();
 
+    // Apple silicon refuses to run anything without a code signature,
+    // and linking in the app invalidated the one the host had.
+    if cfg!(target_os = "macos")
+        && target.binary_format == target_lexicon::BinaryFormat::Macho
+        && matches!(
+            target.architecture,
+            target_lexicon::Architecture::Aarch64(_)
+        )
+    {
+        let output = Command::new("codesign")
+            .args(&["-s", "-", out_filename])
+            .output()
+            .unwrap_or_else(|e| internal_error!("{}", e));
+
+        if !output.status.success() {
+            internal_error!(
+                "Failed to sign the executable - stderr of the `codesign` command was:\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+    }
+
     // Make sure the final executable has permision to execute.
     #[cfg(target_family = "unix")]
     {
@@ -2040,27 +1760,76 @@ pub fn surgery(
     output
 }
 
-#[allow(clippy::too_many_arguments)]
 pub fn surgery_macho(
-    _app_filename: &str,
-    _metadata_filename: &str,
-    _out_filename: &str,
     verbose: bool,
-    _time: bool,
     md: &metadata::Metadata,
     exec_mmap: &mut MmapMut,
     offset_ref: &mut usize, // TODO return this instead of taking a mutable reference to it
     app_obj: object::File,
+    target: &Triple,
 ) {
-    let mut offset = align_by_constraint(md.exec_len as usize, MIN_SECTION_ALIGNMENT);
-    // let new_rodata_section_offset = offset;
+    // Everything the host needs at runtime comes before __LINKEDIT, which is always the last
+    // segment. So the app goes where __LINKEDIT used to be, and __LINKEDIT moves after the app.
+    // That way none of the host's code or data moves, and only the offsets into __LINKEDIT change.
+    let exec_header = load_struct_inplace::<macho::MachHeader64<LittleEndian>>(exec_mmap, 0);
+    let num_load_cmds = exec_header.ncmds.get(NativeEndian);
+    let size_of_cmds = exec_header.sizeofcmds.get(NativeEndian) as usize;
+    let cmds_start = mem::size_of_val(exec_header);
+
+    let mut linkedit = None;
+    let mut code_signature = None;
+    let mut cmd_offset = cmds_start;
+    for _ in 0..num_load_cmds {
+        let info = load_struct_inplace::<macho::LoadCommand<LittleEndian>>(exec_mmap, cmd_offset);
+        let cmd_size = info.cmdsize.get(NativeEndian) as usize;
+
+        match info.cmd.get(NativeEndian) {
+            macho::LC_SEGMENT_64 => {
+                let cmd = load_struct_inplace::<macho::SegmentCommand64<LittleEndian>>(
+                    exec_mmap, cmd_offset,
+                );
+
+                if &cmd.segname[0..11] == b"__LINKEDIT\0" {
+                    linkedit = Some((
+                        cmd.fileoff.get(NativeEndian) as usize,
+                        cmd.filesize.get(NativeEndian) as usize,
+                        cmd.vmaddr.get(NativeEndian) as usize,
+                    ));
+                }
+            }
+            macho::LC_CODE_SIGNATURE => {
+                let cmd = load_struct_inplace::<macho::LinkeditDataCommand<LittleEndian>>(
+                    exec_mmap, cmd_offset,
+                );
+
+                code_signature = Some(cmd.dataoff.get(NativeEndian) as usize);
+            }
+            _ => {}
+        }
+
+        cmd_offset += cmd_size;
+    }
+
+    let (old_linkedit_offset, old_linkedit_size, old_linkedit_vaddr) = match linkedit {
+        Some(linkedit) => linkedit,
+        None => {
+            internal_error!("The host executable does not have a __LINKEDIT segment");
+        }
+    };
 
-    // Align physical and virtual address of new segment.
-    let mut virt_offset = align_to_offset_by_constraint(
-        md.last_vaddr as usize,
-        offset,
-        md.load_align_constraint as usize,
-    );
+    // Adding the app invalidates the code signature, which is always at the very end of
+    // __LINKEDIT. Leave it behind, so that the executable can be signed again afterwards.
+    let linkedit_size = match code_signature {
+        Some(signature_offset) => signature_offset - old_linkedit_offset,
+        None => old_linkedit_size,
+    };
+    let linkedit_data =
+        exec_mmap[old_linkedit_offset..old_linkedit_offset + linkedit_size].to_vec();
+
+    let mut offset = old_linkedit_offset;
+    let new_rodata_section_offset = offset;
+
+    let mut virt_offset = old_linkedit_vaddr;
     let new_rodata_section_vaddr = virt_offset;
     if verbose {
         println!();
@@ -2077,14 +1846,18 @@ pub fn surgery_macho(
     let mut section_offset_map: MutMap<SectionIndex, (usize, usize)> = MutMap::default();
     let mut symbol_vaddr_map: MutMap<SymbolIndex, usize> = MutMap::default();
     let mut app_func_vaddr_map: MutMap<String, usize> = MutMap::default();
-    let mut app_func_size_map: MutMap<String, u64> = MutMap::default();
 
     // TODO: In the future Roc may use a data section to store memoized toplevel thunks
     // in development builds for caching the results of top-level constants
 
     let rodata_sections: Vec<Section> = app_obj
         .sections()
-        .filter(|sec| sec.kind() == SectionKind::ReadOnlyData)
+        .filter(|sec| {
+            matches!(
+                sec.kind(),
+                SectionKind::ReadOnlyData | SectionKind::ReadOnlyString
+            )
+        })
         .collect();
 
     // bss section is like rodata section, but it has zero file size and non-zero virtual size.
@@ -2103,11 +1876,19 @@ pub fn surgery_macho(
 
     // Calculate addresses and load symbols.
     // Note, it is important the bss sections come after the rodata sections.
+    let mut new_text_section = None;
     for sec in rodata_sections
         .iter()
         .chain(bss_sections.iter())
         .chain(text_sections.iter())
     {
+        if sec.kind() == SectionKind::Text && new_text_section.is_none() {
+            // The text gets a segment of its own, since it needs different protections.
+            offset = align_by_constraint(offset, md.load_align_constraint as usize);
+            virt_offset = align_by_constraint(virt_offset, md.load_align_constraint as usize);
+            new_text_section = Some((offset, virt_offset));
+        }
+
         offset = align_by_constraint(offset, MIN_SECTION_ALIGNMENT);
         virt_offset =
             align_to_offset_by_constraint(virt_offset, offset, md.load_align_constraint as usize);
@@ -2127,8 +1908,7 @@ pub fn surgery_macho(
                     symbol_vaddr_map.insert(sym.index(), virt_offset + sym.address() as usize);
                 }
                 if md.app_functions.contains(&name) {
-                    app_func_vaddr_map.insert(name.clone(), virt_offset + sym.address() as usize);
-                    app_func_size_map.insert(name, sym.size());
+                    app_func_vaddr_map.insert(name, virt_offset + sym.address() as usize);
                 }
             }
         }
@@ -2136,7 +1916,7 @@ pub fn surgery_macho(
             Some((_, size)) => size,
             None => 0,
         };
-        if sec.name().unwrap_or_default().starts_with("__BSS") {
+        if sec.kind() == SectionKind::UninitializedData {
             // bss sections only modify the virtual size.
             virt_offset += sec.size() as usize;
         } else if section_size != sec.size() {
@@ -2151,13 +1931,8 @@ pub fn surgery_macho(
         println!("Found App Function Symbols: {:+x?}", app_func_vaddr_map);
     }
 
-    // let (new_text_section_offset, new_text_section_vaddr) = text_sections
-    //     .iter()
-    //     .map(|sec| section_offset_map.get(&sec.index()).unwrap())
-    //     .min()
-    //     .unwrap();
-    // let (new_text_section_offset, new_text_section_vaddr) =
-    //     (*new_text_section_offset, *new_text_section_vaddr);
+    let (new_text_section_offset, new_text_section_vaddr) = new_text_section.unwrap();
+    let is_aarch64 = app_obj.architecture() == Architecture::Aarch64;
 
     // Move data and deal with relocations.
     for sec in rodata_sections
@@ -2187,10 +1962,31 @@ pub fn surgery_macho(
                 sec, section_offset, section_virtual_offset
             );
         }
+
+        // On arm64, addends too big to fit in the instruction come in a relocation of their own,
+        // right before the relocation they apply to.
+        let mut pending_addend = 0;
+
         for rel in sec.relocations() {
             if verbose {
                 println!("\tFound Relocation: {:+x?}", rel);
             }
+
+            if let (
+                true,
+                RelocationKind::MachO {
+                    value: macho::ARM64_RELOC_ADDEND,
+                    ..
+                },
+                RelocationTarget::Section(SectionIndex(addend)),
+            ) = (is_aarch64, rel.1.kind(), rel.1.target())
+            {
+                // The addend is stored as a signed 24 bit number where the section index would be.
+                pending_addend = ((addend as i64) << 40) >> 40;
+                continue;
+            }
+            let addend = rel.1.addend() + mem::take(&mut pending_addend);
+
             match rel.1.target() {
                 RelocationTarget::Symbol(index) => {
                     let target_offset = if let Some(target_offset) = symbol_vaddr_map.get(&index) {
@@ -2207,25 +2003,51 @@ pub fn surgery_macho(
                             .and_then(|sym| sym.name())
                             .ok()
                             .and_then(|name| {
-                                md.roc_symbol_vaddresses.get(name).map(|address| {
-                                    let vaddr = (*address + md.added_byte_count) as i64;
-                                    if verbose {
-                                        println!(
-                                            "\t\tRelocation targets symbol in host: {} @ {:+x}",
-                                            name, vaddr
-                                        );
-                                    }
-                                    vaddr
-                                })
+                                // We store host symbols without the leading underscore Mach-O adds.
+                                md.roc_symbol_vaddresses
+                                    .get(name.trim_start_matches('_'))
+                                    .map(|address| {
+                                        let vaddr = (*address + md.added_byte_count) as i64;
+                                        if verbose {
+                                            println!(
+                                                "\t\tRelocation targets symbol in host: {} @ {:+x}",
+                                                name, vaddr
+                                            );
+                                        }
+                                        vaddr
+                                    })
                             })
                     };
 
                     if let Some(target_offset) = target_offset {
                         let virt_base = section_virtual_offset as usize + rel.0 as usize;
                         let base = section_offset as usize + rel.0 as usize;
+
+                        if let Some(relocation) =
+                            aarch64::Relocation::from_kind(rel.1.kind()).filter(|_| is_aarch64)
+                        {
+                            let inst = u32::from_le_bytes(
+                                <[u8; 4]>::try_from(&exec_mmap[base..base + 4]).unwrap(),
+                            );
+                            let inst = relocation.apply(
+                                inst,
+                                virt_base as u64,
+                                (target_offset + addend) as u64,
+                            );
+                            if verbose {
+                                println!(
+                                    "\t\tRelocation base location: {:+x} (virt: {:+x})",
+                                    base, virt_base
+                                );
+                                println!("\t\tPatched instruction: {:08x}", inst);
+                            }
+                            exec_mmap[base..base + 4].copy_from_slice(&inst.to_le_bytes());
+                            continue;
+                        }
+
                         let target: i64 = match rel.1.kind() {
                             RelocationKind::Relative | RelocationKind::PltRelative => {
-                                target_offset - virt_base as i64 + rel.1.addend()
+                                target_offset - virt_base as i64 + addend
                             }
                             x => {
                                 internal_error!("Relocation Kind not yet support: {:?}", x);
@@ -2271,106 +2093,175 @@ pub fn surgery_macho(
         }
     }
 
+    let new_text_section_size = offset - new_text_section_offset;
+
+    // Put __LINKEDIT back after the app.
+    offset = align_by_constraint(offset, md.load_align_constraint as usize);
+    virt_offset = align_by_constraint(
+        new_text_section_vaddr + new_text_section_size,
+        md.load_align_constraint as usize,
+    );
+    let new_linkedit_offset = offset;
+    let new_linkedit_vaddr = virt_offset;
+    exec_mmap[offset..offset + linkedit_size].copy_from_slice(&linkedit_data);
+    offset += linkedit_size;
+
     // Flush app only data to speed up write to disk.
     exec_mmap
-        .flush_async_range(md.exec_len as usize, offset - md.exec_len as usize)
+        .flush_async_range(
+            new_rodata_section_offset,
+            offset - new_rodata_section_offset,
+        )
         .unwrap_or_else(|e| internal_error!("{}", e));
 
     // TODO: look into merging symbol tables, debug info, and eh frames to enable better debugger experience.
 
-    // let mut cmd_offset = md.macho_cmd_loc as usize;
-
-    // // Load this section (we made room for it earlier) and then mutate all its data to make it the desired command
-    // {
-    //     let cmd =
-    //         load_struct_inplace_mut::<macho::SegmentCommand64<LittleEndian>>(exec_mmap, cmd_offset);
-    //     let size_of_section = mem::size_of::<macho::Section64<LittleEndian>>() as u32;
-    //     let size_of_cmd = mem::size_of_val(cmd);
-
-    //     cmd_offset += size_of_cmd;
-
-    //     cmd.cmd.set(LittleEndian, macho::LC_SEGMENT_64);
-    //     cmd.cmdsize
-    //         .set(LittleEndian, size_of_section + size_of_cmd as u32);
-    //     cmd.segname = *b"__DATA_CONST\0\0\0\0";
-    //     cmd.vmaddr
-    //         .set(LittleEndian, new_rodata_section_vaddr as u64);
-    //     cmd.vmsize.set(
-    //         LittleEndian,
-    //         (new_text_section_vaddr - new_rodata_section_vaddr) as u64,
-    //     );
-    //     cmd.fileoff
-    //         .set(LittleEndian, new_rodata_section_offset as u64);
-    //     cmd.filesize.set(
-    //         LittleEndian,
-    //         (new_text_section_offset - new_rodata_section_offset) as u64,
-    //     );
-    //     cmd.nsects.set(LittleEndian, 1);
-    //     cmd.maxprot.set(LittleEndian, 0x00000003);
-    //     cmd.initprot.set(LittleEndian, 0x00000003);
-
-    //     // TODO set protection
-    // }
-
-    // {
-    //     let cmd = load_struct_inplace_mut::<macho::Section64<LittleEndian>>(exec_mmap, cmd_offset);
-    //     let size_of_cmd = mem::size_of_val(cmd);
-
-    //     cmd_offset += size_of_cmd;
-
-    //     cmd.sectname = *b"__const\0\0\0\0\0\0\0\0\0";
-    //     cmd.segname = *b"__DATA_CONST\0\0\0\0";
-    //     cmd.addr.set(LittleEndian, new_rodata_section_vaddr as u64);
-    //     cmd.size.set(
-    //         LittleEndian,
-    //         (new_text_section_offset - new_rodata_section_offset) as u64,
-    //     );
-    //     cmd.offset.set(LittleEndian, 0); // TODO is this offset since the start of the file, or segment offset?
-    //     cmd.align.set(LittleEndian, 12); // TODO should this be 4096?
-    //     cmd.reloff.set(LittleEndian, 264); // TODO this should NOT be hardcoded! Should get it from somewhere.
-    // }
-
-    // {
-    //     let cmd =
-    //         load_struct_inplace_mut::<macho::SegmentCommand64<LittleEndian>>(exec_mmap, cmd_offset);
-    //     let size_of_section = mem::size_of::<macho::Section64<LittleEndian>>() as u32;
-    //     let size_of_cmd = mem::size_of_val(cmd);
-
-    //     cmd_offset += size_of_cmd;
-
-    //     cmd.cmd.set(LittleEndian, macho::LC_SEGMENT_64);
-    //     cmd.cmdsize
-    //         .set(LittleEndian, size_of_section + size_of_cmd as u32);
-    //     cmd.segname = *b"__TEXT\0\0\0\0\0\0\0\0\0\0";
-    //     cmd.vmaddr.set(LittleEndian, new_text_section_vaddr as u64);
-    //     cmd.vmsize
-    //         .set(LittleEndian, (offset - new_text_section_offset) as u64);
-    //     cmd.fileoff
-    //         .set(LittleEndian, new_text_section_offset as u64);
-    //     cmd.filesize
-    //         .set(LittleEndian, (offset - new_text_section_offset) as u64);
-    //     cmd.nsects.set(LittleEndian, 1);
-    //     cmd.maxprot.set(LittleEndian, 0x00000005); // this is what a zig-generated host had
-    //     cmd.initprot.set(LittleEndian, 0x00000005); // this is what a zig-generated host had
-    // }
-
-    // {
-    //     let cmd = load_struct_inplace_mut::<macho::Section64<LittleEndian>>(exec_mmap, cmd_offset);
-
-    //     cmd.segname = *b"__TEXT\0\0\0\0\0\0\0\0\0\0";
-    //     cmd.sectname = *b"__text\0\0\0\0\0\0\0\0\0\0";
-    //     cmd.addr.set(LittleEndian, new_text_section_vaddr as u64);
-    //     cmd.size
-    //         .set(LittleEndian, (offset - new_text_section_offset) as u64);
-    //     cmd.offset.set(LittleEndian, 0); // TODO is this offset since the start of the file, or segment offset?
-    //     cmd.align.set(LittleEndian, 12); // TODO this is 4096 (2^12) - which load_align_constraint does, above - but should it?
-    //     cmd.flags.set(LittleEndian, 0x80000400); // TODO this is what a zig-generated host had
-    //     cmd.reloff.set(LittleEndian, 264); // TODO this should NOT be hardcoded! Should get it from somewhere.
-    // }
+    // Rebuild the load commands: point everything that lived in __LINKEDIT at its new location,
+    // drop the now invalid code signature, and add a segment for each of the app's rodata and text.
+    // Preprocessing made sure there is room for the two new segments.
+    let linkedit_shift = (new_linkedit_offset - old_linkedit_offset) as u32;
+    let shift = |file_offset: &mut endian::U32<LittleEndian>, size: u32| {
+        if size > 0 {
+            file_offset.set(LittleEndian, file_offset.get(NativeEndian) + linkedit_shift);
+        }
+    };
+
+    let mut new_cmds = Vec::with_capacity(size_of_cmds + 2 * MACHO_SEGMENT_WITH_SECTION_SIZE);
+    let mut new_num_load_cmds = 0;
+    let mut cmd_offset = cmds_start;
+    for _ in 0..num_load_cmds {
+        let info = load_struct_inplace::<macho::LoadCommand<LittleEndian>>(exec_mmap, cmd_offset);
+        let cmd_type = info.cmd.get(NativeEndian);
+        let cmd_size = info.cmdsize.get(NativeEndian) as usize;
+        let mut cmd_data = exec_mmap[cmd_offset..cmd_offset + cmd_size].to_vec();
+        cmd_offset += cmd_size;
 
-    // Update calls from platform and dynamic symbols.
-    // let dynsym_offset = md.dynamic_symbol_table_section_offset + md.added_byte_count;
+        match cmd_type {
+            macho::LC_CODE_SIGNATURE => {
+                continue;
+            }
+            macho::LC_SEGMENT_64 => {
+                let cmd = load_struct_inplace_mut::<macho::SegmentCommand64<LittleEndian>>(
+                    &mut cmd_data,
+                    0,
+                );
+
+                if &cmd.segname[0..11] == b"__LINKEDIT\0" {
+                    cmd.fileoff.set(LittleEndian, new_linkedit_offset as u64);
+                    cmd.filesize.set(LittleEndian, linkedit_size as u64);
+                    cmd.vmaddr.set(LittleEndian, new_linkedit_vaddr as u64);
+                    cmd.vmsize.set(
+                        LittleEndian,
+                        align_by_constraint(linkedit_size, md.load_align_constraint as usize)
+                            as u64,
+                    );
+
+                    new_cmds.extend_from_slice(&macho_segment_with_section(
+                        b"__ROC_RODATA",
+                        b"__const",
+                        new_rodata_section_offset,
+                        new_text_section_offset - new_rodata_section_offset,
+                        new_rodata_section_vaddr,
+                        new_text_section_vaddr - new_rodata_section_vaddr,
+                        macho::VM_PROT_READ,
+                        0,
+                    ));
+                    new_cmds.extend_from_slice(&macho_segment_with_section(
+                        b"__ROC_TEXT",
+                        b"__text",
+                        new_text_section_offset,
+                        new_text_section_size,
+                        new_text_section_vaddr,
+                        align_by_constraint(
+                            new_text_section_size,
+                            md.load_align_constraint as usize,
+                        ),
+                        macho::VM_PROT_READ | macho::VM_PROT_EXECUTE,
+                        macho::S_ATTR_PURE_INSTRUCTIONS | macho::S_ATTR_SOME_INSTRUCTIONS,
+                    ));
+                    new_num_load_cmds += 2;
+                }
+            }
+            macho::LC_SYMTAB => {
+                let cmd =
+                    load_struct_inplace_mut::<macho::SymtabCommand<LittleEndian>>(&mut cmd_data, 0);
+                let num_syms = cmd.nsyms.get(NativeEndian);
+                let str_size = cmd.strsize.get(NativeEndian);
+
+                shift(&mut cmd.symoff, num_syms);
+                shift(&mut cmd.stroff, str_size);
+            }
+            macho::LC_DYSYMTAB => {
+                let cmd = load_struct_inplace_mut::<macho::DysymtabCommand<LittleEndian>>(
+                    &mut cmd_data,
+                    0,
+                );
+
+                let ntoc = cmd.ntoc.get(NativeEndian);
+                let nmodtab = cmd.nmodtab.get(NativeEndian);
+                let nextrefsyms = cmd.nextrefsyms.get(NativeEndian);
+                let nindirectsyms = cmd.nindirectsyms.get(NativeEndian);
+                let nextrel = cmd.nextrel.get(NativeEndian);
+                let nlocrel = cmd.nlocrel.get(NativeEndian);
+
+                shift(&mut cmd.tocoff, ntoc);
+                shift(&mut cmd.modtaboff, nmodtab);
+                shift(&mut cmd.extrefsymoff, nextrefsyms);
+                shift(&mut cmd.indirectsymoff, nindirectsyms);
+                shift(&mut cmd.extreloff, nextrel);
+                shift(&mut cmd.locreloff, nlocrel);
+            }
+            macho::LC_DYLD_INFO | macho::LC_DYLD_INFO_ONLY => {
+                let cmd = load_struct_inplace_mut::<macho::DyldInfoCommand<LittleEndian>>(
+                    &mut cmd_data,
+                    0,
+                );
+
+                let rebase_size = cmd.rebase_size.get(NativeEndian);
+                let bind_size = cmd.bind_size.get(NativeEndian);
+                let weak_bind_size = cmd.weak_bind_size.get(NativeEndian);
+                let lazy_bind_size = cmd.lazy_bind_size.get(NativeEndian);
+                let export_size = cmd.export_size.get(NativeEndian);
+
+                shift(&mut cmd.rebase_off, rebase_size);
+                shift(&mut cmd.bind_off, bind_size);
+                shift(&mut cmd.weak_bind_off, weak_bind_size);
+                shift(&mut cmd.lazy_bind_off, lazy_bind_size);
+                shift(&mut cmd.export_off, export_size);
+            }
+            macho::LC_FUNCTION_STARTS
+            | macho::LC_DATA_IN_CODE
+            | macho::LC_SEGMENT_SPLIT_INFO
+            | macho::LC_DYLIB_CODE_SIGN_DRS
+            | macho::LC_LINKER_OPTIMIZATION_HINT
+            | macho::LC_DYLD_EXPORTS_TRIE
+            | macho::LC_DYLD_CHAINED_FIXUPS => {
+                let cmd = load_struct_inplace_mut::<macho::LinkeditDataCommand<LittleEndian>>(
+                    &mut cmd_data,
+                    0,
+                );
+                let data_size = cmd.datasize.get(NativeEndian);
+
+                shift(&mut cmd.dataoff, data_size);
+            }
+            _ => {
+                // Nothing else refers to __LINKEDIT.
+            }
+        }
 
+        new_cmds.extend_from_slice(&cmd_data);
+        new_num_load_cmds += 1;
+    }
+
+    let cmds_end = cmds_start + size_of_cmds.max(new_cmds.len());
+    exec_mmap[cmds_start..cmds_start + new_cmds.len()].copy_from_slice(&new_cmds);
+    exec_mmap[cmds_start + new_cmds.len()..cmds_end].fill(0);
+
+    let header = load_struct_inplace_mut::<macho::MachHeader64<LittleEndian>>(exec_mmap, 0);
+    header.ncmds.set(LittleEndian, new_num_load_cmds);
+    header.sizeofcmds.set(LittleEndian, new_cmds.len() as u32);
+
+    // Update calls from platform.
     for func_name in md.app_functions.iter() {
         let func_virt_offset = match app_func_vaddr_map.get(func_name) {
             Some(offset) => *offset as u64,
@@ -2378,53 +2269,146 @@ pub fn surgery_macho(
                 internal_error!("Function, {}, was not defined by the app", &func_name);
             }
         };
+
+        redirect_calls(md, exec_mmap, func_name, func_virt_offset, target, verbose);
+    }
+
+    *offset_ref = offset;
+}
+
+const MACHO_SEGMENT_WITH_SECTION_SIZE: usize =
+    mem::size_of::<macho::SegmentCommand64<LittleEndian>>()
+        + mem::size_of::<macho::Section64<LittleEndian>>();
+
+/// A segment load command holding a single section that spans the whole segment's file data.
+#[allow(clippy::too_many_arguments)]
+fn macho_segment_with_section(
+    segment_name: &[u8],
+    section_name: &[u8],
+    file_offset: usize,
+    file_size: usize,
+    vaddr: usize,
+    vm_size: usize,
+    protection: u32,
+    section_flags: u32,
+) -> Vec<u8> {
+    let mut bytes = vec![0; MACHO_SEGMENT_WITH_SECTION_SIZE];
+    let mut segname = [0; 16];
+    segname[..segment_name.len()].copy_from_slice(segment_name);
+    let mut sectname = [0; 16];
+    sectname[..section_name.len()].copy_from_slice(section_name);
+
+    let cmd = load_struct_inplace_mut::<macho::SegmentCommand64<LittleEndian>>(&mut bytes, 0);
+    cmd.cmd.set(LittleEndian, macho::LC_SEGMENT_64);
+    cmd.cmdsize
+        .set(LittleEndian, MACHO_SEGMENT_WITH_SECTION_SIZE as u32);
+    cmd.segname = segname;
+    cmd.vmaddr.set(LittleEndian, vaddr as u64);
+    cmd.vmsize.set(LittleEndian, vm_size as u64);
+    cmd.fileoff.set(LittleEndian, file_offset as u64);
+    cmd.filesize.set(LittleEndian, file_size as u64);
+    cmd.maxprot.set(LittleEndian, protection);
+    cmd.initprot.set(LittleEndian, protection);
+    cmd.nsects.set(LittleEndian, 1);
+
+    let section = load_struct_inplace_mut::<macho::Section64<LittleEndian>>(
+        &mut bytes,
+        mem::size_of::<macho::SegmentCommand64<LittleEndian>>(),
+    );
+    section.sectname = sectname;
+    section.segname = segname;
+    section.addr.set(LittleEndian, vaddr as u64);
+    section.size.set(LittleEndian, file_size as u64);
+    section.offset.set(LittleEndian, file_offset as u32);
+    section.align.set(LittleEndian, 4); // 2^4 matches MIN_SECTION_ALIGNMENT's 16 byte multiples
+    section.flags.set(LittleEndian, section_flags);
+
+    bytes
+}
+
+/// Points every call the host makes to `func_name` at `func_virt_offset`, where the app put it.
+fn redirect_calls(
+    md: &metadata::Metadata,
+    exec_mmap: &mut [u8],
+    func_name: &str,
+    func_virt_offset: u64,
+    target: &Triple,
+    verbose: bool,
+) {
+    let is_aarch64 = matches!(
+        target.architecture,
+        target_lexicon::Architecture::Aarch64(_)
+    );
+
+    if verbose {
+        println!(
+            "Updating calls to {} to the address: {:+x}",
+            &func_name, func_virt_offset
+        );
+    }
+
+    for s in md.surgeries.get(func_name).unwrap_or(&vec![]) {
         if verbose {
-            println!(
-                "Updating calls to {} to the address: {:+x}",
-                &func_name, func_virt_offset
-            );
+            println!("\tPerforming surgery: {:+x?}", s);
         }
-
-        for s in md.surgeries.get(func_name).unwrap_or(&vec![]) {
-            if verbose {
-                println!("\tPerforming surgery: {:+x?}", s);
-            }
-            let surgery_virt_offset = match s.virtual_offset {
-                VirtualOffset::Relative(vs) => (vs + md.added_byte_count) as i64,
-                VirtualOffset::Absolute => 0,
-            };
-            match s.size {
-                4 => {
-                    let target = (func_virt_offset as i64 - surgery_virt_offset) as i32;
-                    if verbose {
-                        println!("\tTarget Jump: {:+x}", target);
-                    }
-                    let data = target.to_le_bytes();
-                    exec_mmap[(s.file_offset + md.added_byte_count) as usize
-                        ..(s.file_offset + md.added_byte_count) as usize + 4]
-                        .copy_from_slice(&data);
+        let surgery_virt_offset = match s.virtual_offset {
+            VirtualOffset::Relative(vs) => (vs + md.added_byte_count) as i64,
+            VirtualOffset::Absolute => 0,
+        };
+        let file_offset = (s.file_offset + md.added_byte_count) as usize;
+        match s.size {
+            4 if is_aarch64 => {
+                let target = func_virt_offset as i64 - surgery_virt_offset;
+                if verbose {
+                    println!("\tTarget Jump: {:+x}", target);
                 }
-                8 => {
-                    let target = func_virt_offset as i64 - surgery_virt_offset;
-                    if verbose {
-                        println!("\tTarget Jump: {:+x}", target);
-                    }
-                    let data = target.to_le_bytes();
-                    exec_mmap[(s.file_offset + md.added_byte_count) as usize
-                        ..(s.file_offset + md.added_byte_count) as usize + 8]
-                        .copy_from_slice(&data);
+                let inst = u32::from_le_bytes(
+                    <[u8; 4]>::try_from(&exec_mmap[file_offset..file_offset + 4]).unwrap(),
+                );
+                let data = aarch64::set_branch_offset(inst, target).to_le_bytes();
+                exec_mmap[file_offset..file_offset + 4].copy_from_slice(&data);
+            }
+            4 => {
+                let target = (func_virt_offset as i64 - surgery_virt_offset) as i32;
+                if verbose {
+                    println!("\tTarget Jump: {:+x}", target);
                 }
-                x => {
-                    internal_error!("Surgery size not yet supported: {}", x);
+                let data = target.to_le_bytes();
+                exec_mmap[file_offset..file_offset + 4].copy_from_slice(&data);
+            }
+            8 => {
+                let target = func_virt_offset as i64 - surgery_virt_offset;
+                if verbose {
+                    println!("\tTarget Jump: {:+x}", target);
                 }
+                let data = target.to_le_bytes();
+                exec_mmap[file_offset..file_offset + 8].copy_from_slice(&data);
+            }
+            x => {
+                internal_error!("Surgery size not yet supported: {}", x);
             }
         }
+    }
 
-        // Replace plt call code with just a jump.
-        // This is a backup incase we missed a call to the plt.
-        if let Some((plt_off, plt_vaddr)) = md.plt_addresses.get(func_name) {
-            let plt_off = (*plt_off + md.added_byte_count) as usize;
-            let plt_vaddr = *plt_vaddr + md.added_byte_count;
+    // Replace plt call code with just a jump.
+    // This is a backup incase we missed a call to the plt.
+    if let Some((plt_off, plt_vaddr)) = md.plt_addresses.get(func_name) {
+        let plt_off = (*plt_off + md.added_byte_count) as usize;
+        let plt_vaddr = *plt_vaddr + md.added_byte_count;
+        let plt_entry_size = plt_layout(target).entry_size as usize;
+        if is_aarch64 {
+            let target = func_virt_offset as i64 - plt_vaddr as i64;
+            if verbose {
+                println!("\tPLT: {:+x}, {:+x}", plt_off, plt_vaddr);
+                println!("\tTarget Jump: {:+x}", target);
+            }
+            let data = aarch64::branch(target).to_le_bytes();
+            exec_mmap[plt_off..plt_off + 4].copy_from_slice(&data);
+            for i in (4..plt_entry_size).step_by(4) {
+                exec_mmap[plt_off + i..plt_off + i + 4]
+                    .copy_from_slice(&aarch64::NOP.to_le_bytes());
+            }
+        } else {
             let jmp_inst_len = 5;
             let target =
                 (func_virt_offset as i64 - (plt_vaddr as i64 + jmp_inst_len as i64)) as i32;
@@ -2435,31 +2419,11 @@ pub fn surgery_macho(
             let data = target.to_le_bytes();
             exec_mmap[plt_off] = 0xE9;
             exec_mmap[plt_off + 1..plt_off + jmp_inst_len].copy_from_slice(&data);
-            for i in jmp_inst_len..PLT_ADDRESS_OFFSET as usize {
+            for i in jmp_inst_len..plt_entry_size {
                 exec_mmap[plt_off + i] = 0x90;
             }
         }
-
-        // Commented out because it doesn't apply to mach-o
-        // if let Some(i) = md.dynamic_symbol_indices.get(func_name) {
-        //     let sym = load_struct_inplace_mut::<elf::Sym64<LittleEndian>>(
-        //         exec_mmap,
-        //         dynsym_offset as usize + *i as usize * mem::size_of::<elf::Sym64<LittleEndian>>(),
-        //     );
-        //     sym.st_value = endian::U64::new(LittleEndian, func_virt_offset as u64);
-        //     sym.st_size = endian::U64::new(
-        //         LittleEndian,
-        //         match app_func_size_map.get(func_name) {
-        //             Some(size) => *size,
-        //             None => {
-        //                 internal_error!("Size missing for: {}", func_name);
-        //             }
-        //         },
-        //     );
-        // }
     }
-
-    *offset_ref = offset;
 }
 
 pub fn surgery_elf(
@@ -2468,6 +2432,7 @@ pub fn surgery_elf(
     exec_mmap: &mut MmapMut,
     offset_ref: &mut usize, // TODO return this instead of taking a mutable reference to it
     app_obj: object::File,
+    target: &Triple,
 ) {
     let elf64 = exec_mmap[4] == 2;
     let litte_endian = exec_mmap[5] == 1;
@@ -2500,6 +2465,11 @@ pub fn surgery_elf(
     let mut sh_tab = vec![];
     sh_tab.extend_from_slice(&exec_mmap[sh_offset as usize..sh_offset as usize + sh_size]);
 
+    // The app functions are about to become defined dynamic symbols.
+    // Generate new hash tables now, while the original section headers are still in place,
+    // so that the dynamic loader can find them.
+    let dynamic_hash_tables = gen_elf_dynamic_hash_tables(md, exec_mmap, sh_offset, sh_num);
+
     let mut offset = sh_offset as usize;
     offset = align_by_constraint(offset, MIN_SECTION_ALIGNMENT);
 
@@ -2672,6 +2642,27 @@ pub fn surgery_elf(
                     if let Some(target_offset) = target_offset {
                         let virt_base = section_virtual_offset as usize + rel.0 as usize;
                         let base = section_offset as usize + rel.0 as usize;
+
+                        if let Some(relocation) = aarch64::Relocation::from_kind(rel.1.kind()) {
+                            let inst = u32::from_le_bytes(
+                                <[u8; 4]>::try_from(&exec_mmap[base..base + 4]).unwrap(),
+                            );
+                            let inst = relocation.apply(
+                                inst,
+                                virt_base as u64,
+                                (target_offset + rel.1.addend()) as u64,
+                            );
+                            if verbose {
+                                println!(
+                                    "\t\tRelocation base location: {:+x} (virt: {:+x})",
+                                    base, virt_base
+                                );
+                                println!("\t\tPatched instruction: {:08x}", inst);
+                            }
+                            exec_mmap[base..base + 4].copy_from_slice(&inst.to_le_bytes());
+                            continue;
+                        }
+
                         let target: i64 = match rel.1.kind() {
                             RelocationKind::Relative | RelocationKind::PltRelative => {
                                 target_offset - virt_base as i64 + rel.1.addend()
@@ -2720,6 +2711,47 @@ pub fn surgery_elf(
         }
     }
 
+    // Append the dynamic hash tables to the end of the new text segment.
+    let mut dynamic_hash_table_locations = vec![];
+    for (sh_type, data) in dynamic_hash_tables {
+        offset = align_by_constraint(offset, 8);
+        let vaddr = new_text_section_vaddr + (offset - new_text_section_offset);
+        exec_mmap[offset..offset + data.len()].copy_from_slice(&data);
+        if verbose {
+            println!(
+                "Dynamic hash table of type {:x} is being put at offset: {:+x}(virt: {:+x})",
+                sh_type, offset, vaddr
+            );
+        }
+        dynamic_hash_table_locations.push((sh_type, offset, vaddr, data.len()));
+        offset += data.len();
+    }
+
+    let dyn_offset = md.dynamic_section_offset + md.added_byte_count;
+    let mut dyn_index = 0;
+    loop {
+        let d = load_struct_inplace_mut::<elf::Dyn64<LittleEndian>>(
+            exec_mmap,
+            dyn_offset as usize + dyn_index * mem::size_of::<elf::Dyn64<LittleEndian>>(),
+        );
+        let sh_type = match d.d_tag.get(NativeEndian) as u32 {
+            elf::DT_NULL => break,
+            elf::DT_GNU_HASH => elf::SHT_GNU_HASH,
+            elf::DT_HASH => elf::SHT_HASH,
+            _ => {
+                dyn_index += 1;
+                continue;
+            }
+        };
+        if let Some((_, _, vaddr, _)) = dynamic_hash_table_locations
+            .iter()
+            .find(|(t, _, _, _)| *t == sh_type)
+        {
+            d.d_val = endian::U64::new(LittleEndian, *vaddr as u64);
+        }
+        dyn_index += 1;
+    }
+
     offset = align_by_constraint(offset, MIN_SECTION_ALIGNMENT);
     let new_sh_offset = offset;
     exec_mmap[offset..offset + sh_size].copy_from_slice(&sh_tab);
@@ -2750,6 +2782,18 @@ pub fn surgery_elf(
     let new_text_section_vaddr = new_rodata_section_vaddr as u64 + new_rodata_section_size as u64;
     let new_text_section_size = new_sh_offset as u64 - new_text_section_offset as u64;
 
+    for sh in section_headers.iter_mut() {
+        let sh_type = sh.sh_type.get(NativeEndian);
+        if let Some((_, offset, vaddr, size)) = dynamic_hash_table_locations
+            .iter()
+            .find(|(t, _, _, _)| *t == sh_type)
+        {
+            sh.sh_offset = endian::U64::new(LittleEndian, *offset as u64);
+            sh.sh_addr = endian::U64::new(LittleEndian, *vaddr as u64);
+            sh.sh_size = endian::U64::new(LittleEndian, *size as u64);
+        }
+    }
+
     let new_rodata_section = &mut section_headers[section_headers.len() - 2];
     new_rodata_section.sh_name = endian::U32::new(LittleEndian, 0);
     new_rodata_section.sh_type = endian::U32::new(LittleEndian, elf::SHT_PROGBITS);
@@ -2817,73 +2861,14 @@ pub fn surgery_elf(
                 internal_error!("Function, {}, was not defined by the app", &func_name);
             }
         };
-        if verbose {
-            println!(
-                "Updating calls to {} to the address: {:+x}",
-                &func_name, func_virt_offset
-            );
-        }
-
-        for s in md.surgeries.get(func_name).unwrap_or(&vec![]) {
-            if verbose {
-                println!("\tPerforming surgery: {:+x?}", s);
-            }
-            let surgery_virt_offset = match s.virtual_offset {
-                VirtualOffset::Relative(vs) => (vs + md.added_byte_count) as i64,
-                VirtualOffset::Absolute => 0,
-            };
-            match s.size {
-                4 => {
-                    let target = (func_virt_offset as i64 - surgery_virt_offset) as i32;
-                    if verbose {
-                        println!("\tTarget Jump: {:+x}", target);
-                    }
-                    let data = target.to_le_bytes();
-                    exec_mmap[(s.file_offset + md.added_byte_count) as usize
-                        ..(s.file_offset + md.added_byte_count) as usize + 4]
-                        .copy_from_slice(&data);
-                }
-                8 => {
-                    let target = func_virt_offset as i64 - surgery_virt_offset;
-                    if verbose {
-                        println!("\tTarget Jump: {:+x}", target);
-                    }
-                    let data = target.to_le_bytes();
-                    exec_mmap[(s.file_offset + md.added_byte_count) as usize
-                        ..(s.file_offset + md.added_byte_count) as usize + 8]
-                        .copy_from_slice(&data);
-                }
-                x => {
-                    internal_error!("Surgery size not yet supported: {}", x);
-                }
-            }
-        }
-
-        // Replace plt call code with just a jump.
-        // This is a backup incase we missed a call to the plt.
-        if let Some((plt_off, plt_vaddr)) = md.plt_addresses.get(func_name) {
-            let plt_off = (*plt_off + md.added_byte_count) as usize;
-            let plt_vaddr = *plt_vaddr + md.added_byte_count;
-            let jmp_inst_len = 5;
-            let target =
-                (func_virt_offset as i64 - (plt_vaddr as i64 + jmp_inst_len as i64)) as i32;
-            if verbose {
-                println!("\tPLT: {:+x}, {:+x}", plt_off, plt_vaddr);
-                println!("\tTarget Jump: {:+x}", target);
-            }
-            let data = target.to_le_bytes();
-            exec_mmap[plt_off] = 0xE9;
-            exec_mmap[plt_off + 1..plt_off + jmp_inst_len].copy_from_slice(&data);
-            for i in jmp_inst_len..PLT_ADDRESS_OFFSET as usize {
-                exec_mmap[plt_off + i] = 0x90;
-            }
-        }
+        redirect_calls(md, exec_mmap, func_name, func_virt_offset, target, verbose);
 
         if let Some(i) = md.dynamic_symbol_indices.get(func_name) {
             let sym = load_struct_inplace_mut::<elf::Sym64<LittleEndian>>(
                 exec_mmap,
                 dynsym_offset as usize + *i as usize * mem::size_of::<elf::Sym64<LittleEndian>>(),
             );
+            sym.st_info = (elf::STB_GLOBAL << 4) | elf::STT_FUNC;
             sym.st_shndx = endian::U16::new(LittleEndian, new_text_section_index as u16);
             sym.st_value = endian::U64::new(LittleEndian, func_virt_offset as u64);
             sym.st_size = endian::U64::new(
@@ -2902,6 +2887,141 @@ pub fn surgery_elf(
     *offset_ref = offset;
 }
 
+/// Generates `.gnu.hash` and `.hash` tables, for whichever of them the host has,
+/// that cover the app functions as well as the symbols the host already defines.
+fn gen_elf_dynamic_hash_tables(
+    md: &metadata::Metadata,
+    exec_mmap: &[u8],
+    sh_offset: u64,
+    sh_num: u16,
+) -> Vec<(u32, Vec<u8>)> {
+    let section_headers = load_structs_inplace::<elf::SectionHeader64<LittleEndian>>(
+        exec_mmap,
+        sh_offset as usize,
+        sh_num as usize,
+    );
+    let dynsym_sec = match section_headers
+        .iter()
+        .find(|sh| sh.sh_type.get(NativeEndian) == elf::SHT_DYNSYM)
+    {
+        Some(sh) => sh,
+        None => return vec![],
+    };
+    let dynstr_offset = section_headers[dynsym_sec.sh_link.get(NativeEndian) as usize]
+        .sh_offset
+        .get(NativeEndian);
+
+    let dynsym_offset = md.dynamic_symbol_table_section_offset + md.added_byte_count;
+    let symbols = load_structs_inplace::<elf::Sym64<LittleEndian>>(
+        exec_mmap,
+        dynsym_offset as usize,
+        md.dynamic_symbol_table_size as usize / mem::size_of::<elf::Sym64<LittleEndian>>(),
+    );
+    let app_symbol_indices: Vec<u64> = md
+        .app_functions
+        .iter()
+        .filter_map(|name| md.dynamic_symbol_indices.get(name).copied())
+        .collect();
+    let names: Vec<&[u8]> = symbols
+        .iter()
+        .map(|sym| {
+            let start = (dynstr_offset + sym.st_name.get(NativeEndian) as u64) as usize;
+            let len = exec_mmap[start..].iter().position(|c| *c == 0).unwrap();
+            &exec_mmap[start..start + len]
+        })
+        .collect();
+    let defined: Vec<bool> = symbols
+        .iter()
+        .enumerate()
+        .map(|(i, sym)| {
+            sym.st_shndx.get(NativeEndian) != elf::SHN_UNDEF
+                || app_symbol_indices.contains(&(i as u64))
+        })
+        .collect();
+
+    let mut tables = vec![];
+    for sh in section_headers {
+        match sh.sh_type.get(NativeEndian) {
+            elf::SHT_GNU_HASH => {
+                tables.push((elf::SHT_GNU_HASH, gen_elf_gnu_hash_table(&names, &defined)))
+            }
+            elf::SHT_HASH => tables.push((elf::SHT_HASH, gen_elf_sysv_hash_table(&names))),
+            _ => {}
+        }
+    }
+    tables
+}
+
+/// Generates a `.gnu.hash` table for the symbols as they are currently ordered.
+/// The format requires symbols that share a bucket to be adjacent in the symbol table,
+/// so without reordering the dynamic symbols everything has to go in a single bucket.
+/// The bloom filter still lets the loader quickly reject names that are not in the table.
+fn gen_elf_gnu_hash_table(names: &[&[u8]], defined: &[bool]) -> Vec<u8> {
+    const BLOOM_SHIFT: u32 = 26;
+
+    let hashes: Vec<u32> = names.iter().map(|name| gnu_hash(name)).collect();
+    let bloom_size = (names.len() * 12 / 64).max(1).next_power_of_two();
+    let mut bloom = vec![0u64; bloom_size];
+    for (hash, _) in hashes.iter().zip(defined).filter(|(_, defined)| **defined) {
+        bloom[(*hash as usize / 64) % bloom_size] |=
+            (1 << (hash % 64)) | (1 << ((hash >> BLOOM_SHIFT) % 64));
+    }
+
+    let sym_offset = 1;
+    let mut out = vec![];
+    out.extend_from_slice(&1u32.to_le_bytes());
+    out.extend_from_slice(&(sym_offset as u32).to_le_bytes());
+    out.extend_from_slice(&(bloom_size as u32).to_le_bytes());
+    out.extend_from_slice(&BLOOM_SHIFT.to_le_bytes());
+    for word in bloom {
+        out.extend_from_slice(&word.to_le_bytes());
+    }
+    if names.len() > sym_offset {
+        out.extend_from_slice(&(sym_offset as u32).to_le_bytes());
+        for (i, hash) in hashes.iter().enumerate().skip(sym_offset) {
+            // The low bit marks the end of a chain.
+            let end = (i == names.len() - 1) as u32;
+            out.extend_from_slice(&((hash & !1) | end).to_le_bytes());
+        }
+    } else {
+        out.extend_from_slice(&0u32.to_le_bytes());
+    }
+    out
+}
+
+/// Generates a System V `.hash` table. Its chains can link symbols in any order.
+fn gen_elf_sysv_hash_table(names: &[&[u8]]) -> Vec<u8> {
+    let bucket_count = (names.len() / 2).max(1);
+    let mut buckets = vec![0u32; bucket_count];
+    let mut chains = vec![0u32; names.len()];
+    for (i, name) in names.iter().enumerate().skip(1) {
+        let bucket = sysv_hash(name) as usize % bucket_count;
+        chains[i] = buckets[bucket];
+        buckets[bucket] = i as u32;
+    }
+
+    let mut out = vec![];
+    out.extend_from_slice(&(bucket_count as u32).to_le_bytes());
+    out.extend_from_slice(&(names.len() as u32).to_le_bytes());
+    for word in buckets.iter().chain(chains.iter()) {
+        out.extend_from_slice(&word.to_le_bytes());
+    }
+    out
+}
+
+fn gnu_hash(name: &[u8]) -> u32 {
+    name.iter()
+        .fold(5381u32, |h, c| h.wrapping_mul(33).wrapping_add(*c as u32))
+}
+
+fn sysv_hash(name: &[u8]) -> u32 {
+    name.iter().fold(0u32, |h, c| {
+        let h = (h << 4).wrapping_add(*c as u32);
+        let g = h & 0xf000_0000;
+        (h ^ (g >> 24)) & !g
+    })
+}
+
 fn align_by_constraint(offset: usize, constraint: usize) -> usize {
     if offset % constraint == 0 {
         offset
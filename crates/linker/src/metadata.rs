@@ -32,6 +32,7 @@ pub struct Metadata {
     pub last_vaddr: u64,
     pub dynamic_section_offset: u64,
     pub dynamic_symbol_table_section_offset: u64,
+    pub dynamic_symbol_table_size: u64,
     pub symbol_table_section_offset: u64,
     pub symbol_table_size: u64,
     pub macho_cmd_loc: u64,
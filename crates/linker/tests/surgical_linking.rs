@@ -0,0 +1,514 @@
+//! Links a tiny app into hosts built for every target the surgical linker supports.
+//!
+//! The hosts are cross-compiled with `zig cc`, so all of these run on any machine with zig
+//! installed, and are skipped (with a warning) elsewhere. The linked executables are only run when they target the machine running the tests;
+//! otherwise we check that the host's call now goes straight to the app.
+//! Hosts built as shared libraries are checked the same way, and must also export the app.
+
+use iced_x86::{Decoder, DecoderOptions, Instruction};
+use object::write;
+use object::{
+    elf, macho, Architecture, BinaryFormat, Endianness, Object, ObjectSection, ObjectSegment,
+    ObjectSymbol, RelocationEncoding, RelocationKind, SymbolFlags, SymbolKind, SymbolScope,
+};
+use roc_build::link::LinkType;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::str::FromStr;
+use target_lexicon::Triple;
+
+const HOST_SRC: &str = r#"
+#include <stdint.h>
+#include <stdlib.h>
+
+extern int32_t roc__mainForHost_1_exposed(void);
+
+__attribute__((used)) void *roc_alloc(size_t size, unsigned int alignment) {
+    return malloc(size);
+}
+
+int main(void) {
+    return roc__mainForHost_1_exposed();
+}
+"#;
+
+const DUMMY_APP_SRC: &str = r#"
+#include <stdint.h>
+
+int32_t roc__mainForHost_1_exposed(void) {
+    return 0;
+}
+"#;
+
+/// The value the app returns, which it loads from its rodata.
+const APP_VALUE: i32 = 42;
+
+/// `roc_alloc(8, 8)`, then load `APP_VALUE` from rodata and return it.
+const AARCH64_APP_CODE: [u32; 8] = [
+    0xa9bf7bfd, // stp x29, x30, [sp, #-16]!
+    0xd2800100, // mov x0, #8
+    0x52800101, // mov w1, #8
+    0x94000000, // bl roc_alloc
+    0x90000008, // adrp x8, value
+    0xb9400100, // ldr w0, [x8, :lo12:value]
+    0xa8c17bfd, // ldp x29, x30, [sp], #16
+    0xd65f03c0, // ret
+];
+const AARCH64_CALL_OFFSET: u64 = 12;
+const AARCH64_ADRP_OFFSET: u64 = 16;
+const AARCH64_LDR_OFFSET: u64 = 20;
+
+/// The same as `AARCH64_APP_CODE`.
+const X86_64_APP_CODE: [u8; 30] = [
+    0x48, 0x83, 0xec, 0x08, // sub rsp, 8
+    0xbf, 0x08, 0x00, 0x00, 0x00, // mov edi, 8
+    0xbe, 0x08, 0x00, 0x00, 0x00, // mov esi, 8
+    0xe8, 0x00, 0x00, 0x00, 0x00, // call roc_alloc
+    0x8b, 0x05, 0x00, 0x00, 0x00, 0x00, // mov eax, [rip + value]
+    0x48, 0x83, 0xc4, 0x08, // add rsp, 8
+    0xc3, // ret
+];
+const X86_64_CALL_OFFSET: u64 = 15;
+const X86_64_LOAD_OFFSET: u64 = 21;
+
+fn app_code(triple: &Triple) -> Vec<u8> {
+    match triple.architecture {
+        target_lexicon::Architecture::Aarch64(_) => AARCH64_APP_CODE
+            .iter()
+            .flat_map(|inst| inst.to_le_bytes())
+            .collect(),
+        target_lexicon::Architecture::X86_64 => X86_64_APP_CODE.to_vec(),
+        _ => unreachable!(),
+    }
+}
+
+/// What the Roc compiler would produce for an app: an object file defining
+/// `roc__mainForHost_1_exposed`, which calls back into the host and reads its own rodata.
+fn build_app(triple: &Triple, app_path: &Path) {
+    let (format, arch) = match (triple.binary_format, triple.architecture) {
+        (target_lexicon::BinaryFormat::Elf, target_lexicon::Architecture::X86_64) => {
+            (BinaryFormat::Elf, Architecture::X86_64)
+        }
+        (target_lexicon::BinaryFormat::Elf, target_lexicon::Architecture::Aarch64(_)) => {
+            (BinaryFormat::Elf, Architecture::Aarch64)
+        }
+        (target_lexicon::BinaryFormat::Macho, target_lexicon::Architecture::X86_64) => {
+            (BinaryFormat::MachO, Architecture::X86_64)
+        }
+        (target_lexicon::BinaryFormat::Macho, target_lexicon::Architecture::Aarch64(_)) => {
+            (BinaryFormat::MachO, Architecture::Aarch64)
+        }
+        _ => unreachable!(),
+    };
+
+    let mut app = write::Object::new(format, arch, Endianness::Little);
+
+    let text = app.section_id(write::StandardSection::Text);
+    let text_offset = app.append_section_data(text, &app_code(triple), 16);
+    app.add_symbol(write::Symbol {
+        name: b"roc__mainForHost_1_exposed".to_vec(),
+        value: text_offset,
+        size: app_code(triple).len() as u64,
+        kind: SymbolKind::Text,
+        scope: SymbolScope::Linkage,
+        weak: false,
+        section: write::SymbolSection::Section(text),
+        flags: SymbolFlags::None,
+    });
+
+    let rodata = app.section_id(write::StandardSection::ReadOnlyData);
+    let value_offset = app.append_section_data(rodata, &APP_VALUE.to_le_bytes(), 16);
+    let value = app.add_symbol(write::Symbol {
+        name: b"value".to_vec(),
+        value: value_offset,
+        size: 4,
+        kind: SymbolKind::Data,
+        scope: SymbolScope::Compilation,
+        weak: false,
+        section: write::SymbolSection::Section(rodata),
+        flags: SymbolFlags::None,
+    });
+
+    let roc_alloc = app.add_symbol(write::Symbol {
+        name: b"roc_alloc".to_vec(),
+        value: 0,
+        size: 0,
+        kind: SymbolKind::Text,
+        scope: SymbolScope::Linkage,
+        weak: false,
+        section: write::SymbolSection::Undefined,
+        flags: SymbolFlags::None,
+    });
+
+    let relocations = match (format, arch) {
+        (_, Architecture::X86_64) => vec![
+            (
+                X86_64_CALL_OFFSET,
+                RelocationKind::PltRelative,
+                RelocationEncoding::X86Branch,
+                roc_alloc,
+            ),
+            (
+                X86_64_LOAD_OFFSET,
+                RelocationKind::Relative,
+                RelocationEncoding::Generic,
+                value,
+            ),
+        ]
+        .into_iter()
+        .map(|(offset, kind, encoding, symbol)| write::Relocation {
+            offset: text_offset + offset,
+            size: 32,
+            kind,
+            encoding,
+            symbol,
+            addend: -4,
+        })
+        .collect::<Vec<_>>(),
+        (BinaryFormat::Elf, Architecture::Aarch64) => vec![
+            (
+                AARCH64_CALL_OFFSET,
+                RelocationKind::Elf(elf::R_AARCH64_CALL26),
+                roc_alloc,
+            ),
+            (
+                AARCH64_ADRP_OFFSET,
+                RelocationKind::Elf(elf::R_AARCH64_ADR_PREL_PG_HI21),
+                value,
+            ),
+            (
+                AARCH64_LDR_OFFSET,
+                RelocationKind::Elf(elf::R_AARCH64_LDST32_ABS_LO12_NC),
+                value,
+            ),
+        ]
+        .into_iter()
+        .map(|(offset, kind, symbol)| write::Relocation {
+            offset: text_offset + offset,
+            size: 32,
+            kind,
+            encoding: RelocationEncoding::Generic,
+            symbol,
+            addend: 0,
+        })
+        .collect(),
+        (_, Architecture::Aarch64) => vec![
+            (
+                AARCH64_CALL_OFFSET,
+                macho::ARM64_RELOC_BRANCH26,
+                true,
+                roc_alloc,
+            ),
+            (AARCH64_ADRP_OFFSET, macho::ARM64_RELOC_PAGE21, true, value),
+            (
+                AARCH64_LDR_OFFSET,
+                macho::ARM64_RELOC_PAGEOFF12,
+                false,
+                value,
+            ),
+        ]
+        .into_iter()
+        .map(|(offset, r_type, relative, symbol)| write::Relocation {
+            offset: text_offset + offset,
+            size: 32,
+            kind: RelocationKind::MachO {
+                value: r_type,
+                relative,
+            },
+            encoding: RelocationEncoding::Generic,
+            symbol,
+            addend: 0,
+        })
+        .collect(),
+        _ => unreachable!(),
+    };
+
+    for relocation in relocations {
+        app.add_relocation(text, relocation).unwrap();
+    }
+
+    fs::write(app_path, app.write().unwrap()).unwrap();
+}
+
+fn zig_is_installed() -> bool {
+    Command::new("zig").arg("version").output().is_ok()
+}
+
+fn zig_cc(triple: &Triple, args: &[&str]) {
+    let zig_target = match (triple.architecture, triple.binary_format) {
+        (target_lexicon::Architecture::X86_64, target_lexicon::BinaryFormat::Elf) => {
+            "x86_64-linux-gnu"
+        }
+        (target_lexicon::Architecture::Aarch64(_), target_lexicon::BinaryFormat::Elf) => {
+            "aarch64-linux-gnu"
+        }
+        (target_lexicon::Architecture::X86_64, target_lexicon::BinaryFormat::Macho) => {
+            "x86_64-macos"
+        }
+        (target_lexicon::Architecture::Aarch64(_), target_lexicon::BinaryFormat::Macho) => {
+            "aarch64-macos"
+        }
+        _ => unreachable!(),
+    };
+
+    let output = Command::new("zig")
+        .args(&["cc", "-target", zig_target])
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "zig cc failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// Builds the host against a dummy libapp, the same way `build_and_preprocess_host` does.
+fn build_host(triple: &Triple, dir: &Path, link_type: LinkType) -> (String, String) {
+    let is_macho = triple.binary_format == target_lexicon::BinaryFormat::Macho;
+    let lib_name = if is_macho {
+        "libapp.dylib"
+    } else {
+        "libapp.so"
+    };
+    let lib_path = dir.join(lib_name);
+    let lib_path = lib_path.to_str().unwrap();
+    let dummy_app_path = dir.join("app.c");
+    let host_src_path = dir.join("host.c");
+    let host_path = dir.join("dynhost");
+
+    fs::write(&dummy_app_path, DUMMY_APP_SRC).unwrap();
+    fs::write(&host_src_path, HOST_SRC).unwrap();
+
+    let lib_name_flag = if is_macho {
+        format!("-Wl,-install_name,{}", lib_name)
+    } else {
+        format!("-Wl,-soname,{}", lib_name)
+    };
+    zig_cc(
+        triple,
+        &[
+            "-shared",
+            &lib_name_flag,
+            dummy_app_path.to_str().unwrap(),
+            "-o",
+            lib_path,
+        ],
+    );
+
+    let mut host_args = vec!["-O0"];
+    if link_type == LinkType::Dylib {
+        host_args.extend(["-shared", "-fPIC", "-Wl,-soname,libhost.so"]);
+    } else {
+        host_args.extend(["-fPIE", "-pie"]);
+    }
+    host_args.extend([
+        host_src_path.to_str().unwrap(),
+        lib_path,
+        "-o",
+        host_path.to_str().unwrap(),
+    ]);
+    if is_macho {
+        host_args.push("-Wl,-headerpad,0x1000");
+    }
+    zig_cc(triple, &host_args);
+
+    (
+        host_path.to_str().unwrap().to_string(),
+        lib_path.to_string(),
+    )
+}
+
+/// Reads `len` bytes of `exec` at virtual address `address`.
+fn read_at_address<'a>(exec: &object::File<'a>, address: u64, len: u64) -> &'a [u8] {
+    exec.segments()
+        .find_map(|segment| {
+            let (_, file_size) = segment.file_range();
+            let start = address.checked_sub(segment.address())?;
+
+            if start + len <= file_size {
+                let data = segment.data().ok()?;
+
+                Some(&data[start as usize..][..len as usize])
+            } else {
+                None
+            }
+        })
+        .unwrap_or_else(|| panic!("{:#x} is not in any segment", address))
+}
+
+/// Where the first call in `main` goes.
+fn main_call_target(triple: &Triple, exec: &object::File) -> u64 {
+    let main = exec
+        .symbols()
+        .find(|sym| sym.name() == Ok("main") || sym.name() == Ok("_main"))
+        .expect("the host has no main");
+    let main_code = read_at_address(exec, main.address(), 64);
+
+    match triple.architecture {
+        target_lexicon::Architecture::Aarch64(_) => main_code
+            .chunks_exact(4)
+            .enumerate()
+            .find_map(|(i, word)| {
+                let inst = u32::from_le_bytes(word.try_into().unwrap());
+                let is_bl = inst & 0xfc00_0000 == 0x9400_0000;
+
+                is_bl.then(|| {
+                    let offset = (((inst & 0x03ff_ffff) << 6) as i32 >> 6) as i64 * 4;
+
+                    (main.address() as i64 + 4 * i as i64 + offset) as u64
+                })
+            })
+            .expect("main does not call anything"),
+        target_lexicon::Architecture::X86_64 => {
+            let mut decoder = Decoder::with_ip(64, main_code, main.address(), DecoderOptions::NONE);
+            let mut inst = Instruction::default();
+
+            while decoder.can_decode() {
+                decoder.decode_out(&mut inst);
+
+                if inst.is_call_near() {
+                    return inst.near_branch_target();
+                }
+            }
+
+            panic!("main does not call anything")
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn is_host_target(triple: &Triple) -> bool {
+    let host = Triple::host();
+
+    host.architecture == triple.architecture
+        && host.operating_system == triple.operating_system
+        && host.binary_format == triple.binary_format
+}
+
+fn link_and_check(target: &str, link_type: LinkType) {
+    let triple = Triple::from_str(target).unwrap();
+    assert!(roc_linker::supported(link_type, &triple));
+
+    if !zig_is_installed() {
+        eprintln!("WARNING: skipping surgical linking for {target} because zig isn't installed!");
+
+        return;
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let dir = dir.path();
+    let (host_path, lib_path) = build_host(&triple, dir, link_type);
+    let app_path = dir.join("app.o");
+    let metadata_path = dir.join("metadata");
+    let exec_path = dir.join("roc_app");
+    let (app_path, metadata_path, exec_path) = (
+        app_path.to_str().unwrap(),
+        metadata_path.to_str().unwrap(),
+        exec_path.to_str().unwrap(),
+    );
+
+    build_app(&triple, Path::new(app_path));
+
+    roc_linker::preprocess(
+        &triple,
+        &host_path,
+        metadata_path,
+        exec_path,
+        Path::new(&lib_path),
+        false,
+        false,
+    );
+    roc_linker::surgery(app_path, metadata_path, exec_path, false, false, &triple);
+
+    let exec_data = fs::read(exec_path).unwrap();
+    let exec = object::File::parse(&*exec_data).unwrap();
+
+    // The call in main used to go to the PLT or a stub; now it goes straight to the app's code.
+    let call_target = main_call_target(&triple, &exec);
+    let code = app_code(&triple);
+    assert_eq!(
+        read_at_address(&exec, call_target, code.len() as u64)[..4],
+        code[..4],
+        "main calls {:#x}, which is not the app",
+        call_target
+    );
+    let plt = exec
+        .sections()
+        .find(|sec| matches!(sec.name(), Ok(".plt" | "__stubs")))
+        .unwrap();
+    assert!(!(plt.address()..plt.address() + plt.size()).contains(&call_target));
+
+    if link_type == LinkType::Dylib {
+        // The app is exported, so whatever loads the library can use it too.
+        let exported = exec
+            .dynamic_symbols()
+            .find(|sym| sym.name() == Ok("roc__mainForHost_1_exposed"))
+            .unwrap();
+
+        assert!(!exported.is_undefined());
+        assert_eq!(exported.address(), call_target);
+    } else if is_host_target(&triple) {
+        let status = Command::new(exec_path).status().unwrap();
+
+        assert_eq!(status.code(), Some(APP_VALUE));
+    }
+}
+
+#[test]
+fn link_linux_x86_64() {
+    link_and_check("x86_64-unknown-linux-gnu", LinkType::Executable);
+}
+
+#[test]
+fn link_linux_aarch64() {
+    link_and_check("aarch64-unknown-linux-gnu", LinkType::Executable);
+}
+
+#[test]
+fn link_macos_x86_64() {
+    link_and_check("x86_64-apple-darwin", LinkType::Executable);
+}
+
+#[test]
+fn link_macos_aarch64() {
+    link_and_check("aarch64-apple-darwin", LinkType::Executable);
+}
+
+#[test]
+fn link_linux_x86_64_lib() {
+    link_and_check("x86_64-unknown-linux-gnu", LinkType::Dylib);
+}
+
+#[test]
+fn link_linux_aarch64_lib() {
+    link_and_check("aarch64-unknown-linux-gnu", LinkType::Dylib);
+}
+
+#[test]
+fn cargo_host_lib_needs_legacy_linker() {
+    let dir = tempfile::tempdir().unwrap();
+    let host_input_path = dir.path().join("host.o");
+
+    fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+    fs::write(dir.path().join("host.c"), "").unwrap();
+
+    assert!(!roc_linker::supported_host(
+        LinkType::Dylib,
+        &host_input_path
+    ));
+    assert!(roc_linker::supported_host(
+        LinkType::Executable,
+        &host_input_path
+    ));
+
+    // A zig host takes precedence over the Cargo.toml, and can be built as a shared library.
+    fs::write(dir.path().join("host.zig"), "").unwrap();
+
+    assert!(roc_linker::supported_host(
+        LinkType::Dylib,
+        &host_input_path
+    ));
+}
@@ -9,18 +9,22 @@ use rustyline::validate::{self, ValidationContext, ValidationResult, Validator};
 use rustyline_derive::{Completer, Helper, Hinter};
 use std::borrow::Cow;
 use std::io;
+use std::time::Instant;
 use target_lexicon::Triple;
 
 use roc_build::link::llvm_module_to_dylib;
 use roc_collections::all::MutSet;
 use roc_gen_llvm::llvm::externs::add_default_roc_externs;
 use roc_gen_llvm::{run_jit_function, run_jit_function_dynamic_type};
-use roc_load::{EntryPoint, MonomorphizedModule};
+use roc_load::{EntryPoint, MonomorphizedModule, SolvedCache};
 use roc_mono::ir::OptLevel;
 use roc_parse::ast::Expr;
-use roc_parse::parser::{EExpr, ELambda, SyntaxError};
+use roc_parse::module::module_defs;
+use roc_parse::parser::{EExpr, ELambda, Parser, SyntaxError};
+use roc_repl_eval::commands::{run_command, ReplCommand, Timings};
 use roc_repl_eval::eval::jit_to_ast;
-use roc_repl_eval::gen::{compile_to_mono, format_answer, ReplOutput};
+use roc_repl_eval::gen::{format_answer, ReplOutput};
+use roc_repl_eval::session::ReplSession;
 use roc_repl_eval::{ReplApp, ReplAppMemory};
 use roc_reporting::report::DEFAULT_PALETTE;
 use roc_std::RocStr;
@@ -108,6 +112,16 @@ impl Validator for InputValidator {
             let arena = bumpalo::Bump::new();
             let state = roc_parse::state::State::new(ctx.input().trim().as_bytes());
 
+            // Definitions without an expression after them are complete once they're followed by
+            // a blank line; they become part of the session.
+            if ctx.input().ends_with('\n') {
+                if let Ok((_, defs, _)) = module_defs().parse(&arena, state.clone()) {
+                    if !defs.is_empty() {
+                        return Ok(ValidationResult::Valid(None));
+                    }
+                }
+            }
+
             match roc_parse::expr::parse_loc_expr(0, &arena, state) {
                 // Special case some syntax errors to allow for multi-line inputs
                 Err((_, EExpr::DefMissingFinalExpr(_), _))
@@ -295,17 +309,23 @@ pub fn mono_module_to_dylib<'a>(
 }
 
 fn gen_and_eval_llvm<'a>(
+    session: &mut ReplSession,
     src: &str,
     target: Triple,
     opt_level: OptLevel,
-) -> Result<ReplOutput, SyntaxError<'a>> {
+    timings: &mut Timings,
+) -> Result<Option<ReplOutput>, SyntaxError<'a>> {
     let arena = Bump::new();
     let target_info = TargetInfo::from(&target);
+    let compile_start = Instant::now();
 
-    let mut loaded = match compile_to_mono(&arena, src, target_info, DEFAULT_PALETTE) {
-        Ok(x) => x,
+    let mut loaded = match session.compile(&arena, src, target_info, DEFAULT_PALETTE) {
+        Ok(Some(x)) => x,
+        Ok(None) => {
+            return Ok(None);
+        }
         Err(prob_strings) => {
-            return Ok(ReplOutput::Problems(prob_strings));
+            return Ok(Some(ReplOutput::Problems(prob_strings)));
         }
     };
 
@@ -327,10 +347,10 @@ fn gen_and_eval_llvm<'a>(
     let (_, main_fn_layout) = match loaded.procedures.keys().find(|(s, _)| *s == main_fn_symbol) {
         Some(layout) => *layout,
         None => {
-            return Ok(ReplOutput::NoProblems {
+            return Ok(Some(ReplOutput::NoProblems {
                 expr: "<function>".to_string(),
                 expr_type: expr_type_str,
-            });
+            }));
         }
     };
 
@@ -339,6 +359,9 @@ fn gen_and_eval_llvm<'a>(
     let (lib, main_fn_name, subs) =
         mono_module_to_dylib(&arena, target, loaded, opt_level).expect("we produce a valid Dylib");
 
+    timings.compile_ms = compile_start.elapsed().as_secs_f64() * 1000.0;
+
+    let eval_start = Instant::now();
     let mut app = CliApp { lib };
 
     let res_answer = jit_to_ast(
@@ -353,24 +376,63 @@ fn gen_and_eval_llvm<'a>(
     );
 
     let formatted = format_answer(&arena, res_answer, expr_type_str);
-    Ok(formatted)
+
+    timings.eval_ms = eval_start.elapsed().as_secs_f64() * 1000.0;
+
+    Ok(Some(formatted))
 }
 
-fn eval_and_format<'a>(src: &str) -> Result<String, SyntaxError<'a>> {
+/// Evaluates the input in the session, and formats the result. Inputs with nothing to evaluate
+/// (definitions and imports) have no output.
+fn eval_and_format<'a>(
+    session: &mut ReplSession,
+    src: &str,
+) -> Result<Option<String>, SyntaxError<'a>> {
+    let mut timings = Timings::default();
+    let opt_output =
+        gen_and_eval_llvm(session, src, Triple::host(), OptLevel::Normal, &mut timings)?;
+
+    let reports_timings = session.reports_timings();
     let format_output = |output| match output {
+        ReplOutput::NoProblems { expr, expr_type } if reports_timings => {
+            format!("\n{} {}:{} {}\n{}", expr, PINK, END_COL, expr_type, timings)
+        }
         ReplOutput::NoProblems { expr, expr_type } => {
             format!("\n{} {}:{} {}", expr, PINK, END_COL, expr_type)
         }
         ReplOutput::Problems(lines) => format!("\n{}\n", lines.join("\n\n")),
     };
 
-    gen_and_eval_llvm(src, Triple::host(), OptLevel::Normal).map(format_output)
+    Ok(opt_output.map(format_output))
+}
+
+/// Runs a command other than `:exit`, and formats what it printed
+fn run_and_format_command(session: &mut ReplSession, command: ReplCommand) -> String {
+    let arena = Bump::new();
+    let target_info = TargetInfo::from(&Triple::host());
+
+    match run_command(session, &arena, command, target_info, DEFAULT_PALETTE) {
+        Ok(output) => format!("\n{}", output),
+        Err(problems) => format!("\n{}\n", problems.join("\n\n")),
+    }
 }
 
 fn report_parse_error(fail: SyntaxError) {
     println!("TODO Gracefully report parse error in repl: {:?}", fail);
 }
 
+/// Imports come from the current directory, and the modules they load are cached like `roc check`
+/// caches them.
+fn new_session() -> ReplSession {
+    let src_dir = std::env::current_dir().unwrap_or_default();
+    let session = ReplSession::new(src_dir);
+
+    match SolvedCache::default_dir() {
+        Some(dir) => session.with_solved_cache(SolvedCache::new(dir)),
+        None => session,
+    }
+}
+
 pub fn main() -> io::Result<()> {
     use rustyline::error::ReadlineError;
     use rustyline::Editor;
@@ -381,6 +443,7 @@ pub fn main() -> io::Result<()> {
     print!("{}{}", WELCOME_MESSAGE, INSTRUCTIONS);
 
     let mut prev_line_blank = false;
+    let mut session = new_session();
     let mut editor = Editor::<ReplHelper>::new();
     let repl_helper = ReplHelper::new();
     editor.set_helper(Some(repl_helper));
@@ -405,9 +468,11 @@ pub fn main() -> io::Result<()> {
                         } else if prev_line_blank {
                             // After two blank lines in a row, give up and try parsing it
                             // even though it's going to fail. This way you don't get stuck.
-                            match eval_and_format(pending_src.as_str()) {
-                                Ok(output) => {
-                                    println!("{}", output);
+                            match eval_and_format(&mut session, pending_src.as_str()) {
+                                Ok(opt_output) => {
+                                    if let Some(output) = opt_output {
+                                        println!("{}", output);
+                                    }
                                 }
                                 Err(fail) => {
                                     report_parse_error(fail);
@@ -422,28 +487,32 @@ pub fn main() -> io::Result<()> {
                             continue; // Skip the part where we reset prev_line_blank to false
                         }
                     }
-                    ":help" => {
-                        println!("Use :exit or :q to exit.");
-                    }
-                    ":exit" => {
-                        break;
-                    }
-                    ":q" => {
-                        break;
+                    _ if pending_src.is_empty() && trim_line.starts_with(':') => {
+                        match ReplCommand::parse(trim_line) {
+                            Some(ReplCommand::Exit) => {
+                                break;
+                            }
+                            Some(command) => {
+                                println!("{}", run_and_format_command(&mut session, command));
+                            }
+                            None => {}
+                        }
                     }
                     _ => {
                         let result = if pending_src.is_empty() {
-                            eval_and_format(trim_line)
+                            eval_and_format(&mut session, trim_line)
                         } else {
                             pending_src.push('\n');
                             pending_src.push_str(trim_line);
 
-                            eval_and_format(pending_src.as_str())
+                            eval_and_format(&mut session, pending_src.as_str())
                         };
 
                         match result {
-                            Ok(output) => {
-                                println!("{}", output);
+                            Ok(opt_output) => {
+                                if let Some(output) = opt_output {
+                                    println!("{}", output);
+                                }
                                 pending_src.clear();
                             }
                             //                            Err(Fail {
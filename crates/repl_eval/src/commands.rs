@@ -0,0 +1,151 @@
+//! The commands starting with `:`, which the CLI and the web REPL both understand.
+
+use bumpalo::Bump;
+use std::fmt;
+use std::path::Path;
+
+use roc_reporting::report::Palette;
+use roc_target::TargetInfo;
+
+use crate::session::ReplSession;
+
+#[cfg(not(target_family = "wasm"))]
+pub const HELP: &str = "\
+:type <expr>       Print the type of an expression, without evaluating it
+:ir <expr>         Print the IR an expression specializes to
+:defs              List the session's imports and definitions
+:load <file.roc>   Import everything an interface module exposes
+:reload            Read the loaded modules again
+:time              Turn reporting compile and eval times on or off
+:help              Print this message
+:exit, :q          Leave the REPL";
+
+/// The web REPL has no files to `:load`, so it leaves those commands out.
+#[cfg(target_family = "wasm")]
+pub const HELP: &str = "\
+:type <expr>       Print the type of an expression, without evaluating it
+:ir <expr>         Print the IR an expression specializes to
+:defs              List the session's imports and definitions
+:time              Turn reporting compile and eval times on or off
+:help              Print this message";
+
+/// Whether `:load` and `:reload` can read files where the REPL runs
+const CAN_LOAD_FILES: bool = !cfg!(target_family = "wasm");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplCommand<'a> {
+    Help,
+    Exit,
+    Type(&'a str),
+    Ir(&'a str),
+    Defs,
+    Load(&'a str),
+    Reload,
+    Time,
+    Unknown(&'a str),
+}
+
+impl<'a> ReplCommand<'a> {
+    /// Returns `None` if the input isn't a command, which is anything not starting with `:`
+    pub fn parse(src: &'a str) -> Option<Self> {
+        let src = src.trim();
+        let rest = src.strip_prefix(':')?;
+
+        let (name, arg) = match rest.find(char::is_whitespace) {
+            Some(index) => (&rest[..index], rest[index..].trim()),
+            None => (rest, ""),
+        };
+
+        let command = match name.to_lowercase().as_str() {
+            "help" | "h" => ReplCommand::Help,
+            "exit" | "q" => ReplCommand::Exit,
+            "type" | "t" => ReplCommand::Type(arg),
+            "ir" => ReplCommand::Ir(arg),
+            "defs" => ReplCommand::Defs,
+            "load" | "l" => ReplCommand::Load(arg),
+            "reload" | "r" => ReplCommand::Reload,
+            "time" => ReplCommand::Time,
+            _ => ReplCommand::Unknown(src),
+        };
+
+        Some(command)
+    }
+}
+
+/// How long an input took to compile, and then to evaluate
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Timings {
+    pub compile_ms: f64,
+    pub eval_ms: f64,
+}
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "compiled in {:.1} ms, evaluated in {:.1} ms",
+            self.compile_ms, self.eval_ms
+        )
+    }
+}
+
+/// Runs a command, returning what to print. Leaving the REPL is up to the frontend, so `:exit`
+/// does nothing here.
+pub fn run_command(
+    session: &mut ReplSession,
+    arena: &Bump,
+    command: ReplCommand,
+    target_info: TargetInfo,
+    palette: Palette,
+) -> Result<String, Vec<String>> {
+    match command {
+        ReplCommand::Help => Ok(HELP.to_string()),
+        ReplCommand::Exit => Ok(String::new()),
+        ReplCommand::Type(src) => session.type_of(
+            arena,
+            expect_arg(":type <expr>", src)?,
+            target_info,
+            palette,
+        ),
+        ReplCommand::Ir(src) => {
+            session.ir(arena, expect_arg(":ir <expr>", src)?, target_info, palette)
+        }
+        ReplCommand::Defs => {
+            let defs = session.defs();
+
+            if defs.is_empty() {
+                Ok("There are no definitions yet.".to_string())
+            } else {
+                Ok(defs)
+            }
+        }
+        ReplCommand::Load(_) | ReplCommand::Reload if !CAN_LOAD_FILES => Err(vec![
+            "The web REPL can't read files, so it doesn't support :load or :reload.".to_string(),
+        ]),
+        ReplCommand::Load(path) => {
+            let path = Path::new(expect_arg(":load <file.roc>", path)?);
+
+            session.load(arena, path, target_info, palette)
+        }
+        ReplCommand::Reload => session.reload(arena, target_info, palette),
+        ReplCommand::Time => {
+            if session.toggle_timings() {
+                Ok("Timing is on.".to_string())
+            } else {
+                Ok("Timing is off.".to_string())
+            }
+        }
+        ReplCommand::Unknown(src) => Err(vec![format!(
+            "I don't know the command `{}`. Use :help to see the ones I know.",
+            src
+        )]),
+    }
+}
+
+fn expect_arg<'a>(usage: &str, arg: &'a str) -> Result<&'a str, Vec<String>> {
+    if arg.is_empty() {
+        Err(vec![format!("Usage: {}", usage)])
+    } else {
+        Ok(arg)
+    }
+}
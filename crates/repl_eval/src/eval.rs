@@ -1,6 +1,6 @@
 use bumpalo::collections::Vec;
 use bumpalo::Bump;
-use roc_types::types::AliasKind;
+use roc_types::types::{gather_tuple_elems, AliasKind, TupleStructure};
 use std::cmp::{max_by_key, min_by_key};
 
 use roc_builtins::bitcode::{FloatWidth, IntWidth};
@@ -10,13 +10,16 @@ use roc_module::ident::TagName;
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::ir::ProcLayout;
 use roc_mono::layout::{
-    union_sorted_tags_help, Builtin, Layout, LayoutCache, UnionLayout, UnionVariant, WrappedVariant,
+    cmp_fields, union_sorted_tags_help, Builtin, Layout, LayoutCache, UnionLayout, UnionVariant,
+    WrappedVariant,
 };
 use roc_parse::ast::{AssignedField, Collection, Expr, StrLiteral};
 use roc_region::all::{Loc, Region};
 use roc_std::RocDec;
 use roc_target::TargetInfo;
-use roc_types::subs::{Content, FlatType, GetSubsSlice, RecordFields, Subs, UnionTags, Variable};
+use roc_types::subs::{
+    Content, FlatType, GetSubsSlice, RecordFields, Subs, UnionTags, Variable, VariableSubsSlice,
+};
 
 use crate::{ReplApp, ReplAppMemory};
 
@@ -360,6 +363,9 @@ fn jit_to_ast_help<'a, A: ReplApp<'a>>(
                 Content::Structure(FlatType::EmptyRecord) => {
                     Ok(struct_to_ast(env, mem, addr, RecordFields::empty()))
                 }
+                Content::Structure(FlatType::Tuple(elems, ext)) => {
+                    Ok(tuple_to_ast(env, mem, addr, *elems, *ext))
+                }
                 Content::Structure(FlatType::TagUnion(tags, _)) => {
                     debug_assert_eq!(tags.len(), 1);
 
@@ -554,6 +560,9 @@ fn addr_to_ast<'a, M: ReplAppMemory>(
             Content::Structure(FlatType::Record(fields, _)) => {
                 struct_to_ast(env, mem, addr, *fields)
             }
+            Content::Structure(FlatType::Tuple(elems, ext)) => {
+                tuple_to_ast(env, mem, addr, *elems, *ext)
+            }
             Content::Structure(FlatType::TagUnion(tags, _)) => {
                 debug_assert_eq!(tags.len(), 1);
 
@@ -1037,6 +1046,83 @@ fn struct_to_ast<'a, 'env, M: ReplAppMemory>(
     }
 }
 
+fn tuple_to_ast<'a, 'env, M: ReplAppMemory>(
+    env: &Env<'a, 'env>,
+    mem: &'a M,
+    addr: usize,
+    elems: VariableSubsSlice,
+    ext: Variable,
+) -> Expr<'a> {
+    let arena = env.arena;
+    let subs = env.subs;
+    let mut layout_cache = LayoutCache::new(env.target_info);
+
+    let TupleStructure { elems, ext: _ } =
+        gather_tuple_elems(subs, elems, ext).expect("something weird in content");
+
+    let mut sorted_elems: std::vec::Vec<_> = elems
+        .into_iter()
+        .enumerate()
+        .map(|(index, elem_var)| {
+            let elem_layout = layout_cache.from_var(arena, elem_var, subs).unwrap();
+
+            (index, elem_var, elem_layout)
+        })
+        .collect();
+
+    // The elements are laid out in memory sorted by descending alignment, but we want to render
+    // them in the order they appear in the tuple.
+    sorted_elems.sort_by(|(index1, _, layout1), (index2, _, layout2)| {
+        cmp_fields(index1, layout1, index2, layout2, env.target_info)
+    });
+
+    let mut output: std::vec::Vec<Option<&'a Loc<Expr<'a>>>> = vec![None; sorted_elems.len()];
+
+    if let [(index, elem_var, elem_layout)] = sorted_elems.as_slice() {
+        // a tuple with a single element is unwrapped to that element
+        let content = subs.get_content_without_compacting(*elem_var);
+        let expr = addr_to_ast(
+            env,
+            mem,
+            addr,
+            &Layout::struct_no_name_order(arena.alloc([*elem_layout])),
+            WhenRecursive::Unreachable,
+            content,
+        );
+
+        output[*index] = Some(&*arena.alloc(Loc::at_zero(expr)));
+    } else {
+        // We'll advance this as we iterate through the elements
+        let mut elem_addr = addr;
+
+        for (index, elem_var, elem_layout) in sorted_elems {
+            let content = subs.get_content_without_compacting(elem_var);
+            let expr = addr_to_ast(
+                env,
+                mem,
+                elem_addr,
+                &elem_layout,
+                WhenRecursive::Unreachable,
+                content,
+            );
+
+            output[index] = Some(&*arena.alloc(Loc::at_zero(expr)));
+
+            // Advance the element pointer to the next element.
+            elem_addr += elem_layout.stack_size(env.target_info) as usize;
+        }
+    }
+
+    let output = Vec::from_iter_in(
+        output
+            .into_iter()
+            .map(|elem| elem.expect("every tuple element is rendered")),
+        arena,
+    );
+
+    Expr::Tuple(Collection::with_items(output.into_bump_slice()))
+}
+
 fn unpack_single_element_tag_union(subs: &Subs, tags: UnionTags) -> (&TagName, &[Variable]) {
     let (tag_name_index, payload_vars_index) = tags.iter_all().next().unwrap();
 
@@ -1,12 +1,14 @@
 use bumpalo::Bump;
-use roc_load::{ExecutionMode, LoadConfig, Threading};
+use roc_load::{ExecutionMode, LoadResult, LoadStart, OptLevel, PropertyTesting, SolvedCache};
 use roc_reporting::report::Palette;
 use std::path::PathBuf;
 
 use roc_fmt::annotation::Formattable;
 use roc_fmt::annotation::{Newlines, Parens};
 use roc_load::{LoadingProblem, MonomorphizedModule};
+use roc_module::symbol::Interns;
 use roc_parse::ast::Expr;
+use roc_problem::can::Problem;
 use roc_region::all::LineInfo;
 use roc_reporting::report::{can_problem, type_problem, RocDocAllocator};
 use roc_target::TargetInfo;
@@ -42,35 +44,45 @@ pub fn format_answer(
     }
 }
 
-pub fn compile_to_mono<'a>(
+/// Loads, type checks and specializes the REPL's app module, along with the modules it imports from
+/// `src_dir`. Canonicalization problems for which `is_ignored` returns true aren't reported.
+pub fn compile_to_mono<'a, F>(
     arena: &'a Bump,
-    src: &str,
+    module_src: &'a str,
+    src_dir: PathBuf,
+    solved_cache: Option<SolvedCache>,
     target_info: TargetInfo,
     palette: Palette,
-) -> Result<MonomorphizedModule<'a>, Vec<String>> {
+    is_ignored: F,
+) -> Result<MonomorphizedModule<'a>, Vec<String>>
+where
+    F: Fn(&Problem, &Interns) -> bool,
+{
     let filename = PathBuf::from("");
-    let src_dir = PathBuf::from("fake/test/path");
-
-    let module_src = arena.alloc(promote_expr_to_module(src));
 
     let exposed_types = Default::default();
-    let loaded = roc_load::load_and_monomorphize_from_str(
-        arena,
-        filename,
-        module_src,
-        src_dir,
-        exposed_types,
-        LoadConfig {
+    let loaded = LoadStart::from_str(arena, filename, module_src, src_dir).and_then(|load_start| {
+        let load_start = match solved_cache {
+            Some(solved_cache) => load_start.with_solved_cache(solved_cache),
+            None => load_start,
+        };
+
+        roc_load::load_single_threaded(
+            arena,
+            load_start,
+            exposed_types,
             target_info,
-            render: roc_reporting::report::RenderTarget::ColorTerminal,
-            threading: Threading::Single,
-            exec_mode: ExecutionMode::Executable,
-        },
-    );
+            roc_reporting::report::RenderTarget::ColorTerminal,
+            ExecutionMode::Executable,
+            PropertyTesting::default(),
+            OptLevel::Normal,
+        )
+    });
 
     let mut loaded = match loaded {
-        Ok(v) => v,
-        Err(LoadingProblem::FormattedReport(report)) => {
+        Ok(LoadResult::Monomorphized(v)) => v,
+        Ok(LoadResult::TypeChecked(_)) => unreachable!(),
+        Err(LoadingProblem::FormattedReport(report, _)) => {
             return Err(vec![report]);
         }
         Err(e) => {
@@ -92,13 +104,18 @@ pub fn compile_to_mono<'a>(
         let can_probs = can_problems.remove(home).unwrap_or_default();
         let type_probs = type_problems.remove(home).unwrap_or_default();
 
+        let can_probs: Vec<_> = can_probs
+            .into_iter()
+            .filter(|problem| !is_ignored(problem, interns))
+            .collect();
+
         let error_count = can_probs.len() + type_probs.len();
 
         if error_count == 0 {
             continue;
         }
 
-        let line_info = LineInfo::new(module_src);
+        let line_info = LineInfo::new(src);
         let src_lines: Vec<&str> = src.split('\n').collect();
 
         // Report parsing and canonicalization problems
@@ -130,17 +147,3 @@ pub fn compile_to_mono<'a>(
         Ok(loaded)
     }
 }
-
-fn promote_expr_to_module(src: &str) -> String {
-    let mut buffer =
-        String::from("app \"app\" provides [replOutput] to \"./platform\"\n\nreplOutput =\n");
-
-    for line in src.lines() {
-        // indent the body!
-        buffer.push_str("    ");
-        buffer.push_str(line);
-        buffer.push('\n');
-    }
-
-    buffer
-}
@@ -2,8 +2,10 @@ use roc_parse::ast::Expr;
 use roc_std::RocDec;
 use roc_target::TargetInfo;
 
+pub mod commands;
 pub mod eval;
 pub mod gen;
+pub mod session;
 
 pub trait ReplApp<'a> {
     type Memory: 'a + ReplAppMemory;
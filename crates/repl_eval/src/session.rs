@@ -0,0 +1,588 @@
+//! The definitions and imports a REPL session has accumulated, which every later input can use.
+
+use bumpalo::Bump;
+use std::path::{Path, PathBuf};
+
+use roc_collections::all::MutSet;
+use roc_load::{MonomorphizedModule, SolvedCache};
+use roc_module::symbol::Interns;
+use roc_parse::ast::{
+    Defs, Expr, ExtractSpaces, Module, Pattern, Spaced, TypeDef, TypeHeader, ValueDef,
+};
+use roc_parse::header::ExposedName;
+use roc_parse::module::{module_defs, parse_header};
+use roc_parse::parser::Parser;
+use roc_parse::state::State;
+use roc_problem::can::Problem;
+use roc_reporting::report::Palette;
+use roc_target::TargetInfo;
+use roc_types::pretty_print::{name_and_print_var, DebugPrint};
+
+use crate::gen::compile_to_mono;
+
+/// A top-level definition the session has accumulated
+#[derive(Debug, Clone)]
+struct SessionDef {
+    /// The names it binds
+    names: Vec<String>,
+    /// Its source code, as it was entered
+    src: String,
+}
+
+/// What an input turned out to be
+enum Input {
+    Expr,
+    Defs(Vec<SessionDef>),
+    /// The definitions, and the source code of the expression after them
+    DefsAndExpr(Vec<SessionDef>, String),
+    Import(String),
+}
+
+/// The definitions and imports entered so far. Each input's expression becomes `replOutput` of a
+/// fresh app module, whose other top-level definitions are the input's own and the session's
+/// definitions it refers to, directly or through other definitions. Only those get type checked
+/// again, and imported modules which didn't change come out of the solved cache, if there is one.
+///
+/// Since they are top-level definitions, they can be anything a module can define, abilities
+/// included, and they can refer to each other regardless of the order they were entered in.
+#[derive(Debug)]
+pub struct ReplSession {
+    defs: Vec<SessionDef>,
+    /// Entries of the app module's `imports` list
+    imports: Vec<String>,
+    /// Where imported modules live
+    src_dir: PathBuf,
+    /// The modules `:load` imported everything from
+    loaded: Vec<PathBuf>,
+    solved_cache: Option<SolvedCache>,
+    report_timings: bool,
+}
+
+impl ReplSession {
+    pub fn new(src_dir: PathBuf) -> Self {
+        Self {
+            defs: Vec::new(),
+            imports: Vec::new(),
+            src_dir,
+            loaded: Vec::new(),
+            solved_cache: None,
+            report_timings: false,
+        }
+    }
+
+    pub fn with_solved_cache(mut self, solved_cache: SolvedCache) -> Self {
+        self.solved_cache = Some(solved_cache);
+
+        self
+    }
+
+    /// Compiles an input, which can be an expression, one or more definitions (of values, or of
+    /// type aliases), definitions followed by an expression, or `import Module` (or
+    /// `import Module.{ name }`). Definitions and imports become part of the session, unless the
+    /// input has problems.
+    ///
+    /// Returns the module to evaluate, or `None` if there is no expression to evaluate.
+    pub fn compile<'a>(
+        &mut self,
+        arena: &'a Bump,
+        src: &str,
+        target_info: TargetInfo,
+        palette: Palette,
+    ) -> Result<Option<MonomorphizedModule<'a>>, Vec<String>> {
+        let src = src.trim();
+
+        match parse_input(arena, src) {
+            Input::Expr => self
+                .compile_body(arena, src, &[], target_info, palette)
+                .map(Some),
+            Input::DefsAndExpr(new_defs, expr) => {
+                let mono = self.compile_body(arena, &expr, &new_defs, target_info, palette)?;
+
+                self.add_defs(new_defs);
+
+                Ok(Some(mono))
+            }
+            Input::Defs(new_defs) => {
+                self.compile_body(arena, "{}", &new_defs, target_info, palette)?;
+                self.add_defs(new_defs);
+
+                Ok(None)
+            }
+            Input::Import(entry) => {
+                self.imports.push(entry);
+
+                if let Err(problems) = self.compile_body(arena, "{}", &[], target_info, palette) {
+                    self.imports.pop();
+
+                    return Err(problems);
+                }
+
+                Ok(None)
+            }
+        }
+    }
+
+    /// The type of an expression, which may use the session's definitions. Nothing gets
+    /// evaluated, and the session stays the way it was.
+    pub fn type_of(
+        &self,
+        arena: &Bump,
+        src: &str,
+        target_info: TargetInfo,
+        palette: Palette,
+    ) -> Result<String, Vec<String>> {
+        let mut mono = self.compile_expr(arena, src, target_info, palette)?;
+        let main_fn_var = *mono.exposed_to_host.values.values().next().unwrap();
+
+        Ok(name_and_print_var(
+            main_fn_var,
+            &mut mono.subs,
+            mono.module_id,
+            &mono.interns,
+            DebugPrint::NOTHING,
+        ))
+    }
+
+    /// The procedures an expression specializes to, the way `ROC_PRINT_IR_AFTER_REFCOUNT` prints
+    /// them, starting with the one for the expression itself.
+    pub fn ir(
+        &self,
+        arena: &Bump,
+        src: &str,
+        target_info: TargetInfo,
+        palette: Palette,
+    ) -> Result<String, Vec<String>> {
+        let mono = self.compile_expr(arena, src, target_info, palette)?;
+        let main_fn_symbol = mono.exposed_to_host.values.keys().next().copied();
+
+        let mut procs: Vec<(bool, String)> = mono
+            .procedures
+            .iter()
+            .map(|((symbol, _), proc)| (Some(*symbol) != main_fn_symbol, proc.to_pretty(200)))
+            .collect();
+
+        procs.sort();
+
+        let procs: Vec<String> = procs.into_iter().map(|(_, proc)| proc).collect();
+
+        Ok(procs.join("\n").trim_end().to_string())
+    }
+
+    /// Imports everything the interface module at `path` exposes. Imported modules come from its
+    /// directory from then on, so every module the session imports has to be in the same one.
+    ///
+    /// Returns the import this added.
+    pub fn load(
+        &mut self,
+        arena: &Bump,
+        path: &Path,
+        target_info: TargetInfo,
+        palette: Palette,
+    ) -> Result<String, Vec<String>> {
+        let path = std::fs::canonicalize(path)
+            .map_err(|err| vec![format!("I couldn't find {}: {}", path.display(), err)])?;
+        let (module_name, entry) = read_exposed(arena, &path)?;
+
+        let mut src_dir = path.clone();
+        src_dir.pop();
+
+        // `Foo.Bar` lives in `Foo/Bar.roc`
+        for _ in 1..module_name.split('.').count() {
+            src_dir.pop();
+        }
+
+        if src_dir != self.src_dir
+            && self
+                .imports
+                .iter()
+                .any(|existing| !imports_module(existing, &module_name))
+        {
+            return Err(vec![format!(
+                "I can only import modules from {}, since the session already imports modules from there.",
+                self.src_dir.display()
+            )]);
+        }
+
+        let previous_imports = self.imports.clone();
+        let previous_src_dir = std::mem::replace(&mut self.src_dir, src_dir);
+
+        self.imports
+            .retain(|existing| !imports_module(existing, &module_name));
+        self.imports.push(entry.clone());
+
+        if let Err(problems) = self.compile_body(arena, "{}", &[], target_info, palette) {
+            self.imports = previous_imports;
+            self.src_dir = previous_src_dir;
+
+            return Err(problems);
+        }
+
+        if !self.loaded.contains(&path) {
+            self.loaded.push(path);
+        }
+
+        Ok(format!("import {}", entry))
+    }
+
+    /// Reads the modules `:load` loaded again, to import what they expose now.
+    ///
+    /// Returns the imports this updated.
+    pub fn reload(
+        &mut self,
+        arena: &Bump,
+        target_info: TargetInfo,
+        palette: Palette,
+    ) -> Result<String, Vec<String>> {
+        if self.loaded.is_empty() {
+            return Ok("No modules have been loaded.".to_string());
+        }
+
+        let exposed = self
+            .loaded
+            .iter()
+            .map(|path| read_exposed(arena, path))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let previous_imports = self.imports.clone();
+        let mut output = Vec::with_capacity(exposed.len());
+
+        for (module_name, entry) in exposed {
+            self.imports
+                .retain(|existing| !imports_module(existing, &module_name));
+            output.push(format!("import {}", entry));
+            self.imports.push(entry);
+        }
+
+        if let Err(problems) = self.compile_body(arena, "{}", &[], target_info, palette) {
+            self.imports = previous_imports;
+
+            return Err(problems);
+        }
+
+        Ok(output.join("\n"))
+    }
+
+    /// The session's imports and definitions, the way they were entered
+    pub fn defs(&self) -> String {
+        let imports = self.imports.iter().map(|entry| format!("import {}", entry));
+        let defs = self.defs.iter().map(|def| def.src.clone());
+
+        imports.chain(defs).collect::<Vec<_>>().join("\n")
+    }
+
+    /// Whether the frontend should report how long each input took to compile and evaluate
+    pub fn reports_timings(&self) -> bool {
+        self.report_timings
+    }
+
+    /// Turns reporting timings on or off, returning whether it's on now
+    pub fn toggle_timings(&mut self) -> bool {
+        self.report_timings = !self.report_timings;
+
+        self.report_timings
+    }
+
+    /// Compiles an expression (which may start with definitions) without changing the session
+    fn compile_expr<'a>(
+        &self,
+        arena: &'a Bump,
+        src: &str,
+        target_info: TargetInfo,
+        palette: Palette,
+    ) -> Result<MonomorphizedModule<'a>, Vec<String>> {
+        let src = src.trim();
+
+        match parse_input(arena, src) {
+            Input::Expr => self.compile_body(arena, src, &[], target_info, palette),
+            Input::DefsAndExpr(new_defs, expr) => {
+                self.compile_body(arena, &expr, &new_defs, target_info, palette)
+            }
+            Input::Defs(_) | Input::Import(_) => Err(vec![format!(
+                "I was expecting an expression, but `{}` doesn't have one.",
+                src
+            )]),
+        }
+    }
+
+    /// Compiles an app module whose `replOutput` is `body`, with `new_defs` and the session's
+    /// definitions they or `body` refer to as its other top-level definitions.
+    fn compile_body<'a>(
+        &self,
+        arena: &'a Bump,
+        body: &str,
+        new_defs: &[SessionDef],
+        target_info: TargetInfo,
+        palette: Palette,
+    ) -> Result<MonomorphizedModule<'a>, Vec<String>> {
+        let mut module_src = String::from("app \"app\" ");
+
+        if !self.imports.is_empty() {
+            module_src.push_str("imports [");
+            module_src.push_str(&self.imports.join(", "));
+            module_src.push_str("] ");
+        }
+
+        module_src.push_str("provides [replOutput] to \"./platform\"\n\n");
+
+        let referenced = self.referenced_defs(body, new_defs);
+
+        for def in referenced.iter().copied().chain(new_defs.iter()) {
+            module_src.push_str(&def.src);
+            module_src.push_str("\n\n");
+        }
+
+        module_src.push_str("replOutput =\n");
+        push_indented(&mut module_src, body);
+
+        // Definitions which the input doesn't use are fine, since later inputs may use them.
+        let session_names: MutSet<&str> = referenced
+            .iter()
+            .chain(new_defs.iter())
+            .flat_map(|def| def.names.iter().map(String::as_str))
+            .collect();
+
+        let is_ignored = |problem: &Problem, interns: &Interns| match problem {
+            Problem::UnusedDef(symbol, _) => session_names.contains(symbol.as_str(interns)),
+            Problem::UnusedImport(..) => true,
+            _ => false,
+        };
+
+        compile_to_mono(
+            arena,
+            arena.alloc(module_src),
+            self.src_dir.clone(),
+            self.solved_cache.clone(),
+            target_info,
+            palette,
+            is_ignored,
+        )
+    }
+
+    /// The session's definitions which `body` or `new_defs` refer to, directly or through other
+    /// definitions, leaving out the ones `new_defs` replaces.
+    fn referenced_defs(&self, body: &str, new_defs: &[SessionDef]) -> Vec<&SessionDef> {
+        let replaced: MutSet<&str> = new_defs
+            .iter()
+            .flat_map(|def| def.names.iter().map(String::as_str))
+            .collect();
+
+        let mut candidates: Vec<usize> = (0..self.defs.len())
+            .filter(|index| {
+                let names = &self.defs[*index].names;
+
+                !names.iter().any(|name| replaced.contains(name.as_str()))
+            })
+            .collect();
+
+        // Taking every identifier-like word for a reference may bring in a definition we don't
+        // need, but never misses one we do.
+        let mut words: MutSet<&str> = identifier_words(body)
+            .chain(new_defs.iter().flat_map(|def| identifier_words(&def.src)))
+            .collect();
+        let mut referenced = Vec::new();
+
+        loop {
+            let (found, rest): (Vec<usize>, Vec<usize>) =
+                candidates.into_iter().partition(|index| {
+                    let names = &self.defs[*index].names;
+
+                    names.iter().any(|name| words.contains(name.as_str()))
+                });
+
+            if found.is_empty() {
+                break;
+            }
+
+            for index in found {
+                words.extend(identifier_words(&self.defs[index].src));
+                referenced.push(index);
+            }
+
+            candidates = rest;
+        }
+
+        // Keep them in the order they were entered.
+        referenced.sort_unstable();
+
+        referenced
+            .into_iter()
+            .map(|index| &self.defs[index])
+            .collect()
+    }
+
+    fn add_defs(&mut self, new_defs: Vec<SessionDef>) {
+        for new_def in new_defs {
+            self.defs
+                .retain(|def| !def.names.iter().any(|name| new_def.names.contains(name)));
+
+            self.defs.push(new_def);
+        }
+    }
+}
+
+/// The name of the interface module at `path`, along with an `imports` entry which exposes
+/// everything it exposes
+fn read_exposed(arena: &Bump, path: &Path) -> Result<(String, String), Vec<String>> {
+    let src = std::fs::read_to_string(path)
+        .map_err(|err| vec![format!("I couldn't read {}: {}", path.display(), err)])?;
+
+    match parse_header(arena, State::new(src.as_bytes())) {
+        Ok((Module::Interface { header }, _)) => {
+            let module_name: &str = header.name.value.into();
+            let module_name = module_name.to_string();
+            let exposed: Vec<&str> = header
+                .exposes
+                .iter()
+                .map(|loc_name| exposed_name(&loc_name.value))
+                .collect();
+
+            let entry = if exposed.is_empty() {
+                module_name.clone()
+            } else {
+                format!("{}.{{ {} }}", module_name, exposed.join(", "))
+            };
+
+            Ok((module_name, entry))
+        }
+        Ok(_) => Err(vec![format!(
+            "{} isn't an interface module, so there's nothing to load from it.",
+            path.display()
+        )]),
+        Err(_) => Err(vec![format!(
+            "I couldn't parse the header of {}. Run `roc check` on it to see why.",
+            path.display()
+        )]),
+    }
+}
+
+fn exposed_name<'a>(spaced: &Spaced<'a, ExposedName<'a>>) -> &'a str {
+    match spaced {
+        Spaced::Item(name) => (*name).into(),
+        Spaced::SpaceBefore(spaced, _) | Spaced::SpaceAfter(spaced, _) => exposed_name(spaced),
+    }
+}
+
+/// Whether an `imports` entry imports the module `module_name`
+fn imports_module(entry: &str, module_name: &str) -> bool {
+    match entry.strip_prefix(module_name) {
+        Some(rest) => rest.is_empty() || rest.starts_with(".{") || rest.starts_with(' '),
+        None => false,
+    }
+}
+
+fn push_indented(buffer: &mut String, src: &str) {
+    for line in src.lines() {
+        // indent the body!
+        buffer.push_str("    ");
+        buffer.push_str(line);
+        buffer.push('\n');
+    }
+}
+
+fn identifier_words(src: &str) -> impl Iterator<Item = &str> {
+    src.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|word| !word.is_empty())
+}
+
+fn parse_input(arena: &Bump, src: &str) -> Input {
+    if let Some(entry) = src.strip_prefix("import ") {
+        return Input::Import(entry.trim().to_string());
+    }
+
+    let state = State::new(src.as_bytes());
+
+    if let Ok((_, defs, _)) = module_defs().parse(arena, state.clone()) {
+        if !defs.is_empty() {
+            return Input::Defs(session_defs(src, &defs));
+        }
+    }
+
+    match roc_parse::expr::parse_loc_expr(0, arena, state) {
+        Ok((_, loc_expr, _)) => match loc_expr.value {
+            Expr::Defs(defs, loc_ret) => {
+                let start = loc_ret.region.start().offset as usize;
+                let end = loc_ret.region.end().offset as usize;
+
+                Input::DefsAndExpr(session_defs(src, defs), src[start..end].to_string())
+            }
+            _ => Input::Expr,
+        },
+        Err(_) => Input::Expr,
+    }
+}
+
+fn session_defs(src: &str, defs: &Defs) -> Vec<SessionDef> {
+    defs.defs()
+        .zip(defs.regions.iter())
+        .map(|(def, region)| {
+            let mut names = Vec::new();
+
+            match def {
+                Ok(TypeDef::Alias {
+                    header: TypeHeader { name, .. },
+                    ..
+                })
+                | Ok(TypeDef::Opaque {
+                    header: TypeHeader { name, .. },
+                    ..
+                }) => {
+                    names.push(name.value.to_string());
+                }
+                Ok(TypeDef::Ability {
+                    header: TypeHeader { name, .. },
+                    members,
+                    ..
+                }) => {
+                    // Using a member is what brings in the ability.
+                    names.push(name.value.to_string());
+                    names.extend(
+                        members
+                            .iter()
+                            .map(|member| member.name.value.extract_spaces().item.to_string()),
+                    );
+                }
+                Err(ValueDef::Annotation(loc_pattern, _)) => {
+                    pattern_names(&loc_pattern.value, &mut names);
+                }
+                Err(ValueDef::Body(loc_pattern, _))
+                | Err(ValueDef::AnnotatedBody {
+                    body_pattern: loc_pattern,
+                    ..
+                }) => {
+                    pattern_names(&loc_pattern.value, &mut names);
+                }
+                Err(ValueDef::Expect { .. }) | Err(ValueDef::ExpectFx { .. }) => {}
+            }
+
+            let start = region.start().offset as usize;
+            let end = region.end().offset as usize;
+
+            SessionDef {
+                names,
+                src: src[start..end].to_string(),
+            }
+        })
+        .collect()
+}
+
+fn pattern_names(pattern: &Pattern, names: &mut Vec<String>) {
+    match pattern {
+        Pattern::Identifier(name) => names.push(name.to_string()),
+        Pattern::Apply(_, args) => {
+            for arg in args.iter() {
+                pattern_names(&arg.value, names);
+            }
+        }
+        Pattern::RecordDestructure(fields) | Pattern::Tuple(fields) => {
+            for field in fields.iter() {
+                pattern_names(&field.value, names);
+            }
+        }
+        Pattern::RequiredField(_, loc_pattern) => pattern_names(&loc_pattern.value, names),
+        Pattern::OptionalField(name, _) => names.push(name.to_string()),
+        Pattern::SpaceBefore(pattern, _) | Pattern::SpaceAfter(pattern, _) => {
+            pattern_names(pattern, names)
+        }
+        _ => {}
+    }
+}
@@ -79,19 +79,38 @@ pub fn get_values<'a>(
 mod test {
     use indoc::indoc;
     use pretty_assertions::assert_eq;
+    use roc_collections::VecMap;
     use roc_gen_llvm::{llvm::build::LlvmBackendMode, run_roc::RocCallResult, run_roc_dylib};
-    use roc_load::{ExecutionMode, LoadConfig, Threading};
+    use roc_load::{ExecutionMode, Expectations, LoadConfig, PropertyTesting, Threading};
+    use roc_module::symbol::ModuleId;
     use roc_reporting::report::RenderTarget;
     use target_lexicon::Triple;
 
-    use crate::run::expect_mono_module_to_dylib;
+    use crate::run::{
+        expect_mono_module_to_dylib, expect_mono_module_to_dylib_dev, ToplevelExpect,
+    };
 
     use super::*;
 
     fn run_expect_test(source: &str, expected: &str) {
-        let arena = bumpalo::Bump::new();
-        let arena = &arena;
+        run_expect_test_help(source, expected, false)
+    }
+
+    fn run_expect_test_dev(source: &str, expected: &str) {
+        run_expect_test_help(source, expected, true)
+    }
 
+    /// Compiles the expects in `source` into a dylib, using the dev backend or LLVM.
+    fn compile_expects<'a>(
+        arena: &'a bumpalo::Bump,
+        source: &str,
+        dev_backend: bool,
+    ) -> (
+        libloading::Library,
+        bumpalo::collections::Vec<'a, ToplevelExpect<'a>>,
+        VecMap<ModuleId, Expectations>,
+        Interns,
+    ) {
         let triple = Triple::host();
         let target = &triple;
 
@@ -109,6 +128,8 @@ mod test {
             render: RenderTarget::ColorTerminal,
             threading: Threading::Single,
             exec_mode: ExecutionMode::Test,
+            property_testing: PropertyTesting::default(),
+            opt_level,
         };
         let loaded = roc_load::load_and_monomorphize_from_str(
             arena,
@@ -121,20 +142,33 @@ mod test {
         .unwrap();
 
         let mut loaded = loaded;
-        let mut expectations = std::mem::take(&mut loaded.expectations);
+        let expectations = std::mem::take(&mut loaded.expectations);
         let loaded = loaded;
 
         let interns = loaded.interns.clone();
 
-        let (lib, expects) = expect_mono_module_to_dylib(
-            arena,
-            target.clone(),
-            loaded,
-            opt_level,
-            LlvmBackendMode::CliTest,
-        )
+        let (lib, expects) = if dev_backend {
+            expect_mono_module_to_dylib_dev(arena, target.clone(), loaded)
+        } else {
+            expect_mono_module_to_dylib(
+                arena,
+                target.clone(),
+                loaded,
+                opt_level,
+                LlvmBackendMode::CliTest,
+            )
+        }
         .unwrap();
 
+        (lib, expects, expectations, interns)
+    }
+
+    fn run_expect_test_help(source: &str, expected: &str, dev_backend: bool) {
+        let arena = bumpalo::Bump::new();
+        let arena = &arena;
+
+        let (lib, expects, mut expectations, interns) = compile_expects(arena, source, dev_backend);
+
         let arena = &bumpalo::Bump::new();
         let interns = arena.alloc(interns);
 
@@ -148,7 +182,7 @@ mod test {
         unsafe { set_shared_buffer((shared_buffer.as_mut_ptr(), BUFFER_SIZE), &mut result) };
 
         let mut writer = Vec::with_capacity(1024);
-        let (_failed, _passed) = crate::run::run_expects(
+        let _outcomes = crate::run::run_expects(
             &mut writer,
             RenderTarget::ColorTerminal,
             arena,
@@ -156,6 +190,7 @@ mod test {
             &lib,
             &mut expectations,
             shared_buffer.as_mut_ptr(),
+            BUFFER_SIZE,
             expects,
         )
         .unwrap();
@@ -184,6 +219,254 @@ mod test {
         }
     }
 
+    /// Runs the expects in `source` in forked workers and returns their outcomes, in order.
+    #[cfg(unix)]
+    fn run_forked_expects(
+        source: &str,
+        max_workers: usize,
+        timeout: std::time::Duration,
+    ) -> Vec<crate::run::ExpectOutcome> {
+        let arena = bumpalo::Bump::new();
+        let arena = &arena;
+
+        let (lib, expects, mut expectations, interns) = compile_expects(arena, source, false);
+
+        let arena = &bumpalo::Bump::new();
+        let interns = arena.alloc(interns);
+
+        let config = crate::run::WorkerConfig {
+            max_workers,
+            timeout,
+            shared_buffer_size: 1024,
+        };
+
+        let mut writer = Vec::with_capacity(1024);
+        crate::run::run_expects_forked(
+            &mut writer,
+            RenderTarget::ColorTerminal,
+            arena,
+            interns,
+            &lib,
+            &mut expectations,
+            expects,
+            config,
+        )
+        .unwrap()
+    }
+
+    #[cfg(unix)]
+    fn forked_statuses(
+        source: &str,
+        max_workers: usize,
+        timeout: std::time::Duration,
+    ) -> Vec<crate::run::ExpectStatus> {
+        run_forked_expects(source, max_workers, timeout)
+            .iter()
+            .map(|outcome| outcome.status)
+            .collect()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn forked_outcomes_keep_the_expect_order() {
+        use crate::run::ExpectStatus::*;
+
+        let source = indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            main = 0
+
+            expect 1 == 1
+
+            expect 1 == 2
+
+            expect 2 == 2
+
+            expect 3 == 4
+            "#
+        );
+
+        for max_workers in [1, 2, 8] {
+            assert_eq!(
+                forked_statuses(source, max_workers, std::time::Duration::from_secs(60)),
+                vec![Passed, Failed, Passed, Failed]
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn forked_failure_reports_the_expect() {
+        let outcomes = run_forked_expects(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect 1 == 2
+                "#
+            ),
+            1,
+            std::time::Duration::from_secs(60),
+        );
+
+        let failure = outcomes[0].failure.as_deref().unwrap();
+        let failure = String::from_utf8(strip_ansi_escapes::strip(failure).unwrap()).unwrap();
+
+        assert!(failure.contains("expect 1 == 2"), "{}", failure);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn forked_values_too_large_for_the_buffer() {
+        let outcomes = run_forked_expects(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect
+                    big = Str.repeat "abcdefgh" 1000
+
+                    big == ""
+                "#
+            ),
+            1,
+            std::time::Duration::from_secs(60),
+        );
+
+        assert_eq!(outcomes[0].status, crate::run::ExpectStatus::Failed);
+
+        let failure = outcomes[0].failure.as_deref().unwrap();
+        let failure = String::from_utf8(strip_ansi_escapes::strip(failure).unwrap()).unwrap();
+
+        assert!(failure.contains("too large to show"), "{}", failure);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn forked_panic_fails_the_expect() {
+        let outcomes = run_forked_expects(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect Num.maxI64 + 1 > 0
+
+                expect 1 == 1
+                "#
+            ),
+            1,
+            std::time::Duration::from_secs(60),
+        );
+
+        assert_eq!(outcomes[0].status, crate::run::ExpectStatus::Failed);
+        assert!(outcomes[0].failure.is_some());
+        // A panic only takes down its own worker.
+        assert_eq!(outcomes[1].status, crate::run::ExpectStatus::Passed);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn forked_stack_overflow_crashes_the_expect() {
+        use crate::run::ExpectStatus::*;
+
+        let source = indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            main = 0
+
+            grow : U64 -> List U64
+            grow = \n -> List.append (grow (n + 1)) n
+
+            expect List.len (grow 0) == 0
+
+            expect 1 == 1
+            "#
+        );
+
+        assert_eq!(
+            forked_statuses(source, 2, std::time::Duration::from_secs(60)),
+            vec![Crashed, Passed]
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn forked_infinite_loop_times_out() {
+        use crate::run::ExpectStatus::*;
+
+        let source = indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            main = 0
+
+            spin : U64 -> U64
+            spin = \n -> if n == 0 then spin 0 else n
+
+            expect spin 0 == 1
+
+            expect 1 == 1
+            "#
+        );
+
+        let outcomes = run_forked_expects(source, 2, std::time::Duration::from_millis(200));
+
+        assert_eq!(
+            outcomes
+                .iter()
+                .map(|outcome| outcome.status)
+                .collect::<Vec<_>>(),
+            vec![TimedOut, Passed]
+        );
+        assert!(outcomes[0].failure.is_some());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn property_expects() {
+        use crate::run::ExpectStatus::*;
+
+        let source = indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            main = 0
+
+            expect \s -> Str.countUtf8Bytes s <= 100
+
+            expect \a, b -> Str.countUtf8Bytes (Str.concat a b) == Str.countUtf8Bytes a + Str.countUtf8Bytes b
+
+            expect \s -> Str.countUtf8Bytes s < 3
+            "#
+        );
+
+        let outcomes = run_forked_expects(source, 1, std::time::Duration::from_secs(60));
+
+        assert_eq!(
+            outcomes
+                .iter()
+                .map(|outcome| outcome.status)
+                .collect::<Vec<_>>(),
+            vec![Passed, Passed, Failed]
+        );
+
+        // The failure shows the generated input, and the seed and size to generate it again.
+        let failure = outcomes[2].failure.as_deref().unwrap();
+        let failure = String::from_utf8(strip_ansi_escapes::strip(failure).unwrap()).unwrap();
+
+        assert!(failure.contains("generated"), "{}", failure);
+        assert!(failure.contains("seed"), "{}", failure);
+        assert!(failure.contains("size"), "{}", failure);
+    }
+
     #[test]
     fn equals_pass() {
         run_expect_test(
@@ -221,6 +504,252 @@ mod test {
         );
     }
 
+    #[test]
+    fn dev_backend_equals_pass() {
+        run_expect_test_dev(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            main = 0
+
+            expect 1 == 1
+            "#,
+            "",
+        );
+    }
+
+    #[test]
+    fn dev_backend_lookup_integer() {
+        run_expect_test_dev(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect
+                    a = 1
+                    b = 2
+
+                    a == b
+                "#
+            ),
+            indoc!(
+                r#"
+                This expectation failed:
+
+                5│>  expect
+                6│>      a = 1
+                7│>      b = 2
+                8│>
+                9│>      a == b
+
+                When it failed, these variables had these values:
+
+                a : Num a
+                a = 1
+
+                b : Num a
+                b = 2
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn dev_backend_lookup_list_of_strings() {
+        run_expect_test_dev(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect
+                    a = ["foo"]
+                    b = ["a string so long that it cannot be short", "bar"]
+
+                    List.len a == List.len b
+                "#
+            ),
+            indoc!(
+                r#"
+                This expectation failed:
+
+                5│>  expect
+                6│>      a = ["foo"]
+                7│>      b = ["a string so long that it cannot be short", "bar"]
+                8│>
+                9│>      List.len a == List.len b
+
+                When it failed, these variables had these values:
+
+                a : List Str
+                a = ["foo"]
+
+                b : List Str
+                b = ["a string so long that it cannot be short", "bar"]
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn dev_backend_lookup_list_of_list_of_strings() {
+        run_expect_test_dev(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect
+                    a = [["foo"], []]
+                    b = [["a string so long that it cannot be short", "bar"]]
+
+                    List.len a == List.len b
+                "#
+            ),
+            indoc!(
+                r#"
+                This expectation failed:
+
+                5│>  expect
+                6│>      a = [["foo"], []]
+                7│>      b = [["a string so long that it cannot be short", "bar"]]
+                8│>
+                9│>      List.len a == List.len b
+
+                When it failed, these variables had these values:
+
+                a : List (List Str)
+                a = [["foo"], []]
+
+                b : List (List Str)
+                b = [["a string so long that it cannot be short", "bar"]]
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn dev_backend_lookup_record_with_string() {
+        run_expect_test_dev(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect
+                    person = { name: "Roc", age: 5 }
+
+                    person.age == 6
+                "#
+            ),
+            indoc!(
+                r#"
+                This expectation failed:
+
+                5│>  expect
+                6│>      person = { name: "Roc", age: 5 }
+                7│>
+                8│>      person.age == 6
+
+                When it failed, these variables had these values:
+
+                person : { age : Num a, name : Str }
+                person = { age: 5, name: "Roc" }
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn dev_backend_lookup_clone_result() {
+        run_expect_test_dev(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                tagNumber : Result Str Str -> I64
+                tagNumber = \result ->
+                    when result is
+                        Ok _ -> 1
+                        Err _ -> 2
+
+                expect
+                    a : Result Str Str
+                    a = Ok "foo"
+
+                    b : Result Str Str
+                    b = Err "a string so long that it cannot be short"
+
+                    tagNumber a == tagNumber b
+                "#
+            ),
+            indoc!(
+                r#"
+                This expectation failed:
+
+                11│>  expect
+                12│>      a : Result Str Str
+                13│>      a = Ok "foo"
+                14│>
+                15│>      b : Result Str Str
+                16│>      b = Err "a string so long that it cannot be short"
+                17│>
+                18│>      tagNumber a == tagNumber b
+
+                When it failed, these variables had these values:
+
+                a : Result Str Str
+                a = Ok "foo"
+
+                b : Result Str Str
+                b = Err "a string so long that it cannot be short"
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn dev_backend_lookup_list_of_results() {
+        run_expect_test_dev(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect
+                    results : List (Result Str Str)
+                    results = [Ok "foo", Err "a string so long that it cannot be short"]
+
+                    List.len results == 3
+                "#
+            ),
+            indoc!(
+                r#"
+                This expectation failed:
+
+                5│>  expect
+                6│>      results : List (Result Str Str)
+                7│>      results = [Ok "foo", Err "a string so long that it cannot be short"]
+                8│>
+                9│>      List.len results == 3
+
+                When it failed, these variables had these values:
+
+                results : List (Result Str Str)
+                results = [Ok "foo", Err "a string so long that it cannot be short"]
+                "#
+            ),
+        );
+    }
+
     #[test]
     fn lookup_integer() {
         run_expect_test(
@@ -1,17 +1,50 @@
 use bumpalo::collections::Vec as BumpVec;
 use bumpalo::Bump;
 use inkwell::context::Context;
-use roc_build::link::llvm_module_to_dylib;
+use roc_build::link::{dev_object_to_dylib, llvm_module_to_dylib, DylibError};
 use roc_collections::{MutSet, VecMap};
 use roc_gen_llvm::llvm::{build::LlvmBackendMode, externs::add_default_roc_externs};
 use roc_load::{EntryPoint, Expectations, MonomorphizedModule};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
-use roc_mono::ir::OptLevel;
+use roc_mono::ir::{CapturesNiche, OptLevel, ProcLayout};
+use roc_mono::layout::{Layout, LayoutIds};
 use roc_region::all::Region;
 use roc_reporting::{error::expect::Renderer, report::RenderTarget};
 use roc_target::TargetInfo;
+use std::io::Write;
+use std::time::{Duration, Instant};
 use target_lexicon::Triple;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpectStatus {
+    Passed,
+    /// The expect's condition was false, or it panicked
+    Failed,
+    /// The process running the expect died, e.g. from a segfault or a stack overflow
+    Crashed,
+    /// The expect was stopped because it ran for too long
+    TimedOut,
+}
+
+/// The outcome of running a single top-level expect
+#[derive(Debug, Clone)]
+pub struct ExpectOutcome {
+    pub symbol: Symbol,
+    pub region: Region,
+    pub duration: Duration,
+    pub status: ExpectStatus,
+    /// The rendered report, if the expect did not pass
+    pub failure: Option<String>,
+}
+
+impl ExpectOutcome {
+    pub fn passed(&self) -> bool {
+        self.status == ExpectStatus::Passed
+    }
+}
+
+/// Runs the expects one after another in this process, writing a report to
+/// `writer` for each one that fails.
 #[allow(clippy::too_many_arguments)]
 pub fn run_expects<W: std::io::Write>(
     writer: &mut W,
@@ -21,85 +54,524 @@ pub fn run_expects<W: std::io::Write>(
     lib: &libloading::Library,
     expectations: &mut VecMap<ModuleId, Expectations>,
     shared_ptr: *mut u8,
+    shared_buffer_size: usize,
     expects: bumpalo::collections::Vec<'_, ToplevelExpect<'_>>,
-) -> std::io::Result<(usize, usize)> {
-    let mut failed = 0;
-    let mut passed = 0;
+) -> std::io::Result<Vec<ExpectOutcome>> {
+    let mut outcomes = Vec::with_capacity(expects.len());
 
     for expect in expects {
-        let result = run_expect(
-            writer,
+        let outcome = run_expect(
             render_target,
             arena,
             interns,
             lib,
             expectations,
             shared_ptr,
+            shared_buffer_size,
             expect,
         )?;
 
-        match result {
-            true => passed += 1,
-            false => failed += 1,
+        if let Some(failure) = &outcome.failure {
+            writer.write_all(failure.as_bytes())?;
         }
+
+        outcomes.push(outcome);
     }
 
-    Ok((failed, passed))
+    Ok(outcomes)
 }
 
 #[allow(clippy::too_many_arguments)]
-fn run_expect<W: std::io::Write>(
-    writer: &mut W,
+fn run_expect(
     render_target: RenderTarget,
     arena: &Bump,
     interns: &Interns,
     lib: &libloading::Library,
     expectations: &mut VecMap<ModuleId, Expectations>,
     shared_ptr: *mut u8,
+    shared_buffer_size: usize,
     expect: ToplevelExpect<'_>,
-) -> std::io::Result<bool> {
-    use roc_gen_llvm::try_run_jit_function;
-
+) -> std::io::Result<ExpectOutcome> {
     let sequence = ExpectSequence::new(shared_ptr.cast());
 
-    let result: Result<(), String> = try_run_jit_function!(lib, expect.name, (), |v: ()| v);
+    let start_time = Instant::now();
+    let result = call_expect(lib, expect);
+    let duration = start_time.elapsed();
 
-    let shared_memory_ptr: *const u8 = shared_ptr.cast();
+    let dropped_frames = sequence.dropped_frames(shared_buffer_size);
 
-    if result.is_err() || sequence.count_failures() > 0 {
-        let module_id = expect.symbol.module_id();
-        let data = expectations.get_mut(&module_id).unwrap();
+    let (status, failure) = if result.is_err() || sequence.count_failures() > 0 || dropped_frames {
+        let report = render_report(
+            render_target,
+            arena,
+            interns,
+            expectations,
+            expect,
+            |renderer, expectations, report| match &result {
+                Err(roc_panic_message) => {
+                    renderer.render_panic(report, roc_panic_message, expect.region)
+                }
+                Ok(()) => render_expect_failures(
+                    report,
+                    renderer,
+                    arena,
+                    expect,
+                    expectations,
+                    interns,
+                    shared_ptr,
+                    sequence.count_failures(),
+                    dropped_frames,
+                ),
+            },
+        )?;
 
-        let path = &data.path;
-        let filename = data.path.to_owned();
-        let source = std::fs::read_to_string(path).unwrap();
+        (ExpectStatus::Failed, Some(report))
+    } else {
+        (ExpectStatus::Passed, None)
+    };
 
-        let renderer = Renderer::new(arena, interns, render_target, module_id, filename, &source);
+    Ok(ExpectOutcome {
+        symbol: expect.symbol,
+        region: expect.region,
+        duration,
+        status,
+        failure,
+    })
+}
 
-        if let Err(roc_panic_message) = result {
-            renderer.render_panic(writer, &roc_panic_message, expect.region)?;
-        } else {
-            let mut offset = ExpectSequence::START_OFFSET;
+/// Renders the report for an expect which did not pass into a String,
+/// so the caller can decide where it goes.
+fn render_report<F>(
+    render_target: RenderTarget,
+    arena: &Bump,
+    interns: &Interns,
+    expectations: &mut VecMap<ModuleId, Expectations>,
+    expect: ToplevelExpect<'_>,
+    render: F,
+) -> std::io::Result<String>
+where
+    F: FnOnce(&Renderer, &mut VecMap<ModuleId, Expectations>, &mut Vec<u8>) -> std::io::Result<()>,
+{
+    let module_id = expect.symbol.module_id();
+    let data = expectations.get_mut(&module_id).unwrap();
 
-            for _ in 0..sequence.count_failures() {
-                offset += render_expect_failure(
-                    writer,
-                    &renderer,
-                    arena,
-                    Some(expect),
-                    expectations,
-                    interns,
-                    shared_memory_ptr,
-                    offset,
-                )?;
+    let path = &data.path;
+    let filename = data.path.to_owned();
+    let source = std::fs::read_to_string(path).unwrap();
+
+    let renderer = Renderer::new(arena, interns, render_target, module_id, filename, &source);
+
+    let mut report = Vec::new();
+
+    render(&renderer, expectations, &mut report)?;
+
+    writeln!(report)?;
+
+    Ok(String::from_utf8_lossy(&report).into_owned())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_expect_failures(
+    writer: &mut impl std::io::Write,
+    renderer: &Renderer,
+    arena: &Bump,
+    expect: ToplevelExpect<'_>,
+    expectations: &mut VecMap<ModuleId, Expectations>,
+    interns: &Interns,
+    shared_ptr: *const u8,
+    failure_count: usize,
+    dropped_frames: bool,
+) -> std::io::Result<()> {
+    let mut offset = ExpectSequence::START_OFFSET;
+
+    for _ in 0..failure_count {
+        offset += render_expect_failure(
+            writer,
+            renderer,
+            arena,
+            Some(expect),
+            expectations,
+            interns,
+            shared_ptr,
+            offset,
+        )?;
+    }
+
+    if dropped_frames && failure_count == 0 {
+        writeln!(
+            writer,
+            "This expect failed, but its values were too large to show."
+        )?;
+    } else if dropped_frames {
+        writeln!(
+            writer,
+            "Other failures of this expect had values too large to show."
+        )?;
+    }
+
+    Ok(())
+}
+
+/// How to run expects in forked worker processes
+#[derive(Debug, Clone, Copy)]
+pub struct WorkerConfig {
+    /// How many expects may run at the same time
+    pub max_workers: usize,
+    /// How long an expect may run before it gets stopped and reported as timed out
+    pub timeout: Duration,
+    /// The size of the buffer each worker shares with this process, for reporting expect failures
+    pub shared_buffer_size: usize,
+}
+
+/// How far a worker got with its expect. The worker writes this to the header in front of
+/// its shared buffer. Exit codes can't tell us this: the expect's platform may call `exit`
+/// itself, and a Rust panic in the worker exits with 101 like any other Rust program.
+#[cfg(unix)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WorkerState {
+    Running = 0,
+    /// The expect returned. Its failures, if any, are in the shared buffer.
+    Finished = 1,
+    /// The expect panicked. The panic message is in the shared buffer.
+    Panicked = 2,
+}
+
+#[cfg(unix)]
+impl WorkerState {
+    fn read(memory: *const u8) -> Self {
+        match unsafe { *(memory as *const usize) } {
+            1 => WorkerState::Finished,
+            2 => WorkerState::Panicked,
+            _ => WorkerState::Running,
+        }
+    }
+
+    fn write(self, memory: *mut u8) {
+        unsafe { *(memory as *mut usize) = self as usize };
+    }
+}
+
+/// The worker's state and the length of its panic message, in front of its shared buffer.
+#[cfg(unix)]
+const WORKER_HEADER_SIZE: usize = 16;
+
+#[cfg(unix)]
+struct Worker<'a> {
+    pid: libc::pid_t,
+    /// The expect's position in the list of expects to run
+    index: usize,
+    expect: ToplevelExpect<'a>,
+    /// The shared memory: a header, followed by the buffer the expect writes its failures to
+    memory: *mut u8,
+    start_time: Instant,
+}
+
+#[cfg(unix)]
+#[derive(Debug, Clone, Copy)]
+enum WorkerExit {
+    Exited(i32),
+    Signaled(i32),
+    TimedOut,
+}
+
+/// Decides what happened to an expect from how its worker stopped and what it wrote before that.
+#[cfg(unix)]
+fn classify_worker(exit: WorkerExit, state: WorkerState, failures: usize) -> ExpectStatus {
+    match (exit, state) {
+        (WorkerExit::TimedOut, _) => ExpectStatus::TimedOut,
+        (WorkerExit::Exited(0), WorkerState::Finished) if failures == 0 => ExpectStatus::Passed,
+        (WorkerExit::Exited(0), WorkerState::Finished | WorkerState::Panicked) => {
+            ExpectStatus::Failed
+        }
+        // Anything else stopped the worker before it could report back, even a clean exit.
+        (WorkerExit::Exited(_) | WorkerExit::Signaled(_), _) => ExpectStatus::Crashed,
+    }
+}
+
+/// Runs each expect in its own forked process, so that a crash or an infinite loop
+/// only affects that one expect. Up to `config.max_workers` expects run at once.
+/// Reports are written to `writer` as the expects finish, but the outcomes are
+/// returned in the same order as the expects.
+#[cfg(unix)]
+#[allow(clippy::too_many_arguments)]
+pub fn run_expects_forked<W: std::io::Write>(
+    writer: &mut W,
+    render_target: RenderTarget,
+    arena: &Bump,
+    interns: &Interns,
+    lib: &libloading::Library,
+    expectations: &mut VecMap<ModuleId, Expectations>,
+    expects: bumpalo::collections::Vec<'_, ToplevelExpect<'_>>,
+    config: WorkerConfig,
+) -> std::io::Result<Vec<ExpectOutcome>> {
+    let mut outcomes: Vec<Option<ExpectOutcome>> = vec![None; expects.len()];
+    let mut pending = expects.into_iter().enumerate();
+    let mut workers: Vec<Worker> = Vec::with_capacity(config.max_workers);
+
+    loop {
+        while workers.len() < config.max_workers.max(1) {
+            match pending.next() {
+                Some((index, expect)) => {
+                    workers.push(spawn_worker(lib, index, expect, config)?);
+                }
+                None => break,
+            }
+        }
+
+        if workers.is_empty() {
+            break;
+        }
+
+        let mut any_finished = false;
+        let mut worker_index = 0;
+
+        while worker_index < workers.len() {
+            match poll_worker(&workers[worker_index], config.timeout) {
+                Some(exit) => {
+                    let worker = workers.swap_remove(worker_index);
+                    let outcome = finish_worker(
+                        &worker,
+                        exit,
+                        render_target,
+                        arena,
+                        interns,
+                        expectations,
+                        config,
+                    )?;
+
+                    if let Some(failure) = &outcome.failure {
+                        writer.write_all(failure.as_bytes())?;
+                    }
+
+                    outcomes[worker.index] = Some(outcome);
+                    any_finished = true;
+                }
+                None => {
+                    worker_index += 1;
+                }
             }
         }
 
-        writeln!(writer)?;
+        if !any_finished {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    Ok(outcomes.into_iter().flatten().collect())
+}
+
+#[cfg(unix)]
+fn spawn_worker<'a>(
+    lib: &libloading::Library,
+    index: usize,
+    expect: ToplevelExpect<'a>,
+    config: WorkerConfig,
+) -> std::io::Result<Worker<'a>> {
+    // Anonymous shared memory stays shared between this process and the forked worker.
+    let memory = unsafe {
+        libc::mmap(
+            std::ptr::null_mut(),
+            WORKER_HEADER_SIZE + config.shared_buffer_size,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_SHARED | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+
+    if memory == libc::MAP_FAILED {
+        return Err(std::io::Error::last_os_error());
+    }
 
-        Ok(false)
+    let memory: *mut u8 = memory.cast();
+
+    WorkerState::Running.write(memory);
+    ExpectSequence::new(unsafe { memory.add(WORKER_HEADER_SIZE) });
+
+    let start_time = Instant::now();
+
+    match unsafe { libc::fork() } {
+        0 => run_worker(lib, expect, memory, config.shared_buffer_size),
+        -1 => Err(std::io::Error::last_os_error()),
+        pid => Ok(Worker {
+            pid,
+            index,
+            expect,
+            memory,
+            start_time,
+        }),
+    }
+}
+
+/// This runs in the forked worker process, and never returns.
+#[cfg(unix)]
+fn run_worker(
+    lib: &libloading::Library,
+    expect: ToplevelExpect<'_>,
+    memory: *mut u8,
+    shared_buffer_size: usize,
+) -> ! {
+    use roc_gen_llvm::run_roc::RocCallResult;
+    use roc_gen_llvm::run_roc_dylib;
+
+    let shared_ptr = unsafe { memory.add(WORKER_HEADER_SIZE) };
+    let set_shared_buffer = run_roc_dylib!(lib, "set_shared_buffer", (*mut u8, usize), ());
+    let mut result = RocCallResult::default();
+
+    unsafe { set_shared_buffer((shared_ptr, shared_buffer_size), &mut result) };
+
+    let result = call_expect(lib, expect);
+
+    match result {
+        Ok(()) => WorkerState::Finished.write(memory),
+        Err(roc_panic_message) => {
+            // The failure frames don't matter if the expect panicked, so
+            // reuse the shared buffer to send the message back instead.
+            let bytes = roc_panic_message.as_bytes();
+            let len = bytes.len().min(shared_buffer_size);
+
+            unsafe {
+                *(memory as *mut usize).add(1) = len;
+                std::ptr::copy_nonoverlapping(bytes.as_ptr(), shared_ptr, len);
+            }
+
+            WorkerState::Panicked.write(memory);
+        }
+    }
+
+    // Skip destructors and atexit handlers; those belong to the parent process.
+    unsafe { libc::_exit(0) }
+}
+
+#[cfg(unix)]
+fn poll_worker(worker: &Worker, timeout: Duration) -> Option<WorkerExit> {
+    let mut status = 0;
+
+    match unsafe { libc::waitpid(worker.pid, &mut status, libc::WNOHANG) } {
+        0 => {
+            if worker.start_time.elapsed() > timeout {
+                unsafe {
+                    libc::kill(worker.pid, libc::SIGKILL);
+                    libc::waitpid(worker.pid, &mut status, 0);
+                }
+
+                Some(WorkerExit::TimedOut)
+            } else {
+                None
+            }
+        }
+        -1 => Some(WorkerExit::Exited(-1)),
+        _ => {
+            if libc::WIFSIGNALED(status) {
+                Some(WorkerExit::Signaled(libc::WTERMSIG(status)))
+            } else {
+                Some(WorkerExit::Exited(libc::WEXITSTATUS(status)))
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn finish_worker(
+    worker: &Worker,
+    exit: WorkerExit,
+    render_target: RenderTarget,
+    arena: &Bump,
+    interns: &Interns,
+    expectations: &mut VecMap<ModuleId, Expectations>,
+    config: WorkerConfig,
+) -> std::io::Result<ExpectOutcome> {
+    let duration = worker.start_time.elapsed();
+    let expect = worker.expect;
+    let state = WorkerState::read(worker.memory);
+    let shared_ptr: *const u8 = unsafe { worker.memory.add(WORKER_HEADER_SIZE) };
+    let sequence = ExpectSequence { ptr: shared_ptr };
+    let dropped_frames = sequence.dropped_frames(config.shared_buffer_size);
+
+    let status = classify_worker(
+        exit,
+        state,
+        sequence.count_failures() + dropped_frames as usize,
+    );
+
+    let failure = if status == ExpectStatus::Passed {
+        None
     } else {
-        Ok(true)
+        let report = render_report(
+            render_target,
+            arena,
+            interns,
+            expectations,
+            expect,
+            |renderer, expectations, report| match (exit, state) {
+                (WorkerExit::TimedOut, _) => {
+                    renderer.render_timeout(report, config.timeout, expect.region)
+                }
+                (WorkerExit::Exited(0), WorkerState::Finished) => render_expect_failures(
+                    report,
+                    renderer,
+                    arena,
+                    expect,
+                    expectations,
+                    interns,
+                    shared_ptr,
+                    sequence.count_failures(),
+                    dropped_frames,
+                ),
+                (WorkerExit::Exited(0), WorkerState::Panicked) => {
+                    let message = unsafe {
+                        let len = *(worker.memory as *const usize).add(1);
+                        let bytes = std::slice::from_raw_parts(shared_ptr, len);
+
+                        String::from_utf8_lossy(bytes).into_owned()
+                    };
+
+                    renderer.render_panic(report, &message, expect.region)
+                }
+                (WorkerExit::Exited(code), _) => renderer.render_crash(
+                    report,
+                    &format!("It exited with code {code} before the expect finished."),
+                    expect.region,
+                ),
+                (WorkerExit::Signaled(signal), _) => renderer.render_crash(
+                    report,
+                    &format!("It was killed by {}.", signal_name(signal)),
+                    expect.region,
+                ),
+            },
+        )?;
+
+        Some(report)
+    };
+
+    unsafe {
+        libc::munmap(
+            worker.memory.cast(),
+            WORKER_HEADER_SIZE + config.shared_buffer_size,
+        )
+    };
+
+    Ok(ExpectOutcome {
+        symbol: expect.symbol,
+        region: expect.region,
+        duration,
+        status,
+        failure,
+    })
+}
+
+#[cfg(unix)]
+fn signal_name(signal: i32) -> String {
+    match signal {
+        libc::SIGSEGV => {
+            "SIGSEGV (a segmentation fault, which can be caused by a stack overflow)".to_string()
+        }
+        libc::SIGBUS => "SIGBUS (a bus error)".to_string(),
+        libc::SIGILL => "SIGILL (an illegal instruction)".to_string(),
+        libc::SIGFPE => "SIGFPE (an arithmetic error)".to_string(),
+        libc::SIGABRT => "SIGABRT (it aborted)".to_string(),
+        libc::SIGKILL => "SIGKILL".to_string(),
+        other => format!("signal {other}"),
     }
 }
 
@@ -109,7 +581,17 @@ pub fn roc_dev_expect(
     expectations: &mut VecMap<ModuleId, Expectations>,
     interns: &Interns,
     shared_ptr: *mut u8,
+    shared_buffer_size: usize,
 ) -> std::io::Result<usize> {
+    if (ExpectSequence { ptr: shared_ptr }).dropped_frames(shared_buffer_size) {
+        writeln!(
+            writer,
+            "An expect failed, but its values were too large to show."
+        )?;
+
+        return Ok(0);
+    }
+
     let frame = ExpectFrame::at_offset(shared_ptr, ExpectSequence::START_OFFSET);
     let module_id = frame.module_id;
 
@@ -138,6 +620,63 @@ pub fn roc_dev_expect(
     )
 }
 
+pub fn roc_dev_dbg(
+    writer: &mut impl std::io::Write,
+    arena: &Bump,
+    expectations: &mut VecMap<ModuleId, Expectations>,
+    interns: &Interns,
+    shared_ptr: *mut u8,
+    shared_buffer_size: usize,
+) -> std::io::Result<usize> {
+    if (ExpectSequence { ptr: shared_ptr }).dropped_frames(shared_buffer_size) {
+        writeln!(writer, "A dbg value was too large to show.")?;
+
+        return Ok(0);
+    }
+
+    // we always run programs as the host
+    let target_info = (&target_lexicon::Triple::host()).into();
+
+    let frame = ExpectFrame::at_offset(shared_ptr, ExpectSequence::START_OFFSET);
+    let module_id = frame.module_id;
+
+    let data = expectations.get_mut(&module_id).unwrap();
+    let filename = data.path.to_owned();
+    let source = std::fs::read_to_string(&data.path).unwrap();
+
+    let renderer = Renderer::new(
+        arena,
+        interns,
+        RenderTarget::ColorTerminal,
+        module_id,
+        filename,
+        &source,
+    );
+
+    let variables: Vec<_> = match data.expectations.get(&frame.region) {
+        None => panic!("region not in list of dbgs"),
+        Some(current) => current.iter().map(|(_, variable)| *variable).collect(),
+    };
+    let subs = arena.alloc(&mut data.subs);
+
+    let (offset, expressions) = crate::get_values(
+        target_info,
+        arena,
+        subs,
+        interns,
+        shared_ptr,
+        frame.start_offset,
+        &variables,
+    )
+    .unwrap();
+
+    for expr in expressions.iter() {
+        renderer.render_dbg(writer, expr, frame.region)?;
+    }
+
+    Ok(offset)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn render_expect_failure<'a>(
     writer: &mut impl std::io::Write,
@@ -217,6 +756,13 @@ impl ExpectSequence {
     fn count_failures(&self) -> usize {
         unsafe { *(self.ptr as *const usize).add(Self::COUNT_INDEX) }
     }
+
+    /// Frames that don't fit in the buffer are dropped, and the offset is left past its end.
+    fn dropped_frames(&self, capacity: usize) -> bool {
+        let offset = unsafe { *(self.ptr as *const usize).add(Self::OFFSET_INDEX) };
+
+        offset > capacity
+    }
 }
 
 struct ExpectFrame {
@@ -249,6 +795,29 @@ pub struct ToplevelExpect<'a> {
     pub name: &'a str,
     pub symbol: Symbol,
     pub region: Region,
+    /// LLVM wraps an expect so that it writes a `RocCallResult`, which catches panics.
+    /// The dev backend exposes the expect itself, which takes no arguments.
+    pub returns_roc_result: bool,
+}
+
+fn call_expect(lib: &libloading::Library, expect: ToplevelExpect<'_>) -> Result<(), String> {
+    use roc_gen_llvm::try_run_jit_function;
+
+    if expect.returns_roc_result {
+        return try_run_jit_function!(lib, expect.name, (), |v: ()| v);
+    }
+
+    unsafe {
+        let main: libloading::Symbol<unsafe extern "C" fn()> = lib
+            .get(expect.name.as_bytes())
+            .ok()
+            .ok_or(format!("Unable to find `{}` in the dylib", expect.name))
+            .expect("errored");
+
+        main();
+    }
+
+    Ok(())
 }
 
 pub fn expect_mono_module_to_dylib<'a>(
@@ -322,6 +891,7 @@ pub fn expect_mono_module_to_dylib<'a>(
                 symbol,
                 region,
                 name,
+                returns_roc_result: true,
             }),
         env.arena,
     );
@@ -351,3 +921,127 @@ pub fn expect_mono_module_to_dylib<'a>(
 
     llvm_module_to_dylib(env.module, &target, opt_level).map(|lib| (lib, expects))
 }
+
+/// Like `expect_mono_module_to_dylib`, but uses the dev backend, which is much faster to compile.
+pub fn expect_mono_module_to_dylib_dev<'a>(
+    arena: &'a Bump,
+    target: Triple,
+    loaded: MonomorphizedModule<'a>,
+) -> Result<(libloading::Library, BumpVec<'a, ToplevelExpect<'a>>), DylibError> {
+    let MonomorphizedModule {
+        module_id,
+        toplevel_expects,
+        procedures,
+        mut interns,
+        ..
+    } = loaded;
+
+    let (expect_symbols, expect_regions) = toplevel_expects.unzip_slices();
+
+    // every toplevel expect is a thunk returning {}, exposed under its layout-based name
+    let top_level = ProcLayout {
+        arguments: &[],
+        result: Layout::UNIT,
+        captures_niche: CapturesNiche::no_niche(),
+    };
+
+    let mut layout_ids = LayoutIds::default();
+    let expects = BumpVec::from_iter_in(
+        expect_symbols
+            .iter()
+            .zip(expect_regions.iter())
+            .map(|(symbol, region)| {
+                let name = layout_ids
+                    .get_toplevel(*symbol, &top_level)
+                    .to_exposed_symbol_string(*symbol, &interns);
+
+                ToplevelExpect {
+                    name: arena.alloc_str(&name),
+                    symbol: *symbol,
+                    region: *region,
+                    returns_roc_result: false,
+                }
+            }),
+        arena,
+    );
+
+    let env = roc_gen_dev::Env {
+        arena,
+        module_id,
+        exposed_to_host: expect_symbols.iter().copied().collect(),
+        lazy_literals: true,
+        // there is no platform to provide these
+        generate_allocators: true,
+        mode: roc_gen_dev::AssemblyBackendMode::Test,
+    };
+
+    let module_object = roc_gen_dev::build_module(&env, &mut interns, &target, procedures);
+    let module_out = module_object
+        .write()
+        .expect("failed to build output object");
+
+    dev_object_to_dylib(&target, &module_out).map(|lib| (lib, expects))
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn classify_finished_workers() {
+        let finished = WorkerState::Finished;
+
+        assert_eq!(
+            classify_worker(WorkerExit::Exited(0), finished, 0),
+            ExpectStatus::Passed
+        );
+        assert_eq!(
+            classify_worker(WorkerExit::Exited(0), finished, 2),
+            ExpectStatus::Failed
+        );
+        assert_eq!(
+            classify_worker(WorkerExit::Exited(0), WorkerState::Panicked, 0),
+            ExpectStatus::Failed
+        );
+    }
+
+    #[test]
+    fn classify_crashed_workers() {
+        // 101 is what a Rust panic exits with; it must not look like a Roc panic.
+        assert_eq!(
+            classify_worker(WorkerExit::Exited(101), WorkerState::Running, 0),
+            ExpectStatus::Crashed
+        );
+        // The expect's platform called `exit(0)` before the expect finished.
+        assert_eq!(
+            classify_worker(WorkerExit::Exited(0), WorkerState::Running, 0),
+            ExpectStatus::Crashed
+        );
+        assert_eq!(
+            classify_worker(WorkerExit::Exited(1), WorkerState::Finished, 0),
+            ExpectStatus::Crashed
+        );
+        assert_eq!(
+            classify_worker(WorkerExit::Signaled(libc::SIGSEGV), WorkerState::Running, 0),
+            ExpectStatus::Crashed
+        );
+        assert_eq!(
+            classify_worker(WorkerExit::TimedOut, WorkerState::Running, 0),
+            ExpectStatus::TimedOut
+        );
+    }
+
+    #[test]
+    fn worker_state_round_trips_through_shared_memory() {
+        let mut memory = [0usize; WORKER_HEADER_SIZE / 8];
+        let memory = memory.as_mut_ptr() as *mut u8;
+
+        assert_eq!(WorkerState::read(memory), WorkerState::Running);
+
+        WorkerState::Panicked.write(memory);
+        assert_eq!(WorkerState::read(memory), WorkerState::Panicked);
+
+        WorkerState::Finished.write(memory);
+        assert_eq!(WorkerState::read(memory), WorkerState::Finished);
+    }
+}
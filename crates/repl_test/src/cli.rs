@@ -131,6 +131,12 @@ pub fn expect_success(input: &str, expected: &str) {
     assert!(out.status.success());
 }
 
+/// Every input but the last should only have definitions (or imports), which print nothing.
+pub fn expect_session_success(inputs: &[&str], expected: &str) {
+    // A blank line ends an input which only has definitions.
+    expect_success(&inputs.join("\n\n"), expected);
+}
+
 pub fn expect_failure(input: &str, expected: &str) {
     let out = repl_eval(input);
 
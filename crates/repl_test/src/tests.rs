@@ -2,11 +2,11 @@
 use indoc::indoc;
 
 #[cfg(not(feature = "wasm"))]
-use crate::cli::{expect_failure, expect_success};
+use crate::cli::{expect_failure, expect_session_success, expect_success};
 
 #[cfg(feature = "wasm")]
 #[allow(unused_imports)]
-use crate::wasm::{expect_failure, expect_success};
+use crate::wasm::{expect_failure, expect_session_success, expect_success};
 
 #[test]
 fn literal_0() {
@@ -588,7 +588,7 @@ fn too_few_args() {
         "Num.add 2",
         indoc!(
             r#"
-                ── TOO FEW ARGS ────────────────────────────────────────────────────────────────
+                ── TOO FEW ARGS [E0301] ────────────────────────────────────────────────────────
 
                 The add function expects 2 arguments, but it got only 1:
 
@@ -609,7 +609,7 @@ fn type_problem() {
         "1 + \"\"",
         indoc!(
             r#"
-                ── TYPE MISMATCH ───────────────────────────────────────────────────────────────
+                ── TYPE MISMATCH [E0301] ───────────────────────────────────────────────────────
 
                 This 2nd argument to add has an unexpected type:
 
@@ -895,7 +895,7 @@ fn parse_problem() {
         "add m n = m + n",
         indoc!(
             r#"
-                ── ARGUMENTS BEFORE EQUALS ─────────────────────────────────────────────────────
+                ── ARGUMENTS BEFORE EQUALS [E0401] ─────────────────────────────────────────────
 
                 I am partway through parsing a definition, but I got stuck here:
 
@@ -927,7 +927,7 @@ fn exhaustiveness_problem() {
         ),
         indoc!(
             r#"
-            ── UNSAFE PATTERN ──────────────────────────────────────────────────────────────
+            ── UNSAFE PATTERN [E0310] ──────────────────────────────────────────────────────
 
             This when does not cover all the possibilities:
 
@@ -961,7 +961,7 @@ fn issue_2343_complete_mono_with_shadowed_vars() {
         ),
         indoc!(
             r#"
-                ── DUPLICATE NAME ──────────────────────────────────────────────────────────────
+                ── DUPLICATE NAME [E0201] ──────────────────────────────────────────────────────
 
                 The b name is first defined here:
 
@@ -1239,3 +1239,139 @@ fn record_of_poly_function_and_string() {
         r#"{ a: <function>, b: "b" } : { a : * -> Str, b : Str }"#,
     );
 }
+
+#[test]
+fn session_value_def() {
+    expect_session_success(&["x = 5", "x + 1"], "6 : Num *");
+}
+
+#[test]
+fn session_function_def() {
+    expect_session_success(&[r#"add1 = \n -> n + 1"#, "add1 41"], "42 : Num *");
+}
+
+#[test]
+fn session_def_using_earlier_def() {
+    expect_session_success(&["a = 1", "b = a + 1", "b"], "2 : Num *");
+}
+
+#[test]
+fn session_redefinition() {
+    expect_session_success(&["x = 5", r#"x = "five""#, "x"], r#""five" : Str"#);
+}
+
+#[test]
+fn session_type_alias() {
+    expect_session_success(
+        &[
+            "Point : { x : I64, y : I64 }",
+            indoc!(
+                r#"
+                origin : Point
+                origin = { x: 0, y: 0 }
+                "#
+            ),
+            "origin",
+        ],
+        "{ x: 0, y: 0 } : Point",
+    );
+}
+
+#[test]
+fn command_type() {
+    expect_success(":type 1 + 1", "Num *");
+}
+
+#[test]
+fn command_type_of_function() {
+    expect_success(r#":type \x, y -> x + y"#, "Num a, Num a -> Num a");
+}
+
+#[test]
+fn command_type_uses_session_defs() {
+    expect_session_success(&[r#"name = "Roc""#, ":type name"], "Str");
+}
+
+#[test]
+fn command_defs() {
+    expect_session_success(&["x = 5", "y = x + 1", ":defs"], "x = 5\ny = x + 1");
+}
+
+#[test]
+fn command_defs_empty() {
+    expect_success(":defs", "There are no definitions yet.");
+}
+
+#[test]
+fn command_time() {
+    expect_success(":time", "Timing is on.");
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn command_load() {
+    let dir = std::env::temp_dir().join("roc_repl_command_load");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let path = dir.join("Greeting.roc");
+    std::fs::write(
+        &path,
+        indoc!(
+            r#"
+            interface Greeting
+                exposes [greeting]
+                imports []
+
+            greeting = "Hello"
+            "#
+        ),
+    )
+    .unwrap();
+
+    expect_success(
+        &format!(":load {}\ngreeting", path.display()),
+        "import Greeting.{ greeting }\n\n\"Hello\" : Str",
+    );
+}
+
+#[cfg(feature = "wasm")]
+#[test]
+fn command_load_unsupported() {
+    expect_failure(
+        ":load Greeting.roc",
+        "The web REPL can't read files, so it doesn't support :load or :reload.",
+    );
+}
+
+#[cfg(feature = "wasm")]
+#[test]
+fn command_help_leaves_out_load() {
+    expect_success(
+        ":help",
+        indoc!(
+            r#"
+            :type <expr>       Print the type of an expression, without evaluating it
+            :ir <expr>         Print the IR an expression specializes to
+            :defs              List the session's imports and definitions
+            :time              Turn reporting compile and eval times on or off
+            :help              Print this message"#
+        ),
+    );
+}
+
+#[test]
+fn session_ability() {
+    expect_session_success(
+        &[
+            "MyHash has myHash : a -> U64 | a has MyHash",
+            indoc!(
+                r#"
+                Id := U64 has [MyHash {myHash: hashId}]
+                hashId = \@Id n -> n
+                "#
+            ),
+            "myHash (@Id 1234)",
+        ],
+        "1234 : U64",
+    );
+}
@@ -240,7 +240,30 @@ fn dummy_system_time_now() -> f64 {
 }
 
 fn run(src: &'static str) -> (bool, String) {
+    run_session(&[src]).pop().unwrap()
+}
+
+/// Runs the inputs one after the other, in a fresh REPL session
+fn run_session(inputs: &[&'static str]) -> Vec<(bool, String)> {
     println!("run");
+
+    if let Ok(_guard) = TEST_MUTEX.lock() {
+        COMPILER
+            .exports
+            .get_function("reset_session_from_test")
+            .unwrap()
+            .call(&[])
+            .unwrap();
+
+        inputs.iter().map(|src| run_input(src)).collect()
+    } else {
+        panic!(
+            "Failed to acquire test mutex! A previous test must have panicked while holding it, running Wasm"
+        )
+    }
+}
+
+fn run_input(src: &'static str) -> (bool, String) {
     REPL_STATE.with(|rs| {
         *rs.borrow_mut().deref_mut() = Some(ReplState {
             src,
@@ -250,20 +273,14 @@ fn run(src: &'static str) -> (bool, String) {
         });
     });
 
-    let ok = if let Ok(_guard) = TEST_MUTEX.lock() {
-        let entrypoint = COMPILER
-            .exports
-            .get_function("entrypoint_from_test")
-            .unwrap();
+    let entrypoint = COMPILER
+        .exports
+        .get_function("entrypoint_from_test")
+        .unwrap();
 
-        let src_len = Value::I32(src.len() as i32);
-        let wasm_ok: i32 = entrypoint.call(&[src_len]).unwrap().deref()[0].unwrap_i32();
-        wasm_ok != 0
-    } else {
-        panic!(
-            "Failed to acquire test mutex! A previous test must have panicked while holding it, running Wasm"
-        )
-    };
+    let src_len = Value::I32(src.len() as i32);
+    let wasm_ok: i32 = entrypoint.call(&[src_len]).unwrap().deref()[0].unwrap_i32();
+    let ok = wasm_ok != 0;
 
     let final_state: ReplState = REPL_STATE.with(|rs| rs.take()).unwrap();
     let output: String = final_state.output.unwrap();
@@ -286,3 +303,21 @@ pub fn expect_failure(input: &'static str, expected: &str) {
     assert_eq!(ok, false);
     assert_eq!(output, expected);
 }
+
+#[allow(dead_code)]
+pub fn expect_session_success(inputs: &[&'static str], expected: &str) {
+    let mut results = run_session(inputs);
+    let (ok, output) = results.pop().unwrap();
+
+    for (def_ok, def_output) in results {
+        if !def_ok {
+            panic!("\n{}\n", def_output);
+        }
+        assert_eq!(def_output, "");
+    }
+
+    if !ok {
+        panic!("\n{}\n", output);
+    }
+    assert_eq!(output, expected);
+}
@@ -26,6 +26,13 @@ pub fn js_get_result_and_memory(buffer_alloc_addr: *mut u8) -> usize {
     unsafe { wasmer_get_result_and_memory(buffer_alloc_addr) }
 }
 
+/// Milliseconds since the Unix epoch, like the JS `Date.now()`
+pub fn js_now() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |duration| duration.as_secs_f64() * 1000.0)
+}
+
 /// Entrypoint for Wasmer tests
 /// - Synchronous API, to avoid the need to run an async executor across the Wasm/native boundary.
 ///   (wasmer has a sync API for creating an Instance, whereas browsers don't)
@@ -47,3 +54,9 @@ pub extern "C" fn entrypoint_from_test(src_len: usize) -> bool {
 
     ok
 }
+
+/// Forgets the REPL session's definitions, so that tests sharing the compiler don't see each other's
+#[no_mangle]
+pub extern "C" fn reset_session_from_test() {
+    crate::repl::reset_session();
+}
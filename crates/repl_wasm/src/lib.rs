@@ -8,7 +8,9 @@ extern crate console_error_panic_hook;
 #[cfg(not(feature = "wasmer"))]
 mod externs_js;
 #[cfg(not(feature = "wasmer"))]
-pub use externs_js::{entrypoint_from_js, js_create_app, js_get_result_and_memory, js_run_app};
+pub use externs_js::{
+    entrypoint_from_js, js_create_app, js_get_result_and_memory, js_now, js_run_app,
+};
 
 //
 // Interface with test code outside the Wasm module
@@ -16,4 +18,7 @@ pub use externs_js::{entrypoint_from_js, js_create_app, js_get_result_and_memory
 #[cfg(feature = "wasmer")]
 mod externs_test;
 #[cfg(feature = "wasmer")]
-pub use externs_test::{entrypoint_from_test, js_create_app, js_get_result_and_memory, js_run_app};
+pub use externs_test::{
+    entrypoint_from_test, js_create_app, js_get_result_and_memory, js_now, js_run_app,
+    reset_session_from_test,
+};
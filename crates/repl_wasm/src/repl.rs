@@ -1,23 +1,38 @@
 use bumpalo::{collections::vec::Vec, Bump};
+use std::cell::RefCell;
 use std::mem::size_of;
+use std::path::PathBuf;
 
 use roc_collections::all::MutSet;
 use roc_gen_wasm::wasm32_result;
 use roc_load::MonomorphizedModule;
 use roc_parse::ast::Expr;
 use roc_repl_eval::{
+    commands::{run_command, ReplCommand, Timings},
     eval::jit_to_ast,
-    gen::{compile_to_mono, format_answer, ReplOutput},
+    gen::{format_answer, ReplOutput},
+    session::ReplSession,
     ReplApp, ReplAppMemory,
 };
 use roc_reporting::report::DEFAULT_PALETTE_HTML;
 use roc_target::TargetInfo;
 use roc_types::pretty_print::{name_and_print_var, DebugPrint};
 
-use crate::{js_create_app, js_get_result_and_memory, js_run_app};
+use crate::{js_create_app, js_get_result_and_memory, js_now, js_run_app};
 
 const WRAPPER_NAME: &str = "wrapper";
 
+thread_local! {
+    /// There's no file system in the browser, so there's nothing to import.
+    static SESSION: RefCell<ReplSession> = RefCell::new(ReplSession::new(PathBuf::new()));
+}
+
+/// Forgets the session's definitions and imports
+#[cfg(feature = "wasmer")]
+pub fn reset_session() {
+    SESSION.with(|session| *session.borrow_mut() = ReplSession::new(PathBuf::new()));
+}
+
 pub struct WasmReplApp<'a> {
     arena: &'a Bump,
 }
@@ -173,10 +188,35 @@ pub async fn entrypoint_from_js(src: String) -> Result<String, String> {
 
     let arena = &Bump::new();
 
-    // Compile the app
     let target_info = TargetInfo::default_wasm32();
-    let mono = match compile_to_mono(arena, &src, target_info, DEFAULT_PALETTE_HTML) {
-        Ok(m) => m,
+
+    if let Some(command) = ReplCommand::parse(&src) {
+        return SESSION
+            .with(|session| {
+                run_command(
+                    &mut session.borrow_mut(),
+                    arena,
+                    command,
+                    target_info,
+                    DEFAULT_PALETTE_HTML,
+                )
+            })
+            .map_err(|problems| problems.join("\n\n"));
+    }
+
+    // Compile the app
+    let compile_start = js_now();
+    let reports_timings = SESSION.with(|session| session.borrow().reports_timings());
+    let compiled = SESSION.with(|session| {
+        session
+            .borrow_mut()
+            .compile(arena, &src, target_info, DEFAULT_PALETTE_HTML)
+    });
+
+    let mono = match compiled {
+        Ok(Some(m)) => m,
+        // Definitions and imports have no output
+        Ok(None) => return Ok(String::new()),
         Err(messages) => return Err(messages.join("\n\n")),
     };
 
@@ -247,6 +287,9 @@ pub async fn entrypoint_from_js(src: String) -> Result<String, String> {
         buffer
     };
 
+    let compile_ms = js_now() - compile_start;
+    let eval_start = js_now();
+
     // Send the compiled binary out to JS and create an executable instance from it
     js_create_app(&app_module_bytes)
         .await
@@ -270,6 +313,14 @@ pub async fn entrypoint_from_js(src: String) -> Result<String, String> {
     // Transform the Expr to a string
     // `Result::Err` becomes a JS exception that will be caught and displayed
     match format_answer(arena, res_answer, expr_type_str) {
+        ReplOutput::NoProblems { expr, expr_type } if reports_timings => {
+            let timings = Timings {
+                compile_ms,
+                eval_ms: js_now() - eval_start,
+            };
+
+            Ok(format!("{} : {}\n{}", expr, expr_type, timings))
+        }
         ReplOutput::NoProblems { expr, expr_type } => Ok(format!("{} : {}", expr, expr_type)),
         ReplOutput::Problems(lines) => Err(format!("\n{}\n", lines.join("\n\n"))),
     }
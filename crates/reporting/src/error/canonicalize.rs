@@ -64,6 +64,7 @@ pub fn can_problem<'b>(
     filename: PathBuf,
     problem: Problem,
 ) -> Report<'b> {
+    let code = problem.code();
     let doc;
     let title;
     let severity;
@@ -381,14 +382,16 @@ pub fn can_problem<'b>(
             field_region,
             record_region,
         } => {
-            return to_invalid_optional_value_report(
+            doc = to_invalid_optional_value_report_help(
                 alloc,
                 lines,
-                filename,
                 field_name,
                 field_region,
                 record_region,
             );
+
+            title = "BAD OPTIONAL VALUE".to_string();
+            severity = Severity::RuntimeError;
         }
         Problem::DuplicateRecordFieldType {
             field_name,
@@ -586,6 +589,7 @@ pub fn can_problem<'b>(
                 ExtensionTypeKind::TagUnion => {
                     ("tag union", "a type variable or another tag union")
                 }
+                ExtensionTypeKind::Tuple => ("tuple", "a type variable or another tuple"),
             };
 
             doc = alloc.stack([
@@ -984,6 +988,7 @@ pub fn can_problem<'b>(
         filename,
         doc,
         severity,
+        code: Some(code),
     }
 }
 
@@ -994,30 +999,6 @@ fn list_builtin_abilities<'a>(alloc: &'a RocDocAllocator<'a>) -> RocDocBuilder<'
     )
 }
 
-fn to_invalid_optional_value_report<'b>(
-    alloc: &'b RocDocAllocator<'b>,
-    lines: &LineInfo,
-    filename: PathBuf,
-    field_name: Lowercase,
-    field_region: Region,
-    record_region: Region,
-) -> Report<'b> {
-    let doc = to_invalid_optional_value_report_help(
-        alloc,
-        lines,
-        field_name,
-        field_region,
-        record_region,
-    );
-
-    Report {
-        title: "BAD OPTIONAL VALUE".to_string(),
-        filename,
-        doc,
-        severity: Severity::RuntimeError,
-    }
-}
-
 fn to_invalid_optional_value_report_help<'b>(
     alloc: &'b RocDocAllocator<'b>,
     lines: &LineInfo,
@@ -148,6 +148,7 @@ impl<'a> Renderer<'a> {
             doc,
             filename: self.filename.clone(),
             severity: crate::report::Severity::RuntimeError,
+            code: None,
         };
 
         let mut buf = String::new();
@@ -162,12 +163,100 @@ impl<'a> Renderer<'a> {
         write!(writer, "{}", buf)
     }
 
+    /// Renders the value printed by a `dbg` as a single line, prefixed with its location.
+    pub fn render_dbg<W>(
+        &self,
+        writer: &mut W,
+        expr: &Expr<'_>,
+        dbg_region: Region,
+    ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        use roc_fmt::annotation::Formattable;
+
+        let mut buf = roc_fmt::Buf::new_in(self.arena);
+        expr.format(&mut buf, 0);
+
+        let start = self.line_info.convert_pos(dbg_region.start());
+
+        writeln!(
+            writer,
+            "[{} {}:{}] {}",
+            self.filename.display(),
+            start.line + 1,
+            start.column + 1,
+            buf.into_bump_str(),
+        )
+    }
+
     pub fn render_panic<W>(
         &self,
         writer: &mut W,
         message: &str,
         expect_region: Region,
     ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        self.render_abnormal_exit(
+            writer,
+            "EXPECT PANICKED",
+            "This expectation crashed while running:",
+            "The crash reported this message:",
+            message,
+            expect_region,
+        )
+    }
+
+    /// Used when the process running an expectation died, e.g. because of a segfault or a stack overflow.
+    pub fn render_crash<W>(
+        &self,
+        writer: &mut W,
+        cause: &str,
+        expect_region: Region,
+    ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        self.render_abnormal_exit(
+            writer,
+            "EXPECT CRASHED",
+            "The process running this expectation stopped unexpectedly:",
+            "Here is what happened to it:",
+            cause,
+            expect_region,
+        )
+    }
+
+    pub fn render_timeout<W>(
+        &self,
+        writer: &mut W,
+        timeout: std::time::Duration,
+        expect_region: Region,
+    ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        self.render_abnormal_exit(
+            writer,
+            "EXPECT TIMED OUT",
+            "This expectation was still running after the time limit:",
+            "The time limit for each expectation is:",
+            &format!("{} seconds", timeout.as_secs_f64()),
+            expect_region,
+        )
+    }
+
+    fn render_abnormal_exit<W>(
+        &self,
+        writer: &mut W,
+        title: &str,
+        intro: &str,
+        details_intro: &str,
+        details: &str,
+        expect_region: Region,
+    ) -> std::io::Result<()>
     where
         W: std::io::Write,
     {
@@ -177,17 +266,18 @@ impl<'a> Renderer<'a> {
         let line_col_region = self.line_info.convert_region(expect_region);
 
         let doc = self.alloc.stack([
-            self.alloc.text("This expectation crashed while running:"),
+            self.alloc.text(intro.to_string()),
             self.alloc.region(line_col_region),
-            self.alloc.text("The crash reported this message:"),
-            self.alloc.text(message),
+            self.alloc.text(details_intro.to_string()),
+            self.alloc.text(details.to_string()),
         ]);
 
         let report = Report {
-            title: "EXPECT PANICKED".into(),
+            title: title.into(),
             doc,
             filename: self.filename.clone(),
             severity: crate::report::Severity::RuntimeError,
+            code: None,
         };
 
         let mut buf = String::new();
@@ -2,6 +2,7 @@ use roc_parse::parser::{ENumber, FileError, SyntaxError};
 use roc_region::all::{LineColumn, LineColumnRegion, LineInfo, Position, Region};
 use std::path::PathBuf;
 
+use crate::explain;
 use crate::report::{Report, RocDocAllocator, RocDocBuilder, Severity};
 use ven_pretty::DocAllocator;
 
@@ -59,6 +60,7 @@ fn to_syntax_report<'a>(
         doc,
         title: "PARSE PROBLEM".to_string(),
         severity: Severity::RuntimeError,
+        code: Some(explain::PARSE_PROBLEM),
     };
 
     match parse_problem {
@@ -73,6 +75,7 @@ fn to_syntax_report<'a>(
                 doc,
                 title: "PARSE PROBLEM".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::PARSE_PROBLEM),
             }
         }
         Unexpected(region) => {
@@ -105,6 +108,7 @@ fn to_syntax_report<'a>(
                 doc,
                 title: "NOT END OF FILE".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::NOT_END_OF_FILE),
             }
         }
         SyntaxError::Eof(region) => {
@@ -118,6 +122,7 @@ fn to_syntax_report<'a>(
                 doc,
                 title: "PARSE PROBLEM".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::PARSE_PROBLEM),
             }
         }
         SyntaxError::OutdentedTooFar => {
@@ -128,6 +133,7 @@ fn to_syntax_report<'a>(
                 doc,
                 title: "PARSE PROBLEM".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::PARSE_PROBLEM),
             }
         }
         Type(typ) => to_type_report(alloc, lines, filename, typ, Position::default()),
@@ -207,6 +213,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: "ARGUMENTS BEFORE EQUALS".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::ARGUMENTS_BEFORE_EQUALS),
             }
         }
 
@@ -294,6 +301,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: "UNKNOWN OPERATOR".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::UNKNOWN_OPERATOR),
             }
         }
 
@@ -320,13 +328,15 @@ fn to_expr_report<'a>(
                 doc,
                 title: "WEIRD IDENTIFIER".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_IDENTIFIER),
             }
         }
 
         EExpr::Start(pos) | EExpr::IndentStart(pos) => {
-            let (title, expecting) = match &context {
+            let (title, code, expecting) = match &context {
                 Context::InNode { .. } | Context::InDef { .. } => (
                     "MISSING EXPRESSION",
+                    explain::MISSING_EXPRESSION,
                     alloc.concat([
                         alloc.reflow("I was expecting to see an expression like "),
                         alloc.parser_suggestion("42"),
@@ -337,6 +347,7 @@ fn to_expr_report<'a>(
                 ),
                 Context::InDefFinalExpr { .. } => (
                     "MISSING FINAL EXPRESSION",
+                    explain::MISSING_FINAL_EXPRESSION,
                     alloc.stack([
                         alloc.concat([
                             alloc.reflow("This definition is missing a final expression."),
@@ -400,6 +411,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: title.to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(code),
             }
         }
 
@@ -428,6 +440,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: "MISSING FINAL EXPRESSION".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::MISSING_FINAL_EXPRESSION),
             }
         }
 
@@ -459,6 +472,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: "SYNTAX PROBLEM".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::SYNTAX_PROBLEM),
             }
         }
 
@@ -482,6 +496,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: "ARGUMENTS BEFORE EQUALS".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::ARGUMENTS_BEFORE_EQUALS),
             }
         }
 
@@ -500,6 +515,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: "BAD BACKPASSING ARROW".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::BAD_BACKPASSING_ARROW),
             }
         }
 
@@ -518,6 +534,7 @@ fn to_expr_report<'a>(
                 doc,
                 title: "RECORD PARSE PROBLEM".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::RECORD_PARSE_PROBLEM),
             }
         }
 
@@ -565,6 +582,7 @@ fn to_lambda_report<'a>(
                     doc,
                     title: "WEIRD ARROW".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::WEIRD_ARROW),
                 }
             }
             _ => {
@@ -587,6 +605,7 @@ fn to_lambda_report<'a>(
                     doc,
                     title: "MISSING ARROW".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::MISSING_ARROW),
                 }
             }
         },
@@ -612,6 +631,7 @@ fn to_lambda_report<'a>(
                     doc,
                     title: "WEIRD ARROW".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::WEIRD_ARROW),
                 }
             }
             _ => {
@@ -634,6 +654,7 @@ fn to_lambda_report<'a>(
                     doc,
                     title: "MISSING ARROW".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::MISSING_ARROW),
                 }
             }
         },
@@ -658,6 +679,7 @@ fn to_lambda_report<'a>(
                     doc,
                     title: "UNFINISHED ARGUMENT LIST".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::UNFINISHED_ARGUMENT_LIST),
                 }
             }
             _ => {
@@ -679,6 +701,7 @@ fn to_lambda_report<'a>(
                     doc,
                     title: "MISSING ARROW".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::MISSING_ARROW),
                 }
             }
         },
@@ -760,6 +783,7 @@ fn to_unfinished_lambda_report<'a>(
         doc,
         title: "UNFINISHED FUNCTION".to_string(),
         severity: Severity::RuntimeError,
+        code: Some(explain::UNFINISHED_FUNCTION),
     }
 }
 
@@ -826,6 +850,7 @@ fn to_str_report<'a>(
                 doc,
                 title: "WEIRD ESCAPE".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_ESCAPE),
             }
         }
         EString::CodePtOpen(pos) | EString::CodePtEnd(pos) => {
@@ -852,6 +877,7 @@ fn to_str_report<'a>(
                 doc,
                 title: "WEIRD CODE POINT".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_CODE_POINT),
             }
         }
         EString::FormatEnd(pos) => {
@@ -873,6 +899,7 @@ fn to_str_report<'a>(
                 doc,
                 title: "ENDLESS FORMAT".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::ENDLESS_FORMAT),
             }
         }
         EString::EndlessSingle(pos) => {
@@ -896,6 +923,7 @@ fn to_str_report<'a>(
                 doc,
                 title: "ENDLESS STRING".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::ENDLESS_STRING),
             }
         }
         EString::EndlessMulti(pos) => {
@@ -919,6 +947,7 @@ fn to_str_report<'a>(
                 doc,
                 title: "ENDLESS STRING".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::ENDLESS_STRING),
             }
         }
         EString::MultilineInsufficientIndent(pos) => {
@@ -940,6 +969,7 @@ fn to_str_report<'a>(
                 doc,
                 title: "INSUFFICIENT INDENT IN MULTI-LINE STRING".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::INSUFFICIENT_INDENT_IN_MULTI_LINE_STRING),
             }
         }
     }
@@ -986,6 +1016,32 @@ fn to_expr_in_parens_report<'a>(
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::UNFINISHED_PARENTHESES),
+            }
+        }
+        EInParens::Empty(pos) => {
+            let surroundings = Region::new(start, pos);
+            let region = LineColumnRegion::from_pos(lines.convert_pos(pos));
+
+            let doc = alloc.stack([
+                alloc.reflow("I am partway through parsing an expression, but I got stuck here:"),
+                alloc.region_with_subregion(lines.convert_region(surroundings), region),
+                alloc.concat([
+                    alloc.reflow("These parentheses are empty, but I was expecting an expression "),
+                    alloc.reflow("inside them, like "),
+                    alloc.parser_suggestion("(32)"),
+                    alloc.reflow(" or a tuple like "),
+                    alloc.parser_suggestion("(32, \"hello\")"),
+                    alloc.reflow("."),
+                ]),
+            ]);
+
+            Report {
+                filename,
+                doc,
+                title: "EMPTY PARENTHESES".to_string(),
+                severity: Severity::RuntimeError,
+                code: Some(explain::EMPTY_PARENTHESES),
             }
         }
         EInParens::Open(pos) | EInParens::IndentOpen(pos) => {
@@ -1011,6 +1067,7 @@ fn to_expr_in_parens_report<'a>(
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::UNFINISHED_PARENTHESES),
             }
         }
     }
@@ -1061,6 +1118,7 @@ fn to_list_report<'a>(
                         doc,
                         title: "UNFINISHED LIST".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::UNFINISHED_LIST),
                     }
                 }
                 _ => {
@@ -1094,6 +1152,7 @@ fn to_list_report<'a>(
                         doc,
                         title: "UNFINISHED LIST".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::UNFINISHED_LIST),
                     }
                 }
             }
@@ -1122,6 +1181,7 @@ fn to_list_report<'a>(
                 doc,
                 title: "UNFINISHED LIST".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::UNFINISHED_LIST),
             }
         }
     }
@@ -1237,6 +1297,7 @@ fn to_unfinished_if_report<'a>(
         doc,
         title: "UNFINISHED IF".to_string(),
         severity: Severity::RuntimeError,
+        code: Some(explain::UNFINISHED_IF),
     }
 }
 
@@ -1270,6 +1331,7 @@ fn to_when_report<'a>(
                         doc,
                         title: "IF GUARD NO CONDITION".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::IF_GUARD_NO_CONDITION),
                     }
                 }
                 _ => to_expr_report(
@@ -1302,6 +1364,7 @@ fn to_when_report<'a>(
                 doc,
                 title: "MISSING ARROW".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::MISSING_ARROW),
             }
         }
 
@@ -1457,6 +1520,7 @@ fn to_unfinished_when_report<'a>(
                 doc,
                 title: "UNFINISHED WHEN".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::UNFINISHED_WHEN),
             }
         }
     }
@@ -1497,6 +1561,7 @@ fn to_unexpected_arrow_report<'a>(
         doc,
         title: "UNEXPECTED ARROW".to_string(),
         severity: Severity::RuntimeError,
+        code: Some(explain::UNEXPECTED_ARROW),
     }
 }
 
@@ -1573,6 +1638,7 @@ fn to_pattern_report<'a>(
                 doc,
                 title: "UNFINISHED PATTERN".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::UNFINISHED_PATTERN),
             }
         }
         EPattern::Record(record, pos) => to_precord_report(alloc, lines, filename, record, *pos),
@@ -1616,6 +1682,7 @@ fn to_precord_report<'a>(
                     doc,
                     title: "UNFINISHED RECORD PATTERN".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::UNFINISHED_RECORD_PATTERN),
                 }
             }
             _ => {
@@ -1633,6 +1700,7 @@ fn to_precord_report<'a>(
                     doc,
                     title: "UNFINISHED RECORD PATTERN".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::UNFINISHED_RECORD_PATTERN),
                 }
             }
         },
@@ -1658,6 +1726,7 @@ fn to_precord_report<'a>(
                         doc,
                         title: "UNFINISHED RECORD PATTERN".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::UNFINISHED_RECORD_PATTERN),
                     }
                 }
                 _ => {
@@ -1678,6 +1747,7 @@ fn to_precord_report<'a>(
                         doc,
                         title: "UNFINISHED RECORD PATTERN".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::UNFINISHED_RECORD_PATTERN),
                     }
                 }
             }
@@ -1703,6 +1773,7 @@ fn to_precord_report<'a>(
                     doc,
                     title: "UNFINISHED RECORD PATTERN".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::UNFINISHED_RECORD_PATTERN),
                 }
             }
             Next::Other(Some(',')) => todo!(),
@@ -1728,6 +1799,7 @@ fn to_precord_report<'a>(
                     doc,
                     title: "PROBLEM IN RECORD PATTERN".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::PROBLEM_IN_RECORD_PATTERN),
                 }
             }
         },
@@ -1770,6 +1842,7 @@ fn to_precord_report<'a>(
                 doc,
                 title: "UNFINISHED RECORD PATTERN".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::UNFINISHED_RECORD_PATTERN),
             }
         }
 
@@ -1794,6 +1867,7 @@ fn to_precord_report<'a>(
                         doc,
                         title: "NEED MORE INDENTATION".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::NEED_MORE_INDENTATION),
                     }
                 }
                 None => {
@@ -1819,6 +1893,7 @@ fn to_precord_report<'a>(
                         doc,
                         title: "UNFINISHED RECORD PATTERN".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::UNFINISHED_RECORD_PATTERN),
                     }
                 }
             }
@@ -1846,6 +1921,31 @@ fn to_pattern_in_parens_report<'a>(
     use roc_parse::parser::PInParens;
 
     match *parse_problem {
+        PInParens::Empty(pos) => {
+            let surroundings = Region::new(start, pos);
+            let region = LineColumnRegion::from_pos(lines.convert_pos(pos));
+
+            let doc = alloc.stack([
+                alloc.reflow("I am partway through parsing a pattern, but I got stuck here:"),
+                alloc.region_with_subregion(lines.convert_region(surroundings), region),
+                alloc.concat([
+                    alloc.reflow("These parentheses are empty, but I was expecting a pattern "),
+                    alloc.reflow("inside them, like "),
+                    alloc.parser_suggestion("(Ok 32)"),
+                    alloc.reflow(" or a tuple like "),
+                    alloc.parser_suggestion("(Ok 32, x)"),
+                    alloc.reflow("."),
+                ]),
+            ]);
+
+            Report {
+                filename,
+                doc,
+                title: "EMPTY PARENTHESES".to_string(),
+                severity: Severity::RuntimeError,
+                code: Some(explain::EMPTY_PARENTHESES),
+            }
+        }
         PInParens::Open(pos) => {
             // `Open` case is for exhaustiveness, this case shouldn not be reachable practically.
             let surroundings = Region::new(start, pos);
@@ -1870,6 +1970,7 @@ fn to_pattern_in_parens_report<'a>(
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::UNFINISHED_PARENTHESES),
             }
         }
 
@@ -1894,6 +1995,7 @@ fn to_pattern_in_parens_report<'a>(
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::UNFINISHED_PARENTHESES),
             }
         }
 
@@ -1917,6 +2019,7 @@ fn to_pattern_in_parens_report<'a>(
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::UNFINISHED_PARENTHESES),
             }
         }
 
@@ -1941,6 +2044,7 @@ fn to_pattern_in_parens_report<'a>(
                         doc,
                         title: "NEED MORE INDENTATION".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::NEED_MORE_INDENTATION),
                     }
                 }
                 None => {
@@ -1966,6 +2070,7 @@ fn to_pattern_in_parens_report<'a>(
                         doc,
                         title: "UNFINISHED PARENTHESES".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::UNFINISHED_PARENTHESES),
                     }
                 }
             }
@@ -1994,6 +2099,7 @@ fn to_malformed_number_literal_report<'a>(
         doc,
         title: "INVALID NUMBER LITERAL".to_string(),
         severity: Severity::RuntimeError,
+        code: Some(explain::INVALID_NUMBER_LITERAL),
     }
 }
 
@@ -2034,6 +2140,7 @@ fn to_type_report<'a>(
                         doc,
                         title: "DOUBLE COMMA".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::DOUBLE_COMMA),
                     }
                 }
                 _ => todo!(),
@@ -2061,6 +2168,7 @@ fn to_type_report<'a>(
                 doc,
                 title: "UNFINISHED TYPE".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::UNFINISHED_TYPE),
             }
         }
 
@@ -2079,6 +2187,7 @@ fn to_type_report<'a>(
                 doc,
                 title: "UNFINISHED TYPE".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::UNFINISHED_TYPE),
             }
         }
 
@@ -2097,6 +2206,7 @@ fn to_type_report<'a>(
                 doc,
                 title: "UNFINISHED TYPE".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::UNFINISHED_TYPE),
             }
         }
 
@@ -2115,6 +2225,7 @@ fn to_type_report<'a>(
                 doc,
                 title: "UNFINISHED INLINE ALIAS".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::UNFINISHED_INLINE_ALIAS),
             }
         }
 
@@ -2132,6 +2243,7 @@ fn to_type_report<'a>(
                 doc,
                 title: "BAD TYPE VARIABLE".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::BAD_TYPE_VARIABLE),
             }
         }
 
@@ -2169,6 +2281,7 @@ fn to_trecord_report<'a>(
                     doc,
                     title: "UNFINISHED RECORD TYPE".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::UNFINISHED_RECORD_TYPE),
                 }
             }
             _ => {
@@ -2190,6 +2303,7 @@ fn to_trecord_report<'a>(
                     doc,
                     title: "UNFINISHED RECORD TYPE".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::UNFINISHED_RECORD_TYPE),
                 }
             }
         },
@@ -2215,6 +2329,7 @@ fn to_trecord_report<'a>(
                         doc,
                         title: "UNFINISHED RECORD TYPE".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::UNFINISHED_RECORD_TYPE),
                     }
                 }
                 _ => {
@@ -2235,6 +2350,7 @@ fn to_trecord_report<'a>(
                         doc,
                         title: "UNFINISHED RECORD TYPE".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::UNFINISHED_RECORD_TYPE),
                     }
                 }
             }
@@ -2260,6 +2376,7 @@ fn to_trecord_report<'a>(
                     doc,
                     title: "UNFINISHED RECORD TYPE".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::UNFINISHED_RECORD_TYPE),
                 }
             }
             Next::Other(Some(',')) => todo!(),
@@ -2285,6 +2402,7 @@ fn to_trecord_report<'a>(
                     doc,
                     title: "PROBLEM IN RECORD TYPE".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::PROBLEM_IN_RECORD_TYPE),
                 }
             }
         },
@@ -2318,6 +2436,7 @@ fn to_trecord_report<'a>(
                 doc,
                 title: "UNFINISHED RECORD TYPE".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::UNFINISHED_RECORD_TYPE),
             }
         }
 
@@ -2342,6 +2461,7 @@ fn to_trecord_report<'a>(
                         doc,
                         title: "NEED MORE INDENTATION".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::NEED_MORE_INDENTATION),
                     }
                 }
                 None => {
@@ -2367,6 +2487,7 @@ fn to_trecord_report<'a>(
                         doc,
                         title: "UNFINISHED RECORD TYPE".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::UNFINISHED_RECORD_TYPE),
                     }
                 }
             }
@@ -2414,6 +2535,7 @@ fn to_ttag_union_report<'a>(
                     doc,
                     title: "UNFINISHED TAG UNION TYPE".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::UNFINISHED_TAG_UNION_TYPE),
                 }
             }
             Next::Other(Some(c)) if c.is_alphabetic() => {
@@ -2436,6 +2558,7 @@ fn to_ttag_union_report<'a>(
                     doc,
                     title: "WEIRD TAG NAME".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::WEIRD_TAG_NAME),
                 }
             }
             _ => {
@@ -2457,6 +2580,7 @@ fn to_ttag_union_report<'a>(
                     doc,
                     title: "UNFINISHED TAG UNION TYPE".to_string(),
                     severity: Severity::RuntimeError,
+                    code: Some(explain::UNFINISHED_TAG_UNION_TYPE),
                 }
             }
         },
@@ -2483,6 +2607,7 @@ fn to_ttag_union_report<'a>(
                         doc,
                         title: "WEIRD TAG NAME".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::WEIRD_TAG_NAME),
                     }
                 }
                 _ => {
@@ -2503,6 +2628,7 @@ fn to_ttag_union_report<'a>(
                         doc,
                         title: "UNFINISHED TAG UNION TYPE".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::UNFINISHED_TAG_UNION_TYPE),
                     }
                 }
             }
@@ -2530,6 +2656,7 @@ fn to_ttag_union_report<'a>(
                 doc,
                 title: "UNFINISHED TAG UNION TYPE".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::UNFINISHED_TAG_UNION_TYPE),
             }
         }
 
@@ -2557,6 +2684,7 @@ fn to_ttag_union_report<'a>(
                         doc,
                         title: "NEED MORE INDENTATION".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::NEED_MORE_INDENTATION),
                     }
                 }
                 None => {
@@ -2582,6 +2710,7 @@ fn to_ttag_union_report<'a>(
                         doc,
                         title: "UNFINISHED TAG UNION TYPE".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::UNFINISHED_TAG_UNION_TYPE),
                     }
                 }
             }
@@ -2601,6 +2730,31 @@ fn to_tinparens_report<'a>(
     use roc_parse::parser::ETypeInParens;
 
     match *parse_problem {
+        ETypeInParens::Empty(pos) => {
+            let surroundings = Region::new(start, pos);
+            let region = LineColumnRegion::from_pos(lines.convert_pos(pos));
+
+            let doc = alloc.stack([
+                alloc.reflow("I am partway through parsing a type, but I got stuck here:"),
+                alloc.region_with_subregion(lines.convert_region(surroundings), region),
+                alloc.concat([
+                    alloc.reflow("These parentheses are empty, but I was expecting a type "),
+                    alloc.reflow("inside them, like "),
+                    alloc.parser_suggestion("(List Person)"),
+                    alloc.reflow(" or a tuple like "),
+                    alloc.parser_suggestion("(List Person, Str)"),
+                    alloc.reflow("."),
+                ]),
+            ]);
+
+            Report {
+                filename,
+                doc,
+                title: "EMPTY PARENTHESES".to_string(),
+                severity: Severity::RuntimeError,
+                code: Some(explain::EMPTY_PARENTHESES),
+            }
+        }
         ETypeInParens::Open(pos) => {
             match what_is_next(alloc.src_lines, lines.convert_pos(pos)) {
                 Next::Keyword(keyword) => {
@@ -2623,6 +2777,7 @@ fn to_tinparens_report<'a>(
                         doc,
                         title: "UNFINISHED PARENTHESES".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::UNFINISHED_PARENTHESES),
                     }
                 }
                 Next::Other(Some(c)) if c.is_alphabetic() => {
@@ -2645,6 +2800,7 @@ fn to_tinparens_report<'a>(
                         doc,
                         title: "WEIRD TAG NAME".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::WEIRD_TAG_NAME),
                     }
                 }
                 _ => {
@@ -2668,6 +2824,7 @@ fn to_tinparens_report<'a>(
                         doc,
                         title: "UNFINISHED PARENTHESES".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::UNFINISHED_PARENTHESES),
                     }
                 }
             }
@@ -2696,6 +2853,7 @@ fn to_tinparens_report<'a>(
                         doc,
                         title: "WEIRD TAG NAME".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::WEIRD_TAG_NAME),
                     }
                 }
                 _ => {
@@ -2716,6 +2874,7 @@ fn to_tinparens_report<'a>(
                         doc,
                         title: "UNFINISHED PARENTHESES".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::UNFINISHED_PARENTHESES),
                     }
                 }
             }
@@ -2744,6 +2903,7 @@ fn to_tinparens_report<'a>(
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::UNFINISHED_PARENTHESES),
             }
         }
 
@@ -2768,6 +2928,7 @@ fn to_tinparens_report<'a>(
                         doc,
                         title: "NEED MORE INDENTATION".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::NEED_MORE_INDENTATION),
                     }
                 }
                 None => {
@@ -2793,6 +2954,7 @@ fn to_tinparens_report<'a>(
                         doc,
                         title: "UNFINISHED PARENTHESES".to_string(),
                         severity: Severity::RuntimeError,
+                        code: Some(explain::UNFINISHED_PARENTHESES),
                     }
                 }
             }
@@ -2826,6 +2988,7 @@ fn to_tapply_report<'a>(
                 doc,
                 title: "DOUBLE DOT".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::DOUBLE_DOT),
             }
         }
         ETypeApply::TrailingDot(pos) => {
@@ -2848,6 +3011,7 @@ fn to_tapply_report<'a>(
                 doc,
                 title: "TRAILING DOT".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::TRAILING_DOT),
             }
         }
         ETypeApply::StartIsNumber(pos) => {
@@ -2870,6 +3034,7 @@ fn to_tapply_report<'a>(
                 doc,
                 title: "WEIRD QUALIFIED NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_QUALIFIED_NAME),
             }
         }
         ETypeApply::StartNotUppercase(pos) => {
@@ -2892,6 +3057,7 @@ fn to_tapply_report<'a>(
                 doc,
                 title: "WEIRD QUALIFIED NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_QUALIFIED_NAME),
             }
         }
 
@@ -2910,6 +3076,7 @@ fn to_tapply_report<'a>(
                 doc,
                 title: "END OF FILE".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::END_OF_FILE),
             }
         }
 
@@ -2950,6 +3117,7 @@ fn to_talias_report<'a>(
                 doc,
                 title: "NOT AN INLINE ALIAS".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::NOT_AN_INLINE_ALIAS),
             }
         }
         ETypeInlineAlias::Qualified(pos) => {
@@ -2966,6 +3134,7 @@ fn to_talias_report<'a>(
                 doc,
                 title: "QUALIFIED ALIAS NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::QUALIFIED_ALIAS_NAME),
             }
         }
         ETypeInlineAlias::ArgumentNotLowercase(pos) => {
@@ -2982,6 +3151,7 @@ fn to_talias_report<'a>(
                 doc,
                 title: "TYPE ARGUMENT NOT LOWERCASE".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::TYPE_ARGUMENT_NOT_LOWERCASE),
             }
         }
     }
@@ -3028,6 +3198,7 @@ fn to_header_report<'a>(
                 doc,
                 title: "INCOMPLETE HEADER".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::INCOMPLETE_HEADER),
             }
         }
 
@@ -3054,6 +3225,7 @@ fn to_header_report<'a>(
                 doc,
                 title: "MISSING HEADER".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::MISSING_HEADER),
             }
         }
 
@@ -3078,6 +3250,7 @@ fn to_header_report<'a>(
                 doc,
                 title: "WEIRD MODULE NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_MODULE_NAME),
             }
         }
 
@@ -3102,6 +3275,7 @@ fn to_header_report<'a>(
                 doc,
                 title: "WEIRD APP NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_APP_NAME),
             }
         }
 
@@ -3124,6 +3298,7 @@ fn to_header_report<'a>(
                 doc,
                 title: "WEIRD MODULE NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_MODULE_NAME),
             }
         }
 
@@ -3147,6 +3322,7 @@ fn to_header_report<'a>(
                 doc,
                 title: "WEIRD GENERATED TYPE NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_GENERATED_TYPE_NAME),
             }
         }
         EHeader::GeneratesWith(generates_with, pos) => {
@@ -3187,6 +3363,7 @@ fn to_generates_with_report<'a>(
                 doc,
                 title: "WEIRD GENERATES".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_GENERATES),
             }
         }
 
@@ -3212,6 +3389,7 @@ fn to_generates_with_report<'a>(
                 doc,
                 title: "WEIRD GENERATES".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_GENERATES),
             }
         }
 
@@ -3253,6 +3431,7 @@ fn to_provides_report<'a>(
                 doc,
                 title: "WEIRD PROVIDES".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_PROVIDES),
             }
         }
 
@@ -3278,6 +3457,7 @@ fn to_provides_report<'a>(
                 doc,
                 title: "WEIRD PROVIDES".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_PROVIDES),
             }
         }
 
@@ -3318,6 +3498,7 @@ fn to_exposes_report<'a>(
                 doc,
                 title: "WEIRD EXPOSES".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_EXPOSES),
             }
         }
 
@@ -3343,6 +3524,7 @@ fn to_exposes_report<'a>(
                 doc,
                 title: "WEIRD EXPOSES".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_EXPOSES),
             }
         }
 
@@ -3382,6 +3564,7 @@ fn to_imports_report<'a>(
                 doc,
                 title: "WEIRD IMPORTS".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_IMPORTS),
             }
         }
 
@@ -3407,6 +3590,7 @@ fn to_imports_report<'a>(
                 doc,
                 title: "WEIRD IMPORTS".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_IMPORTS),
             }
         }
 
@@ -3433,6 +3617,7 @@ fn to_imports_report<'a>(
                 doc,
                 title: "WEIRD MODULE NAME".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_MODULE_NAME),
             }
         }
 
@@ -3452,6 +3637,7 @@ fn to_imports_report<'a>(
                 doc,
                 title: "WEIRD IMPORTS".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::WEIRD_IMPORTS),
             }
         }
 
@@ -3491,6 +3677,7 @@ fn to_requires_report<'a>(
                 doc,
                 title: "MISSING REQUIRES".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::MISSING_REQUIRES),
             }
         }
 
@@ -3518,6 +3705,7 @@ fn to_requires_report<'a>(
                 doc,
                 title: "MISSING REQUIRES".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::MISSING_REQUIRES),
             }
         }
 
@@ -3547,6 +3735,7 @@ fn to_requires_report<'a>(
                 doc,
                 title: "BAD REQUIRES RIGIDS".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::BAD_REQUIRES_RIGIDS),
             }
         }
 
@@ -3576,6 +3765,7 @@ fn to_requires_report<'a>(
                 doc,
                 title: "BAD REQUIRES".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::BAD_REQUIRES),
             }
         }
 
@@ -3613,6 +3803,7 @@ fn to_packages_report<'a>(
                 doc,
                 title: "MISSING PACKAGES".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::MISSING_PACKAGES),
             }
         }
 
@@ -3646,6 +3837,7 @@ fn to_space_report<'a>(
                 doc,
                 title: "TAB CHARACTER".to_string(),
                 severity: Severity::RuntimeError,
+                code: Some(explain::TAB_CHARACTER),
             }
         }
 
@@ -3727,6 +3919,7 @@ fn to_unfinished_ability_report<'a>(
         doc,
         title: "UNFINISHED ABILITY".to_string(),
         severity: Severity::RuntimeError,
+        code: Some(explain::UNFINISHED_ABILITY),
     }
 }
 
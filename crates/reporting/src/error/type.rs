@@ -30,6 +30,22 @@ pub fn type_problem<'b>(
     lines: &LineInfo,
     filename: PathBuf,
     problem: TypeError,
+) -> Option<Report<'b>> {
+    let code = problem.code();
+    let report = to_type_problem_report(alloc, lines, filename, problem)?;
+
+    // The reports are built all over this file, so give them their code in one place.
+    Some(Report {
+        code: Some(code),
+        ..report
+    })
+}
+
+fn to_type_problem_report<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    lines: &LineInfo,
+    filename: PathBuf,
+    problem: TypeError,
 ) -> Option<Report<'b>> {
     use TypeError::*;
 
@@ -39,6 +55,7 @@ pub fn type_problem<'b>(
             filename,
             doc,
             severity: Severity::RuntimeError,
+            code: None,
         })
     }
 
@@ -155,6 +172,7 @@ pub fn type_problem<'b>(
                 filename,
                 doc: alloc.stack(stack),
                 severity: Severity::RuntimeError,
+                code: None,
             };
             Some(report)
         }
@@ -177,6 +195,7 @@ pub fn type_problem<'b>(
                 filename,
                 doc: alloc.stack(stack),
                 severity: Severity::RuntimeError,
+                code: None,
             };
             Some(report)
         }
@@ -191,6 +210,7 @@ pub fn type_problem<'b>(
                 filename,
                 doc,
                 severity,
+                code: None,
             })
         }
         StructuralSpecialization {
@@ -221,6 +241,7 @@ pub fn type_problem<'b>(
                 filename,
                 doc: alloc.stack(stack),
                 severity: Severity::RuntimeError,
+                code: None,
             })
         }
         WrongSpecialization {
@@ -250,6 +271,7 @@ pub fn type_problem<'b>(
                 filename,
                 doc: alloc.stack(stack),
                 severity: Severity::RuntimeError,
+                code: None,
             })
         }
     }
@@ -529,6 +551,7 @@ fn report_mismatch<'b>(
         filename,
         doc: alloc.stack(lines),
         severity: Severity::RuntimeError,
+        code: None,
     }
 }
 
@@ -572,6 +595,7 @@ fn report_bad_type<'b>(
         filename,
         doc: alloc.stack(lines),
         severity: Severity::RuntimeError,
+        code: None,
     }
 }
 
@@ -657,6 +681,7 @@ fn to_expr_report<'b>(
                     comparison,
                 ]),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         Expected::FromAnnotation(name, _arity, annotation_source, expected_type) => {
@@ -780,6 +805,7 @@ fn to_expr_report<'b>(
                     comparison,
                 ]),
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
         Expected::ForReason(reason, expected_type, region) => match reason {
@@ -1124,6 +1150,7 @@ fn to_expr_report<'b>(
                         title: "TOO MANY ARGS".to_string(),
                         doc: alloc.stack(lines),
                         severity: Severity::RuntimeError,
+                        code: None,
                     }
                 }
                 n => {
@@ -1159,6 +1186,7 @@ fn to_expr_report<'b>(
                             title: "TOO MANY ARGS".to_string(),
                             doc: alloc.stack(lines),
                             severity: Severity::RuntimeError,
+                            code: None,
                         }
                     } else {
                         let lines = vec![
@@ -1186,6 +1214,7 @@ fn to_expr_report<'b>(
                             title: "TOO FEW ARGS".to_string(),
                             doc: alloc.stack(lines),
                             severity: Severity::RuntimeError,
+                            code: None,
                         }
                     }
                 }
@@ -1371,6 +1400,7 @@ fn to_expr_report<'b>(
                     filename,
                     doc: alloc.stack(lines),
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
 
@@ -1408,6 +1438,7 @@ fn to_expr_report<'b>(
                     title: "TYPE MISMATCH".to_string(),
                     doc,
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
 
@@ -1664,6 +1695,19 @@ fn format_category<'b>(
             alloc.text(" of type:"),
         ),
 
+        Tuple => (
+            alloc.concat([this_is, alloc.text(" a tuple")]),
+            alloc.text(" of type:"),
+        ),
+
+        TupleAccess(index) => (
+            alloc.concat([
+                alloc.text(format!("{}he value at ", t)),
+                alloc.text(format!(".{}", index)),
+            ]),
+            alloc.text(" is a:"),
+        ),
+
         Accessor(field) => (
             alloc.concat([
                 alloc.text(format!("{}his ", t)),
@@ -1779,6 +1823,7 @@ fn to_pattern_report<'b>(
                 title: "TYPE MISMATCH".to_string(),
                 doc,
                 severity: Severity::RuntimeError,
+                code: None,
             }
         }
 
@@ -1822,6 +1867,7 @@ fn to_pattern_report<'b>(
                     title: "TYPE MISMATCH".to_string(),
                     doc,
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
             PReason::WhenMatch { index, sub_pattern } => {
@@ -1902,6 +1948,7 @@ fn to_pattern_report<'b>(
                     title: "TYPE MISMATCH".to_string(),
                     doc,
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
             PReason::TagArg { .. } | PReason::PatternGuard => {
@@ -1948,6 +1995,7 @@ fn add_pattern_category<'b>(
     let rest = match category {
         Record => alloc.reflow(" record values of type:"),
         EmptyRecord => alloc.reflow(" an empty record:"),
+        Tuple => alloc.reflow(" tuple values of type:"),
         PatternGuard => alloc.reflow(" a pattern guard of type:"),
         PatternDefault => alloc.reflow(" an optional field of type:"),
         Set => alloc.reflow(" sets of type:"),
@@ -2000,6 +2048,7 @@ fn to_circular_report<'b>(
             ])
         },
         severity: Severity::RuntimeError,
+        code: None,
     }
 }
 
@@ -2295,6 +2344,15 @@ fn to_doc_help<'b>(
             )
         }
 
+        Tuple(elems, ext) => report_text::tuple(
+            alloc,
+            elems
+                .into_iter()
+                .map(|elem| to_doc_help(ctx, alloc, Parens::Unnecessary, elem))
+                .collect(),
+            ext_to_doc(alloc, ext),
+        ),
+
         TagUnion(tags_map, ext) => {
             let mut tags = tags_map
                 .into_iter()
@@ -2590,6 +2648,22 @@ fn to_diff<'b>(
             diff_record(alloc, fields1, ext1, fields2, ext2)
         }
 
+        (Tuple(elems1, ext1), Tuple(elems2, ext2))
+            if elems1.len() == elems2.len() && ext1 == ext2 =>
+        {
+            let elems_diff = traverse(alloc, Parens::Unnecessary, elems1, elems2);
+            let left = report_text::tuple(alloc, elems_diff.left, ext_to_doc(alloc, ext1.clone()));
+            let right = report_text::tuple(alloc, elems_diff.right, ext_to_doc(alloc, ext2));
+
+            Diff {
+                left,
+                right,
+                status: elems_diff.status,
+                left_able: elems_diff.left_able,
+                right_able: elems_diff.right_able,
+            }
+        }
+
         (TagUnion(tags1, ext1), TagUnion(tags2, ext2)) => {
             diff_tag_union(alloc, &tags1, ext1, &tags2, ext2)
         }
@@ -3223,6 +3297,30 @@ mod report_text {
         }
     }
 
+    pub fn tuple<'b>(
+        alloc: &'b RocDocAllocator<'b>,
+        elems: Vec<RocDocBuilder<'b>>,
+        opt_ext: Option<RocDocBuilder<'b>>,
+    ) -> RocDocBuilder<'b> {
+        let ext_doc = if let Some(t) = opt_ext {
+            t
+        } else {
+            alloc.nil()
+        };
+
+        if elems.is_empty() {
+            alloc.text("()").append(ext_doc)
+        } else {
+            alloc
+                .concat([
+                    alloc.reflow("( "),
+                    alloc.intersperse(elems, alloc.reflow(", ")),
+                    alloc.reflow(" )"),
+                ])
+                .append(ext_doc)
+        }
+    }
+
     pub fn to_suggestion_record<'b>(
         alloc: &'b RocDocAllocator<'b>,
         f: (Lowercase, RecordField<ErrorType>),
@@ -3568,6 +3666,7 @@ fn type_problem_to_pretty<'b>(
                 RigidVar(y) | RigidAbleVar(y, _) => bad_double_rigid(x, y),
                 Function(_, _, _) => bad_rigid_var(x, alloc.reflow("a function value")),
                 Record(_, _) => bad_rigid_var(x, alloc.reflow("a record value")),
+                Tuple(_, _) => bad_rigid_var(x, alloc.reflow("a tuple value")),
                 TagUnion(_, _) | RecursiveTagUnion(_, _, _) => {
                     bad_rigid_var(x, alloc.reflow("a tag value"))
                 }
@@ -3773,6 +3872,7 @@ fn report_record_field_typo<'b>(
         title: "TYPE MISMATCH".to_string(),
         doc,
         severity: Severity::RuntimeError,
+        code: None,
     }
 }
 
@@ -3808,6 +3908,7 @@ fn exhaustive_problem<'a>(
                     title: "UNSAFE PATTERN".to_string(),
                     doc,
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
             BadDestruct => {
@@ -3832,6 +3933,7 @@ fn exhaustive_problem<'a>(
                     title: "UNSAFE PATTERN".to_string(),
                     doc,
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
             BadCase => {
@@ -3856,6 +3958,7 @@ fn exhaustive_problem<'a>(
                     title: "UNSAFE PATTERN".to_string(),
                     doc,
                     severity: Severity::RuntimeError,
+                    code: None,
                 }
             }
         },
@@ -3885,6 +3988,7 @@ fn exhaustive_problem<'a>(
                 title: "REDUNDANT PATTERN".to_string(),
                 doc,
                 severity: Severity::Warning,
+                code: None,
             }
         }
     }
@@ -3976,6 +4080,16 @@ fn pattern_to_doc_help<'b>(
                         .append(alloc.intersperse(arg_docs, alloc.reflow(", ")))
                         .append(" }")
                 }
+                RenderAs::Tuple => {
+                    let arg_docs = args
+                        .into_iter()
+                        .map(|v| pattern_to_doc_help(alloc, v, false));
+
+                    alloc
+                        .text("( ")
+                        .append(alloc.intersperse(arg_docs, alloc.reflow(", ")))
+                        .append(" )")
+                }
                 RenderAs::Tag | RenderAs::Opaque => {
                     let ctor = &union.alternatives[tag_id.0 as usize];
                     match &ctor.name {
@@ -0,0 +1,218 @@
+//! Stable codes for every kind of report, and the extended explanations `roc explain` prints.
+//!
+//! Problems found after parsing get their code from the kind of problem, see
+//! `roc_problem::can::Problem::code` and `roc_solve_problem::TypeError::code`. Reports with the
+//! same title can come from different kinds of problem, so they can have different codes.
+//! Parse and load problems have one code per report title instead, since the same syntax error
+//! is reported differently depending on the code around it. The constants below are those codes.
+//!
+//! Codes are never reused: if a kind of report goes away, so does its code, but the code
+//! isn't given to a new kind of report.
+//!
+//! Codes are grouped by the phase which reports them:
+//!
+//! - E01xx: canonicalization, e.g. naming problems and unused definitions
+//! - E02xx: canonicalization problems which only crash the program if the code they are in runs
+//! - E03xx: type checking, including exhaustiveness checking
+//! - E04xx: parsing
+//! - E05xx: loading files and modules
+//!
+//! The explanations live in `explanations/<code>.md`, next to this crate's `src`.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorCode {
+    /// e.g. "E0301"
+    pub code: &'static str,
+    /// What the reports with this code are about, e.g. "Type mismatch in an expression"
+    pub summary: &'static str,
+    /// Markdown, with an example of code which causes the report and how to fix it
+    pub explanation: &'static str,
+}
+
+macro_rules! error_codes {
+    ($($($name:ident =)? $code:literal => $summary:literal,)*) => {
+        $($(
+            pub const $name: &str = $code;
+        )?)*
+
+        /// Every error code, in order.
+        pub const ERROR_CODES: &[ErrorCode] = &[
+            $(
+                ErrorCode {
+                    code: $code,
+                    summary: $summary,
+                    explanation: include_str!(concat!("../explanations/", $code, ".md")),
+                },
+            )*
+        ];
+    };
+}
+
+error_codes! {
+    // roc_problem::can::Problem
+    "E0101" => "Unused definition",
+    "E0102" => "Unused import",
+    "E0103" => "Exposed value is never defined",
+    "E0104" => "Unknown generated function",
+    "E0105" => "Unused argument",
+    "E0106" => "Unused name in a `when` branch",
+    "E0107" => "Operators need parentheses",
+    "E0108" => "Pattern isn't allowed here",
+    "E0109" => "Type, ability or member name defined twice",
+    "E0110" => "Type alias refers to itself",
+    "E0111" => "Circular definition",
+    "E0112" => "Unused type alias parameter",
+    "E0113" => "Unbound type variable",
+    "E0114" => "Duplicate field in a record",
+    "E0115" => "Duplicate field in a record type",
+    "E0116" => "Optional field outside a record pattern",
+    "E0117" => "Duplicate tag in a tag union type",
+    "E0118" => "Annotation doesn't match the definition after it",
+    "E0119" => "Type alias parameter isn't a type variable",
+    "E0120" => "Invalid string interpolation",
+    "E0121" => "Unicode escape isn't hexadecimal",
+    "E0122" => "Invalid unicode code point",
+    "E0123" => "Nested datatype",
+    "E0124" => "Invalid extension type",
+    "E0125" => "Ability has type variables",
+    "E0126" => "`has` clause names something that isn't an ability",
+    "E0127" => "`has` clause where it isn't allowed",
+    "E0128" => "Ability member without a `has` clause",
+    "E0129" => "Ability member binds several type variables",
+    "E0130" => "Ability defined inside another definition",
+    "E0131" => "Ability used as a type",
+    "E0132" => "Ability member implemented inside another definition",
+    "E0133" => "Ability can't be derived",
+    "E0134" => "Ability implementation not found",
+    "E0135" => "Implementation of something that isn't an ability member",
+    "E0136" => "Optional ability implementation",
+    "E0137" => "Qualified ability implementation",
+    "E0138" => "Ability implementation isn't a name",
+    "E0139" => "Ability member implemented twice",
+    "E0140" => "Opaque type implements something that isn't an ability",
+    "E0141" => "Unnecessary ability implementations",
+    "E0142" => "Incomplete ability implementation",
+    "E0143" => "Name not bound in all patterns",
+    "E0144" => "Definition doesn't bind any names",
+    "E0145" => "One implementation for several opaque types",
+    // roc_problem::can::RuntimeError
+    "E0201" => "Value name defined twice",
+    "E0202" => "Optional field in a record expression",
+    "E0203" => "Unsupported pattern",
+    "E0204" => "Malformed pattern",
+    "E0205" => "Unresolved type variable",
+    "E0206" => "Erroneous type",
+    "E0207" => "Unrecognized name",
+    "E0208" => "Opaque type not defined",
+    "E0209" => "Opaque type used outside its module",
+    "E0210" => "Opaque type not applied",
+    "E0211" => "Opaque type applied to several values",
+    "E0212" => "Value not exposed",
+    "E0213" => "Module not imported",
+    "E0214" => "Invalid operator precedence",
+    "E0215" => "Malformed identifier",
+    "E0216" => "Malformed type name",
+    "E0217" => "Malformed function",
+    "E0218" => "Record update of something other than a variable",
+    "E0219" => "Invalid float literal",
+    "E0220" => "Invalid integer literal",
+    "E0221" => "Circular definition in an expression",
+    "E0222" => "Non-exhaustive pattern",
+    "E0223" => "Invalid string interpolation in an expression",
+    "E0224" => "Unicode escape in an expression isn't hexadecimal",
+    "E0225" => "Invalid unicode code point in an expression",
+    "E0226" => "Named ability implementation doesn't exist",
+    "E0227" => "Missing ability implementation",
+    "E0228" => "Value that can never exist",
+    "E0229" => "Exposed value is never defined",
+    "E0230" => "Empty character literal",
+    "E0231" => "Character literal with several code points",
+    "E0232" => "Degenerate branch",
+    // roc_solve_problem::TypeError
+    "E0301" => "Type mismatch in an expression",
+    "E0302" => "Type mismatch in a pattern",
+    "E0303" => "Circular type",
+    "E0304" => "Circular definition found while type checking",
+    "E0305" => "Problem with a type",
+    "E0306" => "Name isn't exposed",
+    "E0307" => "Type doesn't implement an ability",
+    "E0308" => "Expression's type doesn't implement a needed ability",
+    "E0309" => "Pattern's type doesn't implement a needed ability",
+    "E0310" => "Patterns don't cover every value",
+    "E0311" => "Redundant pattern",
+    "E0312" => "Ability implemented for a structural type",
+    "E0313" => "Ability implementation for the wrong type",
+    // Parsing, by the title of the report
+    ARGUMENTS_BEFORE_EQUALS = "E0401" => "Arguments before equals",
+    BAD_BACKPASSING_ARROW = "E0402" => "Bad backpassing arrow",
+    BAD_REQUIRES = "E0403" => "Bad requires",
+    BAD_REQUIRES_RIGIDS = "E0404" => "Bad requires rigids",
+    BAD_TYPE_VARIABLE = "E0405" => "Bad type variable",
+    DOUBLE_COMMA = "E0406" => "Double comma",
+    DOUBLE_DOT = "E0407" => "Double dot",
+    EMPTY_PARENTHESES = "E0408" => "Empty parentheses",
+    END_OF_FILE = "E0409" => "End of file",
+    ENDLESS_FORMAT = "E0410" => "Endless format",
+    ENDLESS_STRING = "E0411" => "Endless string",
+    IF_GUARD_NO_CONDITION = "E0412" => "If guard no condition",
+    INCOMPLETE_HEADER = "E0413" => "Incomplete header",
+    INSUFFICIENT_INDENT_IN_MULTI_LINE_STRING = "E0414" => "Insufficient indent in multi-line string",
+    INVALID_NUMBER_LITERAL = "E0415" => "Invalid number literal",
+    MISSING_ARROW = "E0416" => "Missing arrow",
+    MISSING_EXPRESSION = "E0417" => "Missing expression",
+    MISSING_FINAL_EXPRESSION = "E0418" => "Missing final expression",
+    MISSING_HEADER = "E0419" => "Missing header",
+    MISSING_PACKAGES = "E0420" => "Missing packages",
+    MISSING_REQUIRES = "E0421" => "Missing requires",
+    NEED_MORE_INDENTATION = "E0422" => "Need more indentation",
+    NOT_AN_INLINE_ALIAS = "E0423" => "Not an inline alias",
+    NOT_END_OF_FILE = "E0424" => "Not end of file",
+    PARSE_PROBLEM = "E0425" => "Parse problem",
+    PROBLEM_IN_RECORD_PATTERN = "E0426" => "Problem in record pattern",
+    PROBLEM_IN_RECORD_TYPE = "E0427" => "Problem in record type",
+    QUALIFIED_ALIAS_NAME = "E0428" => "Qualified alias name",
+    RECORD_PARSE_PROBLEM = "E0429" => "Record parse problem",
+    TAB_CHARACTER = "E0430" => "Tab character",
+    TRAILING_DOT = "E0431" => "Trailing dot",
+    TYPE_ARGUMENT_NOT_LOWERCASE = "E0432" => "Type argument not lowercase",
+    UNEXPECTED_ARROW = "E0433" => "Unexpected arrow",
+    UNFINISHED_ABILITY = "E0434" => "Unfinished ability",
+    UNFINISHED_ARGUMENT_LIST = "E0435" => "Unfinished argument list",
+    UNFINISHED_FUNCTION = "E0436" => "Unfinished function",
+    UNFINISHED_IF = "E0437" => "Unfinished if",
+    UNFINISHED_INLINE_ALIAS = "E0438" => "Unfinished inline alias",
+    UNFINISHED_LIST = "E0439" => "Unfinished list",
+    UNFINISHED_PARENTHESES = "E0440" => "Unfinished parentheses",
+    UNFINISHED_PATTERN = "E0441" => "Unfinished pattern",
+    UNFINISHED_RECORD_PATTERN = "E0442" => "Unfinished record pattern",
+    UNFINISHED_RECORD_TYPE = "E0443" => "Unfinished record type",
+    UNFINISHED_TAG_UNION_TYPE = "E0444" => "Unfinished tag union type",
+    UNFINISHED_TYPE = "E0445" => "Unfinished type",
+    UNFINISHED_WHEN = "E0446" => "Unfinished when",
+    UNKNOWN_OPERATOR = "E0447" => "Unknown operator",
+    WEIRD_APP_NAME = "E0448" => "Weird app name",
+    WEIRD_ARROW = "E0449" => "Weird arrow",
+    WEIRD_CODE_POINT = "E0450" => "Weird code point",
+    WEIRD_ESCAPE = "E0451" => "Weird escape",
+    WEIRD_EXPOSES = "E0452" => "Weird exposes",
+    WEIRD_GENERATED_TYPE_NAME = "E0453" => "Weird generated type name",
+    WEIRD_GENERATES = "E0454" => "Weird generates",
+    WEIRD_IDENTIFIER = "E0455" => "Weird identifier",
+    WEIRD_IMPORTS = "E0456" => "Weird imports",
+    WEIRD_MODULE_NAME = "E0457" => "Weird module name",
+    WEIRD_PROVIDES = "E0458" => "Weird provides",
+    WEIRD_QUALIFIED_NAME = "E0459" => "Weird qualified name",
+    WEIRD_TAG_NAME = "E0460" => "Weird tag name",
+    // Loading, by the title of the report
+    FILE_NOT_FOUND = "E0501" => "File not found",
+    FILE_PERMISSION_DENIED = "E0502" => "File permission denied",
+    FILE_PROBLEM = "E0503" => "File problem",
+    NO_PLATFORM = "E0504" => "No platform",
+}
+
+/// Looks up a code like "E0301", ignoring case.
+pub fn lookup(code: &str) -> Option<&'static ErrorCode> {
+    ERROR_CODES
+        .iter()
+        .find(|error_code| error_code.code.eq_ignore_ascii_case(code))
+}
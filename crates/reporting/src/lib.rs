@@ -3,4 +3,6 @@
 #![allow(clippy::large_enum_variant)]
 
 pub mod error;
+pub mod explain;
 pub mod report;
+pub mod structured;
@@ -2,6 +2,7 @@ use roc_module::ident::Ident;
 use roc_module::ident::{Lowercase, ModuleName, TagName, Uppercase};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_region::all::LineColumnRegion;
+use std::cell::RefCell;
 use std::fmt;
 use std::path::{Path, PathBuf};
 use ven_pretty::{BoxAllocator, DocAllocator, DocBuilder, Render, RenderAnnotated};
@@ -118,11 +119,15 @@ pub enum RenderTarget {
 }
 
 /// A textual report.
+#[derive(Clone)]
 pub struct Report<'b> {
     pub title: String,
     pub filename: PathBuf,
     pub doc: RocDocBuilder<'b>,
     pub severity: Severity,
+    /// e.g. "E0301", see [crate::explain]. Reports about failed expectations are test results
+    /// rather than problems with the code, so they have none.
+    pub code: Option<&'static str>,
 }
 
 impl<'b> Report<'b> {
@@ -140,7 +145,7 @@ impl<'b> Report<'b> {
     }
 
     /// Render to CI console output, where no colors are available.
-    pub fn render_ci(self, buf: &'b mut String, alloc: &'b RocDocAllocator<'b>) {
+    pub fn render_ci(self, buf: &mut String, alloc: &'b RocDocAllocator<'b>) {
         let err_msg = "<buffer is not a utf-8 encoded string>";
 
         self.pretty(alloc)
@@ -168,10 +173,16 @@ impl<'b> Report<'b> {
         if self.title.is_empty() {
             self.doc
         } else {
+            // Show the code next to the title, e.g. "TYPE MISMATCH [E0301]"
+            let title = match self.code {
+                Some(code) => format!("{} [{}]", self.title, code),
+                None => self.title,
+            };
+
             let header = if self.filename == PathBuf::from("") {
-                crate::report::pretty_header(&self.title)
+                crate::report::pretty_header(&title)
             } else {
-                crate::report::pretty_header_with_path(&self.title, &self.filename)
+                crate::report::pretty_header_with_path(&title, &self.filename)
             };
 
             alloc.stack([alloc.text(header).annotate(Annotation::Header), self.doc])
@@ -297,6 +308,9 @@ pub struct RocDocAllocator<'a> {
     pub src_lines: &'a [&'a str],
     pub home: ModuleId,
     pub interns: &'a Interns,
+    /// The regions highlighted in code snippets built since the last call to
+    /// [RocDocAllocator::take_highlighted_regions], in the order they were built.
+    highlighted_regions: RefCell<Vec<LineColumnRegion>>,
 }
 
 pub type RocDocBuilder<'b> = DocBuilder<'b, RocDocAllocator<'b>, Annotation>;
@@ -333,9 +347,28 @@ impl<'a> RocDocAllocator<'a> {
             home,
             src_lines,
             interns,
+            highlighted_regions: RefCell::new(Vec::new()),
         }
     }
 
+    /// The regions highlighted by the reports built since the last call, without duplicates.
+    /// Building a report and then calling this gives you the regions that report points at.
+    pub fn take_highlighted_regions(&self) -> Vec<LineColumnRegion> {
+        let mut regions = self.highlighted_regions.take();
+        let mut seen = Vec::with_capacity(regions.len());
+
+        regions.retain(|region| {
+            if seen.contains(region) {
+                false
+            } else {
+                seen.push(*region);
+                true
+            }
+        });
+
+        regions
+    }
+
     /// vertical concatenation. Adds a newline between elements
     pub fn vcat<A, I>(&'a self, docs: I) -> DocBuilder<'a, Self, A>
     where
@@ -523,6 +556,10 @@ impl<'a> RocDocAllocator<'a> {
         debug_assert!(region.contains(&sub_region1));
         debug_assert!(region.contains(&sub_region2));
 
+        self.highlighted_regions
+            .borrow_mut()
+            .extend([sub_region1, sub_region2]);
+
         // if true, the final line of the snippet will be some ^^^ that point to the region where
         // the problem is. Otherwise, the snippet will have a > on the lines that are in the region
         // where the problem is.
@@ -641,6 +678,8 @@ impl<'a> RocDocAllocator<'a> {
             }
         }
 
+        self.highlighted_regions.borrow_mut().push(sub_region);
+
         // if true, the final line of the snippet will be some ^^^ that point to the region where
         // the problem is. Otherwise, the snippet will have a > on the lines that are in the region
         // where the problem is.
@@ -0,0 +1,219 @@
+//! Machine-readable diagnostics, for code review bots and editor integrations.
+//!
+//! These carry the same information as the prose reports, but with the regions they point at
+//! and a stable code for the kind of problem, so tools don't need to parse the prose.
+
+use crate::report::{can_problem, type_problem, Report, RocDocAllocator, Severity};
+use roc_problem::can::Problem;
+use roc_region::all::{LineColumnRegion, LineInfo};
+use roc_solve_problem::TypeError;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat {
+    Json,
+    /// The Static Analysis Results Interchange Format, version 2.1.0
+    Sarif,
+}
+
+impl FromStr for ErrorFormat {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "json" => Ok(ErrorFormat::Json),
+            "sarif" => Ok(ErrorFormat::Sarif),
+            _ => Err(format!("Unsupported error format: {string}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub title: String,
+    /// e.g. "E0101", see [crate::explain]
+    pub code: Option<&'static str>,
+    pub filename: PathBuf,
+    /// Every region the report highlights, in the order it shows them
+    pub regions: Vec<LineColumnRegion>,
+    /// The report without its header, rendered as plain text
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// The report must be the last one built with `alloc`, since the regions it highlights
+    /// are the ones `alloc` recorded while building it.
+    pub fn from_report<'b>(mut report: Report<'b>, alloc: &'b RocDocAllocator<'b>) -> Self {
+        let regions = alloc.take_highlighted_regions();
+        let title = std::mem::take(&mut report.title);
+        let code = report.code;
+        let filename = report.filename.clone();
+        let severity = report.severity;
+
+        let mut message = String::new();
+        report.render_ci(&mut message, alloc);
+
+        Diagnostic {
+            severity,
+            title,
+            code,
+            filename,
+            regions,
+            message: message.trim().to_string(),
+        }
+    }
+}
+
+pub fn can_diagnostic<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    lines: &LineInfo,
+    filename: PathBuf,
+    problem: Problem,
+) -> Diagnostic {
+    // Drop anything recorded by reports built before this one.
+    alloc.take_highlighted_regions();
+    let report = can_problem(alloc, lines, filename, problem);
+
+    Diagnostic::from_report(report, alloc)
+}
+
+pub fn type_diagnostic<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    lines: &LineInfo,
+    filename: PathBuf,
+    problem: TypeError,
+) -> Option<Diagnostic> {
+    alloc.take_highlighted_regions();
+    let report = type_problem(alloc, lines, filename, problem)?;
+
+    Some(Diagnostic::from_report(report, alloc))
+}
+
+pub fn render(format: ErrorFormat, diagnostics: &[Diagnostic]) -> String {
+    let value = match format {
+        ErrorFormat::Json => json_value(diagnostics),
+        ErrorFormat::Sarif => sarif_value(diagnostics),
+    };
+
+    let mut buf = serde_json::to_string_pretty(&value).unwrap();
+    buf.push('\n');
+
+    buf
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::RuntimeError => "error",
+        Severity::Warning => "warning",
+    }
+}
+
+fn json_value(diagnostics: &[Diagnostic]) -> Value {
+    let diagnostics: Vec<Value> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            // Lines and columns are 1-based, like in editors.
+            let regions: Vec<Value> = diagnostic
+                .regions
+                .iter()
+                .map(|region| {
+                    json!({
+                        "start_line": region.start.line + 1,
+                        "start_column": region.start.column + 1,
+                        "end_line": region.end.line + 1,
+                        "end_column": region.end.column + 1,
+                    })
+                })
+                .collect();
+
+            json!({
+                "severity": severity_name(diagnostic.severity),
+                "code": diagnostic.code,
+                "title": diagnostic.title,
+                "file": diagnostic.filename.to_string_lossy(),
+                "regions": regions,
+                "message": diagnostic.message,
+            })
+        })
+        .collect();
+
+    json!({ "diagnostics": diagnostics })
+}
+
+fn sarif_value(diagnostics: &[Diagnostic]) -> Value {
+    // Each code is a rule, which results refer to by its index.
+    let mut rules: Vec<(&'static str, &str)> = Vec::new();
+
+    let results: Vec<Value> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let uri = diagnostic.filename.to_string_lossy();
+            let locations: Vec<Value> = diagnostic
+                .regions
+                .iter()
+                .map(|region| {
+                    json!({
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": uri },
+                            "region": {
+                                "startLine": region.start.line + 1,
+                                "startColumn": region.start.column + 1,
+                                "endLine": region.end.line + 1,
+                                "endColumn": region.end.column + 1,
+                            }
+                        }
+                    })
+                })
+                .collect();
+
+            let mut result = json!({
+                "level": severity_name(diagnostic.severity),
+                "message": { "text": diagnostic.message },
+                "locations": locations,
+            });
+
+            if let Some(code) = diagnostic.code {
+                let rule_index = match rules.iter().position(|(rule, _)| *rule == code) {
+                    Some(index) => index,
+                    None => {
+                        rules.push((code, &diagnostic.title));
+                        rules.len() - 1
+                    }
+                };
+
+                result["ruleId"] = json!(code);
+                result["ruleIndex"] = json!(rule_index);
+            }
+
+            result
+        })
+        .collect();
+
+    let rules: Vec<Value> = rules
+        .into_iter()
+        .map(|(code, title)| {
+            json!({
+                "id": code,
+                "shortDescription": { "text": title },
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "roc",
+                    "informationUri": "https://www.roc-lang.org",
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
@@ -0,0 +1,645 @@
+extern crate bumpalo;
+extern crate indoc;
+extern crate roc_reporting;
+
+#[cfg(test)]
+mod test_explain {
+    use bumpalo::Bump;
+    use indoc::indoc;
+    use roc_can::expected::{Expected, PExpected};
+    use roc_collections::all::{HumanIndex, MutSet};
+    use roc_exhaustive as exhaustive;
+    use roc_load::{
+        self, ExecutionMode, LoadConfig, LoadedModule, OptLevel, PropertyTesting, Threading,
+    };
+    use roc_module::called_via::BinOp;
+    use roc_module::ident::{Ident, Lowercase, ModuleName, TagName};
+    use roc_module::symbol::{Interns, ModuleId, Symbol};
+    use roc_parse::ast::Base;
+    use roc_parse::ident::BadIdent;
+    use roc_parse::pattern::PatternType;
+    use roc_problem::can::{
+        BadPattern, CycleEntry, ExtensionTypeKind, FloatErrorKind, IntErrorKind,
+        MalformedPatternProblem, PrecedenceProblem, Problem, RuntimeError, ShadowKind,
+    };
+    use roc_region::all::{LineInfo, Loc, Position, Region};
+    use roc_reporting::explain::{lookup, ERROR_CODES};
+    use roc_reporting::report::{can_problem, type_problem, Report, RocDocAllocator};
+    use roc_solve_problem::{TypeError, Unfulfilled};
+    use roc_types::types::{AliasKind, Category, ErrorType, PatternCategory};
+    use std::fs::File;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    const SOURCE: &str = indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        main = 1
+        "#
+    );
+
+    /// Loads a tiny module, for the interns that reports look up symbol and module names in.
+    fn load() -> (ModuleId, Interns) {
+        let arena = Bump::new();
+
+        let dir = roc_test_utils::TmpDir::new("tmp/explain");
+        let file_path = dir.path().join("Test.roc");
+        let mut file = File::create(&file_path).unwrap();
+        writeln!(file, "{}", SOURCE).unwrap();
+
+        let load_config = LoadConfig {
+            target_info: roc_target::TargetInfo::default_x86_64(),
+            render: roc_reporting::report::RenderTarget::Generic,
+            threading: Threading::Single,
+            exec_mode: ExecutionMode::Check,
+            property_testing: PropertyTesting::default(),
+            opt_level: OptLevel::Normal,
+        };
+        let LoadedModule {
+            module_id, interns, ..
+        } = roc_load::load_and_typecheck(&arena, file_path, Default::default(), load_config)
+            .unwrap();
+
+        (module_id, interns)
+    }
+
+    fn loc_ident(name: &str) -> Loc<Ident> {
+        Loc::at_zero(Ident::from(name))
+    }
+
+    fn cycle() -> Vec<CycleEntry> {
+        vec![CycleEntry {
+            symbol: Symbol::LIST_MAP,
+            symbol_region: Region::zero(),
+            expr_region: Region::zero(),
+        }]
+    }
+
+    /// Makes sure every canonicalization problem has an example below, since adding a variant
+    /// here won't compile until it is handled.
+    fn has_example(problem: &Problem) {
+        use Problem::*;
+
+        match problem {
+            UnusedDef(..)
+            | UnusedImport(..)
+            | ExposedButNotDefined(..)
+            | UnknownGeneratesWith(..)
+            | UnusedArgument(..)
+            | UnusedBranchDef(..)
+            | PrecedenceProblem(..)
+            | UnsupportedPattern(..)
+            | Shadowing { .. }
+            | CyclicAlias(..)
+            | BadRecursion(..)
+            | PhantomTypeArgument { .. }
+            | UnboundTypeVariable { .. }
+            | DuplicateRecordFieldValue { .. }
+            | DuplicateRecordFieldType { .. }
+            | InvalidOptionalValue { .. }
+            | DuplicateTag { .. }
+            | RuntimeError(..)
+            | SignatureDefMismatch { .. }
+            | InvalidAliasRigid { .. }
+            | InvalidInterpolation(..)
+            | InvalidHexadecimal(..)
+            | InvalidUnicodeCodePt(..)
+            | NestedDatatype { .. }
+            | InvalidExtensionType { .. }
+            | AbilityHasTypeVariables { .. }
+            | HasClauseIsNotAbility { .. }
+            | IllegalHasClause { .. }
+            | AbilityMemberMissingHasClause { .. }
+            | AbilityMemberMultipleBoundVars { .. }
+            | AbilityNotOnToplevel { .. }
+            | AbilityUsedAsType(..)
+            | NestedSpecialization(..)
+            | IllegalDerivedAbility(..)
+            | ImplementationNotFound { .. }
+            | NotAnAbilityMember { .. }
+            | OptionalAbilityImpl { .. }
+            | QualifiedAbilityImpl { .. }
+            | AbilityImplNotIdent { .. }
+            | DuplicateImpl { .. }
+            | NotAnAbility(..)
+            | ImplementsNonRequired { .. }
+            | DoesNotImplementAbility { .. }
+            | NotBoundInAllPatterns { .. }
+            | NoIdentifiersIntroduced(..)
+            | OverloadedSpecialization { .. } => {}
+        }
+    }
+
+    /// Some runtime errors are only made after canonicalization, or aren't reported yet,
+    /// so they never get a report. They still have a code, for tools which look at problems
+    /// directly.
+    fn is_reported(error: &RuntimeError) -> bool {
+        use RuntimeError::*;
+
+        match error {
+            Shadowing { .. }
+            | InvalidOptionalValue { .. }
+            | MalformedPattern(..)
+            | LookupNotInScope(..)
+            | OpaqueNotDefined { .. }
+            | OpaqueOutsideScope { .. }
+            | OpaqueNotApplied(..)
+            | OpaqueAppliedToMultipleArgs(..)
+            | ValueNotExposed { .. }
+            | ModuleNotImported { .. }
+            | MalformedIdentifier(..)
+            | MalformedTypeName(..)
+            | InvalidRecordUpdate { .. }
+            | InvalidFloat(..)
+            | InvalidInt(..)
+            | CircularDef(..)
+            | ExposedButNotDefined(..)
+            | EmptySingleQuote(..)
+            | MultipleCharsInSingleQuote(..)
+            | DegenerateBranch(..) => true,
+            // Precedence problems are reported as `Problem::PrecedenceProblem` instead.
+            InvalidPrecedence(..)
+            | UnsupportedPattern(..)
+            | UnresolvedTypeVar
+            | ErroneousType
+            | MalformedClosure(..)
+            | NonExhaustivePattern
+            | InvalidInterpolation(..)
+            | InvalidHexadecimal(..)
+            | InvalidUnicodeCodePt(..)
+            | NoImplementationNamed { .. }
+            | NoImplementation
+            | VoidValue => false,
+        }
+    }
+
+    /// Makes sure every type error has an example below.
+    fn has_type_example(error: &TypeError) {
+        use TypeError::*;
+
+        match error {
+            BadExpr(..)
+            | BadPattern(..)
+            | CircularType(..)
+            | CircularDef(..)
+            | BadType(..)
+            | UnexposedLookup(..)
+            | UnfulfilledAbility(..)
+            | BadExprMissingAbility(..)
+            | BadPatternMissingAbility(..)
+            | Exhaustive(..)
+            | StructuralSpecialization { .. }
+            | WrongSpecialization { .. } => {}
+        }
+    }
+
+    fn runtime_errors() -> Vec<RuntimeError> {
+        let region = Region::zero();
+        let raw = || Box::from("1");
+
+        let mut errors = vec![
+            RuntimeError::Shadowing {
+                original_region: region,
+                shadow: loc_ident("x"),
+                kind: ShadowKind::Variable,
+            },
+            RuntimeError::InvalidOptionalValue {
+                field_name: Lowercase::from("x"),
+                record_region: region,
+                field_region: region,
+            },
+            RuntimeError::UnsupportedPattern(region),
+            RuntimeError::UnresolvedTypeVar,
+            RuntimeError::ErroneousType,
+            RuntimeError::LookupNotInScope(loc_ident("x"), MutSet::default()),
+            RuntimeError::OpaqueNotDefined {
+                usage: loc_ident("Age"),
+                opaques_in_scope: MutSet::default(),
+                opt_defined_alias: None,
+            },
+            RuntimeError::OpaqueOutsideScope {
+                opaque: Ident::from("Age"),
+                referenced_region: region,
+                imported_region: region,
+            },
+            RuntimeError::OpaqueNotApplied(loc_ident("Age")),
+            RuntimeError::OpaqueAppliedToMultipleArgs(region),
+            RuntimeError::ValueNotExposed {
+                module_name: ModuleName::from("List"),
+                ident: Ident::from("frobnicate"),
+                region,
+                exposed_values: Vec::new(),
+            },
+            RuntimeError::ModuleNotImported {
+                module_name: ModuleName::from("Json"),
+                imported_modules: MutSet::default(),
+                region,
+                module_exists: false,
+            },
+            RuntimeError::MalformedIdentifier(
+                Box::from("x"),
+                BadIdent::StrayDot(Position::zero()),
+                region,
+            ),
+            RuntimeError::InvalidPrecedence(
+                PrecedenceProblem::BothNonAssociative(
+                    region,
+                    Loc::at_zero(BinOp::Equals),
+                    Loc::at_zero(BinOp::Equals),
+                ),
+                region,
+            ),
+            RuntimeError::MalformedTypeName(Box::from("x"), region),
+            RuntimeError::MalformedClosure(region),
+            RuntimeError::InvalidRecordUpdate { region },
+            RuntimeError::CircularDef(cycle()),
+            RuntimeError::NonExhaustivePattern,
+            RuntimeError::InvalidInterpolation(region),
+            RuntimeError::InvalidHexadecimal(region),
+            RuntimeError::InvalidUnicodeCodePt(region),
+            RuntimeError::NoImplementationNamed {
+                def_symbol: Symbol::LIST_MAP,
+            },
+            RuntimeError::NoImplementation,
+            RuntimeError::VoidValue,
+            RuntimeError::ExposedButNotDefined(Symbol::LIST_MAP),
+            RuntimeError::EmptySingleQuote(region),
+            RuntimeError::MultipleCharsInSingleQuote(region),
+            RuntimeError::DegenerateBranch(region),
+        ];
+
+        for problem in [
+            MalformedPatternProblem::MalformedInt,
+            MalformedPatternProblem::MalformedFloat,
+            MalformedPatternProblem::MalformedBase(Base::Hex),
+            MalformedPatternProblem::Unknown,
+            MalformedPatternProblem::QualifiedIdentifier,
+            MalformedPatternProblem::BadIdent(BadIdent::StrayDot(Position::zero())),
+            MalformedPatternProblem::EmptySingleQuote,
+            MalformedPatternProblem::MultipleCharsInSingleQuote,
+        ] {
+            errors.push(RuntimeError::MalformedPattern(problem, region));
+        }
+
+        for kind in [
+            FloatErrorKind::Error,
+            FloatErrorKind::NegativeInfinity,
+            FloatErrorKind::PositiveInfinity,
+            FloatErrorKind::IntSuffix,
+        ] {
+            errors.push(RuntimeError::InvalidFloat(kind, region, raw()));
+        }
+
+        for kind in [
+            IntErrorKind::Empty,
+            IntErrorKind::InvalidDigit,
+            IntErrorKind::Overflow,
+            IntErrorKind::Underflow,
+            IntErrorKind::FloatSuffix,
+            IntErrorKind::OverflowsSuffix {
+                suffix_type: "U8",
+                max_value: 255,
+            },
+            IntErrorKind::UnderflowsSuffix {
+                suffix_type: "U8",
+                min_value: 0,
+            },
+        ] {
+            errors.push(RuntimeError::InvalidInt(kind, Base::Decimal, region, raw()));
+        }
+
+        errors
+    }
+
+    fn problems() -> Vec<Problem> {
+        let region = Region::zero();
+        let symbol = Symbol::LIST_MAP;
+
+        let mut problems = vec![
+            Problem::UnusedDef(symbol, region),
+            Problem::UnusedImport(ModuleId::LIST, region),
+            Problem::ExposedButNotDefined(symbol),
+            Problem::UnknownGeneratesWith(loc_ident("frobnicate")),
+            Problem::UnusedArgument(symbol, false, Symbol::LIST_LEN, region),
+            Problem::UnusedBranchDef(symbol, region),
+            Problem::PrecedenceProblem(PrecedenceProblem::BothNonAssociative(
+                region,
+                Loc::at_zero(BinOp::Equals),
+                Loc::at_zero(BinOp::Equals),
+            )),
+            Problem::UnsupportedPattern(BadPattern::Unsupported(PatternType::TopLevelDef), region),
+            Problem::Shadowing {
+                original_region: region,
+                shadow: loc_ident("x"),
+                kind: ShadowKind::Variable,
+            },
+            Problem::CyclicAlias(symbol, region, Vec::new(), AliasKind::Structural),
+            Problem::BadRecursion(cycle()),
+            Problem::PhantomTypeArgument {
+                typ: symbol,
+                variable_region: region,
+                variable_name: Lowercase::from("a"),
+                alias_kind: AliasKind::Structural,
+            },
+            Problem::UnboundTypeVariable {
+                typ: symbol,
+                num_unbound: 1,
+                one_occurrence: region,
+                kind: AliasKind::Structural,
+            },
+            Problem::DuplicateRecordFieldValue {
+                field_name: Lowercase::from("x"),
+                record_region: region,
+                field_region: region,
+                replaced_region: region,
+            },
+            Problem::DuplicateRecordFieldType {
+                field_name: Lowercase::from("x"),
+                record_region: region,
+                field_region: region,
+                replaced_region: region,
+            },
+            Problem::InvalidOptionalValue {
+                field_name: Lowercase::from("x"),
+                record_region: region,
+                field_region: region,
+            },
+            Problem::DuplicateTag {
+                tag_name: TagName("Red".into()),
+                tag_union_region: region,
+                tag_region: region,
+                replaced_region: region,
+            },
+            Problem::SignatureDefMismatch {
+                annotation_pattern: region,
+                def_pattern: region,
+            },
+            Problem::InvalidAliasRigid {
+                alias_name: symbol,
+                region,
+            },
+            Problem::InvalidInterpolation(region),
+            Problem::InvalidHexadecimal(region),
+            Problem::InvalidUnicodeCodePt(region),
+            Problem::NestedDatatype {
+                alias: symbol,
+                def_region: region,
+                differing_recursion_region: region,
+            },
+            Problem::InvalidExtensionType {
+                region,
+                kind: ExtensionTypeKind::Record,
+            },
+            Problem::AbilityHasTypeVariables {
+                name: Symbol::BOOL_EQ,
+                variables_region: region,
+            },
+            Problem::HasClauseIsNotAbility { region },
+            Problem::IllegalHasClause { region },
+            Problem::AbilityMemberMissingHasClause {
+                member: Symbol::BOOL_IS_EQ,
+                ability: Symbol::BOOL_EQ,
+                region,
+            },
+            Problem::AbilityMemberMultipleBoundVars {
+                member: Symbol::BOOL_IS_EQ,
+                ability: Symbol::BOOL_EQ,
+                span_has_clauses: region,
+                bound_var_names: vec![Lowercase::from("a"), Lowercase::from("b")],
+            },
+            Problem::AbilityNotOnToplevel { region },
+            Problem::AbilityUsedAsType(Lowercase::from("a"), Symbol::BOOL_EQ, region),
+            Problem::NestedSpecialization(Symbol::BOOL_IS_EQ, region),
+            Problem::IllegalDerivedAbility(region),
+            Problem::ImplementationNotFound {
+                member: Symbol::BOOL_IS_EQ,
+                region,
+            },
+            Problem::NotAnAbilityMember {
+                ability: Symbol::BOOL_EQ,
+                name: "isNotEq".to_string(),
+                region,
+            },
+            Problem::OptionalAbilityImpl {
+                ability: Symbol::BOOL_EQ,
+                region,
+            },
+            Problem::QualifiedAbilityImpl { region },
+            Problem::AbilityImplNotIdent { region },
+            Problem::DuplicateImpl {
+                original: region,
+                duplicate: region,
+            },
+            Problem::NotAnAbility(region),
+            Problem::ImplementsNonRequired {
+                region,
+                ability: Symbol::BOOL_EQ,
+                not_required: vec![symbol],
+            },
+            Problem::DoesNotImplementAbility {
+                region,
+                ability: Symbol::BOOL_EQ,
+                not_implemented: vec![Symbol::BOOL_IS_EQ],
+            },
+            Problem::NotBoundInAllPatterns {
+                unbound_symbol: symbol,
+                region,
+            },
+            Problem::NoIdentifiersIntroduced(region),
+            Problem::OverloadedSpecialization {
+                overload: region,
+                original_opaque: symbol,
+                ability_member: Symbol::BOOL_IS_EQ,
+            },
+        ];
+
+        problems.extend(runtime_errors().into_iter().map(Problem::RuntimeError));
+
+        problems
+    }
+
+    fn type_errors() -> Vec<TypeError> {
+        let region = Region::zero();
+        let unfulfilled = || Unfulfilled::OpaqueDoesNotImplement {
+            typ: Symbol::LIST_MAP,
+            ability: Symbol::BOOL_EQ,
+        };
+
+        vec![
+            TypeError::BadExpr(
+                region,
+                Category::Str,
+                ErrorType::Error,
+                Expected::NoExpectation(ErrorType::Error),
+            ),
+            TypeError::BadPattern(
+                region,
+                PatternCategory::Str,
+                ErrorType::Error,
+                PExpected::NoExpectation(ErrorType::Error),
+            ),
+            TypeError::CircularType(region, Symbol::LIST_MAP, ErrorType::Error),
+            TypeError::CircularDef(cycle()),
+            TypeError::BadType(roc_types::types::Problem::Shadowed(
+                region,
+                loc_ident("Age"),
+            )),
+            TypeError::UnexposedLookup(Symbol::LIST_MAP),
+            TypeError::UnfulfilledAbility(unfulfilled()),
+            TypeError::BadExprMissingAbility(
+                region,
+                Category::Str,
+                ErrorType::Error,
+                vec![unfulfilled()],
+            ),
+            TypeError::BadPatternMissingAbility(
+                region,
+                PatternCategory::Str,
+                ErrorType::Error,
+                vec![unfulfilled()],
+            ),
+            TypeError::Exhaustive(exhaustive::Error::Incomplete(
+                region,
+                exhaustive::Context::BadCase,
+                vec![exhaustive::Pattern::Anything],
+            )),
+            TypeError::Exhaustive(exhaustive::Error::Redundant {
+                overall_region: region,
+                branch_region: region,
+                index: HumanIndex::FIRST,
+            }),
+            TypeError::StructuralSpecialization {
+                region,
+                typ: ErrorType::Error,
+                ability: Symbol::BOOL_EQ,
+                member: Symbol::BOOL_IS_EQ,
+            },
+            TypeError::WrongSpecialization {
+                region,
+                ability_member: Symbol::BOOL_IS_EQ,
+                expected_opaque: Symbol::LIST_MAP,
+                found_opaque: Symbol::LIST_LEN,
+            },
+        ]
+    }
+
+    fn assert_explained(description: &str, code: &str, group: &str) {
+        assert!(
+            code.starts_with(group),
+            "{} has code {}, which isn't in the {}xx group",
+            description,
+            code,
+            group
+        );
+        assert!(
+            lookup(code).is_some(),
+            "{} has code {}, which has no explanation",
+            description,
+            code
+        );
+    }
+
+    /// The first line of a report, which has its title and code.
+    fn header_of<'b>(report: Report<'b>, alloc: &'b RocDocAllocator<'b>) -> String {
+        let mut buf = String::new();
+        report.render_ci(&mut buf, alloc);
+
+        buf.lines().next().unwrap_or_default().to_string()
+    }
+
+    #[test]
+    fn every_problem_has_an_explained_code() {
+        let (home, interns) = load();
+        let src_lines: Vec<&str> = SOURCE.split('\n').collect();
+        let lines = LineInfo::new(SOURCE);
+        let alloc = RocDocAllocator::new(&src_lines, home, &interns);
+        let filename = || PathBuf::from("Test.roc");
+
+        let mut used = MutSet::default();
+
+        for problem in problems() {
+            has_example(&problem);
+
+            let description = format!("{:?}", problem);
+            let code = problem.code();
+            let (group, reported) = match &problem {
+                Problem::RuntimeError(error) => ("E02", is_reported(error)),
+                _ => ("E01", true),
+            };
+            assert_explained(&description, code, group);
+            used.insert(code);
+
+            if !reported {
+                continue;
+            }
+
+            let report = can_problem(&alloc, &lines, filename(), problem);
+            let header = header_of(report, &alloc);
+            assert!(
+                header.contains(&format!("[{}]", code)),
+                "{} is reported with the header {:?}",
+                description,
+                header
+            );
+        }
+
+        for error in type_errors() {
+            has_type_example(&error);
+
+            let description = format!("{:?}", error);
+            let code = error.code();
+            assert_explained(&description, code, "E03");
+            used.insert(code);
+
+            if let Some(report) = type_problem(&alloc, &lines, filename(), error) {
+                let header = header_of(report, &alloc);
+                assert!(
+                    header.contains(&format!("[{}]", code)),
+                    "{} is reported with the header {:?}",
+                    description,
+                    header
+                );
+            }
+        }
+
+        // Parse and load problems have a code per title instead, see `roc_reporting::explain`.
+        for error_code in ERROR_CODES {
+            if ["E01", "E02", "E03"]
+                .iter()
+                .any(|group| error_code.code.starts_with(group))
+            {
+                assert!(
+                    used.contains(error_code.code),
+                    "No problem has the code {}",
+                    error_code.code
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn codes_are_in_order_and_explained() {
+        for (index, error_code) in ERROR_CODES.iter().enumerate() {
+            let code = error_code.code;
+
+            assert!(
+                code.len() == 5
+                    && code.starts_with('E')
+                    && code[1..].chars().all(|c| c.is_ascii_digit()),
+                "{} doesn't look like E0123",
+                code
+            );
+            // Codes are in order, which also makes sure none is used twice.
+            if let Some(previous) = index.checked_sub(1).map(|index| ERROR_CODES[index].code) {
+                assert!(previous < code, "{} comes after {}", code, previous);
+            }
+            assert!(
+                error_code.explanation.contains("```"),
+                "The explanation of {} has no example",
+                code
+            );
+            assert_eq!(lookup(&code.to_lowercase()), Some(error_code));
+        }
+    }
+}
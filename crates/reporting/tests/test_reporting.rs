@@ -13,7 +13,10 @@ mod test_reporting {
     use indoc::indoc;
     use roc_can::abilities::AbilitiesStore;
     use roc_can::expr::PendingDerives;
-    use roc_load::{self, ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
+    use roc_load::{
+        self, ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, OptLevel, PropertyTesting,
+        Threading,
+    };
     use roc_module::symbol::{Interns, ModuleId};
     use roc_region::all::LineInfo;
     use roc_reporting::report::{
@@ -38,6 +41,7 @@ mod test_reporting {
             doc,
             filename: filename_from_string(r"/code/proj/Main.roc"),
             severity: Severity::RuntimeError,
+            code: None,
         }
     }
 
@@ -88,6 +92,8 @@ mod test_reporting {
                 render: RenderTarget::Generic,
                 threading: Threading::Single,
                 exec_mode: ExecutionMode::Check,
+                property_testing: PropertyTesting::default(),
+                opt_level: OptLevel::Normal,
             };
             let result =
                 roc_load::load_and_typecheck(arena, full_file_path, exposed_types, load_config);
@@ -140,7 +146,7 @@ mod test_reporting {
         let mut buf = String::new();
 
         match infer_expr_help_new(subdir, arena, src) {
-            Err(LoadingProblem::FormattedReport(fail)) => fail,
+            Err(LoadingProblem::FormattedReport(fail, _)) => fail,
             Ok((module_src, type_problems, can_problems, home, interns)) => {
                 let lines = LineInfo::new(&module_src);
                 let src_lines: Vec<&str> = module_src.split('\n').collect();
@@ -425,7 +431,7 @@ mod test_reporting {
         "#
         ),
         @r###"
-    ── NOT EXPOSED ─────────────────────────────────────────── /code/proj/Main.roc ─
+    ── NOT EXPOSED [E0212] ─────────────────────────────────── /code/proj/Main.roc ─
 
     The List module does not expose `isempty`:
 
@@ -452,7 +458,7 @@ mod test_reporting {
         "#
         ),
         @r###"
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [E0101] ───────────────────────────── /code/proj/Main.roc ─
 
     `y` is not used anywhere in your code.
 
@@ -477,7 +483,7 @@ mod test_reporting {
        "#
         ),
         @r###"
-    ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE NAME [E0201] ──────────────────────────────── /code/proj/Main.roc ─
 
     The `i` name is first defined here:
 
@@ -509,7 +515,7 @@ mod test_reporting {
        "#
         ),
         @r###"
-    ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE NAME [E0109] ──────────────────────────────── /code/proj/Main.roc ─
 
     The `Booly` name is first defined here:
 
@@ -541,7 +547,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0107] ──────────────────────────────── /code/proj/Main.roc ─
 
     Using != and == together requires parentheses, to clarify how they
     should be grouped.
@@ -570,7 +576,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-        ── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─
+        ── UNRECOGNIZED NAME [E0207] ───────────────────────────── /code/proj/Main.roc ─
 
         Nothing is named `bar` in this scope.
 
@@ -594,7 +600,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNRECOGNIZED NAME [E0207] ───────────────────────────── /code/proj/Main.roc ─
 
     Nothing is named `true` in this scope.
 
@@ -626,7 +632,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0107] ──────────────────────────────── /code/proj/Main.roc ─
 
     Using more than one == like this requires parentheses, to clarify how
     things should be grouped.
@@ -652,7 +658,7 @@ mod test_reporting {
          "#
         ),
         @r###"
-    ── UNUSED ARGUMENT ─────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED ARGUMENT [E0105] ─────────────────────────────── /code/proj/Main.roc ─
 
     `box` doesn't use `htmlChildren`.
 
@@ -665,7 +671,7 @@ mod test_reporting {
     at the start of a variable name is a way of saying that the variable
     is not used.
 
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [E0101] ───────────────────────────── /code/proj/Main.roc ─
 
     `y` is not used anywhere in your code.
 
@@ -749,7 +755,7 @@ mod test_reporting {
             ),
             indoc!(
                 r#"
-                <cyan>── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─<reset>
+                <cyan>── UNRECOGNIZED NAME [E0207] ───────────────────────────── /code/proj/Main.roc ─<reset>
 
                 Nothing is named `theAdmin` in this scope.
 
@@ -775,7 +781,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `if` condition needs to be a Bool:
 
@@ -801,7 +807,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `if` guard condition needs to be a Bool:
 
@@ -826,7 +832,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `if` has an `else` branch with a different type from its `then` branch:
 
@@ -853,7 +859,7 @@ mod test_reporting {
              "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 3rd branch of this `if` does not match all the previous branches:
 
@@ -883,7 +889,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 2nd branch of this `when` does not match all the previous branches:
 
@@ -912,7 +918,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This list contains elements with different types:
 
@@ -939,7 +945,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This list contains elements with different types:
 
@@ -972,7 +978,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     I cannot update the `.foo` field like this:
 
@@ -1002,7 +1008,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [E0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1027,7 +1033,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [E0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1053,7 +1059,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [E0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1066,7 +1072,7 @@ mod test_reporting {
 
         List ∞ -> a
 
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [E0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `g`:
 
@@ -1093,7 +1099,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression is used in an unexpected way:
 
@@ -1129,7 +1135,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression is used in an unexpected way:
 
@@ -1163,7 +1169,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-        ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+        ── CIRCULAR TYPE [E0303] ───────────────────────────────── /code/proj/Main.roc ─
 
         I'm inferring a weird self-referential type for `f`:
 
@@ -1189,7 +1195,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-        ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+        ── CIRCULAR TYPE [E0303] ───────────────────────────────── /code/proj/Main.roc ─
 
         I'm inferring a weird self-referential type for `f`:
 
@@ -1227,7 +1233,7 @@ mod test_reporting {
         // against that extra variable, rather than possibly having to translate a `Type`
         // again.
         @r###"
-        ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+        ── CIRCULAR TYPE [E0303] ───────────────────────────────── /code/proj/Main.roc ─
 
         I'm inferring a weird self-referential type for `f`:
 
@@ -1240,7 +1246,7 @@ mod test_reporting {
 
             List ∞ -> List a
 
-        ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+        ── CIRCULAR TYPE [E0303] ───────────────────────────────── /code/proj/Main.roc ─
 
         I'm inferring a weird self-referential type for `g`:
 
@@ -1267,7 +1273,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-        ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+        ── CIRCULAR TYPE [E0303] ───────────────────────────────── /code/proj/Main.roc ─
 
         I'm inferring a weird self-referential type for `f`:
 
@@ -1280,7 +1286,7 @@ mod test_reporting {
 
             List ∞ -> List a
 
-        ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+        ── CIRCULAR TYPE [E0303] ───────────────────────────────── /code/proj/Main.roc ─
 
         I'm inferring a weird self-referential type for `g`:
 
@@ -1308,7 +1314,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `f` has an unexpected type:
 
@@ -1342,7 +1348,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `f` has an unexpected type:
 
@@ -1376,7 +1382,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `f` has an unexpected type:
 
@@ -1410,7 +1416,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the `then` branch of this `if` expression:
 
@@ -1444,7 +1450,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `x` definition:
 
@@ -1477,7 +1483,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `x` definition:
 
@@ -1509,7 +1515,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TOO MANY ARGS ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY ARGS [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The `x` value is not a function, but it was given 1 argument:
 
@@ -1531,7 +1537,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TOO MANY ARGS ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY ARGS [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The `f` function expects 1 argument, but it got 2 instead:
 
@@ -1553,7 +1559,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TOO FEW ARGS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── TOO FEW ARGS [E0301] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `f` function expects 2 arguments, but it got only 1:
 
@@ -1574,7 +1580,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -1603,7 +1609,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0302] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 2nd pattern in this `when` does not match the previous ones:
 
@@ -1629,7 +1635,7 @@ mod test_reporting {
              "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -1657,7 +1663,7 @@ mod test_reporting {
              "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -1686,7 +1692,7 @@ mod test_reporting {
              "#
         ),
         @r###"
-    ── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNRECOGNIZED NAME [E0207] ───────────────────────────── /code/proj/Main.roc ─
 
     Nothing is named `foo` in this scope.
 
@@ -1742,7 +1748,7 @@ mod test_reporting {
         ),
         // Just putting this here. We should probably handle or-patterns better
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0302] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 2nd pattern in this branch does not match the previous ones:
 
@@ -1770,7 +1776,7 @@ mod test_reporting {
         ),
         // Maybe this should specifically say the pattern doesn't work?
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression is used in an unexpected way:
 
@@ -1798,7 +1804,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of this definition:
 
@@ -1829,7 +1835,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0204] ──────────────────────────────── /code/proj/Main.roc ─
 
     This integer pattern is malformed:
 
@@ -1850,7 +1856,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0204] ──────────────────────────────── /code/proj/Main.roc ─
 
     This float pattern is malformed:
 
@@ -1871,7 +1877,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0204] ──────────────────────────────── /code/proj/Main.roc ─
 
     This hex integer pattern is malformed:
 
@@ -1892,7 +1898,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0204] ──────────────────────────────── /code/proj/Main.roc ─
 
     This octal integer pattern is malformed:
 
@@ -1913,7 +1919,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0204] ──────────────────────────────── /code/proj/Main.roc ─
 
     This binary integer pattern is malformed:
 
@@ -1935,7 +1941,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `x` definition:
 
@@ -1981,7 +1987,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the `else` branch of this `if` expression:
 
@@ -2015,7 +2021,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -2050,7 +2056,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -2086,7 +2092,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNRECOGNIZED NAME [E0207] ───────────────────────────── /code/proj/Main.roc ─
 
     Nothing is named `ok` in this scope.
 
@@ -2117,7 +2123,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [E0101] ───────────────────────────── /code/proj/Main.roc ─
 
     `ok` is not used anywhere in your code.
 
@@ -2127,7 +2133,7 @@ mod test_reporting {
     If you didn't intend on using `ok` then remove it so future readers of
     your code don't wonder why it is there.
 
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -2159,7 +2165,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-        ── CIRCULAR DEFINITION ─────────────────────────────────── /code/proj/Main.roc ─
+        ── CIRCULAR DEFINITION [E0221] ─────────────────────────── /code/proj/Main.roc ─
 
         The `f` value is defined directly in terms of itself, causing an
         infinite loop.
@@ -2179,7 +2185,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── CIRCULAR DEFINITION ─────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR DEFINITION [E0221] ─────────────────────────── /code/proj/Main.roc ─
 
     The `foo` definition is causing a very tricky infinite loop:
 
@@ -2207,7 +2213,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `x` record doesn’t have a `foo` field:
 
@@ -2229,7 +2235,7 @@ mod test_reporting {
         ),
         // TODO also suggest fields with the correct type
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `x` record doesn’t have a `foo` field:
 
@@ -2262,7 +2268,7 @@ mod test_reporting {
         ),
         // TODO also suggest fields with the correct type
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `r` record doesn’t have a `foo` field:
 
@@ -2290,7 +2296,7 @@ mod test_reporting {
         ),
         // TODO also suggest fields with the correct type
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `x` record doesn’t have a `foo` field:
 
@@ -2320,7 +2326,7 @@ mod test_reporting {
         ),
         // TODO also suggest fields with the correct type
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `add` has an unexpected type:
 
@@ -2345,7 +2351,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `add` has an unexpected type:
 
@@ -2373,7 +2379,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `add` has an unexpected type:
 
@@ -2401,7 +2407,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -2435,7 +2441,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -2474,7 +2480,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [E0310] ──────────────────────────────── /code/proj/Main.roc ─
 
     This pattern does not cover all the possibilities:
 
@@ -2505,7 +2511,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression is used in an unexpected way:
 
@@ -2536,7 +2542,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [E0310] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -2563,7 +2569,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [E0310] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -2591,7 +2597,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [E0310] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -2620,7 +2626,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [E0310] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -2649,7 +2655,7 @@ mod test_reporting {
         ),
         // Tip: Looks like a record field guard is not exhaustive. Learn more about record pattern matches at TODO.
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [E0310] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -2678,7 +2684,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [E0310] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -2704,7 +2710,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [E0310] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -2731,7 +2737,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── REDUNDANT PATTERN ───────────────────────────────────── /code/proj/Main.roc ─
+    ── REDUNDANT PATTERN [E0311] ───────────────────────────── /code/proj/Main.roc ─
 
     The 2nd pattern is redundant:
 
@@ -2759,7 +2765,7 @@ mod test_reporting {
         ),
         // de-aliases the alias to give a better error message
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `f` has an unexpected type:
 
@@ -2797,7 +2803,7 @@ mod test_reporting {
         ),
         // should not report Bar as unused!
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [E0110] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `Foo` alias is self-recursive in an invalid way:
 
@@ -2823,7 +2829,7 @@ mod test_reporting {
         ),
         // should not report Bar as unused!
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [E0110] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `Foo` alias is self-recursive in an invalid way:
 
@@ -2843,7 +2849,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── DUPLICATE FIELD NAME ────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE FIELD NAME [E0114] ────────────────────────── /code/proj/Main.roc ─
 
     This record defines the `.x` field twice!
 
@@ -2867,7 +2873,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── DUPLICATE FIELD NAME ────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE FIELD NAME [E0114] ────────────────────────── /code/proj/Main.roc ─
 
     This record defines the `.x` field twice!
 
@@ -2895,7 +2901,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── DUPLICATE FIELD NAME ────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE FIELD NAME [E0114] ────────────────────────── /code/proj/Main.roc ─
 
     This record defines the `.x` field twice!
 
@@ -2930,7 +2936,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── DUPLICATE FIELD NAME ────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE FIELD NAME [E0114] ────────────────────────── /code/proj/Main.roc ─
 
     This record defines the `.x` field twice!
 
@@ -2963,7 +2969,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── DUPLICATE FIELD NAME ────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE FIELD NAME [E0115] ────────────────────────── /code/proj/Main.roc ─
 
     This record type defines the `.foo` field twice!
 
@@ -2991,7 +2997,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── DUPLICATE TAG NAME ──────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE TAG NAME [E0117] ──────────────────────────── /code/proj/Main.roc ─
 
     This tag union type defines the `Foo` tag twice!
 
@@ -3020,7 +3026,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── NAMING PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── NAMING PROBLEM [E0118] ──────────────────────────────── /code/proj/Main.roc ─
 
     This annotation does not match the definition immediately following
     it:
@@ -3056,7 +3062,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0119] ──────────────────────────────── /code/proj/Main.roc ─
 
     This definition of `MyAlias` has an unexpected pattern:
 
@@ -3065,7 +3071,7 @@ mod test_reporting {
 
     Only type variables like `a` or `value` can occur in this position.
 
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [E0101] ───────────────────────────── /code/proj/Main.roc ─
 
     `MyAlias` is not used anywhere in your code.
 
@@ -3088,7 +3094,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0119] ──────────────────────────────── /code/proj/Main.roc ─
 
     This definition of `Age` has an unexpected pattern:
 
@@ -3110,7 +3116,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TOO MANY TYPE ARGUMENTS ─────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY TYPE ARGUMENTS [E0305] ─────────────────────── /code/proj/Main.roc ─
 
     The `Num` opaque expects 1 type argument, but it got 2 instead:
 
@@ -3132,7 +3138,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TOO MANY TYPE ARGUMENTS ─────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY TYPE ARGUMENTS [E0305] ─────────────────────── /code/proj/Main.roc ─
 
     The `Num` opaque expects 1 type argument, but it got 2 instead:
 
@@ -3156,7 +3162,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TOO FEW TYPE ARGUMENTS ──────────────────────────────── /code/proj/Main.roc ─
+    ── TOO FEW TYPE ARGUMENTS [E0305] ──────────────────────── /code/proj/Main.roc ─
 
     The `Pair` alias expects 2 type arguments, but it got 1 instead:
 
@@ -3180,7 +3186,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TOO MANY TYPE ARGUMENTS ─────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY TYPE ARGUMENTS [E0305] ─────────────────────── /code/proj/Main.roc ─
 
     The `Pair` alias expects 2 type arguments, but it got 3 instead:
 
@@ -3203,7 +3209,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNUSED TYPE ALIAS PARAMETER ─────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED TYPE ALIAS PARAMETER [E0112] ─────────────────── /code/proj/Main.roc ─
 
     The `a` type parameter is not used in the `Foo` alias definition:
 
@@ -3225,7 +3231,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── ARGUMENTS BEFORE EQUALS ────────────────── tmp/elm_function_syntax/Test.roc ─
+    ── ARGUMENTS BEFORE EQUALS [E0401] ────────── tmp/elm_function_syntax/Test.roc ─
 
     I am partway through parsing a definition, but I got stuck here:
 
@@ -3253,7 +3259,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `x` definition:
 
@@ -3290,7 +3296,7 @@ mod test_reporting {
         // TODO render tag unions across multiple lines
         // TODO do not show recursion var if the recursion var does not render on the surface of a type
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `x` definition:
 
@@ -3327,7 +3333,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0220] ──────────────────────────────── /code/proj/Main.roc ─
 
     This integer literal is too big:
 
@@ -3339,7 +3345,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0220] ──────────────────────────────── /code/proj/Main.roc ─
 
     This integer literal is too small:
 
@@ -3351,7 +3357,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0220] ──────────────────────────────── /code/proj/Main.roc ─
 
     This integer literal is too big:
 
@@ -3363,7 +3369,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0220] ──────────────────────────────── /code/proj/Main.roc ─
 
     This integer literal is too small:
 
@@ -3375,7 +3381,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `add` has an unexpected type:
 
@@ -3404,7 +3410,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0219] ──────────────────────────────── /code/proj/Main.roc ─
 
     This float literal is too big:
 
@@ -3416,7 +3422,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0219] ──────────────────────────────── /code/proj/Main.roc ─
 
     This float literal is too small:
 
@@ -3449,7 +3455,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0220] ──────────────────────────────── /code/proj/Main.roc ─
 
     This integer literal contains an invalid digit:
 
@@ -3461,7 +3467,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0220] ──────────────────────────────── /code/proj/Main.roc ─
 
     This hex integer literal contains an invalid digit:
 
@@ -3473,7 +3479,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0220] ──────────────────────────────── /code/proj/Main.roc ─
 
     This octal integer literal contains an invalid digit:
 
@@ -3485,7 +3491,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0220] ──────────────────────────────── /code/proj/Main.roc ─
 
     This binary integer literal contains an invalid digit:
 
@@ -3515,7 +3521,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0220] ──────────────────────────────── /code/proj/Main.roc ─
 
     This hex integer literal contains no digits:
 
@@ -3527,7 +3533,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0220] ──────────────────────────────── /code/proj/Main.roc ─
 
     This octal integer literal contains no digits:
 
@@ -3539,7 +3545,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0220] ──────────────────────────────── /code/proj/Main.roc ─
 
     This binary integer literal contains no digits:
 
@@ -3563,7 +3569,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0219] ──────────────────────────────── /code/proj/Main.roc ─
 
     This float literal contains an invalid digit:
 
@@ -3594,7 +3600,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0218] ──────────────────────────────── /code/proj/Main.roc ─
 
     This expression cannot be updated:
 
@@ -3603,7 +3609,7 @@ mod test_reporting {
 
     Only variables can be updated with record update syntax.
 
-    ── MODULE NOT IMPORTED ─────────────────────────────────── /code/proj/Main.roc ─
+    ── MODULE NOT IMPORTED [E0213] ─────────────────────────── /code/proj/Main.roc ─
 
     The `Test` module is not imported:
 
@@ -3618,7 +3624,7 @@ mod test_reporting {
         Dict
         Result
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0218] ──────────────────────────────── /code/proj/Main.roc ─
 
     This expression cannot be updated:
 
@@ -3637,7 +3643,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── MODULE NOT IMPORTED ─────────────────────────────────── /code/proj/Main.roc ─
+    ── MODULE NOT IMPORTED [E0213] ─────────────────────────── /code/proj/Main.roc ─
 
     The `Foo` module is not imported:
 
@@ -3662,7 +3668,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `add` has an unexpected type:
 
@@ -3690,7 +3696,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0302] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 1st argument to `f` is weird:
 
@@ -3719,7 +3725,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of this definition:
 
@@ -3750,7 +3756,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0302] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 1st argument to `f` is weird:
 
@@ -3783,7 +3789,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -3816,7 +3822,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression is used in an unexpected way:
 
@@ -3847,7 +3853,7 @@ mod test_reporting {
                 "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to this function has an unexpected type:
 
@@ -3881,7 +3887,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -3915,7 +3921,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -3943,7 +3949,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── BAD OPTIONAL VALUE ──────────────────────────────────── /code/proj/Main.roc ─
+    ── BAD OPTIONAL VALUE [E0116] ──────────────────────────── /code/proj/Main.roc ─
 
     This record uses an optional value for the `.y` field in an incorrect
     context!
@@ -3980,7 +3986,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── REDUNDANT PATTERN ───────────────────────────────────── /code/proj/Main.roc ─
+    ── REDUNDANT PATTERN [E0311] ───────────────────────────── /code/proj/Main.roc ─
 
     The 3rd pattern is redundant:
 
@@ -4025,7 +4031,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNUSED ARGUMENT ─────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED ARGUMENT [E0105] ─────────────────────────────── /code/proj/Main.roc ─
 
     `f` doesn't use `foo`.
 
@@ -4047,7 +4053,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0215] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am trying to parse a qualified name here:
 
@@ -4068,7 +4074,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0215] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am trying to parse a qualified name here:
 
@@ -4088,7 +4094,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0215] ──────────────────────────────── /code/proj/Main.roc ─
 
     I trying to parse a record field access here:
 
@@ -4110,7 +4116,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNKNOWN OPERATOR ──────────────── tmp/type_annotation_double_colon/Test.roc ─
+    ── UNKNOWN OPERATOR [E0447] ──────── tmp/type_annotation_double_colon/Test.roc ─
 
     This looks like an operator, but it's not one I recognize!
 
@@ -4142,7 +4148,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TOO MANY ARGS ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY ARGS [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This value is not a function, but it was given 3 arguments:
 
@@ -4161,7 +4167,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED TAG UNION TYPE ───────────────────── tmp/tag_union_open/Test.roc ─
+    ── UNFINISHED TAG UNION TYPE [E0444] ───────────── tmp/tag_union_open/Test.roc ─
 
     I am partway through parsing a tag union type, but I got stuck here:
 
@@ -4183,7 +4189,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED TAG UNION TYPE ────────────────────── tmp/tag_union_end/Test.roc ─
+    ── UNFINISHED TAG UNION TYPE [E0444] ────────────── tmp/tag_union_end/Test.roc ─
 
     I am partway through parsing a tag union type, but I got stuck here:
 
@@ -4205,7 +4211,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── WEIRD TAG NAME ────────────────── tmp/tag_union_lowercase_tag_name/Test.roc ─
+    ── WEIRD TAG NAME [E0460] ────────── tmp/tag_union_lowercase_tag_name/Test.roc ─
 
     I am partway through parsing a tag union type, but I got stuck here:
 
@@ -4226,7 +4232,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── WEIRD TAG NAME ─────────── tmp/tag_union_second_lowercase_tag_name/Test.roc ─
+    ── WEIRD TAG NAME [E0460] ─── tmp/tag_union_second_lowercase_tag_name/Test.roc ─
 
     I am partway through parsing a tag union type, but I got stuck here:
 
@@ -4247,7 +4253,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED RECORD TYPE ────────────────────── tmp/record_type_open/Test.roc ─
+    ── UNFINISHED RECORD TYPE [E0443] ────────────── tmp/record_type_open/Test.roc ─
 
     I am partway through parsing a record type, but I got stuck here:
 
@@ -4270,7 +4276,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED RECORD TYPE ─────────────── tmp/record_type_open_indent/Test.roc ─
+    ── UNFINISHED RECORD TYPE [E0443] ─────── tmp/record_type_open_indent/Test.roc ─
 
     I am partway through parsing a record type, but I got stuck here:
 
@@ -4292,7 +4298,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED RECORD TYPE ─────────────────────── tmp/record_type_end/Test.roc ─
+    ── UNFINISHED RECORD TYPE [E0443] ─────────────── tmp/record_type_end/Test.roc ─
 
     I am partway through parsing a record type, but I got stuck here:
 
@@ -4314,7 +4320,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED RECORD TYPE ──────── tmp/record_type_keyword_field_name/Test.roc ─
+    ── UNFINISHED RECORD TYPE [E0443] ─ ...record_type_keyword_field_name/Test.roc ─
 
     I just started parsing a record type, but I got stuck on this field
     name:
@@ -4336,7 +4342,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED RECORD TYPE ───────────── tmp/record_type_missing_comma/Test.roc ─
+    ── UNFINISHED RECORD TYPE [E0443] ───── tmp/record_type_missing_comma/Test.roc ─
 
     I am partway through parsing a record type, but I got stuck here:
 
@@ -4353,7 +4359,7 @@ mod test_reporting {
         record_type_tab,
         "f : { foo \t }",
         @r###"
-    ── TAB CHARACTER ──────────────────────────────── tmp/record_type_tab/Test.roc ─
+    ── TAB CHARACTER [E0430] ──────────────────────── tmp/record_type_tab/Test.roc ─
 
     I encountered a tab character
 
@@ -4368,7 +4374,7 @@ mod test_reporting {
         comment_with_tab,
         "# comment with a \t\n4",
         @r###"
-    ── TAB CHARACTER ─────────────────────────────── tmp/comment_with_tab/Test.roc ─
+    ── TAB CHARACTER [E0430] ─────────────────────── tmp/comment_with_tab/Test.roc ─
 
     I encountered a tab character
 
@@ -4388,7 +4394,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED PARENTHESES ────────────────── tmp/type_in_parens_start/Test.roc ─
+    ── UNFINISHED PARENTHESES [E0440] ────────── tmp/type_in_parens_start/Test.roc ─
 
     I just started parsing a type in parentheses, but I got stuck here:
 
@@ -4410,7 +4416,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED PARENTHESES ──────────────────── tmp/type_in_parens_end/Test.roc ─
+    ── UNFINISHED PARENTHESES [E0440] ──────────── tmp/type_in_parens_end/Test.roc ─
 
     I am partway through parsing a type in parentheses, but I got stuck
     here:
@@ -4435,7 +4441,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0216] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am confused by this type name:
 
@@ -4446,7 +4452,7 @@ mod test_reporting {
     qualified by a module name, like Bool or Http.Request.Request.
     "###
     );
-    //                ── DOUBLE DOT ──────────────────────────────────────────────────────────────────
+    //                ── DOUBLE DOT [E0407] ──────────────────────────────────────────────────────────
     //
     //                I encountered two dots in a row:
     //
@@ -4465,7 +4471,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0216] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am confused by this type name:
 
@@ -4476,7 +4482,7 @@ mod test_reporting {
     qualified by a module name, like Bool or Http.Request.Request.
     "###
     );
-    //                ── TRAILING DOT ────────────────────────────────────────────────────────────────
+    //                ── TRAILING DOT [E0431] ────────────────────────────────────────────────────────
     //
     //                I encountered a dot with nothing after it:
     //
@@ -4494,7 +4500,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED TYPE ───────────────────────── tmp/type_apply_stray_dot/Test.roc ─
+    ── UNFINISHED TYPE [E0445] ───────────────── tmp/type_apply_stray_dot/Test.roc ─
 
     I just started parsing a type, but I got stuck here:
 
@@ -4515,7 +4521,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0216] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am confused by this type name:
 
@@ -4526,7 +4532,7 @@ mod test_reporting {
     qualified by a module name, like Bool or Http.Request.Request.
     "###
     );
-    //                ── WEIRD QUALIFIED NAME ────────────────────────────────────────────────────────
+    //                ── WEIRD QUALIFIED NAME [E0459] ────────────────────────────────────────────────
     //
     //                I encountered a number at the start of a qualified name segment:
     //
@@ -4546,7 +4552,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0216] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am confused by this type name:
 
@@ -4566,7 +4572,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── MISSING FINAL EXPRESSION ──────── tmp/def_missing_final_expression/Test.roc ─
+    ── MISSING FINAL EXPRESSION [E0418] ─ ...def_missing_final_expression/Test.roc ─
 
     I am partway through parsing a definition, but I got stuck here:
 
@@ -4597,7 +4603,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED INLINE ALIAS ──────────────────── tmp/type_inline_alias/Test.roc ─
+    ── UNFINISHED INLINE ALIAS [E0438] ──────────── tmp/type_inline_alias/Test.roc ─
 
     I just started parsing an inline type alias, but I got stuck here:
 
@@ -4619,7 +4625,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── DOUBLE COMMA ─────────────────────────────── tmp/type_double_comma/Test.roc ─
+    ── DOUBLE COMMA [E0406] ─────────────────────── tmp/type_double_comma/Test.roc ─
 
     I just started parsing a function argument type, but I encountered two
     commas in a row:
@@ -4642,7 +4648,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED TYPE ─────────────────────── tmp/type_argument_no_arrow/Test.roc ─
+    ── UNFINISHED TYPE [E0445] ─────────────── tmp/type_argument_no_arrow/Test.roc ─
 
     I am partway through parsing a type, but I got stuck here:
 
@@ -4665,7 +4671,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED TYPE ───────────── tmp/type_argument_arrow_then_nothing/Test.roc ─
+    ── UNFINISHED TYPE [E0445] ───── tmp/type_argument_arrow_then_nothing/Test.roc ─
 
     I just started parsing a type, but I got stuck here:
 
@@ -4690,7 +4696,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `myDict` definition:
 
@@ -4725,7 +4731,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `myDict` definition:
 
@@ -4757,7 +4763,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── IF GUARD NO CONDITION ───────────── tmp/if_guard_without_condition/Test.roc ─
+    ── IF GUARD NO CONDITION [E0412] ───── tmp/if_guard_without_condition/Test.roc ─
 
     I just started parsing an if guard, but there is no guard condition:
 
@@ -4782,7 +4788,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED PATTERN ────────────────────────── tmp/empty_or_pattern/Test.roc ─
+    ── UNFINISHED PATTERN [E0441] ────────────────── tmp/empty_or_pattern/Test.roc ─
 
     I just started parsing a pattern, but I got stuck here:
 
@@ -4807,7 +4813,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── MISSING EXPRESSION ───────────────────── tmp/pattern_binds_keyword/Test.roc ─
+    ── MISSING EXPRESSION [E0417] ───────────── tmp/pattern_binds_keyword/Test.roc ─
 
     I am partway through parsing a `when` expression, but I got stuck here:
 
@@ -4829,7 +4835,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED WHEN ─────────────────────────── tmp/when_missing_arrow/Test.roc ─
+    ── UNFINISHED WHEN [E0446] ─────────────────── tmp/when_missing_arrow/Test.roc ─
 
     I was partway through parsing a `when` expression, but I got stuck here:
 
@@ -4860,7 +4866,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED ARGUMENT LIST ───────────────── tmp/lambda_double_comma/Test.roc ─
+    ── UNFINISHED ARGUMENT LIST [E0435] ───────── tmp/lambda_double_comma/Test.roc ─
 
     I am partway through parsing a function argument list, but I got stuck
     at this comma:
@@ -4881,7 +4887,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED ARGUMENT LIST ──────────────── tmp/lambda_leading_comma/Test.roc ─
+    ── UNFINISHED ARGUMENT LIST [E0435] ──────── tmp/lambda_leading_comma/Test.roc ─
 
     I am partway through parsing a function argument list, but I got stuck
     at this comma:
@@ -4897,7 +4903,7 @@ mod test_reporting {
     // this should get better with time
     // TODO this formerly gave
     //
-    //                ── UNFINISHED WHEN ─────────────────────────────────────────────────────────────
+    //                ── UNFINISHED WHEN [E0446] ─────────────────────────────────────────────────────
     //
     //                I was partway through parsing a `when` expression, but I got stuck here:
     //
@@ -4928,7 +4934,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── NOT END OF FILE ──────────────────────── tmp/when_outdented_branch/Test.roc ─
+    ── NOT END OF FILE [E0424] ──────────────── tmp/when_outdented_branch/Test.roc ─
 
     I expected to reach the end of the file, but got stuck here:
 
@@ -4947,7 +4953,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNEXPECTED ARROW ─────────────── tmp/when_over_indented_underscore/Test.roc ─
+    ── UNEXPECTED ARROW [E0433] ─────── tmp/when_over_indented_underscore/Test.roc ─
 
     I am parsing a `when` expression right now, but this arrow is confusing
     me:
@@ -4983,7 +4989,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNEXPECTED ARROW ────────────────────── tmp/when_over_indented_int/Test.roc ─
+    ── UNEXPECTED ARROW [E0433] ────────────── tmp/when_over_indented_int/Test.roc ─
 
     I am parsing a `when` expression right now, but this arrow is confusing
     me:
@@ -5022,7 +5028,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED IF ────────────────────────────── tmp/if_outdented_then/Test.roc ─
+    ── UNFINISHED IF [E0437] ────────────────────── tmp/if_outdented_then/Test.roc ─
 
     I was partway through parsing an `if` expression, but I got stuck here:
 
@@ -5042,7 +5048,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED IF ──────────────────────────────── tmp/if_missing_else/Test.roc ─
+    ── UNFINISHED IF [E0437] ──────────────────────── tmp/if_missing_else/Test.roc ─
 
     I was partway through parsing an `if` expression, but I got stuck here:
 
@@ -5061,7 +5067,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED LIST ──────────────────────────── tmp/list_double_comma/Test.roc ─
+    ── UNFINISHED LIST [E0439] ──────────────────── tmp/list_double_comma/Test.roc ─
 
     I am partway through started parsing a list, but I got stuck here:
 
@@ -5081,7 +5087,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── UNFINISHED LIST ───────────────────────────── tmp/list_without_end/Test.roc ─
+    ── UNFINISHED LIST [E0439] ───────────────────── tmp/list_without_end/Test.roc ─
 
     I am partway through started parsing a list, but I got stuck here:
 
@@ -5107,7 +5113,7 @@ mod test_reporting {
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0219] ──────────────────────────────── /code/proj/Main.roc ─
 
     This float literal contains an invalid digit:
 
@@ -5125,7 +5131,7 @@ mod test_reporting {
         unicode_not_hex,
         r#""abc\u(zzzz)def""#,
         @r###"
-    ── WEIRD CODE POINT ───────────────────────────── tmp/unicode_not_hex/Test.roc ─
+    ── WEIRD CODE POINT [E0450] ───────────────────── tmp/unicode_not_hex/Test.roc ─
 
     I am partway through parsing a unicode code point, but I got stuck
     here:
@@ -5143,7 +5149,7 @@ mod test_reporting {
         interpolate_not_identifier,
         r#""abc\(32)def""#,
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0120] ──────────────────────────────── /code/proj/Main.roc ─
 
     This string interpolation is invalid:
 
@@ -5161,7 +5167,7 @@ mod test_reporting {
         unicode_too_large,
         r#""abc\u(110000)def""#,
         @r###"
-    ── INVALID UNICODE ─────────────────────────────────────── /code/proj/Main.roc ─
+    ── INVALID UNICODE [E0122] ─────────────────────────────── /code/proj/Main.roc ─
 
     This unicode code point is invalid:
 
@@ -5176,7 +5182,7 @@ mod test_reporting {
         weird_escape,
         r#""abc\qdef""#,
         @r###"
-    ── WEIRD ESCAPE ──────────────────────────────────── tmp/weird_escape/Test.roc ─
+    ── WEIRD ESCAPE [E0451] ──────────────────────────── tmp/weird_escape/Test.roc ─
 
     I was partway through parsing a  string literal, but I got stuck here:
 
@@ -5200,7 +5206,7 @@ mod test_reporting {
         single_no_end,
         r#""there is no end"#,
         @r###"
-    ── ENDLESS STRING ───────────────────────────────── tmp/single_no_end/Test.roc ─
+    ── ENDLESS STRING [E0411] ───────────────────────── tmp/single_no_end/Test.roc ─
 
     I cannot find the end of this string:
 
@@ -5216,7 +5222,7 @@ mod test_reporting {
         multi_no_end,
         r#""""there is no end"#,
         @r###"
-    ── ENDLESS STRING ────────────────────────────────── tmp/multi_no_end/Test.roc ─
+    ── ENDLESS STRING [E0411] ────────────────────────── tmp/multi_no_end/Test.roc ─
 
     I cannot find the end of this block string:
 
@@ -5232,7 +5238,7 @@ mod test_reporting {
         multi_insufficient_indent,
         "    \"\"\"\n  testing\n    \"\"\"", // 4 space indent on the start, 2 space on the `testing` line
         @r###"
-    ── INSUFFICIENT INDENT IN MULTI-LINE STRING ─ ..._insufficient_indent/Test.roc ─
+    ── INSUFFICIENT INDENT IN MULTI-LINE STRING [E0414] ─ ...cient_indent/Test.roc ─
 
     This multiline string is not sufficiently indented:
 
@@ -5256,7 +5262,7 @@ mod test_reporting {
             "#,
             ),
             @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `if` has an `else` branch with a different type from its `then` branch:
 
@@ -5282,7 +5288,7 @@ mod test_reporting {
                 $name,
                 &format!(r#"if True then "abc" else 1 {} 2"#, $op),
                 |golden| assert_eq!(golden, format!(
-r#"── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+r#"── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
 This `if` has an `else` branch with a different type from its `then` branch:
 
@@ -5325,7 +5331,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `foo` record doesn’t have a `if` field:
 
@@ -5344,7 +5350,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── NOT EXPOSED ─────────────────────────────────────────── /code/proj/Main.roc ─
+    ── NOT EXPOSED [E0212] ─────────────────────────────────── /code/proj/Main.roc ─
 
     The Num module does not expose `if`:
 
@@ -5368,7 +5374,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0215] ──────────────────────────────── /code/proj/Main.roc ─
 
     I trying to parse a record field access here:
 
@@ -5387,7 +5393,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0215] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am very confused by this field access:
 
@@ -5406,7 +5412,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0215] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am very confused by this field access
 
@@ -5427,7 +5433,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0215] ──────────────────────────────── /code/proj/Main.roc ─
 
     I trying to parse a record field access here:
 
@@ -5446,7 +5452,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── NAMING PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── NAMING PROBLEM [E0204] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am trying to parse an identifier here:
 
@@ -5480,7 +5486,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNKNOWN OPERATOR ───────────────────────────────────── tmp/case_of/Test.roc ─
+    ── UNKNOWN OPERATOR [E0447] ───────────────────────────── tmp/case_of/Test.roc ─
 
     This looks like an operator, but it's not one I recognize!
 
@@ -5507,7 +5513,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNRECOGNIZED NAME [E0207] ───────────────────────────── /code/proj/Main.roc ─
 
     Nothing is named `bar` in this scope.
 
@@ -5532,7 +5538,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNKNOWN OPERATOR ──────────────────────────── tmp/invalid_operator/Test.roc ─
+    ── UNKNOWN OPERATOR [E0447] ──────────────────── tmp/invalid_operator/Test.roc ─
 
     This looks like an operator, but it's not one I recognize!
 
@@ -5557,7 +5563,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNKNOWN OPERATOR ───────────────────────────────── tmp/double_plus/Test.roc ─
+    ── UNKNOWN OPERATOR [E0447] ───────────────────────── tmp/double_plus/Test.roc ─
 
     This looks like an operator, but it's not one I recognize!
 
@@ -5584,7 +5590,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNKNOWN OPERATOR ────────────────────────────── tmp/inline_hastype/Test.roc ─
+    ── UNKNOWN OPERATOR [E0447] ────────────────────── tmp/inline_hastype/Test.roc ─
 
     This looks like an operator, but it's not one I recognize!
 
@@ -5613,7 +5619,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNKNOWN OPERATOR ───────────────────────────── tmp/wild_case_arrow/Test.roc ─
+    ── UNKNOWN OPERATOR [E0447] ───────────────────── tmp/wild_case_arrow/Test.roc ─
 
     This looks like an operator, but it's not one I recognize!
 
@@ -5644,7 +5650,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r#"
-                ── WEIRD PROVIDES ──────────────────────────────────────── /code/proj/Main.roc ─
+                ── WEIRD PROVIDES [E0458] ──────────────────────────────── /code/proj/Main.roc ─
 
                 I am partway through parsing a provides list, but I got stuck here:
 
@@ -5681,7 +5687,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r#"
-                ── BAD REQUIRES ────────────────────────────────────────── /code/proj/Main.roc ─
+                ── BAD REQUIRES [E0403] ────────────────────────────────── /code/proj/Main.roc ─
 
                 I am partway through parsing a header, but I got stuck here:
 
@@ -5709,7 +5715,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r#"
-                ── WEIRD IMPORTS ───────────────────────────────────────── /code/proj/Main.roc ─
+                ── WEIRD IMPORTS [E0456] ───────────────────────────────── /code/proj/Main.roc ─
 
                 I am partway through parsing a header, but I got stuck here:
 
@@ -5736,7 +5742,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r#"
-                ── WEIRD EXPOSES ───────────────────────────────────────── /code/proj/Main.roc ─
+                ── WEIRD EXPOSES [E0452] ───────────────────────────────── /code/proj/Main.roc ─
 
                 I am partway through parsing an `exposes` list, but I got stuck here:
 
@@ -5764,7 +5770,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r#"
-                ── WEIRD MODULE NAME ───────────────────────────────────── /code/proj/Main.roc ─
+                ── WEIRD MODULE NAME [E0457] ───────────────────────────── /code/proj/Main.roc ─
 
                 I am partway through parsing a header, but got stuck here:
 
@@ -5790,7 +5796,7 @@ All branches in an `if` must have the same type!
             ),
             indoc!(
                 r#"
-                ── WEIRD APP NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+                ── WEIRD APP NAME [E0448] ──────────────────────────────── /code/proj/Main.roc ─
 
                 I am partway through parsing a header, but got stuck here:
 
@@ -5814,7 +5820,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TOO MANY ARGS ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY ARGS [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This value is not a function, but it was given 2 arguments:
 
@@ -5835,7 +5841,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TOO MANY ARGS ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY ARGS [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This value is not a function, but it was given 2 arguments:
 
@@ -5857,7 +5863,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `x` definition:
 
@@ -5883,7 +5889,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNFINISHED PARENTHESES ──────────────── tmp/pattern_in_parens_open/Test.roc ─
+    ── UNFINISHED PARENTHESES [E0440] ──────── tmp/pattern_in_parens_open/Test.roc ─
 
     I am partway through parsing a pattern in parentheses, but I got stuck
     here:
@@ -5906,7 +5912,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNFINISHED PARENTHESES ─────────── tmp/pattern_in_parens_end_comma/Test.roc ─
+    ── UNFINISHED PARENTHESES [E0440] ─── tmp/pattern_in_parens_end_comma/Test.roc ─
 
     I am partway through parsing a pattern in parentheses, but I got stuck
     here:
@@ -5927,7 +5933,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNFINISHED PARENTHESES ───────────────── tmp/pattern_in_parens_end/Test.roc ─
+    ── UNFINISHED PARENTHESES [E0440] ───────── tmp/pattern_in_parens_end/Test.roc ─
 
     I am partway through parsing a pattern in parentheses, but I got stuck
     here:
@@ -5951,7 +5957,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── NEED MORE INDENTATION ─────────── tmp/pattern_in_parens_indent_end/Test.roc ─
+    ── NEED MORE INDENTATION [E0422] ─── tmp/pattern_in_parens_indent_end/Test.roc ─
 
     I am partway through parsing a pattern in parentheses, but I got stuck
     here:
@@ -5973,7 +5979,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNFINISHED PARENTHESES ───────── tmp/pattern_in_parens_indent_open/Test.roc ─
+    ── UNFINISHED PARENTHESES [E0440] ─ tmp/pattern_in_parens_indent_open/Test.roc ─
 
     I just started parsing a pattern in parentheses, but I got stuck here:
 
@@ -5999,7 +6005,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── NEED MORE INDENTATION ──────────────────── tmp/outdented_in_parens/Test.roc ─
+    ── NEED MORE INDENTATION [E0422] ──────────── tmp/outdented_in_parens/Test.roc ─
 
     I am partway through parsing a type in parentheses, but I got stuck
     here:
@@ -6024,7 +6030,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `map` has an unexpected type:
 
@@ -6052,7 +6058,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This `expect` condition needs to be a Bool:
 
@@ -6079,7 +6085,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `mul` has an unexpected type:
 
@@ -6094,7 +6100,7 @@ All branches in an `if` must have the same type!
 
         Num *
 
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `mult` definition:
 
@@ -6123,7 +6129,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `mul` has an unexpected type:
 
@@ -6138,7 +6144,7 @@ All branches in an `if` must have the same type!
 
         Num a
 
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `mult` definition:
 
@@ -6173,7 +6179,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE NAME [E0109] ──────────────────────────────── /code/proj/Main.roc ─
 
     The `Result` name is first defined here:
 
@@ -6188,7 +6194,7 @@ All branches in an `if` must have the same type!
     Since these aliases have the same name, it's easy to use the wrong one
     on accident. Give one of them a new name.
 
-    ── TOO FEW TYPE ARGUMENTS ──────────────────────────────── /code/proj/Main.roc ─
+    ── TOO FEW TYPE ARGUMENTS [E0305] ──────────────────────── /code/proj/Main.roc ─
 
     The `Result` alias expects 2 type arguments, but it got 1 instead:
 
@@ -6216,7 +6222,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE NAME [E0109] ──────────────────────────────── /code/proj/Main.roc ─
 
     The `Result` name is first defined here:
 
@@ -6231,7 +6237,7 @@ All branches in an `if` must have the same type!
     Since these aliases have the same name, it's easy to use the wrong one
     on accident. Give one of them a new name.
 
-    ── TOO MANY TYPE ARGUMENTS ─────────────────────────────── /code/proj/Main.roc ─
+    ── TOO MANY TYPE ARGUMENTS [E0305] ─────────────────────── /code/proj/Main.roc ─
 
     The `Result` alias expects 2 type arguments, but it got 3 instead:
 
@@ -6253,7 +6259,7 @@ All branches in an `if` must have the same type!
         ),
         // TODO: We should tell the user that we inferred `_` as `a`
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -6287,7 +6293,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -6319,7 +6325,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -6355,7 +6361,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `inner` definition:
 
@@ -6384,7 +6390,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── NOT AN INLINE ALIAS ────────── tmp/error_inline_alias_not_an_alias/Test.roc ─
+    ── NOT AN INLINE ALIAS [E0423] ── tmp/error_inline_alias_not_an_alias/Test.roc ─
 
     The inline type after this `as` is not a type alias:
 
@@ -6404,7 +6410,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── QUALIFIED ALIAS NAME ──────────── tmp/error_inline_alias_qualified/Test.roc ─
+    ── QUALIFIED ALIAS NAME [E0428] ──── tmp/error_inline_alias_qualified/Test.roc ─
 
     This type alias has a qualified name:
 
@@ -6424,7 +6430,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE ARGUMENT NOT LOWERCASE ─ ...r_inline_alias_argument_uppercase/Test.roc ─
+    ── TYPE ARGUMENT NOT LOWERCASE [E0432] ─ ..._alias_argument_uppercase/Test.roc ─
 
     This alias type argument is not lowercase:
 
@@ -6448,7 +6454,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `isEmpty` has an unexpected type:
 
@@ -6484,7 +6490,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `c` has an unexpected type:
 
@@ -6517,7 +6523,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [E0110] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `F` alias is self-recursive in an invalid way:
 
@@ -6540,7 +6546,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [E0110] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `F` alias is self-recursive in an invalid way:
 
@@ -6562,7 +6568,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [E0110] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `F` alias is self-recursive in an invalid way:
 
@@ -6587,7 +6593,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0302] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 1st argument to `job` is weird:
 
@@ -6606,7 +6612,7 @@ All branches in an `if` must have the same type!
     type says this field is optional. Learn more about optional fields at
     TODO.
 
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `job` has an unexpected type:
 
@@ -6641,7 +6647,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `job` definition:
 
@@ -6672,7 +6678,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── NESTED DATATYPE ─────────────────────────────────────── /code/proj/Main.roc ─
+    ── NESTED DATATYPE [E0123] ─────────────────────────────── /code/proj/Main.roc ─
 
     `Nested` is a nested datatype. Here is one recursive usage of it:
 
@@ -6700,7 +6706,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── NESTED DATATYPE ─────────────────────────────────────── /code/proj/Main.roc ─
+    ── NESTED DATATYPE [E0123] ─────────────────────────────── /code/proj/Main.roc ─
 
     `Nested` is a nested datatype. Here is one recursive usage of it:
 
@@ -6748,7 +6754,7 @@ All branches in an `if` must have the same type!
 
                     let real = format!(indoc!(
                         r#"
-                        ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+                        ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
                         This 1st argument to `use` has an unexpected type:
 
@@ -6815,7 +6821,7 @@ All branches in an `if` must have the same type!
 
                     let real = format!(indoc!(
                         r#"
-                        ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+                        ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
                         The branches of this `when` expression don't match the condition:
 
@@ -6867,7 +6873,7 @@ All branches in an `if` must have the same type!
         ),
         // TODO: link to number suffixes
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0220] ──────────────────────────────── /code/proj/Main.roc ─
 
     This integer literal contains an invalid digit:
 
@@ -6890,7 +6896,7 @@ All branches in an `if` must have the same type!
         ),
         // TODO: link to number suffixes
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0220] ──────────────────────────────── /code/proj/Main.roc ─
 
     This integer literal contains an invalid digit:
 
@@ -6912,7 +6918,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── CONFLICTING NUMBER SUFFIX ───────────────────────────── /code/proj/Main.roc ─
+    ── CONFLICTING NUMBER SUFFIX [E0220] ───────────────────── /code/proj/Main.roc ─
 
     This number literal is an integer, but it has a float suffix:
 
@@ -6929,7 +6935,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── CONFLICTING NUMBER SUFFIX ───────────────────────────── /code/proj/Main.roc ─
+    ── CONFLICTING NUMBER SUFFIX [E0219] ───────────────────── /code/proj/Main.roc ─
 
     This number literal is a float, but it has an integer suffix:
 
@@ -6942,7 +6948,7 @@ All branches in an `if` must have the same type!
         u8_overflow,
         "256u8",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [E0220] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -6958,7 +6964,7 @@ All branches in an `if` must have the same type!
         negative_u8,
         "-1u8",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [E0220] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -6974,7 +6980,7 @@ All branches in an `if` must have the same type!
         u16_overflow,
         "65536u16",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [E0220] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -6990,7 +6996,7 @@ All branches in an `if` must have the same type!
         negative_u16,
         "-1u16",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [E0220] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7006,7 +7012,7 @@ All branches in an `if` must have the same type!
         u32_overflow,
         "4_294_967_296u32",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [E0220] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7022,7 +7028,7 @@ All branches in an `if` must have the same type!
         negative_u32,
         "-1u32",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [E0220] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7038,7 +7044,7 @@ All branches in an `if` must have the same type!
         u64_overflow,
         "18_446_744_073_709_551_616u64",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [E0220] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7054,7 +7060,7 @@ All branches in an `if` must have the same type!
         negative_u64,
         "-1u64",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [E0220] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7070,7 +7076,7 @@ All branches in an `if` must have the same type!
         negative_u128,
         "-1u128",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [E0220] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7086,7 +7092,7 @@ All branches in an `if` must have the same type!
         i8_overflow,
         "128i8",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [E0220] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7102,7 +7108,7 @@ All branches in an `if` must have the same type!
         i8_underflow,
         "-129i8",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [E0220] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7118,7 +7124,7 @@ All branches in an `if` must have the same type!
         i16_overflow,
         "32768i16",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [E0220] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7134,7 +7140,7 @@ All branches in an `if` must have the same type!
         i16_underflow,
         "-32769i16",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [E0220] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7150,7 +7156,7 @@ All branches in an `if` must have the same type!
         i32_overflow,
         "2_147_483_648i32",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [E0220] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7166,7 +7172,7 @@ All branches in an `if` must have the same type!
         i32_underflow,
         "-2_147_483_649i32",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [E0220] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7182,7 +7188,7 @@ All branches in an `if` must have the same type!
         i64_overflow,
         "9_223_372_036_854_775_808i64",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [E0220] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7198,7 +7204,7 @@ All branches in an `if` must have the same type!
         i64_underflow,
         "-9_223_372_036_854_775_809i64",
         @r###"
-    ── NUMBER UNDERFLOWS SUFFIX ────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER UNDERFLOWS SUFFIX [E0220] ────────────────────── /code/proj/Main.roc ─
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7214,7 +7220,7 @@ All branches in an `if` must have the same type!
         i128_overflow,
         "170_141_183_460_469_231_731_687_303_715_884_105_728i128",
         @r###"
-    ── NUMBER OVERFLOWS SUFFIX ─────────────────────────────── /code/proj/Main.roc ─
+    ── NUMBER OVERFLOWS SUFFIX [E0220] ─────────────────────── /code/proj/Main.roc ─
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7236,7 +7242,7 @@ All branches in an `if` must have the same type!
         // TODO: this error message could be improved, e.g. something like "This argument can
         // be used as ... because of its literal value"
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `get` has an unexpected type:
 
@@ -7262,7 +7268,7 @@ All branches in an `if` must have the same type!
              "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `get` has an unexpected type:
 
@@ -7289,7 +7295,7 @@ All branches in an `if` must have the same type!
              "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `get` has an unexpected type:
 
@@ -7316,7 +7322,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -7347,7 +7353,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [E0110] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `R` alias is self-recursive in an invalid way:
 
@@ -7370,7 +7376,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [E0110] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `R` alias is self-recursive in an invalid way:
 
@@ -7394,7 +7400,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [E0110] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `Foo` alias is recursive in an invalid way:
 
@@ -7429,7 +7435,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE NAME [E0109] ──────────────────────────────── /code/proj/Main.roc ─
 
     The `Result` name is first defined here:
 
@@ -7454,7 +7460,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── OPAQUE TYPE NOT DEFINED ─────────────────────────────── /code/proj/Main.roc ─
+    ── OPAQUE TYPE NOT DEFINED [E0208] ─────────────────────── /code/proj/Main.roc ─
 
     The opaque type Age referenced here is not defined:
 
@@ -7475,7 +7481,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── OPAQUE TYPE NOT DEFINED ─────────────────────────────── /code/proj/Main.roc ─
+    ── OPAQUE TYPE NOT DEFINED [E0208] ─────────────────────── /code/proj/Main.roc ─
 
     The opaque type Age referenced here is not defined:
 
@@ -7489,7 +7495,7 @@ All branches in an `if` must have the same type!
 
     Note: It looks like there are no opaque types declared in this scope yet!
 
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [E0101] ───────────────────────────── /code/proj/Main.roc ─
 
     `Age` is not used anywhere in your code.
 
@@ -7512,7 +7518,7 @@ All branches in an `if` must have the same type!
         // and checking it during can. The reason the error appears is because it is parsed as
         // Apply(Error(OtherModule), [@Age, 21])
         @r###"
-    ── OPAQUE TYPE NOT DEFINED ─────────────────────────────── /code/proj/Main.roc ─
+    ── OPAQUE TYPE NOT DEFINED [E0208] ─────────────────────── /code/proj/Main.roc ─
 
     The opaque type Age referenced here is not defined:
 
@@ -7521,7 +7527,7 @@ All branches in an `if` must have the same type!
 
     Note: It looks like there are no opaque types declared in this scope yet!
 
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0215] ──────────────────────────────── /code/proj/Main.roc ─
 
     I am trying to parse a qualified name here:
 
@@ -7548,7 +7554,7 @@ All branches in an `if` must have the same type!
         // `@Age` can be linked to the declaration of `Age` inside `age`, and a suggestion to
         // raise that declaration to the outer scope.
         @r###"
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [E0101] ───────────────────────────── /code/proj/Main.roc ─
 
     `Age` is not used anywhere in your code.
 
@@ -7558,7 +7564,7 @@ All branches in an `if` must have the same type!
     If you didn't intend on using `Age` then remove it so future readers of
     your code don't wonder why it is there.
 
-    ── OPAQUE TYPE NOT DEFINED ─────────────────────────────── /code/proj/Main.roc ─
+    ── OPAQUE TYPE NOT DEFINED [E0208] ─────────────────────── /code/proj/Main.roc ─
 
     The opaque type Age referenced here is not defined:
 
@@ -7580,7 +7586,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── MODULE NOT IMPORTED ─────────────────────────────────── /code/proj/Main.roc ─
+        ── MODULE NOT IMPORTED [E0213] ─────────────────────────── /code/proj/Main.roc ─
 
         The `Task` module is not imported:
 
@@ -7612,7 +7618,7 @@ All branches in an `if` must have the same type!
         // TODO(opaques): error could be improved by saying that the opaque definition demands
         // that the argument be a U8, and linking to the definitin!
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression is used in an unexpected way:
 
@@ -7641,7 +7647,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression is used in an unexpected way:
 
@@ -7671,7 +7677,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `v` definition:
 
@@ -7709,7 +7715,7 @@ All branches in an `if` must have the same type!
         // TODO(opaques): error could be improved by saying that the user-provided pattern
         // probably wants to change "Age" to "@Age"!
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0302] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 1st argument to `f` is weird:
 
@@ -7744,7 +7750,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0302] ───────────────────────────────── /code/proj/Main.roc ─
 
     The 2nd pattern in this `when` does not match the previous ones:
 
@@ -7775,7 +7781,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -7813,7 +7819,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [E0310] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -7846,7 +7852,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `y` has an unexpected type:
 
@@ -7879,7 +7885,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [E0310] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -7903,7 +7909,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── INVALID_EXTENSION_TYPE ──────────────────────────────── /code/proj/Main.roc ─
+    ── INVALID_EXTENSION_TYPE [E0124] ──────────────────────── /code/proj/Main.roc ─
 
     This record extension type is invalid:
 
@@ -7924,7 +7930,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── INVALID_EXTENSION_TYPE ──────────────────────────────── /code/proj/Main.roc ─
+    ── INVALID_EXTENSION_TYPE [E0124] ──────────────────────── /code/proj/Main.roc ─
 
     This tag union extension type is invalid:
 
@@ -7951,7 +7957,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNRECOGNIZED NAME [E0207] ───────────────────────────── /code/proj/Main.roc ─
 
     Nothing is named `UnknownType` in this scope.
 
@@ -7965,7 +7971,7 @@ All branches in an `if` must have the same type!
         Unsigned32
         Unsigned16
 
-    ── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNRECOGNIZED NAME [E0207] ───────────────────────────── /code/proj/Main.roc ─
 
     Nothing is named `UnknownType` in this scope.
 
@@ -7992,7 +7998,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNFINISHED ABILITY ── tmp/ability_first_demand_not_indented_enough/Test.roc ─
+    ── UNFINISHED ABILITY [E0434] ─ ...y_first_demand_not_indented_enough/Test.roc ─
 
     I was partway through parsing an ability definition, but I got stuck
     here:
@@ -8017,7 +8023,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── UNFINISHED ABILITY ─── tmp/ability_demands_not_indented_with_first/Test.roc ─
+        ── UNFINISHED ABILITY [E0434] ─ ...ty_demands_not_indented_with_first/Test.roc ─
 
         I was partway through parsing an ability definition, but I got stuck
         here:
@@ -8040,7 +8046,7 @@ All branches in an `if` must have the same type!
                 "#
         ),
         @r#"
-        ── UNFINISHED ABILITY ───────────── tmp/ability_demand_value_has_args/Test.roc ─
+        ── UNFINISHED ABILITY [E0434] ───── tmp/ability_demand_value_has_args/Test.roc ─
 
         I was partway through parsing an ability definition, but I got stuck
         here:
@@ -8063,7 +8069,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNFINISHED ABILITY ────────── tmp/ability_non_signature_expression/Test.roc ─
+    ── UNFINISHED ABILITY [E0434] ── tmp/ability_non_signature_expression/Test.roc ─
 
     I was partway through parsing an ability definition, but I got stuck
     here:
@@ -8086,7 +8092,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNBOUND TYPE VARIABLE ───────────────────────────────── /code/proj/Main.roc ─
+    ── UNBOUND TYPE VARIABLE [E0113] ───────────────────────── /code/proj/Main.roc ─
 
     The definition of `I` has an unbound type variable:
 
@@ -8108,7 +8114,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNBOUND TYPE VARIABLE ───────────────────────────────── /code/proj/Main.roc ─
+    ── UNBOUND TYPE VARIABLE [E0113] ───────────────────────── /code/proj/Main.roc ─
 
     The definition of `I` has an unbound type variable:
 
@@ -8130,7 +8136,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNBOUND TYPE VARIABLE ───────────────────────────────── /code/proj/Main.roc ─
+    ── UNBOUND TYPE VARIABLE [E0113] ───────────────────────── /code/proj/Main.roc ─
 
     The definition of `I` has 2 unbound type variables.
 
@@ -8154,7 +8160,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNBOUND TYPE VARIABLE ───────────────────────────────── /code/proj/Main.roc ─
+    ── UNBOUND TYPE VARIABLE [E0113] ───────────────────────── /code/proj/Main.roc ─
 
     The definition of `I` has an unbound type variable:
 
@@ -8176,7 +8182,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNBOUND TYPE VARIABLE ───────────────────────────────── /code/proj/Main.roc ─
+    ── UNBOUND TYPE VARIABLE [E0113] ───────────────────────── /code/proj/Main.roc ─
 
     The definition of `I` has an unbound type variable:
 
@@ -8199,7 +8205,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── ABILITY HAS TYPE VARIABLES ──────────────────────────── /code/proj/Main.roc ─
+        ── ABILITY HAS TYPE VARIABLES [E0125] ──────────────────── /code/proj/Main.roc ─
 
         The definition of the `Hash` ability includes type variables:
 
@@ -8209,7 +8215,7 @@ All branches in an `if` must have the same type!
         Abilities cannot depend on type variables, but their member values
         can!
 
-        ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+        ── UNUSED DEFINITION [E0101] ───────────────────────────── /code/proj/Main.roc ─
 
         `Hash` is not used anywhere in your code.
 
@@ -8231,7 +8237,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── HAS CLAUSE IS NOT AN ABILITY ────────────────────────── /code/proj/Main.roc ─
+        ── HAS CLAUSE IS NOT AN ABILITY [E0126] ────────────────── /code/proj/Main.roc ─
 
         The type referenced in this "has" clause is not an ability:
 
@@ -8250,7 +8256,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+        ── DUPLICATE NAME [E0109] ──────────────────────────────── /code/proj/Main.roc ─
 
         The `a` name is first defined here:
 
@@ -8279,7 +8285,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+        ── DUPLICATE NAME [E0109] ──────────────────────────────── /code/proj/Main.roc ─
 
         The `Ability` name is first defined here:
 
@@ -8306,7 +8312,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── ABILITY MEMBER MISSING HAS CLAUSE ───────────────────── /code/proj/Main.roc ─
+        ── ABILITY MEMBER MISSING HAS CLAUSE [E0128] ───────────── /code/proj/Main.roc ─
 
         The definition of the ability member `ab` does not include a `has` clause
         binding a type variable to the ability `Ability`:
@@ -8321,7 +8327,7 @@ All branches in an `if` must have the same type!
 
         Otherwise, the function does not need to be part of the ability!
 
-        ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+        ── UNUSED DEFINITION [E0101] ───────────────────────────── /code/proj/Main.roc ─
 
         `Ability` is not used anywhere in your code.
 
@@ -8339,23 +8345,23 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [] to "./platform"
 
-            Eq has eq : a, b -> Bool.Bool | a has Eq, b has Eq
+            MEq has eq : a, b -> Bool.Bool | a has MEq, b has MEq
             "#
         ),
         @r#"
-        ── ABILITY MEMBER BINDS MULTIPLE VARIABLES ─────────────── /code/proj/Main.roc ─
+        ── ABILITY MEMBER BINDS MULTIPLE VARIABLES [E0129] ─────── /code/proj/Main.roc ─
 
         The definition of the ability member `eq` includes multiple variables
-        bound to the `Eq`` ability:`
+        bound to the `MEq`` ability:`
 
-        3│  Eq has eq : a, b -> Bool.Bool | a has Eq, b has Eq
-                                            ^^^^^^^^^^^^^^^^^^
+        3│  MEq has eq : a, b -> Bool.Bool | a has MEq, b has MEq
+                                             ^^^^^^^^^^^^^^^^^^^^
 
         Ability members can only bind one type variable to their parent
         ability. Otherwise, I wouldn't know what type implements an ability by
         looking at specializations!
 
-        Hint: Did you mean to only bind `a` to `Eq`?
+        Hint: Did you mean to only bind `a` to `MEq`?
         "#
     );
 
@@ -8371,7 +8377,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── ILLEGAL HAS CLAUSE ──────────────────────────────────── /code/proj/Main.roc ─
+        ── ILLEGAL HAS CLAUSE [E0127] ──────────────────────────── /code/proj/Main.roc ─
 
         A `has` clause is not allowed here:
 
@@ -8380,7 +8386,7 @@ All branches in an `if` must have the same type!
 
         `has` clauses can only be specified on the top-level type annotations.
 
-        ── ABILITY MEMBER MISSING HAS CLAUSE ───────────────────── /code/proj/Main.roc ─
+        ── ABILITY MEMBER MISSING HAS CLAUSE [E0128] ───────────── /code/proj/Main.roc ─
 
         The definition of the ability member `hash` does not include a `has`
         clause binding a type variable to the ability `Hash`:
@@ -8411,7 +8417,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+        ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
         Something is off with this specialization of `hash`:
 
@@ -8434,22 +8440,22 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [eq, le] to "./platform"
 
-            Eq has
-                eq : a, a -> Bool | a has Eq
-                le : a, a -> Bool | a has Eq
+            MEq has
+                eq : a, a -> Bool | a has MEq
+                le : a, a -> Bool | a has MEq
 
-            Id := U64 has [Eq {eq}]
+            Id := U64 has [MEq {eq}]
 
             eq = \@Id m, @Id n -> m == n
             "#
         ),
         @r###"
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E0142] ───────────── /code/proj/Main.roc ─
 
-    This type does not fully implement the `Eq` ability:
+    This type does not fully implement the `MEq` ability:
 
-    7│  Id := U64 has [Eq {eq}]
-                       ^^^^^^^
+    7│  Id := U64 has [MEq {eq}]
+                       ^^^^^^^^
 
     The following necessary members are missing implementations:
 
@@ -8470,7 +8476,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [E0101] ───────────────────────────── /code/proj/Main.roc ─
 
     `hash` is not used anywhere in your code.
 
@@ -8499,7 +8505,7 @@ All branches in an `if` must have the same type!
         ),
         // TODO: the error message here could be seriously improved!
         @r###"
-    ── OVERLOADED SPECIALIZATION ───────────────────────────── /code/proj/Main.roc ─
+    ── OVERLOADED SPECIALIZATION [E0145] ───────────────────── /code/proj/Main.roc ─
 
     This ability member specialization is already claimed to specialize
     another opaque type:
@@ -8512,7 +8518,7 @@ All branches in an `if` must have the same type!
     Ability specializations can only provide implementations for one
     opauqe type, since all opaque types are different!
 
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This specialization of `hash` is overly general:
 
@@ -8562,17 +8568,17 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [eq] to "./platform"
 
-            Eq has
-                eq : a, a -> Bool | a has Eq
+            MEq has
+                eq : a, a -> Bool | a has MEq
 
-            You := {} has [Eq {eq}]
+            You := {} has [MEq {eq}]
             AndI := {}
 
             eq = \@You {}, @AndI {} -> False
             "#
         ),
         @r#"
-        ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+        ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
         Something is off with this specialization of `eq`:
 
@@ -8610,7 +8616,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+        ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
         Something is off with the body of the `hash` definition:
 
@@ -8626,7 +8632,7 @@ All branches in an `if` must have the same type!
 
             U32
 
-        ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+        ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
         Something is off with this specialization of `hash`:
 
@@ -8666,7 +8672,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0308] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -8679,7 +8685,7 @@ All branches in an `if` must have the same type!
 
     Only builtin abilities can have generated implementations!
 
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0308] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -8704,7 +8710,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── ABILITY NOT ON TOP-LEVEL ────────────────────────────── /code/proj/Main.roc ─
+        ── ABILITY NOT ON TOP-LEVEL [E0130] ────────────────────── /code/proj/Main.roc ─
 
         This ability definition is not on the top-level of a module:
 
@@ -8732,7 +8738,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+        ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
         Something is off with the body of the `hashable` definition:
 
@@ -8778,7 +8784,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── ABILITY USED AS TYPE ────────────────────────────────── /code/proj/Main.roc ─
+        ── ABILITY USED AS TYPE [E0131] ────────────────────────── /code/proj/Main.roc ─
 
         You are attempting to use the ability `Hash` as a type directly:
 
@@ -8792,7 +8798,7 @@ All branches in an `if` must have the same type!
 
             a has Hash
 
-        ── ABILITY USED AS TYPE ────────────────────────────────── /code/proj/Main.roc ─
+        ── ABILITY USED AS TYPE [E0131] ────────────────────────── /code/proj/Main.roc ─
 
         You are attempting to use the ability `Hash` as a type directly:
 
@@ -8822,7 +8828,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+        ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
         The branches of this `when` expression don't match the condition:
 
@@ -8871,7 +8877,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── WEIRD IMPORTS ────────────────────────── tmp/imports_missing_comma/Test.roc ─
+        ── WEIRD IMPORTS [E0456] ────────────────── tmp/imports_missing_comma/Test.roc ─
 
         I am partway through parsing a imports list, but I got stuck here:
 
@@ -8896,7 +8902,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+        ── UNSAFE PATTERN [E0310] ──────────────────────────────── /code/proj/Main.roc ─
 
         This `when` does not cover all the possibilities:
 
@@ -8943,7 +8949,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── SPECIALIZATION NOT ON TOP-LEVEL ─────────────────────── /code/proj/Main.roc ─
+        ── SPECIALIZATION NOT ON TOP-LEVEL [E0132] ─────────────── /code/proj/Main.roc ─
 
         This specialization of the `default` ability member is in a nested
         scope:
@@ -8968,7 +8974,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `isEq` has an unexpected type:
 
@@ -9004,7 +9010,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `remove` has an unexpected type:
 
@@ -9025,7 +9031,7 @@ All branches in an `if` must have the same type!
     change the type annotation to be more specific? Maybe change the code
     to be more general?
 
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [E0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `new`:
 
@@ -9038,7 +9044,7 @@ All branches in an `if` must have the same type!
 
         { set : Set ∞ }
 
-    ── CIRCULAR TYPE ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── CIRCULAR TYPE [E0303] ───────────────────────────────── /code/proj/Main.roc ─
 
     I'm inferring a weird self-referential type for `goal`:
 
@@ -9068,7 +9074,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── CIRCULAR DEFINITION ─────────────────────────────────── /code/proj/Main.roc ─
+        ── CIRCULAR DEFINITION [E0304] ─────────────────────────── /code/proj/Main.roc ─
 
         The `t1` definition is causing a very tricky infinite loop:
 
@@ -9096,7 +9102,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+        ── TYPE MISMATCH [E0308] ───────────────────────────────── /code/proj/Main.roc ─
 
         This expression has a type that does not implement the abilities it's expected to:
 
@@ -9112,6 +9118,31 @@ All branches in an `if` must have the same type!
         "#
     );
 
+    test_report!(
+        function_does_not_implement_eq,
+        indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            main = (\x -> x) == (\x -> x)
+            "#
+        ),
+        @r#"
+        ── TYPE MISMATCH [E0308] ───────────────────────────────── /code/proj/Main.roc ─
+
+        This expression has a type that does not implement the abilities it's expected to:
+
+        3│  main = (\x -> x) == (\x -> x)
+                    ^^^^^^^
+
+        Roc can't generate an implementation of the `Bool.Eq` ability for
+
+            a -> a
+
+        Note: `Eq` cannot be generated for functions.
+        "#
+    );
+
     test_report!(
         nested_opaque_does_not_implement_encoding,
         indoc!(
@@ -9125,7 +9156,7 @@ All branches in an `if` must have the same type!
         // TODO: this error message is quite unfortunate. We should remove the duplication, and
         // also support regions that point to things in other modules. See also https://github.com/roc-lang/roc/issues/3056.
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0308] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -9163,7 +9194,7 @@ All branches in an `if` must have the same type!
                 "#
         ),
         @r#"
-            ── CIRCULAR DEFINITION ─────────────────────────────────── /code/proj/Main.roc ─
+            ── CIRCULAR DEFINITION [E0304] ─────────────────────────── /code/proj/Main.roc ─
 
             The `t1` definition is causing a very tricky infinite loop:
 
@@ -9187,29 +9218,29 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [A] to "./platform"
 
-            Eq has eq : a, a -> U64 | a has Eq
+            MEq has eq : a, a -> U64 | a has MEq
 
-            A := U8 has [Eq {eq}]
+            A := U8 has [MEq {eq}]
             "#
         ),
         @r###"
-    ── IMPLEMENTATION NOT FOUND ────────────────────────────── /code/proj/Main.roc ─
+    ── IMPLEMENTATION NOT FOUND [E0134] ────────────────────── /code/proj/Main.roc ─
 
     An implementation of `eq` could not be found in this scope:
 
-    5│  A := U8 has [Eq {eq}]
-                         ^^
+    5│  A := U8 has [MEq {eq}]
+                          ^^
 
     Tip: consider adding a value of name `eq` in this scope, or using
     another variable that implements this ability member, like
     { eq: myeq }
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E0142] ───────────── /code/proj/Main.roc ─
 
-    This type does not fully implement the `Eq` ability:
+    This type does not fully implement the `MEq` ability:
 
-    5│  A := U8 has [Eq {eq}]
-                     ^^^^^^^
+    5│  A := U8 has [MEq {eq}]
+                     ^^^^^^^^
 
     The following necessary members are missing implementations:
 
@@ -9223,34 +9254,34 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [A, myEq] to "./platform"
 
-            Eq has eq : a, a -> Bool | a has Eq
+            MEq has eq : a, a -> Bool | a has MEq
 
-            A := U8 has [ Eq {eq: aEq} ]
+            A := U8 has [ MEq {eq: aEq} ]
 
             myEq = \m, n -> m == n
             "#
         ),
         @r###"
-    ── UNRECOGNIZED NAME ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNRECOGNIZED NAME [E0207] ───────────────────────────── /code/proj/Main.roc ─
 
     Nothing is named `aEq` in this scope.
 
-    5│  A := U8 has [ Eq {eq: aEq} ]
-                              ^^^
+    5│  A := U8 has [ MEq {eq: aEq} ]
+                               ^^^
 
     Did you mean one of these?
 
         Eq
+        MEq
         myEq
         eq
-        U8
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E0142] ───────────── /code/proj/Main.roc ─
 
-    This type does not fully implement the `Eq` ability:
+    This type does not fully implement the `MEq` ability:
 
-    5│  A := U8 has [ Eq {eq: aEq} ]
-                      ^^^^^^^^^^^^
+    5│  A := U8 has [ MEq {eq: aEq} ]
+                      ^^^^^^^^^^^^^
 
     The following necessary members are missing implementations:
 
@@ -9264,31 +9295,31 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [A, myEq] to "./platform"
 
-            Eq has eq : a, a -> Bool | a has Eq
+            MEq has eq : a, a -> Bool | a has MEq
 
-            A := U8 has [ Eq {eq ? aEq} ]
+            A := U8 has [ MEq {eq ? aEq} ]
 
             myEq = \m, n -> m == n
             "#
         ),
         @r###"
-    ── OPTIONAL ABILITY IMPLEMENTATION ─────────────────────── /code/proj/Main.roc ─
+    ── OPTIONAL ABILITY IMPLEMENTATION [E0136] ─────────────── /code/proj/Main.roc ─
 
     Ability implementations cannot be optional:
 
-    5│  A := U8 has [ Eq {eq ? aEq} ]
-                          ^^^^^^^^
+    5│  A := U8 has [ MEq {eq ? aEq} ]
+                           ^^^^^^^^
 
     Custom implementations must be supplied fully.
 
 
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E0142] ───────────── /code/proj/Main.roc ─
 
-    This type does not fully implement the `Eq` ability:
+    This type does not fully implement the `MEq` ability:
 
-    5│  A := U8 has [ Eq {eq ? aEq} ]
-                      ^^^^^^^^^^^^^
+    5│  A := U8 has [ MEq {eq ? aEq} ]
+                      ^^^^^^^^^^^^^^
 
     The following necessary members are missing implementations:
 
@@ -9310,7 +9341,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── OPTIONAL ABILITY IMPLEMENTATION ─────────────────────── /code/proj/Main.roc ─
+    ── OPTIONAL ABILITY IMPLEMENTATION [E0136] ─────────────── /code/proj/Main.roc ─
 
     Ability implementations cannot be optional:
 
@@ -9323,7 +9354,7 @@ All branches in an `if` must have the same type!
     record of implementations. For example,    has [Encoding] will attempt
     to derive `Encoding`
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E0142] ───────────── /code/proj/Main.roc ─
 
     This type does not fully implement the `Encoding` ability:
 
@@ -9342,28 +9373,28 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [A] to "./platform"
 
-            Eq has eq : a, a -> Bool | a has Eq
+            MEq has eq : a, a -> Bool | a has MEq
 
-            A := U8 has [ Eq {eq : Bool.eq} ]
+            A := U8 has [ MEq {eq : Bool.eq} ]
             "#
         ),
         @r###"
-    ── QUALIFIED ABILITY IMPLEMENTATION ────────────────────── /code/proj/Main.roc ─
+    ── QUALIFIED ABILITY IMPLEMENTATION [E0137] ────────────── /code/proj/Main.roc ─
 
     This ability implementation is qualified:
 
-    5│  A := U8 has [ Eq {eq : Bool.eq} ]
-                               ^^^^^^^
+    5│  A := U8 has [ MEq {eq : Bool.eq} ]
+                                ^^^^^^^
 
     Custom implementations must be defined in the local scope, and
     unqualified.
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E0142] ───────────── /code/proj/Main.roc ─
 
-    This type does not fully implement the `Eq` ability:
+    This type does not fully implement the `MEq` ability:
 
-    5│  A := U8 has [ Eq {eq : Bool.eq} ]
-                      ^^^^^^^^^^^^^^^^^
+    5│  A := U8 has [ MEq {eq : Bool.eq} ]
+                      ^^^^^^^^^^^^^^^^^^
 
     The following necessary members are missing implementations:
 
@@ -9377,30 +9408,30 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [A] to "./platform"
 
-            Eq has eq : a, a -> Bool | a has Eq
+            MEq has eq : a, a -> Bool | a has MEq
 
-            A := U8 has [ Eq {eq : \m, n -> m == n} ]
+            A := U8 has [ MEq {eq : \m, n -> m == n} ]
             "#
         ),
         @r###"
-    ── ABILITY IMPLEMENTATION NOT IDENTIFIER ───────────────── /code/proj/Main.roc ─
+    ── ABILITY IMPLEMENTATION NOT IDENTIFIER [E0138] ───────── /code/proj/Main.roc ─
 
     This ability implementation is not an identifier:
 
-    5│  A := U8 has [ Eq {eq : \m, n -> m == n} ]
-                               ^^^^^^^^^^^^^^^
+    5│  A := U8 has [ MEq {eq : \m, n -> m == n} ]
+                                ^^^^^^^^^^^^^^^
 
     Custom ability implementations defined in this position can only be
     unqualified identifiers, not arbitrary expressions.
 
     Tip: consider defining this expression as a variable.
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E0142] ───────────── /code/proj/Main.roc ─
 
-    This type does not fully implement the `Eq` ability:
+    This type does not fully implement the `MEq` ability:
 
-    5│  A := U8 has [ Eq {eq : \m, n -> m == n} ]
-                      ^^^^^^^^^^^^^^^^^^^^^^^^^
+    5│  A := U8 has [ MEq {eq : \m, n -> m == n} ]
+                      ^^^^^^^^^^^^^^^^^^^^^^^^^^
 
     The following necessary members are missing implementations:
 
@@ -9414,25 +9445,25 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [A] to "./platform"
 
-            Eq has eq : a, a -> Bool | a has Eq
+            MEq has eq : a, a -> Bool | a has MEq
 
-            A := U8 has [ Eq {eq: eqA, eq: eqA} ]
+            A := U8 has [ MEq {eq: eqA, eq: eqA} ]
 
             eqA = \@A m, @A n -> m == n
             "#
         ),
         @r###"
-    ── DUPLICATE IMPLEMENTATION ────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE IMPLEMENTATION [E0139] ────────────────────── /code/proj/Main.roc ─
 
     This ability member implementation is duplicate:
 
-    5│  A := U8 has [ Eq {eq: eqA, eq: eqA} ]
-                                   ^^^^^^^
+    5│  A := U8 has [ MEq {eq: eqA, eq: eqA} ]
+                                    ^^^^^^^
 
     The first implementation was defined here:
 
-    5│  A := U8 has [ Eq {eq: eqA, eq: eqA} ]
-                          ^^^^^^^
+    5│  A := U8 has [ MEq {eq: eqA, eq: eqA} ]
+                           ^^^^^^^
 
     Only one custom implementation can be defined for an ability member.
     "###
@@ -9450,7 +9481,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── NOT AN ABILITY ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── NOT AN ABILITY [E0140] ──────────────────────────────── /code/proj/Main.roc ─
 
     This identifier is not an ability in scope:
 
@@ -9473,7 +9504,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── ILLEGAL DERIVE ──────────────────────────────────────── /code/proj/Main.roc ─
+        ── ILLEGAL DERIVE [E0133] ──────────────────────────────── /code/proj/Main.roc ─
 
         This ability cannot be derived:
 
@@ -9496,7 +9527,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+        ── INCOMPLETE ABILITY IMPLEMENTATION [E0307] ───────────── /code/proj/Main.roc ─
 
         Roc can't derive an implementation of the `Encode.Encoding` for `A`:
 
@@ -9521,7 +9552,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+        ── INCOMPLETE ABILITY IMPLEMENTATION [E0307] ───────────── /code/proj/Main.roc ─
 
         Roc can't derive an implementation of the `Encode.Encoding` for `A`:
 
@@ -9573,7 +9604,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── DUPLICATE NAME [E0201] ──────────────────────────────── /code/proj/Main.roc ─
 
     The `main` name is first defined here:
 
@@ -9588,7 +9619,7 @@ All branches in an `if` must have the same type!
     Since these variables have the same name, it's easy to use the wrong
     one on accident. Give one of them a new name.
 
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [E0144] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -9622,7 +9653,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r#"
-        ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+        ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
         Something is off with the body of the `withOpen` definition:
 
@@ -9657,7 +9688,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-        ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+        ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
         This expression is used in an unexpected way:
 
@@ -9704,7 +9735,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 1st argument to `foo` has an unexpected type:
 
@@ -9749,7 +9780,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `isEq` has an unexpected type:
 
@@ -9774,7 +9805,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `isEq` has an unexpected type:
 
@@ -9811,7 +9842,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the 2nd branch of this `when` expression:
 
@@ -9845,7 +9876,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     This 2nd argument to `map` has an unexpected type:
 
@@ -9871,7 +9902,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-        ── NAME NOT BOUND IN ALL PATTERNS ──────────────────────── /code/proj/Main.roc ─
+        ── NAME NOT BOUND IN ALL PATTERNS [E0143] ──────────────── /code/proj/Main.roc ─
 
         `x` is not bound in all patterns of this `when` branch
 
@@ -9882,7 +9913,7 @@ All branches in an `if` must have the same type!
         of the branch. Otherwise, the program would crash when it tries to use
         an identifier that wasn't bound!
 
-        ── NAME NOT BOUND IN ALL PATTERNS ──────────────────────── /code/proj/Main.roc ─
+        ── NAME NOT BOUND IN ALL PATTERNS [E0143] ──────────────── /code/proj/Main.roc ─
 
         `y` is not bound in all patterns of this `when` branch
 
@@ -9893,7 +9924,7 @@ All branches in an `if` must have the same type!
         of the branch. Otherwise, the program would crash when it tries to use
         an identifier that wasn't bound!
 
-        ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+        ── UNUSED DEFINITION [E0106] ───────────────────────────── /code/proj/Main.roc ─
 
         `y` is not used in this `when` branch.
 
@@ -9915,7 +9946,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNSAFE PATTERN [E0310] ──────────────────────────────── /code/proj/Main.roc ─
 
     This `when` does not cover all the possibilities:
 
@@ -9941,7 +9972,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+    ── SYNTAX PROBLEM [E0215] ──────────────────────────────── /code/proj/Main.roc ─
 
     Underscores are not allowed in identifier names:
 
@@ -9968,7 +9999,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [E0144] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -9980,7 +10011,7 @@ All branches in an `if` must have the same type!
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [E0144] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -9992,7 +10023,7 @@ All branches in an `if` must have the same type!
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [E0144] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10004,7 +10035,7 @@ All branches in an `if` must have the same type!
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [E0144] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10034,7 +10065,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [E0144] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10046,7 +10077,7 @@ All branches in an `if` must have the same type!
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [E0144] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10058,7 +10089,7 @@ All branches in an `if` must have the same type!
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [E0144] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10070,7 +10101,7 @@ All branches in an `if` must have the same type!
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION ──────────────────────────────── /code/proj/Main.roc ─
+    ── UNNECESSARY DEFINITION [E0144] ──────────────────────── /code/proj/Main.roc ─
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10098,7 +10129,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [E0101] ───────────────────────────── /code/proj/Main.roc ─
 
     `hash` is not used anywhere in your code.
 
@@ -10125,7 +10156,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── WRONG SPECIALIZATION TYPE ───────────────────────────── /code/proj/Main.roc ─
+    ── WRONG SPECIALIZATION TYPE [E0313] ───────────────────── /code/proj/Main.roc ─
 
     This specialization of `hash` is not for the expected type:
 
@@ -10148,7 +10179,7 @@ All branches in an `if` must have the same type!
                 "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `x` definition:
 
@@ -10178,7 +10209,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── CYCLIC ALIAS ────────────────────────────────────────── /code/proj/Main.roc ─
+    ── CYCLIC ALIAS [E0110] ────────────────────────────────── /code/proj/Main.roc ─
 
     The `Recursive` opaque is self-recursive in an invalid way:
 
@@ -10200,7 +10231,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E0307] ───────────── /code/proj/Main.roc ─
 
     Roc can't derive an implementation of the `Decode.Decoding` for `A`:
 
@@ -10225,7 +10256,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
+    ── INCOMPLETE ABILITY IMPLEMENTATION [E0307] ───────────── /code/proj/Main.roc ─
 
     Roc can't derive an implementation of the `Decode.Decoding` for `A`:
 
@@ -10279,7 +10310,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0308] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -10322,7 +10353,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r###"
-    ── UNUSED ARGUMENT ─────────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED ARGUMENT [E0105] ─────────────────────────────── /code/proj/Main.roc ─
 
     This function doesn't use `x`.
 
@@ -10354,7 +10385,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     The branches of this `when` expression don't match the condition:
 
@@ -10386,7 +10417,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the body of the `f` definition:
 
@@ -10417,7 +10448,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0301] ───────────────────────────────── /code/proj/Main.roc ─
 
     Something is off with the `then` branch of this `if` expression:
 
@@ -10446,7 +10477,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r###"
-    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+    ── UNUSED DEFINITION [E0106] ───────────────────────────── /code/proj/Main.roc ─
 
     `foo` is not used in this `when` branch.
 
@@ -10472,7 +10503,7 @@ All branches in an `if` must have the same type!
             "#
         ),
     @r###"
-    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+    ── TYPE MISMATCH [E0308] ───────────────────────────────── /code/proj/Main.roc ─
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -0,0 +1,279 @@
+extern crate bumpalo;
+extern crate indoc;
+extern crate roc_reporting;
+
+#[cfg(test)]
+mod test_structured {
+    use bumpalo::Bump;
+    use indoc::indoc;
+    use roc_load::{
+        self, ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, OptLevel, PropertyTesting,
+        Threading,
+    };
+    use roc_region::all::LineInfo;
+    use roc_reporting::report::{RenderTarget, RocDocAllocator};
+    use roc_reporting::structured::{
+        can_diagnostic, render, type_diagnostic, Diagnostic, ErrorFormat,
+    };
+    use std::fs::File;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    fn promote_expr_to_module(src: &str) -> String {
+        let mut buffer = String::from("app \"test\" provides [main] to \"./platform\"\n\nmain =\n");
+
+        for line in src.lines() {
+            // indent the body!
+            buffer.push_str("    ");
+            buffer.push_str(line);
+            buffer.push('\n');
+        }
+
+        buffer
+    }
+
+    fn diagnostics(subdir: &str, src: &str) -> Vec<Diagnostic> {
+        let arena = Bump::new();
+        let module_src = promote_expr_to_module(src);
+
+        // Use a deterministic temporary directory for each test, since tests run in parallel.
+        let dir = roc_test_utils::TmpDir::new(&format!("tmp/structured/{}", subdir));
+        let file_path = dir.path().join("Test.roc");
+        let mut file = File::create(&file_path).unwrap();
+        writeln!(file, "{}", module_src).unwrap();
+
+        let load_config = LoadConfig {
+            target_info: roc_target::TargetInfo::default_x86_64(),
+            render: RenderTarget::Generic,
+            threading: Threading::Single,
+            exec_mode: ExecutionMode::Check,
+            property_testing: PropertyTesting::default(),
+            opt_level: OptLevel::Normal,
+        };
+        let LoadedModule {
+            module_id: home,
+            mut can_problems,
+            mut type_problems,
+            interns,
+            ..
+        } = roc_load::load_and_typecheck(&arena, file_path, Default::default(), load_config)
+            .unwrap();
+
+        let filename = PathBuf::from(r"/code/proj/Main.roc");
+        let lines = LineInfo::new(&module_src);
+        let src_lines: Vec<&str> = module_src.split('\n').collect();
+        let alloc = RocDocAllocator::new(&src_lines, home, &interns);
+
+        let mut diagnostics = Vec::new();
+
+        for problem in can_problems.remove(&home).unwrap_or_default() {
+            diagnostics.push(can_diagnostic(&alloc, &lines, filename.clone(), problem));
+        }
+
+        for problem in type_problems.remove(&home).unwrap_or_default() {
+            diagnostics.extend(type_diagnostic(&alloc, &lines, filename.clone(), problem));
+        }
+
+        diagnostics
+    }
+
+    #[test]
+    fn json_unused_def() {
+        let diagnostics = diagnostics(
+            "json_unused_def",
+            indoc!(
+                r#"
+                x = 1
+                y = 2
+
+                x
+                "#
+            ),
+        );
+
+        insta::assert_snapshot!(render(ErrorFormat::Json, &diagnostics), @r###"
+        {
+          "diagnostics": [
+            {
+              "code": "E0101",
+              "file": "/code/proj/Main.roc",
+              "message": "`y` is not used anywhere in your code.\n\n5│      y = 2\n        ^\n\nIf you didn't intend on using `y` then remove it so future readers of\nyour code don't wonder why it is there.",
+              "regions": [
+                {
+                  "end_column": 6,
+                  "end_line": 5,
+                  "start_column": 5,
+                  "start_line": 5
+                }
+              ],
+              "severity": "warning",
+              "title": "UNUSED DEFINITION"
+            }
+          ]
+        }
+        "###);
+    }
+
+    #[test]
+    fn json_regions_belong_to_their_own_report() {
+        let diagnostics = diagnostics(
+            "json_regions_belong_to_their_own_report",
+            indoc!(
+                r#"
+                y = 9
+
+                box = \class, htmlChildren ->
+                    div [class] []
+
+                div = \_, _ -> 4
+
+                box "wizard" []
+                "#
+            ),
+        );
+
+        let regions: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.code, diagnostic.regions.len()))
+            .collect();
+
+        assert_eq!(regions, [(Some("E0105"), 1), (Some("E0101"), 1)]);
+    }
+
+    #[test]
+    fn sarif_unused_def() {
+        let diagnostics = diagnostics(
+            "sarif_unused_def",
+            indoc!(
+                r#"
+                x = 1
+                y = 2
+
+                x
+                "#
+            ),
+        );
+
+        insta::assert_snapshot!(render(ErrorFormat::Sarif, &diagnostics), @r###"
+        {
+          "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+          "runs": [
+            {
+              "results": [
+                {
+                  "level": "warning",
+                  "locations": [
+                    {
+                      "physicalLocation": {
+                        "artifactLocation": {
+                          "uri": "/code/proj/Main.roc"
+                        },
+                        "region": {
+                          "endColumn": 6,
+                          "endLine": 5,
+                          "startColumn": 5,
+                          "startLine": 5
+                        }
+                      }
+                    }
+                  ],
+                  "message": {
+                    "text": "`y` is not used anywhere in your code.\n\n5│      y = 2\n        ^\n\nIf you didn't intend on using `y` then remove it so future readers of\nyour code don't wonder why it is there."
+                  },
+                  "ruleId": "E0101",
+                  "ruleIndex": 0
+                }
+              ],
+              "tool": {
+                "driver": {
+                  "informationUri": "https://www.roc-lang.org",
+                  "name": "roc",
+                  "rules": [
+                    {
+                      "id": "E0101",
+                      "shortDescription": {
+                        "text": "UNUSED DEFINITION"
+                      }
+                    }
+                  ]
+                }
+              }
+            }
+          ],
+          "version": "2.1.0"
+        }
+        "###);
+    }
+
+    fn loading_problem(subdir: &str, file_name: &str, src: Option<&str>) -> (PathBuf, Diagnostic) {
+        let arena = Bump::new();
+
+        let dir = roc_test_utils::TmpDir::new(&format!("tmp/structured/{}", subdir));
+        let file_path = dir.path().join(file_name);
+
+        if let Some(src) = src {
+            let mut file = File::create(&file_path).unwrap();
+            writeln!(file, "{}", src).unwrap();
+        }
+
+        let load_config = LoadConfig {
+            target_info: roc_target::TargetInfo::default_x86_64(),
+            render: RenderTarget::Generic,
+            threading: Threading::Single,
+            exec_mode: ExecutionMode::Check,
+            property_testing: PropertyTesting::default(),
+            opt_level: OptLevel::Normal,
+        };
+
+        match roc_load::load_and_typecheck(
+            &arena,
+            file_path.clone(),
+            Default::default(),
+            load_config,
+        ) {
+            Err(LoadingProblem::FormattedReport(_, diagnostic)) => (file_path, diagnostic),
+            Err(other) => panic!("expected a report, got {:?}", other),
+            Ok(_) => panic!("expected loading {} to fail", file_name),
+        }
+    }
+
+    #[test]
+    fn syntax_error_points_at_its_region() {
+        let (file_path, diagnostic) = loading_problem(
+            "syntax_error_points_at_its_region",
+            "Main.roc",
+            Some(indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = [
+                "#
+            )),
+        );
+
+        assert_eq!(diagnostic.title, "UNFINISHED LIST");
+        assert_eq!(diagnostic.code, Some("E0439"));
+        assert_eq!(diagnostic.filename, file_path);
+        assert!(diagnostic
+            .message
+            .starts_with("I cannot find the end of this list:"));
+
+        // Lines are 0-based here, so this is the `main = [` line.
+        assert!(!diagnostic.regions.is_empty());
+        assert!(diagnostic
+            .regions
+            .iter()
+            .all(|region| region.start.line == 2 && region.end.line == 2));
+    }
+
+    #[test]
+    fn missing_file_names_the_file() {
+        let (file_path, diagnostic) =
+            loading_problem("missing_file_names_the_file", "Missing.roc", None);
+
+        assert_eq!(diagnostic.title, "FILE NOT FOUND");
+        assert_eq!(diagnostic.code, Some("E0501"));
+        assert_eq!(diagnostic.filename, file_path);
+        assert_eq!(diagnostic.regions, []);
+    }
+}
@@ -0,0 +1,167 @@
+//! Hashing which agrees with the hashing Roc's `Dict` does on its keys.
+//!
+//! The hash function is wyhash, as implemented in the builtins' `hash.zig`.
+use crate::{RocBox, RocDec, RocList, RocStr, I128, U128};
+
+/// Hashes a value exactly the way compiled Roc code hashes it, so that a dictionary built
+/// on the host can be read by Roc (and the other way around).
+///
+/// Roc hashes a value structurally: numbers and `Bool` by their bytes, a `Str` by its bytes,
+/// a `List` by its length followed by each element, and a record by each of its fields in
+/// the order they are laid out in memory. Each of these feeds the previous hash into the
+/// next one as its seed.
+pub trait RocHash {
+    fn roc_hash(&self, seed: u64) -> u64;
+}
+
+macro_rules! roc_hash_bytes {
+    ($($t:ty),*) => {
+        $(
+            impl RocHash for $t {
+                fn roc_hash(&self, seed: u64) -> u64 {
+                    wyhash(seed, &self.to_ne_bytes())
+                }
+            }
+        )*
+    };
+}
+
+roc_hash_bytes!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize);
+
+impl RocHash for bool {
+    fn roc_hash(&self, seed: u64) -> u64 {
+        wyhash(seed, &[*self as u8])
+    }
+}
+
+macro_rules! roc_hash_wide {
+    ($($t:ty),*) => {
+        $(
+            impl RocHash for $t {
+                fn roc_hash(&self, seed: u64) -> u64 {
+                    wyhash(seed, &self.0)
+                }
+            }
+        )*
+    };
+}
+
+roc_hash_wide!(U128, I128, RocDec);
+
+impl RocHash for RocStr {
+    fn roc_hash(&self, seed: u64) -> u64 {
+        wyhash(seed, self.as_str().as_bytes())
+    }
+}
+
+impl<T: RocHash> RocHash for RocList<T> {
+    fn roc_hash(&self, seed: u64) -> u64 {
+        // the length goes in first, so that e.g. `[[], [1]]` and `[[1], []]` hash differently
+        let seed = (self.len() as u64).roc_hash(seed);
+
+        self.iter().fold(seed, |seed, elem| elem.roc_hash(seed))
+    }
+}
+
+impl<T: RocHash> RocHash for RocBox<T> {
+    fn roc_hash(&self, seed: u64) -> u64 {
+        (**self).roc_hash(seed)
+    }
+}
+
+/// A `Hasher` which feeds every write through wyhash, for combining the hashes of
+/// values whose order doesn't matter.
+#[derive(Default)]
+pub(crate) struct WyHasher(u64);
+
+impl core::hash::Hasher for WyHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0 = wyhash(self.0, bytes);
+    }
+}
+
+const PRIMES: [u64; 5] = [
+    0xa0761d6478bd642f,
+    0xe7037ed1a0b428db,
+    0x8ebc6af09c88c6e3,
+    0x589965cc75374cc3,
+    0x1d8e4e27c47d124f,
+];
+
+fn read_bytes(bytes: usize, data: &[u8]) -> u64 {
+    let mut buf = [0u8; 8];
+    buf[..bytes].copy_from_slice(&data[..bytes]);
+
+    u64::from_le_bytes(buf)
+}
+
+fn read_8bytes_swapped(data: &[u8]) -> u64 {
+    read_bytes(4, data) << 32 | read_bytes(4, &data[4..])
+}
+
+/// Reads the 1 to 8 bytes at the end of the input the way `hash.zig` does.
+fn read_tail(data: &[u8]) -> u64 {
+    match data.len() {
+        1 => read_bytes(1, data),
+        2 => read_bytes(2, data),
+        3 => read_bytes(2, data) << 8 | read_bytes(1, &data[2..]),
+        4 => read_bytes(4, data),
+        5 => read_bytes(4, data) << 8 | read_bytes(1, &data[4..]),
+        6 => read_bytes(4, data) << 16 | read_bytes(2, &data[4..]),
+        7 => read_bytes(4, data) << 24 | read_bytes(2, &data[4..]) << 8 | read_bytes(1, &data[6..]),
+        8 => read_8bytes_swapped(data),
+        _ => unreachable!(),
+    }
+}
+
+fn mum(a: u64, b: u64) -> u64 {
+    let r = a as u128 * b as u128;
+
+    ((r >> 64) ^ r) as u64
+}
+
+fn mix0(a: u64, b: u64, seed: u64) -> u64 {
+    mum(a ^ seed ^ PRIMES[0], b ^ seed ^ PRIMES[1])
+}
+
+fn mix1(a: u64, b: u64, seed: u64) -> u64 {
+    mum(a ^ seed ^ PRIMES[2], b ^ seed ^ PRIMES[3])
+}
+
+pub(crate) fn wyhash(seed: u64, input: &[u8]) -> u64 {
+    let aligned_len = input.len() - (input.len() % 32);
+    let mut seed = seed;
+
+    for b in input[..aligned_len].chunks_exact(32) {
+        seed = mix0(read_bytes(8, b), read_bytes(8, &b[8..]), seed)
+            ^ mix1(read_bytes(8, &b[16..]), read_bytes(8, &b[24..]), seed);
+    }
+
+    let rem = &input[aligned_len..];
+
+    seed = match rem.len() {
+        0 => seed,
+        1..=8 => mix0(read_tail(rem), PRIMES[4], seed),
+        9..=16 => mix0(read_8bytes_swapped(rem), read_tail(&rem[8..]), seed),
+        17..=24 => {
+            mix0(
+                read_8bytes_swapped(rem),
+                read_8bytes_swapped(&rem[8..]),
+                seed,
+            ) ^ mix1(read_tail(&rem[16..]), PRIMES[4], seed)
+        }
+        _ => {
+            mix0(
+                read_8bytes_swapped(rem),
+                read_8bytes_swapped(&rem[8..]),
+                seed,
+            ) ^ mix1(read_8bytes_swapped(&rem[16..]), read_tail(&rem[24..]), seed)
+        }
+    };
+
+    mum(seed ^ input.len() as u64, PRIMES[4])
+}
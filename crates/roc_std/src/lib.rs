@@ -10,6 +10,7 @@ use core::mem::{ManuallyDrop, MaybeUninit};
 use core::ops::Drop;
 use core::str;
 
+mod hash;
 mod roc_box;
 mod roc_dict;
 mod roc_list;
@@ -17,6 +18,7 @@ mod roc_set;
 mod roc_str;
 mod storage;
 
+pub use hash::RocHash;
 pub use roc_box::RocBox;
 pub use roc_dict::RocDict;
 pub use roc_list::RocList;
@@ -1,19 +1,37 @@
+use crate::hash::{RocHash, WyHasher};
 use crate::roc_list::{self, RocList};
 use core::{
+    cmp::Ordering,
     fmt::{self, Debug},
-    hash::Hash,
+    hash::{Hash, Hasher},
 };
 
-#[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct RocDict<K, V>(RocList<(K, V)>);
+/// This must have the same memory layout as `Dict` in the Roc builtins.
+///
+/// `data` holds the entries in insertion order, and `buckets` is an open-addressing
+/// hash table over it: a bucket of 0 is vacant, and a bucket of `n` points to `data[n - 1]`.
+/// Roc relies on the buckets being filled in whenever `data` is not empty, which is why
+/// building a dictionary needs its keys to hash the way Roc hashes them (see [`RocHash`]).
+///
+/// Like `Dict` in Roc, comparisons and hashing ignore the order the entries were inserted in.
+#[derive(Default, Clone)]
+#[repr(C)]
+pub struct RocDict<K, V> {
+    buckets: RocList<usize>,
+    data: RocList<(K, V)>,
+}
 
 impl<K, V> RocDict<K, V> {
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.data.len()
     }
 
     pub fn with_capacity(capacity: usize) -> Self {
-        Self(RocList::with_capacity(capacity))
+        // The buckets are allocated by Roc on the first insert, so only reserve room for the entries.
+        Self {
+            buckets: RocList::empty(),
+            data: RocList::with_capacity(capacity),
+        }
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &(K, V)> {
@@ -21,30 +39,162 @@ impl<K, V> RocDict<K, V> {
     }
 
     pub fn iter_keys(&self) -> impl Iterator<Item = &K> {
-        self.0.iter().map(|(key, _)| key)
+        self.data.iter().map(|(key, _)| key)
     }
 
     pub fn iter_values(&self) -> impl Iterator<Item = &V> {
-        self.0.iter().map(|(_, val)| val)
+        self.data.iter().map(|(_, val)| val)
     }
 }
 
-impl<K: Hash, V> RocDict<K, V> {
+impl<K: RocHash + PartialEq, V> RocDict<K, V> {
+    /// If `src` has the same key more than once, the last value wins.
     #[allow(unused)]
     pub fn from_iter<I: Iterator<Item = (K, V)>>(src: I) -> Self {
-        let mut ret = Self::with_capacity(src.size_hint().0);
+        let all: RocList<(K, V)> = src.collect();
+
+        if all.is_empty() {
+            return Self {
+                buckets: RocList::empty(),
+                data: all,
+            };
+        }
 
-        for (key, val) in src {
-            unsafe {
-                ret.insert_unchecked(key, val);
+        // Walk backwards, so the entry we keep for each key is the first one we see.
+        let mut seen = vacant_buckets(all.len());
+        let mut keep: RocList<bool> = all.iter().map(|_| false).collect();
+
+        {
+            let (seen, keep) = (seen.as_unique_mut_slice(), keep.as_unique_mut_slice());
+
+            for index in (0..all.len()).rev() {
+                if let Err(bucket) = probe(seen, all.as_slice(), &all[index].0) {
+                    seen[bucket] = index + 1;
+                    keep[index] = true;
+                }
             }
         }
 
-        ret
+        let data: RocList<(K, V)> = all
+            .into_iter()
+            .zip(keep.iter())
+            .filter_map(|(entry, keep)| if *keep { Some(entry) } else { None })
+            .collect();
+
+        let mut buckets = vacant_buckets(data.len());
+
+        {
+            let buckets = buckets.as_unique_mut_slice();
+
+            for (index, (key, _)) in data.iter().enumerate() {
+                let mut bucket = home_bucket(buckets, key);
+
+                while buckets[bucket] != 0 {
+                    bucket = (bucket + 1) & (buckets.len() - 1);
+                }
+
+                buckets[bucket] = index + 1;
+            }
+        }
+
+        Self { buckets, data }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        if self.buckets.is_empty() {
+            return None;
+        }
+
+        probe(&self.buckets, &self.data, key)
+            .ok()
+            .map(|index| &self.data[index].1)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+}
+
+impl<K: Ord, V> RocDict<K, V> {
+    /// The entries sorted by key, for comparing dictionaries regardless of insertion order.
+    fn sorted(&self) -> RocList<&(K, V)> {
+        let mut entries: RocList<&(K, V)> = self.iter().collect();
+
+        // keys are unique, so an unstable sort is enough
+        entries
+            .as_unique_mut_slice()
+            .sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+        entries
     }
+}
+
+/// Enough vacant buckets to hold `len` entries, like `bucketCountFor` in `Dict.roc`.
+fn vacant_buckets(len: usize) -> RocList<usize> {
+    let mut count = 8;
+
+    while len * 8 > count * 7 {
+        count *= 2;
+    }
+
+    core::iter::repeat(0).take(count).collect()
+}
+
+fn home_bucket<K: RocHash>(buckets: &[usize], key: &K) -> usize {
+    key.roc_hash(0) as usize & (buckets.len() - 1)
+}
+
+/// Like `probe` in `Dict.roc`: the index into `data` of the entry for `key`, or else the
+/// vacant bucket it would go in.
+fn probe<K: RocHash + PartialEq, V>(
+    buckets: &[usize],
+    data: &[(K, V)],
+    key: &K,
+) -> Result<usize, usize> {
+    let mut bucket = home_bucket(buckets, key);
+
+    loop {
+        match buckets[bucket] {
+            0 => return Err(bucket),
+            n if data[n - 1].0 == *key => return Ok(n - 1),
+            _ => bucket = (bucket + 1) & (buckets.len() - 1),
+        }
+    }
+}
+
+impl<K: RocHash + PartialEq, V: PartialEq> PartialEq for RocDict<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        // Keys are unique, so every entry finding its match means the entries are the same.
+        self.len() == other.len() && self.iter().all(|(key, val)| other.get(key) == Some(val))
+    }
+}
+
+impl<K: RocHash + Eq, V: Eq> Eq for RocDict<K, V> {}
+
+impl<K: RocHash + Ord, V: PartialOrd> PartialOrd for RocDict<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.sorted().iter().partial_cmp(other.sorted().iter())
+    }
+}
+
+impl<K: RocHash + Ord, V: Ord> Ord for RocDict<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sorted().iter().cmp(other.sorted().iter())
+    }
+}
+
+impl<K: Hash, V: Hash> Hash for RocDict<K, V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Adding up the hashes of the entries makes the result independent of their order.
+        let entries = self.iter().fold(0u64, |sum, entry| {
+            let mut hasher = WyHasher::default();
+            entry.hash(&mut hasher);
+
+            sum.wrapping_add(hasher.finish())
+        });
 
-    unsafe fn insert_unchecked(&mut self, _key: K, _val: V) {
-        todo!();
+        self.len().hash(state);
+        entries.hash(state);
     }
 }
 
@@ -63,7 +213,7 @@ impl<K, V> IntoIterator for RocDict<K, V> {
     type IntoIter = roc_list::IntoIter<(K, V)>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+        self.data.into_iter()
     }
 }
 
@@ -72,6 +222,6 @@ impl<'a, K, V> IntoIterator for &'a RocDict<K, V> {
     type IntoIter = core::slice::Iter<'a, (K, V)>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.as_slice().iter()
+        self.data.as_slice().iter()
     }
 }
@@ -116,6 +116,20 @@ impl<T> RocList<T> {
         &*self
     }
 
+    /// Only for lists which were just created, and so can't be shared with anything else.
+    pub(crate) fn as_unique_mut_slice(&mut self) -> &mut [T] {
+        debug_assert!(self.storage().map_or(true, |storage| storage.is_unique()));
+
+        if let Some(elements) = self.elements {
+            let elements =
+                ptr::slice_from_raw_parts_mut(elements.as_ptr().cast::<T>(), self.length);
+
+            unsafe { &mut *elements }
+        } else {
+            &mut []
+        }
+    }
+
     #[inline(always)]
     fn elements_and_storage(&self) -> Option<(NonNull<ManuallyDrop<T>>, &Cell<Storage>)> {
         let elements = self.elements?;
@@ -1,10 +1,13 @@
+use crate::hash::RocHash;
 use crate::roc_dict::RocDict;
 use core::{
+    cmp::Ordering,
     fmt::{self, Debug},
-    hash::Hash,
+    hash::{Hash, Hasher},
 };
 
-#[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Default, Clone)]
+#[repr(transparent)]
 pub struct RocSet<T>(RocDict<T, ()>);
 
 impl<T> RocSet<T> {
@@ -23,11 +26,41 @@ impl<T> RocSet<T> {
     }
 }
 
-impl<T: Hash> RocSet<T> {
+impl<T: RocHash + PartialEq> RocSet<T> {
     #[allow(unused)]
     pub fn from_iter<I: Iterator<Item = T>>(src: I) -> Self {
         Self(RocDict::from_iter(src.map(|elem| (elem, ()))))
     }
+
+    pub fn contains(&self, elem: &T) -> bool {
+        self.0.contains_key(elem)
+    }
+}
+
+impl<T: RocHash + PartialEq> PartialEq for RocSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: RocHash + Eq> Eq for RocSet<T> {}
+
+impl<T: RocHash + Ord> PartialOrd for RocSet<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<T: RocHash + Ord> Ord for RocSet<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T: Hash> Hash for RocSet<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
 }
 
 impl<T: Debug> Debug for RocSet<T> {
@@ -59,7 +59,7 @@ pub unsafe extern "C" fn roc_memset(dst: *mut c_void, c: i32, n: usize) -> *mut
 
 #[cfg(test)]
 mod test_roc_std {
-    use roc_std::{RocBox, RocDec, RocList, RocResult, RocStr};
+    use roc_std::{RocBox, RocDec, RocDict, RocList, RocResult, RocSet, RocStr};
 
     fn roc_str_byte_representation(string: &RocStr) -> [u8; RocStr::SIZE] {
         unsafe { core::mem::transmute_copy(string) }
@@ -239,6 +239,97 @@ mod test_roc_std {
         assert_eq!(from_array.capacity(), from_slice.capacity());
     }
 
+    fn hash_of<T: core::hash::Hash>(value: &T) -> u64 {
+        use std::hash::Hasher;
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn dict_ignores_insertion_order() {
+        let entries = [
+            (1u64, RocStr::from("one")),
+            (2, "two".into()),
+            (3, "three".into()),
+        ];
+        let forwards = RocDict::from_iter(entries.clone().into_iter());
+        let backwards = RocDict::from_iter(entries.into_iter().rev());
+
+        assert_eq!(forwards, backwards);
+        assert_eq!(forwards.cmp(&backwards), core::cmp::Ordering::Equal);
+        assert_eq!(hash_of(&forwards), hash_of(&backwards));
+
+        let fewer = RocDict::from_iter([(2u64, RocStr::from("two"))].into_iter());
+        assert_ne!(forwards, fewer);
+    }
+
+    #[test]
+    fn set_ignores_insertion_order() {
+        let forwards = RocSet::from_iter([1u64, 2, 3].into_iter());
+        let backwards = RocSet::from_iter([3u64, 2, 1].into_iter());
+
+        assert_eq!(forwards, backwards);
+        assert_eq!(forwards.cmp(&backwards), core::cmp::Ordering::Equal);
+        assert_eq!(hash_of(&forwards), hash_of(&backwards));
+    }
+
+    #[test]
+    fn dict_from_iter_keeps_last_value() {
+        let dict = RocDict::from_iter([(1u64, 10u64), (2, 20), (1, 11)].into_iter());
+
+        assert_eq!(dict.len(), 2);
+        assert_eq!(
+            dict,
+            RocDict::from_iter([(2u64, 20u64), (1, 11)].into_iter())
+        );
+        assert_eq!(dict.get(&1), Some(&11));
+    }
+
+    #[test]
+    fn dict_from_iter_fills_in_buckets() {
+        let dict =
+            RocDict::from_iter((0..100u64).map(|n| (RocStr::from(n.to_string().as_str()), n)));
+
+        assert_eq!(dict.len(), 100);
+
+        for n in 0..100u64 {
+            assert_eq!(dict.get(&RocStr::from(n.to_string().as_str())), Some(&n));
+        }
+
+        assert_eq!(dict.get(&RocStr::from("100")), None);
+        assert!(!RocSet::<u64>::from_iter(core::iter::empty()).contains(&0));
+    }
+
+    #[test]
+    fn roc_hash_matches_builtins() {
+        use roc_std::RocHash;
+
+        // the test vectors of the wyhash in the builtins' hash.zig
+        let vectors: [(u64, &str, u64); 7] = [
+            (0, "", 0x0),
+            (1, "a", 0xbed235177f41d328),
+            (2, "abc", 0xbe348debe59b27c3),
+            (3, "message digest", 0x37320f657213a290),
+            (4, "abcdefghijklmnopqrstuvwxyz", 0xd0b270e1d8a7019c),
+            (
+                5,
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                0x602a1894d3bbfe7f,
+            ),
+            (
+                6,
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                0x829e9c148b75970e,
+            ),
+        ];
+
+        for (seed, input, expected) in vectors {
+            assert_eq!(RocStr::from(input).roc_hash(seed), expected, "{:?}", input);
+        }
+    }
+
     #[test]
     fn roc_result_to_rust_result() {
         let greeting = "Hello, World!";
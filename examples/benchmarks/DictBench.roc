@@ -0,0 +1,40 @@
+app "dict-bench"
+    packages { pf: "platform/main.roc" }
+    imports [pf.Task]
+    provides [main] to pf
+
+main : Task.Task {} []
+main =
+    Task.after
+        Task.getInt
+        \n ->
+            keys = List.range 0 n
+
+            # insert every key, look every key up, then remove the even ones
+            dict : Dict I64 I64
+            dict = List.walk keys Dict.empty \accum, key -> Dict.insert accum key (key * 2)
+
+            found = List.walk keys 0 \count, key ->
+                if Dict.contains dict key then
+                    count + 1
+                else
+                    count
+
+            remaining =
+                List.walk keys dict \accum, key ->
+                    if key % 2 == 0 then
+                        Dict.remove accum key
+                    else
+                        accum
+
+            # the same again, with string keys
+            strDict : Dict Str I64
+            strDict = List.walk keys Dict.empty \accum, key -> Dict.insert accum (Num.toStr key) key
+
+            strSum = List.walk keys 0 \sum, key ->
+                when Dict.get strDict (Num.toStr key) is
+                    Ok value -> sum + value
+                    Err KeyNotFound -> sum
+
+            "\(Num.toStr found) \(Num.toStr (Dict.len remaining)) \(Num.toStr strSum)"
+            |> Task.putLine